@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::any::Any;
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -37,6 +38,7 @@ use jj_lib::store::Store;
 use jj_lib::working_copy::CheckoutError;
 use jj_lib::working_copy::CheckoutOptions;
 use jj_lib::working_copy::CheckoutStats;
+use jj_lib::working_copy::FileTypeOverride;
 use jj_lib::working_copy::LockedWorkingCopy;
 use jj_lib::working_copy::ResetError;
 use jj_lib::working_copy::SnapshotError;
@@ -169,6 +171,16 @@ impl WorkingCopy for ConflictsWorkingCopy {
         self.inner.sparse_patterns()
     }
 
+    fn untracked_paths(&self) -> Result<&[RepoPathBuf], WorkingCopyStateError> {
+        self.inner.untracked_paths()
+    }
+
+    fn file_type_overrides(
+        &self,
+    ) -> Result<&BTreeMap<RepoPathBuf, FileTypeOverride>, WorkingCopyStateError> {
+        self.inner.file_type_overrides()
+    }
+
     fn start_mutation(&self) -> Result<Box<dyn LockedWorkingCopy>, WorkingCopyStateError> {
         let inner = self.inner.start_mutation()?;
         Ok(Box::new(LockedConflictsWorkingCopy {
@@ -287,6 +299,30 @@ impl LockedWorkingCopy for LockedConflictsWorkingCopy {
         self.inner.set_sparse_patterns(new_sparse_patterns, options)
     }
 
+    fn untracked_paths(&self) -> Result<&[RepoPathBuf], WorkingCopyStateError> {
+        self.inner.untracked_paths()
+    }
+
+    fn set_untracked_paths(
+        &mut self,
+        new_untracked_paths: Vec<RepoPathBuf>,
+    ) -> Result<(), WorkingCopyStateError> {
+        self.inner.set_untracked_paths(new_untracked_paths)
+    }
+
+    fn file_type_overrides(
+        &self,
+    ) -> Result<&BTreeMap<RepoPathBuf, FileTypeOverride>, WorkingCopyStateError> {
+        self.inner.file_type_overrides()
+    }
+
+    fn set_file_type_overrides(
+        &mut self,
+        new_file_type_overrides: BTreeMap<RepoPathBuf, FileTypeOverride>,
+    ) -> Result<(), WorkingCopyStateError> {
+        self.inner.set_file_type_overrides(new_file_type_overrides)
+    }
+
     fn finish(
         self: Box<Self>,
         operation_id: OperationId,
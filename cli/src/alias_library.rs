@@ -0,0 +1,132 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Alias libraries included by `template-aliases.include` and
+//! `revset-aliases.include`.
+
+use jj_lib::config::ConfigGetError;
+use jj_lib::config::ConfigLayer;
+use jj_lib::config::ConfigNamePathBuf;
+use jj_lib::file_util::expand_home_path;
+
+use crate::command_error::CommandError;
+
+/// Key of the alias tables which lists the libraries to include.
+pub const INCLUDE_KEY: &str = "include";
+
+/// Alias library shipped with `jj`, which can be included by name.
+#[derive(Clone, Copy, Debug)]
+pub struct AliasLibrary {
+    pub name: &'static str,
+    pub description: &'static str,
+    text: &'static str,
+}
+
+impl AliasLibrary {
+    /// Parses the library as a config layer of the same source as the
+    /// `including_layer`.
+    fn parse_layer(&self, including_layer: &ConfigLayer) -> ConfigLayer {
+        // Syntax error in built-in libraries isn't a user error.
+        ConfigLayer::parse(including_layer.source, self.text).unwrap()
+    }
+}
+
+pub const BUILTIN_ALIAS_LIBRARIES: &[AliasLibrary] = &[
+    AliasLibrary {
+        name: "compact-log",
+        description: "Shorter ids, author names, and relative timestamps",
+        text: include_str!("config/aliases/compact-log.toml"),
+    },
+    AliasLibrary {
+        name: "detailed-log",
+        description: "Longer ids, author names with email addresses, and time zones",
+        text: include_str!("config/aliases/detailed-log.toml"),
+    },
+    AliasLibrary {
+        name: "forge-links",
+        description: "Links commit ids to their web page on a forge",
+        text: include_str!("config/aliases/forge-links.toml"),
+    },
+];
+
+/// Looks up built-in alias library by `name`.
+pub fn find_alias_library(name: &str) -> Option<&'static AliasLibrary> {
+    BUILTIN_ALIAS_LIBRARIES
+        .iter()
+        .find(|library| library.name == name)
+}
+
+/// Loads the libraries listed in `<table_name>.include` of the `layer`.
+///
+/// Each entry is either the name of a built-in library, or the path to a TOML
+/// file. Relative paths are resolved from the directory of the config file.
+/// A library is loaded as a config layer, and only its `table_name` table is
+/// supposed to be used.
+pub fn load_included_libraries(
+    layer: &ConfigLayer,
+    table_name: &ConfigNamePathBuf,
+) -> Result<Vec<ConfigLayer>, CommandError> {
+    let mut include_name = table_name.clone();
+    include_name.push(INCLUDE_KEY);
+    let Ok(Some(item)) = layer.look_up_item(&include_name) else {
+        return Ok(vec![]);
+    };
+    // An alias named "include" is a string.
+    if item.is_str() {
+        return Ok(vec![]);
+    }
+    let type_error = |error: String| ConfigGetError::Type {
+        name: include_name.to_string(),
+        error: error.into(),
+        source_path: layer.path.clone(),
+    };
+    let entries = item
+        .as_array()
+        .ok_or_else(|| type_error(format!("Expected an array, but is {}", item.type_name())))?;
+    let mut libraries = vec![];
+    for entry in entries {
+        let entry = entry.as_str().ok_or_else(|| {
+            type_error(format!("Expected a string, but is {}", entry.type_name()))
+        })?;
+        if !entry.ends_with(".toml") {
+            let library = find_alias_library(entry)
+                .ok_or_else(|| type_error(format!("Unknown alias library {entry:?}")))?;
+            libraries.push(library.parse_layer(layer));
+            continue;
+        }
+        let mut path = expand_home_path(entry);
+        if let Some(dir) = layer.path.as_ref().and_then(|path| path.parent()) {
+            path = dir.join(path);
+        }
+        libraries.push(ConfigLayer::load_from_file(layer.source, path)?);
+    }
+    Ok(libraries)
+}
+
+#[cfg(test)]
+mod tests {
+    use jj_lib::config::ConfigSource;
+
+    use super::*;
+
+    #[test]
+    fn test_builtin_alias_libraries_parse() {
+        let layer = ConfigLayer::empty(ConfigSource::User);
+        for library in BUILTIN_ALIAS_LIBRARIES {
+            let library_layer = library.parse_layer(&layer);
+            let table_name = ConfigNamePathBuf::from_iter(["template-aliases"]);
+            assert!(library_layer.look_up_table(&table_name).unwrap().is_some());
+        }
+    }
+}
@@ -0,0 +1,206 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Providers of CI statuses of commits.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+
+use jj_lib::backend::CommitId;
+use jj_lib::config::ConfigGetError;
+use jj_lib::config::ConfigGetResultExt as _;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::settings::UserSettings;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::ci_status::CiStatus;
+
+#[derive(Debug, Error)]
+pub enum CiProviderError {
+    #[error("Failed to run {program}")]
+    Spawn {
+        program: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("{program} failed: {stderr}")]
+    Command { program: String, stderr: String },
+    #[error("Failed to parse the response of the CI provider")]
+    Parse(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum CiProviderConfigError {
+    #[error("No CI provider configured")]
+    NotConfigured,
+    #[error("Unknown CI provider '{0}'")]
+    Unknown(String),
+    #[error(transparent)]
+    Config(#[from] ConfigGetError),
+}
+
+/// Fetches the CI statuses of commits from a CI system.
+pub trait CiStatusProvider {
+    /// Returns the combined status of the CI checks of the commit, or `None`
+    /// if no checks ran for it.
+    fn fetch_status(&self, commit_id: &CommitId) -> Result<Option<CiStatus>, CiProviderError>;
+}
+
+/// Creates the provider selected by the `ci.provider` setting.
+pub fn ci_provider_from_settings(
+    settings: &UserSettings,
+    workspace_root: &Path,
+) -> Result<Box<dyn CiStatusProvider>, CiProviderConfigError> {
+    let name = settings
+        .get_string("ci.provider")
+        .optional()?
+        .ok_or(CiProviderConfigError::NotConfigured)?;
+    match name.as_str() {
+        "github" => Ok(Box::new(GitHubCiProvider::from_settings(
+            settings,
+            workspace_root,
+        )?)),
+        _ => Err(CiProviderConfigError::Unknown(name)),
+    }
+}
+
+/// Fetches the GitHub check runs of commits with the GitHub CLI.
+#[derive(Clone, Debug)]
+pub struct GitHubCiProvider {
+    program: String,
+    /// `owner/name` of the GitHub repository. If unset, the GitHub CLI finds
+    /// the repository from the Git remotes of the working directory.
+    repository: Option<String>,
+    cwd: PathBuf,
+}
+
+impl GitHubCiProvider {
+    pub fn from_settings(
+        settings: &UserSettings,
+        workspace_root: &Path,
+    ) -> Result<Self, ConfigGetError> {
+        Ok(GitHubCiProvider {
+            program: settings.get_string("ci.github.program")?,
+            repository: settings.get_string("ci.github.repository").optional()?,
+            cwd: workspace_root.to_owned(),
+        })
+    }
+}
+
+impl CiStatusProvider for GitHubCiProvider {
+    fn fetch_status(&self, commit_id: &CommitId) -> Result<Option<CiStatus>, CiProviderError> {
+        // The GitHub CLI substitutes {owner} and {repo} from GH_REPO or the
+        // current repository.
+        let endpoint = format!(
+            "repos/{{owner}}/{{repo}}/commits/{}/check-runs?per_page=100",
+            commit_id.hex()
+        );
+        let mut command = Command::new(&self.program);
+        command
+            .args(["api", &endpoint])
+            .current_dir(&self.cwd)
+            .stdin(Stdio::null());
+        if let Some(repository) = &self.repository {
+            command.env("GH_REPO", repository);
+        }
+        let output = command.output().map_err(|source| CiProviderError::Spawn {
+            program: self.program.clone(),
+            source,
+        })?;
+        if !output.status.success() {
+            return Err(CiProviderError::Command {
+                program: self.program.clone(),
+                stderr: String::from_utf8_lossy(&output.stderr)
+                    .trim_end()
+                    .to_owned(),
+            });
+        }
+        let response: CheckRunsResponse = serde_json::from_slice(&output.stdout)?;
+        Ok(combine_check_runs(&response.check_runs))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckRunsResponse {
+    check_runs: Vec<CheckRun>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckRun {
+    status: String,
+    conclusion: Option<String>,
+}
+
+/// Combines the check runs of a commit into a single status. Any failed check
+/// makes the commit fail, even if other checks are still running.
+fn combine_check_runs(check_runs: &[CheckRun]) -> Option<CiStatus> {
+    if check_runs.is_empty() {
+        return None;
+    }
+    let is_failure = |run: &CheckRun| {
+        matches!(
+            run.conclusion.as_deref(),
+            Some("failure" | "timed_out" | "cancelled" | "action_required" | "startup_failure")
+        )
+    };
+    if check_runs.iter().any(is_failure) {
+        Some(CiStatus::Failure)
+    } else if check_runs.iter().any(|run| run.status != "completed") {
+        Some(CiStatus::Pending)
+    } else {
+        Some(CiStatus::Success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_run(status: &str, conclusion: Option<&str>) -> CheckRun {
+        CheckRun {
+            status: status.to_owned(),
+            conclusion: conclusion.map(|s| s.to_owned()),
+        }
+    }
+
+    #[test]
+    fn test_combine_check_runs() {
+        assert_eq!(combine_check_runs(&[]), None);
+        assert_eq!(
+            combine_check_runs(&[
+                check_run("completed", Some("success")),
+                check_run("completed", Some("skipped")),
+            ]),
+            Some(CiStatus::Success)
+        );
+        assert_eq!(
+            combine_check_runs(&[
+                check_run("completed", Some("success")),
+                check_run("in_progress", None),
+            ]),
+            Some(CiStatus::Pending)
+        );
+        assert_eq!(
+            combine_check_runs(&[
+                check_run("in_progress", None),
+                check_run("completed", Some("failure")),
+            ]),
+            Some(CiStatus::Failure)
+        );
+    }
+}
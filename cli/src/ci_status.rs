@@ -0,0 +1,225 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! On-disk cache of CI statuses fetched from a CI provider.
+//!
+//! CI systems report results per commit, so statuses are recorded by commit
+//! id. The cache is only updated when statuses are explicitly refreshed by
+//! `jj ci-status refresh`.
+//!
+//! The statuses can be queried by the `ci_failed()` revset function, and by
+//! the `commit.ci_status()` template method.
+
+use std::any::Any;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use jj_lib::backend::CommitId;
+use jj_lib::commit::Commit;
+use jj_lib::extensions_map::ExtensionsMap;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::revset::RevsetExtensions;
+use jj_lib::revset::RevsetFilterExtension;
+use jj_lib::revset::RevsetFilterPredicate;
+
+use crate::commit_templater::CommitTemplateBuildFnTable;
+use crate::commit_templater::CommitTemplateLanguage;
+use crate::commit_templater::CommitTemplateLanguageExtension;
+use crate::keyed_cache;
+use crate::keyed_cache::CacheValue;
+use crate::keyed_cache::KeyedCache;
+use crate::template_builder::TemplateLanguage as _;
+use crate::templater::TemplatePropertyExt as _;
+
+/// Combined status of the CI checks of a commit.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CiStatus {
+    /// Some checks haven't completed yet, and none has failed.
+    Pending,
+    /// All checks succeeded.
+    Success,
+    /// At least one check failed.
+    Failure,
+}
+
+impl CiStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CiStatus::Pending => "pending",
+            CiStatus::Success => "success",
+            CiStatus::Failure => "failure",
+        }
+    }
+}
+
+impl CacheValue for CiStatus {
+    fn encode(&self) -> String {
+        self.as_str().to_owned()
+    }
+
+    fn decode(data: &str) -> Option<Self> {
+        match data {
+            "pending" => Some(CiStatus::Pending),
+            "success" => Some(CiStatus::Success),
+            "failure" => Some(CiStatus::Failure),
+            _ => None,
+        }
+    }
+}
+
+/// Stores CI statuses by commit id.
+#[derive(Debug)]
+pub struct CiStatusCache {
+    cache: KeyedCache<CiStatus>,
+}
+
+impl CiStatusCache {
+    pub fn new(path: PathBuf) -> Self {
+        CiStatusCache {
+            cache: KeyedCache::new(path),
+        }
+    }
+
+    /// CI statuses of the repo at `repo_path`.
+    pub fn for_repo(repo_path: &Path) -> Self {
+        Self::new(repo_path.join("ci_statuses"))
+    }
+
+    /// Looks up the last fetched status of the commit.
+    pub fn get(&self, commit_id: &CommitId) -> Option<CiStatus> {
+        self.cache.get(commit_id.as_bytes())
+    }
+
+    /// Records the statuses of the commits. `None` removes the recorded
+    /// status, e.g. if no CI checks ran for the commit.
+    pub fn update(
+        &self,
+        updates: impl IntoIterator<Item = (CommitId, Option<CiStatus>)>,
+    ) -> io::Result<()> {
+        self.cache.update(
+            updates
+                .into_iter()
+                .map(|(commit_id, status)| (commit_id.to_bytes(), status)),
+        )
+    }
+
+    /// Removes all recorded statuses.
+    pub fn clear(&self) -> io::Result<()> {
+        self.cache.clear()
+    }
+}
+
+/// Registers the `ci_failed()` revset function.
+pub(crate) fn add_revset_functions(extensions: &mut RevsetExtensions) {
+    extensions.add_custom_function("ci_failed", |_diagnostics, function, context| {
+        function.expect_no_arguments()?;
+        let cache = keyed_cache::get_revset_extension_data(function, context, "CI statuses")?;
+        Ok(RevsetExpression::filter(RevsetFilterPredicate::Extension(
+            Rc::new(CiFailedFilter { cache }),
+        )))
+    });
+}
+
+/// Makes the CI statuses of the repo at `repo_path` available to the revset
+/// functions.
+pub(crate) fn insert_revset_extension_data(data: &mut ExtensionsMap, repo_path: &Path) {
+    keyed_cache::insert_revset_extension_data(data, CiStatusCache::for_repo(repo_path));
+}
+
+/// Matches commits whose cached CI status is failure.
+#[derive(Debug)]
+struct CiFailedFilter {
+    cache: Arc<CiStatusCache>,
+}
+
+impl RevsetFilterExtension for CiFailedFilter {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn matches_commit(&self, commit: &Commit) -> bool {
+        self.cache.get(commit.id()) == Some(CiStatus::Failure)
+    }
+}
+
+/// Provides the `commit.ci_status()` template method.
+pub(crate) struct CiStatusTemplateExtension;
+
+impl CommitTemplateLanguageExtension for CiStatusTemplateExtension {
+    fn build_fn_table<'repo>(&self) -> CommitTemplateBuildFnTable<'repo> {
+        type L<'repo> = CommitTemplateLanguage<'repo>;
+        let mut table = CommitTemplateBuildFnTable::empty();
+        table.commit_methods.insert(
+            "ci_status",
+            |language, _diagnostics, _build_ctx, self_property, function| {
+                function.expect_no_arguments()?;
+                let cache = CiStatusCache::for_repo(language.repo_path());
+                let out_property = self_property.map(move |commit| {
+                    cache
+                        .get(commit.id())
+                        .map_or_else(String::new, |status| status.as_str().to_owned())
+                });
+                Ok(L::wrap_string(out_property))
+            },
+        );
+        table
+    }
+
+    fn build_cache_extensions(&self, _extensions: &mut ExtensionsMap) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_and_get() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("ci_statuses");
+        let commit1 = CommitId::from_hex("abc1");
+        let commit2 = CommitId::from_hex("abc2");
+        assert_eq!(CiStatusCache::new(path.clone()).get(&commit1), None);
+
+        let cache = CiStatusCache::new(path.clone());
+        cache
+            .update([
+                (commit1.clone(), Some(CiStatus::Pending)),
+                (commit2.clone(), Some(CiStatus::Success)),
+            ])
+            .unwrap();
+        let cache = CiStatusCache::new(path.clone());
+        assert_eq!(cache.get(&commit1), Some(CiStatus::Pending));
+        assert_eq!(cache.get(&commit2), Some(CiStatus::Success));
+
+        // Statuses of other commits are kept
+        cache
+            .update([(commit1.clone(), Some(CiStatus::Failure))])
+            .unwrap();
+        let cache = CiStatusCache::new(path.clone());
+        assert_eq!(cache.get(&commit1), Some(CiStatus::Failure));
+        assert_eq!(cache.get(&commit2), Some(CiStatus::Success));
+
+        cache.update([(commit1.clone(), None)]).unwrap();
+        let cache = CiStatusCache::new(path.clone());
+        assert_eq!(cache.get(&commit1), None);
+        assert_eq!(cache.get(&commit2), Some(CiStatus::Success));
+
+        cache.clear().unwrap();
+        assert_eq!(CiStatusCache::new(path).get(&commit2), None);
+    }
+}
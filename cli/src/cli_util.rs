@@ -65,14 +65,21 @@ use jj_lib::config::ConfigNamePathBuf;
 use jj_lib::config::ConfigSource;
 use jj_lib::config::StackedConfig;
 use jj_lib::conflicts::ConflictMarkerStyle;
+use jj_lib::conflicts::ConflictMarkerStyleOverrides;
+use jj_lib::extensions_map::ExtensionsMap;
+use jj_lib::file_util::expand_home_path;
 use jj_lib::fileset;
 use jj_lib::fileset::FilesetDiagnostics;
 use jj_lib::fileset::FilesetExpression;
 use jj_lib::gitignore::GitIgnoreError;
 use jj_lib::gitignore::GitIgnoreFile;
 use jj_lib::id_prefix::IdPrefixContext;
+use jj_lib::mailmap::Mailmap;
+use jj_lib::matchers::IntersectionMatcher;
 use jj_lib::matchers::Matcher;
+use jj_lib::merge::Merge;
 use jj_lib::merge::MergedTreeValue;
+use jj_lib::merge_strategy::MergeStrategies;
 use jj_lib::merged_tree::MergedTree;
 use jj_lib::object_id::ObjectId;
 use jj_lib::op_heads_store;
@@ -111,10 +118,12 @@ use jj_lib::revset::RevsetWorkspaceContext;
 use jj_lib::revset::SymbolResolverExtension;
 use jj_lib::revset::UserRevsetExpression;
 use jj_lib::rewrite::restore_tree;
+use jj_lib::rewrite::RebaseOptions;
 use jj_lib::settings::HumanByteSize;
 use jj_lib::settings::UserSettings;
 use jj_lib::str_util::StringPattern;
 use jj_lib::transaction::Transaction;
+use jj_lib::tree_diff_cache::TreeDiffCache;
 use jj_lib::view::View;
 use jj_lib::working_copy;
 use jj_lib::working_copy::CheckoutOptions;
@@ -138,6 +147,9 @@ use tracing::instrument;
 use tracing_chrome::ChromeLayerBuilder;
 use tracing_subscriber::prelude::*;
 
+use crate::alias_library::load_included_libraries;
+use crate::alias_library::INCLUDE_KEY;
+use crate::ci_status::CiStatusTemplateExtension;
 use crate::command_error::cli_error;
 use crate::command_error::config_error_with_message;
 use crate::command_error::handle_command_result;
@@ -146,7 +158,9 @@ use crate::command_error::internal_error_with_message;
 use crate::command_error::print_parse_diagnostics;
 use crate::command_error::user_error;
 use crate::command_error::user_error_with_hint;
+use crate::command_error::user_error_with_message;
 use crate::command_error::CommandError;
+use crate::commit_templater::CommitLabelRule;
 use crate::commit_templater::CommitTemplateLanguage;
 use crate::commit_templater::CommitTemplateLanguageExtension;
 use crate::complete;
@@ -160,22 +174,26 @@ use crate::diff_util;
 use crate::diff_util::DiffFormat;
 use crate::diff_util::DiffFormatArgs;
 use crate::diff_util::DiffRenderer;
+use crate::event_log::EventLog;
 use crate::formatter::FormatRecorder;
 use crate::formatter::Formatter;
 use crate::formatter::PlainTextFormatter;
 use crate::merge_tools::DiffEditor;
+use crate::merge_tools::ExternalMergeDriverRunner;
 use crate::merge_tools::MergeEditor;
 use crate::merge_tools::MergeToolConfigError;
 use crate::operation_templater::OperationTemplateLanguage;
 use crate::operation_templater::OperationTemplateLanguageExtension;
 use crate::revset_util;
 use crate::revset_util::RevsetExpressionEvaluator;
+use crate::secret_scan::SecretScanner;
 use crate::template_builder;
 use crate::template_builder::TemplateLanguage;
 use crate::template_parser::TemplateAliasesMap;
 use crate::template_parser::TemplateDiagnostics;
 use crate::templater::PropertyPlaceholder;
 use crate::templater::TemplateRenderer;
+use crate::test_results::TestResultTemplateExtension;
 use crate::text_util;
 use crate::ui::ColorChoice;
 use crate::ui::Ui;
@@ -343,6 +361,16 @@ impl CommandHelper {
         &self.data.settings
     }
 
+    /// Factories to load the stores of the repo.
+    pub fn store_factories(&self) -> &StoreFactories {
+        &self.data.store_factories
+    }
+
+    /// Factories to load or create working copies.
+    pub fn working_copy_factories(&self) -> &WorkingCopyFactories {
+        &self.data.working_copy_factories
+    }
+
     /// Resolves configuration for new workspace located at the specified path.
     pub fn settings_for_new_workspace(
         &self,
@@ -483,7 +511,7 @@ impl CommandHelper {
     #[instrument(skip_all)]
     pub fn load_workspace(&self) -> Result<Workspace, CommandError> {
         let loader = self.workspace_loader()?;
-        loader
+        let workspace = loader
             .load(
                 &self.data.settings,
                 &self.data.store_factories,
@@ -491,7 +519,9 @@ impl CommandHelper {
             )
             .map_err(|err| {
                 map_workspace_load_error(err, self.data.global_args.repository.as_deref())
-            })
+            })?;
+        enable_tree_diff_cache(&workspace, &self.data.settings)?;
+        Ok(workspace)
     }
 
     /// Loads workspace located at the specified path.
@@ -502,13 +532,15 @@ impl CommandHelper {
         settings: &UserSettings,
     ) -> Result<Workspace, CommandError> {
         let loader = self.new_workspace_loader_at(workspace_root)?;
-        loader
+        let workspace = loader
             .load(
                 settings,
                 &self.data.store_factories,
                 &self.data.working_copy_factories,
             )
-            .map_err(|err| map_workspace_load_error(err, None))
+            .map_err(|err| map_workspace_load_error(err, None))?;
+        enable_tree_diff_cache(&workspace, settings)?;
+        Ok(workspace)
     }
 
     /// Note that unless you have a good reason not to do so, you should always
@@ -782,9 +814,15 @@ pub struct WorkspaceCommandEnvironment {
     template_aliases_map: TemplateAliasesMap,
     path_converter: RepoPathUiConverter,
     workspace_id: WorkspaceId,
+    repo_path: PathBuf,
     immutable_heads_expression: Rc<UserRevsetExpression>,
+    protected_expression: Option<Rc<UserRevsetExpression>>,
     short_prefixes_expression: Option<Rc<UserRevsetExpression>>,
+    commit_label_rules: Vec<CommitLabelRule>,
     conflict_marker_style: ConflictMarkerStyle,
+    conflict_marker_style_overrides: ConflictMarkerStyleOverrides,
+    mailmap: Arc<Mailmap>,
+    revset_extension_data: ExtensionsMap,
 }
 
 impl WorkspaceCommandEnvironment {
@@ -804,12 +842,28 @@ impl WorkspaceCommandEnvironment {
             template_aliases_map,
             path_converter,
             workspace_id: workspace.workspace_id().to_owned(),
+            repo_path: workspace.repo_path().to_owned(),
             immutable_heads_expression: RevsetExpression::root(),
+            protected_expression: None,
             short_prefixes_expression: None,
+            commit_label_rules: vec![],
             conflict_marker_style: settings.get("ui.conflict-marker-style")?,
+            conflict_marker_style_overrides: load_conflict_marker_style_overrides(settings)?,
+            mailmap: Arc::new(Mailmap::default()),
+            revset_extension_data: ExtensionsMap::empty(),
         };
+        crate::test_results::insert_revset_extension_data(
+            &mut env.revset_extension_data,
+            workspace.repo_path(),
+        );
+        crate::ci_status::insert_revset_extension_data(
+            &mut env.revset_extension_data,
+            workspace.repo_path(),
+        );
         env.immutable_heads_expression = env.load_immutable_heads_expression(ui)?;
+        env.protected_expression = env.load_protected_expression(ui)?;
         env.short_prefixes_expression = env.load_short_prefixes_expression(ui)?;
+        env.commit_label_rules = env.load_commit_label_rules(ui)?;
         Ok(env)
     }
 
@@ -833,13 +887,34 @@ impl WorkspaceCommandEnvironment {
         } else {
             chrono::Local::now()
         };
-        RevsetParseContext::new(
+        let context = RevsetParseContext::new(
             &self.revset_aliases_map,
             self.settings.user_email(),
             now.into(),
             self.command.revset_extensions(),
             Some(workspace_context),
         )
+        .with_extension_data(&self.revset_extension_data);
+        if self.mailmap.is_empty() {
+            context
+        } else {
+            context.with_mailmap(self.mailmap.clone())
+        }
+    }
+
+    /// Parses the given fileset expressions and concatenates them all.
+    pub fn parse_union_filesets(
+        &self,
+        ui: &Ui,
+        file_args: &[String],
+    ) -> Result<FilesetExpression, CommandError> {
+        let mut diagnostics = FilesetDiagnostics::new();
+        let expressions: Vec<_> = file_args
+            .iter()
+            .map(|arg| fileset::parse_maybe_bare(&mut diagnostics, arg, &self.path_converter))
+            .try_collect()?;
+        print_parse_diagnostics(ui, "In fileset expression", &diagnostics)?;
+        Ok(FilesetExpression::union_all(expressions))
     }
 
     /// Creates fresh new context which manages cache of short commit/change ID
@@ -869,6 +944,13 @@ impl WorkspaceCommandEnvironment {
         self.conflict_marker_style
     }
 
+    /// User-configured conflict marker style for materializing conflicts in
+    /// the file at `path`
+    pub fn conflict_marker_style_for(&self, path: &RepoPath) -> ConflictMarkerStyle {
+        self.conflict_marker_style_overrides
+            .get(path, self.conflict_marker_style)
+    }
+
     fn load_immutable_heads_expression(
         &self,
         ui: &Ui,
@@ -883,6 +965,24 @@ impl WorkspaceCommandEnvironment {
         Ok(expression)
     }
 
+    fn load_protected_expression(
+        &self,
+        ui: &Ui,
+    ) -> Result<Option<Rc<UserRevsetExpression>>, CommandError> {
+        let Some(revset_string) = self.settings.get_string("revsets.protected").optional()? else {
+            return Ok(None);
+        };
+        let mut diagnostics = RevsetDiagnostics::new();
+        let expression = revset::parse(
+            &mut diagnostics,
+            &revset_string,
+            &self.revset_parse_context(),
+        )
+        .map_err(|err| config_error_with_message("Invalid `revsets.protected`", err))?;
+        print_parse_diagnostics(ui, "In `revsets.protected`", &diagnostics)?;
+        Ok(Some(expression))
+    }
+
     fn load_short_prefixes_expression(
         &self,
         ui: &Ui,
@@ -908,6 +1008,54 @@ impl WorkspaceCommandEnvironment {
         }
     }
 
+    fn load_commit_label_rules(&self, ui: &Ui) -> Result<Vec<CommitLabelRule>, CommandError> {
+        #[derive(serde::Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct RuleConfig {
+            revset: Option<String>,
+            author: Option<String>,
+            label: Option<String>,
+        }
+
+        let mut rules = vec![];
+        for label in self.settings.table_keys("commit-labels") {
+            let name = ConfigNamePathBuf::from_iter(["commit-labels", label]);
+            let rule: RuleConfig = self.settings.get(&name)?;
+            let mut expression = RevsetExpression::all();
+            if let Some(revset_string) = &rule.revset {
+                let mut diagnostics = RevsetDiagnostics::new();
+                let revset_expression = revset::parse(
+                    &mut diagnostics,
+                    revset_string,
+                    &self.revset_parse_context(),
+                )
+                .map_err(|err| config_error_with_message(format!("Invalid `{name}`"), err))?;
+                print_parse_diagnostics(ui, &format!("In `{name}`"), &diagnostics)?;
+                expression = expression.intersection(&revset_expression);
+            }
+            if let Some(author) = &rule.author {
+                // Unprefixed patterns match substrings as in the `author()` revset.
+                let pattern = match author.split_once(':') {
+                    Some((kind, pattern)) => StringPattern::from_str_kind(pattern, kind),
+                    None => Ok(StringPattern::substring(author)),
+                }
+                .map_err(|err| config_error_with_message(format!("Invalid `{name}`"), err))?;
+                let author_expression =
+                    RevsetExpression::filter(RevsetFilterPredicate::AuthorName(pattern.clone()))
+                        .union(&RevsetExpression::filter(
+                            RevsetFilterPredicate::AuthorEmail(pattern),
+                        ));
+                expression = expression.intersection(&author_expression);
+            }
+            rules.push(CommitLabelRule {
+                label: label.to_owned(),
+                expression,
+                context_label: rule.label,
+            });
+        }
+        Ok(rules)
+    }
+
     /// Returns first immutable commit + lower and upper bounds on number of
     /// immutable commits.
     fn find_immutable_commit<'a>(
@@ -957,6 +1105,35 @@ impl WorkspaceCommandEnvironment {
         Ok(Some((first_immutable, lower, upper)))
     }
 
+    /// Returns first protected commit among the `commits` and their
+    /// descendants, which would be rewritten as well.
+    fn find_protected_commit<'a>(
+        &self,
+        repo: &dyn Repo,
+        commits: impl IntoIterator<Item = &'a CommitId>,
+    ) -> Result<Option<CommitId>, CommandError> {
+        let Some(protected_expression) = &self.protected_expression else {
+            return Ok(None);
+        };
+        if self.command.global_args().allow_protected {
+            return Ok(None);
+        }
+        let id_prefix_context = IdPrefixContext::new(self.command.revset_extensions().clone());
+        let to_rewrite_revset =
+            RevsetExpression::commits(commits.into_iter().cloned().collect_vec());
+        let mut expression = RevsetExpressionEvaluator::new(
+            repo,
+            self.command.revset_extensions().clone(),
+            &id_prefix_context,
+            protected_expression.clone(),
+        );
+        expression.intersect_with(&to_rewrite_revset.descendants());
+        let mut commit_id_iter = expression
+            .evaluate_to_commit_ids()
+            .map_err(|e| config_error_with_message("Invalid `revsets.protected`", e))?;
+        Ok(commit_id_iter.next().transpose()?)
+    }
+
     /// Parses template of the given language into evaluation tree.
     ///
     /// `wrap_self` specifies the type of the top-level property, which should
@@ -991,9 +1168,11 @@ impl WorkspaceCommandEnvironment {
             repo,
             &self.path_converter,
             &self.workspace_id,
+            &self.repo_path,
             self.revset_parse_context(),
             id_prefix_context,
             self.immutable_expression(),
+            &self.commit_label_rules,
             self.conflict_marker_style,
             &self.command.data.commit_template_extensions,
         )
@@ -1044,10 +1223,11 @@ impl WorkspaceCommandHelper {
         ui: &Ui,
         workspace: Workspace,
         repo: Arc<ReadonlyRepo>,
-        env: WorkspaceCommandEnvironment,
+        mut env: WorkspaceCommandEnvironment,
         loaded_at_head: bool,
     ) -> Result<Self, CommandError> {
         let settings = workspace.settings();
+        env.mailmap = Arc::new(load_mailmap(&workspace, &repo)?);
         let commit_summary_template_text = settings.get_string("templates.commit_summary")?;
         let op_summary_template_text = settings.get_string("templates.op_summary")?;
         let may_update_working_copy =
@@ -1250,6 +1430,8 @@ impl WorkspaceCommandHelper {
     pub fn checkout_options(&self) -> CheckoutOptions {
         CheckoutOptions {
             conflict_marker_style: self.env.conflict_marker_style(),
+            conflict_marker_style_overrides: self.env.conflict_marker_style_overrides.clone(),
+            conflict_labels: None,
         }
     }
 
@@ -1354,19 +1536,44 @@ to the current parents may contain changes from multiple commits.
         }
     }
 
+    /// Like `parse_file_patterns()`, but restricts the default to the current
+    /// directory if `ui.path-scope` is `"cwd"`.
+    pub fn parse_file_patterns_in_scope(
+        &self,
+        ui: &Ui,
+        values: &[String],
+    ) -> Result<FilesetExpression, CommandError> {
+        if values.is_empty() {
+            if let Some(path) = self.cwd_path_scope()? {
+                return Ok(FilesetExpression::prefix_path(path));
+            }
+        }
+        self.parse_file_patterns(ui, values)
+    }
+
+    /// Returns the path of the current directory if `ui.path-scope` is `"cwd"`
+    /// and the current directory is a subdirectory of the workspace.
+    fn cwd_path_scope(&self) -> Result<Option<RepoPathBuf>, CommandError> {
+        let scope: PathScope = self.settings().get("ui.path-scope")?;
+        if scope == PathScope::Workspace {
+            return Ok(None);
+        }
+        let Ok(relative_path) = self.env.command.cwd().strip_prefix(self.workspace_root()) else {
+            return Ok(None);
+        };
+        if relative_path.as_os_str().is_empty() {
+            return Ok(None);
+        }
+        Ok(RepoPathBuf::from_relative_path(relative_path).ok())
+    }
+
     /// Parses the given fileset expressions and concatenates them all.
     pub fn parse_union_filesets(
         &self,
         ui: &Ui,
         file_args: &[String], // TODO: introduce FileArg newtype?
     ) -> Result<FilesetExpression, CommandError> {
-        let mut diagnostics = FilesetDiagnostics::new();
-        let expressions: Vec<_> = file_args
-            .iter()
-            .map(|arg| fileset::parse_maybe_bare(&mut diagnostics, arg, self.path_converter()))
-            .try_collect()?;
-        print_parse_diagnostics(ui, "In fileset expression", &diagnostics)?;
-        Ok(FilesetExpression::union_all(expressions))
+        self.env.parse_union_filesets(ui, file_args)
     }
 
     pub fn auto_tracking_matcher(&self, ui: &Ui) -> Result<Box<dyn Matcher>, CommandError> {
@@ -1386,9 +1593,10 @@ to the current parents may contain changes from multiple commits.
 
     pub fn snapshot_options_with_start_tracking_matcher<'a>(
         &self,
+        ui: &Ui,
         start_tracking_matcher: &'a dyn Matcher,
     ) -> Result<SnapshotOptions<'a>, CommandError> {
-        let base_ignores = self.base_ignores()?;
+        let base_ignores = self.base_ignores(ui)?;
         let fsmonitor_settings = self.settings().fsmonitor_settings()?;
         let HumanByteSize(mut max_new_file_size) = self
             .settings()
@@ -1404,6 +1612,8 @@ to the current parents may contain changes from multiple commits.
             start_tracking_matcher,
             max_new_file_size,
             conflict_marker_style,
+            conflict_marker_style_overrides: self.env.conflict_marker_style_overrides.clone(),
+            content_check: None,
         })
     }
 
@@ -1412,20 +1622,34 @@ to the current parents may contain changes from multiple commits.
     }
 
     #[cfg(not(feature = "git"))]
-    pub fn base_ignores(&self) -> Result<Arc<GitIgnoreFile>, GitIgnoreError> {
+    pub fn base_ignores(&self, _ui: &Ui) -> Result<Arc<GitIgnoreFile>, GitIgnoreError> {
         Ok(GitIgnoreFile::empty())
     }
 
     #[cfg(feature = "git")]
     #[instrument(skip_all)]
-    pub fn base_ignores(&self) -> Result<Arc<GitIgnoreFile>, GitIgnoreError> {
+    pub fn base_ignores(&self, ui: &Ui) -> Result<Arc<GitIgnoreFile>, GitIgnoreError> {
         let get_excludes_file_path = |config: &gix::config::File| -> Option<PathBuf> {
-            // TODO: maybe use path() and interpolate(), which can process non-utf-8
-            // path on Unix.
-            if let Some(value) = config.string("core.excludesFile") {
-                let path = str::from_utf8(&value)
-                    .ok()
-                    .map(jj_lib::file_util::expand_home_path)?;
+            if let Some(value) = config.path("core.excludesFile") {
+                // Use path() and interpolate() so "~" and "~user" are expanded
+                // in the same way as git, and non-UTF-8 paths are preserved.
+                let home_dir = std::env::var_os("HOME").map(PathBuf::from);
+                let context = gix::config::path::interpolate::Context {
+                    home_dir: home_dir.as_deref(),
+                    home_for_user: Some(gix::config::path::interpolate::home_for_user),
+                    ..Default::default()
+                };
+                let path = match value.interpolate(context) {
+                    Ok(path) => path,
+                    Err(err) => {
+                        writeln!(
+                            ui.warning_default(),
+                            "Failed to expand the path of core.excludesFile: {err}"
+                        )
+                        .ok();
+                        return None;
+                    }
+                };
                 // The configured path is usually absolute, but if it's relative,
                 // the "git" command would read the file at the work-tree directory.
                 Some(self.workspace_root().join(path))
@@ -1449,8 +1673,10 @@ to the current parents may contain changes from multiple commits.
             if let Some(excludes_file_path) = get_excludes_file_path(&git_repo.config_snapshot()) {
                 git_ignores = git_ignores.chain_with_file("", excludes_file_path)?;
             }
+            // info/exclude is shared by all worktrees, so read it from the
+            // common directory in case the repo is a linked worktree.
             git_ignores = git_ignores
-                .chain_with_file("", git_backend.git_repo_path().join("info").join("exclude"))?;
+                .chain_with_file("", git_repo.common_dir().join("info").join("exclude"))?;
         } else if let Ok(git_config) = gix::config::File::from_globals() {
             if let Some(excludes_file_path) = get_excludes_file_path(&git_config) {
                 git_ignores = git_ignores.chain_with_file("", excludes_file_path)?;
@@ -1498,7 +1724,7 @@ to the current parents may contain changes from multiple commits.
         ui: &Ui,
         tool_name: Option<&str>,
     ) -> Result<DiffEditor, CommandError> {
-        let base_ignores = self.base_ignores()?;
+        let base_ignores = self.base_ignores(ui)?;
         let conflict_marker_style = self.env.conflict_marker_style();
         if let Some(name) = tool_name {
             Ok(DiffEditor::with_name(
@@ -1700,6 +1926,11 @@ to the current parents may contain changes from multiple commits.
         &self.env.template_aliases_map
     }
 
+    /// Mapping of author identities to canonical ones.
+    pub fn mailmap(&self) -> &Mailmap {
+        &self.env.mailmap
+    }
+
     /// Parses template of the given language into evaluation tree.
     ///
     /// `wrap_self` specifies the type of the top-level property, which should
@@ -1835,11 +2066,12 @@ to the current parents may contain changes from multiple commits.
         &self,
         commits: impl IntoIterator<Item = &'a CommitId>,
     ) -> Result<(), CommandError> {
+        let commits = commits.into_iter().collect_vec();
         let Some((commit_id, lower_bound, upper_bound)) = self
             .env
-            .find_immutable_commit(self.repo().as_ref(), commits)?
+            .find_immutable_commit(self.repo().as_ref(), commits.iter().copied())?
         else {
-            return Ok(());
+            return self.check_not_protected(commits);
         };
         let error = if &commit_id == self.repo().store().root_commit_id() {
             user_error(format!("The root commit {commit_id:.12} is immutable"))
@@ -1868,6 +2100,28 @@ to the current parents may contain changes from multiple commits.
         Err(error)
     }
 
+    fn check_not_protected<'a>(
+        &self,
+        commits: impl IntoIterator<Item = &'a CommitId>,
+    ) -> Result<(), CommandError> {
+        let Some(commit_id) = self
+            .env
+            .find_protected_commit(self.repo().as_ref(), commits)?
+        else {
+            return Ok(());
+        };
+        let mut error = user_error(format!("Commit {commit_id:.12} is protected"));
+        let commit = self.repo().store().get_commit(&commit_id)?;
+        error.add_formatted_hint_with(|formatter| {
+            write!(formatter, "Could not modify commit: ")?;
+            self.write_commit_summary(formatter, &commit)?;
+            Ok(())
+        });
+        error.add_hint("Protected commits are configured by `revsets.protected`.");
+        error.add_hint("Use `--allow-protected` to rewrite it anyway.");
+        Err(error)
+    }
+
     #[instrument(skip_all)]
     fn snapshot_working_copy(
         &mut self,
@@ -1887,12 +2141,23 @@ to the current parents may contain changes from multiple commits.
             // committing the working copy.
             return Ok(SnapshotStats::default());
         };
-        let auto_tracking_matcher = self
+        let mut auto_tracking_matcher = self
             .auto_tracking_matcher(ui)
             .map_err(snapshot_command_error)?;
+        // New files outside of the current directory aren't tracked until
+        // snapshotted from a directory containing them.
+        if let Some(path) = self.cwd_path_scope().map_err(snapshot_command_error)? {
+            let scope_matcher = FilesetExpression::prefix_path(path).to_matcher();
+            auto_tracking_matcher = Box::new(IntersectionMatcher::new(
+                auto_tracking_matcher,
+                scope_matcher,
+            ));
+        }
         let options = self
-            .snapshot_options_with_start_tracking_matcher(&auto_tracking_matcher)
+            .snapshot_options_with_start_tracking_matcher(ui, &auto_tracking_matcher)
             .map_err(snapshot_command_error)?;
+        let secret_scanner =
+            SecretScanner::from_settings(ui, self.settings()).map_err(snapshot_command_error)?;
 
         // Compare working-copy tree and operation with repo's, and reload as needed.
         let mut locked_ws = self
@@ -1956,12 +2221,28 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
             let mut options = options;
             let progress = crate::progress::snapshot_progress(ui);
             options.progress = progress.as_ref().map(|x| x as _);
-            locked_ws
-                .locked_wc()
-                .snapshot(&options)
-                .map_err(snapshot_command_error)?
+            let content_check = secret_scanner.as_ref().map(|scanner| {
+                |path: &RepoPath, content: &[u8]| scanner.check_content(path, content)
+            });
+            options.content_check = content_check.as_ref().map(|x| x as _);
+            let result = locked_ws.locked_wc().snapshot(&options);
+            if let Some(scanner) = &secret_scanner {
+                scanner
+                    .report_findings(ui, self.env.path_converter())
+                    .map_err(snapshot_command_error)?;
+            }
+            result.map_err(snapshot_command_error)?
         };
         if new_tree_id != *wc_commit.tree_id() {
+            if let Some(event_log) = ui.event_log() {
+                event_log
+                    .emit(
+                        "operation_started",
+                        serde_json::json!({ "description": "snapshot working copy" }),
+                    )
+                    .map_err(snapshot_command_error)?;
+            }
+            let old_repo = self.user_repo.repo.clone();
             let mut tx =
                 start_repo_transaction(&self.user_repo.repo, self.env.command.string_args());
             tx.set_is_snapshot(true);
@@ -1998,6 +2279,18 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
                 .commit("snapshot working copy")
                 .map_err(snapshot_command_error)?;
             self.user_repo = ReadonlyUserRepo::new(repo);
+            emit_committed_events(ui, |event_log| {
+                emit_rewritten_commits_event(event_log, &old_repo, &self.user_repo.repo)?;
+                event_log.emit(
+                    "operation_finished",
+                    serde_json::json!({
+                        "operation_id": self.user_repo.repo.op_id().hex(),
+                        "description": "snapshot working copy",
+                    }),
+                )?;
+                Ok(())
+            })
+            .map_err(snapshot_command_error)?;
         }
         locked_ws
             .finish(self.user_repo.repo.op_id().clone())
@@ -2071,7 +2364,14 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
             writeln!(ui.status(), "Nothing changed.")?;
             return Ok(());
         }
-        let num_rebased = tx.repo_mut().rebase_descendants()?;
+        let description = description.into();
+        if let Some(event_log) = ui.event_log() {
+            event_log.emit(
+                "operation_started",
+                serde_json::json!({ "description": description }),
+            )?;
+        }
+        let num_rebased = rebase_descendants(tx.repo_mut(), self.settings())?;
         if num_rebased > 0 {
             writeln!(ui.status(), "Rebased {num_rebased} descendant commits")?;
         }
@@ -2118,7 +2418,10 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
             crate::git_util::print_failed_git_export(ui, &refs)?;
         }
 
-        self.user_repo = ReadonlyUserRepo::new(tx.commit(description)?);
+        self.user_repo = ReadonlyUserRepo::new(tx.commit(&description)?);
+        emit_committed_events(ui, |event_log| {
+            emit_rewritten_commits_event(event_log, &old_repo, self.repo())
+        })?;
 
         // Update working copy before reporting repo changes, so that
         // potential errors while reporting changes (broken pipe, etc)
@@ -2161,6 +2464,16 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
                 )?;
             }
         }
+        emit_committed_events(ui, |event_log| {
+            event_log.emit(
+                "operation_finished",
+                serde_json::json!({
+                    "operation_id": self.repo().op_id().hex(),
+                    "description": description,
+                }),
+            )?;
+            Ok(())
+        })?;
         Ok(())
     }
 
@@ -2171,9 +2484,10 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
         ui: &Ui,
         old_repo: &Arc<ReadonlyRepo>,
     ) -> Result<(), CommandError> {
-        let Some(mut fmt) = ui.status_formatter() else {
+        let maybe_fmt = ui.status_formatter();
+        if maybe_fmt.is_none() && ui.event_log().is_none() {
             return Ok(());
-        };
+        }
         let old_view = old_repo.view();
         let new_repo = self.repo().as_ref();
         let new_view = new_repo.view();
@@ -2217,6 +2531,36 @@ See https://jj-vcs.github.io/jj/latest/working-copy/#stale-working-copy \
         new_conflicts_by_change_id
             .retain(|change_id, _commits| !removed_conflicts_by_change_id.contains_key(change_id));
 
+        emit_committed_events(ui, |event_log| {
+            let commit_ids_json = |commits_by_change_id: &IndexMap<&ChangeId, Vec<&Commit>>| {
+                commits_by_change_id
+                    .values()
+                    .flatten()
+                    .map(|commit| commit.id().hex())
+                    .collect_vec()
+            };
+            if !new_conflicts_by_change_id.is_empty() {
+                event_log.emit(
+                    "conflicts_created",
+                    serde_json::json!({
+                        "commit_ids": commit_ids_json(&new_conflicts_by_change_id),
+                    }),
+                )?;
+            }
+            if !resolved_conflicts_by_change_id.is_empty() {
+                event_log.emit(
+                    "conflicts_resolved",
+                    serde_json::json!({
+                        "commit_ids": commit_ids_json(&resolved_conflicts_by_change_id),
+                    }),
+                )?;
+            }
+            Ok(())
+        })?;
+        let Some(mut fmt) = maybe_fmt else {
+            return Ok(());
+        };
+
         // TODO: Also report new divergence and maybe resolved divergence
         let template = self.commit_summary_template();
         if !resolved_conflicts_by_change_id.is_empty() {
@@ -2404,6 +2748,13 @@ impl WorkspaceCommandTransaction<'_> {
         self.tx.repo_mut()
     }
 
+    /// Rebases descendants of the rewritten commits. See
+    /// [`rebase_descendants()`].
+    pub fn rebase_descendants(&mut self) -> Result<usize, CommandError> {
+        let settings = self.helper.settings().clone();
+        rebase_descendants(self.repo_mut(), &settings)
+    }
+
     pub fn check_out(&mut self, commit: &Commit) -> Result<Commit, CheckOutCommitError> {
         let workspace_id = self.helper.workspace_id().to_owned();
         self.id_prefix_context.take(); // invalidate
@@ -2504,6 +2855,20 @@ pub fn find_workspace_dir(cwd: &Path) -> &Path {
         .unwrap_or(cwd)
 }
 
+/// Enables the persistent tree diff cache of the loaded repo if configured.
+fn enable_tree_diff_cache(
+    workspace: &Workspace,
+    settings: &UserSettings,
+) -> Result<(), CommandError> {
+    if let Some(cache) = TreeDiffCache::from_settings(settings, workspace.repo_path())? {
+        workspace
+            .repo_loader()
+            .store()
+            .enable_tree_diff_cache(cache);
+    }
+    Ok(())
+}
+
 fn map_workspace_load_error(err: WorkspaceLoadError, user_wc_path: Option<&str>) -> CommandError {
     match err {
         WorkspaceLoadError::NoWorkspaceHere(wc_path) => {
@@ -2571,6 +2936,31 @@ pub fn start_repo_transaction(repo: &Arc<ReadonlyRepo>, string_args: &[String])
     tx
 }
 
+/// Loads the `merge.strategies` config, running merge drivers as
+/// subprocesses.
+pub fn load_merge_strategies(settings: &UserSettings) -> Result<MergeStrategies, CommandError> {
+    let strategies = MergeStrategies::from_settings(settings)?;
+    Ok(strategies.with_driver_runner(Arc::new(ExternalMergeDriverRunner)))
+}
+
+/// Rebases descendants of the rewritten commits, resolving the file conflicts
+/// left by the rebase with the configured `merge.strategies`. Returns the
+/// number of rebased descendants.
+pub fn rebase_descendants(
+    mut_repo: &mut MutableRepo,
+    settings: &UserSettings,
+) -> Result<usize, CommandError> {
+    let options = RebaseOptions {
+        merge_strategies: load_merge_strategies(settings)?,
+        ..Default::default()
+    };
+    let mut num_rebased = 0;
+    mut_repo.rebase_descendants_with_options(&options, |_old_commit, _rebased_commit| {
+        num_rebased += 1;
+    })?;
+    Ok(num_rebased)
+}
+
 fn update_stale_working_copy(
     mut locked_ws: LockedWorkspace,
     op_id: OperationId,
@@ -2793,6 +3183,26 @@ Discard the conflicting changes with `jj restore --from {}`.",
             short_commit_hash(new_commit.id())
         )?;
     }
+    if !stats.unrepresentable_paths.is_empty() {
+        writeln!(
+            ui.warning_default(),
+            "{} paths were not checked out because they can't be represented on this file system.",
+            stats.unrepresentable_paths.len()
+        )?;
+        writeln!(
+            ui.hint_default(),
+            "Use `jj status` to list the skipped paths. They are left unchanged in the \
+             working-copy commit."
+        )?;
+    }
+    if !stats.retracked_paths.is_empty() {
+        writeln!(
+            ui.warning_default(),
+            "{} untracked paths are tracked again because the new working-copy commit contains \
+             them.",
+            stats.retracked_paths.len()
+        )?;
+    }
     Ok(())
 }
 
@@ -2857,6 +3267,91 @@ pub fn print_trackable_remote_bookmarks(ui: &Ui, view: &View) -> io::Result<()>
     Ok(())
 }
 
+/// Emits events about an operation which has already been committed, if the
+/// event log is enabled. Since the operation can't be rolled back at this
+/// point, a failure to emit the events is only reported as a warning.
+fn emit_committed_events(
+    ui: &Ui,
+    emit: impl FnOnce(&EventLog) -> Result<(), CommandError>,
+) -> io::Result<()> {
+    let Some(event_log) = ui.event_log() else {
+        return Ok(());
+    };
+    if let Err(err) = emit(event_log) {
+        writeln!(
+            ui.warning_default(),
+            "Failed to write to the event log: {}",
+            err.error
+        )?;
+    }
+    Ok(())
+}
+
+/// Emits `commits_rewritten` event describing the visible commits added or
+/// removed between `old_repo` and `new_repo`.
+fn emit_rewritten_commits_event(
+    event_log: &EventLog,
+    old_repo: &ReadonlyRepo,
+    new_repo: &ReadonlyRepo,
+) -> Result<(), CommandError> {
+    let old_heads = RevsetExpression::commits(old_repo.view().heads().iter().cloned().collect());
+    let new_heads = RevsetExpression::commits(new_repo.view().heads().iter().cloned().collect());
+    let removed_ids: Vec<CommitId> = new_heads
+        .range(&old_heads)
+        .evaluate(new_repo)?
+        .iter()
+        .try_collect()?;
+    let added_commits: Vec<Commit> = old_heads
+        .range(&new_heads)
+        .evaluate(new_repo)?
+        .iter()
+        .commits(new_repo.store())
+        .try_collect()?;
+    if removed_ids.is_empty() && added_commits.is_empty() {
+        return Ok(());
+    }
+
+    let removed_ids_set: HashSet<&CommitId> = removed_ids.iter().collect();
+    let mut rewritten: IndexMap<&CommitId, Vec<&CommitId>> = IndexMap::new();
+    let mut created = vec![];
+    // Report the oldest commits first
+    for commit in added_commits.iter().rev() {
+        let mut predecessor_ids = commit
+            .predecessor_ids()
+            .iter()
+            .filter(|id| removed_ids_set.contains(id))
+            .peekable();
+        if predecessor_ids.peek().is_none() {
+            created.push(commit.id().hex());
+        }
+        for id in predecessor_ids {
+            rewritten.entry(id).or_default().push(commit.id());
+        }
+    }
+    let abandoned = removed_ids
+        .iter()
+        .rev()
+        .filter(|id| !rewritten.contains_key(id))
+        .map(|id| id.hex())
+        .collect_vec();
+    let rewritten: serde_json::Map<_, _> = rewritten
+        .iter()
+        .map(|(old_id, new_ids)| {
+            let new_ids = new_ids.iter().map(|id| id.hex()).collect_vec();
+            (old_id.hex(), new_ids.into())
+        })
+        .collect();
+    event_log.emit(
+        "commits_rewritten",
+        serde_json::json!({
+            "rewritten": rewritten,
+            "abandoned": abandoned,
+            "created": created,
+        }),
+    )?;
+    Ok(())
+}
+
 pub fn update_working_copy(
     repo: &Arc<ReadonlyRepo>,
     workspace: &mut Workspace,
@@ -2865,6 +3360,10 @@ pub fn update_working_copy(
     options: &CheckoutOptions,
 ) -> Result<CheckoutStats, CommandError> {
     let old_tree_id = old_commit.map(|commit| commit.tree_id().clone());
+    let options = CheckoutOptions {
+        conflict_labels: conflict_labels_for_commit(repo.as_ref(), new_commit)?,
+        ..options.clone()
+    };
     // TODO: CheckoutError::ConcurrentCheckout should probably just result in a
     // warning for most commands (but be an error for the checkout command)
     let stats = workspace
@@ -2872,7 +3371,7 @@ pub fn update_working_copy(
             repo.op_id().clone(),
             old_tree_id.as_ref(),
             new_commit,
-            options,
+            &options,
         )
         .map_err(|err| {
             internal_error_with_message(
@@ -2883,6 +3382,117 @@ pub fn update_working_copy(
     Ok(stats)
 }
 
+/// Labels the terms of the conflicts in `commit`'s tree with the commits they
+/// came from, if known.
+fn conflict_labels_for_commit(
+    repo: &dyn Repo,
+    commit: &Commit,
+) -> Result<Option<Merge<String>>, CommandError> {
+    if !commit.has_conflict()? {
+        return Ok(None);
+    }
+    let term_commits = conflict_term_commits(repo, commit)?;
+    Ok(term_commits.map(|commits| commits.map(conflict_term_label)))
+}
+
+/// Short description of the commit a conflict term came from.
+pub(crate) fn conflict_term_label(commit: &Commit) -> String {
+    let change_hash = &commit.change_id().reverse_hex()[..8];
+    match commit.description().lines().next() {
+        Some(first_line) => format!("{change_hash} \"{first_line}\""),
+        None => change_hash.to_owned(),
+    }
+}
+
+/// Finds the commits whose trees are the terms of `commit`'s tree, if they
+/// were recorded when the conflict was created by rebasing or squashing, or if
+/// `commit` merges parents whose trees have no conflicts.
+pub(crate) fn conflict_term_commits(
+    repo: &dyn Repo,
+    commit: &Commit,
+) -> Result<Option<Merge<Commit>>, CommandError> {
+    if let Some(sources) = commit.conflict_sources() {
+        // The source commits might have been garbage-collected since.
+        if let Ok(commits) = sources.try_map(|id| repo.store().get_commit(id)) {
+            return Ok(Some(commits));
+        }
+    }
+    let parents: Vec<_> = commit.parents().try_collect()?;
+    if parents.len() < 2 {
+        return Ok(None);
+    }
+    for parent in &parents {
+        if parent.has_conflict()? {
+            return Ok(None);
+        }
+    }
+    // Mirrors how `merge_commit_trees()` merges the parents one by one with
+    // their common ancestor.
+    let parent_ids = parents
+        .iter()
+        .map(|parent| parent.id().clone())
+        .collect_vec();
+    let mut commits = vec![parents[0].clone()];
+    for (i, parent) in parents.iter().enumerate().skip(1) {
+        let ancestor_ids = repo
+            .index()
+            .common_ancestors(&parent_ids[0..i], &parent_ids[i..][..1]);
+        let [ancestor_id] = ancestor_ids.as_slice() else {
+            return Ok(None);
+        };
+        let ancestor = repo.store().get_commit(ancestor_id)?;
+        if ancestor.has_conflict()? {
+            return Ok(None);
+        }
+        commits.push(ancestor);
+        commits.push(parent.clone());
+    }
+    Ok(Some(Merge::from_vec(commits)))
+}
+
+/// Loads the `.mailmap` file of the working-copy commit, followed by the file
+/// specified by `mailmap.file`, whose entries take precedence.
+fn load_mailmap(workspace: &Workspace, repo: &ReadonlyRepo) -> Result<Mailmap, CommandError> {
+    // The working-copy commit might be unreadable, which is reported once the
+    // command accesses it.
+    let mut mailmap = repo
+        .view()
+        .get_wc_commit_id(workspace.workspace_id())
+        .and_then(|commit_id| repo.store().get_commit(commit_id).ok())
+        .and_then(|commit| Mailmap::from_tree(&commit.tree().ok()?).ok())
+        .unwrap_or_default();
+    if let Some(path) = workspace.settings().get_string("mailmap.file").optional()? {
+        let path = workspace.workspace_root().join(expand_home_path(&path));
+        let text = std::fs::read_to_string(&path).map_err(|err| {
+            user_error_with_message(
+                format!("Failed to read mailmap file {}", path.display()),
+                err,
+            )
+        })?;
+        mailmap.add_text(&text);
+    }
+    Ok(mailmap)
+}
+
+fn load_conflict_marker_style_overrides(
+    settings: &UserSettings,
+) -> Result<ConflictMarkerStyleOverrides, ConfigGetError> {
+    let rules = settings
+        .table_keys("ui.conflict-marker-styles")
+        .map(|pattern| {
+            let name = ["ui", "conflict-marker-styles", pattern];
+            let style = settings.get(name)?;
+            let glob = glob::Pattern::new(pattern).map_err(|err| ConfigGetError::Type {
+                name: ConfigNamePathBuf::from_iter(name).to_string(),
+                error: err.into(),
+                source_path: None,
+            })?;
+            Ok((glob, style))
+        })
+        .try_collect()?;
+    Ok(ConflictMarkerStyleOverrides::new(rules))
+}
+
 pub fn load_template_aliases(
     ui: &Ui,
     stacked_config: &StackedConfig,
@@ -2904,16 +3514,27 @@ pub fn load_template_aliases(
                 .into());
             }
         };
-        for (decl, item) in table.iter() {
-            let r = item
-                .as_str()
-                .ok_or_else(|| format!("Expected a string, but is {}", item.type_name()))
-                .and_then(|v| aliases_map.insert(decl, v).map_err(|e| e.to_string()));
-            if let Err(s) = r {
-                writeln!(
-                    ui.warning_default(),
-                    "Failed to load `{table_name}.{decl}`: {s}"
-                )?;
+        // Included libraries are loaded first so the aliases of this layer
+        // override them.
+        let libraries = load_included_libraries(layer, &table_name)?;
+        let library_tables = libraries
+            .iter()
+            .filter_map(|library| library.look_up_table(&table_name).ok().flatten());
+        for table in library_tables.chain([table]) {
+            for (decl, item) in table.iter() {
+                if decl == INCLUDE_KEY && !item.is_str() {
+                    continue;
+                }
+                let r = item
+                    .as_str()
+                    .ok_or_else(|| format!("Expected a string, but is {}", item.type_name()))
+                    .and_then(|v| aliases_map.insert(decl, v).map_err(|e| e.to_string()));
+                if let Err(s) = r {
+                    writeln!(
+                        ui.warning_default(),
+                        "Failed to load `{table_name}.{decl}`: {s}"
+                    )?;
+                }
             }
         }
     }
@@ -3213,6 +3834,13 @@ pub struct GlobalArgs {
     /// `immutable_heads()` revset or the `immutable` template keyword.
     #[arg(long, global = true)]
     pub ignore_immutable: bool,
+    /// Allow rewriting protected commits
+    ///
+    /// By default, Jujutsu refuses to rewrite commits in the configured
+    /// `revsets.protected` set, including the ones that would be rebased
+    /// because their ancestors are rewritten. This option disables that check.
+    #[arg(long, global = true)]
+    pub allow_protected: bool,
     /// Operation to load the repo at
     ///
     /// Operation to load the repo at. By default, Jujutsu loads the repo at the
@@ -3242,9 +3870,22 @@ pub struct GlobalArgs {
         add = ArgValueCandidates::new(complete::operations),
     )]
     pub at_operation: Option<String>,
+    /// Write machine-readable events to the given file descriptor
+    ///
+    /// Events such as started and finished operations, rewritten commits, new
+    /// conflicts, and progress are written as JSON objects, one per line. This
+    /// is intended for GUI frontends wrapping the CLI. The normal output
+    /// isn't affected.
+    ///
+    /// Only supported on Unix.
+    #[arg(long, global = true, value_name = "FD")]
+    pub event_log: Option<i32>,
     /// Enable debug logging
     #[arg(long, global = true)]
     pub debug: bool,
+    /// Ignore the `commands.<name>.default-args` setting
+    #[arg(long, global = true)]
+    pub no_default_args: bool,
 
     #[command(flatten)]
     pub early_args: EarlyArgs,
@@ -3309,6 +3950,16 @@ impl EarlyArgs {
     }
 }
 
+/// Paths which commands like `jj status` apply to if no paths are specified.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum PathScope {
+    /// The whole workspace
+    Workspace,
+    /// The current directory, if it's in the workspace
+    Cwd,
+}
+
 /// Wrapper around revset expression argument.
 ///
 /// An empty string is rejected early by the CLI value parser, but it's still
@@ -3466,8 +4117,11 @@ fn resolve_aliases(
                     let alias_definition: Vec<String> = config.get(["aliases", alias_name])?;
                     assert!(string_args.ends_with(&alias_args));
                     string_args.truncate(string_args.len() - 1 - alias_args.len());
-                    string_args.extend(alias_definition);
-                    string_args.extend_from_slice(&alias_args);
+                    string_args.extend(substitute_alias_args(
+                        alias_name,
+                        alias_definition,
+                        &alias_args,
+                    )?);
                     resolved_aliases.insert(alias_name);
                     continue;
                 } else {
@@ -3481,6 +4135,169 @@ fn resolve_aliases(
     }
 }
 
+/// Substitutes the `$1`, `$2`, ... and `$@` items of the alias definition with
+/// the arguments passed to the alias. The arguments which aren't referenced
+/// are appended. `$$1` and `$$@` are substituted with the literal `$1` and
+/// `$@`.
+fn substitute_alias_args(
+    alias_name: &str,
+    definition: Vec<String>,
+    args: &[String],
+) -> Result<Vec<String>, CommandError> {
+    let parse_index = |item: &str| {
+        let digits = item.strip_prefix('$')?;
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        digits.parse::<usize>().ok().filter(|&index| index > 0)
+    };
+    let mut expanded = Vec::with_capacity(definition.len() + args.len());
+    let mut used = vec![false; args.len()];
+    let mut used_all = false;
+    for item in &definition {
+        if item == "$@" {
+            expanded.extend_from_slice(args);
+            used_all = true;
+        } else if let Some(index) = parse_index(item) {
+            let Some(arg) = args.get(index - 1) else {
+                return Err(user_error_with_hint(
+                    format!("Missing argument ${index} for alias `{alias_name}`"),
+                    format!(
+                        "The alias is defined as {}",
+                        toml_edit::Value::from_iter(&definition)
+                    ),
+                ));
+            };
+            expanded.push(arg.clone());
+            used[index - 1] = true;
+        } else if item == "$$@"
+            || item
+                .strip_prefix('$')
+                .is_some_and(|rest| parse_index(rest).is_some())
+        {
+            expanded.push(item[1..].to_owned());
+        } else {
+            expanded.push(item.clone());
+        }
+    }
+    if !used_all {
+        expanded.extend(
+            itertools::zip_eq(args, used)
+                .filter(|(_, used)| !used)
+                .map(|(arg, _)| arg.clone()),
+        );
+    }
+    Ok(expanded)
+}
+
+/// Inserts the arguments configured by `commands.<name>.default-args` after the
+/// name of the invoked command. Default options which are also given on the
+/// command line are dropped, so that the command-line arguments take
+/// precedence. Names of subcommands are separated by dots, e.g.
+/// `commands.file.list.default-args`.
+fn resolve_default_args(
+    config: &StackedConfig,
+    app: &Command,
+    mut string_args: Vec<String>,
+) -> Result<Vec<String>, CommandError> {
+    let end = string_args
+        .iter()
+        .position(|arg| arg == "--")
+        .unwrap_or(string_args.len());
+    if string_args[..end]
+        .iter()
+        .any(|arg| arg == "--no-default-args")
+    {
+        return Ok(string_args);
+    }
+
+    // Look up the (sub)command names, which may be aliases of the commands.
+    // Values of the options preceding the command name can't be told apart
+    // from the command name, but that shouldn't matter in practice.
+    let mut commands = vec![app];
+    let mut name_path = vec!["commands"];
+    let mut insert_pos = None;
+    let mut pos = 1;
+    while let Some((index, subcommand)) = (pos..end).find_map(|index| {
+        let command = commands.last().unwrap();
+        Some((index, command.find_subcommand(&string_args[index])?))
+    }) {
+        commands.push(subcommand);
+        name_path.push(subcommand.get_name());
+        pos = index + 1;
+        insert_pos = Some(pos);
+    }
+    let Some(insert_pos) = insert_pos else {
+        return Ok(string_args);
+    };
+    name_path.push("default-args");
+    let default_args: Option<Vec<String>> = config.get(name_path.as_slice()).optional()?;
+
+    // Global options aren't propagated to the subcommands until the app is
+    // built, so the options are looked up in all the commands.
+    let given_ids: HashSet<&str> = string_args[1..end]
+        .iter()
+        .filter_map(|arg| find_option(&commands, arg))
+        .map(|option| option.get_id().as_str())
+        .collect();
+    let mut args = default_args.unwrap_or_default().into_iter();
+    let mut kept_args = vec![];
+    while let Some(arg) = args.next() {
+        let Some(option) = find_option(&commands, &arg) else {
+            kept_args.push(arg);
+            continue;
+        };
+        let value = if option.get_action().takes_values() && !has_attached_value(&arg) {
+            args.next()
+        } else {
+            None
+        };
+        if !given_ids.contains(option.get_id().as_str()) {
+            kept_args.push(arg);
+            kept_args.extend(value);
+        }
+    }
+    string_args.splice(insert_pos..insert_pos, kept_args);
+    Ok(string_args)
+}
+
+/// Finds the option named by `arg`, e.g. `--foo=bar` or `-f`, in the given
+/// commands.
+fn find_option<'a>(commands: &[&'a Command], arg: &str) -> Option<&'a clap::Arg> {
+    if let Some(long) = arg.strip_prefix("--") {
+        let name = long.split_once('=').map_or(long, |(name, _)| name);
+        commands
+            .iter()
+            .flat_map(|command| command.get_arguments())
+            .find(|option| {
+                option.get_long() == Some(name)
+                    || option
+                        .get_all_aliases()
+                        .is_some_and(|aliases| aliases.contains(&name))
+            })
+    } else {
+        let short = arg.strip_prefix('-')?.chars().next()?;
+        commands
+            .iter()
+            .flat_map(|command| command.get_arguments())
+            .find(|option| {
+                option.get_short() == Some(short)
+                    || option
+                        .get_all_short_aliases()
+                        .is_some_and(|aliases| aliases.contains(&short))
+            })
+    }
+}
+
+/// Returns whether the option `arg` includes its value, e.g. `--foo=bar` or
+/// `-fbar`.
+fn has_attached_value(arg: &str) -> bool {
+    match arg.strip_prefix("--") {
+        Some(long) => long.contains('='),
+        None => arg.chars().count() > 2,
+    }
+}
+
 /// Parse args that must be interpreted early, e.g. before printing help.
 fn parse_early_args(
     app: &Command,
@@ -3551,9 +4368,9 @@ fn handle_shell_completion(
             // the index accordingly, strip the last "", and append remainder?
             let pad_len = usize::saturating_sub(index + 1, orig_args.len());
             let padded_args = orig_args.chain(std::iter::repeat_n(OsString::new(), pad_len));
-            expand_args(ui, app, padded_args, config)?
+            expand_command_and_aliases(ui, app, padded_args, config)?
         } else {
-            expand_args(ui, app, orig_args, config)?
+            expand_command_and_aliases(ui, app, orig_args, config)?
         };
         args.extend(resolved_aliases.into_iter().map(OsString::from));
     }
@@ -3575,6 +4392,19 @@ pub fn expand_args(
     app: &Command,
     args_os: impl IntoIterator<Item = OsString>,
     config: &StackedConfig,
+) -> Result<Vec<String>, CommandError> {
+    let string_args = expand_command_and_aliases(ui, app, args_os, config)?;
+    resolve_default_args(config, app, string_args)
+}
+
+/// Expands the default command and aliases, but not the default arguments of
+/// the command. The default arguments shouldn't be inserted in front of the
+/// argument to be completed.
+fn expand_command_and_aliases(
+    ui: &Ui,
+    app: &Command,
+    args_os: impl IntoIterator<Item = OsString>,
+    config: &StackedConfig,
 ) -> Result<Vec<String>, CommandError> {
     let mut string_args: Vec<String> = vec![];
     for arg_os in args_os {
@@ -3620,6 +4450,24 @@ pub fn format_template<C: Clone>(ui: &Ui, arg: &C, template: &TemplateRenderer<C
     output.into_string_lossy()
 }
 
+/// Formats the template as plain text, and replaces the trailing newline (if
+/// any) with a NUL byte. This is the record format of `-z` outputs, which can
+/// be consumed by `xargs -0` and the like.
+pub fn format_template_null_terminated<C: Clone>(
+    arg: &C,
+    template: &TemplateRenderer<C>,
+) -> Vec<u8> {
+    let mut output = vec![];
+    template
+        .format(arg, &mut PlainTextFormatter::new(&mut output))
+        .expect("write() to vec backed formatter should never fail");
+    if output.ends_with(b"\n") {
+        output.pop();
+    }
+    output.push(b'\0');
+    output
+}
+
 /// CLI command builder and runner.
 #[must_use]
 pub struct CliRunner {
@@ -3648,6 +4496,10 @@ impl CliRunner {
     pub fn init() -> Self {
         let tracing_subscription = TracingSubscription::init();
         crate::cleanup_guard::init();
+        let mut revset_extensions = RevsetExtensions::default();
+        crate::test_results::add_revset_functions(&mut revset_extensions);
+        crate::ci_status::add_revset_functions(&mut revset_extensions);
+        crate::conventional_commits::add_revset_functions(&mut revset_extensions);
         CliRunner {
             tracing_subscription,
             app: crate::commands::default_app(),
@@ -3656,8 +4508,11 @@ impl CliRunner {
             store_factories: StoreFactories::default(),
             working_copy_factories: default_working_copy_factories(),
             workspace_loader_factory: Box::new(DefaultWorkspaceLoaderFactory),
-            revset_extensions: Default::default(),
-            commit_template_extensions: vec![],
+            revset_extensions,
+            commit_template_extensions: vec![
+                Arc::new(TestResultTemplateExtension),
+                Arc::new(CiStatusTemplateExtension),
+            ],
             operation_template_extensions: vec![],
             dispatch_fn: Box::new(crate::commands::run_command),
             start_hook_fns: vec![],
@@ -3865,6 +4720,21 @@ impl CliRunner {
             process_global_args_fn(ui, &matches)?;
         }
         config_env.set_command_name(command_name(&matches));
+        if let Some(fd) = args.global_args.event_log {
+            let event_log = EventLog::from_fd(fd).map_err(|err| {
+                user_error_with_message(format!("Failed to open event log fd {fd}"), err)
+            })?;
+            ui.set_event_log(event_log);
+        }
+        if let Some(event_log) = ui.event_log() {
+            event_log.emit(
+                "command_started",
+                serde_json::json!({
+                    "command": command_name(&matches),
+                    "args": string_args[1..],
+                }),
+            )?;
+        }
 
         let maybe_workspace_loader = if let Some(path) = &args.global_args.repository {
             // TODO: maybe path should be canonicalized by WorkspaceLoader?
@@ -3935,6 +4805,14 @@ impl CliRunner {
         let mut ui = Ui::with_config(config.as_ref())
             .expect("default config should be valid, env vars are stringly typed");
         let result = self.run_internal(&mut ui, config);
+        if let Some(event_log) = ui.event_log() {
+            let data = match &result {
+                Ok(()) => serde_json::json!({ "success": true }),
+                Err(err) => serde_json::json!({ "success": false, "error": err.error.to_string() }),
+            };
+            // The error will be reported to stderr anyway.
+            event_log.emit("command_finished", data).ok();
+        }
         let exit_code = handle_command_result(&mut ui, result);
         ui.finalize_pager();
         exit_code
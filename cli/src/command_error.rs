@@ -36,6 +36,7 @@ use jj_lib::gitignore::GitIgnoreError;
 use jj_lib::op_heads_store::OpHeadResolutionError;
 use jj_lib::op_heads_store::OpHeadsStoreError;
 use jj_lib::op_store::OpStoreError;
+use jj_lib::op_sync::OpSyncError;
 use jj_lib::op_walk::OpsetEvaluationError;
 use jj_lib::op_walk::OpsetResolutionError;
 use jj_lib::repo::CheckOutCommitError;
@@ -56,8 +57,10 @@ use jj_lib::working_copy::ResetError;
 use jj_lib::working_copy::SnapshotError;
 use jj_lib::working_copy::WorkingCopyStateError;
 use jj_lib::workspace::WorkspaceInitError;
+use jj_lib::workspace_store::WorkspaceStoreError;
 use thiserror::Error;
 
+use crate::ci_provider::CiProviderConfigError;
 use crate::cli_util::short_operation_hash;
 use crate::config::ConfigEnvError;
 use crate::description_util::ParseBulkEditMessageError;
@@ -68,6 +71,7 @@ use crate::formatter::FormatRecorder;
 use crate::formatter::Formatter;
 use crate::merge_tools::ConflictResolveError;
 use crate::merge_tools::DiffEditError;
+use crate::merge_tools::MergeDriverError;
 use crate::merge_tools::MergeToolConfigError;
 use crate::merge_tools::MergeToolPartialResolutionError;
 use crate::revset_util::BookmarkNameParseError;
@@ -317,6 +321,7 @@ impl From<BackendError> for CommandError {
     fn from(err: BackendError) -> Self {
         match &err {
             BackendError::Unsupported(_) => user_error(err),
+            BackendError::Other(source) if source.is::<MergeDriverError>() => user_error(err),
             _ => internal_error_with_message("Unexpected error from backend", err),
         }
     }
@@ -353,10 +358,17 @@ impl From<WorkspaceInitError> for CommandError {
                 internal_error_with_message("Failed to access the repository", err)
             }
             WorkspaceInitError::SignInit(err) => user_error(err),
+            WorkspaceInitError::WorkspaceStore(err) => err.into(),
         }
     }
 }
 
+impl From<WorkspaceStoreError> for CommandError {
+    fn from(err: WorkspaceStoreError) -> Self {
+        internal_error_with_message("Failed to access the workspace store", err)
+    }
+}
+
 impl From<OpHeadResolutionError> for CommandError {
     fn from(err: OpHeadResolutionError) -> Self {
         match err {
@@ -395,6 +407,18 @@ impl From<OpStoreError> for CommandError {
     }
 }
 
+impl From<OpSyncError> for CommandError {
+    fn from(err: OpSyncError) -> Self {
+        match err {
+            OpSyncError::UnsupportedBackend => user_error(err),
+            OpSyncError::IdMismatch { .. } => internal_error(err),
+            OpSyncError::OpStore(err) => err.into(),
+            OpSyncError::OpHeadsStore(err) => err.into(),
+            OpSyncError::Backend(err) => err.into(),
+        }
+    }
+}
+
 impl From<RepoLoaderError> for CommandError {
     fn from(err: RepoLoaderError) -> Self {
         internal_error_with_message("Failed to load the repo", err)
@@ -460,6 +484,20 @@ impl From<MergeToolConfigError> for CommandError {
     }
 }
 
+impl From<CiProviderConfigError> for CommandError {
+    fn from(err: CiProviderConfigError) -> Self {
+        match err {
+            CiProviderConfigError::NotConfigured => user_error_with_hint(
+                err,
+                "Set `ci.provider` to the CI system to fetch statuses from, e.g. `jj config set \
+                 --repo ci.provider github`",
+            ),
+            CiProviderConfigError::Unknown(_) => config_error(err),
+            CiProviderConfigError::Config(err) => err.into(),
+        }
+    }
+}
+
 impl From<TextEditError> for CommandError {
     fn from(err: TextEditError) -> Self {
         user_error(err)
@@ -485,8 +523,11 @@ mod git {
     use jj_lib::git::GitFetchError;
     use jj_lib::git::GitFetchPrepareError;
     use jj_lib::git::GitImportError;
+    use jj_lib::git::GitNotesError;
     use jj_lib::git::GitPushError;
+    use jj_lib::git::GitRemoteDefaultBranchError;
     use jj_lib::git::GitRemoteManagementError;
+    use jj_lib::git::GitWorktreeError;
     use jj_lib::git::UnexpectedGitBackendError;
 
     use super::*;
@@ -551,6 +592,15 @@ jj currently does not support partial clones. To use jj with this repository, tr
         }
     }
 
+    impl From<GitRemoteDefaultBranchError> for CommandError {
+        fn from(err: GitRemoteDefaultBranchError) -> Self {
+            match err {
+                GitRemoteDefaultBranchError::Prepare(err) => err.into(),
+                GitRemoteDefaultBranchError::Fetch(err) => err.into(),
+            }
+        }
+    }
+
     impl From<GitFetchPrepareError> for CommandError {
         fn from(err: GitFetchPrepareError) -> Self {
             match err {
@@ -591,6 +641,31 @@ jj currently does not support partial clones. To use jj with this repository, tr
         }
     }
 
+    impl From<GitNotesError> for CommandError {
+        fn from(err: GitNotesError) -> Self {
+            match err {
+                GitNotesError::NoteAlreadyExists(..) => {
+                    user_error_with_hint(err, "Use --force to replace the existing note.")
+                }
+                GitNotesError::InternalGitError(_) => {
+                    user_error_with_message("Failed to access Git notes", err)
+                }
+                _ => user_error(err),
+            }
+        }
+    }
+
+    impl From<GitWorktreeError> for CommandError {
+        fn from(err: GitWorktreeError) -> Self {
+            match err {
+                GitWorktreeError::UnexpectedBackend(_) => user_error(err),
+                GitWorktreeError::InternalGitError(_) => {
+                    internal_error_with_message("Failed to read Git worktrees", err)
+                }
+            }
+        }
+    }
+
     impl From<GitConfigParseError> for CommandError {
         fn from(err: GitConfigParseError) -> Self {
             internal_error_with_message("Failed to parse Git config", err)
@@ -28,6 +28,7 @@ use tracing::instrument;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::command_error::CommandError;
+use crate::commit_picker;
 use crate::complete;
 use crate::ui::Ui;
 
@@ -39,6 +40,10 @@ use crate::ui::Ui;
 ///
 /// If a working-copy commit gets abandoned, it will be given a new, empty
 /// commit. This is true in general; it is not specific to this command.
+///
+/// With `--interactive`, the revisions to abandon are shown as a checklist
+/// before anything is changed. Revisions can be deselected, and nothing is
+/// abandoned if the selection is cancelled.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct AbandonArgs {
     /// The revision(s) to abandon (default: @)
@@ -65,6 +70,9 @@ pub(crate) struct AbandonArgs {
     /// Do not modify the content of the children of the abandoned commits
     #[arg(long)]
     restore_descendants: bool,
+    /// Interactively choose which of the revisions to abandon
+    #[arg(long, short)]
+    interactive: bool,
 }
 
 #[instrument(skip_all)]
@@ -82,6 +90,11 @@ pub(crate) fn cmd_abandon(
     }
     .evaluate_to_commits()?
     .try_collect()?;
+    let to_abandon = if args.interactive && !to_abandon.is_empty() {
+        commit_picker::pick_commits(&workspace_command, "Abandon", to_abandon)?
+    } else {
+        to_abandon
+    };
     if to_abandon.is_empty() {
         writeln!(ui.status(), "No revisions to abandon.")?;
         return Ok(());
@@ -0,0 +1,667 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error;
+use std::io;
+use std::io::BufRead as _;
+use std::io::Write as _;
+use std::iter;
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::thread;
+use std::time::Duration;
+
+use futures::StreamExt as _;
+use itertools::Itertools as _;
+use jj_lib::annotate::get_annotation_for_file;
+use jj_lib::backend::Signature;
+use jj_lib::commit::Commit;
+use jj_lib::copies::CopiesTreeDiffEntry;
+use jj_lib::copies::CopyRecords;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::matchers::Matcher;
+use jj_lib::merged_tree::MergedTree;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::op_heads_store::OpHeadsStoreError;
+use jj_lib::op_store::OperationId;
+use jj_lib::repo::Repo;
+use jj_lib::repo::RepoLoader;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::rewrite::merge_commit_trees_with_strategies;
+use jj_lib::rewrite::restore_tree;
+use jj_lib::rewrite::squash_commits;
+use jj_lib::rewrite::CommitWithSelection;
+use once_cell::sync::Lazy;
+use pollster::FutureExt as _;
+use serde::de::DeserializeOwned;
+use serde::Deserialize as _;
+use serde_json::json;
+use serde_json::Value;
+use tracing::instrument;
+
+use crate::cli_util::load_merge_strategies;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::command_error::CommandError;
+use crate::command_error::ErrorHint;
+use crate::description_util::join_message_paragraphs;
+use crate::description_util::try_combine_messages;
+use crate::diff_util::diff_status_label_and_char;
+use crate::diff_util::get_copy_records;
+use crate::diff_util::DiffFormat;
+use crate::diff_util::UnifiedDiffOptions;
+use crate::formatter::PlainTextFormatter;
+use crate::time_util::format_absolute_timestamp_with;
+use crate::time_util::FormattingItems;
+use crate::ui::Ui;
+
+/// Run a JSON-RPC server for editor and IDE integration
+///
+/// Requests are read from stdin, and responses are written to stdout. Each
+/// message is a JSON-RPC 2.0 object written on a single line. Clients should
+/// send an `initialize` request with the protocol version they speak before
+/// any other requests.
+///
+/// A `op_heads_changed` notification is sent whenever the operation heads
+/// move, either by a request or by another jj process. The server exits when
+/// stdin is closed.
+///
+/// See the [API documentation] for the list of methods.
+///
+/// [API documentation]:
+///     https://jj-vcs.github.io/jj/latest/technical/api/
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct ApiArgs {}
+
+/// Version of the protocol. Bumped when incompatible changes are made.
+const API_VERSION: u64 = 1;
+
+/// How often the operation heads are checked while waiting for requests.
+const OP_HEADS_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// Error codes defined by the JSON-RPC 2.0 specification.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+// Implementation-defined server errors.
+const COMMAND_ERROR: i64 = -32000;
+const UNSUPPORTED_VERSION: i64 = -32001;
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_api(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &ApiArgs,
+) -> Result<(), CommandError> {
+    let workspace = command.load_workspace()?;
+    let repo_loader = workspace.repo_loader();
+    let mut op_heads = get_op_heads(repo_loader)?;
+
+    // Stdin is read by separate thread so the operation heads can be polled
+    // while the client is idle.
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    loop {
+        match receiver.recv_timeout(OP_HEADS_POLL_INTERVAL) {
+            Ok(line) => {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                if let Some(response) = handle_message(ui, command, &line) {
+                    write_message(ui, &response)?;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        let new_op_heads = get_op_heads(repo_loader)?;
+        if new_op_heads != op_heads {
+            let notification = json!({
+                "jsonrpc": "2.0",
+                "method": "op_heads_changed",
+                "params": {
+                    "operation_ids": new_op_heads.iter().map(|id| id.hex()).collect_vec(),
+                },
+            });
+            write_message(ui, &notification)?;
+            op_heads = new_op_heads;
+        }
+    }
+    Ok(())
+}
+
+fn get_op_heads(repo_loader: &RepoLoader) -> Result<Vec<OperationId>, OpHeadsStoreError> {
+    let mut op_heads = repo_loader.op_heads_store().get_op_heads()?;
+    op_heads.sort();
+    Ok(op_heads)
+}
+
+fn write_message(ui: &Ui, message: &Value) -> io::Result<()> {
+    let mut stdout = ui.stdout();
+    writeln!(stdout, "{message}")?;
+    stdout.flush()
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Request {
+    jsonrpc: String,
+    /// Request id, which may be `null`. `None` if the request is a
+    /// notification.
+    #[serde(default, deserialize_with = "deserialize_id")]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// Deserializes a present `id` as `Some`, even if it's `null`, so that only
+/// requests without an `id` are taken as notifications.
+fn deserialize_id<'de, D>(deserializer: D) -> Result<Option<Value>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Value::deserialize(deserializer).map(Some)
+}
+
+#[derive(Debug)]
+struct ApiError {
+    code: i64,
+    message: String,
+    hints: Vec<String>,
+}
+
+impl ApiError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        ApiError {
+            code,
+            message: message.into(),
+            hints: vec![],
+        }
+    }
+}
+
+impl From<CommandError> for ApiError {
+    fn from(err: CommandError) -> Self {
+        let message = iter::successors(Some(err.error.as_ref() as &dyn error::Error), |err| {
+            err.source()
+        })
+        .join("\nCaused by: ");
+        let hints = err
+            .hints
+            .iter()
+            .map(|hint| match hint {
+                ErrorHint::PlainText(message) => message.clone(),
+                ErrorHint::Formatted(recorder) => {
+                    String::from_utf8_lossy(recorder.data()).into_owned()
+                }
+            })
+            .collect();
+        ApiError {
+            code: COMMAND_ERROR,
+            message,
+            hints,
+        }
+    }
+}
+
+/// Processes the request message. Returns the response message if the request
+/// isn't a notification.
+fn handle_message(ui: &Ui, command: &CommandHelper, line: &str) -> Option<Value> {
+    let value: Value = match serde_json::from_str(line) {
+        Ok(value) => value,
+        Err(err) => {
+            let err = ApiError::new(PARSE_ERROR, err.to_string());
+            return Some(error_response(Value::Null, err));
+        }
+    };
+    let request = match serde_json::from_value::<Request>(value) {
+        Ok(request) if request.jsonrpc == "2.0" => request,
+        Ok(_) => {
+            let err = ApiError::new(INVALID_REQUEST, "Unsupported JSON-RPC version");
+            return Some(error_response(Value::Null, err));
+        }
+        Err(err) => {
+            let err = ApiError::new(INVALID_REQUEST, err.to_string());
+            return Some(error_response(Value::Null, err));
+        }
+    };
+    let result = call_method(ui, command, &request.method, request.params);
+    let id = request.id?;
+    match result {
+        Ok(result) => Some(json!({ "jsonrpc": "2.0", "id": id, "result": result })),
+        Err(err) => Some(error_response(id, err)),
+    }
+}
+
+fn error_response(id: Value, err: ApiError) -> Value {
+    let mut error = json!({ "code": err.code, "message": err.message });
+    if !err.hints.is_empty() {
+        error["data"] = json!({ "hints": err.hints });
+    }
+    json!({ "jsonrpc": "2.0", "id": id, "error": error })
+}
+
+fn call_method(
+    ui: &Ui,
+    command: &CommandHelper,
+    method: &str,
+    params: Value,
+) -> Result<Value, ApiError> {
+    match method {
+        "initialize" => api_initialize(command, parse_params(params)?),
+        "status" => Ok(api_status(ui, command, parse_params(params)?)?),
+        "log" => Ok(api_log(ui, command, parse_params(params)?)?),
+        "diff" => Ok(api_diff(ui, command, parse_params(params)?)?),
+        "annotate" => Ok(api_annotate(ui, command, parse_params(params)?)?),
+        "describe" => Ok(api_describe(ui, command, parse_params(params)?)?),
+        "new" => Ok(api_new(ui, command, parse_params(params)?)?),
+        "squash" => Ok(api_squash(ui, command, parse_params(params)?)?),
+        _ => Err(ApiError::new(
+            METHOD_NOT_FOUND,
+            format!("Unknown method: {method}"),
+        )),
+    }
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, ApiError> {
+    let params = if params.is_null() { json!({}) } else { params };
+    serde_json::from_value(params).map_err(|err| ApiError::new(INVALID_PARAMS, err.to_string()))
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct InitializeParams {
+    version: u64,
+}
+
+fn api_initialize(command: &CommandHelper, params: InitializeParams) -> Result<Value, ApiError> {
+    if params.version != API_VERSION {
+        return Err(ApiError::new(
+            UNSUPPORTED_VERSION,
+            format!(
+                "Unsupported protocol version {} (supported: {API_VERSION})",
+                params.version
+            ),
+        ));
+    }
+    let workspace = command.load_workspace()?;
+    Ok(json!({
+        "version": API_VERSION,
+        "jj_version": command.app().get_version(),
+        "workspace_root": workspace.workspace_root().to_string_lossy(),
+    }))
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StatusParams {}
+
+fn api_status(
+    ui: &Ui,
+    command: &CommandHelper,
+    _params: StatusParams,
+) -> Result<Value, CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo();
+    let Some(wc_commit_id) = workspace_command.get_wc_commit_id() else {
+        return Ok(json!({ "working_copy": null }));
+    };
+    let wc_commit = repo.store().get_commit(wc_commit_id)?;
+    let parents: Vec<_> = wc_commit.parents().try_collect()?;
+    let matcher = EverythingMatcher;
+    let copy_records = commit_copy_records(repo.as_ref(), &wc_commit, &matcher)?;
+    let changes = diff_files_to_json(
+        &wc_commit.parent_tree(repo.as_ref())?,
+        &wc_commit.tree()?,
+        &matcher,
+        &copy_records,
+    )?;
+    let conflicts = wc_commit
+        .tree()?
+        .conflicts()
+        .map(|(path, _value)| path.as_internal_file_string().to_owned())
+        .collect_vec();
+    Ok(json!({
+        "working_copy": commit_to_json(repo.as_ref(), &wc_commit)?,
+        "parents": parents
+            .iter()
+            .map(|commit| commit_to_json(repo.as_ref(), commit))
+            .try_collect::<_, Vec<_>, _>()?,
+        "changes": changes,
+        "conflicts": conflicts,
+    }))
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct LogParams {
+    revset: Option<String>,
+    limit: Option<usize>,
+}
+
+fn api_log(ui: &Ui, command: &CommandHelper, params: LogParams) -> Result<Value, CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo();
+    let revset_string = match params.revset {
+        Some(revset) => revset,
+        None => workspace_command.settings().get_string("revsets.log")?,
+    };
+    let commits = workspace_command
+        .parse_revset(ui, &RevisionArg::from(revset_string))?
+        .evaluate_to_commits()?
+        .take(params.limit.unwrap_or(usize::MAX))
+        .map(|commit| commit_to_json(repo.as_ref(), &commit?))
+        .try_collect::<_, Vec<_>, _>()?;
+    Ok(json!({ "commits": commits }))
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct DiffParams {
+    revision: Option<String>,
+    paths: Vec<String>,
+}
+
+fn api_diff(ui: &Ui, command: &CommandHelper, params: DiffParams) -> Result<Value, CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo();
+    let commit = resolve_single_rev(ui, &workspace_command, params.revision)?;
+    let matcher = workspace_command
+        .parse_file_patterns(ui, &params.paths)?
+        .to_matcher();
+    let from_tree = commit.parent_tree(repo.as_ref())?;
+    let to_tree = commit.tree()?;
+    let copy_records = commit_copy_records(repo.as_ref(), &commit, matcher.as_ref())?;
+    let files = diff_files_to_json(&from_tree, &to_tree, matcher.as_ref(), &copy_records)?;
+    let options = UnifiedDiffOptions::from_settings(workspace_command.settings())?;
+    let diff_renderer = workspace_command.diff_renderer(vec![DiffFormat::Git(Box::new(options))]);
+    let mut patch = vec![];
+    diff_renderer.show_diff(
+        ui,
+        &mut PlainTextFormatter::new(&mut patch),
+        &from_tree,
+        &to_tree,
+        matcher.as_ref(),
+        &copy_records,
+        ui.term_width(),
+    )?;
+    Ok(json!({
+        "commit": commit_to_json(repo.as_ref(), &commit)?,
+        "files": files,
+        "patch": String::from_utf8_lossy(&patch),
+    }))
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AnnotateParams {
+    path: String,
+    #[serde(default)]
+    revision: Option<String>,
+}
+
+fn api_annotate(
+    ui: &Ui,
+    command: &CommandHelper,
+    params: AnnotateParams,
+) -> Result<Value, CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo();
+    let starting_commit = resolve_single_rev(ui, &workspace_command, params.revision)?;
+    let file_path = workspace_command.parse_file_path(&params.path)?;
+    let file_value = starting_commit.tree()?.path_value(&file_path)?;
+    let ui_path = workspace_command.format_file_path(&file_path);
+    if file_value.is_absent() {
+        return Err(user_error(format!("No such path: {ui_path}")));
+    }
+    if file_value.is_tree() {
+        return Err(user_error(format!(
+            "Path exists but is not a regular file: {ui_path}"
+        )));
+    }
+    let domain = RevsetExpression::all();
+    let annotation = get_annotation_for_file(repo.as_ref(), &starting_commit, &domain, &file_path)?;
+    let lines = annotation
+        .lines()
+        .enumerate()
+        .map(|(line_number, (commit_id, content))| {
+            let commit_id = commit_id.expect("should reached to the empty ancestor");
+            let commit = repo.store().get_commit(commit_id)?;
+            Ok::<_, CommandError>(json!({
+                "line_number": line_number + 1,
+                "commit_id": commit.id().hex(),
+                "change_id": commit.change_id().reverse_hex(),
+                "content": String::from_utf8_lossy(content),
+            }))
+        })
+        .try_collect::<_, Vec<_>, _>()?;
+    Ok(json!({ "lines": lines }))
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DescribeParams {
+    #[serde(default)]
+    revision: Option<String>,
+    message: String,
+}
+
+fn api_describe(
+    ui: &Ui,
+    command: &CommandHelper,
+    params: DescribeParams,
+) -> Result<Value, CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let commit = resolve_single_rev(ui, &workspace_command, params.revision)?;
+    workspace_command.check_rewritable([commit.id()])?;
+    let mut tx = workspace_command.start_transaction();
+    let new_commit = tx
+        .repo_mut()
+        .rewrite_commit(&commit)
+        .set_description(join_message_paragraphs(&[params.message]))
+        .write()?;
+    tx.rebase_descendants()?;
+    let result = json!({ "commit": commit_to_json(tx.repo(), &new_commit)? });
+    tx.finish(ui, format!("describe commit {}", commit.id().hex()))?;
+    Ok(result)
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct NewParams {
+    parents: Vec<String>,
+    message: String,
+}
+
+fn api_new(ui: &Ui, command: &CommandHelper, params: NewParams) -> Result<Value, CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let parent_args = if params.parents.is_empty() {
+        vec![RevisionArg::AT]
+    } else {
+        params.parents.into_iter().map(RevisionArg::from).collect()
+    };
+    let parent_commits = workspace_command
+        .resolve_some_revsets_default_single(ui, &parent_args)?
+        .into_iter()
+        .collect_vec();
+    let parent_ids = parent_commits.iter().map(|c| c.id().clone()).collect();
+    let merge_strategies = load_merge_strategies(command.settings())?;
+    let mut tx = workspace_command.start_transaction();
+    let merged_tree =
+        merge_commit_trees_with_strategies(tx.repo(), &parent_commits, &merge_strategies)?;
+    let new_commit = tx
+        .repo_mut()
+        .new_commit(parent_ids, merged_tree.id())
+        .set_description(join_message_paragraphs(&[params.message]))
+        .write()?;
+    tx.edit(&new_commit)?;
+    let result = json!({ "commit": commit_to_json(tx.repo(), &new_commit)? });
+    tx.finish(ui, "new empty commit")?;
+    Ok(result)
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SquashParams {
+    revision: Option<String>,
+    into: Option<String>,
+    paths: Vec<String>,
+    message: Option<String>,
+}
+
+fn api_squash(
+    ui: &Ui,
+    command: &CommandHelper,
+    params: SquashParams,
+) -> Result<Value, CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let source = resolve_single_rev(ui, &workspace_command, params.revision)?;
+    let destination = match params.into {
+        Some(into) => workspace_command.resolve_single_rev(ui, &RevisionArg::from(into))?,
+        None => {
+            let mut parents: Vec<_> = source.parents().try_collect()?;
+            if parents.len() != 1 {
+                return Err(user_error_with_hint(
+                    "Cannot squash merge commits without a specified destination",
+                    "Use `into` to specify which parent to squash into",
+                ));
+            }
+            parents.pop().unwrap()
+        }
+    };
+    if source.id() == destination.id() {
+        return Err(user_error("Source and destination cannot be the same"));
+    }
+    let matcher = workspace_command
+        .parse_file_patterns(ui, &params.paths)?
+        .to_matcher();
+    workspace_command.check_rewritable([source.id(), destination.id()])?;
+
+    let mut tx = workspace_command.start_transaction();
+    let tx_description = format!("squash commits into {}", destination.id().hex());
+    let parent_tree = source.parent_tree(tx.repo())?;
+    let selected_tree_id = restore_tree(&source.tree()?, &parent_tree, matcher.as_ref())?;
+    let source_commits = [CommitWithSelection {
+        commit: source.clone(),
+        selected_tree: tx.repo().store().get_root_tree(&selected_tree_id)?,
+        parent_tree,
+    }];
+    let Some(squashed) = squash_commits(tx.repo_mut(), &source_commits, &destination, false)?
+    else {
+        return Err(user_error("No changes to squash"));
+    };
+    let description = match params.message {
+        Some(message) => join_message_paragraphs(&[message]),
+        None => {
+            try_combine_messages(&squashed.abandoned_commits, &destination).ok_or_else(|| {
+                user_error_with_hint(
+                    "Both source and destination have descriptions",
+                    "Use `message` to specify the description of the combined commit",
+                )
+            })?
+        }
+    };
+    let new_commit = squashed
+        .commit_builder
+        .set_description(description)
+        .write()?;
+    tx.rebase_descendants()?;
+    let result = json!({ "commit": commit_to_json(tx.repo(), &new_commit)? });
+    tx.finish(ui, tx_description)?;
+    Ok(result)
+}
+
+fn resolve_single_rev(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    revision: Option<String>,
+) -> Result<Commit, CommandError> {
+    let revision = revision.map_or(RevisionArg::AT, RevisionArg::from);
+    workspace_command.resolve_single_rev(ui, &revision)
+}
+
+fn commit_copy_records(
+    repo: &dyn Repo,
+    commit: &Commit,
+    matcher: &dyn Matcher,
+) -> Result<CopyRecords, CommandError> {
+    let mut copy_records = CopyRecords::default();
+    for parent_id in commit.parent_ids() {
+        let records = get_copy_records(repo.store(), parent_id, commit.id(), matcher)?;
+        copy_records.add_records(records)?;
+    }
+    Ok(copy_records)
+}
+
+fn diff_files_to_json(
+    from_tree: &MergedTree,
+    to_tree: &MergedTree,
+    matcher: &dyn Matcher,
+    copy_records: &CopyRecords,
+) -> Result<Vec<Value>, CommandError> {
+    async {
+        let mut tree_diff = from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
+        let mut files = vec![];
+        while let Some(CopiesTreeDiffEntry { path, values }) = tree_diff.next().await {
+            let (before, after) = values?;
+            let (status, _) = diff_status_label_and_char(&path, &before, &after);
+            let mut file = json!({
+                "path": path.target().as_internal_file_string(),
+                "status": status,
+            });
+            if path.copy_operation().is_some() {
+                file["source"] = json!(path.source().as_internal_file_string());
+            }
+            files.push(file);
+        }
+        Ok(files)
+    }
+    .block_on()
+}
+
+fn commit_to_json(repo: &dyn Repo, commit: &Commit) -> Result<Value, CommandError> {
+    Ok(json!({
+        "commit_id": commit.id().hex(),
+        "change_id": commit.change_id().reverse_hex(),
+        "parent_ids": commit.parent_ids().iter().map(|id| id.hex()).collect_vec(),
+        "description": commit.description(),
+        "author": signature_to_json(commit.author()),
+        "committer": signature_to_json(commit.committer()),
+        "empty": commit.is_empty(repo)?,
+        "conflict": commit.has_conflict()?,
+    }))
+}
+
+fn signature_to_json(signature: &Signature) -> Value {
+    static RFC3339_FORMAT: Lazy<FormattingItems> =
+        Lazy::new(|| FormattingItems::parse("%Y-%m-%dT%H:%M:%S%:z").unwrap());
+    json!({
+        "name": signature.name,
+        "email": signature.email,
+        "timestamp": format_absolute_timestamp_with(&signature.timestamp, &RFC3339_FORMAT).ok(),
+    })
+}
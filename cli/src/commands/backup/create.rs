@@ -0,0 +1,222 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+
+use itertools::Itertools as _;
+use jj_lib::file_util::IoResultExt as _;
+use tracing::instrument;
+
+use super::FORMAT_VERSION;
+use super::FORMAT_VERSION_PATH;
+use super::REPO_DIR;
+use super::WORKING_COPY_TYPE_PATH;
+use super::WORKSPACE_ID_PATH;
+use crate::cli_util::short_operation_hash;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::user_error_with_message;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Write a backup of the repo to an archive
+///
+/// The working copy is snapshotted first. The backup contains all commits and
+/// operations, the commit index, and the repo configuration, but not the
+/// working-copy files, which are checked out again when the backup is
+/// restored.
+///
+/// If the repo is backed by an external Git repo, e.g. because it's colocated,
+/// the Git repo is included in the backup, and it will be restored as an
+/// internal Git repo.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct BackupCreateArgs {
+    /// The file to write the archive to, or `-` for stdout
+    ///
+    /// The file must not exist yet.
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    destination: String,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_backup_create(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BackupCreateArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let destination = if args.destination == "-" {
+        write_backup(&workspace_command, io::stdout().lock())?;
+        "stdout".to_owned()
+    } else {
+        let path = command.cwd().join(&args.destination);
+        let file = File::create_new(&path).map_err(|err| {
+            user_error_with_message(format!("Failed to create {}", args.destination), err)
+        })?;
+        write_backup(&workspace_command, file)?;
+        args.destination.clone()
+    };
+    writeln!(
+        ui.status(),
+        "Backed up the repo at operation {} to {destination}",
+        short_operation_hash(workspace_command.repo().op_id())
+    )?;
+    Ok(())
+}
+
+fn write_backup(
+    workspace_command: &WorkspaceCommandHelper,
+    writer: impl Write,
+) -> Result<(), CommandError> {
+    let mut builder = tar::Builder::new(BufWriter::new(writer));
+    builder.follow_symlinks(false);
+    let to_command_error = |err| user_error_with_message("Failed to write backup", err);
+    append_file_data(
+        &mut builder,
+        FORMAT_VERSION_PATH,
+        format!("{FORMAT_VERSION}\n").as_bytes(),
+    )
+    .map_err(to_command_error)?;
+    append_file_data(
+        &mut builder,
+        WORKSPACE_ID_PATH,
+        workspace_command.workspace_id().as_str().as_bytes(),
+    )
+    .map_err(to_command_error)?;
+    append_file_data(
+        &mut builder,
+        WORKING_COPY_TYPE_PATH,
+        workspace_command.working_copy().name().as_bytes(),
+    )
+    .map_err(to_command_error)?;
+
+    let repo_path = workspace_command.repo_path();
+    builder
+        .append_dir(REPO_DIR, repo_path)
+        .map_err(to_command_error)?;
+    // The repo may be modified while it's archived. Since objects and
+    // operations are written before the operation heads pointing to them, the
+    // heads are archived first, then the operations, then everything else.
+    let names = sorted_dir_entries(repo_path)?
+        .into_iter()
+        .sorted_by_key(|name| match name.as_str() {
+            "op_heads" => 0,
+            "op_store" => 1,
+            _ => 2,
+        })
+        .collect_vec();
+    for name in names {
+        let path = repo_path.join(&name);
+        let archive_path = Path::new(REPO_DIR).join(&name);
+        if name == "store" {
+            append_store(&mut builder, &path, &archive_path)?;
+        } else {
+            append_path(&mut builder, &path, &archive_path)?;
+        }
+    }
+    builder
+        .into_inner()
+        .and_then(|mut writer| writer.flush())
+        .map_err(to_command_error)?;
+    Ok(())
+}
+
+/// Archives the commit store. An external Git repo is archived as an internal
+/// one.
+fn append_store(
+    builder: &mut tar::Builder<impl Write>,
+    store_path: &Path,
+    archive_path: &Path,
+) -> Result<(), CommandError> {
+    builder
+        .append_dir(archive_path, store_path)
+        .map_err(|err| user_error_with_message("Failed to write backup", err))?;
+    let git_target_path = store_path.join("git_target");
+    let git_repo_path = if git_target_path.is_file() {
+        let git_target = fs::read_to_string(&git_target_path).context(&git_target_path)?;
+        let git_repo_path = store_path.join(git_target.trim());
+        append_file_data(builder, archive_path.join("git_target"), b"git")
+            .map_err(|err| user_error_with_message("Failed to write backup", err))?;
+        append_path(builder, &git_repo_path, &archive_path.join("git"))?;
+        Some(git_repo_path)
+    } else {
+        None
+    };
+    for name in sorted_dir_entries(store_path)? {
+        let path = store_path.join(&name);
+        if git_repo_path.is_some() && (name == "git_target" || name == "git") {
+            continue;
+        }
+        append_path(builder, &path, &archive_path.join(&name))?;
+    }
+    Ok(())
+}
+
+/// Archives the file or directory at `path`, skipping lock files.
+fn append_path(
+    builder: &mut tar::Builder<impl Write>,
+    path: &Path,
+    archive_path: &Path,
+) -> Result<(), CommandError> {
+    let metadata = path.symlink_metadata().context(path)?;
+    if metadata.is_dir() {
+        builder
+            .append_dir(archive_path, path)
+            .map_err(|err| user_error_with_message("Failed to write backup", err))?;
+        for name in sorted_dir_entries(path)? {
+            if name == "lock" || name.ends_with(".lock") {
+                continue;
+            }
+            append_path(builder, &path.join(&name), &archive_path.join(&name))?;
+        }
+    } else {
+        builder
+            .append_path_with_name(path, archive_path)
+            .map_err(|err| user_error_with_message("Failed to write backup", err))?;
+    }
+    Ok(())
+}
+
+fn append_file_data(
+    builder: &mut tar::Builder<impl Write>,
+    archive_path: impl AsRef<Path>,
+    data: &[u8],
+) -> io::Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len().try_into().unwrap());
+    header.set_mode(0o644);
+    header.set_entry_type(tar::EntryType::Regular);
+    builder.append_data(&mut header, archive_path, data)
+}
+
+fn sorted_dir_entries(path: &Path) -> Result<Vec<String>, CommandError> {
+    let mut names = vec![];
+    for entry in path.read_dir().context(path)? {
+        let entry = entry.context(path)?;
+        let name = entry.file_name().into_string().map_err(|name| {
+            user_error_with_message(
+                "Failed to write backup",
+                format!("Unsupported file name {name:?}"),
+            )
+        })?;
+        names.push(name);
+    }
+    names.sort();
+    Ok(names)
+}
@@ -0,0 +1,63 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod create;
+mod restore;
+
+use clap::Subcommand;
+use tracing::instrument;
+
+use self::create::cmd_backup_create;
+use self::create::BackupCreateArgs;
+use self::restore::cmd_backup_restore;
+use self::restore::BackupRestoreArgs;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Version of the archive format, stored in the first entry of the archive.
+const FORMAT_VERSION: u32 = 1;
+const FORMAT_VERSION_PATH: &str = "jj-backup-version";
+const WORKSPACE_ID_PATH: &str = "workspace-id";
+const WORKING_COPY_TYPE_PATH: &str = "working-copy-type";
+/// Directory of the archive which contains the `.jj/repo` directory.
+const REPO_DIR: &str = "repo";
+
+/// Back up the whole repo, including the operation log
+///
+/// A backup is a tar archive of the commits, the operation log, and the
+/// metadata of the current workspace. Restoring it gives back the repo with
+/// its full undo history, so it's a good idea to create one before running
+/// risky operations.
+///
+/// The archive is written and read as a stream, so it can be piped through a
+/// compression tool, e.g. `jj backup create - | zstd > repo.tar.zst` and `zstd
+/// -d < repo.tar.zst | jj backup restore - restored`.
+#[derive(Subcommand, Clone, Debug)]
+pub(crate) enum BackupCommand {
+    Create(BackupCreateArgs),
+    Restore(BackupRestoreArgs),
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_backup(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &BackupCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        BackupCommand::Create(args) => cmd_backup_create(ui, command, args),
+        BackupCommand::Restore(args) => cmd_backup_restore(ui, command, args),
+    }
+}
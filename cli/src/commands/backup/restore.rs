@@ -0,0 +1,257 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Write as _;
+use std::path::Path;
+
+use jj_lib::commit::Commit;
+use jj_lib::file_util;
+use jj_lib::file_util::IoResultExt as _;
+use jj_lib::op_store::WorkspaceId;
+use jj_lib::repo::Repo as _;
+use jj_lib::repo::RepoLoader;
+use jj_lib::working_copy::CheckoutStats;
+use jj_lib::workspace::Workspace;
+use tracing::instrument;
+
+use super::FORMAT_VERSION;
+use super::FORMAT_VERSION_PATH;
+use super::REPO_DIR;
+use super::WORKING_COPY_TYPE_PATH;
+use super::WORKSPACE_ID_PATH;
+use crate::cli_util::print_checkout_stats;
+use crate::cli_util::short_operation_hash;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::internal_error_with_message;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Restore a repo from a backup archive
+///
+/// A new workspace is created at the destination, with the commits and the
+/// operation log as they were when the backup was created. The working-copy
+/// commit of the backed-up workspace is checked out in it.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct BackupRestoreArgs {
+    /// The archive created by `jj backup create`, or `-` for stdin
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    source: String,
+    /// Where to create the restored workspace
+    ///
+    /// The directory must not exist yet or be empty.
+    #[arg(value_hint = clap::ValueHint::DirPath)]
+    destination: String,
+}
+
+/// Workspace metadata read from the archive.
+struct BackupMetadata {
+    workspace_id: WorkspaceId,
+    working_copy_type: String,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_backup_restore(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BackupRestoreArgs,
+) -> Result<(), CommandError> {
+    let destination_path = command.cwd().join(&args.destination);
+    let destination_existed = destination_path.exists();
+    if destination_existed && !is_empty_dir(&destination_path) {
+        return Err(user_error(
+            "Destination path exists and is not an empty directory",
+        ));
+    }
+    fs::create_dir_all(&destination_path).context(&destination_path)?;
+    let destination_path = dunce::canonicalize(&destination_path).context(&destination_path)?;
+
+    let restore_result = restore_workspace(ui, command, args, &destination_path);
+    if restore_result.is_err() {
+        let clean_up_dirs = || -> io::Result<()> {
+            let jj_dir = destination_path.join(".jj");
+            if jj_dir.exists() {
+                fs::remove_dir_all(jj_dir)?;
+            }
+            if !destination_existed {
+                fs::remove_dir(&destination_path)?;
+            }
+            Ok(())
+        };
+        if let Err(err) = clean_up_dirs() {
+            writeln!(
+                ui.warning_default(),
+                "Failed to clean up {}: {}",
+                destination_path.display(),
+                err
+            )
+            .ok();
+        }
+    }
+    let (workspace_command, wc_commit, stats) = restore_result?;
+
+    writeln!(
+        ui.status(),
+        r#"Restored the repo at operation {} into "{}""#,
+        short_operation_hash(workspace_command.repo().op_id()),
+        file_util::relative_path(command.cwd(), &destination_path).display()
+    )?;
+    if let Some(mut formatter) = ui.status_formatter() {
+        write!(formatter, "Working copy now at: ")?;
+        formatter.with_label("working_copy", |fmt| {
+            workspace_command.write_commit_summary(fmt, &wc_commit)
+        })?;
+        writeln!(formatter)?;
+    }
+    print_checkout_stats(ui, stats, &wc_commit)?;
+    Ok(())
+}
+
+/// Unpacks the backup into a new workspace at `destination_path`, and checks
+/// out its working-copy commit.
+fn restore_workspace(
+    ui: &Ui,
+    command: &CommandHelper,
+    args: &BackupRestoreArgs,
+    destination_path: &Path,
+) -> Result<(WorkspaceCommandHelper, Commit, CheckoutStats), CommandError> {
+    let jj_dir = destination_path.join(".jj");
+    fs::create_dir(&jj_dir).context(&jj_dir)?;
+    let metadata = if args.source == "-" {
+        unpack_backup(io::stdin().lock(), &jj_dir)?
+    } else {
+        let path = command.cwd().join(&args.source);
+        let file = File::open(&path).map_err(|err| {
+            user_error_with_message(format!("Failed to open {}", args.source), err)
+        })?;
+        unpack_backup(BufReader::new(file), &jj_dir)?
+    };
+    let repo_path = jj_dir.join("repo");
+    if !repo_path.is_dir() {
+        return Err(user_error("The backup doesn't contain a repo"));
+    }
+
+    let settings = command.settings_for_new_workspace(destination_path)?;
+    let repo_loader =
+        RepoLoader::init_from_file_system(&settings, &repo_path, command.store_factories())
+            .map_err(|err| user_error_with_message("Failed to load the restored repo", err))?;
+    let repo = repo_loader.load_at_head()?;
+    let Some(working_copy_factory) = command
+        .working_copy_factories()
+        .get(&metadata.working_copy_type)
+    else {
+        return Err(user_error(format!(
+            "Unsupported working copy type '{}'",
+            metadata.working_copy_type
+        )));
+    };
+    let working_copy_state_path = jj_dir.join("working_copy");
+    fs::create_dir(&working_copy_state_path).context(&working_copy_state_path)?;
+    // The working-copy state starts out empty, and is then updated to the
+    // working-copy commit.
+    let working_copy = working_copy_factory.init_working_copy(
+        repo.store().clone(),
+        destination_path.to_owned(),
+        working_copy_state_path.clone(),
+        repo.op_id().clone(),
+        metadata.workspace_id.clone(),
+    )?;
+    let working_copy_type_path = working_copy_state_path.join("type");
+    fs::write(&working_copy_type_path, working_copy.name()).context(&working_copy_type_path)?;
+    let workspace = Workspace::new(destination_path, repo_path, working_copy, repo_loader)?;
+    let mut workspace_command = command.for_workable_repo(ui, workspace, repo.clone())?;
+    if workspace_command.get_wc_commit_id().is_none() {
+        return Err(user_error(format!(
+            "Workspace {} no longer exists in the backed-up repo",
+            metadata.workspace_id.as_str()
+        )));
+    }
+
+    let checkout_options = workspace_command.checkout_options();
+    let (mut locked_ws, wc_commit) = workspace_command.unchecked_start_working_copy_mutation()?;
+    let stats = locked_ws
+        .locked_wc()
+        .check_out(&wc_commit, &checkout_options)
+        .map_err(|err| internal_error_with_message("Failed to check out the working copy", err))?;
+    locked_ws.finish(repo.op_id().clone())?;
+    Ok((workspace_command, wc_commit, stats))
+}
+
+/// Unpacks the repo into `jj_dir`, and returns the workspace metadata.
+fn unpack_backup(reader: impl Read, jj_dir: &Path) -> Result<BackupMetadata, CommandError> {
+    let to_command_error = |err| user_error_with_message("Failed to read backup", err);
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = archive.entries().map_err(to_command_error)?;
+    let mut version_entry = entries
+        .next()
+        .ok_or_else(|| user_error("The backup is empty"))?
+        .map_err(to_command_error)?;
+    if version_entry.path().map_err(to_command_error)? != Path::new(FORMAT_VERSION_PATH) {
+        return Err(user_error("Not a backup created by `jj backup create`"));
+    }
+    let version = read_entry_text(&mut version_entry)?;
+    if version != FORMAT_VERSION.to_string() {
+        return Err(user_error(format!(
+            "Unsupported backup format version {version}"
+        )));
+    }
+
+    let mut workspace_id = None;
+    let mut working_copy_type = None;
+    for entry in entries {
+        let mut entry = entry.map_err(to_command_error)?;
+        let path = entry.path().map_err(to_command_error)?.into_owned();
+        if path == Path::new(WORKSPACE_ID_PATH) {
+            workspace_id = Some(WorkspaceId::new(read_entry_text(&mut entry)?));
+        } else if path == Path::new(WORKING_COPY_TYPE_PATH) {
+            working_copy_type = Some(read_entry_text(&mut entry)?);
+        } else if path.starts_with(REPO_DIR) {
+            // Paths escaping the destination directory are skipped.
+            entry.unpack_in(jj_dir).map_err(to_command_error)?;
+        } else {
+            return Err(user_error(format!(
+                "Unexpected file {} in the backup",
+                path.display()
+            )));
+        }
+    }
+    Ok(BackupMetadata {
+        workspace_id: workspace_id.ok_or_else(|| user_error("The backup has no workspace"))?,
+        working_copy_type: working_copy_type
+            .ok_or_else(|| user_error("The backup has no working copy type"))?,
+    })
+}
+
+fn read_entry_text(entry: &mut impl Read) -> Result<String, CommandError> {
+    let mut text = String::new();
+    entry
+        .read_to_string(&mut text)
+        .map_err(|err| user_error_with_message("Failed to read backup", err))?;
+    Ok(text.trim_end().to_owned())
+}
+
+fn is_empty_dir(path: &Path) -> bool {
+    if let Ok(mut entries) = path.read_dir() {
+        entries.next().is_none()
+    } else {
+        false
+    }
+}
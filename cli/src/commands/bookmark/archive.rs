@@ -0,0 +1,80 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCandidates;
+use itertools::Itertools as _;
+use jj_lib::str_util::StringPattern;
+
+use super::find_local_bookmarks;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Hide existing bookmarks without deleting them
+///
+/// Archived bookmarks are kept as is, but are omitted from `jj bookmark list`
+/// and `jj log` output by default. Use `jj bookmark list --include-archived`
+/// to list them, and `jj bookmark unarchive` to restore them.
+#[derive(clap::Args, Clone, Debug)]
+pub struct BookmarkArchiveArgs {
+    /// The bookmarks to archive
+    ///
+    /// By default, the specified name matches exactly. Use `glob:` prefix to
+    /// select bookmarks by [wildcard pattern].
+    ///
+    /// [wildcard pattern]:
+    ///     https://jj-vcs.github.io/jj/latest/revsets/#string-patterns
+    #[arg(
+        required = true,
+        value_parser = StringPattern::parse,
+        add = ArgValueCandidates::new(complete::local_bookmarks),
+    )]
+    names: Vec<StringPattern>,
+}
+
+pub fn cmd_bookmark_archive(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &BookmarkArchiveArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo().clone();
+    let view = repo.view();
+    let matched_bookmarks = find_local_bookmarks(view, &args.names)?
+        .into_iter()
+        .filter(|(name, _)| !view.is_bookmark_archived(name))
+        .collect_vec();
+    if matched_bookmarks.is_empty() {
+        writeln!(ui.status(), "Nothing changed.")?;
+        return Ok(());
+    }
+    let mut tx = workspace_command.start_transaction();
+    for (name, _) in &matched_bookmarks {
+        tx.repo_mut().set_bookmark_archived(name, true);
+    }
+    writeln!(
+        ui.status(),
+        "Archived {} bookmarks.",
+        matched_bookmarks.len()
+    )?;
+    tx.finish(
+        ui,
+        format!(
+            "archive bookmark {}",
+            matched_bookmarks.iter().map(|(name, _)| name).join(", ")
+        ),
+    )?;
+    Ok(())
+}
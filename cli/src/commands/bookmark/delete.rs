@@ -17,6 +17,7 @@ use itertools::Itertools as _;
 use jj_lib::op_store::RefTarget;
 use jj_lib::str_util::StringPattern;
 
+use super::confirm_bulk_operation;
 use super::find_local_bookmarks;
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
@@ -58,6 +59,14 @@ pub fn cmd_bookmark_delete(
     let mut workspace_command = command.workspace_helper(ui)?;
     let repo = workspace_command.repo().clone();
     let matched_bookmarks = find_local_bookmarks(repo.view(), &args.names)?;
+    let all_exact = args.names.iter().all(StringPattern::is_exact);
+    let names = matched_bookmarks
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .collect_vec();
+    if !confirm_bulk_operation(ui, "deleted", all_exact, &names)? {
+        return Ok(());
+    }
     let mut tx = workspace_command.start_transaction();
     for (name, _) in &matched_bookmarks {
         tx.repo_mut()
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::iter;
+
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
 use jj_lib::op_store::BookmarkTarget;
@@ -21,6 +23,7 @@ use jj_lib::refs::RemoteRefSymbol;
 use jj_lib::str_util::StringPattern;
 use jj_lib::view::View;
 
+use super::confirm_bulk_operation;
 use super::find_bookmarks_with;
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
@@ -66,6 +69,21 @@ pub fn cmd_bookmark_forget(
     let mut workspace_command = command.workspace_helper(ui)?;
     let repo = workspace_command.repo().clone();
     let matched_bookmarks = find_forgettable_bookmarks(repo.view(), &args.names)?;
+    let all_exact = args.names.iter().all(StringPattern::is_exact);
+    let names = matched_bookmarks
+        .iter()
+        .flat_map(|(name, bookmark_target)| {
+            let remote_names = bookmark_target
+                .remote_refs
+                .iter()
+                .filter(|_| args.include_remotes)
+                .map(move |(remote, _)| RemoteRefSymbol { name, remote }.to_string());
+            iter::once(name.to_string()).chain(remote_names)
+        })
+        .collect_vec();
+    if !confirm_bulk_operation(ui, "forgotten", all_exact, &names)? {
+        return Ok(());
+    }
     let mut tx = workspace_command.start_transaction();
     let mut forgotten_remote: usize = 0;
     for (name, bookmark_target) in &matched_bookmarks {
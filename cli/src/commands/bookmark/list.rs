@@ -13,6 +13,9 @@
 // limitations under the License.
 
 use std::collections::HashSet;
+use std::io;
+use std::iter;
+use std::rc::Rc;
 
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools;
@@ -20,12 +23,16 @@ use jj_lib::repo::Repo;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::str_util::StringPattern;
 
+use crate::cli_util::format_template_null_terminated;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::command_error::CommandError;
 use crate::commit_templater::CommitTemplateLanguage;
 use crate::commit_templater::RefName;
 use crate::complete;
+use crate::formatter::FormatRecorder;
+use crate::formatter::Formatter;
+use crate::text_util;
 use crate::ui::Ui;
 
 /// List bookmarks and their targets
@@ -76,6 +83,14 @@ pub struct BookmarkListArgs {
     #[arg(long, short, conflicts_with_all = ["all_remotes"])]
     conflicted: bool,
 
+    /// Show archived bookmarks as well
+    #[arg(long)]
+    include_archived: bool,
+
+    /// Group bookmarks by `/`-separated prefixes of their names
+    #[arg(long)]
+    tree: bool,
+
     /// Show bookmarks whose local name matches
     ///
     /// By default, the specified name matches exactly. Use `glob:` prefix to
@@ -106,6 +121,14 @@ pub struct BookmarkListArgs {
     ///     https://jj-vcs.github.io/jj/latest/templates/
     #[arg(long, short = 'T', add = ArgValueCandidates::new(complete::template_aliases))]
     template: Option<String>,
+
+    /// Terminate each entry with a NUL byte instead of a newline
+    ///
+    /// The template output is printed without color, and its trailing newline
+    /// is replaced with a NUL byte, so the output can be safely consumed by
+    /// `xargs -0` and the like.
+    #[arg(long = "null", short = 'z', conflicts_with = "tree")]
+    null_terminated: bool,
 }
 
 pub fn cmd_bookmark_list(
@@ -161,6 +184,18 @@ pub fn cmd_bookmark_list(
             .labeled("bookmark_list")
     };
 
+    #[cfg(feature = "git")]
+    let push_remotes =
+        crate::git_util::BookmarkPushRemotes::from_settings(workspace_command.settings())?;
+
+    let write_ref_name = |out: &mut dyn Formatter, ref_name: &Rc<RefName>| {
+        if args.null_terminated {
+            out.write_all(&format_template_null_terminated(ref_name, &template))
+        } else {
+            template.format(ref_name, out)
+        }
+    };
+
     ui.request_pager();
     let mut formatter = ui.stdout_formatter();
 
@@ -171,8 +206,16 @@ pub fn cmd_bookmark_list(
             .as_ref()
             .is_none_or(|bookmark_names| bookmark_names.contains(name))
             && (!args.conflicted || target.local_target.has_conflict())
+            && (args.include_archived || !view.is_bookmark_archived(name))
     });
+    let mut tree_dirs: Vec<&str> = vec![];
     for (name, bookmark_target) in bookmarks_to_list {
+        let mut recorder = FormatRecorder::new();
+        let out: &mut dyn Formatter = if args.tree {
+            &mut recorder
+        } else {
+            formatter.as_mut()
+        };
         let local_target = bookmark_target.local_target;
         let remote_refs = bookmark_target.remote_refs;
         let (mut tracking_remote_refs, untracked_remote_refs) = remote_refs
@@ -193,17 +236,23 @@ pub fn cmd_bookmark_list(
 
         let include_local_only = !args.tracked && args.remotes.is_none();
         if include_local_only && local_target.is_present() || !tracking_remote_refs.is_empty() {
-            let ref_name = RefName::local(
+            #[cfg(feature = "git")]
+            let push_remote = push_remotes.get(name)?.map(ToOwned::to_owned);
+            #[cfg(not(feature = "git"))]
+            let push_remote = None;
+            let ref_name = RefName::local_with_metadata(
                 name,
                 local_target.clone(),
                 remote_refs.iter().map(|&(_, remote_ref)| remote_ref),
+                view.is_bookmark_archived(name),
+                push_remote,
             );
-            template.format(&ref_name, formatter.as_mut())?;
+            write_ref_name(out, &ref_name)?;
         }
 
         for &(remote, remote_ref) in &tracking_remote_refs {
             let ref_name = RefName::remote(name, remote, remote_ref.clone(), local_target);
-            template.format(&ref_name, formatter.as_mut())?;
+            write_ref_name(out, &ref_name)?;
         }
 
         if local_target.is_absent() && !tracking_remote_refs.is_empty() {
@@ -216,9 +265,13 @@ pub fn cmd_bookmark_list(
         if !args.tracked && (args.all_remotes || args.remotes.is_some()) {
             for &(remote, remote_ref) in &untracked_remote_refs {
                 let ref_name = RefName::remote_only(name, remote, remote_ref.target.clone());
-                template.format(&ref_name, formatter.as_mut())?;
+                write_ref_name(out, &ref_name)?;
             }
         }
+
+        if args.tree && !recorder.data().is_empty() {
+            write_tree_entry(formatter.as_mut(), &mut tree_dirs, name, &recorder)?;
+        }
     }
 
     drop(formatter);
@@ -244,3 +297,29 @@ pub fn cmd_bookmark_list(
 
     Ok(())
 }
+
+/// Writes the recorded bookmark entry indented by the number of `/`-separated
+/// prefixes of the `name`. Prefix headers are written as needed. Bookmarks
+/// should be sorted by name so the entries sharing a prefix are grouped.
+fn write_tree_entry<'a>(
+    formatter: &mut dyn Formatter,
+    tree_dirs: &mut Vec<&'a str>,
+    name: &'a str,
+    recorder: &FormatRecorder,
+) -> io::Result<()> {
+    let mut dirs = name.split('/').collect_vec();
+    dirs.pop();
+    let common_len = iter::zip(&*tree_dirs, &dirs)
+        .take_while(|(a, b)| a == b)
+        .count();
+    tree_dirs.truncate(common_len);
+    for &dir in &dirs[common_len..] {
+        write!(formatter, "{}", "  ".repeat(tree_dirs.len()))?;
+        writeln!(formatter.labeled("bookmark"), "{dir}/")?;
+        tree_dirs.push(dir);
+    }
+    let indent = "  ".repeat(tree_dirs.len());
+    text_util::write_indented(formatter, recorder, |formatter| {
+        write!(formatter, "{indent}")
+    })
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod archive;
 mod create;
 mod delete;
 mod forget;
@@ -20,8 +21,11 @@ mod r#move;
 mod rename;
 mod set;
 mod track;
+mod unarchive;
 mod untrack;
 
+use std::io::Write as _;
+
 use itertools::Itertools as _;
 use jj_lib::backend::CommitId;
 use jj_lib::op_store::RefTarget;
@@ -31,6 +35,8 @@ use jj_lib::repo::Repo;
 use jj_lib::str_util::StringPattern;
 use jj_lib::view::View;
 
+use self::archive::cmd_bookmark_archive;
+use self::archive::BookmarkArchiveArgs;
 use self::create::cmd_bookmark_create;
 use self::create::BookmarkCreateArgs;
 use self::delete::cmd_bookmark_delete;
@@ -47,6 +53,8 @@ use self::set::cmd_bookmark_set;
 use self::set::BookmarkSetArgs;
 use self::track::cmd_bookmark_track;
 use self::track::BookmarkTrackArgs;
+use self::unarchive::cmd_bookmark_unarchive;
+use self::unarchive::BookmarkUnarchiveArgs;
 use self::untrack::cmd_bookmark_untrack;
 use self::untrack::BookmarkUntrackArgs;
 use crate::cli_util::CommandHelper;
@@ -66,6 +74,7 @@ use crate::ui::Ui;
 ///     https://jj-vcs.github.io/jj/latest/bookmarks
 #[derive(clap::Subcommand, Clone, Debug)]
 pub enum BookmarkCommand {
+    Archive(BookmarkArchiveArgs),
     #[command(visible_alias("c"))]
     Create(BookmarkCreateArgs),
     #[command(visible_alias("d"))]
@@ -82,6 +91,7 @@ pub enum BookmarkCommand {
     Set(BookmarkSetArgs),
     #[command(visible_alias("t"))]
     Track(BookmarkTrackArgs),
+    Unarchive(BookmarkUnarchiveArgs),
     Untrack(BookmarkUntrackArgs),
 }
 
@@ -91,6 +101,7 @@ pub fn cmd_bookmark(
     subcommand: &BookmarkCommand,
 ) -> Result<(), CommandError> {
     match subcommand {
+        BookmarkCommand::Archive(args) => cmd_bookmark_archive(ui, command, args),
         BookmarkCommand::Create(args) => cmd_bookmark_create(ui, command, args),
         BookmarkCommand::Delete(args) => cmd_bookmark_delete(ui, command, args),
         BookmarkCommand::Forget(args) => cmd_bookmark_forget(ui, command, args),
@@ -99,6 +110,7 @@ pub fn cmd_bookmark(
         BookmarkCommand::Rename(args) => cmd_bookmark_rename(ui, command, args),
         BookmarkCommand::Set(args) => cmd_bookmark_set(ui, command, args),
         BookmarkCommand::Track(args) => cmd_bookmark_track(ui, command, args),
+        BookmarkCommand::Unarchive(args) => cmd_bookmark_unarchive(ui, command, args),
         BookmarkCommand::Untrack(args) => cmd_bookmark_untrack(ui, command, args),
     }
 }
@@ -173,6 +185,34 @@ fn find_remote_bookmarks<'a>(
     }
 }
 
+/// Asks the user to confirm the `action` on the `names` matched by wildcard
+/// patterns. Returns `true` without prompting if the patterns are all exact,
+/// only one bookmark matched, or the user cannot be prompted.
+fn confirm_bulk_operation(
+    ui: &Ui,
+    action: &str,
+    all_exact: bool,
+    names: &[String],
+) -> Result<bool, CommandError> {
+    if all_exact || names.len() <= 1 || !Ui::can_prompt() {
+        return Ok(true);
+    }
+    writeln!(
+        ui.stderr(),
+        "The following {} bookmarks will be {action}:",
+        names.len()
+    )?;
+    for name in names {
+        writeln!(ui.stderr(), "  {name}")?;
+    }
+    if ui.prompt_yes_no("Continue?", Some(false))? {
+        Ok(true)
+    } else {
+        writeln!(ui.status(), "Nothing changed.")?;
+        Ok(false)
+    }
+}
+
 /// Whether or not the `bookmark` has any tracked remotes (i.e. is a tracking
 /// local bookmark.)
 fn has_tracked_remote_bookmarks(view: &View, bookmark: &str) -> bool {
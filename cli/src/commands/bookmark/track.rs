@@ -17,6 +17,7 @@ use std::collections::HashMap;
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
 
+use super::confirm_bulk_operation;
 use super::find_remote_bookmarks;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RemoteBookmarkNamePattern;
@@ -68,6 +69,14 @@ pub fn cmd_bookmark_track(
             symbols.push(symbol);
         }
     }
+    let all_exact = args.names.iter().all(RemoteBookmarkNamePattern::is_exact);
+    let names = symbols
+        .iter()
+        .map(|symbol| symbol.to_string())
+        .collect_vec();
+    if !confirm_bulk_operation(ui, "tracked", all_exact, &names)? {
+        return Ok(());
+    }
     let mut tx = workspace_command.start_transaction();
     for &symbol in &symbols {
         tx.repo_mut().track_remote_bookmark(symbol);
@@ -0,0 +1,263 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use futures::StreamExt as _;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::conventional_commit::ConventionalCommit;
+use jj_lib::fileset;
+use jj_lib::fileset::FilesetDiagnostics;
+use jj_lib::fileset::FilesetExpression;
+use jj_lib::matchers::Matcher;
+use jj_lib::repo::Repo;
+use jj_lib::repo_path::RepoPathUiConverter;
+use jj_lib::revset::RevsetIteratorExt as _;
+use pollster::FutureExt as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::print_parse_diagnostics;
+use crate::command_error::CommandError;
+use crate::commit_templater::CommitTemplateLanguage;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Generate release notes from revisions
+///
+/// Lists the revisions grouped by the categories configured in
+/// `changelog.categories`, oldest revision first, as Markdown. Each revision
+/// is put in the first category it matches, and categories without revisions
+/// are omitted. For example, `jj changelog -r v1.0..v1.1` summarizes the
+/// changes between two releases.
+///
+/// A category matches a revision if the revision has one of its
+/// conventional-commit `types` (such as `feat` in `feat(cli): add flag`),
+/// modifies one of its `paths`, or has one of its `trailers`. A category
+/// without any of these matches all revisions.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct ChangelogArgs {
+    /// Which revisions to include
+    #[arg(
+        long,
+        short,
+        required = true,
+        value_name = "REVSETS",
+        add = ArgValueCandidates::new(complete::all_revisions)
+    )]
+    revisions: Vec<RevisionArg>,
+    /// Render each revision using the given template
+    ///
+    /// Defaults to the `templates.changelog_entry` setting. See
+    /// [`jj help -k templates`] for more information.
+    ///
+    /// [`jj help -k templates`]:
+    ///     https://jj-vcs.github.io/jj/latest/templates/
+    #[arg(long, short = 'T', add = ArgValueCandidates::new(complete::template_aliases))]
+    template: Option<String>,
+}
+
+/// Category as configured in `changelog.categories`.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CategoryConfig {
+    title: String,
+    #[serde(default)]
+    types: Vec<String>,
+    #[serde(default)]
+    paths: Vec<String>,
+    #[serde(default)]
+    trailers: Vec<String>,
+    #[serde(default)]
+    breaking: bool,
+}
+
+struct Category {
+    config: CategoryConfig,
+    matcher: Option<Box<dyn Matcher>>,
+    commits: Vec<Commit>,
+}
+
+impl Category {
+    fn matches(&self, repo: &dyn Repo, commit: &Commit) -> Result<bool, CommandError> {
+        let CategoryConfig {
+            types,
+            trailers,
+            breaking,
+            ..
+        } = &self.config;
+        if types.is_empty() && trailers.is_empty() && !breaking && self.matcher.is_none() {
+            return Ok(true);
+        }
+        if let Some(parsed) = ConventionalCommit::parse(commit.description()) {
+            if *breaking && parsed.breaking {
+                return Ok(true);
+            }
+            if types
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(parsed.kind))
+            {
+                return Ok(true);
+            }
+        }
+        if description_trailer_keys(commit.description())
+            .iter()
+            .any(|key| trailers.iter().any(|name| name.eq_ignore_ascii_case(key)))
+        {
+            return Ok(true);
+        }
+        if let Some(matcher) = &self.matcher {
+            let parent_tree = commit.parent_tree(repo)?;
+            let tree = commit.tree()?;
+            let mut diff_stream = parent_tree.diff_stream(&tree, matcher.as_ref());
+            if diff_stream.next().block_on().is_some() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_changelog(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ChangelogArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let settings = workspace_command.settings();
+    let mut categories: Vec<Category> = settings
+        .get::<Vec<CategoryConfig>>("changelog.categories")?
+        .into_iter()
+        .map(|config| -> Result<_, CommandError> {
+            let matcher = if config.paths.is_empty() {
+                None
+            } else {
+                let mut diagnostics = FilesetDiagnostics::new();
+                let expression = FilesetExpression::union_all(
+                    config
+                        .paths
+                        .iter()
+                        .map(|arg| {
+                            fileset::parse(
+                                &mut diagnostics,
+                                arg,
+                                &RepoPathUiConverter::Fs {
+                                    cwd: "".into(),
+                                    base: "".into(),
+                                },
+                            )
+                        })
+                        .try_collect()?,
+                );
+                print_parse_diagnostics(ui, "In `changelog.categories`", &diagnostics)?;
+                Some(expression.to_matcher())
+            };
+            Ok(Category {
+                config,
+                matcher,
+                commits: vec![],
+            })
+        })
+        .try_collect()?;
+
+    let repo = workspace_command.repo();
+    let template = {
+        let language = workspace_command.commit_template_language();
+        let text = match &args.template {
+            Some(value) => value.to_owned(),
+            None => settings.get_string("templates.changelog_entry")?,
+        };
+        workspace_command
+            .parse_template(ui, &language, &text, CommitTemplateLanguage::wrap_commit)?
+            .labeled("changelog")
+    };
+
+    let revset = workspace_command
+        .parse_union_revsets(ui, &args.revisions)?
+        .evaluate()?;
+    // The revisions are iterated from newest to oldest.
+    let commits: Vec<_> = revset.iter().commits(repo.store()).try_collect()?;
+    for commit in commits.into_iter().rev() {
+        for category in &mut categories {
+            if category.matches(repo.as_ref(), &commit)? {
+                category.commits.push(commit);
+                break;
+            }
+        }
+    }
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    let categories = categories
+        .iter()
+        .filter(|category| !category.commits.is_empty());
+    for (i, category) in categories.enumerate() {
+        if i > 0 {
+            writeln!(formatter)?;
+        }
+        formatter.with_label("changelog", |formatter| {
+            writeln!(
+                formatter.labeled("heading"),
+                "### {}",
+                category.config.title
+            )
+        })?;
+        writeln!(formatter)?;
+        for commit in &category.commits {
+            template.format(commit, formatter.as_mut())?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the keys of the trailers in the last paragraph of the description.
+///
+/// Like Git, the last paragraph is only considered to be trailers if all of its
+/// lines are `<key>: <value>` trailers or their continuation lines. The key is
+/// a token of alphanumeric characters and `-`, except for the `BREAKING
+/// CHANGE` key of conventional commits.
+fn description_trailer_keys(description: &str) -> Vec<&str> {
+    let trimmed = description.trim_end();
+    let last_paragraph = match trimmed.rfind("\n\n") {
+        Some(pos) => &trimmed[pos + 2..],
+        // The subject line isn't a trailer.
+        None => "",
+    };
+    let mut keys = vec![];
+    for (i, line) in last_paragraph.lines().enumerate() {
+        if i > 0 && line.starts_with([' ', '\t']) {
+            // Continuation of the previous trailer's value
+            continue;
+        }
+        match parse_trailer_key(line) {
+            Some(key) => keys.push(key),
+            None => return vec![],
+        }
+    }
+    keys
+}
+
+fn parse_trailer_key(line: &str) -> Option<&str> {
+    let (key, value) = line.split_once(": ")?;
+    let is_token = !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    if (is_token || key == "BREAKING CHANGE") && !value.trim().is_empty() {
+        Some(key)
+    } else {
+        None
+    }
+}
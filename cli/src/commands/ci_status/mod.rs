@@ -0,0 +1,45 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod refresh;
+
+use clap::Subcommand;
+use tracing::instrument;
+
+use self::refresh::cmd_ci_status_refresh;
+use self::refresh::CiStatusRefreshArgs;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Manage CI statuses of revisions
+///
+/// CI statuses are fetched from the provider configured by `ci.provider` and
+/// cached in the repo. The cached statuses can be shown by the `ci_status()`
+/// commit template method and queried with the `ci_failed()` revset function.
+#[derive(Subcommand, Clone, Debug)]
+pub(crate) enum CiStatusCommand {
+    Refresh(CiStatusRefreshArgs),
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_ci_status(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &CiStatusCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        CiStatusCommand::Refresh(args) => cmd_ci_status_refresh(ui, command, args),
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use tracing::instrument;
+
+use crate::ci_provider::ci_provider_from_settings;
+use crate::ci_status::CiStatusCache;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::user_error_with_message;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Fetch the CI statuses of revisions from the CI provider
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct CiStatusRefreshArgs {
+    /// The revisions to fetch statuses of
+    ///
+    /// If no revisions are specified, this defaults to the `revsets.ci-status`
+    /// setting.
+    #[arg(
+        long, short,
+        value_name = "REVSETS",
+        add = ArgValueCandidates::new(complete::all_revisions),
+    )]
+    revisions: Vec<RevisionArg>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_ci_status_refresh(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &CiStatusRefreshArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let provider = ci_provider_from_settings(
+        workspace_command.settings(),
+        workspace_command.workspace_root(),
+    )?;
+    let revset_expression = if args.revisions.is_empty() {
+        let revset_string = workspace_command
+            .settings()
+            .get_string("revsets.ci-status")?;
+        workspace_command.parse_revset(ui, &RevisionArg::from(revset_string))?
+    } else {
+        workspace_command.parse_union_revsets(ui, &args.revisions)?
+    };
+    let commits: Vec<Commit> = revset_expression.evaluate_to_commits()?.try_collect()?;
+
+    let template = workspace_command.commit_summary_template();
+    let mut updates = vec![];
+    for commit in &commits {
+        let status = match provider.fetch_status(commit.id()) {
+            Ok(status) => status,
+            Err(err) => {
+                // The provider may not know about commits that haven't been
+                // pushed, so keep going.
+                let mut formatter = ui.stderr_formatter();
+                write!(
+                    formatter.labeled("warning").with_heading("Warning: "),
+                    "Failed to fetch CI status of "
+                )?;
+                template.format(commit, formatter.as_mut())?;
+                writeln!(formatter.labeled("warning"), ": {err}")?;
+                continue;
+            }
+        };
+        updates.push((commit.id().clone(), status));
+        if let Some(mut formatter) = ui.status_formatter() {
+            let status = status.map_or("none", |status| status.as_str());
+            write!(formatter.labeled("ci_status"), "{status}")?;
+            write!(formatter, ": ")?;
+            template.format(commit, formatter.as_mut())?;
+            writeln!(formatter)?;
+        }
+    }
+    CiStatusCache::for_repo(workspace_command.repo_path())
+        .update(updates)
+        .map_err(|err| user_error_with_message("Failed to cache CI statuses", err))?;
+    Ok(())
+}
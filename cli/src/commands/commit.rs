@@ -22,6 +22,8 @@ use crate::cli_util::CommandHelper;
 use crate::command_error::user_error;
 use crate::command_error::CommandError;
 use crate::complete;
+use crate::description_util::check_conventional_commit;
+use crate::description_util::check_edited_conventional_commit;
 use crate::description_util::description_template;
 use crate::description_util::edit_description;
 use crate::description_util::join_message_paragraphs;
@@ -67,6 +69,13 @@ pub(crate) struct CommitArgs {
         value_parser = parse_author
     )]
     author: Option<(String, String)>,
+    /// Do not advance bookmarks pointing to the parent
+    ///
+    /// By default, bookmarks enabled by the
+    /// `experimental-advance-branches.enabled-branches` setting are moved to
+    /// the committed change.
+    #[arg(long)]
+    no_advance: bool,
 }
 
 #[instrument(skip_all)]
@@ -84,7 +93,11 @@ pub(crate) fn cmd_commit(
     let matcher = workspace_command
         .parse_file_patterns(ui, &args.paths)?
         .to_matcher();
-    let advanceable_bookmarks = workspace_command.get_advanceable_bookmarks(commit.parent_ids())?;
+    let advanceable_bookmarks = if args.no_advance {
+        vec![]
+    } else {
+        workspace_command.get_advanceable_bookmarks(commit.parent_ids())?
+    };
     let diff_selector =
         workspace_command.diff_selector(ui, args.tool.as_deref(), args.interactive)?;
     let text_editor = workspace_command.text_editor()?;
@@ -131,14 +144,18 @@ new working-copy commit.
     }
 
     let description = if !args.message_paragraphs.is_empty() {
-        join_message_paragraphs(&args.message_paragraphs)
+        let description = join_message_paragraphs(&args.message_paragraphs);
+        check_conventional_commit(tx.settings(), &description)?;
+        description
     } else {
         if commit_builder.description().is_empty() {
             commit_builder.set_description(tx.settings().get_string("ui.default-description")?);
         }
         let temp_commit = commit_builder.write_hidden()?;
         let template = description_template(ui, &tx, "", &temp_commit)?;
-        edit_description(&text_editor, &template)?
+        let description = edit_description(&text_editor, &template)?;
+        check_edited_conventional_commit(&text_editor, tx.settings(), &description)?;
+        description
     };
     commit_builder.set_description(description);
     let new_commit = commit_builder.write(tx.repo_mut())?;
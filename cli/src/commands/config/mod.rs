@@ -17,6 +17,7 @@ mod get;
 mod list;
 mod path;
 mod set;
+mod theme;
 mod unset;
 
 use std::path::Path;
@@ -36,6 +37,8 @@ use self::path::cmd_config_path;
 use self::path::ConfigPathArgs;
 use self::set::cmd_config_set;
 use self::set::ConfigSetArgs;
+use self::theme::cmd_config_theme;
+use self::theme::ConfigThemeCommand;
 use self::unset::cmd_config_unset;
 use self::unset::ConfigUnsetArgs;
 use crate::cli_util::CommandHelper;
@@ -135,6 +138,8 @@ pub(crate) enum ConfigCommand {
     Path(ConfigPathArgs),
     #[command(visible_alias("s"))]
     Set(ConfigSetArgs),
+    #[command(subcommand)]
+    Theme(ConfigThemeCommand),
     #[command(visible_alias("u"))]
     Unset(ConfigUnsetArgs),
 }
@@ -151,6 +156,7 @@ pub(crate) fn cmd_config(
         ConfigCommand::List(args) => cmd_config_list(ui, command, args),
         ConfigCommand::Path(args) => cmd_config_path(ui, command, args),
         ConfigCommand::Set(args) => cmd_config_set(ui, command, args),
+        ConfigCommand::Theme(subcommand) => cmd_config_theme(ui, command, subcommand),
         ConfigCommand::Unset(args) => cmd_config_unset(ui, command, args),
     }
 }
@@ -0,0 +1,113 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io;
+use std::io::Write as _;
+
+use jj_lib::config::StackedConfig;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::config::default_config_layers;
+use crate::formatter::Formatter;
+use crate::formatter::FormatterFactory;
+use crate::theme::Background;
+use crate::theme::Theme;
+use crate::theme::BUILTIN_THEMES;
+use crate::ui::Ui;
+
+/// List built-in color themes
+///
+/// Each theme is shown with a preview of a commit rendered in the theme's
+/// colors. Your own `colors` settings aren't applied to the previews.
+#[derive(clap::Args, Clone, Debug)]
+pub struct ConfigThemeListArgs {}
+
+#[instrument(skip_all)]
+pub fn cmd_config_theme_list(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &ConfigThemeListArgs,
+) -> Result<(), CommandError> {
+    let current_theme = command.settings().get_string("ui.theme")?;
+    for theme in BUILTIN_THEMES {
+        {
+            let mut formatter = ui.stdout_formatter();
+            write!(formatter, "{}", theme.name)?;
+            if theme.name == current_theme {
+                write!(formatter, " (current)")?;
+            }
+            writeln!(formatter, ": {}", theme.description)?;
+        }
+        if theme.has_variants() {
+            for background in [Background::Dark, Background::Light] {
+                write!(ui.stdout(), "  {:<6} ", format!("{}:", background.name()))?;
+                write_preview(ui, theme, background)?;
+            }
+        } else {
+            write!(ui.stdout(), "  ")?;
+            write_preview(ui, theme, Background::Dark)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_preview(ui: &Ui, theme: &Theme, background: Background) -> Result<(), CommandError> {
+    if !ui.color() {
+        return Ok(write_sample_commit(ui.stdout_formatter().as_mut())?);
+    }
+    let mut config = StackedConfig::with_defaults();
+    config.extend_layers(default_config_layers());
+    config.add_layer(theme.to_layer(background));
+    let factory = FormatterFactory::color(&config, false)?;
+    let mut formatter = factory.new_formatter(ui.stdout());
+    Ok(write_sample_commit(formatter.as_mut())?)
+}
+
+/// Writes a line resembling `jj log` output for the working-copy commit.
+fn write_sample_commit(formatter: &mut dyn Formatter) -> io::Result<()> {
+    let write_id = |formatter: &mut dyn Formatter, label: &str, prefix: &str, rest: &str| {
+        formatter.with_label(label, |formatter| {
+            write!(formatter.labeled("prefix"), "{prefix}")?;
+            write!(formatter.labeled("rest"), "{rest}")
+        })
+    };
+    formatter.with_label("log", |formatter| {
+        formatter.with_label("node", |formatter| {
+            write!(formatter.labeled("working_copy"), "@")
+        })?;
+        write!(formatter, "  ")?;
+        formatter.with_label("working_copy", |formatter| {
+            write_id(formatter, "change_id", "q", "pvuntsm")?;
+            write!(formatter, " ")?;
+            write!(formatter.labeled("author"), "user@example.com")?;
+            write!(formatter, " ")?;
+            write!(formatter.labeled("timestamp"), "2001-02-03 08:05:07")?;
+            write!(formatter, " ")?;
+            write!(formatter.labeled("bookmarks"), "main")?;
+            write!(formatter, " ")?;
+            write_id(formatter, "commit_id", "2", "30dd059")?;
+            write!(formatter, " ")?;
+            write!(formatter.labeled("empty"), "(empty)")?;
+            write!(formatter, " ")?;
+            formatter.with_label("empty", |formatter| {
+                formatter.with_label("description", |formatter| {
+                    write!(formatter.labeled("placeholder"), "(no description set)")
+                })
+            })
+        })
+    })?;
+    writeln!(formatter)
+}
@@ -0,0 +1,42 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod list;
+
+use self::list::cmd_config_theme_list;
+use self::list::ConfigThemeListArgs;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Manage color themes
+///
+/// The theme is selected by the `ui.theme` config option. Themes may have
+/// variants for dark and light terminal backgrounds, which are chosen by
+/// `ui.theme-background`.
+#[derive(clap::Subcommand, Clone, Debug)]
+pub enum ConfigThemeCommand {
+    #[command(visible_alias("l"))]
+    List(ConfigThemeListArgs),
+}
+
+pub fn cmd_config_theme(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &ConfigThemeCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        ConfigThemeCommand::List(args) => cmd_config_theme_list(ui, command, args),
+    }
+}
@@ -0,0 +1,219 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Debug;
+use std::io::Write as _;
+
+use clap::Subcommand;
+use itertools::Itertools as _;
+use jj_lib::backend::TreeValue;
+use jj_lib::commit::Commit;
+use jj_lib::merge::Merge;
+use jj_lib::object_id::ObjectId as _;
+
+use crate::cli_util::conflict_term_commits;
+use crate::cli_util::conflict_term_label;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum DebugConflictCommand {
+    Show(DebugConflictShowArgs),
+}
+
+/// Show the terms of the conflicts in a revision
+///
+/// Prints the terms of the revision's tree and of each conflicted file,
+/// starting with the first positive term and then alternating negative and
+/// positive terms. If the revision merges conflict-free parents, the commit
+/// each term came from is shown too.
+#[derive(clap::Args, Clone, Debug)]
+pub struct DebugConflictShowArgs {
+    /// The revision to show the conflicts of
+    #[arg(long, short, default_value = "@", value_name = "REVSET")]
+    revision: RevisionArg,
+    /// Only show conflicts in these paths
+    #[arg(value_name = "FILESETS")]
+    paths: Vec<String>,
+    /// Print the conflicts as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+pub fn cmd_debug_conflict(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &DebugConflictCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        DebugConflictCommand::Show(args) => cmd_debug_conflict_show(ui, command, args),
+    }
+}
+
+fn cmd_debug_conflict_show(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &DebugConflictShowArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo();
+    let commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
+    let matcher = workspace_command
+        .parse_file_patterns(ui, &args.paths)?
+        .to_matcher();
+    let tree = commit.tree()?;
+    let tree_ids = tree.as_merge().map(|tree| tree.id().clone());
+    let term_commits = conflict_term_commits(repo.as_ref(), &commit)?;
+    let conflicts: Vec<_> = tree
+        .conflicts()
+        .filter(|(path, _)| matcher.matches(path))
+        .map(|(path, value)| value.map(|value| (path, value)))
+        .try_collect()?;
+
+    if args.json {
+        let term_commit_json =
+            |index: usize, num_terms: usize| match term_commit(&term_commits, index, num_terms) {
+                Some(commit) => serde_json::json!({
+                    "commit_id": commit.id().hex(),
+                    "change_id": commit.change_id().reverse_hex(),
+                }),
+                None => serde_json::Value::Null,
+            };
+        let tree_terms = tree_ids
+            .iter()
+            .enumerate()
+            .map(|(index, id)| {
+                serde_json::json!({
+                    "sign": term_sign(index),
+                    "tree_id": id.hex(),
+                    "commit": term_commit_json(index, tree_ids.as_slice().len()),
+                })
+            })
+            .collect_vec();
+        let conflicts_json = conflicts
+            .iter()
+            .map(|(path, value)| {
+                let terms = value
+                    .iter()
+                    .enumerate()
+                    .map(|(index, term)| {
+                        let mut json = term_value_json(term.as_ref());
+                        json["sign"] = term_sign(index).into();
+                        json["commit"] = term_commit_json(index, value.as_slice().len());
+                        json
+                    })
+                    .collect_vec();
+                serde_json::json!({
+                    "path": path.as_internal_file_string(),
+                    "num_sides": value.num_sides(),
+                    "terms": terms,
+                })
+            })
+            .collect_vec();
+        let value = serde_json::json!({
+            "commit_id": commit.id().hex(),
+            "tree": tree_terms,
+            "conflicts": conflicts_json,
+        });
+        writeln!(ui.stdout(), "{value}")?;
+        return Ok(());
+    }
+
+    let label = |index: usize, num_terms: usize| match term_commit(&term_commits, index, num_terms)
+    {
+        Some(commit) => format!(" ({})", conflict_term_label(commit)),
+        None => String::new(),
+    };
+    let mut stdout = ui.stdout();
+    writeln!(stdout, "Tree: {} sides", tree_ids.num_sides())?;
+    for (index, id) in tree_ids.iter().enumerate() {
+        writeln!(
+            stdout,
+            "  {} tree {}{}",
+            term_sign(index),
+            id.hex(),
+            label(index, tree_ids.as_slice().len())
+        )?;
+    }
+    for (path, value) in &conflicts {
+        let ui_path = workspace_command.format_file_path(path);
+        writeln!(stdout, "{ui_path}: {}-sided conflict", value.num_sides())?;
+        for (index, term) in value.iter().enumerate() {
+            writeln!(
+                stdout,
+                "  {} {}{}",
+                term_sign(index),
+                describe_term_value(term.as_ref()),
+                label(index, value.as_slice().len())
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the commit the term at `index` came from, if the conflict has as
+/// many terms as there are known commits.
+fn term_commit(
+    term_commits: &Option<Merge<Commit>>,
+    index: usize,
+    num_terms: usize,
+) -> Option<&Commit> {
+    let commits = term_commits.as_ref()?.as_slice();
+    (commits.len() == num_terms).then(|| &commits[index])
+}
+
+/// Terms are stored as alternating positive and negative terms.
+fn term_sign(index: usize) -> &'static str {
+    if index % 2 == 0 {
+        "+"
+    } else {
+        "-"
+    }
+}
+
+fn describe_term_value(value: Option<&TreeValue>) -> String {
+    match value {
+        None => "absent".to_owned(),
+        Some(TreeValue::File { id, executable }) => {
+            if *executable {
+                format!("executable file {}", id.hex())
+            } else {
+                format!("file {}", id.hex())
+            }
+        }
+        Some(TreeValue::Symlink(id)) => format!("symlink {}", id.hex()),
+        Some(TreeValue::Tree(id)) => format!("tree {}", id.hex()),
+        Some(TreeValue::GitSubmodule(id)) => format!("submodule {}", id.hex()),
+        Some(TreeValue::Conflict(id)) => format!("legacy conflict {}", id.hex()),
+    }
+}
+
+fn term_value_json(value: Option<&TreeValue>) -> serde_json::Value {
+    match value {
+        None => serde_json::json!({ "type": "absent" }),
+        Some(TreeValue::File { id, executable }) => {
+            serde_json::json!({ "type": "file", "id": id.hex(), "executable": executable })
+        }
+        Some(TreeValue::Symlink(id)) => serde_json::json!({ "type": "symlink", "id": id.hex() }),
+        Some(TreeValue::Tree(id)) => serde_json::json!({ "type": "tree", "id": id.hex() }),
+        Some(TreeValue::GitSubmodule(id)) => {
+            serde_json::json!({ "type": "submodule", "id": id.hex() })
+        }
+        Some(TreeValue::Conflict(id)) => {
+            serde_json::json!({ "type": "legacy_conflict", "id": id.hex() })
+        }
+    }
+}
@@ -0,0 +1,362 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::io::Read as _;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use futures::StreamExt as _;
+use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::backend::FileId;
+use jj_lib::backend::Signature;
+use jj_lib::backend::SymlinkId;
+use jj_lib::commit::Commit;
+use jj_lib::conflicts::materialize_merge_result_to_bytes;
+use jj_lib::conflicts::materialize_tree_value;
+use jj_lib::conflicts::MaterializedTreeValue;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::merge::MergedTreeValue;
+use jj_lib::merged_tree::TreeDiffEntry;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPath;
+use jj_lib::repo_path::RepoPathBuf;
+use pollster::FutureExt as _;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// The ref the exported commits are written to.
+const EXPORT_REF: &str = "refs/jj/fast-export";
+
+/// Print commits as a `git fast-import` stream
+///
+/// The stream contains the given commits in topological order, along with the
+/// contents of the files changed by them. Conflicted files are exported with
+/// conflict markers. Parents which aren't exported are omitted, and the
+/// commits are exported with all their files instead.
+///
+/// The commits are written to `refs/jj/fast-export`. Local bookmarks pointing
+/// to exported commits are written to `refs/heads/`.
+///
+/// Use `--export-marks` to save the exported commits, and `--import-marks` to
+/// skip them in later exports.
+#[derive(clap::Args, Clone, Debug)]
+pub struct DebugFastExportArgs {
+    /// Commits to export
+    #[arg(long, short, default_value = "all()", value_name = "REVSETS")]
+    revisions: Vec<RevisionArg>,
+    /// Read the marks of previously exported commits from this file
+    #[arg(long, value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+    import_marks: Option<PathBuf>,
+    /// Write the marks of all exported commits to this file
+    #[arg(long, value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+    export_marks: Option<PathBuf>,
+}
+
+pub fn cmd_debug_fast_export(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &DebugFastExportArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo();
+    let mut marks = match &args.import_marks {
+        Some(path) => read_marks(path)?,
+        None => HashMap::new(),
+    };
+    let mut commits: Vec<_> = workspace_command
+        .parse_union_revsets(ui, &args.revisions)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    commits.reverse();
+
+    let mut stdout = ui.stdout();
+    let mut exporter = FastExporter {
+        workspace_command: &workspace_command,
+        out: io::BufWriter::new(&mut stdout),
+        next_mark: marks.values().max().map_or(1, |mark| mark + 1),
+        file_marks: HashMap::new(),
+        symlink_marks: HashMap::new(),
+    };
+    for commit in &commits {
+        if commit.id() == repo.store().root_commit_id() || marks.contains_key(commit.id()) {
+            continue;
+        }
+        let mark = exporter.write_commit(commit, &marks)?;
+        marks.insert(commit.id().clone(), mark);
+    }
+    for (name, target) in repo.view().local_bookmarks() {
+        let Some(mark) = target.as_normal().and_then(|id| marks.get(id)) else {
+            continue;
+        };
+        writeln!(exporter.out, "reset refs/heads/{name}")?;
+        writeln!(exporter.out, "from :{mark}")?;
+        writeln!(exporter.out)?;
+    }
+    exporter.out.flush()?;
+    drop(exporter);
+
+    if let Some(path) = &args.export_marks {
+        let content: String = marks
+            .iter()
+            .sorted_by_key(|(_, mark)| **mark)
+            .map(|(id, mark)| format!(":{mark} {}\n", id.hex()))
+            .collect();
+        fs::write(path, content).map_err(|err| {
+            user_error_with_message(format!("Failed to write marks to {}", path.display()), err)
+        })?;
+    }
+    Ok(())
+}
+
+/// Reads the marks file written by `--export-marks`.
+fn read_marks(path: &Path) -> Result<HashMap<CommitId, u64>, CommandError> {
+    let content = fs::read_to_string(path).map_err(|err| {
+        user_error_with_message(format!("Failed to read marks from {}", path.display()), err)
+    })?;
+    content
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            line.strip_prefix(':')
+                .and_then(|line| line.split_once(' '))
+                .and_then(|(mark, id)| Some((CommitId::try_from_hex(id).ok()?, mark.parse().ok()?)))
+                .ok_or_else(|| user_error(format!("Invalid line in marks file: {line}")))
+        })
+        .collect()
+}
+
+struct FastExporter<'a, W> {
+    workspace_command: &'a WorkspaceCommandHelper,
+    out: W,
+    next_mark: u64,
+    file_marks: HashMap<FileId, u64>,
+    symlink_marks: HashMap<SymlinkId, u64>,
+}
+
+impl<W: Write> FastExporter<'_, W> {
+    fn new_mark(&mut self) -> u64 {
+        let mark = self.next_mark;
+        self.next_mark += 1;
+        mark
+    }
+
+    /// Writes the commit and the blobs it refers to. Returns the mark of the
+    /// commit.
+    fn write_commit(
+        &mut self,
+        commit: &Commit,
+        marks: &HashMap<CommitId, u64>,
+    ) -> Result<u64, CommandError> {
+        let author = format_signature(commit.author()).ok_or_else(|| {
+            user_error(format!(
+                "Cannot export commit {} with invalid author name or email",
+                commit.id().hex()
+            ))
+        })?;
+        let committer = format_signature(commit.committer()).ok_or_else(|| {
+            user_error(format!(
+                "Cannot export commit {} with invalid committer name or email",
+                commit.id().hex()
+            ))
+        })?;
+        let store = self.workspace_command.repo().store();
+        let parent_marks = commit
+            .parent_ids()
+            .iter()
+            .filter_map(|id| Some((id, *marks.get(id)?)))
+            .collect_vec();
+        let base_tree = match parent_marks.first() {
+            Some((id, _)) => store.get_commit(id)?.tree()?,
+            None => store.get_root_tree(&store.empty_merged_tree_id())?,
+        };
+        let tree = commit.tree()?;
+
+        let mut deletions = vec![];
+        let mut modifications = vec![];
+        let mut diff_stream = base_tree.diff_stream(&tree, &EverythingMatcher);
+        while let Some(TreeDiffEntry { path, values }) = diff_stream.next().block_on() {
+            let (_before, after) = values?;
+            if after.is_absent() {
+                deletions.push(path);
+            } else {
+                let (mode, data_ref) = self.write_blob(&path, after)?;
+                modifications.push((path, mode, data_ref));
+            }
+        }
+
+        if parent_marks.is_empty() {
+            // Otherwise, the current tip of the ref would become the parent.
+            writeln!(self.out, "reset {EXPORT_REF}")?;
+        }
+        let mark = self.new_mark();
+        writeln!(self.out, "commit {EXPORT_REF}")?;
+        writeln!(self.out, "mark :{mark}")?;
+        writeln!(self.out, "original-oid {}", commit.id().hex())?;
+        writeln!(self.out, "author {author}")?;
+        writeln!(self.out, "committer {committer}")?;
+        self.write_data(commit.description().as_bytes())?;
+        for (i, (_, parent_mark)) in parent_marks.iter().enumerate() {
+            let command = if i == 0 { "from" } else { "merge" };
+            writeln!(self.out, "{command} :{parent_mark}")?;
+        }
+        // Deletions go first so a deleted directory won't remove a file added
+        // in its place.
+        for path in &deletions {
+            writeln!(self.out, "D {}", quote_path(path))?;
+        }
+        for (path, mode, data_ref) in &modifications {
+            writeln!(self.out, "M {mode} {data_ref} {}", quote_path(path))?;
+        }
+        writeln!(self.out)?;
+        Ok(mark)
+    }
+
+    /// Writes the contents of the value as a blob if needed. Returns the mode
+    /// and the data reference of the entry.
+    fn write_blob(
+        &mut self,
+        path: &RepoPath,
+        value: MergedTreeValue,
+    ) -> Result<(&'static str, String), CommandError> {
+        let store = self.workspace_command.repo().store();
+        let materialized = materialize_tree_value(store, path, value).block_on()?;
+        let (mode, mark) = match materialized {
+            MaterializedTreeValue::Absent => unreachable!("absent values should be excluded"),
+            MaterializedTreeValue::AccessDenied(err) => {
+                return Err(user_error_with_message(
+                    format!("Failed to read {path:?}"),
+                    err,
+                ));
+            }
+            MaterializedTreeValue::File {
+                id,
+                executable,
+                mut reader,
+            } => {
+                let mark = match self.file_marks.get(&id) {
+                    Some(mark) => *mark,
+                    None => {
+                        let mut contents = vec![];
+                        reader.read_to_end(&mut contents)?;
+                        let mark = self.write_blob_data(&contents)?;
+                        self.file_marks.insert(id, mark);
+                        mark
+                    }
+                };
+                (file_mode(executable), mark)
+            }
+            MaterializedTreeValue::Symlink { id, target } => {
+                let mark = match self.symlink_marks.get(&id) {
+                    Some(mark) => *mark,
+                    None => {
+                        let mark = self.write_blob_data(target.as_bytes())?;
+                        self.symlink_marks.insert(id, mark);
+                        mark
+                    }
+                };
+                ("120000", mark)
+            }
+            MaterializedTreeValue::FileConflict {
+                contents,
+                executable,
+                ..
+            } => {
+                let contents = materialize_merge_result_to_bytes(
+                    &contents,
+                    self.workspace_command.env().conflict_marker_style_for(path),
+                );
+                (file_mode(executable), self.write_blob_data(&contents)?)
+            }
+            MaterializedTreeValue::OtherConflict { id } => {
+                ("100644", self.write_blob_data(id.describe().as_bytes())?)
+            }
+            MaterializedTreeValue::GitSubmodule(id) => return Ok(("160000", id.hex())),
+            MaterializedTreeValue::Tree(_) => unreachable!("entries should not contain trees"),
+        };
+        Ok((mode, format!(":{mark}")))
+    }
+
+    fn write_blob_data(&mut self, data: &[u8]) -> io::Result<u64> {
+        let mark = self.new_mark();
+        writeln!(self.out, "blob")?;
+        writeln!(self.out, "mark :{mark}")?;
+        self.write_data(data)?;
+        Ok(mark)
+    }
+
+    fn write_data(&mut self, data: &[u8]) -> io::Result<()> {
+        writeln!(self.out, "data {}", data.len())?;
+        self.out.write_all(data)?;
+        writeln!(self.out)
+    }
+}
+
+fn file_mode(executable: bool) -> &'static str {
+    if executable {
+        "100755"
+    } else {
+        "100644"
+    }
+}
+
+/// Formats the signature as a fast-import identity. Returns `None` if the name
+/// or email contains characters which would corrupt the stream.
+fn format_signature(signature: &Signature) -> Option<String> {
+    let is_invalid = |c| matches!(c, '<' | '>' | '\n');
+    if signature.name.contains(is_invalid) || signature.email.contains(is_invalid) {
+        return None;
+    }
+    let timestamp = &signature.timestamp;
+    let seconds = timestamp.timestamp.0.div_euclid(1000);
+    let sign = if timestamp.tz_offset < 0 { '-' } else { '+' };
+    let offset = timestamp.tz_offset.abs();
+    let time = format!("{seconds} {sign}{:02}{:02}", offset / 60, offset % 60);
+    if signature.name.is_empty() {
+        Some(format!("<{}> {time}", signature.email))
+    } else {
+        Some(format!("{} <{}> {time}", signature.name, signature.email))
+    }
+}
+
+/// Quotes the path if it can't be written as is.
+fn quote_path(path: &RepoPathBuf) -> String {
+    let path = path.as_internal_file_string();
+    if !path.starts_with('"') && !path.contains('\n') {
+        return path.to_owned();
+    }
+    let mut quoted = String::from('"');
+    for c in path.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
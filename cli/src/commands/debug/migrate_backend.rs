@@ -0,0 +1,719 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::io::Read as _;
+use std::io::Write as _;
+use std::path::Path;
+use std::slice;
+
+use clap::ValueEnum;
+use itertools::Itertools as _;
+use jj_lib::backend::Backend;
+use jj_lib::backend::BackendError;
+use jj_lib::backend::Commit;
+use jj_lib::backend::CommitId;
+use jj_lib::backend::ConflictId;
+use jj_lib::backend::ConflictTerm;
+use jj_lib::backend::FileId;
+use jj_lib::backend::MergedTreeId;
+use jj_lib::backend::SymlinkId;
+use jj_lib::backend::TreeId;
+use jj_lib::backend::TreeValue;
+use jj_lib::dag_walk;
+use jj_lib::default_index::DefaultIndexStore;
+use jj_lib::file_util;
+use jj_lib::git;
+use jj_lib::git_backend::GitBackend;
+use jj_lib::local_backend::LocalBackend;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::op_store::OperationId;
+use jj_lib::op_store::RefTarget;
+use jj_lib::op_store::View;
+use jj_lib::op_walk;
+use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPath;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::tree_diff_cache::TreeDiffCache;
+use pollster::FutureExt as _;
+use tempfile::NamedTempFile;
+
+use crate::ci_status::CiStatusCache;
+use crate::cli_util::CommandHelper;
+use crate::command_error::internal_error;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::command_error::user_error_with_message;
+use crate::command_error::CommandError;
+use crate::progress::counter_progress;
+use crate::test_results::TestResults;
+use crate::ui::Ui;
+
+/// Copy all objects of the repo into a different commit backend
+///
+/// All commits reachable from the operation log are copied into a new store
+/// backed by the given backend. Change ids, bookmarks, and the operation log
+/// are preserved, but commit ids, operation ids, and commit signatures are not.
+///
+/// Copied commits are recorded in `.jj/repo/migrate_backend`, so an
+/// interrupted migration can be resumed by running this command again. The
+/// same goes for a migration interrupted while the new store is moved into
+/// place. The old store is kept in `.jj/repo/store.old`.
+///
+/// The repo must have only one workspace.
+#[derive(clap::Args, Clone, Debug)]
+pub struct DebugMigrateBackendArgs {
+    /// The backend to migrate to
+    #[arg(long, value_enum)]
+    to: MigrationTarget,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+enum MigrationTarget {
+    /// The native backend (also known as the "local" backend)
+    Native,
+    /// A Git repo stored inside the `.jj` directory
+    Git,
+}
+
+impl MigrationTarget {
+    fn backend_name(self) -> &'static str {
+        match self {
+            MigrationTarget::Native => LocalBackend::name(),
+            MigrationTarget::Git => GitBackend::name(),
+        }
+    }
+}
+
+pub fn cmd_debug_migrate_backend(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &DebugMigrateBackendArgs,
+) -> Result<(), CommandError> {
+    let repo_path = command.workspace_loader()?.repo_path().to_owned();
+    let store_path = repo_path.join("store");
+    let old_store_path = repo_path.join("store.old");
+    let migration_path = repo_path.join("migrate_backend");
+    let target_name = args.to.backend_name();
+
+    // The repo can't be loaded if the stores were only partially swapped, so
+    // finish an interrupted swap first.
+    if let Some((old_head_id, new_head_id)) = read_swap_marker(&migration_path)? {
+        let new_store_path = migration_path.join("store");
+        let type_path = if new_store_path.exists() {
+            new_store_path.join("type")
+        } else {
+            store_path.join("type")
+        };
+        let name = fs::read_to_string(&type_path).map_err(internal_error)?;
+        if name != target_name {
+            return Err(user_error_with_hint(
+                format!("A migration to the {name} backend is in progress"),
+                format!("Run this command with `--to {name}` to finish it."),
+            ));
+        }
+        writeln!(
+            ui.status(),
+            "Finishing the interrupted migration to the {target_name} backend."
+        )?;
+        swap_stores(command, &repo_path, &old_head_id, &new_head_id)?;
+        writeln!(
+            ui.hint_default(),
+            "The old store was moved to {}.",
+            file_util::relative_path(command.cwd(), &old_store_path).display()
+        )?;
+        return Ok(());
+    }
+
+    // Snapshot the working copy so the changes in it will be migrated.
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo().clone();
+    let source = command
+        .store_factories()
+        .load_backend(command.settings(), &store_path)
+        .map_err(internal_error)?;
+    if source.name() == target_name {
+        return Err(user_error(format!(
+            "The repo already uses the {target_name} backend"
+        )));
+    }
+    if repo.view().wc_commit_ids().len() > 1 {
+        return Err(user_error_with_hint(
+            "Cannot migrate a repo with multiple workspaces",
+            "Forget the other workspaces by `jj workspace forget` first.",
+        ));
+    }
+    if old_store_path.symlink_metadata().is_ok() {
+        return Err(user_error_with_hint(
+            format!(
+                "{} already exists",
+                file_util::relative_path(command.cwd(), &old_store_path).display()
+            ),
+            "Remove the backup of the previous migration first.",
+        ));
+    }
+
+    let target = load_or_init_target(command, &migration_path, args.to)?;
+    let commit_map_path = migration_path.join("commit_map");
+    let mut copier = ObjectCopier::new(source.as_ref(), target.as_ref());
+    copier.commits = read_commit_map(&commit_map_path)?;
+    if !copier.commits.is_empty() {
+        writeln!(
+            ui.status(),
+            "Resuming migration with {} commits already copied.",
+            copier.commits.len()
+        )?;
+    }
+
+    // Collect the operations parents first, along with the commits their views
+    // refer to.
+    let op_store = repo.op_store();
+    let mut ops: Vec<_> =
+        op_walk::walk_ancestors(slice::from_ref(repo.operation())).try_collect()?;
+    ops.reverse();
+    let mut views = HashMap::new();
+    for op in &ops {
+        let view = op_store.read_view(op.view_id())?;
+        views.insert(op.view_id().clone(), view);
+    }
+    let start_ids = views
+        .values()
+        .flat_map(view_commit_ids)
+        .unique()
+        .cloned()
+        .collect_vec();
+
+    let commits = dag_walk::topo_order_forward_ok(
+        start_ids
+            .into_iter()
+            .map(|id| Ok::<_, CommandError>(repo.store().get_commit(&id)?)),
+        |commit| commit.id().clone(),
+        |commit| {
+            if copier.commits.contains_key(commit.id()) {
+                return vec![];
+            }
+            let mut neighbors = commit
+                .parent_ids()
+                .iter()
+                .map(|id| Ok(repo.store().get_commit(id)?))
+                .collect_vec();
+            // Predecessors may have been garbage collected. They are dropped
+            // from the copied commits.
+            for id in &commit.store_commit().predecessors {
+                match repo.store().get_commit(id) {
+                    Ok(predecessor) => neighbors.push(Ok(predecessor)),
+                    Err(BackendError::ObjectNotFound { .. }) => {}
+                    Err(err) => neighbors.push(Err(err.into())),
+                }
+            }
+            neighbors
+        },
+    )?;
+    let commits = commits
+        .into_iter()
+        .filter(|commit| !copier.commits.contains_key(commit.id()))
+        .collect_vec();
+
+    let mut commit_map_file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&commit_map_path)
+        .map_err(|err| user_error_with_message("Failed to open the commit map", err))?;
+    let mut progress = counter_progress(ui, "Copying commits", commits.len());
+    for (i, commit) in commits.iter().enumerate() {
+        if let Some(progress) = &mut progress {
+            progress(i);
+        }
+        let new_id = copier.copy_commit(commit.store_commit())?;
+        writeln!(commit_map_file, "{} {}", commit.id().hex(), new_id.hex())
+            .map_err(|err| user_error_with_message("Failed to write the commit map", err))?;
+        copier.commits.insert(commit.id().clone(), new_id);
+    }
+    drop(progress);
+    drop(commit_map_file);
+
+    // Rewrite the operation log to refer to the copied commits. The root
+    // operation doesn't have to be rewritten since its view is derived from the
+    // root commit of the store.
+    let keep_git_refs = args.to == MigrationTarget::Git;
+    let mut op_map = HashMap::new();
+    for op in &ops {
+        if op.id() == op_store.root_operation_id() {
+            op_map.insert(op.id().clone(), op.id().clone());
+            continue;
+        }
+        let view = copier.map_view(&views[op.view_id()], keep_git_refs);
+        let mut new_op = op.store_operation().clone();
+        new_op.view_id = op_store.write_view(&view)?;
+        new_op.parents = new_op.parents.iter().map(|id| op_map[id].clone()).collect();
+        let new_op_id = op_store.write_operation(&new_op)?;
+        op_map.insert(op.id().clone(), new_op_id);
+    }
+    let old_head_id = repo.op_id().clone();
+    let new_head_id = op_map[&old_head_id].clone();
+
+    if repo.op_heads_store().get_op_heads()? != [old_head_id.clone()] {
+        return Err(user_error(
+            "Concurrent operation detected. Run this command again to resume the migration.",
+        ));
+    }
+    let num_commits = copier.commits.len();
+    drop(copier);
+    drop(source);
+    drop(target);
+    drop(repo);
+    drop(workspace_command);
+
+    write_swap_marker(&migration_path, &old_head_id, &new_head_id)?;
+    swap_stores(command, &repo_path, &old_head_id, &new_head_id)?;
+
+    writeln!(
+        ui.status(),
+        "Migrated {num_commits} commits and {num_ops} operations to the {target_name} backend.",
+        num_ops = ops.len()
+    )?;
+    writeln!(
+        ui.hint_default(),
+        "The old store was moved to {}.",
+        file_util::relative_path(command.cwd(), &old_store_path).display()
+    )?;
+    Ok(())
+}
+
+/// Moves the new store into place, and makes the rewritten operations the
+/// current ones.
+///
+/// Steps which were already done by an interrupted run are skipped. The
+/// migration state, including the marker written by [`write_swap_marker()`],
+/// is removed last.
+fn swap_stores(
+    command: &CommandHelper,
+    repo_path: &Path,
+    old_head_id: &OperationId,
+    new_head_id: &OperationId,
+) -> Result<(), CommandError> {
+    let store_path = repo_path.join("store");
+    let old_store_path = repo_path.join("store.old");
+    let migration_path = repo_path.join("migrate_backend");
+    let new_store_path = migration_path.join("store");
+    if new_store_path.exists() {
+        if !old_store_path.exists() {
+            fs::rename(&store_path, &old_store_path)
+                .map_err(|err| user_error_with_message("Failed to move the old store", err))?;
+        }
+        fs::rename(&new_store_path, &store_path)
+            .map_err(|err| user_error_with_message("Failed to move the new store", err))?;
+    }
+
+    let mut workspace = command.load_workspace()?;
+    let repo_loader = workspace.repo_loader();
+    let op_heads_store = repo_loader.op_heads_store();
+    if op_heads_store.get_op_heads()?.contains(old_head_id) {
+        op_heads_store.update_op_heads(slice::from_ref(old_head_id), new_head_id)?;
+    }
+
+    // The index, cached tree diffs, test results, and CI statuses refer to the
+    // old commits and trees.
+    let index_store = repo_loader.index_store();
+    if let Some(default_index_store) = index_store.as_any().downcast_ref::<DefaultIndexStore>() {
+        default_index_store.reinit().map_err(internal_error)?;
+    }
+    TreeDiffCache::new(repo_path.join("tree_diff_cache"), 0)
+        .clear()
+        .map_err(internal_error)?;
+    TestResults::for_repo(repo_path)
+        .clear()
+        .map_err(internal_error)?;
+    CiStatusCache::for_repo(repo_path)
+        .clear()
+        .map_err(internal_error)?;
+
+    // The working-copy state refers to trees in the old store.
+    let new_head_op = repo_loader.load_operation(new_head_id)?;
+    let new_repo = repo_loader.load_at(&new_head_op)?;
+    if let Some(wc_commit_id) = new_repo.view().get_wc_commit_id(workspace.workspace_id()) {
+        let wc_commit = new_repo.store().get_commit(wc_commit_id)?;
+        let mut locked_ws = workspace.start_working_copy_mutation()?;
+        locked_ws.locked_wc().recover(&wc_commit)?;
+        locked_ws.finish(new_head_id.clone())?;
+    }
+
+    fs::remove_dir_all(&migration_path)
+        .map_err(|err| user_error_with_message("Failed to remove the migration state", err))?;
+    Ok(())
+}
+
+/// Records the head operations before and after the migration, so that the
+/// stores can be swapped by [`swap_stores()`] again if it's interrupted.
+fn write_swap_marker(
+    migration_path: &Path,
+    old_head_id: &OperationId,
+    new_head_id: &OperationId,
+) -> Result<(), CommandError> {
+    let write = || -> io::Result<()> {
+        let mut temp_file = NamedTempFile::new_in(migration_path)?;
+        writeln!(
+            temp_file.as_file_mut(),
+            "{} {}",
+            old_head_id.hex(),
+            new_head_id.hex()
+        )?;
+        temp_file
+            .persist(migration_path.join("swap"))
+            .map_err(|err| err.error)?;
+        Ok(())
+    };
+    write().map_err(|err| user_error_with_message("Failed to write the migration state", err))
+}
+
+/// Reads the head operations recorded by [`write_swap_marker()`].
+fn read_swap_marker(
+    migration_path: &Path,
+) -> Result<Option<(OperationId, OperationId)>, CommandError> {
+    let content = match fs::read_to_string(migration_path.join("swap")) {
+        Ok(content) => content,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(user_error_with_message(
+                "Failed to read the migration state",
+                err,
+            ))
+        }
+    };
+    let (old, new) = content
+        .trim_end()
+        .split_once(' ')
+        .ok_or_else(|| user_error("The migration state is corrupted"))?;
+    let parse = |hex| {
+        OperationId::try_from_hex(hex).map_err(|_| user_error("The migration state is corrupted"))
+    };
+    Ok(Some((parse(old)?, parse(new)?)))
+}
+
+/// Loads the store of an interrupted migration, or creates a new one.
+fn load_or_init_target(
+    command: &CommandHelper,
+    migration_path: &Path,
+    target: MigrationTarget,
+) -> Result<Box<dyn Backend>, CommandError> {
+    let settings = command.settings();
+    let store_path = migration_path.join("store");
+    let type_path = store_path.join("type");
+    match fs::read_to_string(&type_path) {
+        Ok(name) if name == target.backend_name() => {
+            return match target {
+                MigrationTarget::Native => Ok(Box::new(LocalBackend::load(&store_path))),
+                MigrationTarget::Git => Ok(Box::new(
+                    GitBackend::load(settings, &store_path).map_err(internal_error)?,
+                )),
+            };
+        }
+        Ok(name) => {
+            return Err(user_error_with_hint(
+                format!("A migration to the {name} backend is in progress"),
+                format!(
+                    "Resume it, or remove {} to start over.",
+                    file_util::relative_path(command.cwd(), migration_path).display()
+                ),
+            ));
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(internal_error(err)),
+    }
+
+    // The store may have been partially initialized by an interrupted run.
+    match fs::remove_dir_all(&store_path) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(internal_error(err)),
+    }
+    fs::create_dir_all(&store_path)
+        .map_err(|err| user_error_with_message("Failed to create the new store", err))?;
+    let backend: Box<dyn Backend> = match target {
+        MigrationTarget::Native => Box::new(LocalBackend::init(&store_path)),
+        MigrationTarget::Git => {
+            Box::new(GitBackend::init_internal(settings, &store_path).map_err(internal_error)?)
+        }
+    };
+    fs::write(&type_path, backend.name())
+        .map_err(|err| user_error_with_message("Failed to create the new store", err))?;
+    Ok(backend)
+}
+
+/// Reads the commits copied by an interrupted migration.
+fn read_commit_map(path: &Path) -> Result<HashMap<CommitId, CommitId>, CommandError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(err) => {
+            return Err(user_error_with_message(
+                "Failed to read the commit map",
+                err,
+            ))
+        }
+    };
+    // The last line may be incomplete if the migration was interrupted while
+    // writing it.
+    let map = content
+        .split_terminator('\n')
+        .filter_map(|line| {
+            let (old, new) = line.split_once(' ')?;
+            Some((
+                CommitId::try_from_hex(old).ok()?,
+                CommitId::try_from_hex(new).ok()?,
+            ))
+        })
+        .collect();
+    Ok(map)
+}
+
+fn view_commit_ids(view: &View) -> impl Iterator<Item = &CommitId> {
+    let ref_targets = itertools::chain!(
+        view.local_bookmarks.values(),
+        view.tags.values(),
+        view.remote_views
+            .values()
+            .flat_map(|remote_view| remote_view.bookmarks.values())
+            .map(|remote_ref| &remote_ref.target),
+        view.git_refs.values(),
+        [&view.git_head],
+    );
+    itertools::chain!(
+        &view.head_ids,
+        view.wc_commit_ids.values(),
+        ref_targets.flat_map(|target| target.added_ids().chain(target.removed_ids())),
+    )
+}
+
+/// Copies objects from one backend to another, remembering the ids of the
+/// copied objects.
+struct ObjectCopier<'a> {
+    source: &'a dyn Backend,
+    target: &'a dyn Backend,
+    commits: HashMap<CommitId, CommitId>,
+    trees: HashMap<TreeId, TreeId>,
+    files: HashMap<FileId, FileId>,
+    symlinks: HashMap<SymlinkId, SymlinkId>,
+    conflicts: HashMap<ConflictId, ConflictId>,
+}
+
+impl<'a> ObjectCopier<'a> {
+    fn new(source: &'a dyn Backend, target: &'a dyn Backend) -> Self {
+        ObjectCopier {
+            source,
+            target,
+            commits: HashMap::new(),
+            trees: HashMap::from([(
+                source.empty_tree_id().clone(),
+                target.empty_tree_id().clone(),
+            )]),
+            files: HashMap::new(),
+            symlinks: HashMap::new(),
+            conflicts: HashMap::new(),
+        }
+    }
+
+    /// Copies the commit, whose parents and existing predecessors must have
+    /// been copied already.
+    fn copy_commit(&mut self, commit: &Commit) -> Result<CommitId, CommandError> {
+        if commit.parents.is_empty() {
+            return Ok(self.target.root_commit_id().clone());
+        }
+        let root_tree = match &commit.root_tree {
+            MergedTreeId::Legacy(id) => MergedTreeId::Legacy(self.copy_tree(RepoPath::root(), id)?),
+            MergedTreeId::Merge(ids) => {
+                MergedTreeId::Merge(ids.try_map(|id| self.copy_tree(RepoPath::root(), id))?)
+            }
+        };
+        let new_commit = Commit {
+            parents: commit
+                .parents
+                .iter()
+                .map(|id| self.commits[id].clone())
+                .collect(),
+            predecessors: commit
+                .predecessors
+                .iter()
+                .filter_map(|id| self.commits.get(id).cloned())
+                .collect(),
+            root_tree,
+            change_id: commit.change_id.clone(),
+            description: commit.description.clone(),
+            author: commit.author.clone(),
+            committer: commit.committer.clone(),
+            duplicated_from: commit.duplicated_from.clone(),
+            // The sources are only meaningful if all of them were migrated.
+            conflict_sources: commit
+                .conflict_sources
+                .iter()
+                .map(|id| self.commits.get(id).cloned())
+                .collect::<Option<_>>()
+                .unwrap_or_default(),
+            // Signatures are made over the backend-specific commit data.
+            secure_sig: None,
+        };
+        let (id, _) = self.target.write_commit(new_commit, None).block_on()?;
+        Ok(id)
+    }
+
+    fn copy_tree(&mut self, dir: &RepoPath, id: &TreeId) -> Result<TreeId, CommandError> {
+        if let Some(new_id) = self.trees.get(id) {
+            return Ok(new_id.clone());
+        }
+        let tree = self.source.read_tree(dir, id).block_on()?;
+        let mut new_tree = jj_lib::backend::Tree::default();
+        for entry in tree.entries() {
+            let path = dir.join(entry.name());
+            let value = self.copy_tree_value(&path, entry.value())?;
+            new_tree.set(entry.name().to_owned(), value);
+        }
+        let new_id = self.target.write_tree(dir, &new_tree).block_on()?;
+        self.trees.insert(id.clone(), new_id.clone());
+        Ok(new_id)
+    }
+
+    fn copy_tree_value(
+        &mut self,
+        path: &RepoPathBuf,
+        value: &TreeValue,
+    ) -> Result<TreeValue, CommandError> {
+        let new_value = match value {
+            TreeValue::File { id, executable } => TreeValue::File {
+                id: self.copy_file(path, id)?,
+                executable: *executable,
+            },
+            TreeValue::Symlink(id) => TreeValue::Symlink(self.copy_symlink(path, id)?),
+            TreeValue::Tree(id) => TreeValue::Tree(self.copy_tree(path, id)?),
+            TreeValue::GitSubmodule(id) => {
+                if self.target.name() != GitBackend::name() {
+                    return Err(user_error(format!(
+                        "Cannot migrate Git submodule at {path:?} to the {} backend",
+                        self.target.name()
+                    )));
+                }
+                TreeValue::GitSubmodule(id.clone())
+            }
+            TreeValue::Conflict(id) => TreeValue::Conflict(self.copy_conflict(path, id)?),
+        };
+        Ok(new_value)
+    }
+
+    fn copy_file(&mut self, path: &RepoPath, id: &FileId) -> Result<FileId, CommandError> {
+        if let Some(new_id) = self.files.get(id) {
+            return Ok(new_id.clone());
+        }
+        let mut contents = vec![];
+        self.source
+            .read_file(path, id)
+            .block_on()?
+            .read_to_end(&mut contents)
+            .map_err(|err| BackendError::ReadObject {
+                object_type: "file".to_owned(),
+                hash: id.hex(),
+                source: err.into(),
+            })?;
+        let new_id = self
+            .target
+            .write_file(path, &mut contents.as_slice())
+            .block_on()?;
+        self.files.insert(id.clone(), new_id.clone());
+        Ok(new_id)
+    }
+
+    fn copy_symlink(&mut self, path: &RepoPath, id: &SymlinkId) -> Result<SymlinkId, CommandError> {
+        if let Some(new_id) = self.symlinks.get(id) {
+            return Ok(new_id.clone());
+        }
+        let target = self.source.read_symlink(path, id).block_on()?;
+        let new_id = self.target.write_symlink(path, &target).block_on()?;
+        self.symlinks.insert(id.clone(), new_id.clone());
+        Ok(new_id)
+    }
+
+    fn copy_conflict(
+        &mut self,
+        path: &RepoPathBuf,
+        id: &ConflictId,
+    ) -> Result<ConflictId, CommandError> {
+        if let Some(new_id) = self.conflicts.get(id) {
+            return Ok(new_id.clone());
+        }
+        let conflict = self.source.read_conflict(path, id)?;
+        let mut copy_terms = |terms: &[ConflictTerm]| -> Result<Vec<_>, CommandError> {
+            terms
+                .iter()
+                .map(|term| {
+                    Ok(ConflictTerm {
+                        value: self.copy_tree_value(path, &term.value)?,
+                    })
+                })
+                .collect()
+        };
+        let new_conflict = jj_lib::backend::Conflict {
+            removes: copy_terms(&conflict.removes)?,
+            adds: copy_terms(&conflict.adds)?,
+        };
+        let new_id = self.target.write_conflict(path, &new_conflict)?;
+        self.conflicts.insert(id.clone(), new_id.clone());
+        Ok(new_id)
+    }
+
+    /// Maps the commit ids in the view to the copied commits.
+    fn map_view(&self, view: &View, keep_git_refs: bool) -> View {
+        let map_target = |target: &RefTarget| {
+            RefTarget::from_merge(
+                target
+                    .as_merge()
+                    .map(|id| id.as_ref().map(|id| self.commits[id].clone())),
+            )
+        };
+        let mut new_view = view.clone();
+        new_view.head_ids = view
+            .head_ids
+            .iter()
+            .map(|id| self.commits[id].clone())
+            .collect();
+        for target in new_view.local_bookmarks.values_mut() {
+            *target = map_target(target);
+        }
+        for target in new_view.tags.values_mut() {
+            *target = map_target(target);
+        }
+        for remote_view in new_view.remote_views.values_mut() {
+            for remote_ref in remote_view.bookmarks.values_mut() {
+                remote_ref.target = map_target(&remote_ref.target);
+            }
+        }
+        if keep_git_refs {
+            for target in new_view.git_refs.values_mut() {
+                *target = map_target(target);
+            }
+            new_view.git_head = map_target(&view.git_head);
+        } else {
+            // Git refs are meaningless without a Git backend.
+            new_view.git_refs.clear();
+            new_view.git_head = RefTarget::absent();
+            new_view
+                .remote_views
+                .remove(git::REMOTE_NAME_FOR_LOCAL_GIT_REPO);
+        }
+        for id in new_view.wc_commit_ids.values_mut() {
+            *id = self.commits[id].clone();
+        }
+        new_view
+    }
+}
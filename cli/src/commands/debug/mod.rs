@@ -12,17 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod conflict;
 mod copy_detection;
+mod fast_export;
 mod fileset;
 mod index;
 mod init_local;
 mod local_working_copy;
+mod migrate_backend;
 mod operation;
 mod reindex;
 mod revset;
 mod snapshot;
 mod template;
 mod tree;
+pub mod unlock;
 mod watchman;
 mod working_copy;
 
@@ -32,8 +36,12 @@ use std::fmt::Debug;
 use clap::Subcommand;
 use jj_lib::local_working_copy::LocalWorkingCopy;
 
+use self::conflict::cmd_debug_conflict;
+use self::conflict::DebugConflictCommand;
 use self::copy_detection::cmd_debug_copy_detection;
 use self::copy_detection::CopyDetectionArgs;
+use self::fast_export::cmd_debug_fast_export;
+use self::fast_export::DebugFastExportArgs;
 use self::fileset::cmd_debug_fileset;
 use self::fileset::DebugFilesetArgs;
 use self::index::cmd_debug_index;
@@ -42,6 +50,8 @@ use self::init_local::cmd_debug_init_local;
 use self::init_local::DebugInitLocalArgs;
 use self::local_working_copy::cmd_debug_local_working_copy;
 use self::local_working_copy::DebugLocalWorkingCopyArgs;
+use self::migrate_backend::cmd_debug_migrate_backend;
+use self::migrate_backend::DebugMigrateBackendArgs;
 use self::operation::cmd_debug_operation;
 use self::operation::DebugOperationArgs;
 use self::reindex::cmd_debug_reindex;
@@ -54,6 +64,8 @@ use self::template::cmd_debug_template;
 use self::template::DebugTemplateArgs;
 use self::tree::cmd_debug_tree;
 use self::tree::DebugTreeArgs;
+use self::unlock::cmd_debug_unlock;
+use self::unlock::DebugUnlockArgs;
 use self::watchman::cmd_debug_watchman;
 use self::watchman::DebugWatchmanCommand;
 use self::working_copy::cmd_debug_working_copy;
@@ -67,11 +79,15 @@ use crate::ui::Ui;
 #[derive(Subcommand, Clone, Debug)]
 #[command(hide = true)]
 pub enum DebugCommand {
+    #[command(subcommand)]
+    Conflict(DebugConflictCommand),
     CopyDetection(CopyDetectionArgs),
+    FastExport(DebugFastExportArgs),
     Fileset(DebugFilesetArgs),
     Index(DebugIndexArgs),
     InitLocal(DebugInitLocalArgs),
     LocalWorkingCopy(DebugLocalWorkingCopyArgs),
+    MigrateBackend(DebugMigrateBackendArgs),
     #[command(visible_alias = "view")]
     Operation(DebugOperationArgs),
     Reindex(DebugReindexArgs),
@@ -79,6 +95,7 @@ pub enum DebugCommand {
     Snapshot(DebugSnapshotArgs),
     Template(DebugTemplateArgs),
     Tree(DebugTreeArgs),
+    Unlock(DebugUnlockArgs),
     #[command(subcommand)]
     Watchman(DebugWatchmanCommand),
     WorkingCopy(DebugWorkingCopyArgs),
@@ -90,17 +107,21 @@ pub fn cmd_debug(
     subcommand: &DebugCommand,
 ) -> Result<(), CommandError> {
     match subcommand {
+        DebugCommand::Conflict(args) => cmd_debug_conflict(ui, command, args),
         DebugCommand::CopyDetection(args) => cmd_debug_copy_detection(ui, command, args),
+        DebugCommand::FastExport(args) => cmd_debug_fast_export(ui, command, args),
         DebugCommand::Fileset(args) => cmd_debug_fileset(ui, command, args),
         DebugCommand::Index(args) => cmd_debug_index(ui, command, args),
         DebugCommand::InitLocal(args) => cmd_debug_init_local(ui, command, args),
         DebugCommand::LocalWorkingCopy(args) => cmd_debug_local_working_copy(ui, command, args),
+        DebugCommand::MigrateBackend(args) => cmd_debug_migrate_backend(ui, command, args),
         DebugCommand::Operation(args) => cmd_debug_operation(ui, command, args),
         DebugCommand::Reindex(args) => cmd_debug_reindex(ui, command, args),
         DebugCommand::Revset(args) => cmd_debug_revset(ui, command, args),
         DebugCommand::Snapshot(args) => cmd_debug_snapshot(ui, command, args),
         DebugCommand::Template(args) => cmd_debug_template(ui, command, args),
         DebugCommand::Tree(args) => cmd_debug_tree(ui, command, args),
+        DebugCommand::Unlock(args) => cmd_debug_unlock(ui, command, args),
         DebugCommand::Watchman(args) => cmd_debug_watchman(ui, command, args),
         DebugCommand::WorkingCopy(args) => cmd_debug_working_copy(ui, command, args),
     }
@@ -13,14 +13,21 @@
 // limitations under the License.
 
 use std::fmt::Debug;
+use std::io;
 use std::io::Write as _;
 
+use jj_lib::default_index::revset_engine;
+use jj_lib::default_index::revset_engine::RevsetProfile;
+use jj_lib::default_index::DefaultReadonlyIndex;
 use jj_lib::object_id::ObjectId;
+use jj_lib::repo::Repo as _;
 use jj_lib::revset;
+use jj_lib::revset::Revset;
 use jj_lib::revset::RevsetDiagnostics;
 
 use crate::cli_util::CommandHelper;
 use crate::command_error::print_parse_diagnostics;
+use crate::command_error::user_error;
 use crate::command_error::CommandError;
 use crate::revset_util;
 use crate::ui::Ui;
@@ -29,6 +36,10 @@ use crate::ui::Ui;
 #[derive(clap::Args, Clone, Debug)]
 pub struct DebugRevsetArgs {
     revision: String,
+    /// Print the number of commits and the time spent on each node of the
+    /// evaluated expression
+    #[arg(long)]
+    explain: bool,
 }
 
 pub fn cmd_debug_revset(
@@ -67,7 +78,27 @@ pub fn cmd_debug_revset(
     writeln!(ui.stdout(), "{backend_expression:#?}")?;
     writeln!(ui.stdout())?;
 
-    let revset = expression.evaluate_unoptimized(repo)?;
+    let (revset, profile): (Box<dyn Revset>, _) = if args.explain {
+        let index = repo
+            .readonly_index()
+            .as_any()
+            .downcast_ref::<DefaultReadonlyIndex>()
+            .ok_or_else(|| {
+                user_error(format!(
+                    "Cannot explain revsets evaluated by indexes of type '{}'",
+                    repo.index_store().name()
+                ))
+            })?;
+        let (revset, profile) = revset_engine::evaluate_with_profile(
+            &backend_expression,
+            repo.store(),
+            index.search_index(),
+            index.clone(),
+        )?;
+        (Box::new(revset), Some(profile))
+    } else {
+        (expression.evaluate_unoptimized(repo)?, None)
+    };
     writeln!(ui.stdout(), "-- Evaluated:")?;
     writeln!(ui.stdout(), "{revset:#?}")?;
     writeln!(ui.stdout())?;
@@ -76,5 +107,32 @@ pub fn cmd_debug_revset(
     for commit_id in revset.iter() {
         writeln!(ui.stdout(), "{}", commit_id?.hex())?;
     }
+
+    if let Some(profile) = profile {
+        writeln!(ui.stdout())?;
+        writeln!(ui.stdout(), "-- Profile:")?;
+        write_profile(ui, &profile)?;
+    }
+    Ok(())
+}
+
+fn write_profile(ui: &Ui, profile: &RevsetProfile) -> io::Result<()> {
+    for node in profile.nodes() {
+        let mut stats = vec![format!("evaluated in {:.2?}", node.evaluation_time)];
+        if node.yielded_count > 0 || !node.iteration_time.is_zero() {
+            stats.push(format!(
+                "yielded {} in {:.2?}",
+                node.yielded_count, node.iteration_time
+            ));
+        }
+        if node.tested_count > 0 {
+            stats.push(format!(
+                "matched {}/{} in {:.2?}",
+                node.matched_count, node.tested_count, node.predicate_time
+            ));
+        }
+        let indent = "  ".repeat(node.depth);
+        writeln!(ui.stdout(), "{indent}{}: {}", node.label, stats.join(", "))?;
+    }
     Ok(())
 }
@@ -0,0 +1,117 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use jj_lib::lock::break_lock;
+use jj_lib::lock::LockOwner;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::user_error_with_message;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Inspect and remove lock files left behind by crashed processes
+///
+/// Lock files record the process that holds them and when it was acquired. A
+/// lock looks stale if the process ran on this host and is no longer running,
+/// or if it has been held for more than an hour. Locks are only reported
+/// unless `--force` is given, since the owner may still hold the lock even if
+/// it looks stale, e.g. if it runs in another container on the same host.
+///
+/// Locks are never removed automatically, so this command is mostly useful
+/// for diagnosing commands that seem to hang.
+#[derive(clap::Args, Clone, Debug)]
+pub struct DebugUnlockArgs {
+    /// Remove locks even if their owner might still hold them
+    #[arg(long)]
+    force: bool,
+}
+
+pub fn cmd_debug_unlock(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &DebugUnlockArgs,
+) -> Result<(), CommandError> {
+    // Don't load the workspace since that might wait for a lock.
+    let workspace_loader = command.workspace_loader()?;
+    let workspace_root = workspace_loader.workspace_root();
+    let lock_paths = lock_file_paths(workspace_root, workspace_loader.repo_path());
+    let mut found = false;
+    for path in lock_paths.iter().filter(|path| path.exists()) {
+        found = true;
+        let display_path = path.strip_prefix(workspace_root).unwrap_or(path);
+        let owner = LockOwner::read(path).map_err(|err| {
+            user_error_with_message(
+                format!("Failed to read lock file {}", display_path.display()),
+                err,
+            )
+        })?;
+        let stale_reason = owner.as_ref().and_then(|owner| owner.stale_reason());
+        let holder = describe_owner(owner.as_ref());
+        if args.force {
+            let removed = break_lock(path).map_err(|err| {
+                user_error_with_message(
+                    format!("Failed to remove lock file {}", display_path.display()),
+                    err,
+                )
+            })?;
+            if removed {
+                writeln!(
+                    ui.status(),
+                    "Removed lock {} {holder}",
+                    display_path.display()
+                )?;
+            }
+        } else if let Some(reason) = stale_reason {
+            writeln!(
+                ui.status(),
+                "Lock {} is {holder}, but looks stale ({reason}); use --force to remove it",
+                display_path.display()
+            )?;
+        } else {
+            writeln!(
+                ui.status(),
+                "Lock {} is {holder}; use --force to remove it",
+                display_path.display()
+            )?;
+        }
+    }
+    if !found {
+        writeln!(ui.status(), "No lock files found.")?;
+    }
+    Ok(())
+}
+
+/// Returns the paths of the lock files which can be left behind in a
+/// workspace and its repo.
+pub(crate) fn lock_file_paths(workspace_root: &Path, repo_path: &Path) -> [PathBuf; 3] {
+    [
+        workspace_root
+            .join(".jj")
+            .join("working_copy")
+            .join("working_copy.lock"),
+        repo_path.join("op_heads").join("lock"),
+        repo_path.join("store").join("extra").join("lock"),
+    ]
+}
+
+pub(crate) fn describe_owner(owner: Option<&LockOwner>) -> String {
+    match owner {
+        Some(owner) => format!("held by {owner}"),
+        None => "held by an unknown process".to_owned(),
+    }
+}
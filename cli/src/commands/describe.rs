@@ -28,6 +28,8 @@ use crate::cli_util::RevisionArg;
 use crate::command_error::user_error;
 use crate::command_error::CommandError;
 use crate::complete;
+use crate::description_util::check_conventional_commit;
+use crate::description_util::check_edited_conventional_commit;
 use crate::description_util::description_template;
 use crate::description_util::edit_description;
 use crate::description_util::edit_multiple_descriptions;
@@ -238,6 +240,16 @@ pub(crate) fn cmd_describe(
         }
     };
 
+    for (commit, new_description) in &commit_descriptions {
+        if new_description != commit.description() {
+            if use_editor {
+                check_edited_conventional_commit(&text_editor, tx.settings(), new_description)?;
+            } else {
+                check_conventional_commit(tx.settings(), new_description)?;
+            }
+        }
+    }
+
     // Filter out unchanged commits to avoid rebasing descendants in
     // `transform_descendants` below unnecessarily.
     let commit_descriptions: HashMap<_, _> = commit_descriptions
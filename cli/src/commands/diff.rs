@@ -26,6 +26,7 @@ use crate::cli_util::RevisionArg;
 use crate::command_error::CommandError;
 use crate::complete;
 use crate::diff_util::get_copy_records;
+use crate::diff_util::show_names_null_terminated;
 use crate::diff_util::DiffFormatArgs;
 use crate::ui::Ui;
 
@@ -75,12 +76,26 @@ pub(crate) struct DiffArgs {
     )]
     to: Option<RevisionArg>,
     /// Restrict the diff to these paths
+    ///
+    /// If no paths are specified, the whole workspace is compared, or only the
+    /// current directory if `ui.path-scope` is set to `"cwd"`.
     #[arg(
         value_name = "FILESETS",
         value_hint = clap::ValueHint::AnyPath,
         add = ArgValueCompleter::new(complete::modified_revision_or_range_files),
     )]
     paths: Vec<String>,
+    /// Terminate each path with a NUL byte instead of a newline
+    ///
+    /// Only applies to `--name-only`. The paths can be safely consumed by
+    /// `xargs -0` and the like.
+    #[arg(
+        long = "null",
+        short = 'z',
+        requires = "name_only",
+        conflicts_with = "long-format"
+    )]
+    null_terminated: bool,
     #[command(flatten)]
     format: DiffFormatArgs,
 }
@@ -93,7 +108,7 @@ pub(crate) fn cmd_diff(
 ) -> Result<(), CommandError> {
     let workspace_command = command.workspace_helper(ui)?;
     let repo = workspace_command.repo();
-    let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
+    let fileset_expression = workspace_command.parse_file_patterns_in_scope(ui, &args.paths)?;
     let matcher = fileset_expression.to_matcher();
     let resolve_revision = |r: &Option<RevisionArg>| {
         workspace_command.resolve_single_rev(ui, r.as_ref().unwrap_or(&RevisionArg::AT))
@@ -122,17 +137,26 @@ pub(crate) fn cmd_diff(
         }
     }
 
-    let diff_renderer = workspace_command.diff_renderer_for(&args.format)?;
     ui.request_pager();
-    diff_renderer.show_diff(
-        ui,
-        ui.stdout_formatter().as_mut(),
-        &from_tree,
-        &to_tree,
-        &matcher,
-        &copy_records,
-        ui.term_width(),
-    )?;
+    if args.null_terminated {
+        let tree_diff = from_tree.diff_stream_with_copies(&to_tree, &matcher, &copy_records);
+        show_names_null_terminated(
+            ui.stdout_formatter().as_mut(),
+            tree_diff,
+            workspace_command.path_converter(),
+        )?;
+    } else {
+        let diff_renderer = workspace_command.diff_renderer_for(&args.format)?;
+        diff_renderer.show_diff(
+            ui,
+            ui.stdout_formatter().as_mut(),
+            &from_tree,
+            &to_tree,
+            &matcher,
+            &copy_records,
+            ui.term_width(),
+        )?;
+    }
     print_unmatched_explicit_paths(
         ui,
         &workspace_command,
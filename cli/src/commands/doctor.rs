@@ -0,0 +1,582 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+
+use jj_lib::default_index::DefaultIndexStore;
+use jj_lib::fsmonitor::FsmonitorSettings;
+use jj_lib::lock::LockOwner;
+use jj_lib::repo::Repo as _;
+use jj_lib::settings::HumanByteSize;
+use jj_lib::workspace::Workspace;
+use tracing::instrument;
+
+use crate::cli_util::short_operation_hash;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::internal_error;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::command_error::user_error_with_message;
+use crate::command_error::CommandError;
+use crate::commands::debug::unlock::describe_owner;
+use crate::commands::debug::unlock::lock_file_paths;
+use crate::ui::Ui;
+
+/// Commit index size above which it's reported as oversized.
+const MAX_INDEX_SIZE: u64 = 1 << 30;
+
+/// Diagnose and repair common problems with the repository
+///
+/// Checks for stale lock files, corrupted or outdated commit index files,
+/// workspaces of Git worktrees which no longer exist, a Git HEAD which is out
+/// of sync in colocated repos, problems with Watchman, and oversized commit
+/// index files. The checks which need to load the repo are skipped if the
+/// commit index is corrupted, since loading the repo would rebuild it.
+///
+/// The report starts with a description of the jj build and of the storage
+/// used by the repo, which is useful to include in bug reports. Then each
+/// check is printed on its own line as `<status> <check>: <message>`, where
+/// `<status>` is one of `ok`, `warning`, `problem`, or `fixed`. The command
+/// fails if any problem is left.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct DoctorArgs {
+    /// Repair the problems which can be safely repaired
+    ///
+    /// The commit index is updated or rebuilt, workspaces of deleted Git
+    /// worktrees are forgotten if their recorded location is the worktree's,
+    /// Git HEAD is imported, and commit index files of deleted operations are
+    /// removed. Stale locks are only reported; they can be removed with `jj
+    /// debug unlock --force`.
+    #[arg(long)]
+    fix: bool,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Status {
+    Ok,
+    Warning,
+    Problem,
+    Fixed,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Warning => "warning",
+            Status::Problem => "problem",
+            Status::Fixed => "fixed",
+        }
+    }
+}
+
+struct Finding {
+    check: &'static str,
+    status: Status,
+    message: String,
+    /// Whether `--fix` would repair it.
+    fixable: bool,
+}
+
+/// Findings of the checks, which are printed once all checks have run.
+struct Report {
+    fix: bool,
+    findings: Vec<Finding>,
+}
+
+impl Report {
+    fn add(&mut self, check: &'static str, status: Status, message: impl Into<String>) {
+        self.findings.push(Finding {
+            check,
+            status,
+            message: message.into(),
+            fixable: false,
+        });
+    }
+
+    fn add_fixable(&mut self, check: &'static str, status: Status, message: impl Into<String>) {
+        self.findings.push(Finding {
+            check,
+            status,
+            message: message.into(),
+            fixable: true,
+        });
+    }
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_doctor(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &DoctorArgs,
+) -> Result<(), CommandError> {
+    let mut report = Report {
+        fix: args.fix,
+        findings: vec![],
+    };
+    // Locks and the index are checked before loading the repo, which might
+    // wait for a lock or fail to load a corrupted index.
+    check_locks(command, &mut report)?;
+    let workspace = command.load_workspace()?;
+    if check_index(ui, command, &workspace, &mut report)? {
+        let mut workspace_command = command.workspace_helper_no_snapshot(ui)?;
+        check_workspaces(ui, &mut workspace_command, &mut report)?;
+        check_git_head(ui, &mut workspace_command, &mut report)?;
+        check_watchman(&workspace_command, &mut report)?;
+        check_index_files(&workspace_command, &mut report)?;
+    } else {
+        report.add(
+            "repo",
+            Status::Warning,
+            "skipped the remaining checks since the commit index is corrupted",
+        );
+    }
+
+    print_environment(ui, command, &workspace)?;
+    let mut formatter = ui.stdout_formatter();
+    for finding in &report.findings {
+        let label = match finding.status {
+            Status::Ok | Status::Fixed => "doctor ok",
+            Status::Warning => "warning",
+            Status::Problem => "error",
+        };
+        write!(formatter.labeled(label), "{}", finding.status.as_str())?;
+        writeln!(formatter, " {}: {}", finding.check, finding.message)?;
+    }
+    drop(formatter);
+
+    let problems = report
+        .findings
+        .iter()
+        .filter(|finding| finding.status == Status::Problem)
+        .collect::<Vec<_>>();
+    if problems.is_empty() {
+        return Ok(());
+    }
+    let message = match problems.len() {
+        1 => "Found 1 problem".to_owned(),
+        n => format!("Found {n} problems"),
+    };
+    let num_fixable = problems.iter().filter(|finding| finding.fixable).count();
+    if num_fixable > 0 {
+        Err(user_error_with_hint(
+            message,
+            format!("Run `jj doctor --fix` to repair {num_fixable} of them."),
+        ))
+    } else {
+        Err(user_error(message))
+    }
+}
+
+fn print_environment(
+    ui: &Ui,
+    command: &CommandHelper,
+    workspace: &Workspace,
+) -> Result<(), CommandError> {
+    let repo_loader = workspace.repo_loader();
+    let version = command.app().render_version();
+    let fsmonitor = match workspace.settings().fsmonitor_settings()? {
+        FsmonitorSettings::Watchman(_) => "watchman",
+        FsmonitorSettings::Test { .. } => "test",
+        FsmonitorSettings::None => "none",
+    };
+    let mut formatter = ui.stdout_formatter();
+    writeln!(
+        formatter,
+        "jj version: {}",
+        version.trim().trim_start_matches("jj ")
+    )?;
+    writeln!(
+        formatter,
+        "platform: {} {}",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    )?;
+    writeln!(
+        formatter,
+        "commit backend: {}",
+        repo_loader.store().backend_name()
+    )?;
+    writeln!(
+        formatter,
+        "operation store: {}",
+        repo_loader.op_store().name()
+    )?;
+    writeln!(
+        formatter,
+        "commit index: {}",
+        repo_loader.index_store().name()
+    )?;
+    writeln!(
+        formatter,
+        "working copy: {}",
+        workspace.working_copy().name()
+    )?;
+    writeln!(formatter, "fsmonitor: {fsmonitor}")?;
+    writeln!(formatter)?;
+    Ok(())
+}
+
+fn check_locks(command: &CommandHelper, report: &mut Report) -> Result<(), CommandError> {
+    let workspace_loader = command.workspace_loader()?;
+    let workspace_root = workspace_loader.workspace_root();
+    let mut found = false;
+    for path in lock_file_paths(workspace_root, workspace_loader.repo_path()) {
+        if !path.exists() {
+            continue;
+        }
+        found = true;
+        let display_path = path.strip_prefix(workspace_root).unwrap_or(&path).display();
+        let owner = LockOwner::read(&path).map_err(|err| {
+            user_error_with_message(format!("Failed to read lock file {display_path}"), err)
+        })?;
+        let holder = describe_owner(owner.as_ref());
+        // The owner may still hold a lock that looks stale, so it's left to
+        // `jj debug unlock --force` to remove it.
+        if let Some(stale_reason) = owner.as_ref().and_then(|owner| owner.stale_reason()) {
+            report.add(
+                "locks",
+                Status::Problem,
+                format!("stale lock {display_path} {holder} ({stale_reason})"),
+            );
+        } else {
+            report.add(
+                "locks",
+                Status::Warning,
+                format!("lock {display_path} is {holder}"),
+            );
+        }
+    }
+    if !found {
+        report.add("locks", Status::Ok, "no lock files found");
+    }
+    Ok(())
+}
+
+/// Returns whether the repo can be loaded without rebuilding the commit index.
+fn check_index(
+    ui: &Ui,
+    command: &CommandHelper,
+    workspace: &Workspace,
+    report: &mut Report,
+) -> Result<bool, CommandError> {
+    let repo_loader = workspace.repo_loader();
+    let op = command.resolve_operation(ui, repo_loader)?;
+    let index_store = repo_loader.index_store();
+    let Some(default_index_store) = index_store.as_any().downcast_ref::<DefaultIndexStore>() else {
+        report.add(
+            "index",
+            Status::Ok,
+            format!("indexes of type '{}' aren't checked", index_store.name()),
+        );
+        return Ok(true);
+    };
+    match default_index_store.check_index_at_operation(op.id(), repo_loader.store()) {
+        Ok(true) => report.add("index", Status::Ok, "commit index is up to date"),
+        Ok(false) if report.fix => {
+            default_index_store
+                .build_index_at_operation(&op, repo_loader.store())
+                .map_err(internal_error)?;
+            report.add(
+                "index",
+                Status::Fixed,
+                format!("indexed operation {}", short_operation_hash(op.id())),
+            );
+        }
+        Ok(false) => report.add_fixable(
+            "index",
+            Status::Warning,
+            format!(
+                "operation {} isn't indexed yet",
+                short_operation_hash(op.id())
+            ),
+        ),
+        Err(err) if report.fix => {
+            // Hold the op heads lock so that no other process commits an
+            // operation while the index is being rebuilt from scratch.
+            let _lock = repo_loader.op_heads_store().lock()?;
+            default_index_store.reinit().map_err(internal_error)?;
+            default_index_store
+                .build_index_at_operation(&op, repo_loader.store())
+                .map_err(internal_error)?;
+            report.add(
+                "index",
+                Status::Fixed,
+                format!("rebuilt corrupted commit index ({err})"),
+            );
+        }
+        Err(err) => {
+            report.add_fixable(
+                "index",
+                Status::Problem,
+                format!("commit index is corrupted ({err})"),
+            );
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(feature = "git")]
+fn check_workspaces(
+    ui: &Ui,
+    workspace_command: &mut WorkspaceCommandHelper,
+    report: &mut Report,
+) -> Result<(), CommandError> {
+    use itertools::Itertools as _;
+    use jj_lib::op_store::WorkspaceId;
+    use jj_lib::workspace_store::SimpleWorkspaceStore;
+    use jj_lib::workspace_store::WorkspaceStore as _;
+
+    let repo = workspace_command.repo().clone();
+    let num_workspaces = repo.view().wc_commit_ids().len();
+    // Workspaces created by `jj git worktree map` are named after their
+    // worktree. A workspace is only considered to belong to a deleted worktree
+    // if its recorded root is the worktree path, since another workspace may
+    // have the same name.
+    let workspace_store = SimpleWorkspaceStore::load(workspace_command.repo_path());
+    let worktrees = jj_lib::git::get_worktrees(repo.store()).unwrap_or_default();
+    let mut orphaned_ids = vec![];
+    let mut unknown_ids = vec![];
+    for worktree in &worktrees {
+        let Some(path) = worktree.path.as_ref().filter(|path| !path.is_dir()) else {
+            continue;
+        };
+        let id = WorkspaceId::new(worktree.name.clone());
+        if &id == workspace_command.workspace_id() || repo.view().get_wc_commit_id(&id).is_none() {
+            continue;
+        }
+        match workspace_store.get_workspace_path(&id)? {
+            Some(root) if root == *path => orphaned_ids.push(id),
+            Some(_) => {}
+            None => unknown_ids.push(id),
+        }
+    }
+    if orphaned_ids.is_empty() && unknown_ids.is_empty() {
+        report.add(
+            "workspaces",
+            Status::Ok,
+            format!("{num_workspaces} workspaces"),
+        );
+        return Ok(());
+    }
+    if report.fix && !orphaned_ids.is_empty() {
+        let mut tx = workspace_command.start_transaction();
+        for id in &orphaned_ids {
+            tx.repo_mut().remove_wc_commit(id)?;
+        }
+        tx.finish(ui, "forget workspaces of deleted git worktrees")?;
+        workspace_store.forget(&orphaned_ids.iter().collect_vec())?;
+    }
+    for id in &orphaned_ids {
+        let message = format!(
+            "workspace {} belongs to a Git worktree which no longer exists",
+            id.as_str()
+        );
+        if report.fix {
+            report.add("workspaces", Status::Fixed, format!("forgot {message}"));
+        } else {
+            report.add_fixable("workspaces", Status::Problem, message);
+        }
+    }
+    for id in &unknown_ids {
+        report.add(
+            "workspaces",
+            Status::Warning,
+            format!(
+                "workspace {} may belong to a Git worktree which no longer exists, but its \
+                 location isn't recorded; forget it with `jj workspace forget` if so",
+                id.as_str()
+            ),
+        );
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "git"))]
+fn check_workspaces(
+    _ui: &Ui,
+    workspace_command: &mut WorkspaceCommandHelper,
+    report: &mut Report,
+) -> Result<(), CommandError> {
+    let num_workspaces = workspace_command.repo().view().wc_commit_ids().len();
+    report.add(
+        "workspaces",
+        Status::Ok,
+        format!("{num_workspaces} workspaces"),
+    );
+    Ok(())
+}
+
+#[cfg(feature = "git")]
+fn check_git_head(
+    ui: &Ui,
+    workspace_command: &mut WorkspaceCommandHelper,
+    report: &mut Report,
+) -> Result<(), CommandError> {
+    use jj_lib::backend::CommitId;
+
+    if !workspace_command.working_copy_shared_with_git() {
+        report.add("git-head", Status::Ok, "not colocated with Git");
+        return Ok(());
+    }
+    let repo = workspace_command.repo();
+    let git_backend = jj_lib::git::get_git_backend(repo.store())?;
+    let git_head_id = git_backend
+        .git_repo()
+        .head_id()
+        .ok()
+        .map(|id| CommitId::from_bytes(id.as_bytes()));
+    if git_head_id.as_ref() == repo.view().git_head().as_normal() {
+        report.add("git-head", Status::Ok, "Git HEAD is in sync");
+    } else if report.fix {
+        // Importing the Git HEAD is part of snapshotting a colocated repo.
+        workspace_command.maybe_snapshot(ui)?;
+        report.add("git-head", Status::Fixed, "imported Git HEAD");
+    } else {
+        report.add_fixable(
+            "git-head",
+            Status::Problem,
+            "Git HEAD was moved since it was last imported",
+        );
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "git"))]
+fn check_git_head(
+    _ui: &Ui,
+    _workspace_command: &mut WorkspaceCommandHelper,
+    report: &mut Report,
+) -> Result<(), CommandError> {
+    report.add("git-head", Status::Ok, "jj was built without Git support");
+    Ok(())
+}
+
+fn check_watchman(
+    workspace_command: &WorkspaceCommandHelper,
+    report: &mut Report,
+) -> Result<(), CommandError> {
+    let FsmonitorSettings::Watchman(config) = workspace_command.settings().fsmonitor_settings()?
+    else {
+        report.add("watchman", Status::Ok, "not enabled");
+        return Ok(());
+    };
+    #[cfg(feature = "watchman")]
+    {
+        use jj_lib::local_working_copy::LocalWorkingCopy;
+
+        let Some(wc) = workspace_command
+            .working_copy()
+            .as_any()
+            .downcast_ref::<LocalWorkingCopy>()
+        else {
+            report.add(
+                "watchman",
+                Status::Problem,
+                "Watchman requires a standard local-disk working copy",
+            );
+            return Ok(());
+        };
+        match wc.query_watchman(&config) {
+            Ok(_) => report.add("watchman", Status::Ok, "Watchman is working"),
+            Err(err) => report.add(
+                "watchman",
+                Status::Problem,
+                format!("failed to query Watchman ({err})"),
+            ),
+        }
+    }
+    #[cfg(not(feature = "watchman"))]
+    {
+        let _ = config;
+        report.add(
+            "watchman",
+            Status::Problem,
+            "Watchman is enabled, but jj was built without the `watchman` feature",
+        );
+    }
+    Ok(())
+}
+
+fn check_index_files(
+    workspace_command: &WorkspaceCommandHelper,
+    report: &mut Report,
+) -> Result<(), CommandError> {
+    let repo = workspace_command.repo();
+    let index_store = repo.index_store();
+    let Some(default_index_store) = index_store.as_any().downcast_ref::<DefaultIndexStore>() else {
+        return Ok(());
+    };
+    let stale_op_ids = default_index_store
+        .find_stale_operation_links(repo.op_store().as_ref())
+        .map_err(internal_error)?;
+    if !stale_op_ids.is_empty() {
+        if report.fix {
+            default_index_store
+                .remove_operation_links(&stale_op_ids)
+                .map_err(internal_error)?;
+            report.add(
+                "index-files",
+                Status::Fixed,
+                format!(
+                    "removed {} index files of deleted operations",
+                    stale_op_ids.len()
+                ),
+            );
+        } else {
+            report.add_fixable(
+                "index-files",
+                Status::Problem,
+                format!("{} index files of deleted operations", stale_op_ids.len()),
+            );
+        }
+    }
+    let size = dir_size(&workspace_command.repo_path().join("index")).map_err(internal_error)?;
+    if size > MAX_INDEX_SIZE {
+        report.add(
+            "index-files",
+            Status::Warning,
+            format!(
+                "commit index uses {}; run `jj debug reindex` to rebuild it",
+                HumanByteSize(size)
+            ),
+        );
+    } else if stale_op_ids.is_empty() {
+        report.add(
+            "index-files",
+            Status::Ok,
+            "no index files of deleted operations",
+        );
+    }
+    Ok(())
+}
+
+fn dir_size(path: &Path) -> io::Result<u64> {
+    let mut size = 0;
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            size += dir_size(&entry.path())?;
+        } else {
+            size += metadata.len();
+        }
+    }
+    Ok(size)
+}
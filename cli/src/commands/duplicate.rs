@@ -46,6 +46,10 @@ use crate::ui::Ui;
 /// `--insert-after` or `--insert-before` arguments are provided, the new
 /// children indicated by the arguments will be rebased onto the heads of the
 /// specified commits.
+///
+/// Each new commit records the change ID of the commit it was duplicated from.
+/// It is available as `duplicated_from` in templates, and is used by
+/// `jj rebase --skip-applied` to detect changes which are already applied.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct DuplicateArgs {
     /// The revision(s) to duplicate (default: @)
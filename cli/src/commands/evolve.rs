@@ -0,0 +1,388 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use itertools::Itertools as _;
+use jj_lib::backend::ChangeId;
+use jj_lib::commit::Commit;
+use jj_lib::commit::CommitIteratorExt as _;
+use jj_lib::repo::Repo as _;
+use tracing::instrument;
+
+use crate::cli_util::short_change_hash;
+use crate::cli_util::short_commit_hash;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::user_error;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Resolve divergent changes
+///
+/// A change is divergent when more than one visible commit has its change ID,
+/// for example after it was rewritten concurrently in two workspaces. A
+/// divergent change can be resolved by keeping one of its commits, by merging
+/// the contents of its commits into one, or by giving one of its commits a new
+/// change ID. Descendants and bookmarks of the commits that are replaced are
+/// updated, and all resolutions are applied in a single operation.
+///
+/// Without `--keep`, `--merge`, or `--renumber`, you're asked how to resolve
+/// each divergent change among the given revisions.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct EvolveArgs {
+    /// Revisions whose divergent changes to resolve [default: mutable()]
+    #[arg(
+        value_name = "REVSETS",
+        conflicts_with_all = ["keep", "merge", "renumber"],
+        add = ArgValueCandidates::new(complete::mutable_revisions)
+    )]
+    revisions: Vec<RevisionArg>,
+    /// Only list the divergent changes
+    #[arg(long, short, conflicts_with_all = ["keep", "merge", "renumber"])]
+    list: bool,
+    /// Keep these commits and abandon the other commits of their changes
+    #[arg(
+        long,
+        value_name = "REVSETS",
+        add = ArgValueCandidates::new(complete::mutable_revisions)
+    )]
+    keep: Vec<RevisionArg>,
+    /// Merge the contents of all commits of the changes of these revisions
+    ///
+    /// The changes of the other commits compared to their parents are applied
+    /// on top of the newest commit, which keeps its parents and description.
+    #[arg(
+        long,
+        value_name = "REVSETS",
+        add = ArgValueCandidates::new(complete::mutable_revisions)
+    )]
+    merge: Vec<RevisionArg>,
+    /// Give these commits new change IDs
+    #[arg(
+        long,
+        value_name = "REVSETS",
+        add = ArgValueCandidates::new(complete::mutable_revisions)
+    )]
+    renumber: Vec<RevisionArg>,
+}
+
+/// How to resolve a divergent change.
+#[derive(Clone, Debug)]
+enum Resolution {
+    Keep(Commit),
+    Merge,
+    Renumber(Commit),
+}
+
+/// A divergent change and the commits that have its change ID, newest first.
+struct DivergentChange {
+    change_id: ChangeId,
+    commits: Vec<Commit>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_evolve(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &EvolveArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let mut resolutions: Vec<(DivergentChange, Resolution)> = vec![];
+    if args.keep.is_empty() && args.merge.is_empty() && args.renumber.is_empty() {
+        let commits: Vec<_> = if args.revisions.is_empty() {
+            workspace_command.parse_revset(ui, &RevisionArg::from("mutable()".to_owned()))?
+        } else {
+            workspace_command.parse_union_revsets(ui, &args.revisions)?
+        }
+        .evaluate_to_commits()?
+        .try_collect()?;
+        let changes = find_divergent_changes(&workspace_command, &commits)?;
+        if changes.is_empty() {
+            writeln!(ui.status(), "No divergent changes.")?;
+            return Ok(());
+        }
+        if args.list {
+            print_divergent_changes(ui, &workspace_command, &changes)?;
+            return Ok(());
+        }
+        for change in changes {
+            if let Some(resolution) = choose_resolution(ui, &workspace_command, &change)? {
+                resolutions.push((change, resolution));
+            }
+        }
+    } else {
+        let mut seen_change_ids = HashSet::new();
+        let mut add_resolutions = |revisions: &[RevisionArg],
+                                   to_resolution: fn(&Commit) -> Resolution|
+         -> Result<(), CommandError> {
+            let commits: Vec<_> = workspace_command
+                .parse_union_revsets(ui, revisions)?
+                .evaluate_to_commits()?
+                .try_collect()?;
+            for commit in commits {
+                let change = divergent_change(&workspace_command, &commit)?.ok_or_else(|| {
+                    user_error(format!(
+                        "Change {} is not divergent",
+                        short_change_hash(commit.change_id())
+                    ))
+                })?;
+                if !seen_change_ids.insert(change.change_id.clone()) {
+                    return Err(user_error(format!(
+                        "Change {} was given more than one resolution",
+                        short_change_hash(&change.change_id)
+                    )));
+                }
+                resolutions.push((change, to_resolution(&commit)));
+            }
+            Ok(())
+        };
+        add_resolutions(&args.keep, |commit| Resolution::Keep(commit.clone()))?;
+        add_resolutions(&args.merge, |_| Resolution::Merge)?;
+        add_resolutions(&args.renumber, |commit| {
+            Resolution::Renumber(commit.clone())
+        })?;
+    }
+    if resolutions.is_empty() {
+        writeln!(ui.status(), "Nothing changed.")?;
+        return Ok(());
+    }
+
+    for (change, resolution) in &resolutions {
+        check_resolution(&workspace_command, change, resolution)?;
+    }
+
+    let mut tx = workspace_command.start_transaction();
+    let mut resolved_commits = vec![];
+    for (change, resolution) in &resolutions {
+        let resolved_commit = match resolution {
+            Resolution::Keep(kept) => {
+                for commit in &change.commits {
+                    if commit.id() != kept.id() {
+                        tx.repo_mut()
+                            .set_rewritten_commit(commit.id().clone(), kept.id().clone());
+                    }
+                }
+                kept.clone()
+            }
+            Resolution::Merge => {
+                let (newest, others) = change.commits.split_first().unwrap();
+                let mut tree = newest.tree()?;
+                for commit in others {
+                    tree = tree.merge(&commit.parent_tree(tx.repo())?, &commit.tree()?)?;
+                }
+                let new_commit = tx
+                    .repo_mut()
+                    .rewrite_commit(newest)
+                    .set_tree_id(tree.id())
+                    .write()?;
+                for commit in others {
+                    tx.repo_mut()
+                        .set_rewritten_commit(commit.id().clone(), new_commit.id().clone());
+                }
+                new_commit
+            }
+            Resolution::Renumber(commit) => tx
+                .repo_mut()
+                .rewrite_commit(commit)
+                .generate_new_change_id()
+                .write()?,
+        };
+        resolved_commits.push(resolved_commit);
+    }
+    let num_rebased = tx.rebase_descendants()?;
+
+    if let Some(mut formatter) = ui.status_formatter() {
+        for ((change, resolution), commit) in resolutions.iter().zip(&resolved_commits) {
+            match resolution {
+                Resolution::Keep(_) => write!(formatter, "Kept ")?,
+                Resolution::Merge => {
+                    write!(formatter, "Merged {} commits into ", change.commits.len())?;
+                }
+                Resolution::Renumber(_) => write!(formatter, "Gave a new change ID to ")?,
+            }
+            tx.write_commit_summary(formatter.as_mut(), commit)?;
+            writeln!(formatter)?;
+        }
+        if num_rebased > 0 {
+            writeln!(formatter, "Rebased {num_rebased} descendant commits")?;
+        }
+    }
+    let transaction_description = match resolutions.as_slice() {
+        [(change, _)] => format!(
+            "resolve divergence of change {}",
+            change.change_id.reverse_hex()
+        ),
+        _ => format!("resolve divergence of {} changes", resolutions.len()),
+    };
+    tx.finish(ui, transaction_description)?;
+    Ok(())
+}
+
+/// Returns the divergent change of `commit`, or `None` if it isn't divergent.
+fn divergent_change(
+    workspace_command: &WorkspaceCommandHelper,
+    commit: &Commit,
+) -> Result<Option<DivergentChange>, CommandError> {
+    let repo = workspace_command.repo();
+    let commit_ids = repo
+        .resolve_change_id(commit.change_id())
+        .unwrap_or_default();
+    if commit_ids.len() < 2 {
+        return Ok(None);
+    }
+    let mut commits: Vec<_> = commit_ids
+        .iter()
+        .map(|id| repo.store().get_commit(id))
+        .try_collect()?;
+    commits.sort_by_key(|commit| std::cmp::Reverse(commit.committer().timestamp.timestamp));
+    Ok(Some(DivergentChange {
+        change_id: commit.change_id().clone(),
+        commits,
+    }))
+}
+
+fn find_divergent_changes(
+    workspace_command: &WorkspaceCommandHelper,
+    commits: &[Commit],
+) -> Result<Vec<DivergentChange>, CommandError> {
+    let mut seen_change_ids = HashSet::new();
+    let mut changes = vec![];
+    for commit in commits {
+        if !seen_change_ids.insert(commit.change_id()) {
+            continue;
+        }
+        if let Some(change) = divergent_change(workspace_command, commit)? {
+            changes.push(change);
+        }
+    }
+    Ok(changes)
+}
+
+fn print_divergent_changes(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    changes: &[DivergentChange],
+) -> Result<(), CommandError> {
+    let mut formatter = ui.stdout_formatter();
+    let template = workspace_command.commit_summary_template();
+    for change in changes {
+        writeln!(
+            formatter,
+            "Change {} has {} commits:",
+            short_change_hash(&change.change_id),
+            change.commits.len()
+        )?;
+        for commit in &change.commits {
+            write!(formatter, "  ")?;
+            template.format(commit, formatter.as_mut())?;
+            writeln!(formatter)?;
+        }
+    }
+    Ok(())
+}
+
+/// Asks the user how to resolve `change`. Returns `None` if it should be
+/// skipped.
+fn choose_resolution(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    change: &DivergentChange,
+) -> Result<Option<Resolution>, CommandError> {
+    let num_commits = change.commits.len();
+    let mut formatter = ui.stdout_formatter();
+    writeln!(
+        formatter,
+        "Change {} is divergent:",
+        short_change_hash(&change.change_id)
+    )?;
+    let template = workspace_command.commit_summary_template();
+    for (i, commit) in change.commits.iter().enumerate() {
+        write!(formatter, "{}: ", i + 1)?;
+        template.format(commit, formatter.as_mut())?;
+        writeln!(formatter)?;
+    }
+    writeln!(
+        formatter,
+        "k1-k{num_commits}: keep that commit and abandon the others"
+    )?;
+    writeln!(formatter, "m: merge the contents of the commits")?;
+    writeln!(
+        formatter,
+        "r1-r{num_commits}: give that commit a new change ID"
+    )?;
+    writeln!(formatter, "s: skip this change")?;
+    drop(formatter);
+
+    let choices = (1..=num_commits)
+        .flat_map(|i| [format!("k{i}"), format!("r{i}")])
+        .chain(["m".to_owned(), "s".to_owned()])
+        .collect_vec();
+    let choice = ui.prompt_choice("choose how to resolve the divergence", &choices, None)?;
+    let commit_at = |index: &str| change.commits[index.parse::<usize>().unwrap() - 1].clone();
+    let resolution = match choice.split_at(1) {
+        ("k", index) => Resolution::Keep(commit_at(index)),
+        ("r", index) => Resolution::Renumber(commit_at(index)),
+        ("m", _) => Resolution::Merge,
+        _ => return Ok(None),
+    };
+    Ok(Some(resolution))
+}
+
+/// Checks that the commits rewritten by `resolution` are mutable, and that the
+/// commits replaced by another commit aren't its ancestors.
+fn check_resolution(
+    workspace_command: &WorkspaceCommandHelper,
+    change: &DivergentChange,
+    resolution: &Resolution,
+) -> Result<(), CommandError> {
+    let (replaced, target) = match resolution {
+        Resolution::Keep(kept) => (
+            change
+                .commits
+                .iter()
+                .filter(|commit| commit.id() != kept.id())
+                .collect_vec(),
+            kept,
+        ),
+        Resolution::Merge => {
+            let (newest, others) = change.commits.split_first().unwrap();
+            (others.iter().collect(), newest)
+        }
+        Resolution::Renumber(commit) => {
+            workspace_command.check_rewritable([commit.id()])?;
+            return Ok(());
+        }
+    };
+    workspace_command.check_rewritable(change.commits.iter().ids())?;
+    let index = workspace_command.repo().index();
+    for commit in replaced {
+        if index.is_ancestor(commit.id(), target.id())
+            || index.is_ancestor(target.id(), commit.id())
+        {
+            return Err(user_error(format!(
+                "Cannot resolve divergence of change {} since commit {} and {} are ancestors of \
+                 each other",
+                short_change_hash(&change.change_id),
+                short_commit_hash(commit.id()),
+                short_commit_hash(target.id()),
+            )));
+        }
+    }
+    Ok(())
+}
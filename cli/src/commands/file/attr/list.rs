@@ -0,0 +1,54 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use tracing::instrument;
+
+use super::FileAttrMode;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// List file type overrides in the working copy
+///
+/// Each line shows the overridden file type (`n`, `x`, or `l`) and the path.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct FileAttrListArgs {
+    /// Only list overrides of paths matching these prefixes
+    #[arg(value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
+    paths: Vec<String>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_file_attr_list(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &FileAttrListArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let matcher = workspace_command
+        .parse_file_patterns(ui, &args.paths)?
+        .to_matcher();
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for (path, file_type) in workspace_command.working_copy().file_type_overrides()? {
+        if matcher.matches(path) {
+            let mode = FileAttrMode::from_override(*file_type);
+            let ui_path = workspace_command.format_file_path(path);
+            writeln!(formatter, "{} {ui_path}", mode.name())?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,143 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod list;
+mod set;
+mod unset;
+
+use std::collections::BTreeMap;
+use std::io::Write as _;
+
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::working_copy::FileTypeOverride;
+
+use self::list::cmd_file_attr_list;
+use self::list::FileAttrListArgs;
+use self::set::cmd_file_attr_set;
+use self::set::FileAttrSetArgs;
+use self::unset::cmd_file_attr_unset;
+use self::unset::FileAttrUnsetArgs;
+use crate::cli_util::print_snapshot_stats;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Manage file type overrides in the working copy
+///
+/// Some file systems can't represent executable bits or symlinks (e.g. FAT, or
+/// Windows without symlink support). File type overrides tell `jj` which type
+/// to record in the working-copy commit for a path, regardless of the file on
+/// disk. For example, you can create a symlink in a commit by writing the link
+/// target to a regular file, and then running `jj file attr set l <path>`.
+///
+/// The overrides are stored in the working copy, and only apply to the current
+/// workspace. To change the executable bit in arbitrary revisions, use `jj file
+/// chmod`.
+#[derive(clap::Subcommand, Clone, Debug)]
+pub enum FileAttrCommand {
+    List(FileAttrListArgs),
+    Set(FileAttrSetArgs),
+    Unset(FileAttrUnsetArgs),
+}
+
+pub fn cmd_file_attr(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &FileAttrCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        FileAttrCommand::List(args) => cmd_file_attr_list(ui, command, args),
+        FileAttrCommand::Set(args) => cmd_file_attr_set(ui, command, args),
+        FileAttrCommand::Unset(args) => cmd_file_attr_unset(ui, command, args),
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+enum FileAttrMode {
+    /// Record as a non-executable file (alias: normal)
+    // We use short names for enum values so that errors say that the possible
+    // values are `n, x, l`.
+    #[value(name = "n", alias("normal"))]
+    Normal,
+    /// Record as an executable file (alias: executable)
+    #[value(name = "x", alias("executable"))]
+    Executable,
+    /// Record as a symlink, using the file content as the target (alias:
+    /// symlink)
+    #[value(name = "l", alias("symlink"))]
+    Symlink,
+}
+
+impl FileAttrMode {
+    fn from_override(file_type: FileTypeOverride) -> Self {
+        match file_type {
+            FileTypeOverride::Normal => Self::Normal,
+            FileTypeOverride::Executable => Self::Executable,
+            FileTypeOverride::Symlink => Self::Symlink,
+        }
+    }
+
+    fn to_override(self) -> FileTypeOverride {
+        match self {
+            Self::Normal => FileTypeOverride::Normal,
+            Self::Executable => FileTypeOverride::Executable,
+            Self::Symlink => FileTypeOverride::Symlink,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Normal => "n",
+            Self::Executable => "x",
+            Self::Symlink => "l",
+        }
+    }
+}
+
+/// Updates the file type overrides, and snapshots the working copy so the
+/// working-copy commit reflects the new overrides.
+fn update_file_type_overrides(
+    ui: &mut Ui,
+    mut workspace_command: WorkspaceCommandHelper,
+    description: &str,
+    update: impl FnOnce(&mut BTreeMap<RepoPathBuf, FileTypeOverride>),
+) -> Result<(), CommandError> {
+    let auto_tracking_matcher = workspace_command.auto_tracking_matcher(ui)?;
+    let options = workspace_command
+        .snapshot_options_with_start_tracking_matcher(ui, &auto_tracking_matcher)?;
+
+    let mut tx = workspace_command.start_transaction().into_inner();
+    let (mut locked_ws, wc_commit) = workspace_command.start_working_copy_mutation()?;
+    let mut file_type_overrides = locked_ws.locked_wc().file_type_overrides()?.clone();
+    update(&mut file_type_overrides);
+    locked_ws
+        .locked_wc()
+        .set_file_type_overrides(file_type_overrides)?;
+    let (new_tree_id, stats) = locked_ws.locked_wc().snapshot(&options)?;
+    if new_tree_id != *wc_commit.tree_id() {
+        tx.repo_mut()
+            .rewrite_commit(&wc_commit)
+            .set_tree_id(new_tree_id)
+            .write()?;
+        let num_rebased = tx.repo_mut().rebase_descendants()?;
+        if num_rebased > 0 {
+            writeln!(ui.status(), "Rebased {num_rebased} descendant commits")?;
+        }
+    }
+    let repo = tx.commit(description)?;
+    locked_ws.finish(repo.op_id().clone())?;
+    print_snapshot_stats(ui, &stats, workspace_command.env().path_converter())?;
+    Ok(())
+}
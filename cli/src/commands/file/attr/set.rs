@@ -0,0 +1,70 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCompleter;
+use itertools::Itertools as _;
+use tracing::instrument;
+
+use super::update_file_type_overrides;
+use super::FileAttrMode;
+use crate::cli_util::print_unmatched_explicit_paths;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Override the file type of paths in the working copy
+///
+/// The paths must be tracked in the working-copy commit.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct FileAttrSetArgs {
+    mode: FileAttrMode,
+    /// Paths to override the file type for
+    #[arg(
+        required = true,
+        value_name = "FILESETS",
+        value_hint = clap::ValueHint::AnyPath,
+        add = ArgValueCompleter::new(complete::all_revision_files),
+    )]
+    paths: Vec<String>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_file_attr_set(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &FileAttrSetArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let wc_commit = workspace_command.resolve_single_rev(ui, &RevisionArg::AT)?;
+    let tree = wc_commit.tree()?;
+    let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
+    let matcher = fileset_expression.to_matcher();
+    print_unmatched_explicit_paths(ui, &workspace_command, &fileset_expression, [&tree])?;
+
+    let paths = tree
+        .entries_matching(matcher.as_ref())
+        .map(|(path, _value)| path)
+        .collect_vec();
+    let file_type = args.mode.to_override();
+    let description = format!(
+        "override file type of {} paths to {}",
+        paths.len(),
+        args.mode.name()
+    );
+    update_file_type_overrides(ui, workspace_command, &description, |overrides| {
+        overrides.extend(paths.into_iter().map(|path| (path, file_type)));
+    })
+}
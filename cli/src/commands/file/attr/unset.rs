@@ -0,0 +1,55 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCompleter;
+use tracing::instrument;
+
+use super::update_file_type_overrides;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Remove file type overrides from paths in the working copy
+///
+/// The file types will be determined by the files on disk again.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct FileAttrUnsetArgs {
+    /// Paths to remove the overrides from
+    #[arg(
+        required = true,
+        value_name = "FILESETS",
+        value_hint = clap::ValueHint::AnyPath,
+        add = ArgValueCompleter::new(complete::all_revision_files),
+    )]
+    paths: Vec<String>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_file_attr_unset(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &FileAttrUnsetArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let matcher = workspace_command
+        .parse_file_patterns(ui, &args.paths)?
+        .to_matcher();
+    update_file_type_overrides(
+        ui,
+        workspace_command,
+        "remove file type overrides",
+        |overrides| overrides.retain(|path, _| !matcher.matches(path)),
+    )
+}
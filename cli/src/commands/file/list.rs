@@ -12,9 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io::Write;
+
 use clap_complete::ArgValueCandidates;
 use tracing::instrument;
 
+use crate::cli_util::format_template_null_terminated;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::command_error::CommandError;
@@ -52,6 +55,20 @@ pub(crate) struct FileListArgs {
     /// Only list files matching these prefixes (instead of all files)
     #[arg(value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
     paths: Vec<String>,
+
+    /// List paths which are intentionally left untracked in the working copy
+    ///
+    /// These paths were untracked by `jj file untrack --keep`.
+    #[arg(long, conflicts_with_all = ["revision", "template"])]
+    untracked: bool,
+
+    /// Terminate each entry with a NUL byte instead of a newline
+    ///
+    /// The template output is printed without color, and its trailing newline
+    /// is replaced with a NUL byte, so paths with unusual characters can be
+    /// safely consumed by `xargs -0` and the like.
+    #[arg(long = "null", short = 'z')]
+    null_terminated: bool,
 }
 
 #[instrument(skip_all)]
@@ -61,6 +78,24 @@ pub(crate) fn cmd_file_list(
     args: &FileListArgs,
 ) -> Result<(), CommandError> {
     let workspace_command = command.workspace_helper(ui)?;
+    if args.untracked {
+        let matcher = workspace_command
+            .parse_file_patterns(ui, &args.paths)?
+            .to_matcher();
+        ui.request_pager();
+        let mut formatter = ui.stdout_formatter();
+        for path in workspace_command.working_copy().untracked_paths()? {
+            if matcher.matches(path) {
+                let ui_path = workspace_command.format_file_path(path);
+                if args.null_terminated {
+                    write!(formatter, "{ui_path}\0")?;
+                } else {
+                    writeln!(formatter, "{ui_path}")?;
+                }
+            }
+        }
+        return Ok(());
+    }
     let commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
     let tree = commit.tree()?;
     let matcher = workspace_command
@@ -89,7 +124,11 @@ pub(crate) fn cmd_file_list(
             path,
             value: value?,
         };
-        template.format(&entry, formatter.as_mut())?;
+        if args.null_terminated {
+            formatter.write_all(&format_template_null_terminated(&entry, &template))?;
+        } else {
+            template.format(&entry, formatter.as_mut())?;
+        }
     }
     Ok(())
 }
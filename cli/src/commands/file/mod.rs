@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod annotate;
+mod attr;
 mod chmod;
 mod list;
 mod show;
@@ -27,6 +28,8 @@ use crate::ui::Ui;
 #[derive(clap::Subcommand, Clone, Debug)]
 pub enum FileCommand {
     Annotate(annotate::FileAnnotateArgs),
+    #[command(subcommand)]
+    Attr(attr::FileAttrCommand),
     Chmod(chmod::FileChmodArgs),
     List(list::FileListArgs),
     Show(show::FileShowArgs),
@@ -41,6 +44,7 @@ pub fn cmd_file(
 ) -> Result<(), CommandError> {
     match subcommand {
         FileCommand::Annotate(args) => annotate::cmd_file_annotate(ui, command, args),
+        FileCommand::Attr(subcommand) => attr::cmd_file_attr(ui, command, subcommand),
         FileCommand::Chmod(args) => chmod::cmd_file_chmod(ui, command, args),
         FileCommand::List(args) => list::cmd_file_list(ui, command, args),
         FileCommand::Show(args) => show::cmd_file_show(ui, command, args),
@@ -136,7 +136,9 @@ fn write_tree_entries<P: AsRef<RepoPath>>(
             MaterializedTreeValue::FileConflict { contents, .. } => {
                 materialize_merge_result(
                     &contents,
-                    workspace_command.env().conflict_marker_style(),
+                    workspace_command
+                        .env()
+                        .conflict_marker_style_for(path.as_ref()),
                     &mut ui.stdout_formatter(),
                 )?;
             }
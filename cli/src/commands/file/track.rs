@@ -36,6 +36,9 @@ use crate::ui::Ui;
 /// `snapshot.auto-track` (e.g. to `"none()"` or `"glob:**/*.rs"`). Files that
 /// don't match the pattern can be manually tracked using this command. The
 /// default pattern is `all()` and this command has no effect.
+///
+/// Paths untracked by `jj file untrack --keep` can also be tracked again using
+/// this command.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct FileTrackArgs {
     /// Paths to track
@@ -53,10 +56,22 @@ pub(crate) fn cmd_file_track(
     let matcher = workspace_command
         .parse_file_patterns(ui, &args.paths)?
         .to_matcher();
-    let options = workspace_command.snapshot_options_with_start_tracking_matcher(&matcher)?;
+    let options = workspace_command.snapshot_options_with_start_tracking_matcher(ui, &matcher)?;
 
     let mut tx = workspace_command.start_transaction().into_inner();
     let (mut locked_ws, _wc_commit) = workspace_command.start_working_copy_mutation()?;
+    // Paths which were intentionally left untracked should now be tracked
+    let (retracked_paths, new_untracked_paths): (Vec<_>, Vec<_>) = locked_ws
+        .locked_wc()
+        .untracked_paths()?
+        .iter()
+        .cloned()
+        .partition(|path| matcher.matches(path));
+    if !retracked_paths.is_empty() {
+        locked_ws
+            .locked_wc()
+            .set_untracked_paths(new_untracked_paths)?;
+    }
     let (_tree_id, track_stats) = locked_ws.locked_wc().snapshot(&options)?;
     let num_rebased = tx.repo_mut().rebase_descendants()?;
     if num_rebased > 0 {
@@ -29,9 +29,13 @@ use crate::complete;
 use crate::ui::Ui;
 
 /// Stop tracking specified paths in the working copy
+///
+/// The files are left on disk. Unless `--keep` is given, the paths must be
+/// ignored, or they will be added back by the next command.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct FileUntrackArgs {
-    /// Paths to untrack. They must already be ignored.
+    /// Paths to untrack. They must already be ignored, unless `--keep` is
+    /// given.
     ///
     /// The paths could be ignored via a .gitignore or .git/info/exclude (in
     /// colocated repos).
@@ -42,6 +46,14 @@ pub(crate) struct FileUntrackArgs {
         add = ArgValueCompleter::new(complete::all_revision_files),
     )]
     paths: Vec<String>,
+
+    /// Keep the paths untracked even if they aren't ignored
+    ///
+    /// The paths are recorded in the working copy, and won't be tracked again
+    /// until `jj file track` is run on them, or a commit containing them is
+    /// checked out. Use `jj file list --untracked` to list them.
+    #[arg(long)]
+    keep: bool,
 }
 
 #[instrument(skip_all)]
@@ -56,16 +68,20 @@ pub(crate) fn cmd_file_untrack(
         .parse_file_patterns(ui, &args.paths)?
         .to_matcher();
     let auto_tracking_matcher = workspace_command.auto_tracking_matcher(ui)?;
-    let options =
-        workspace_command.snapshot_options_with_start_tracking_matcher(&auto_tracking_matcher)?;
+    let options = workspace_command
+        .snapshot_options_with_start_tracking_matcher(ui, &auto_tracking_matcher)?;
 
     let mut tx = workspace_command.start_transaction().into_inner();
     let (mut locked_ws, wc_commit) = workspace_command.start_working_copy_mutation()?;
     // Create a new tree without the unwanted files
     let mut tree_builder = MergedTreeBuilder::new(wc_commit.tree_id().clone());
     let wc_tree = wc_commit.tree()?;
-    for (path, _value) in wc_tree.entries_matching(matcher.as_ref()) {
-        tree_builder.set_or_remove(path, Merge::absent());
+    let untracked_paths = wc_tree
+        .entries_matching(matcher.as_ref())
+        .map(|(path, _value)| path)
+        .collect_vec();
+    for path in &untracked_paths {
+        tree_builder.set_or_remove(path.clone(), Merge::absent());
     }
     let new_tree_id = tree_builder.write_tree(&store)?;
     let new_commit = tx
@@ -73,6 +89,14 @@ pub(crate) fn cmd_file_untrack(
         .rewrite_commit(&wc_commit)
         .set_tree_id(new_tree_id)
         .write()?;
+    if args.keep {
+        // Record the paths so they won't be added back by snapshots
+        let mut new_untracked_paths = locked_ws.locked_wc().untracked_paths()?.to_vec();
+        new_untracked_paths.extend(untracked_paths);
+        locked_ws
+            .locked_wc()
+            .set_untracked_paths(new_untracked_paths)?;
+    }
     // Reset the working copy to the new commit
     locked_ws.locked_wc().reset(&new_commit)?;
     // Commit the working copy again so we can inform the user if paths couldn't be
@@ -97,7 +121,7 @@ pub(crate) fn cmd_file_untrack(
             return Err(user_error_with_hint(
                 message,
                 "Files that are not ignored will be added back by the next command.
-Make sure they're ignored, then try again.",
+Make sure they're ignored, then try again, or use `--keep` to keep them untracked.",
             ));
         } else {
             // This means there were some concurrent changes made in the working copy. We
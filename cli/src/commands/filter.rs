@@ -0,0 +1,399 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::Read as _;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use bstr::ByteSlice as _;
+use clap_complete::ArgValueCandidates;
+use itertools::Itertools as _;
+use jj_lib::backend::BackendResult;
+use jj_lib::backend::FileId;
+use jj_lib::backend::TreeValue;
+use jj_lib::mailmap::Mailmap;
+use jj_lib::matchers::Matcher;
+use jj_lib::merged_tree::MergedTree;
+use jj_lib::merged_tree::MergedTreeBuilder;
+use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPath;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::store::Store;
+use pollster::FutureExt as _;
+use regex::bytes::Regex;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Rewrite the contents and descriptions of many revisions at once
+///
+/// Each of the specified revisions is rewritten by applying the given filters
+/// to its files and description. The change ids are preserved, and
+/// descendants and bookmarks are rebased onto the rewritten revisions. This is
+/// similar to `git filter-repo`.
+///
+/// Files are filtered in this order: `--keep` and `--remove` select the files,
+/// `--strip-files-bigger-than` and `--replace-text` modify their contents, and
+/// `--rename` moves them.
+///
+/// Expression files passed to `--replace-text` and `--replace-message`
+/// contain one replacement per line. A line like `secret==>replacement`
+/// replaces `secret` by `replacement`. If `==>replacement` is omitted,
+/// `***REMOVED***` is used. Lines starting with `regex:` match the rest of the
+/// pattern as a regular expression, and the replacement may refer to capture
+/// groups as `$1` or `${name}`. A `literal:` prefix can be used to match text
+/// starting with `regex:`. Empty lines and lines starting with `#` are
+/// ignored.
+///
+/// The file passed to `--mailmap` uses the `.mailmap` format of Git, for
+/// example `Jane Doe <jane@example.com> <jane@old.example.com>`. The authors
+/// and committers of the revisions are replaced by their canonical names and
+/// emails. The original committers and timestamps are kept, instead of
+/// recording the current user as the committer.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct FilterArgs {
+    /// The revisions to rewrite
+    #[arg(
+        long,
+        short,
+        default_value = "mutable()",
+        value_name = "REVSETS",
+        add = ArgValueCandidates::new(complete::mutable_revisions)
+    )]
+    revisions: Vec<RevisionArg>,
+    /// Keep only the files matching these filesets (can be repeated)
+    #[arg(long, value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
+    keep: Vec<String>,
+    /// Remove the files matching these filesets (can be repeated)
+    #[arg(long, value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
+    remove: Vec<String>,
+    /// Remove files larger than this many bytes
+    ///
+    /// The size may have a `k`, `M`, or `G` suffix.
+    #[arg(long, value_name = "SIZE", value_parser = parse_size)]
+    strip_files_bigger_than: Option<u64>,
+    /// Replace text in the files by the expressions in this file
+    #[arg(long, value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+    replace_text: Option<PathBuf>,
+    /// Replace text in the descriptions by the expressions in this file
+    #[arg(long, value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+    replace_message: Option<PathBuf>,
+    /// Move files from the directory OLD to NEW (can be repeated)
+    ///
+    /// The paths are relative to the current directory. The first matching
+    /// rename is applied.
+    #[arg(long, value_name = "OLD:NEW")]
+    rename: Vec<String>,
+    /// Rewrite author and committer identities by the mailmap in this file
+    #[arg(long, value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+    mailmap: Option<PathBuf>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_filter(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &FilterArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let keep_expression = workspace_command.parse_file_patterns(ui, &args.keep)?;
+    let remove_expression = workspace_command.parse_union_filesets(ui, &args.remove)?;
+    let matcher = keep_expression.difference(remove_expression).to_matcher();
+    let renames: Vec<_> = args
+        .rename
+        .iter()
+        .map(|rename| {
+            let (old, new) = rename.split_once(':').ok_or_else(|| {
+                user_error(format!("Invalid rename {rename:?}, expected OLD:NEW"))
+            })?;
+            Ok::<_, CommandError>((
+                workspace_command.parse_file_path(old)?,
+                workspace_command.parse_file_path(new)?,
+            ))
+        })
+        .try_collect()?;
+    let text_replacements = match &args.replace_text {
+        Some(path) => read_replacements(path)?,
+        None => vec![],
+    };
+    let message_replacements = match &args.replace_message {
+        Some(path) => read_replacements(path)?,
+        None => vec![],
+    };
+    let mailmap = args
+        .mailmap
+        .as_ref()
+        .map(|path| {
+            let content = std::fs::read_to_string(path).map_err(|err| {
+                user_error_with_message(format!("Failed to read {}", path.display()), err)
+            })?;
+            Ok::<_, CommandError>(Mailmap::parse(&content))
+        })
+        .transpose()?;
+
+    let commit_ids: Vec<_> = workspace_command
+        .parse_union_revsets(ui, &args.revisions)?
+        .evaluate_to_commit_ids()?
+        .try_collect()?;
+    workspace_command.check_rewritable(&commit_ids)?;
+    let store = workspace_command.repo().store().clone();
+    let mut filter = TreeFilter {
+        store: store.clone(),
+        matcher: matcher.as_ref(),
+        renames: &renames,
+        max_file_size: args.strip_files_bigger_than,
+        replacements: &text_replacements,
+        files: HashMap::new(),
+    };
+    // Filter the trees upfront so that invalid renames are reported before
+    // anything is rewritten.
+    let new_trees: HashMap<_, _> = commit_ids
+        .iter()
+        .map(|id| {
+            let commit = store.get_commit(id)?;
+            let new_tree = filter.filter_tree(&commit.tree()?)?;
+            Ok::<_, CommandError>((id.clone(), new_tree))
+        })
+        .try_collect()?;
+
+    let mut tx = workspace_command.start_transaction();
+    let mut num_rewritten = 0;
+    let mut num_rebased = 0;
+    tx.repo_mut()
+        .transform_descendants(commit_ids, |rewriter| {
+            if let Some(new_tree) = new_trees.get(rewriter.old_commit().id()) {
+                let old_commit = rewriter.old_commit();
+                let new_description =
+                    replace_all(&message_replacements, old_commit.description().as_bytes());
+                let new_description = new_description.to_str_lossy().into_owned();
+                let old_tree_id = old_commit.tree_id().clone();
+                let old_description = old_commit.description().to_owned();
+                // The original committer is kept when rewriting the identities,
+                // so that the mailmap is applied to it.
+                let new_identities = mailmap.as_ref().map(|mailmap| {
+                    (
+                        mailmap.resolve_signature(old_commit.author()),
+                        mailmap.resolve_signature(old_commit.committer()),
+                    )
+                });
+                let identities_changed =
+                    new_identities
+                        .as_ref()
+                        .is_some_and(|(new_author, new_committer)| {
+                            new_author != old_commit.author()
+                                || new_committer != old_commit.committer()
+                        });
+                if new_tree.id() != old_tree_id
+                    || new_description != old_description
+                    || identities_changed
+                    || rewriter.parents_changed()
+                {
+                    let mut builder = rewriter
+                        .reparent()
+                        .set_tree_id(new_tree.id())
+                        .set_description(new_description);
+                    if let Some((new_author, new_committer)) = new_identities {
+                        builder = builder.set_author(new_author).set_committer(new_committer);
+                    }
+                    builder.write()?;
+                    num_rewritten += 1;
+                }
+            } else if rewriter.parents_changed() {
+                rewriter.rebase()?.write()?;
+                num_rebased += 1;
+            }
+            Ok(())
+        })?;
+    if num_rewritten == 0 && num_rebased == 0 {
+        writeln!(ui.status(), "Nothing changed.")?;
+        return Ok(());
+    }
+    writeln!(ui.status(), "Rewrote {num_rewritten} commits")?;
+    if num_rebased > 0 {
+        writeln!(ui.status(), "Rebased {num_rebased} descendant commits")?;
+    }
+    tx.finish(ui, format!("filter {num_rewritten} commits"))?;
+    Ok(())
+}
+
+fn parse_size(value: &str) -> Result<u64, String> {
+    let (digits, multiplier) = match value.char_indices().last() {
+        Some((i, 'k' | 'K')) => (&value[..i], 1 << 10),
+        Some((i, 'M')) => (&value[..i], 1 << 20),
+        Some((i, 'G')) => (&value[..i], 1 << 30),
+        _ => (value, 1),
+    };
+    digits
+        .parse::<u64>()
+        .ok()
+        .and_then(|size| size.checked_mul(multiplier))
+        .ok_or_else(|| format!("invalid size {value:?}"))
+}
+
+/// Text replacement read from an expressions file.
+struct Replacement {
+    pattern: Regex,
+    replacement: Vec<u8>,
+}
+
+fn read_replacements(path: &Path) -> Result<Vec<Replacement>, CommandError> {
+    let content = std::fs::read_to_string(path).map_err(|err| {
+        user_error_with_message(format!("Failed to read {}", path.display()), err)
+    })?;
+    content
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (pattern, replacement) = line.split_once("==>").unwrap_or((line, "***REMOVED***"));
+            let (pattern, replacement) = if let Some(pattern) = pattern.strip_prefix("regex:") {
+                (Cow::Borrowed(pattern), Cow::Borrowed(replacement))
+            } else {
+                let pattern = pattern.strip_prefix("literal:").unwrap_or(pattern);
+                // Capture group references are only meaningful in regexes.
+                (
+                    Cow::Owned(regex::escape(pattern)),
+                    Cow::Owned(replacement.replace('$', "$$")),
+                )
+            };
+            let pattern = Regex::new(&pattern).map_err(|err| {
+                user_error_with_message(format!("Invalid expression in {}", path.display()), err)
+            })?;
+            Ok(Replacement {
+                pattern,
+                replacement: replacement.as_bytes().to_vec(),
+            })
+        })
+        .collect()
+}
+
+fn replace_all<'a>(replacements: &[Replacement], text: &'a [u8]) -> Cow<'a, [u8]> {
+    let mut text = Cow::Borrowed(text);
+    for Replacement {
+        pattern,
+        replacement,
+    } in replacements
+    {
+        if let Cow::Owned(new_text) = pattern.replace_all(&text, replacement.as_slice()) {
+            text = Cow::Owned(new_text);
+        }
+    }
+    text
+}
+
+struct TreeFilter<'a> {
+    store: Arc<Store>,
+    matcher: &'a dyn Matcher,
+    renames: &'a [(RepoPathBuf, RepoPathBuf)],
+    max_file_size: Option<u64>,
+    replacements: &'a [Replacement],
+    /// Filtered file ids, or `None` if the file was removed.
+    files: HashMap<FileId, Option<FileId>>,
+}
+
+impl TreeFilter<'_> {
+    fn filter_tree(&mut self, tree: &MergedTree) -> Result<MergedTree, CommandError> {
+        let mut tree_builder = MergedTreeBuilder::new(self.store.empty_merged_tree_id());
+        // Source path of each path in the new tree
+        let mut source_paths: HashMap<RepoPathBuf, RepoPathBuf> = HashMap::new();
+        for (path, value) in tree.entries_matching(self.matcher) {
+            let value = value?;
+            let mut new_value = value.clone();
+            for term in new_value.iter_mut() {
+                if let Some(TreeValue::File { id, executable }) = term {
+                    *term = self.filter_file(&path, id)?.map(|id| TreeValue::File {
+                        id,
+                        executable: *executable,
+                    });
+                }
+            }
+            // Remove the file if all sides of the conflict were removed.
+            let new_value = new_value.simplify();
+            if new_value.is_absent() {
+                continue;
+            }
+            let new_path = self.rename(&path).unwrap_or_else(|| path.clone());
+            if let Some(other_path) = source_paths.get(&new_path) {
+                return Err(user_error(format!(
+                    "Cannot write both {} and {} to {}",
+                    other_path.as_internal_file_string(),
+                    path.as_internal_file_string(),
+                    new_path.as_internal_file_string()
+                )));
+            }
+            source_paths.insert(new_path.clone(), path);
+            tree_builder.set_or_remove(new_path, new_value);
+        }
+        let tree_id = tree_builder.write_tree(&self.store)?;
+        Ok(self.store.get_root_tree(&tree_id)?)
+    }
+
+    fn filter_file(&mut self, path: &RepoPath, id: &FileId) -> BackendResult<Option<FileId>> {
+        if self.max_file_size.is_none() && self.replacements.is_empty() {
+            return Ok(Some(id.clone()));
+        }
+        if let Some(new_id) = self.files.get(id) {
+            return Ok(new_id.clone());
+        }
+        let mut contents = vec![];
+        self.store
+            .read_file(path, id)?
+            .read_to_end(&mut contents)
+            .map_err(|err| jj_lib::backend::BackendError::ReadFile {
+                path: path.to_owned(),
+                id: id.clone(),
+                source: err.into(),
+            })?;
+        let new_id = if self
+            .max_file_size
+            .is_some_and(|max_size| contents.len() as u64 > max_size)
+        {
+            None
+        } else {
+            match replace_all(self.replacements, &contents) {
+                Cow::Borrowed(_) => Some(id.clone()),
+                Cow::Owned(new_contents) => Some(
+                    self.store
+                        .write_file(path, &mut new_contents.as_slice())
+                        .block_on()?,
+                ),
+            }
+        };
+        self.files.insert(id.clone(), new_id.clone());
+        Ok(new_id)
+    }
+
+    fn rename(&self, path: &RepoPath) -> Option<RepoPathBuf> {
+        self.renames.iter().find_map(|(old, new)| {
+            let rest = path.strip_prefix(old)?;
+            let new_path = [
+                new.as_internal_file_string(),
+                rest.as_internal_file_string(),
+            ]
+            .into_iter()
+            .filter(|component| !component.is_empty())
+            .join("/");
+            Some(RepoPathBuf::from_internal_string(new_path))
+        })
+    }
+}
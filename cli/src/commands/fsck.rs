@@ -0,0 +1,136 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use jj_lib::default_index::DefaultIndexStore;
+use jj_lib::fsck;
+use jj_lib::fsck::FsckReport;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::internal_error;
+use crate::command_error::user_error;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Verify the integrity of the repository
+///
+/// Walks the operation log and reads every operation, view, commit, tree, and
+/// file reachable from it. Objects are checked against their ids where the
+/// backend supports it, and commits are checked against the commit index.
+///
+/// Each problem is printed on its own line as `<kind> <id>: <message>`, where
+/// `<kind>` is one of `operation`, `commit`, `tree`, `file`, `hash`, or
+/// `index`. The command fails if any problem was found.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct FsckArgs {
+    /// Rebuild derived data such as the commit index before checking
+    #[arg(long)]
+    repair: bool,
+    /// Print the report as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_fsck(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &FsckArgs,
+) -> Result<(), CommandError> {
+    // Resolve the operation without loading the repo. The index might be
+    // corrupted, which is reported by the check unless it's rebuilt first.
+    let workspace = command.load_workspace()?;
+    let repo_loader = workspace.repo_loader();
+    let op = command.resolve_operation(ui, repo_loader)?;
+    if args.repair {
+        let index_store = repo_loader.index_store();
+        let Some(default_index_store) = index_store.as_any().downcast_ref::<DefaultIndexStore>()
+        else {
+            return Err(user_error(format!(
+                "Cannot rebuild indexes of type '{}'",
+                index_store.name()
+            )));
+        };
+        default_index_store.reinit().map_err(internal_error)?;
+        default_index_store
+            .build_index_at_operation(&op, repo_loader.store())
+            .map_err(internal_error)?;
+        writeln!(ui.status(), "Rebuilt commit index.")?;
+    }
+    let report = fsck::check_repo(repo_loader, &op);
+    if args.json {
+        print_report_json(ui, &report)?;
+    } else {
+        print_report(ui, &report)?;
+    }
+    if report.is_ok() {
+        Ok(())
+    } else {
+        let num_problems = report.problems.len();
+        Err(user_error(format!(
+            "Found {num_problems} integrity problem{}",
+            if num_problems == 1 { "" } else { "s" }
+        )))
+    }
+}
+
+fn print_report(ui: &Ui, report: &FsckReport) -> Result<(), CommandError> {
+    let mut formatter = ui.stdout_formatter();
+    for problem in &report.problems {
+        writeln!(
+            formatter,
+            "{} {}: {}",
+            problem.kind, problem.object_id, problem.message
+        )?;
+    }
+    drop(formatter);
+    writeln!(
+        ui.status(),
+        "Checked {} operation{}, {} commit{}, {} tree{}, and {} file{}.",
+        report.num_operations,
+        if report.num_operations == 1 { "" } else { "s" },
+        report.num_commits,
+        if report.num_commits == 1 { "" } else { "s" },
+        report.num_trees,
+        if report.num_trees == 1 { "" } else { "s" },
+        report.num_files,
+        if report.num_files == 1 { "" } else { "s" },
+    )?;
+    Ok(())
+}
+
+fn print_report_json(ui: &Ui, report: &FsckReport) -> Result<(), CommandError> {
+    let problems = report
+        .problems
+        .iter()
+        .map(|problem| {
+            serde_json::json!({
+                "kind": problem.kind.as_str(),
+                "id": problem.object_id,
+                "message": problem.message,
+            })
+        })
+        .collect::<Vec<_>>();
+    let value = serde_json::json!({
+        "operations": report.num_operations,
+        "commits": report.num_commits,
+        "trees": report.num_trees,
+        "files": report.num_files,
+        "problems": problems,
+    });
+    writeln!(ui.stdout(), "{value}")?;
+    Ok(())
+}
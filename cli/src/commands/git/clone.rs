@@ -37,6 +37,7 @@ use crate::git_util::absolute_git_url;
 use crate::git_util::get_git_repo;
 use crate::git_util::print_git_import_stats;
 use crate::git_util::with_remote_git_callbacks;
+use crate::progress::indexing_progress;
 use crate::ui::Ui;
 
 /// Create a new repo backed by a clone of a Git repo
@@ -64,6 +65,11 @@ pub struct GitCloneArgs {
     /// Create a shallow clone of the given depth
     #[arg(long)]
     depth: Option<NonZeroU32>,
+    /// The remote bookmark to check out
+    ///
+    /// By default, the default bookmark of the remote is checked out.
+    #[arg(long, short)]
+    bookmark: Option<String>,
 }
 
 fn clone_destination_for_source(source: &str) -> Option<&str> {
@@ -146,24 +152,39 @@ pub fn cmd_git_clone(
 
     let (mut workspace_command, default_branch) = clone_result?;
     if let Some(name) = &default_branch {
+        // Record the remote's default branch as Git would do.
+        let git_repo = get_git_repo(workspace_command.repo().store())?;
+        git::set_remote_head(&git_repo, remote_name, Some(name))?;
         let default_symbol = RemoteRefSymbol {
             name,
             remote: remote_name,
         };
         write_repository_level_trunk_alias(ui, workspace_command.repo_path(), default_symbol)?;
+    }
 
-        let default_branch_remote_ref = workspace_command
-            .repo()
-            .view()
-            .get_remote_bookmark(default_symbol);
-        if let Some(commit_id) = default_branch_remote_ref.target.as_normal().cloned() {
+    if let Some(name) = args.bookmark.as_ref().or(default_branch.as_ref()) {
+        let symbol = RemoteRefSymbol {
+            name,
+            remote: remote_name,
+        };
+        let remote_ref = workspace_command.repo().view().get_remote_bookmark(symbol);
+        if let Some(commit_id) = remote_ref.target.as_normal().cloned() {
             let mut checkout_tx = workspace_command.start_transaction();
             // For convenience, create local bookmark as Git would do.
-            checkout_tx.repo_mut().track_remote_bookmark(default_symbol);
+            checkout_tx.repo_mut().track_remote_bookmark(symbol);
             if let Ok(commit) = checkout_tx.repo().store().get_commit(&commit_id) {
                 checkout_tx.check_out(&commit)?;
             }
-            checkout_tx.finish(ui, "check out git remote's default branch")?;
+            if args.bookmark.is_some() {
+                checkout_tx.finish(ui, format!("check out git remote's bookmark {name}"))?;
+            } else {
+                checkout_tx.finish(ui, "check out git remote's default branch")?;
+            }
+        } else if args.bookmark.is_some() {
+            writeln!(
+                ui.warning_default(),
+                "No bookmark {symbol} to check out in the cloned repo"
+            )?;
         }
     }
     Ok(())
@@ -227,7 +248,10 @@ fn fetch_new_remote(
     })?;
     let default_branch =
         with_remote_git_callbacks(ui, |cb| git_fetch.get_default_branch(remote_name, cb))?;
-    let import_stats = git_fetch.import_refs()?;
+    let import_stats = {
+        let mut progress = indexing_progress(ui);
+        git_fetch.import_refs(progress.as_mut().map(|x| x as _))?
+    };
     print_git_import_stats(ui, fetch_tx.repo(), &import_stats, true)?;
     fetch_tx.finish(ui, "fetch from git remote into empty repo")?;
     Ok(default_branch)
@@ -0,0 +1,284 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use itertools::Itertools as _;
+use jj_lib::file_util;
+use jj_lib::git;
+use jj_lib::op_store::RefTarget;
+use jj_lib::repo::Repo as _;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::internal_error;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::command_error::user_error_with_message;
+use crate::command_error::CommandError;
+use crate::git_util::print_failed_git_export;
+use crate::ui::Ui;
+
+/// Convert the repo to or from colocated layout
+///
+/// In a colocated repo, the Git repo is stored in the `.git` directory of the
+/// workspace, and `git` commands can be used in the same working directory.
+/// This command moves the Git repo stored inside the `.jj` directory to `.git`,
+/// and sets the Git `HEAD` and index to the parent of the working-copy commit.
+/// Use `--disable` to move the `.git` directory back into `.jj`.
+///
+/// Only the workspace which contains the repo can be converted, not the ones
+/// added by `jj workspace add`. The Git repo must not have linked worktrees.
+#[derive(clap::Args, Clone, Debug)]
+pub struct GitColocateArgs {
+    /// Move the Git repo back into the `.jj` directory
+    #[arg(long)]
+    disable: bool,
+}
+
+pub fn cmd_git_colocate(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &GitColocateArgs,
+) -> Result<(), CommandError> {
+    if args.disable {
+        disable_colocation(ui, command)
+    } else {
+        enable_colocation(ui, command)
+    }
+}
+
+fn enable_colocation(ui: &mut Ui, command: &CommandHelper) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    if workspace_command.working_copy_shared_with_git() {
+        return Err(user_error("The workspace is already colocated with Git"));
+    }
+    check_repo_workspace(&workspace_command)?;
+    let workspace_root = workspace_command.workspace_root().to_owned();
+    let store_path = workspace_command.repo_path().join("store");
+    let internal_git_path = store_path.join("git");
+    let dot_git_path = workspace_root.join(".git");
+    let git_backend = git::get_git_backend(workspace_command.repo().store())?;
+    if !is_same_path(git_backend.git_repo_path(), &internal_git_path) {
+        return Err(user_error_with_hint(
+            "The Git repo isn't managed by jj",
+            format!(
+                "The repo is backed by the Git repo at {}.",
+                git_backend.git_repo_path().display()
+            ),
+        ));
+    }
+    if dot_git_path.symlink_metadata().is_ok() {
+        return Err(user_error(
+            "Cannot colocate the Git repo because .git already exists in the workspace",
+        ));
+    }
+    check_no_linked_worktrees(&internal_git_path)?;
+
+    // Export refs and set up HEAD before moving the Git repo so they won't be
+    // imported as changes made by Git.
+    let wc_commit_id = workspace_command
+        .get_wc_commit_id()
+        .ok_or_else(|| user_error("This command requires a working copy"))?
+        .clone();
+    let wc_commit = workspace_command.repo().store().get_commit(&wc_commit_id)?;
+    let mut tx = workspace_command.start_transaction();
+    let failed_refs = git::export_refs(tx.repo_mut())?;
+    git::reset_head(tx.repo_mut(), &wc_commit)?;
+    if tx.repo().has_changes() {
+        tx.finish(ui, "colocate git repo")?;
+    }
+    print_failed_git_export(ui, &failed_refs)?;
+
+    fs::rename(&internal_git_path, &dot_git_path)
+        .map_err(|err| user_error_with_message("Failed to move the Git repo", err))?;
+    let git_target = file_util::relative_path(&store_path, &workspace_root).join(".git");
+    let result = set_git_bare(&dot_git_path, false)
+        .and_then(|()| write_git_target(&store_path, &git_target));
+    if let Err(err) = result {
+        // Move the Git repo back so the repo stays usable.
+        rollback_git_repo_move(
+            &store_path,
+            &dot_git_path,
+            &internal_git_path,
+            true,
+            Path::new("git"),
+        );
+        return Err(err);
+    }
+    fs::write(workspace_root.join(".jj").join(".gitignore"), "/*\n")
+        .map_err(|err| user_error_with_message("Failed to write .jj/.gitignore file", err))?;
+
+    // The ignore rules of the colocated Git repo apply from now on, so
+    // snapshot the working copy to record the files which became visible.
+    command.workspace_helper(ui)?;
+
+    writeln!(
+        ui.status(),
+        r#"Moved the Git repo to "{}". The workspace is now colocated with Git."#,
+        file_util::relative_path(command.cwd(), &dot_git_path).display()
+    )?;
+    Ok(())
+}
+
+fn disable_colocation(ui: &mut Ui, command: &CommandHelper) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    if !workspace_command.working_copy_shared_with_git() {
+        return Err(user_error("The workspace isn't colocated with Git"));
+    }
+    check_repo_workspace(&workspace_command)?;
+    let workspace_root = workspace_command.workspace_root().to_owned();
+    let store_path = workspace_command.repo_path().join("store");
+    let internal_git_path = store_path.join("git");
+    let dot_git_path = workspace_root.join(".git");
+    let git_backend = git::get_git_backend(workspace_command.repo().store())?;
+    let is_dot_git_dir = dot_git_path
+        .symlink_metadata()
+        .is_ok_and(|metadata| metadata.is_dir());
+    if !is_dot_git_dir || !is_same_path(git_backend.git_repo_path(), &dot_git_path) {
+        return Err(user_error_with_hint(
+            "The Git repo isn't stored in the .git directory of the workspace",
+            format!(
+                "The repo is backed by the Git repo at {}.",
+                git_backend.git_repo_path().display()
+            ),
+        ));
+    }
+    if internal_git_path.symlink_metadata().is_ok() {
+        return Err(internal_error(format!(
+            "{} already exists",
+            internal_git_path.display()
+        )));
+    }
+    check_no_linked_worktrees(&dot_git_path)?;
+    drop(workspace_command);
+
+    fs::rename(&dot_git_path, &internal_git_path)
+        .map_err(|err| user_error_with_message("Failed to move the Git repo", err))?;
+    let result = set_git_bare(&internal_git_path, true)
+        .and_then(|()| write_git_target(&store_path, Path::new("git")));
+    if let Err(err) = result {
+        // Move the Git repo back so the repo stays usable.
+        let git_target = file_util::relative_path(&store_path, &workspace_root).join(".git");
+        rollback_git_repo_move(
+            &store_path,
+            &internal_git_path,
+            &dot_git_path,
+            false,
+            &git_target,
+        );
+        return Err(err);
+    }
+    match fs::remove_file(workspace_root.join(".jj").join(".gitignore")) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => {
+            return Err(user_error_with_message(
+                "Failed to remove .jj/.gitignore file",
+                err,
+            ));
+        }
+    }
+
+    // The Git HEAD is no longer tracked since there's no Git working copy.
+    let mut workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    if workspace_command.repo().view().git_head().is_present() {
+        let mut tx = workspace_command.start_transaction();
+        tx.repo_mut().set_git_head_target(RefTarget::absent());
+        tx.finish(ui, "disable git colocation")?;
+    }
+
+    writeln!(
+        ui.status(),
+        "Moved the Git repo into the jj repo. The workspace is no longer colocated with Git."
+    )?;
+    Ok(())
+}
+
+/// Checks that the workspace is the one containing the repo. Other workspaces
+/// can't be colocated since they don't own the Git repo.
+fn check_repo_workspace(workspace_command: &WorkspaceCommandHelper) -> Result<(), CommandError> {
+    let repo_dir = workspace_command.workspace_root().join(".jj").join("repo");
+    if repo_dir.is_dir() && is_same_path(&repo_dir, workspace_command.repo_path()) {
+        Ok(())
+    } else {
+        Err(user_error_with_hint(
+            "This workspace doesn't contain the repo",
+            "Run this command in the workspace created by `jj git init` or `jj git clone`.",
+        ))
+    }
+}
+
+fn check_no_linked_worktrees(git_repo_path: &Path) -> Result<(), CommandError> {
+    let worktree_names = match fs::read_dir(git_repo_path.join("worktrees")) {
+        Ok(entries) => entries
+            .map_ok(|entry| entry.file_name().to_string_lossy().into_owned())
+            .try_collect()
+            .map_err(internal_error)?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => vec![],
+        Err(err) => return Err(internal_error(err)),
+    };
+    if worktree_names.is_empty() {
+        Ok(())
+    } else {
+        Err(user_error_with_hint(
+            format!(
+                "The Git repo has linked worktrees: {}",
+                worktree_names.iter().sorted().join(", ")
+            ),
+            "Remove them by `git worktree remove` first.",
+        ))
+    }
+}
+
+fn is_same_path(a: &Path, b: &Path) -> bool {
+    match (dunce::canonicalize(a), dunce::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Moves the Git repo from `new_path` back to `old_path`, and restores the
+/// original `core.bare` and `git_target`. Errors are ignored since the caller
+/// reports the original error.
+fn rollback_git_repo_move(
+    store_path: &Path,
+    new_path: &Path,
+    old_path: &Path,
+    old_bare: bool,
+    old_git_target: &Path,
+) {
+    set_git_bare(new_path, old_bare).ok();
+    if fs::rename(new_path, old_path).is_ok() {
+        write_git_target(store_path, old_git_target).ok();
+    }
+}
+
+fn set_git_bare(git_repo_path: &Path, bare: bool) -> Result<(), CommandError> {
+    git2::Config::open(&git_repo_path.join("config"))
+        .and_then(|mut config| config.set_bool("core.bare", bare))
+        .map_err(|err| user_error_with_message("Failed to update the Git config", err))
+}
+
+fn write_git_target(store_path: &Path, git_repo_path: &Path) -> Result<(), CommandError> {
+    // Use forward slashes so the repo can be shared with WSL on Windows.
+    let git_target = git_repo_path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .join("/");
+    fs::write(store_path.join("git_target"), git_target)
+        .map_err(|err| user_error_with_message("Failed to write the Git repo path", err))
+}
@@ -16,10 +16,12 @@ use std::collections::HashSet;
 
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools;
+use jj_lib::backend::Timestamp;
 use jj_lib::config::ConfigGetResultExt as _;
 use jj_lib::git;
 use jj_lib::git::GitFetch;
 use jj_lib::repo::Repo;
+use jj_lib::settings::GitFetchTags;
 use jj_lib::str_util::StringPattern;
 
 use crate::cli_util::CommandHelper;
@@ -30,8 +32,10 @@ use crate::command_error::user_error;
 use crate::command_error::CommandError;
 use crate::commands::git::get_single_remote;
 use crate::complete;
+use crate::git_util;
 use crate::git_util::print_git_import_stats;
 use crate::git_util::with_remote_git_callbacks;
+use crate::progress::indexing_progress;
 use crate::ui::Ui;
 
 /// Fetch from a Git remote
@@ -75,6 +79,40 @@ pub struct GitFetchArgs {
     /// Fetch from all remotes
     #[arg(long, conflicts_with = "remotes")]
     all_remotes: bool,
+    /// Which tags to fetch
+    ///
+    /// This defaults to the `git.fetch-tags` setting, which fetches the tags
+    /// pointing to fetched commits unless configured otherwise. `--tags`
+    /// without a value fetches all tags, and updates tags which were moved on
+    /// the remote.
+    #[arg(
+        long,
+        value_name = "MODE",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "all"
+    )]
+    tags: Option<FetchTagsArg>,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+enum FetchTagsArg {
+    /// Fetch all tags
+    All,
+    /// Fetch tags pointing to fetched commits
+    Reachable,
+    /// Don't fetch tags
+    None,
+}
+
+impl From<FetchTagsArg> for GitFetchTags {
+    fn from(arg: FetchTagsArg) -> Self {
+        match arg {
+            FetchTagsArg::All => GitFetchTags::All,
+            FetchTagsArg::Reachable => GitFetchTags::Reachable,
+            FetchTagsArg::None => GitFetchTags::None,
+        }
+    }
 }
 
 #[tracing::instrument(skip(ui, command))]
@@ -92,6 +130,41 @@ pub fn cmd_git_fetch(
         args.remotes.clone()
     };
 
+    fetch_remotes(
+        ui,
+        &mut workspace_command,
+        remote_patterns,
+        &args.branch,
+        args.tags.map(GitFetchTags::from),
+    )?;
+    Ok(())
+}
+
+/// Fetches all bookmarks from the remotes configured by `git.fetch`, and
+/// returns the names of the fetched remotes.
+pub(crate) fn fetch_default_remotes(
+    ui: &mut Ui,
+    workspace_command: &mut WorkspaceCommandHelper,
+) -> Result<Vec<String>, CommandError> {
+    let remote_patterns = get_default_fetch_remotes(ui, workspace_command)?;
+    fetch_remotes(
+        ui,
+        workspace_command,
+        remote_patterns,
+        &[StringPattern::everything()],
+        None,
+    )
+}
+
+/// Fetches from the remotes matching the `remote_patterns`, and returns the
+/// names of the fetched remotes.
+fn fetch_remotes(
+    ui: &mut Ui,
+    workspace_command: &mut WorkspaceCommandHelper,
+    remote_patterns: Vec<StringPattern>,
+    branch: &[StringPattern],
+    fetch_tags: Option<GitFetchTags>,
+) -> Result<Vec<String>, CommandError> {
     let all_remotes = git::get_all_remote_names(workspace_command.repo().store())?;
 
     let mut matching_remotes = HashSet::new();
@@ -128,12 +201,25 @@ pub fn cmd_git_fetch(
         .collect_vec();
 
     let mut tx = workspace_command.start_transaction();
-    do_git_fetch(ui, &mut tx, &remotes, &args.branch)?;
+    do_git_fetch(ui, &mut tx, &remotes, branch, fetch_tags)?;
     tx.finish(
         ui,
         format!("fetch from git remote(s) {}", remotes.iter().join(",")),
     )?;
-    Ok(())
+    let now = workspace_command
+        .settings()
+        .operation_timestamp()
+        .unwrap_or_else(Timestamp::now);
+    if let Err(err) = git_util::write_last_fetch_time(workspace_command.repo_path(), &now) {
+        writeln!(
+            ui.warning_default(),
+            "Failed to record the time of the fetch: {err}"
+        )?;
+    }
+    Ok(remotes
+        .into_iter()
+        .map(|remote| remote.to_owned())
+        .collect())
 }
 
 const DEFAULT_REMOTE: &str = "origin";
@@ -174,8 +260,12 @@ fn do_git_fetch(
     tx: &mut WorkspaceCommandTransaction,
     remotes: &[&str],
     branch_names: &[StringPattern],
+    fetch_tags: Option<GitFetchTags>,
 ) -> Result<(), CommandError> {
-    let git_settings = tx.settings().git_settings()?;
+    let mut git_settings = tx.settings().git_settings()?;
+    if let Some(fetch_tags) = fetch_tags {
+        git_settings.fetch_tags = fetch_tags;
+    }
     let mut git_fetch = GitFetch::new(tx.repo_mut(), &git_settings)?;
 
     for remote_name in remotes {
@@ -183,7 +273,10 @@ fn do_git_fetch(
             git_fetch.fetch(remote_name, branch_names, callbacks, None)
         })?;
     }
-    let import_stats = git_fetch.import_refs()?;
+    let import_stats = {
+        let mut progress = indexing_progress(ui);
+        git_fetch.import_refs(progress.as_mut().map(|x| x as _))?
+    };
     print_git_import_stats(ui, tx.repo(), &import_stats, true)?;
     warn_if_branches_not_found(ui, tx, branch_names, remotes)
 }
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io::Write as _;
+
 use jj_lib::git;
 
 use crate::cli_util::CommandHelper;
@@ -23,6 +25,9 @@ use crate::ui::Ui;
 ///
 /// If a working-copy commit gets abandoned, it will be given a new, empty
 /// commit. This is true in general; it is not specific to this command.
+///
+/// If `git.import-stash` is enabled, the entries of the Git stash are imported
+/// as hidden commits.
 #[derive(clap::Args, Clone, Debug)]
 pub struct GitImportArgs {}
 
@@ -39,6 +44,23 @@ pub fn cmd_git_import(
     git::import_head(tx.repo_mut())?;
     let stats = git::import_refs(tx.repo_mut(), &git_settings)?;
     print_git_import_stats(ui, tx.repo(), &stats, true)?;
+    if git_settings.import_stash {
+        let stash_commits = git::import_stash(tx.repo_mut())?;
+        if !stash_commits.is_empty() {
+            writeln!(
+                ui.status(),
+                "Imported {} Git stash entries as hidden commits:",
+                stash_commits.len()
+            )?;
+            if let Some(mut formatter) = ui.status_formatter() {
+                for commit in &stash_commits {
+                    write!(formatter, "  ")?;
+                    tx.write_commit_summary(formatter.as_mut(), commit)?;
+                    writeln!(formatter)?;
+                }
+            }
+        }
+    }
     tx.finish(ui, "import git refs")?;
     Ok(())
 }
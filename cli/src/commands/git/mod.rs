@@ -13,13 +13,17 @@
 // limitations under the License.
 
 mod clone;
+mod colocate;
 mod export;
-mod fetch;
+pub mod fetch;
 mod import;
 mod init;
+mod notes;
 mod push;
 mod remote;
+mod stash;
 mod submodule;
+mod worktree;
 
 use std::path::Path;
 
@@ -33,6 +37,8 @@ use jj_lib::store::Store;
 
 use self::clone::cmd_git_clone;
 use self::clone::GitCloneArgs;
+use self::colocate::cmd_git_colocate;
+use self::colocate::GitColocateArgs;
 use self::export::cmd_git_export;
 use self::export::GitExportArgs;
 use self::fetch::cmd_git_fetch;
@@ -41,12 +47,18 @@ use self::import::cmd_git_import;
 use self::import::GitImportArgs;
 use self::init::cmd_git_init;
 use self::init::GitInitArgs;
+use self::notes::cmd_git_notes;
+use self::notes::GitNotesCommand;
 use self::push::cmd_git_push;
 use self::push::GitPushArgs;
 use self::remote::cmd_git_remote;
 use self::remote::RemoteCommand;
+use self::stash::cmd_git_stash;
+use self::stash::GitStashCommand;
 use self::submodule::cmd_git_submodule;
 use self::submodule::GitSubmoduleCommand;
+use self::worktree::cmd_git_worktree;
+use self::worktree::GitWorktreeCommand;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::WorkspaceCommandHelper;
 use crate::command_error::user_error_with_message;
@@ -65,15 +77,22 @@ use crate::ui::Ui;
 #[derive(Subcommand, Clone, Debug)]
 pub enum GitCommand {
     Clone(GitCloneArgs),
+    Colocate(GitColocateArgs),
     Export(GitExportArgs),
     Fetch(GitFetchArgs),
     Import(GitImportArgs),
     Init(GitInitArgs),
+    #[command(subcommand)]
+    Notes(GitNotesCommand),
     Push(GitPushArgs),
     #[command(subcommand)]
     Remote(RemoteCommand),
+    #[command(subcommand)]
+    Stash(GitStashCommand),
     #[command(subcommand, hide = true)]
     Submodule(GitSubmoduleCommand),
+    #[command(subcommand)]
+    Worktree(GitWorktreeCommand),
 }
 
 pub fn cmd_git(
@@ -83,13 +102,17 @@ pub fn cmd_git(
 ) -> Result<(), CommandError> {
     match subcommand {
         GitCommand::Clone(args) => cmd_git_clone(ui, command, args),
+        GitCommand::Colocate(args) => cmd_git_colocate(ui, command, args),
         GitCommand::Export(args) => cmd_git_export(ui, command, args),
         GitCommand::Fetch(args) => cmd_git_fetch(ui, command, args),
         GitCommand::Import(args) => cmd_git_import(ui, command, args),
         GitCommand::Init(args) => cmd_git_init(ui, command, args),
+        GitCommand::Notes(args) => cmd_git_notes(ui, command, args),
         GitCommand::Push(args) => cmd_git_push(ui, command, args),
         GitCommand::Remote(args) => cmd_git_remote(ui, command, args),
+        GitCommand::Stash(args) => cmd_git_stash(ui, command, args),
         GitCommand::Submodule(args) => cmd_git_submodule(ui, command, args),
+        GitCommand::Worktree(args) => cmd_git_worktree(ui, command, args),
     }
 }
 
@@ -0,0 +1,70 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use jj_lib::git;
+use jj_lib::repo::Repo as _;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::description_util::edit_description;
+use crate::description_util::join_message_paragraphs;
+use crate::ui::Ui;
+
+/// Attach a note to a commit
+///
+/// The note is written directly to the underlying Git repo, so it is visible
+/// to `git notes`. Notes in the default ref can be pushed with `jj git push
+/// --notes`.
+#[derive(clap::Args, Clone, Debug)]
+pub struct GitNotesAddArgs {
+    /// The revision to attach the note to
+    #[arg(long, short, default_value = "@", value_name = "REVSET")]
+    revision: RevisionArg,
+    /// The note message to use (don't open editor)
+    #[arg(long = "message", short, value_name = "MESSAGE")]
+    message_paragraphs: Vec<String>,
+    /// Replace the existing note, if any
+    #[arg(long, short)]
+    force: bool,
+    /// The notes ref to write to
+    #[arg(long = "ref", default_value = git::DEFAULT_NOTES_REF, value_name = "REF")]
+    notes_ref: String,
+}
+
+pub fn cmd_git_notes_add(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &GitNotesAddArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
+    let store = workspace_command.repo().store();
+    let message = if args.message_paragraphs.is_empty() {
+        let existing = git::read_note(store, &args.notes_ref, commit.id())?;
+        let text_editor = workspace_command.text_editor()?;
+        edit_description(&text_editor, existing.as_deref().unwrap_or(""))?
+    } else {
+        join_message_paragraphs(&args.message_paragraphs)
+    };
+    git::write_note(
+        store,
+        &args.notes_ref,
+        commit.id(),
+        &message,
+        &command.settings().signature(),
+        args.force,
+    )?;
+    Ok(())
+}
@@ -0,0 +1,48 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod add;
+mod show;
+
+use clap::Subcommand;
+
+use self::add::cmd_git_notes_add;
+use self::add::GitNotesAddArgs;
+use self::show::cmd_git_notes_show;
+use self::show::GitNotesShowArgs;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Manage Git notes attached to commits
+///
+/// Notes are stored in the underlying Git repo under `refs/notes/commits` by
+/// default, keyed by commit id. They can be displayed in templates with
+/// `git_note()`.
+#[derive(Subcommand, Clone, Debug)]
+pub enum GitNotesCommand {
+    Add(GitNotesAddArgs),
+    Show(GitNotesShowArgs),
+}
+
+pub fn cmd_git_notes(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &GitNotesCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        GitNotesCommand::Add(args) => cmd_git_notes_add(ui, command, args),
+        GitNotesCommand::Show(args) => cmd_git_notes_show(ui, command, args),
+    }
+}
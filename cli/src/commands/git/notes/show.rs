@@ -0,0 +1,54 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use jj_lib::git;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo as _;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::user_error;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Show the note attached to a commit
+#[derive(clap::Args, Clone, Debug)]
+pub struct GitNotesShowArgs {
+    /// The revision whose note to show
+    #[arg(long, short, default_value = "@", value_name = "REVSET")]
+    revision: RevisionArg,
+    /// The notes ref to read from
+    #[arg(long = "ref", default_value = git::DEFAULT_NOTES_REF, value_name = "REF")]
+    notes_ref: String,
+}
+
+pub fn cmd_git_notes_show(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &GitNotesShowArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
+    let store = workspace_command.repo().store();
+    let Some(note) = git::read_note(store, &args.notes_ref, commit.id())? else {
+        return Err(user_error(format!(
+            "No note found for commit {}",
+            commit.id().hex()
+        )));
+    };
+    write!(ui.stdout(), "{note}")?;
+    Ok(())
+}
@@ -18,16 +18,27 @@ use std::fmt;
 use std::io;
 use std::io::Write;
 
+use bstr::ByteVec as _;
 use clap::ArgGroup;
 use clap_complete::ArgValueCandidates;
+use futures::StreamExt as _;
+use indexmap::IndexMap;
 use indexmap::IndexSet;
 use itertools::Itertools;
 use jj_lib::backend::CommitId;
+use jj_lib::backend::FileId;
+use jj_lib::backend::TreeValue;
 use jj_lib::commit::Commit;
 use jj_lib::commit::CommitIteratorExt as _;
 use jj_lib::config::ConfigGetResultExt as _;
+use jj_lib::fileset;
+use jj_lib::fileset::FilesetDiagnostics;
+use jj_lib::fileset::FilesetExpression;
 use jj_lib::git;
 use jj_lib::git::GitBranchPushTargets;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::matchers::Matcher;
+use jj_lib::merged_tree::TreeDiffEntry;
 use jj_lib::object_id::ObjectId;
 use jj_lib::op_store::RefTarget;
 use jj_lib::refs::classify_bookmark_push_action;
@@ -36,11 +47,17 @@ use jj_lib::refs::BookmarkPushUpdate;
 use jj_lib::refs::LocalAndRemoteRef;
 use jj_lib::refs::RemoteRefSymbol;
 use jj_lib::repo::Repo;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::repo_path::RepoPathUiConverter;
 use jj_lib::revset::RevsetExpression;
+use jj_lib::settings::HumanByteSize;
 use jj_lib::settings::UserSettings;
 use jj_lib::signing::SignBehavior;
 use jj_lib::str_util::StringPattern;
 use jj_lib::view::View;
+use jj_lib::view::TOPIC_METADATA_KEY;
+use pollster::FutureExt as _;
+use regex::Regex;
 
 use crate::cli_util::short_change_hash;
 use crate::cli_util::short_commit_hash;
@@ -48,12 +65,18 @@ use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::cli_util::WorkspaceCommandHelper;
 use crate::cli_util::WorkspaceCommandTransaction;
+use crate::command_error::config_error;
+use crate::command_error::config_error_with_message;
+use crate::command_error::print_parse_diagnostics;
 use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
 use crate::command_error::CommandError;
 use crate::commands::git::get_single_remote;
 use crate::complete;
 use crate::formatter::Formatter;
+use crate::formatter::PlainTextFormatter;
 use crate::git_util::with_remote_git_callbacks;
+use crate::git_util::BookmarkPushRemotes;
 use crate::ui::Ui;
 
 /// Push to a Git remote
@@ -61,11 +84,12 @@ use crate::ui::Ui;
 /// By default, pushes tracking bookmarks pointing to
 /// `remote_bookmarks(remote=<remote>)..@`. Use `--bookmark` to push specific
 /// bookmarks. Use `--all` to push all bookmarks. Use `--change` to generate
-/// bookmark names based on the change IDs of specific commits.
+/// bookmark names based on the change IDs of specific commits. Use `--topic`
+/// to push the changes in a topic.
 ///
 /// Unlike in Git, the remote to push to is not derived from the tracked remote
-/// bookmarks. Use `--remote` to select the remote Git repository by name. There
-/// is no option to push to multiple remotes.
+/// bookmarks. Use `--remote` to select the remote Git repository by name. The
+/// option can be repeated to push to multiple remotes.
 ///
 /// Before the command actually moves, creates, or deletes a remote bookmark, it
 /// makes several [safety checks]. If there is a problem, you may need to run
@@ -79,15 +103,22 @@ use crate::ui::Ui;
 ///     https://jj-vcs.github.io/jj/latest/bookmarks/#conflicts
 
 #[derive(clap::Args, Clone, Debug)]
-#[command(group(ArgGroup::new("specific").args(&["bookmark", "change", "revisions"]).multiple(true)))]
+#[command(group(ArgGroup::new("specific").args(&["bookmark", "change", "topic", "revisions"]).multiple(true)))]
 #[command(group(ArgGroup::new("what").args(&["all", "deleted", "tracked"]).conflicts_with("specific")))]
 pub struct GitPushArgs {
-    /// The remote to push to (only named remotes are supported)
+    /// The remote to push to (only named remotes are supported, can be
+    /// repeated)
+    ///
+    /// This defaults to the remote configured for the `--bookmark`s in the
+    /// `git.bookmark-push-remotes` setting, and then to the `git.push` setting.
+    /// If neither is configured, and if there are multiple remotes, the remote
+    /// named "origin" will be used.
     ///
-    /// This defaults to the `git.push` setting. If that is not configured, and
-    /// if there are multiple remotes, the remote named "origin" will be used.
+    /// If multiple remotes are specified, the same bookmarks are pushed to
+    /// each of them in turn. A failure to push to one remote doesn't prevent
+    /// pushing to the others.
     #[arg(long, add = ArgValueCandidates::new(complete::git_remotes))]
-    remote: Option<String>,
+    remote: Vec<String>,
     /// Push only this bookmark, or bookmarks matching a pattern (can be
     /// repeated)
     ///
@@ -130,6 +161,13 @@ pub struct GitPushArgs {
     /// it's set to `true`, `--allow-new` is no-op.
     #[arg(long, short = 'N', conflicts_with = "what")]
     allow_new: bool,
+    /// Allow deleting remote bookmarks whose local bookmarks were deleted
+    ///
+    /// Without this option, `jj git push` asks for confirmation before deleting
+    /// remote bookmarks, or fails if the input isn't a terminal. This is
+    /// implied by `--all` and `--deleted`.
+    #[arg(long)]
+    allow_delete: bool,
     /// Allow pushing commits with empty descriptions
     #[arg(long)]
     allow_empty_description: bool,
@@ -156,7 +194,8 @@ pub struct GitPushArgs {
     ///
     /// The created bookmark will be tracked automatically. Use the
     /// `git.push-bookmark-prefix` setting to change the prefix for generated
-    /// names.
+    /// names, or the `templates.git_push_bookmark` setting to generate the
+    /// names from a template.
     #[arg(
         long,
         short,
@@ -168,6 +207,24 @@ pub struct GitPushArgs {
         add = ArgValueCandidates::new(complete::mutable_revisions)
     )]
     change: Vec<RevisionArg>,
+    /// Push the changes in this topic by creating a bookmark named after the
+    /// topic (can be repeated)
+    ///
+    /// The bookmark name is the topic prefixed by `git.push-bookmark-prefix`.
+    /// The bookmark is created or moved to the head of the topic, so all the
+    /// changes in the topic are pushed. The topic must have a single head. An
+    /// existing bookmark is only moved if it points to a change in the topic.
+    #[arg(long, value_name = "TOPIC")]
+    topic: Vec<String>,
+    /// Also push the notes written by `jj git notes add`
+    ///
+    /// The `refs/notes/commits` ref is pushed to the same ref on the remote.
+    /// The remote notes ref is only updated if it's a fast-forward, so notes
+    /// added by others aren't lost.
+    ///
+    /// This can also be turned on by the `git.push-notes` setting.
+    #[arg(long)]
+    notes: bool,
     /// Only display what will change on the remote
     #[arg(long)]
     dry_run: bool,
@@ -196,12 +253,54 @@ pub fn cmd_git_push(
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
 
-    let remote = if let Some(name) = &args.remote {
-        name.clone()
+    let remotes = if !args.remote.is_empty() {
+        args.remote.iter().unique().cloned().collect_vec()
+    } else if let Some(name) = get_bookmark_push_remote(&workspace_command, &args.bookmark)? {
+        vec![name]
     } else {
-        get_default_push_remote(ui, &workspace_command)?
+        get_default_push_remotes(ui, &workspace_command)?
     };
 
+    if let [remote] = &remotes[..] {
+        return push_to_remote(ui, &mut workspace_command, args, remote);
+    }
+    // Each remote is pushed to in a separate transaction so the remote
+    // bookmarks of the remotes that were pushed to successfully are recorded
+    // even if pushing to another remote fails.
+    let mut failed_remotes = vec![];
+    let mut failure_hints = vec![];
+    for remote in &remotes {
+        if let Err(err) = push_to_remote(ui, &mut workspace_command, args, remote) {
+            writeln!(
+                ui.warning_default(),
+                "Failed to push to {remote}: {}",
+                err.error
+            )?;
+            failed_remotes.push(remote.as_str());
+            failure_hints.extend(err.hints);
+        }
+    }
+    if failed_remotes.is_empty() {
+        Ok(())
+    } else {
+        let mut error = user_error(format!(
+            "Failed to push to {} of {} remotes: {}",
+            failed_remotes.len(),
+            remotes.len(),
+            failed_remotes.join(", ")
+        ));
+        error.hints.extend(failure_hints);
+        Err(error)
+    }
+}
+
+fn push_to_remote(
+    ui: &Ui,
+    workspace_command: &mut WorkspaceCommandHelper,
+    args: &GitPushArgs,
+    remote: &str,
+) -> Result<(), CommandError> {
+    let remote = remote.to_owned();
     let mut tx = workspace_command.start_transaction();
     let view = tx.repo().view();
     let tx_description;
@@ -247,8 +346,15 @@ pub fn cmd_git_push(
 
         // Process --change bookmarks first because matching bookmarks can be moved.
         let bookmark_prefix = tx.settings().get_string("git.push-bookmark-prefix")?;
-        let change_bookmark_names =
-            update_change_bookmarks(ui, &mut tx, &args.change, &bookmark_prefix)?;
+        let mut change_bookmark_names =
+            update_change_bookmarks(ui, &mut tx, &args.change, &bookmark_prefix, &remote)?;
+        change_bookmark_names.extend(update_topic_bookmarks(
+            ui,
+            &mut tx,
+            &args.topic,
+            &bookmark_prefix,
+            &remote,
+        )?);
         let change_bookmarks = change_bookmark_names.iter().map(|bookmark_name| {
             let targets = LocalAndRemoteRef {
                 local_target: tx.repo().view().get_local_bookmark(bookmark_name),
@@ -264,7 +370,7 @@ pub fn cmd_git_push(
             if !seen_bookmarks.insert(bookmark_name) {
                 continue;
             }
-            let allow_new = true; // --change implies creation of remote bookmark
+            let allow_new = true; // --change/--topic implies creation of remote bookmark
             match classify_bookmark_update(bookmark_name, &remote, targets, allow_new) {
                 Ok(Some(update)) => bookmark_updates.push((bookmark_name.to_owned(), update)),
                 Ok(None) => writeln!(
@@ -291,8 +397,10 @@ pub fn cmd_git_push(
             }
         }
 
-        let use_default_revset =
-            args.bookmark.is_empty() && args.change.is_empty() && args.revisions.is_empty();
+        let use_default_revset = args.bookmark.is_empty()
+            && args.change.is_empty()
+            && args.topic.is_empty()
+            && args.revisions.is_empty();
         let bookmarks_targeted = find_bookmarks_targeted_by_revisions(
             ui,
             tx.base_workspace_helper(),
@@ -322,8 +430,14 @@ pub fn cmd_git_push(
             &remote
         );
     }
+    let push_notes = args.notes || tx.settings().get_bool("git.push-notes")?;
     if bookmark_updates.is_empty() {
-        writeln!(ui.status(), "Nothing changed.")?;
+        if !(push_notes
+            && !args.dry_run
+            && push_notes_to_remote(ui, tx.settings(), tx.repo(), &remote)?)
+        {
+            writeln!(ui.status(), "Nothing changed.")?;
+        }
         return Ok(());
     }
 
@@ -369,6 +483,22 @@ pub fn cmd_git_push(
         return Ok(());
     }
 
+    let deleted_bookmarks = bookmark_updates
+        .iter()
+        .filter_map(|(bookmark_name, update)| match update {
+            BookmarkPushUpdate {
+                old_target: Some(old_target),
+                new_target: None,
+            } => Some((bookmark_name.clone(), old_target.clone())),
+            _ => None,
+        })
+        .collect_vec();
+    // --all and --deleted explicitly ask for deleted bookmarks to be pushed
+    let allow_delete = args.allow_delete || args.all || args.deleted;
+    if !deleted_bookmarks.is_empty() && !allow_delete {
+        confirm_remote_bookmark_deletion(ui, &tx, &remote, &deleted_bookmarks)?;
+    }
+
     let targets = GitBranchPushTargets {
         branch_updates: bookmark_updates,
     };
@@ -377,6 +507,120 @@ pub fn cmd_git_push(
         git::push_branches(tx.repo_mut(), &git_settings, &remote, &targets, cb)
     })?;
     tx.finish(ui, tx_description)?;
+    if !deleted_bookmarks.is_empty() {
+        print_deleted_bookmarks_restore_hint(ui, &remote, &deleted_bookmarks)?;
+    }
+    if push_notes {
+        push_notes_to_remote(
+            ui,
+            workspace_command.settings(),
+            workspace_command.repo().as_ref(),
+            &remote,
+        )?;
+    }
+    Ok(())
+}
+
+/// Pushes the default notes ref, if it exists. Returns true if it was pushed.
+fn push_notes_to_remote(
+    ui: &Ui,
+    settings: &UserSettings,
+    repo: &dyn Repo,
+    remote: &str,
+) -> Result<bool, CommandError> {
+    let notes_ref = git::DEFAULT_NOTES_REF;
+    let git_settings = settings.git_settings()?;
+    let pushed = with_remote_git_callbacks(ui, |cb| {
+        git::push_notes(repo.store(), &git_settings, remote, notes_ref, cb)
+    })
+    .map_err(|err| match err {
+        git::GitPushError::RefUpdateRejected(_) => user_error_with_hint(
+            format!(
+                "Failed to push notes {notes_ref} to {remote}, which has notes that aren't in the \
+                 local ref"
+            ),
+            "Fetch the remote notes and combine them with `git notes merge` first.",
+        ),
+        err => err.into(),
+    })?;
+    if pushed {
+        writeln!(ui.status(), "Pushed notes {notes_ref} to {remote}")?;
+    }
+    Ok(pushed)
+}
+
+/// Asks the user whether the remote bookmarks should be deleted, or fails if
+/// the user can't be prompted.
+fn confirm_remote_bookmark_deletion(
+    ui: &Ui,
+    tx: &WorkspaceCommandTransaction,
+    remote: &str,
+    deleted_bookmarks: &[(String, CommitId)],
+) -> Result<(), CommandError> {
+    let workspace_command = tx.base_workspace_helper();
+    let write_deleted_bookmarks = |formatter: &mut dyn Formatter| -> io::Result<()> {
+        for (bookmark_name, old_target) in deleted_bookmarks {
+            write!(formatter, "  {bookmark_name}@{remote}: ")?;
+            match tx.base_repo().store().get_commit(old_target) {
+                Ok(commit) => workspace_command.write_commit_summary(formatter, &commit)?,
+                Err(_) => write!(formatter, "{}", short_commit_hash(old_target))?,
+            }
+            writeln!(formatter)?;
+        }
+        Ok(())
+    };
+    let bookmark_term = make_bookmark_term(
+        &deleted_bookmarks
+            .iter()
+            .map(|(bookmark_name, _)| bookmark_name)
+            .collect_vec(),
+    );
+    if !Ui::can_prompt() {
+        let mut error = user_error(format!(
+            "Refusing to delete {bookmark_term} on remote {remote} without --allow-delete"
+        ));
+        error.add_formatted_hint_with(|formatter| {
+            writeln!(formatter, "The remote bookmarks point to:")?;
+            write_deleted_bookmarks(formatter)
+        });
+        error.add_hint("Use --allow-delete to delete the remote bookmarks.");
+        return Err(error);
+    }
+    if let Some(mut formatter) = ui.status_formatter() {
+        writeln!(formatter, "The following remote bookmarks will be deleted:")?;
+        write_deleted_bookmarks(formatter.as_mut())?;
+    }
+    if !ui.prompt_yes_no(&format!("Delete {bookmark_term} on remote {remote}?"), None)? {
+        return Err(user_error("Push aborted"));
+    }
+    Ok(())
+}
+
+fn print_deleted_bookmarks_restore_hint(
+    ui: &Ui,
+    remote: &str,
+    deleted_bookmarks: &[(String, CommitId)],
+) -> io::Result<()> {
+    writeln!(
+        ui.hint_default(),
+        "To restore the deleted remote bookmarks, recreate and push them:"
+    )?;
+    let mut formatter = ui.hint_no_heading();
+    for (bookmark_name, old_target) in deleted_bookmarks {
+        writeln!(
+            formatter,
+            "  jj bookmark create -r {} {bookmark_name}",
+            short_commit_hash(old_target)
+        )?;
+    }
+    writeln!(
+        formatter,
+        "  jj git push --remote {remote} --allow-new {}",
+        deleted_bookmarks
+            .iter()
+            .map(|(bookmark_name, _)| format!("--bookmark {bookmark_name}"))
+            .join(" ")
+    )?;
     Ok(())
 }
 
@@ -420,8 +664,10 @@ fn validate_commits_ready_to_push(
         sign_settings.behavior = sign_behavior;
         sign_settings
     });
+    let push_checks = load_push_checks(ui, settings)?;
 
     let mut commits_to_sign = vec![];
+    let mut check_failures = vec![];
 
     for commit in workspace_helper
         .attach_revset_evaluator(commits_to_push)
@@ -468,15 +714,214 @@ fn validate_commits_ready_to_push(
             }
             return Err(error);
         }
+        for check in &push_checks {
+            if let Some(reason) = check.evaluate(repo.as_ref(), settings, &commit)? {
+                check_failures.push((check, commit.clone(), reason));
+            }
+        }
         if let Some(sign_settings) = &sign_settings {
             if !commit.is_signed() && sign_settings.should_sign(commit.store_commit()) {
                 commits_to_sign.push(commit);
             }
         }
     }
+
+    let (errors, warnings): (Vec<_>, Vec<_>) = check_failures
+        .into_iter()
+        .partition(|(check, _, _)| check.severity == PushCheckSeverity::Error);
+    for (check, commit, reason) in &warnings {
+        writeln!(
+            ui.warning_default(),
+            "Push check `{}` failed for commit {} since {reason}",
+            check.name,
+            short_commit_hash(commit.id()),
+        )?;
+    }
+    if !errors.is_empty() {
+        let mut error = user_error(format!(
+            "Won't push since {} of the configured git.push-checks failed",
+            errors.len()
+        ));
+        for (check, commit, reason) in errors {
+            error.add_formatted_hint_with(|formatter| {
+                write!(formatter, "Check `{}` failed for ", check.name)?;
+                workspace_helper.write_commit_summary(formatter, &commit)?;
+                write!(formatter, ": {reason}")?;
+                Ok(())
+            });
+        }
+        return Err(error);
+    }
     Ok(commits_to_sign)
 }
 
+/// Severity of a failed rule of the `git.push-checks` table.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum PushCheckSeverity {
+    #[default]
+    Error,
+    Warning,
+}
+
+enum PushCheckRule {
+    /// The description must match the regex.
+    Description(Regex),
+    /// Files matching the fileset must not be added or modified.
+    ForbiddenPaths(Box<dyn Matcher>),
+    /// Added or modified files must not be larger than the size.
+    MaxFileSize(HumanByteSize),
+    /// The commit must be authored by the configured user.
+    ForeignAuthor,
+    /// The commit must not have more than one parent.
+    MergeCommits,
+}
+
+/// Rule configured in the `git.push-checks` table.
+struct PushCheck {
+    name: String,
+    severity: PushCheckSeverity,
+    rule: PushCheckRule,
+}
+
+impl PushCheck {
+    /// Returns the reason why the `commit` violates this rule, if it does.
+    fn evaluate(
+        &self,
+        repo: &dyn Repo,
+        settings: &UserSettings,
+        commit: &Commit,
+    ) -> Result<Option<String>, CommandError> {
+        let reason = match &self.rule {
+            PushCheckRule::Description(regex) => (!regex.is_match(commit.description()))
+                .then(|| format!("its description doesn't match `{}`", regex.as_str())),
+            PushCheckRule::ForbiddenPaths(matcher) => {
+                changed_files(repo, commit, matcher.as_ref())?
+                    .into_iter()
+                    .next()
+                    .map(|(path, _)| {
+                        format!(
+                            "it modifies forbidden path {}",
+                            path.as_internal_file_string()
+                        )
+                    })
+            }
+            PushCheckRule::MaxFileSize(max_size) => {
+                let mut reason = None;
+                for (path, id) in changed_files(repo, commit, &EverythingMatcher)? {
+                    // Stop reading once the file is known to be too large.
+                    let reader = repo.store().read_file(&path, &id)?;
+                    let mut reader = io::Read::take(reader, max_size.0.saturating_add(1));
+                    let size = io::copy(&mut reader, &mut io::sink())?;
+                    if size > max_size.0 {
+                        reason = Some(format!(
+                            "file {} is larger than {max_size}",
+                            path.as_internal_file_string(),
+                        ));
+                        break;
+                    }
+                }
+                reason
+            }
+            PushCheckRule::ForeignAuthor => (commit.author().email != settings.user_email())
+                .then(|| format!("it is authored by {}", commit.author().email)),
+            PushCheckRule::MergeCommits => {
+                (commit.parent_ids().len() > 1).then(|| "it is a merge commit".to_owned())
+            }
+        };
+        Ok(reason)
+    }
+}
+
+/// Returns the files added or modified by the `commit` which match the
+/// `matcher`.
+fn changed_files(
+    repo: &dyn Repo,
+    commit: &Commit,
+    matcher: &dyn Matcher,
+) -> Result<Vec<(RepoPathBuf, FileId)>, CommandError> {
+    let parent_tree = commit.parent_tree(repo)?;
+    let tree = commit.tree()?;
+    let mut files = vec![];
+    let mut diff_stream = parent_tree.diff_stream(&tree, matcher);
+    while let Some(TreeDiffEntry { path, values }) = diff_stream.next().block_on() {
+        let (_, after) = values?;
+        if let Some(Some(TreeValue::File { id, .. })) = after.as_resolved() {
+            files.push((path, id.clone()));
+        }
+    }
+    Ok(files)
+}
+
+/// Parses the `git.push-checks` config table.
+fn load_push_checks(ui: &Ui, settings: &UserSettings) -> Result<Vec<PushCheck>, CommandError> {
+    settings
+        .table_keys("git.push-checks")
+        // Sort keys early so errors and reports are deterministic.
+        .sorted()
+        .map(|name| -> Result<PushCheck, CommandError> {
+            let severity = settings
+                .get(["git", "push-checks", name, "severity"])
+                .optional()?
+                .unwrap_or_default();
+            let rule = match name {
+                "description" => {
+                    let pattern: String = settings.get(["git", "push-checks", name, "pattern"])?;
+                    let regex = Regex::new(&pattern).map_err(|err| {
+                        config_error_with_message(
+                            "Invalid regex in `git.push-checks.description.pattern`",
+                            err,
+                        )
+                    })?;
+                    PushCheckRule::Description(regex)
+                }
+                "forbidden-paths" => {
+                    let patterns: Vec<String> =
+                        settings.get(["git", "push-checks", name, "patterns"])?;
+                    let mut diagnostics = FilesetDiagnostics::new();
+                    let expression = FilesetExpression::union_all(
+                        patterns
+                            .iter()
+                            .map(|arg| {
+                                fileset::parse(
+                                    &mut diagnostics,
+                                    arg,
+                                    &RepoPathUiConverter::Fs {
+                                        cwd: "".into(),
+                                        base: "".into(),
+                                    },
+                                )
+                            })
+                            .try_collect()?,
+                    );
+                    print_parse_diagnostics(
+                        ui,
+                        "In `git.push-checks.forbidden-paths`",
+                        &diagnostics,
+                    )?;
+                    PushCheckRule::ForbiddenPaths(expression.to_matcher())
+                }
+                "max-file-size" => PushCheckRule::MaxFileSize(
+                    settings
+                        .get_value_with(["git", "push-checks", name, "size"], TryInto::try_into)?,
+                ),
+                "foreign-author" => PushCheckRule::ForeignAuthor,
+                "merge-commits" => PushCheckRule::MergeCommits,
+                _ => {
+                    return Err(config_error(format!(
+                        "Unknown push check `git.push-checks.{name}`"
+                    )));
+                }
+            };
+            Ok(PushCheck {
+                name: name.to_owned(),
+                severity,
+                rule,
+            })
+        })
+        .try_collect()
+}
+
 /// Signs commits before pushing.
 ///
 /// Returns the number of commits with rebased descendants and the updated list
@@ -587,13 +1032,19 @@ fn print_commits_ready_to_push(
     Ok(())
 }
 
-fn get_default_push_remote(
+fn get_default_push_remotes(
     ui: &Ui,
     workspace_command: &WorkspaceCommandHelper,
-) -> Result<String, CommandError> {
+) -> Result<Vec<String>, CommandError> {
+    const KEY: &str = "git.push";
     let settings = workspace_command.settings();
-    if let Some(remote) = settings.get_string("git.push").optional()? {
-        Ok(remote)
+    if let Ok(remotes) = settings.get::<Vec<String>>(KEY) {
+        if remotes.is_empty() {
+            return Err(config_error(format!("`{KEY}` must not be an empty list")));
+        }
+        Ok(remotes.into_iter().unique().collect())
+    } else if let Some(remote) = settings.get_string(KEY).optional()? {
+        Ok(vec![remote])
     } else if let Some(remote) = get_single_remote(workspace_command.repo().store())? {
         // similar to get_default_fetch_remotes
         if remote != DEFAULT_REMOTE {
@@ -602,9 +1053,45 @@ fn get_default_push_remote(
                 "Pushing to the only existing remote: {remote}"
             )?;
         }
-        Ok(remote)
+        Ok(vec![remote])
     } else {
-        Ok(DEFAULT_REMOTE.to_owned())
+        Ok(vec![DEFAULT_REMOTE.to_owned()])
+    }
+}
+
+/// Returns the remote configured by `git.bookmark-push-remotes` for the
+/// bookmarks selected by `--bookmark`, or `None` if the default push remote
+/// should be used.
+fn get_bookmark_push_remote(
+    workspace_command: &WorkspaceCommandHelper,
+    bookmark_patterns: &[StringPattern],
+) -> Result<Option<String>, CommandError> {
+    let push_remotes = BookmarkPushRemotes::from_settings(workspace_command.settings())?;
+    let view = workspace_command.repo().view();
+    let mut remotes_by_bookmark = vec![];
+    for pattern in bookmark_patterns {
+        for (bookmark_name, _) in view.local_bookmarks_matching(pattern) {
+            remotes_by_bookmark.push((bookmark_name, push_remotes.get(bookmark_name)?));
+        }
+    }
+    let remotes: IndexSet<_> = remotes_by_bookmark
+        .iter()
+        .map(|(_, remote)| *remote)
+        .collect();
+    match remotes.len() {
+        0 => Ok(None),
+        1 => Ok(remotes[0].map(ToOwned::to_owned)),
+        _ => {
+            let mut error = user_error(
+                "The selected bookmarks are configured to be pushed to different remotes",
+            );
+            for (bookmark_name, remote) in remotes_by_bookmark.iter().unique() {
+                let remote = remote.unwrap_or("the default remote");
+                error.add_hint(format!("Bookmark {bookmark_name} is pushed to {remote}"));
+            }
+            error.add_hint("Use --remote to select the remote, or push the bookmarks separately.");
+            Err(error)
+        }
     }
 }
 
@@ -677,11 +1164,15 @@ fn classify_bookmark_update(
 }
 
 /// Creates or moves bookmarks based on the change IDs.
+///
+/// The bookmark names are generated by the `templates.git_push_bookmark`
+/// template if configured, or from the `bookmark_prefix` and the change IDs.
 fn update_change_bookmarks(
     ui: &Ui,
     tx: &mut WorkspaceCommandTransaction,
     changes: &[RevisionArg],
     bookmark_prefix: &str,
+    remote: &str,
 ) -> Result<Vec<String>, CommandError> {
     if changes.is_empty() {
         // NOTE: we don't want resolve_some_revsets_default_single to fail if the
@@ -689,30 +1180,115 @@ fn update_change_bookmarks(
         return Ok(vec![]);
     }
 
-    let mut bookmark_names = Vec::new();
     let workspace_command = tx.base_workspace_helper();
     let all_commits = workspace_command.resolve_some_revsets_default_single(ui, changes)?;
+    let template = tx
+        .settings()
+        .get_string("templates.git_push_bookmark")
+        .optional()?
+        .map(|text| workspace_command.parse_commit_template(ui, &text))
+        .transpose()?;
 
+    let view = tx.base_repo().view();
+    let mut commits_by_bookmark: IndexMap<String, Commit> = IndexMap::new();
     for commit in all_commits {
-        let workspace_command = tx.base_workspace_helper();
         let short_change_id = short_change_hash(commit.change_id());
-        let mut bookmark_name = format!("{bookmark_prefix}{}", commit.change_id().hex());
-        let view = tx.base_repo().view();
-        if view.get_local_bookmark(&bookmark_name).is_absent() {
-            // A local bookmark with the full change ID doesn't exist already, so use the
-            // short ID if it's not ambiguous (which it shouldn't be most of the time).
-            if workspace_command
-                .resolve_single_rev(ui, &RevisionArg::from(short_change_id.clone()))
-                .is_ok()
-            {
-                // Short change ID is not ambiguous, so update the bookmark name to use it.
-                bookmark_name = format!("{bookmark_prefix}{short_change_id}");
-            };
+        let bookmark_name = if let Some(template) = &template {
+            let mut output = Vec::new();
+            template
+                .format(&commit, &mut PlainTextFormatter::new(&mut output))
+                .expect("write() to vec backed formatter should never fail");
+            let bookmark_name = output.into_string_lossy().trim().to_owned();
+            if bookmark_name.is_empty() {
+                return Err(user_error(format!(
+                    "The templates.git_push_bookmark template generated an empty bookmark name \
+                     for revision {short_change_id}"
+                )));
+            }
+            bookmark_name
+        } else {
+            let mut bookmark_name = format!("{bookmark_prefix}{}", commit.change_id().hex());
+            if view.get_local_bookmark(&bookmark_name).is_absent() {
+                // A local bookmark with the full change ID doesn't exist already, so use the
+                // short ID if it's not ambiguous (which it shouldn't be most of the time).
+                if workspace_command
+                    .resolve_single_rev(ui, &RevisionArg::from(short_change_id.clone()))
+                    .is_ok()
+                {
+                    // Short change ID is not ambiguous, so update the bookmark name to use it.
+                    bookmark_name = format!("{bookmark_prefix}{short_change_id}");
+                };
+            }
+            bookmark_name
+        };
+        if !git2::Reference::is_valid_name(&format!("refs/heads/{bookmark_name}")) {
+            return Err(user_error(format!(
+                "Invalid generated bookmark name {bookmark_name} for revision {short_change_id}"
+            )));
+        }
+        // A tracked remote bookmark was likely created for the same change,
+        // but an untracked remote bookmark belongs to someone else.
+        let local_target = view.get_local_bookmark(&bookmark_name);
+        let remote_ref = view.get_remote_bookmark(RemoteRefSymbol {
+            name: &bookmark_name,
+            remote,
+        });
+        if local_target.is_absent() && remote_ref.is_present() && !remote_ref.is_tracking() {
+            return Err(user_error_with_hint(
+                format!(
+                    "Generated bookmark name {bookmark_name} for revision {short_change_id} \
+                     already exists on remote {remote}"
+                ),
+                "Create a bookmark with a different name, and push it with --bookmark instead.",
+            ));
+        }
+        // Names generated by the template might not be specific to the change,
+        // so only move an existing local bookmark if it was created for the
+        // same change. Otherwise e.g. the main bookmark could be moved.
+        if template.is_some() && local_target.is_present() {
+            let target_commit = local_target
+                .as_normal()
+                .map(|id| tx.base_repo().store().get_commit(id))
+                .transpose()?;
+            if target_commit.is_none_or(|target| target.change_id() != commit.change_id()) {
+                return Err(user_error_with_hint(
+                    format!(
+                        "Generated bookmark name {bookmark_name} for revision {short_change_id} \
+                         is already used by another change"
+                    ),
+                    "Create a bookmark with a different name, and push it with --bookmark instead.",
+                ));
+            }
+        }
+        match commits_by_bookmark.entry(bookmark_name) {
+            indexmap::map::Entry::Occupied(entry) if entry.get().id() != commit.id() => {
+                return Err(user_error(format!(
+                    "Generated bookmark name {} for revision {short_change_id} is also used for \
+                     revision {}",
+                    entry.key(),
+                    short_change_hash(entry.get().change_id())
+                )));
+            }
+            indexmap::map::Entry::Occupied(_) => {}
+            indexmap::map::Entry::Vacant(entry) => {
+                entry.insert(commit);
+            }
         }
-        if view.get_local_bookmark(&bookmark_name).is_absent() {
+    }
+    drop(template);
+
+    let mut bookmark_names = Vec::new();
+    for (bookmark_name, commit) in commits_by_bookmark {
+        if tx
+            .repo()
+            .view()
+            .get_local_bookmark(&bookmark_name)
+            .is_absent()
+        {
             writeln!(
                 ui.status(),
-                "Creating bookmark {bookmark_name} for revision {short_change_id}",
+                "Creating bookmark {bookmark_name} for revision {}",
+                short_change_hash(commit.change_id())
             )?;
         }
         tx.repo_mut()
@@ -722,6 +1298,89 @@ fn update_change_bookmarks(
     Ok(bookmark_names)
 }
 
+/// Creates or moves bookmarks named after the topics to the heads of the
+/// topics.
+fn update_topic_bookmarks(
+    ui: &Ui,
+    tx: &mut WorkspaceCommandTransaction,
+    topics: &[String],
+    bookmark_prefix: &str,
+    remote: &str,
+) -> Result<Vec<String>, CommandError> {
+    let mut bookmark_names = Vec::new();
+    for topic in topics {
+        let bookmark_name = format!("{bookmark_prefix}{topic}");
+        if !git2::Reference::is_valid_name(&format!("refs/heads/{bookmark_name}")) {
+            return Err(user_error(format!(
+                "Invalid bookmark name {bookmark_name} for topic {topic}"
+            )));
+        }
+        let expression = RevsetExpression::change_metadata(
+            TOPIC_METADATA_KEY.to_owned(),
+            StringPattern::exact(topic),
+        )
+        .heads();
+        let heads: Vec<Commit> = tx
+            .base_workspace_helper()
+            .attach_revset_evaluator(expression)
+            .evaluate_to_commits()?
+            .try_collect()?;
+        let head = match &heads[..] {
+            [] => return Err(user_error(format!("No changes in topic {topic}"))),
+            [head] => head,
+            _ => {
+                return Err(user_error_with_hint(
+                    format!("Topic {topic} has {} heads", heads.len()),
+                    "Rebase the changes in the topic onto each other, or push the heads \
+                     separately with --change.",
+                ));
+            }
+        };
+        let view = tx.base_repo().view();
+        let local_target = view.get_local_bookmark(&bookmark_name);
+        let remote_ref = view.get_remote_bookmark(RemoteRefSymbol {
+            name: &bookmark_name,
+            remote,
+        });
+        if local_target.is_absent() && remote_ref.is_present() && !remote_ref.is_tracking() {
+            return Err(user_error_with_hint(
+                format!(
+                    "Bookmark {bookmark_name} for topic {topic} already exists on remote {remote}"
+                ),
+                "Rename the topic, or push it with --bookmark instead.",
+            ));
+        }
+        // Only move a bookmark which was created for the topic, i.e. which
+        // points to a change in the topic.
+        if local_target.is_present() {
+            let target_commit = local_target
+                .as_normal()
+                .map(|id| tx.base_repo().store().get_commit(id))
+                .transpose()?;
+            let is_in_topic = target_commit
+                .is_some_and(|commit| view.get_topic(commit.change_id()) == Some(topic.as_str()));
+            if !is_in_topic {
+                return Err(user_error_with_hint(
+                    format!(
+                        "Bookmark {bookmark_name} already exists and doesn't point to a change in \
+                         topic {topic}"
+                    ),
+                    "Rename the topic, or push it with --bookmark instead.",
+                ));
+            }
+        } else {
+            writeln!(
+                ui.status(),
+                "Creating bookmark {bookmark_name} for topic {topic}"
+            )?;
+        }
+        tx.repo_mut()
+            .set_local_bookmark_target(&bookmark_name, RefTarget::normal(head.id().clone()));
+        bookmark_names.push(bookmark_name);
+    }
+    Ok(bookmark_names)
+}
+
 fn find_bookmarks_to_push<'a>(
     view: &'a View,
     bookmark_patterns: &[StringPattern],
@@ -16,6 +16,7 @@ mod add;
 mod list;
 mod remove;
 mod rename;
+mod set_head;
 mod set_url;
 
 use clap::Subcommand;
@@ -28,6 +29,8 @@ use self::remove::cmd_git_remote_remove;
 use self::remove::GitRemoteRemoveArgs;
 use self::rename::cmd_git_remote_rename;
 use self::rename::GitRemoteRenameArgs;
+use self::set_head::cmd_git_remote_set_head;
+use self::set_head::GitRemoteSetHeadArgs;
 use self::set_url::cmd_git_remote_set_url;
 use self::set_url::GitRemoteSetUrlArgs;
 use crate::cli_util::CommandHelper;
@@ -43,6 +46,7 @@ pub enum RemoteCommand {
     List(GitRemoteListArgs),
     Remove(GitRemoteRemoveArgs),
     Rename(GitRemoteRenameArgs),
+    SetHead(GitRemoteSetHeadArgs),
     SetUrl(GitRemoteSetUrlArgs),
 }
 
@@ -56,6 +60,7 @@ pub fn cmd_git_remote(
         RemoteCommand::List(args) => cmd_git_remote_list(ui, command, args),
         RemoteCommand::Remove(args) => cmd_git_remote_remove(ui, command, args),
         RemoteCommand::Rename(args) => cmd_git_remote_rename(ui, command, args),
+        RemoteCommand::SetHead(args) => cmd_git_remote_set_head(ui, command, args),
         RemoteCommand::SetUrl(args) => cmd_git_remote_set_url(ui, command, args),
     }
 }
@@ -0,0 +1,140 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap_complete::ArgValueCandidates;
+use jj_lib::config::ConfigFile;
+use jj_lib::config::ConfigSource;
+use jj_lib::git;
+use jj_lib::git::GitRemoteManagementError;
+use jj_lib::refs::RemoteRefSymbol;
+use jj_lib::repo::Repo as _;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::user_error;
+use crate::command_error::CommandError;
+use crate::commands::git::write_repository_level_trunk_alias;
+use crate::complete;
+use crate::git_util::get_git_repo;
+use crate::git_util::with_remote_git_callbacks;
+use crate::ui::Ui;
+
+/// Set the default bookmark of a Git remote
+///
+/// The default bookmark is recorded as `refs/remotes/<REMOTE>/HEAD` in the
+/// underlying Git repo, like `git remote set-head` does. `jj git clone` sets
+/// it to the default bookmark of the cloned remote.
+///
+/// If the revset alias `trunk()` isn't configured for the repository, or is
+/// set to a bookmark of the same remote, it is updated to point to the new
+/// default bookmark. The alias isn't updated if it's configured in the user
+/// config.
+#[derive(clap::Args, Clone, Debug)]
+pub struct GitRemoteSetHeadArgs {
+    /// The remote's name
+    #[arg(add = ArgValueCandidates::new(complete::git_remotes))]
+    remote: String,
+    /// The remote bookmark to set as the default
+    #[arg(required_unless_present_any = ["auto", "delete"])]
+    bookmark: Option<String>,
+    /// Query the remote for its default bookmark
+    #[arg(long, conflicts_with_all = ["bookmark", "delete"])]
+    auto: bool,
+    /// Delete the recorded default bookmark
+    #[arg(long, conflicts_with = "bookmark")]
+    delete: bool,
+}
+
+pub fn cmd_git_remote_set_head(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &GitRemoteSetHeadArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let git_repo = get_git_repo(workspace_command.repo().store())?;
+    if git_repo.find_remote(&args.remote).is_err() {
+        return Err(GitRemoteManagementError::NoSuchRemote(args.remote.clone()).into());
+    }
+    if args.delete {
+        git::set_remote_head(&git_repo, &args.remote, None)?;
+        return Ok(());
+    }
+
+    let bookmark = if args.auto {
+        let git_settings = workspace_command.settings().git_settings()?;
+        let store = workspace_command.repo().store();
+        with_remote_git_callbacks(ui, |cb| {
+            git::get_remote_default_branch(store, &git_settings, &args.remote, cb)
+        })?
+        .ok_or_else(|| {
+            user_error(format!(
+                "Cannot determine the default bookmark of remote '{}'",
+                args.remote
+            ))
+        })?
+    } else {
+        args.bookmark
+            .clone()
+            .expect("clap requires bookmark unless --auto/--delete")
+    };
+    let symbol = RemoteRefSymbol {
+        name: &bookmark,
+        remote: &args.remote,
+    };
+    if workspace_command
+        .repo()
+        .view()
+        .get_remote_bookmark(symbol)
+        .is_absent()
+    {
+        return Err(user_error(format!("No such remote bookmark: {symbol}")));
+    }
+    git::set_remote_head(&git_repo, &args.remote, Some(&bookmark))?;
+
+    let repo_path = workspace_command.repo_path();
+    let config_file = ConfigFile::load_or_empty(ConfigSource::Repo, repo_path.join("config.toml"))?;
+    let update_trunk_alias = match config_file
+        .layer()
+        .look_up_item(["revset-aliases", "trunk()"])
+    {
+        Ok(None) => true,
+        Ok(Some(item)) => item
+            .as_str()
+            .and_then(|value| value.rsplit_once('@'))
+            .is_some_and(|(_, remote)| remote.trim_matches('"') == args.remote),
+        Err(_) => false,
+    };
+    // The repository-level alias would override the user's alias.
+    let user_trunk_alias = workspace_command
+        .settings()
+        .config()
+        .layers()
+        .iter()
+        .filter(|layer| layer.source == ConfigSource::User)
+        .any(|layer| {
+            matches!(
+                layer.look_up_item(["revset-aliases", "trunk()"]),
+                Ok(Some(_))
+            )
+        });
+    if update_trunk_alias && user_trunk_alias {
+        writeln!(
+            ui.warning_default(),
+            "Not setting the revset alias `trunk()` to `{symbol}` since it is configured in the \
+             user config"
+        )?;
+    } else if update_trunk_alias {
+        write_repository_level_trunk_alias(ui, repo_path, symbol)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,77 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use jj_lib::git;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::repo::Repo as _;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::user_error;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Apply an entry of the Git stash to the working-copy commit
+///
+/// The changes recorded in the stash entry are merged into the working-copy
+/// commit, like `git stash apply` does. Conflicts are recorded in the
+/// working-copy commit. The entry isn't removed from the stash.
+#[derive(clap::Args, Clone, Debug)]
+pub struct GitStashApplyArgs {
+    /// The stash entry to apply, as in `stash@{INDEX}`
+    #[arg(default_value_t = 0)]
+    index: usize,
+}
+
+pub fn cmd_git_stash_apply(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &GitStashApplyArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let store = workspace_command.repo().store().clone();
+    let entries = git::get_stash_entries(&store)?;
+    let entry = entries
+        .get(args.index)
+        .ok_or_else(|| user_error(format!("No stash entry stash@{{{}}}", args.index)))?;
+    let wc_commit_id = workspace_command
+        .get_wc_commit_id()
+        .ok_or_else(|| user_error("This command requires a working copy"))?
+        .clone();
+    workspace_command.check_rewritable([&wc_commit_id])?;
+    let wc_commit = store.get_commit(&wc_commit_id)?;
+
+    let (base_commit, stash_tree) = git::get_stash_contents(&store, entry)?;
+    let new_tree = wc_commit.tree()?.merge(&base_commit.tree()?, &stash_tree)?;
+    if new_tree.id() == *wc_commit.tree_id() {
+        writeln!(ui.status(), "Nothing changed.")?;
+        return Ok(());
+    }
+    let mut tx = workspace_command.start_transaction();
+    tx.repo_mut()
+        .rewrite_commit(&wc_commit)
+        .set_tree_id(new_tree.id())
+        .write()?;
+    tx.rebase_descendants()?;
+    tx.finish(
+        ui,
+        format!(
+            "apply git stash entry stash@{{{}}} ({})",
+            args.index,
+            entry.commit_id.hex()
+        ),
+    )?;
+    Ok(())
+}
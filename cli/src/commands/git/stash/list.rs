@@ -0,0 +1,48 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use jj_lib::git;
+use jj_lib::repo::Repo as _;
+
+use crate::cli_util::short_commit_hash;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// List entries of the Git stash
+///
+/// The newest entry is listed first as `stash@{0}`.
+#[derive(clap::Args, Clone, Debug)]
+pub struct GitStashListArgs {}
+
+pub fn cmd_git_stash_list(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &GitStashListArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let entries = git::get_stash_entries(workspace_command.repo().store())?;
+    let mut formatter = ui.stdout_formatter();
+    for (index, entry) in entries.iter().enumerate() {
+        writeln!(
+            formatter,
+            "stash@{{{index}}}: {} {}",
+            short_commit_hash(&entry.commit_id),
+            entry.message
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,48 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod apply;
+mod list;
+
+use clap::Subcommand;
+
+use self::apply::cmd_git_stash_apply;
+use self::apply::GitStashApplyArgs;
+use self::list::cmd_git_stash_list;
+use self::list::GitStashListArgs;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Inspect and apply entries of the Git stash
+///
+/// Changes stashed by `git stash` in the underlying Git repo aren't visible in
+/// jj. These commands can be used to recover them. Set `git.import-stash` to
+/// import the stash entries as hidden commits on `jj git import`.
+#[derive(Subcommand, Clone, Debug)]
+pub enum GitStashCommand {
+    Apply(GitStashApplyArgs),
+    List(GitStashListArgs),
+}
+
+pub fn cmd_git_stash(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &GitStashCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        GitStashCommand::Apply(args) => cmd_git_stash_apply(ui, command, args),
+        GitStashCommand::List(args) => cmd_git_stash_list(ui, command, args),
+    }
+}
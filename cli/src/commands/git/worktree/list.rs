@@ -0,0 +1,70 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use itertools::Itertools as _;
+use jj_lib::file_util;
+use jj_lib::git;
+use jj_lib::repo::Repo as _;
+
+use super::find_mapped_workspace;
+use crate::cli_util::short_commit_hash;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// List worktrees of the underlying Git repo
+///
+/// Shows the branch or commit checked out in each worktree, and the jj
+/// workspace the worktree is mapped to, if any. The main worktree isn't
+/// listed.
+#[derive(clap::Args, Clone, Debug)]
+pub struct GitWorktreeListArgs {}
+
+pub fn cmd_git_worktree_list(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &GitWorktreeListArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let worktrees = git::get_worktrees(workspace_command.repo().store())?;
+    let mut formatter = ui.stdout_formatter();
+    for worktree in &worktrees {
+        let path = match &worktree.path {
+            Some(path) => file_util::relative_path(command.cwd(), path)
+                .display()
+                .to_string(),
+            None => "<unknown path>".to_owned(),
+        };
+        let mut details = vec![];
+        match (&worktree.branch, &worktree.head_id) {
+            (Some(branch), _) => details.push(format!("branch {branch}")),
+            (None, Some(id)) => details.push(format!("detached at {}", short_commit_hash(id))),
+            (None, None) => {}
+        }
+        if let Some(workspace_id) = find_mapped_workspace(command, &workspace_command, worktree) {
+            details.push(format!("workspace {}", workspace_id.as_str()));
+        }
+        if worktree.is_locked {
+            details.push("locked".to_owned());
+        }
+        write!(formatter, "{}: {path}", worktree.name)?;
+        if !details.is_empty() {
+            write!(formatter, " ({})", details.iter().join(", "))?;
+        }
+        writeln!(formatter)?;
+    }
+    Ok(())
+}
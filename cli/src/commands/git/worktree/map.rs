@@ -0,0 +1,133 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use jj_lib::file_util;
+use jj_lib::git;
+use jj_lib::op_store::WorkspaceId;
+use jj_lib::repo::Repo as _;
+use jj_lib::workspace::Workspace;
+
+use super::find_mapped_workspace;
+use crate::cli_util::CommandHelper;
+use crate::command_error::user_error;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Create jj workspaces in Git worktrees
+///
+/// A workspace named after the worktree is created in the worktree directory.
+/// Its working-copy commit is created on top of the commit checked out in the
+/// worktree, and the files in the worktree are left as they are.
+#[derive(clap::Args, Clone, Debug)]
+pub struct GitWorktreeMapArgs {
+    /// Names of the worktrees to map (defaults to all unmapped worktrees)
+    #[arg(value_name = "WORKTREE")]
+    names: Vec<String>,
+}
+
+pub fn cmd_git_worktree_map(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &GitWorktreeMapArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let worktrees = git::get_worktrees(workspace_command.repo().store())?;
+    if let Some(name) = args
+        .names
+        .iter()
+        .find(|name| !worktrees.iter().any(|worktree| &worktree.name == *name))
+    {
+        return Err(user_error(format!("No Git worktree named '{name}'")));
+    }
+
+    // Each mapping is recorded on top of the operation of the previous one.
+    let mut repo = workspace_command.repo().clone();
+    let mut num_mapped = 0;
+    for worktree in &worktrees {
+        let is_requested = args.names.contains(&worktree.name);
+        if !args.names.is_empty() && !is_requested {
+            continue;
+        }
+        if let Some(workspace_id) = find_mapped_workspace(command, &workspace_command, worktree) {
+            if is_requested {
+                writeln!(
+                    ui.warning_default(),
+                    "Worktree {} is already mapped to workspace {}",
+                    worktree.name,
+                    workspace_id.as_str()
+                )?;
+            }
+            continue;
+        }
+        let Some(path) = worktree.path.as_ref().filter(|path| path.is_dir()) else {
+            writeln!(
+                ui.warning_default(),
+                "Skipping worktree {} whose directory doesn't exist",
+                worktree.name
+            )?;
+            continue;
+        };
+        if path.join(".jj").exists() {
+            return Err(user_error(format!(
+                "Worktree {} already contains a workspace of another repo",
+                worktree.name
+            )));
+        }
+        let workspace_id = WorkspaceId::new(worktree.name.clone());
+        if repo.view().get_wc_commit_id(&workspace_id).is_some() {
+            return Err(user_error(format!(
+                "Workspace named '{}' already exists",
+                worktree.name
+            )));
+        }
+
+        let (new_workspace, new_repo) = Workspace::init_workspace_with_existing_repo(
+            path,
+            workspace_command.repo_path(),
+            &repo,
+            command.get_working_copy_factory()?,
+            workspace_id.clone(),
+        )?;
+        let mut new_workspace_command = command.for_workable_repo(ui, new_workspace, new_repo)?;
+        let head_commit = match &worktree.head_id {
+            Some(id) => new_workspace_command.repo().store().get_commit(id)?,
+            None => new_workspace_command.repo().store().root_commit(),
+        };
+        let mut tx = new_workspace_command.start_transaction().into_inner();
+        let new_wc_commit = tx.repo_mut().check_out(workspace_id, &head_commit)?;
+        let (mut locked_ws, _wc_commit) = new_workspace_command.start_working_copy_mutation()?;
+        // The files were checked out by Git, so only the working-copy state
+        // needs to be updated.
+        locked_ws.locked_wc().reset(&new_wc_commit)?;
+        tx.repo_mut().rebase_descendants()?;
+        repo = tx.commit(format!(
+            "create workspace {} in git worktree",
+            worktree.name
+        ))?;
+        locked_ws.finish(repo.op_id().clone())?;
+        writeln!(
+            ui.status(),
+            "Mapped worktree {} to workspace in \"{}\"",
+            worktree.name,
+            file_util::relative_path(command.cwd(), path).display()
+        )?;
+        num_mapped += 1;
+    }
+    if num_mapped == 0 && args.names.is_empty() {
+        writeln!(ui.status(), "Nothing changed.")?;
+    }
+    Ok(())
+}
@@ -0,0 +1,71 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod list;
+mod map;
+
+use clap::Subcommand;
+use jj_lib::git::GitWorktree;
+use jj_lib::op_store::WorkspaceId;
+
+use self::list::cmd_git_worktree_list;
+use self::list::GitWorktreeListArgs;
+use self::map::cmd_git_worktree_map;
+use self::map::GitWorktreeMapArgs;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Manage worktrees of the underlying Git repo
+///
+/// Worktrees created by `git worktree add` share the Git repo, so the
+/// branches checked out in them are updated by jj like any other branch. If
+/// jj moves or deletes such a branch, the HEAD of the worktree is detached at
+/// its current commit.
+///
+/// A Git worktree can be mapped to a jj workspace with `jj git worktree map`.
+/// The workspace isn't colocated with the worktree, so Git's HEAD and index of
+/// the worktree aren't updated by jj.
+#[derive(Subcommand, Clone, Debug)]
+pub enum GitWorktreeCommand {
+    List(GitWorktreeListArgs),
+    Map(GitWorktreeMapArgs),
+}
+
+pub fn cmd_git_worktree(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &GitWorktreeCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        GitWorktreeCommand::List(args) => cmd_git_worktree_list(ui, command, args),
+        GitWorktreeCommand::Map(args) => cmd_git_worktree_map(ui, command, args),
+    }
+}
+
+/// Returns the jj workspace of this repo located in the worktree directory.
+fn find_mapped_workspace(
+    command: &CommandHelper,
+    workspace_command: &WorkspaceCommandHelper,
+    worktree: &GitWorktree,
+) -> Option<WorkspaceId> {
+    let path = worktree.path.as_ref()?;
+    if !path.join(".jj").is_dir() {
+        return None;
+    }
+    let workspace = command.load_workspace_at(path, command.settings()).ok()?;
+    (workspace.repo_path() == workspace_command.repo_path())
+        .then(|| workspace.workspace_id().clone())
+}
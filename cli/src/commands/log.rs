@@ -12,25 +12,41 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::sync::Arc;
+
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
 use itertools::Itertools as _;
+use jj_lib::backend::BackendError;
 use jj_lib::backend::CommitId;
+use jj_lib::backend::MillisSinceEpoch;
+use jj_lib::commit::Commit;
 use jj_lib::config::ConfigGetError;
 use jj_lib::config::ConfigGetResultExt as _;
 use jj_lib::graph::reverse_graph;
 use jj_lib::graph::GraphEdge;
 use jj_lib::graph::GraphEdgeType;
+use jj_lib::graph::GraphNode;
 use jj_lib::graph::TopoGroupedGraphIterator;
+use jj_lib::object_id::ObjectId as _;
 use jj_lib::repo::Repo;
+use jj_lib::revset::Revset;
 use jj_lib::revset::RevsetEvaluationError;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::revset::RevsetFilterPredicate;
 use jj_lib::revset::RevsetIteratorExt;
 use jj_lib::settings::UserSettings;
+use jj_lib::store::Store;
+use jj_lib::str_util::StringPattern;
 use tracing::instrument;
 
 use crate::cli_util::format_template;
+use crate::cli_util::format_template_null_terminated;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::LogContentFormat;
 use crate::cli_util::RevisionArg;
@@ -38,8 +54,13 @@ use crate::command_error::CommandError;
 use crate::commit_templater::CommitTemplateLanguage;
 use crate::complete;
 use crate::diff_util::DiffFormatArgs;
+use crate::diff_util::DiffMergesMode;
+use crate::formatter::Formatter;
+use crate::formatter::PlainTextFormatter;
 use crate::graphlog::get_graphlog;
+use crate::graphlog::EdgeStyle;
 use crate::graphlog::GraphStyle;
+use crate::templater::TemplateRenderer;
 use crate::ui::Ui;
 
 /// Show revision history
@@ -84,6 +105,18 @@ pub(crate) struct LogArgs {
         add = ArgValueCompleter::new(complete::log_files),
     )]
     paths: Vec<String>,
+    /// Show revisions whose diff adds or removes lines matching the regex
+    ///
+    /// If paths are specified, only the diffs of these paths are searched.
+    /// This is equivalent to intersecting the revisions with
+    /// `diff_contains(regex:REGEX, FILESETS)`.
+    #[arg(
+        long,
+        short = 'G',
+        value_name = "REGEX",
+        value_parser = StringPattern::regex
+    )]
+    diff_contains: Option<StringPattern>,
     /// Limit number of revisions to show
     ///
     /// Applied after revisions are filtered and reordered topologically, but
@@ -93,9 +126,28 @@ pub(crate) struct LogArgs {
     /// Show revisions in the opposite order (older revisions first)
     #[arg(long)]
     reversed: bool,
+    /// How to order the revisions
+    ///
+    /// Children are always shown before their parents. If not specified, this
+    /// defaults to the `ui.log-order` setting.
+    #[arg(long, value_enum)]
+    order: Option<LogOrder>,
+    /// Follow only the first parent of merge commits
+    ///
+    /// Revisions which are only reachable through the other parents of merges
+    /// are omitted.
+    #[arg(long)]
+    first_parent: bool,
     /// Don't show the graph, show a flat list of revisions
     #[arg(long)]
     no_graph: bool,
+    /// Terminate each revision with a NUL byte instead of a newline
+    ///
+    /// Implies `--no-graph`. The template output is printed without color or
+    /// wrapping, and its trailing newline is replaced with a NUL byte, so the
+    /// output can be safely consumed by `xargs -0` and the like.
+    #[arg(long = "null", short = 'z', conflicts_with_all = ["patch", "format"])]
+    null_terminated: bool,
     /// Render each revision using the given template
     ///
     /// Run `jj log -T` to list the built-in templates.
@@ -114,10 +166,51 @@ pub(crate) struct LogArgs {
     #[arg(long, short = 'T', add = ArgValueCandidates::new(complete::template_aliases))]
     template: Option<String>,
     /// Show patch
+    ///
+    /// By default, merge commits are compared to the automatic merge of their
+    /// parents, so the patch shows how conflicts were resolved. See
+    /// `--diff-merges` for other ways to show merge commits.
     #[arg(long, short = 'p')]
     patch: bool,
+    /// How to show changes in merge commits
+    #[arg(long, value_enum, value_name = "MODE", default_value_t = DiffMergesMode::Remerge)]
+    diff_merges: DiffMergesMode,
     #[command(flatten)]
     diff_format: DiffFormatArgs,
+    /// Export the graph in the given format instead of drawing it
+    ///
+    /// The graph is emitted as a [Graphviz] DOT or a [Mermaid] flowchart
+    /// document, which can be rendered by external tools or embedded in
+    /// documentation. Each revision is labeled with the output of the
+    /// template. Edges to revisions which were elided from the graph are
+    /// dashed.
+    ///
+    /// [Graphviz]: https://graphviz.org/
+    ///
+    /// [Mermaid]: https://mermaid.js.org/
+    #[arg(long, value_enum, value_name = "FORMAT", conflicts_with_all = ["no_graph", "patch"])]
+    format: Option<GraphExportFormat>,
+}
+
+/// Order of the revisions in the log
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum LogOrder {
+    /// Keep the revisions of each topological branch together
+    Topo,
+    /// Show revisions with newer committer dates first
+    Date,
+    /// Show revisions with newer author dates first
+    AuthorDate,
+}
+
+/// Format of the exported graph
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub(crate) enum GraphExportFormat {
+    /// Graphviz DOT language
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
 }
 
 #[instrument(skip_all)]
@@ -131,22 +224,34 @@ pub(crate) fn cmd_log(
 
     let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
     let revset_expression = {
-        // only use default revset if neither revset nor path are specified
-        let mut expression = if args.revisions.is_empty() && args.paths.is_empty() {
-            let revset_string = settings.get_string("revsets.log")?;
-            workspace_command.parse_revset(ui, &RevisionArg::from(revset_string))?
-        } else if !args.revisions.is_empty() {
-            workspace_command.parse_union_revsets(ui, &args.revisions)?
-        } else {
-            // a path was specified so we use all() and add path filter later
-            workspace_command.attach_revset_evaluator(RevsetExpression::all())
-        };
+        // only use default revset if neither revset nor path nor diff pattern
+        // are specified
+        let mut expression =
+            if args.revisions.is_empty() && args.paths.is_empty() && args.diff_contains.is_none() {
+                let revset_string = settings.get_string("revsets.log")?;
+                workspace_command.parse_revset(ui, &RevisionArg::from(revset_string))?
+            } else if !args.revisions.is_empty() {
+                workspace_command.parse_union_revsets(ui, &args.revisions)?
+            } else {
+                // a path or diff pattern was specified so we use all() and add
+                // filters later
+                workspace_command.attach_revset_evaluator(RevsetExpression::all())
+            };
         if !args.paths.is_empty() {
             // Beware that args.paths = ["root:."] is not identical to []. The
             // former will filter out empty commits.
             let predicate = RevsetFilterPredicate::File(fileset_expression.clone());
             expression.intersect_with(&RevsetExpression::filter(predicate));
         }
+        if let Some(pattern) = &args.diff_contains {
+            // The path filter above is cheaper, so the diffs are only searched
+            // in revisions which modify the given paths.
+            let predicate = RevsetFilterPredicate::DiffContains {
+                text: pattern.clone(),
+                files: fileset_expression.clone(),
+            };
+            expression.intersect_with(&RevsetExpression::filter(predicate));
+        }
         expression
     };
 
@@ -159,10 +264,15 @@ pub(crate) fn cmd_log(
     let graph_style = GraphStyle::from_settings(settings)?;
 
     let use_elided_nodes = settings.get_bool("ui.log-synthetic-elided-nodes")?;
+    let order = match args.order {
+        Some(order) => order,
+        None => settings.get("ui.log-order")?,
+    };
     let with_content_format = LogContentFormat::new(ui, settings)?;
 
     let template;
     let node_template;
+    let edge_template;
     {
         let language = workspace_command.commit_template_language();
         let template_string = match &args.template {
@@ -185,6 +295,18 @@ pub(crate) fn cmd_log(
                 CommitTemplateLanguage::wrap_commit_opt,
             )?
             .labeled("node");
+        edge_template = settings
+            .get_string("templates.log_edge")
+            .optional()?
+            .map(|template_string| {
+                workspace_command.parse_template(
+                    ui,
+                    &language,
+                    &template_string,
+                    CommitTemplateLanguage::wrap_commit_opt,
+                )
+            })
+            .transpose()?;
     }
 
     {
@@ -192,28 +314,31 @@ pub(crate) fn cmd_log(
         let mut formatter = ui.stdout_formatter();
         let formatter = formatter.as_mut();
 
-        if !args.no_graph {
+        if let Some(export_format) = args.format {
+            let nodes: Vec<_> = iter_sorted_log_graph(
+                repo.as_ref(),
+                workspace_command.get_wc_commit_id(),
+                revset.as_ref(),
+                order,
+                args,
+            )?
+            .try_collect()?;
+            write_graph_export(formatter, export_format, store, &template, nodes)?;
+        } else if !args.no_graph && !args.null_terminated {
             let mut raw_output = formatter.raw()?;
             let mut graph = get_graphlog(graph_style, raw_output.as_mut());
-            let iter: Box<dyn Iterator<Item = _>> = {
-                let mut forward_iter = TopoGroupedGraphIterator::new(revset.iter_graph());
-                // Emit the working-copy branch first, which is usually most
-                // interesting. This also helps stabilize output order.
-                if let Some(id) = workspace_command.get_wc_commit_id() {
-                    let has_commit = revset.containing_fn();
-                    if has_commit(id)? {
-                        forward_iter.prioritize_branch(id.clone());
-                    }
-                }
-                // The input to TopoGroupedGraphIterator shouldn't be truncated
-                // because the prioritized commit must exist in the input set.
-                let forward_iter = forward_iter.take(args.limit.unwrap_or(usize::MAX));
-                if args.reversed {
-                    Box::new(reverse_graph(forward_iter, |id| id)?.into_iter().map(Ok))
-                } else {
-                    Box::new(forward_iter)
-                }
+            let mut edge_styles = HashMap::new();
+            let mut edge_style_for = |commit: Option<Commit>| {
+                let template = edge_template.as_ref()?;
+                get_edge_style(ui, template, commit, &mut edge_styles)
             };
+            let iter = iter_sorted_log_graph(
+                repo.as_ref(),
+                workspace_command.get_wc_commit_id(),
+                revset.as_ref(),
+                order,
+                args,
+            )?;
             for node in iter {
                 let (commit_id, edges) = node?;
 
@@ -258,21 +383,23 @@ pub(crate) fn cmd_log(
                 }
                 if let Some(renderer) = &diff_renderer {
                     let mut formatter = ui.new_formatter(&mut buffer);
-                    renderer.show_patch(
+                    renderer.show_patch_with_diff_merges(
                         ui,
                         formatter.as_mut(),
                         &commit,
                         matcher.as_ref(),
                         within_graph.width(),
+                        args.diff_merges,
                     )?;
                 }
 
-                let node_symbol = format_template(ui, &Some(commit), &node_template);
-                graph.add_node(
+                let node_symbol = format_template(ui, &Some(commit.clone()), &node_template);
+                graph.add_styled_node(
                     &key,
                     &graphlog_edges,
                     &node_symbol,
                     &String::from_utf8_lossy(&buffer),
+                    edge_style_for(Some(commit)),
                 )?;
                 for elided_target in elided_targets {
                     let elided_key = (elided_target, true);
@@ -285,17 +412,30 @@ pub(crate) fn cmd_log(
                         writeln!(formatter.labeled("elided"), "(elided revisions)")
                     })?;
                     let node_symbol = format_template(ui, &None, &node_template);
-                    graph.add_node(
+                    graph.add_styled_node(
                         &elided_key,
                         &edges,
                         &node_symbol,
                         &String::from_utf8_lossy(&buffer),
+                        edge_style_for(None),
                     )?;
                 }
             }
         } else {
             let iter: Box<dyn Iterator<Item = Result<CommitId, RevsetEvaluationError>>> = {
-                let forward_iter = revset.iter().take(args.limit.unwrap_or(usize::MAX));
+                let forward_iter: Box<dyn Iterator<Item = _>> = match order {
+                    LogOrder::Topo if !args.first_parent => revset.iter(),
+                    LogOrder::Topo => {
+                        let graph_iter = iter_log_graph(revset.as_ref(), args.first_parent);
+                        Box::new(graph_iter.map_ok(|(commit_id, _)| commit_id))
+                    }
+                    LogOrder::Date | LogOrder::AuthorDate => {
+                        let graph_iter = iter_log_graph(revset.as_ref(), args.first_parent);
+                        let sorted_iter = DateSortedGraphIterator::new(store, graph_iter, order);
+                        Box::new(sorted_iter.map_ok(|(commit_id, _)| commit_id))
+                    }
+                };
+                let forward_iter = forward_iter.take(args.limit.unwrap_or(usize::MAX));
                 if args.reversed {
                     let entries: Vec<_> = forward_iter.try_collect()?;
                     Box::new(entries.into_iter().rev().map(Ok))
@@ -305,16 +445,47 @@ pub(crate) fn cmd_log(
             };
             for commit_or_error in iter.commits(store) {
                 let commit = commit_or_error?;
+                if args.null_terminated {
+                    formatter.write_all(&format_template_null_terminated(&commit, &template))?;
+                    continue;
+                }
                 with_content_format
                     .write(formatter, |formatter| template.format(&commit, formatter))?;
                 if let Some(renderer) = &diff_renderer {
                     let width = ui.term_width();
-                    renderer.show_patch(ui, formatter, &commit, matcher.as_ref(), width)?;
+                    renderer.show_patch_with_diff_merges(
+                        ui,
+                        formatter,
+                        &commit,
+                        matcher.as_ref(),
+                        width,
+                        args.diff_merges,
+                    )?;
                 }
             }
         }
     }
 
+    #[cfg(feature = "git")]
+    if let (Some(mut formatter), Some(wc_commit_id)) =
+        (ui.status_formatter(), workspace_command.get_wc_commit_id())
+    {
+        let wc_commit = store.get_commit(wc_commit_id)?;
+        crate::git_util::write_remote_staleness_notice(
+            ui,
+            formatter.as_mut(),
+            &workspace_command,
+            &wc_commit,
+        )?;
+    }
+    #[cfg(feature = "git")]
+    crate::git_util::maybe_spawn_background_fetch(
+        ui,
+        workspace_command.settings(),
+        workspace_command.workspace_root(),
+        workspace_command.repo_path(),
+    )?;
+
     // Check to see if the user might have specified a path when they intended
     // to specify a revset.
     if let ([], [only_path]) = (args.revisions.as_slice(), args.paths.as_slice()) {
@@ -342,6 +513,280 @@ pub(crate) fn cmd_log(
     Ok(())
 }
 
+/// Returns the graph of the `revset` in the order and with the limit requested
+/// by the command arguments.
+fn iter_sorted_log_graph<'a>(
+    repo: &'a dyn Repo,
+    wc_commit_id: Option<&CommitId>,
+    revset: &'a dyn Revset,
+    order: LogOrder,
+    args: &LogArgs,
+) -> Result<LogGraphIter<'a>, CommandError> {
+    let graph_iter = iter_log_graph(revset, args.first_parent);
+    let forward_iter: LogGraphIter<'a> = match order {
+        LogOrder::Topo => {
+            let mut forward_iter = TopoGroupedGraphIterator::new(graph_iter);
+            // Emit the working-copy branch first, which is usually most
+            // interesting. This also helps stabilize output order. The
+            // working-copy commit might be filtered out by --first-parent, so
+            // it can't be prioritized then.
+            if let Some(id) = wc_commit_id {
+                let has_commit = revset.containing_fn();
+                if !args.first_parent && has_commit(id)? {
+                    forward_iter.prioritize_branch(id.clone());
+                }
+            }
+            Box::new(forward_iter)
+        }
+        LogOrder::Date | LogOrder::AuthorDate => Box::new(DateSortedGraphIterator::new(
+            repo.store(),
+            graph_iter,
+            order,
+        )),
+    };
+    // The input to TopoGroupedGraphIterator shouldn't be truncated because the
+    // prioritized commit must exist in the input set.
+    let forward_iter = forward_iter.take(args.limit.unwrap_or(usize::MAX));
+    if args.reversed {
+        Ok(Box::new(
+            reverse_graph(forward_iter, |id| id)?.into_iter().map(Ok),
+        ))
+    } else {
+        Ok(Box::new(forward_iter))
+    }
+}
+
+/// Writes the graph `nodes` as a Graphviz or Mermaid document, labeling each
+/// revision with the output of the `template`.
+fn write_graph_export(
+    formatter: &mut dyn Formatter,
+    format: GraphExportFormat,
+    store: &Arc<Store>,
+    template: &TemplateRenderer<Commit>,
+    nodes: Vec<GraphNode<CommitId>>,
+) -> Result<(), CommandError> {
+    let included: HashSet<&CommitId> = nodes.iter().map(|(id, _)| id).collect();
+    let mut labels = Vec::with_capacity(nodes.len());
+    for (id, _) in &nodes {
+        let commit = store.get_commit(id)?;
+        let mut output = vec![];
+        template.format(&commit, &mut PlainTextFormatter::new(&mut output))?;
+        let label = String::from_utf8_lossy(&output);
+        labels.push(label.trim_end_matches('\n').to_owned());
+    }
+    // Edges to revisions outside of the graph (because of --limit or a missing
+    // parent) aren't emitted.
+    let edges = nodes.iter().flat_map(|(id, edges)| {
+        edges
+            .iter()
+            .filter(|edge| included.contains(&edge.target))
+            .map(move |edge| (id, &edge.target, edge.edge_type))
+    });
+    match format {
+        GraphExportFormat::Dot => {
+            writeln!(formatter, "digraph {{")?;
+            writeln!(formatter, "    node [shape=box];")?;
+            for ((id, _), label) in nodes.iter().zip(&labels) {
+                let label: String = label
+                    .lines()
+                    .map(|line| {
+                        let line = line.replace('\\', "\\\\").replace('"', "\\\"");
+                        format!("{line}\\l")
+                    })
+                    .collect();
+                writeln!(formatter, "    \"{}\" [label=\"{label}\"];", id.hex())?;
+            }
+            for (source, target, edge_type) in edges {
+                let attributes = match edge_type {
+                    GraphEdgeType::Indirect => " [style=dashed]",
+                    GraphEdgeType::Direct | GraphEdgeType::Missing => "",
+                };
+                writeln!(
+                    formatter,
+                    "    \"{}\" -> \"{}\"{attributes};",
+                    source.hex(),
+                    target.hex()
+                )?;
+            }
+            writeln!(formatter, "}}")?;
+        }
+        GraphExportFormat::Mermaid => {
+            writeln!(formatter, "flowchart TD")?;
+            for ((id, _), label) in nodes.iter().zip(&labels) {
+                let label = label
+                    .lines()
+                    .map(|line| {
+                        line.replace('#', "#35;")
+                            .replace('"', "#quot;")
+                            .replace('<', "#lt;")
+                            .replace('>', "#gt;")
+                    })
+                    .join("<br>");
+                writeln!(formatter, "    {}[\"{label}\"]", id.hex())?;
+            }
+            for (source, target, edge_type) in edges {
+                let arrow = match edge_type {
+                    GraphEdgeType::Indirect => "-.->",
+                    GraphEdgeType::Direct | GraphEdgeType::Missing => "-->",
+                };
+                writeln!(formatter, "    {} {arrow} {}", source.hex(), target.hex())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+type LogGraphIter<'a> =
+    Box<dyn Iterator<Item = Result<GraphNode<CommitId>, RevsetEvaluationError>> + 'a>;
+
+/// Returns the graph of the `revset`, filtered down to the revisions reachable
+/// from its heads by following the first parents if `first_parent` is set.
+fn iter_log_graph<'a>(revset: &'a dyn Revset, first_parent: bool) -> LogGraphIter<'a> {
+    if first_parent {
+        revset.iter_first_parent_graph()
+    } else {
+        revset.iter_graph()
+    }
+}
+
+/// Number of revisions [`DateSortedGraphIterator`] reads ahead of the revision
+/// it emits.
+const DATE_SORT_LOOKAHEAD: usize = 1000;
+
+/// Sort key of a revision: its timestamp, with ties broken by the original
+/// (topological) order.
+type DateSortKey = (MillisSinceEpoch, Reverse<usize>, CommitId);
+
+/// Sorts the graph so that newer revisions come first, as long as children
+/// come before their parents.
+///
+/// Up to [`DATE_SORT_LOOKAHEAD`] revisions are read ahead, so the output can
+/// be emitted before the whole graph is walked. A revision older than one
+/// further ahead in the input might thus come first.
+struct DateSortedGraphIterator<'a, I> {
+    input: I,
+    store: &'a Arc<Store>,
+    order: LogOrder,
+    /// Number of revisions read from the input so far
+    num_read: usize,
+    input_done: bool,
+    /// Revisions read from the input but not emitted yet
+    nodes: HashMap<CommitId, (DateSortKey, Vec<GraphEdge<CommitId>>)>,
+    /// Number of children of each revision which haven't been emitted yet
+    num_pending_children: HashMap<CommitId, usize>,
+    /// Keys of the revisions read from the input whose children have all been
+    /// emitted
+    ready: BinaryHeap<DateSortKey>,
+}
+
+impl<'a, I> DateSortedGraphIterator<'a, I>
+where
+    I: Iterator<Item = Result<GraphNode<CommitId>, RevsetEvaluationError>>,
+{
+    fn new(store: &'a Arc<Store>, input: I, order: LogOrder) -> Self {
+        DateSortedGraphIterator {
+            input,
+            store,
+            order,
+            num_read: 0,
+            input_done: false,
+            nodes: HashMap::new(),
+            num_pending_children: HashMap::new(),
+            ready: BinaryHeap::new(),
+        }
+    }
+
+    fn read_node(&mut self, (commit_id, edges): GraphNode<CommitId>) -> Result<(), BackendError> {
+        let commit = self.store.get_commit(&commit_id)?;
+        let signature = match self.order {
+            LogOrder::AuthorDate => commit.author(),
+            LogOrder::Topo | LogOrder::Date => commit.committer(),
+        };
+        let key = (
+            signature.timestamp.timestamp,
+            Reverse(self.num_read),
+            commit_id.clone(),
+        );
+        self.num_read += 1;
+        for edge in &edges {
+            if edge.edge_type != GraphEdgeType::Missing {
+                *self
+                    .num_pending_children
+                    .entry(edge.target.clone())
+                    .or_default() += 1;
+            }
+        }
+        // The children come first in the input, so they have all been read
+        if !self.num_pending_children.contains_key(&commit_id) {
+            self.ready.push(key.clone());
+        }
+        self.nodes.insert(commit_id, (key, edges));
+        Ok(())
+    }
+
+    fn try_next(&mut self) -> Result<Option<GraphNode<CommitId>>, RevsetEvaluationError> {
+        while !self.input_done && self.nodes.len() <= DATE_SORT_LOOKAHEAD {
+            match self.input.next() {
+                Some(node) => self.read_node(node?)?,
+                None => self.input_done = true,
+            }
+        }
+        // The earliest revision read from the input which hasn't been emitted
+        // has no pending children, so there's always one ready if any are left.
+        let Some((_, _, commit_id)) = self.ready.pop() else {
+            return Ok(None);
+        };
+        let (_, edges) = self.nodes.remove(&commit_id).unwrap();
+        for edge in &edges {
+            if edge.edge_type == GraphEdgeType::Missing {
+                continue;
+            }
+            let num_pending = self.num_pending_children.get_mut(&edge.target).unwrap();
+            *num_pending -= 1;
+            if *num_pending == 0 {
+                self.num_pending_children.remove(&edge.target);
+                if let Some((key, _)) = self.nodes.get(&edge.target) {
+                    self.ready.push(key.clone());
+                }
+            }
+        }
+        Ok(Some((commit_id, edges)))
+    }
+}
+
+impl<I> Iterator for DateSortedGraphIterator<'_, I>
+where
+    I: Iterator<Item = Result<GraphNode<CommitId>, RevsetEvaluationError>>,
+{
+    type Item = Result<GraphNode<CommitId>, RevsetEvaluationError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next().transpose()
+    }
+}
+
+/// Evaluates the edge `template` to the labels to draw the edges below the
+/// `commit` node with.
+fn get_edge_style(
+    ui: &Ui,
+    template: &TemplateRenderer<Option<Commit>>,
+    commit: Option<Commit>,
+    cache: &mut HashMap<String, Option<Rc<EdgeStyle>>>,
+) -> Option<Rc<EdgeStyle>> {
+    let mut output = vec![];
+    template
+        .format(&commit, &mut PlainTextFormatter::new(&mut output))
+        .expect("write() to vec backed formatter should never fail");
+    let labels = String::from_utf8_lossy(&output).into_owned();
+    cache
+        .entry(labels)
+        .or_insert_with_key(|labels| {
+            let labels = labels.split_whitespace().collect_vec();
+            EdgeStyle::new(ui, &labels).map(Rc::new)
+        })
+        .clone()
+}
+
 pub fn get_node_template(
     style: GraphStyle,
     settings: &UserSettings,
@@ -0,0 +1,62 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod register;
+mod run;
+
+use clap::Subcommand;
+use tracing::instrument;
+
+use self::register::cmd_maintenance_register;
+use self::register::MaintenanceRegisterArgs;
+use self::run::cmd_maintenance_run;
+use self::run::MaintenanceRunArgs;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Perform housekeeping on the repository
+///
+/// Maintenance consists of the following tasks, which can be enabled or
+/// disabled by the `maintenance.<task>.enabled` settings:
+///
+/// * `prefetch`: fetch from the default Git remotes (disabled by default)
+/// * `op-log-compaction`: abandon operations older than
+///   `maintenance.op-log-compaction.keep-days` (disabled by default)
+/// * `gc`: garbage-collect unreachable operations and objects, like `jj util
+///   gc`
+/// * `index`: make sure the commit index is up to date
+/// * `search-index`: build the full-text search index, which speeds up
+///   `description()`, `author()`, and path queries
+///
+/// Changed-path filters aren't built separately. The search index records the
+/// paths modified by each commit, so path queries use it once the
+/// `search-index` task has run.
+#[derive(Subcommand, Clone, Debug)]
+pub(crate) enum MaintenanceCommand {
+    Register(MaintenanceRegisterArgs),
+    Run(MaintenanceRunArgs),
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_maintenance(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &MaintenanceCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        MaintenanceCommand::Register(args) => cmd_maintenance_register(ui, command, args),
+        MaintenanceCommand::Run(args) => cmd_maintenance_run(ui, command, args),
+    }
+}
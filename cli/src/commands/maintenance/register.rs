@@ -0,0 +1,202 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::env;
+use std::io::Write as _;
+
+use itertools::Itertools as _;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::user_error_with_message;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Print configuration to run maintenance periodically
+///
+/// The printed configuration runs `jj maintenance run` for the current
+/// workspace every day. It isn't installed automatically. Follow the printed
+/// hint to install it.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct MaintenanceRegisterArgs {
+    /// The scheduler to print configuration for
+    ///
+    /// Defaults to `launchd` on macOS, and to `cron` elsewhere.
+    #[arg(long, value_enum)]
+    scheduler: Option<Scheduler>,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+enum Scheduler {
+    /// A crontab entry
+    Cron,
+    /// A systemd service and timer for the user instance of systemd
+    Systemd,
+    /// A launchd agent property list
+    Launchd,
+}
+
+pub(crate) fn cmd_maintenance_register(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &MaintenanceRegisterArgs,
+) -> Result<(), CommandError> {
+    let workspace = command.load_workspace()?;
+    let workspace_root = workspace.workspace_root().to_str().ok_or_else(|| {
+        user_error_with_message(
+            "Cannot register a workspace whose path isn't valid UTF-8",
+            workspace.workspace_root().display().to_string(),
+        )
+    })?;
+    let jj_path = env::current_exe()?;
+    let jj_path = jj_path.to_str().ok_or_else(|| {
+        user_error_with_message(
+            "Cannot register the jj executable whose path isn't valid UTF-8",
+            jj_path.display().to_string(),
+        )
+    })?;
+    let program_args = [
+        jj_path,
+        "--repository",
+        workspace_root,
+        "maintenance",
+        "run",
+        "--quiet",
+    ];
+    let name = job_name(workspace_root);
+
+    let scheduler = args.scheduler.unwrap_or(if cfg!(target_os = "macos") {
+        Scheduler::Launchd
+    } else {
+        Scheduler::Cron
+    });
+    let mut stdout = ui.stdout();
+    match scheduler {
+        Scheduler::Cron => {
+            let command_line = program_args.iter().map(|arg| shell_quote(arg)).join(" ");
+            writeln!(stdout, "# jj maintenance for {workspace_root}")?;
+            writeln!(stdout, "0 3 * * * {command_line}")?;
+            drop(stdout);
+            writeln!(
+                ui.hint_default(),
+                "Add the line above to your crontab, for example with `crontab -e`."
+            )?;
+        }
+        Scheduler::Systemd => {
+            let command_line = program_args.iter().map(|arg| systemd_quote(arg)).join(" ");
+            write!(
+                stdout,
+                "\
+# {name}.service
+[Unit]
+Description=jj maintenance for {workspace_root}
+
+[Service]
+Type=oneshot
+ExecStart={command_line}
+
+# {name}.timer
+[Unit]
+Description=Daily jj maintenance for {workspace_root}
+
+[Timer]
+OnCalendar=daily
+Persistent=true
+
+[Install]
+WantedBy=timers.target
+"
+            )?;
+            drop(stdout);
+            writeln!(
+                ui.hint_default(),
+                "Save the units above to ~/.config/systemd/user/, then run `systemctl --user \
+                 enable --now {name}.timer`."
+            )?;
+        }
+        Scheduler::Launchd => {
+            let label = format!("dev.jj-vcs.{name}");
+            let program_args = program_args
+                .iter()
+                .map(|arg| format!("    <string>{}</string>\n", xml_escape(arg)))
+                .join("");
+            write!(
+                stdout,
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+  <key>Label</key>
+  <string>{label}</string>
+  <key>ProgramArguments</key>
+  <array>
+{program_args}  </array>
+  <key>StartCalendarInterval</key>
+  <dict>
+    <key>Hour</key>
+    <integer>3</integer>
+    <key>Minute</key>
+    <integer>0</integer>
+  </dict>
+</dict>
+</plist>
+"#
+            )?;
+            drop(stdout);
+            writeln!(
+                ui.hint_default(),
+                "Save the property list above to ~/Library/LaunchAgents/{label}.plist, then run \
+                 `launchctl load ~/Library/LaunchAgents/{label}.plist`."
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns a name for the scheduled job, derived from the workspace directory
+/// name.
+fn job_name(workspace_root: &str) -> String {
+    let dir_name = workspace_root
+        .rsplit(['/', '\\'])
+        .find(|component| !component.is_empty())
+        .unwrap_or("root");
+    let dir_name: String = dir_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("jj-maintenance-{dir_name}")
+}
+
+fn shell_quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', r"'\''"))
+}
+
+fn systemd_quote(arg: &str) -> String {
+    let escaped = arg
+        .replace('\\', r"\\")
+        .replace('"', r#"\""#)
+        .replace('%', "%%");
+    format!("\"{escaped}\"")
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
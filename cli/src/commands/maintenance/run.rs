@@ -0,0 +1,291 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+use std::slice;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use clap::ValueEnum as _;
+use itertools::Itertools as _;
+use jj_lib::backend::Timestamp;
+use jj_lib::default_index::AsCompositeIndex as _;
+use jj_lib::default_index::DefaultIndexStore;
+use jj_lib::default_index::DefaultReadonlyIndex;
+use jj_lib::op_walk;
+use jj_lib::repo::Repo as _;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::internal_error;
+use crate::command_error::user_error;
+use crate::command_error::CommandError;
+#[cfg(feature = "git")]
+use crate::commands::git::fetch::fetch_default_remotes;
+use crate::commands::operation::abandon::reparent_operations;
+use crate::commands::operation::abandon::update_workspace_operation;
+use crate::ui::Ui;
+
+const MILLIS_PER_DAY: i64 = 24 * 60 * 60 * 1000;
+
+/// Run maintenance tasks
+///
+/// By default, the tasks enabled by the `maintenance.<task>.enabled` settings
+/// are run. If a task fails, the remaining tasks are still run. A summary of
+/// the tasks is printed at the end.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct MaintenanceRunArgs {
+    /// Run only the given task, even if it's disabled (can be repeated)
+    #[arg(long, value_enum, value_name = "TASK")]
+    task: Vec<MaintenanceTask>,
+}
+
+/// Maintenance tasks, in the order they are run.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum MaintenanceTask {
+    /// Fetch from the default Git remotes
+    Prefetch,
+    /// Abandon operations older than `maintenance.op-log-compaction.keep-days`
+    OpLogCompaction,
+    /// Garbage-collect unreachable operations and objects
+    Gc,
+    /// Make sure the commit index is up to date
+    Index,
+    /// Build the full-text search index of descriptions, authors, and paths
+    SearchIndex,
+}
+
+impl MaintenanceTask {
+    fn name(self) -> &'static str {
+        match self {
+            MaintenanceTask::Prefetch => "prefetch",
+            MaintenanceTask::OpLogCompaction => "op-log-compaction",
+            MaintenanceTask::Gc => "gc",
+            MaintenanceTask::Index => "index",
+            MaintenanceTask::SearchIndex => "search-index",
+        }
+    }
+}
+
+enum TaskOutcome {
+    Done(String),
+    Skipped(String),
+    Failed,
+}
+
+pub(crate) fn cmd_maintenance_run(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &MaintenanceRunArgs,
+) -> Result<(), CommandError> {
+    if !command.is_at_head_operation() {
+        return Err(user_error(
+            "Cannot run maintenance from a non-head operation",
+        ));
+    }
+    let settings = command.settings();
+    let mut selected_tasks = vec![];
+    for &task in MaintenanceTask::value_variants() {
+        let selected = if args.task.is_empty() {
+            settings.get_bool(["maintenance", task.name(), "enabled"])?
+        } else {
+            args.task.contains(&task)
+        };
+        if selected {
+            selected_tasks.push(task);
+        }
+    }
+    // Snapshot the working copy once so that the tasks see the latest changes.
+    command.workspace_helper(ui)?;
+
+    let mut outcomes = vec![];
+    let mut failed_tasks = vec![];
+    let mut failure_hints = vec![];
+    for &task in MaintenanceTask::value_variants() {
+        if !selected_tasks.contains(&task) {
+            if args.task.is_empty() {
+                outcomes.push((task, TaskOutcome::Skipped("disabled".to_owned())));
+            }
+            continue;
+        }
+        let outcome = match run_task(ui, command, task) {
+            Ok(outcome) => outcome,
+            Err(err) => {
+                writeln!(
+                    ui.warning_default(),
+                    "Maintenance task {} failed: {}",
+                    task.name(),
+                    err.error
+                )?;
+                failed_tasks.push(task.name());
+                failure_hints.extend(err.hints);
+                TaskOutcome::Failed
+            }
+        };
+        outcomes.push((task, outcome));
+    }
+
+    let mut formatter = ui.stdout_formatter();
+    for (task, outcome) in &outcomes {
+        write!(formatter, "{}: ", task.name())?;
+        match outcome {
+            TaskOutcome::Done(summary) => writeln!(formatter, "{summary}")?,
+            TaskOutcome::Skipped(reason) => writeln!(formatter, "skipped ({reason})")?,
+            TaskOutcome::Failed => writeln!(formatter.labeled("error"), "failed")?,
+        }
+    }
+    drop(formatter);
+
+    if failed_tasks.is_empty() {
+        Ok(())
+    } else {
+        let mut error = user_error(format!(
+            "{} of {} maintenance tasks failed: {}",
+            failed_tasks.len(),
+            selected_tasks.len(),
+            failed_tasks.join(", ")
+        ));
+        error.hints.extend(failure_hints);
+        Err(error)
+    }
+}
+
+fn run_task(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    task: MaintenanceTask,
+) -> Result<TaskOutcome, CommandError> {
+    match task {
+        MaintenanceTask::Prefetch => prefetch(ui, command),
+        MaintenanceTask::OpLogCompaction => compact_op_log(ui, command),
+        MaintenanceTask::Gc => gc(ui, command),
+        MaintenanceTask::Index => warm_index(ui, command),
+        MaintenanceTask::SearchIndex => build_search_index(ui, command),
+    }
+}
+
+#[cfg(feature = "git")]
+fn prefetch(ui: &mut Ui, command: &CommandHelper) -> Result<TaskOutcome, CommandError> {
+    let mut workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    let remotes = fetch_default_remotes(ui, &mut workspace_command)?;
+    Ok(TaskOutcome::Done(format!(
+        "fetched from {}",
+        remotes.join(", ")
+    )))
+}
+
+#[cfg(not(feature = "git"))]
+fn prefetch(_ui: &mut Ui, _command: &CommandHelper) -> Result<TaskOutcome, CommandError> {
+    Ok(TaskOutcome::Skipped(
+        "jj was built without Git support".to_owned(),
+    ))
+}
+
+fn compact_op_log(ui: &Ui, command: &CommandHelper) -> Result<TaskOutcome, CommandError> {
+    let keep_days: u32 = command
+        .settings()
+        .get("maintenance.op-log-compaction.keep-days")?;
+    let now = command
+        .settings()
+        .operation_timestamp()
+        .unwrap_or_else(Timestamp::now);
+    let cutoff = now.timestamp.0 - i64::from(keep_days) * MILLIS_PER_DAY;
+
+    let mut workspace = command.load_workspace()?;
+    let repo_loader = workspace.repo_loader();
+    let root_op = repo_loader.root_operation();
+    let current_head_ops = op_walk::get_current_head_ops(
+        repo_loader.op_store(),
+        repo_loader.op_heads_store().as_ref(),
+    )?;
+    // The ancestors of the newest expired operation are also abandoned.
+    let expired_op = op_walk::walk_ancestors(&current_head_ops)
+        .filter_ok(|op| *op != root_op && !current_head_ops.contains(op))
+        .find(|op| {
+            op.as_ref()
+                .map_or(true, |op| op.metadata().end_time.timestamp.0 < cutoff)
+        })
+        .transpose()?;
+    let stats = if let Some(expired_op) = expired_op {
+        reparent_operations(
+            &workspace,
+            &root_op,
+            slice::from_ref(&expired_op),
+            &current_head_ops,
+        )?
+    } else {
+        None
+    };
+    let Some(stats) = stats else {
+        return Ok(TaskOutcome::Done(format!(
+            "no operations older than {keep_days} days"
+        )));
+    };
+    update_workspace_operation(ui, command, &mut workspace, &current_head_ops, &stats)?;
+    Ok(TaskOutcome::Done(format!(
+        "abandoned {} operations older than {keep_days} days",
+        stats.unreachable_count
+    )))
+}
+
+fn gc(ui: &Ui, command: &CommandHelper) -> Result<TaskOutcome, CommandError> {
+    let keep_days: u32 = command.settings().get("maintenance.gc.keep-days")?;
+    let keep_newer = SystemTime::now() - Duration::from_secs(u64::from(keep_days) * 86400);
+    let workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    let repo = workspace_command.repo();
+    repo.op_store()
+        .gc(slice::from_ref(repo.op_id()), keep_newer)?;
+    repo.store().gc(repo.index(), keep_newer)?;
+    Ok(TaskOutcome::Done(format!(
+        "pruned unreachable objects older than {keep_days} days"
+    )))
+}
+
+fn warm_index(ui: &Ui, command: &CommandHelper) -> Result<TaskOutcome, CommandError> {
+    // Loading the repo indexes the operations which haven't been indexed yet.
+    let workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    let index = workspace_command.repo().readonly_index();
+    let Some(default_index) = index.as_any().downcast_ref::<DefaultReadonlyIndex>() else {
+        return Ok(TaskOutcome::Done("up to date".to_owned()));
+    };
+    // Computing the stats reads all index segments.
+    let stats = default_index.as_composite().stats();
+    Ok(TaskOutcome::Done(format!(
+        "{} commits indexed",
+        stats.num_commits
+    )))
+}
+
+fn build_search_index(ui: &Ui, command: &CommandHelper) -> Result<TaskOutcome, CommandError> {
+    let workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    let repo = workspace_command.repo();
+    let index_store = repo.index_store();
+    let (Some(default_index_store), Some(default_index)) = (
+        index_store.as_any().downcast_ref::<DefaultIndexStore>(),
+        repo.readonly_index()
+            .as_any()
+            .downcast_ref::<DefaultReadonlyIndex>(),
+    ) else {
+        return Ok(TaskOutcome::Skipped(
+            "not supported by the commit index".to_owned(),
+        ));
+    };
+    default_index_store
+        .search_index()
+        .enable()
+        .map_err(internal_error)?;
+    let num_added = default_index_store
+        .update_search_index(default_index.as_composite(), repo.store())
+        .map_err(internal_error)?;
+    Ok(TaskOutcome::Done(format!("{num_added} commits indexed")))
+}
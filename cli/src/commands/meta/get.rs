@@ -0,0 +1,57 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::user_error;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Print the value of a metadata entry of a change
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct MetaGetArgs {
+    /// The revision whose change to look up
+    #[arg(
+        long, short,
+        default_value = "@",
+        value_name = "REVSET",
+        add = ArgValueCandidates::new(complete::all_revisions),
+    )]
+    revision: RevisionArg,
+    /// The key of the entry
+    key: String,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_meta_get(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &MetaGetArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
+    let value = workspace_command
+        .repo()
+        .view()
+        .get_change_metadata(commit.change_id(), &args.key)
+        .ok_or_else(|| user_error(format!("No metadata entry named {}", args.key)))?;
+    writeln!(ui.stdout(), "{value}")?;
+    Ok(())
+}
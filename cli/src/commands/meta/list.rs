@@ -0,0 +1,59 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// List the metadata entries of a change
+///
+/// Entries are printed as `key=value` lines in order of keys.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct MetaListArgs {
+    /// The revision whose change to look up
+    #[arg(
+        long, short,
+        default_value = "@",
+        value_name = "REVSET",
+        add = ArgValueCandidates::new(complete::all_revisions),
+    )]
+    revision: RevisionArg,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_meta_list(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &MetaListArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let commit = workspace_command.resolve_single_rev(ui, &args.revision)?;
+    let repo = workspace_command.repo();
+    let Some(entries) = repo.view().change_metadata(commit.change_id()) else {
+        return Ok(());
+    };
+    let mut formatter = ui.stdout_formatter();
+    for (key, value) in entries {
+        write!(formatter.labeled("key"), "{key}")?;
+        writeln!(formatter, "={value}")?;
+    }
+    Ok(())
+}
@@ -0,0 +1,62 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod get;
+mod list;
+mod remove;
+mod set;
+
+use clap::Subcommand;
+use tracing::instrument;
+
+use self::get::cmd_meta_get;
+use self::get::MetaGetArgs;
+use self::list::cmd_meta_list;
+use self::list::MetaListArgs;
+use self::remove::cmd_meta_remove;
+use self::remove::MetaRemoveArgs;
+use self::set::cmd_meta_set;
+use self::set::MetaSetArgs;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Manage custom metadata attached to changes
+///
+/// Metadata entries are key/value pairs such as review URLs or ticket ids.
+/// They are attached to the change id, so they are kept when the change is
+/// rewritten. Entries can be shown with the `metadata()` commit template method
+/// and queried with the `metadata()` revset function.
+#[derive(Subcommand, Clone, Debug)]
+pub(crate) enum MetaCommand {
+    Get(MetaGetArgs),
+    #[command(visible_alias("l"))]
+    List(MetaListArgs),
+    Remove(MetaRemoveArgs),
+    Set(MetaSetArgs),
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_meta(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &MetaCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        MetaCommand::Get(args) => cmd_meta_get(ui, command, args),
+        MetaCommand::List(args) => cmd_meta_list(ui, command, args),
+        MetaCommand::Remove(args) => cmd_meta_remove(ui, command, args),
+        MetaCommand::Set(args) => cmd_meta_set(ui, command, args),
+    }
+}
@@ -14,26 +14,36 @@
 
 mod abandon;
 mod absorb;
+mod api;
 mod backout;
+mod backup;
 #[cfg(feature = "bench")]
 mod bench;
 mod bookmark;
+mod changelog;
+mod ci_status;
 mod commit;
 mod config;
 mod debug;
 mod describe;
 mod diff;
 mod diffedit;
+mod doctor;
 mod duplicate;
 mod edit;
 mod evolog;
+mod evolve;
 mod file;
+mod filter;
 mod fix;
+mod fsck;
 #[cfg(feature = "git")]
 mod git;
 mod help;
 mod interdiff;
 mod log;
+mod maintenance;
+mod meta;
 mod new;
 mod next;
 mod operation;
@@ -44,6 +54,7 @@ mod resolve;
 mod restore;
 mod root;
 mod run;
+mod shortlog;
 mod show;
 mod sign;
 mod simplify_parents;
@@ -52,9 +63,13 @@ mod split;
 mod squash;
 mod status;
 mod tag;
+mod test;
+mod topic;
 mod unsign;
 mod util;
+mod verify;
 mod version;
+mod web;
 mod workspace;
 
 use std::fmt::Debug;
@@ -87,7 +102,10 @@ const STYLES: Styles = Styles::styled()
 enum Command {
     Abandon(abandon::AbandonArgs),
     Absorb(absorb::AbsorbArgs),
+    Api(api::ApiArgs),
     Backout(backout::BackoutArgs),
+    #[command(subcommand)]
+    Backup(backup::BackupCommand),
     #[cfg(feature = "bench")]
     #[command(subcommand)]
     Bench(bench::BenchCommand),
@@ -96,6 +114,9 @@ enum Command {
     // TODO: Remove in jj 0.28+
     #[command(subcommand, hide = true)]
     Branch(bookmark::BookmarkCommand),
+    Changelog(changelog::ChangelogArgs),
+    #[command(subcommand)]
+    CiStatus(ci_status::CiStatusCommand),
     Commit(commit::CommitArgs),
     #[command(subcommand)]
     Config(config::ConfigCommand),
@@ -104,19 +125,27 @@ enum Command {
     Describe(describe::DescribeArgs),
     Diff(diff::DiffArgs),
     Diffedit(diffedit::DiffeditArgs),
+    Doctor(doctor::DoctorArgs),
     Duplicate(duplicate::DuplicateArgs),
     Edit(edit::EditArgs),
     #[command(alias = "obslog", visible_alias = "evolution-log")]
     Evolog(evolog::EvologArgs),
+    Evolve(evolve::EvolveArgs),
     #[command(subcommand)]
     File(file::FileCommand),
+    Filter(filter::FilterArgs),
     Fix(fix::FixArgs),
+    Fsck(fsck::FsckArgs),
     #[cfg(feature = "git")]
     #[command(subcommand)]
     Git(git::GitCommand),
     Help(help::HelpArgs),
     Interdiff(interdiff::InterdiffArgs),
     Log(log::LogArgs),
+    #[command(subcommand)]
+    Maintenance(maintenance::MaintenanceCommand),
+    #[command(subcommand)]
+    Meta(meta::MetaCommand),
     New(new::NewArgs),
     Next(next::NextArgs),
     #[command(subcommand)]
@@ -131,6 +160,7 @@ enum Command {
     #[command(hide = true)]
     // TODO: Flesh out.
     Run(run::RunArgs),
+    Shortlog(shortlog::ShortlogArgs),
     Show(show::ShowArgs),
     Sign(sign::SignArgs),
     SimplifyParents(simplify_parents::SimplifyParentsArgs),
@@ -141,12 +171,17 @@ enum Command {
     Status(status::StatusArgs),
     #[command(subcommand)]
     Tag(tag::TagCommand),
+    Test(test::TestArgs),
+    #[command(subcommand)]
+    Topic(topic::TopicCommand),
     #[command(subcommand)]
     Util(util::UtilCommand),
     /// Undo an operation (shortcut for `jj op undo`)
     Undo(operation::undo::OperationUndoArgs),
     Unsign(unsign::UnsignArgs),
+    Verify(verify::VerifyArgs),
     Version(version::VersionArgs),
+    Web(web::WebArgs),
     #[command(subcommand)]
     Workspace(workspace::WorkspaceCommand),
 }
@@ -161,7 +196,9 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
     match &subcommand {
         Command::Abandon(args) => abandon::cmd_abandon(ui, command_helper, args),
         Command::Absorb(args) => absorb::cmd_absorb(ui, command_helper, args),
+        Command::Api(args) => api::cmd_api(ui, command_helper, args),
         Command::Backout(args) => backout::cmd_backout(ui, command_helper, args),
+        Command::Backup(args) => backup::cmd_backup(ui, command_helper, args),
         #[cfg(feature = "bench")]
         Command::Bench(args) => bench::cmd_bench(ui, command_helper, args),
         Command::Bookmark(args) => bookmark::cmd_bookmark(ui, command_helper, args),
@@ -169,24 +206,32 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
             let cmd = renamed_cmd("branch", "bookmark", bookmark::cmd_bookmark);
             cmd(ui, command_helper, args)
         }
+        Command::Changelog(args) => changelog::cmd_changelog(ui, command_helper, args),
+        Command::CiStatus(args) => ci_status::cmd_ci_status(ui, command_helper, args),
         Command::Commit(args) => commit::cmd_commit(ui, command_helper, args),
         Command::Config(args) => config::cmd_config(ui, command_helper, args),
         Command::Debug(args) => debug::cmd_debug(ui, command_helper, args),
         Command::Describe(args) => describe::cmd_describe(ui, command_helper, args),
         Command::Diff(args) => diff::cmd_diff(ui, command_helper, args),
         Command::Diffedit(args) => diffedit::cmd_diffedit(ui, command_helper, args),
+        Command::Doctor(args) => doctor::cmd_doctor(ui, command_helper, args),
         Command::Duplicate(args) => duplicate::cmd_duplicate(ui, command_helper, args),
         Command::Edit(args) => edit::cmd_edit(ui, command_helper, args),
         Command::File(args) => file::cmd_file(ui, command_helper, args),
+        Command::Filter(args) => filter::cmd_filter(ui, command_helper, args),
         Command::Fix(args) => fix::cmd_fix(ui, command_helper, args),
+        Command::Fsck(args) => fsck::cmd_fsck(ui, command_helper, args),
         #[cfg(feature = "git")]
         Command::Git(args) => git::cmd_git(ui, command_helper, args),
         Command::Help(args) => help::cmd_help(ui, command_helper, args),
         Command::Interdiff(args) => interdiff::cmd_interdiff(ui, command_helper, args),
         Command::Log(args) => log::cmd_log(ui, command_helper, args),
+        Command::Maintenance(args) => maintenance::cmd_maintenance(ui, command_helper, args),
+        Command::Meta(args) => meta::cmd_meta(ui, command_helper, args),
         Command::New(args) => new::cmd_new(ui, command_helper, args),
         Command::Next(args) => next::cmd_next(ui, command_helper, args),
         Command::Evolog(args) => evolog::cmd_evolog(ui, command_helper, args),
+        Command::Evolve(args) => evolve::cmd_evolve(ui, command_helper, args),
         Command::Operation(args) => operation::cmd_operation(ui, command_helper, args),
         Command::Parallelize(args) => parallelize::cmd_parallelize(ui, command_helper, args),
         Command::Prev(args) => prev::cmd_prev(ui, command_helper, args),
@@ -198,6 +243,7 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
         Command::SimplifyParents(args) => {
             simplify_parents::cmd_simplify_parents(ui, command_helper, args)
         }
+        Command::Shortlog(args) => shortlog::cmd_shortlog(ui, command_helper, args),
         Command::Show(args) => show::cmd_show(ui, command_helper, args),
         Command::Sign(args) => sign::cmd_sign(ui, command_helper, args),
         Command::Sparse(args) => sparse::cmd_sparse(ui, command_helper, args),
@@ -205,10 +251,14 @@ pub fn run_command(ui: &mut Ui, command_helper: &CommandHelper) -> Result<(), Co
         Command::Squash(args) => squash::cmd_squash(ui, command_helper, args),
         Command::Status(args) => status::cmd_status(ui, command_helper, args),
         Command::Tag(args) => tag::cmd_tag(ui, command_helper, args),
+        Command::Test(args) => test::cmd_test(ui, command_helper, args),
+        Command::Topic(args) => topic::cmd_topic(ui, command_helper, args),
         Command::Undo(args) => operation::undo::cmd_op_undo(ui, command_helper, args),
         Command::Unsign(args) => unsign::cmd_unsign(ui, command_helper, args),
         Command::Util(args) => util::cmd_util(ui, command_helper, args),
+        Command::Verify(args) => verify::cmd_verify(ui, command_helper, args),
         Command::Version(args) => version::cmd_version(ui, command_helper, args),
+        Command::Web(args) => web::cmd_web(ui, command_helper, args),
         Command::Workspace(args) => workspace::cmd_workspace(ui, command_helper, args),
     }
 }
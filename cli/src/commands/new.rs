@@ -19,11 +19,12 @@ use clap_complete::ArgValueCandidates;
 use itertools::Itertools;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::CommitIteratorExt;
-use jj_lib::rewrite::merge_commit_trees;
+use jj_lib::rewrite::merge_commit_trees_with_strategies;
 use jj_lib::rewrite::rebase_commit;
 use tracing::instrument;
 
 use crate::cli_util::compute_commit_location;
+use crate::cli_util::load_merge_strategies;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::command_error::CommandError;
@@ -84,6 +85,13 @@ pub(crate) struct NewArgs {
         add = ArgValueCandidates::new(complete::mutable_revisions),
     )]
     insert_before: Option<Vec<RevisionArg>>,
+    /// Do not advance bookmarks pointing to the parent's parent
+    ///
+    /// By default, bookmarks enabled by the
+    /// `experimental-advance-branches.enabled-branches` setting are moved to
+    /// the parent of the new change.
+    #[arg(long)]
+    no_advance: bool,
 }
 
 #[instrument(skip_all)]
@@ -114,7 +122,7 @@ pub(crate) fn cmd_new(
     let mut advanceable_bookmarks = vec![];
 
     if args.insert_before.is_none() && args.insert_after.is_none() {
-        let should_advance_bookmarks = parent_commits.len() == 1;
+        let should_advance_bookmarks = parent_commits.len() == 1 && !args.no_advance;
         if should_advance_bookmarks {
             advance_bookmarks_target = Some(parent_commit_ids[0].clone());
             advanceable_bookmarks =
@@ -124,8 +132,10 @@ pub(crate) fn cmd_new(
 
     let parent_commit_ids_set: HashSet<CommitId> = parent_commit_ids.iter().cloned().collect();
 
+    let merge_strategies = load_merge_strategies(command.settings())?;
     let mut tx = workspace_command.start_transaction();
-    let merged_tree = merge_commit_trees(tx.repo(), &parent_commits)?;
+    let merged_tree =
+        merge_commit_trees_with_strategies(tx.repo(), &parent_commits, &merge_strategies)?;
     let new_commit = tx
         .repo_mut()
         .new_commit(parent_commit_ids, merged_tree.id())
@@ -144,7 +154,7 @@ pub(crate) fn cmd_new(
         rebase_commit(tx.repo_mut(), child_commit, new_parent_ids)?;
         num_rebased += 1;
     }
-    num_rebased += tx.repo_mut().rebase_descendants()?;
+    num_rebased += tx.rebase_descendants()?;
 
     if args.no_edit {
         if let Some(mut formatter) = ui.status_formatter() {
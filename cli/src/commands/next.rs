@@ -69,6 +69,9 @@ pub(crate) struct NextArgs {
     #[arg(long, short, conflicts_with = "edit")]
     no_edit: bool,
     /// Jump to the next conflicted descendant
+    ///
+    /// This moves to the closest descendants with conflicts, which is useful
+    /// for resolving conflicts in a stack bottom-up after rebasing it.
     #[arg(long, conflicts_with = "offset")]
     conflict: bool,
 }
@@ -19,6 +19,9 @@ use std::slice;
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools as _;
 use jj_lib::op_walk;
+use jj_lib::op_walk::ReparentStats;
+use jj_lib::operation::Operation;
+use jj_lib::workspace::Workspace;
 
 use crate::cli_util::short_operation_hash;
 use crate::cli_util::CommandHelper;
@@ -102,50 +105,93 @@ pub fn cmd_op_abandon(
         return Err(err);
     }
 
+    let Some(stats) = reparent_operations(
+        &workspace,
+        &abandon_root_op,
+        &abandon_head_ops,
+        &current_head_ops,
+    )?
+    else {
+        writeln!(ui.status(), "Nothing changed.")?;
+        return Ok(());
+    };
+    writeln!(
+        ui.status(),
+        "Abandoned {} operations and reparented {} descendant operations.",
+        stats.unreachable_count,
+        stats.rewritten_count,
+    )?;
+    update_workspace_operation(ui, command, &mut workspace, &current_head_ops, &stats)?;
+    Ok(())
+}
+
+/// Abandons the `abandon_head_ops` and their ancestors down to the
+/// `abandon_root_op` (exclusive), and reparents the descendant operations onto
+/// the `abandon_root_op`.
+///
+/// Returns `None` if none of the `current_head_ops` had to be reparented. The
+/// working-copy operation should be updated by [`update_workspace_operation`]
+/// otherwise.
+pub(crate) fn reparent_operations(
+    workspace: &Workspace,
+    abandon_root_op: &Operation,
+    abandon_head_ops: &[Operation],
+    current_head_ops: &[Operation],
+) -> Result<Option<ReparentStats>, CommandError> {
+    let repo_loader = workspace.repo_loader();
+    let op_store = repo_loader.op_store();
+    let op_heads_store = repo_loader.op_heads_store();
     // Reparent descendants, count the number of abandoned operations.
     let stats = op_walk::reparent_range(
         op_store.as_ref(),
-        &abandon_head_ops,
-        &current_head_ops,
-        &abandon_root_op,
+        abandon_head_ops,
+        current_head_ops,
+        abandon_root_op,
     )?;
     assert_eq!(
         current_head_ops.len(),
         stats.new_head_ids.len(),
         "all current_head_ops should be reparented as they aren't included in abandon_head_ops"
     );
-    let reparented_head_ops = || iter::zip(&current_head_ops, &stats.new_head_ids);
+    let reparented_head_ops = || iter::zip(current_head_ops, &stats.new_head_ids);
     if reparented_head_ops().all(|(old, new_id)| old.id() == new_id) {
-        writeln!(ui.status(), "Nothing changed.")?;
-        return Ok(());
+        return Ok(None);
     }
-    writeln!(
-        ui.status(),
-        "Abandoned {} operations and reparented {} descendant operations.",
-        stats.unreachable_count,
-        stats.rewritten_count,
-    )?;
     for (old, new_id) in reparented_head_ops().filter(|&(old, new_id)| old.id() != new_id) {
         op_heads_store.update_op_heads(slice::from_ref(old.id()), new_id)?;
     }
-    // Remap the operation id of the current workspace. If there were any
-    // divergent operations, user will need to re-abandon their ancestors.
-    if !command.global_args().ignore_working_copy {
-        let mut locked_ws = workspace.start_working_copy_mutation()?;
-        let old_op_id = locked_ws.locked_wc().old_operation_id();
-        if let Some((_, new_id)) = reparented_head_ops().find(|(old, _)| old.id() == old_op_id) {
-            locked_ws.finish(new_id.clone())?;
-        } else {
-            writeln!(
-                ui.warning_default(),
-                "The working copy operation {} is not updated because it differs from the repo {}.",
-                short_operation_hash(old_op_id),
-                current_head_ops
-                    .iter()
-                    .map(|op| short_operation_hash(op.id()))
-                    .join(", "),
-            )?;
-        }
+    Ok(Some(stats))
+}
+
+/// Remaps the operation id of the current workspace after
+/// [`reparent_operations`].
+pub(crate) fn update_workspace_operation(
+    ui: &Ui,
+    command: &CommandHelper,
+    workspace: &mut Workspace,
+    current_head_ops: &[Operation],
+    stats: &ReparentStats,
+) -> Result<(), CommandError> {
+    // If there were any divergent operations, user will need to re-abandon
+    // their ancestors.
+    if command.global_args().ignore_working_copy {
+        return Ok(());
+    }
+    let reparented_head_ops = || iter::zip(current_head_ops, &stats.new_head_ids);
+    let mut locked_ws = workspace.start_working_copy_mutation()?;
+    let old_op_id = locked_ws.locked_wc().old_operation_id();
+    if let Some((_, new_id)) = reparented_head_ops().find(|(old, _)| old.id() == old_op_id) {
+        locked_ws.finish(new_id.clone())?;
+    } else {
+        writeln!(
+            ui.warning_default(),
+            "The working copy operation {} is not updated because it differs from the repo {}.",
+            short_operation_hash(old_op_id),
+            current_head_ops
+                .iter()
+                .map(|op| short_operation_hash(op.id()))
+                .join(", "),
+        )?;
     }
     Ok(())
 }
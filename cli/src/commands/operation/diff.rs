@@ -17,8 +17,10 @@ use std::convert::Infallible;
 use std::sync::Arc;
 
 use clap_complete::ArgValueCandidates;
+use futures::StreamExt as _;
 use indexmap::IndexMap;
 use itertools::Itertools;
+use jj_lib::backend::BackendError;
 use jj_lib::backend::ChangeId;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
@@ -26,6 +28,8 @@ use jj_lib::dag_walk;
 use jj_lib::graph::GraphEdge;
 use jj_lib::graph::TopoGroupedGraphIterator;
 use jj_lib::matchers::EverythingMatcher;
+use jj_lib::matchers::Matcher;
+use jj_lib::merged_tree::MergedTree;
 use jj_lib::op_store::RefTarget;
 use jj_lib::op_store::RemoteRef;
 use jj_lib::op_store::RemoteRefState;
@@ -35,6 +39,8 @@ use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo;
 use jj_lib::revset;
 use jj_lib::revset::RevsetIteratorExt as _;
+use jj_lib::rewrite::rebase_to_dest_parent;
+use pollster::FutureExt as _;
 
 use crate::cli_util::CommandHelper;
 use crate::cli_util::LogContentFormat;
@@ -539,6 +545,44 @@ fn compute_operation_commits_diff(
     Ok(changes)
 }
 
+/// Returns true if the changes in commits between two operations modify any
+/// path matched by `matcher`. Modified changes are compared the same way as
+/// `show_change_diff()` does.
+pub fn op_diff_touches_paths(
+    current_repo: &dyn Repo,
+    from_repo: &ReadonlyRepo,
+    to_repo: &ReadonlyRepo,
+    matcher: &dyn Matcher,
+) -> Result<bool, CommandError> {
+    let changes = compute_operation_commits_diff(current_repo, from_repo, to_repo)?;
+    for change in changes.values() {
+        let tree_pairs: Vec<(MergedTree, MergedTree)> =
+            match (&*change.removed_commits, &*change.added_commits) {
+                (predecessors @ ([] | [_]), [commit]) => {
+                    let from_tree = rebase_to_dest_parent(current_repo, predecessors, commit)?;
+                    vec![(from_tree, commit.tree()?)]
+                }
+                // Abandoned or divergent changes are compared with their parents.
+                (removed_commits, added_commits) => {
+                    itertools::chain(removed_commits, added_commits)
+                        .map(|commit| Ok((commit.parent_tree(current_repo)?, commit.tree()?)))
+                        .try_collect::<_, _, BackendError>()?
+                }
+            };
+        for (from_tree, to_tree) in tree_pairs {
+            if from_tree
+                .diff_stream(&to_tree, matcher)
+                .next()
+                .block_on()
+                .is_some()
+            {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
 /// Displays the diffs of a modified change. The output differs based on the
 /// commits added and removed for the change.
 /// If there is a single added and removed commit, the diff is shown between the
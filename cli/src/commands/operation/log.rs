@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::slice;
 
 use clap_complete::ArgValueCandidates;
@@ -20,14 +23,17 @@ use jj_lib::config::ConfigGetError;
 use jj_lib::config::ConfigGetResultExt as _;
 use jj_lib::graph::reverse_graph;
 use jj_lib::graph::GraphEdge;
-use jj_lib::op_store::OpStoreError;
+use jj_lib::matchers::Matcher;
+use jj_lib::op_store::OperationId;
 use jj_lib::op_walk;
 use jj_lib::operation::Operation;
 use jj_lib::repo::RepoLoader;
 use jj_lib::settings::UserSettings;
 
+use super::diff::op_diff_touches_paths;
 use super::diff::show_op_diff;
 use crate::cli_util::format_template;
+use crate::cli_util::format_template_null_terminated;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::LogContentFormat;
 use crate::cli_util::WorkspaceCommandEnvironment;
@@ -62,6 +68,13 @@ pub struct OperationLogArgs {
     /// Don't show the graph, show a flat list of operations
     #[arg(long)]
     no_graph: bool,
+    /// Terminate each operation with a NUL byte instead of a newline
+    ///
+    /// Implies `--no-graph`. The template output is printed without color or
+    /// wrapping, and its trailing newline is replaced with a NUL byte, so the
+    /// output can be safely consumed by `xargs -0` and the like.
+    #[arg(long = "null", short = 'z', conflicts_with_all = ["op_diff", "patch"])]
+    null_terminated: bool,
     /// Render each operation using the given template
     ///
     /// You can specify arbitrary template expressions using the
@@ -82,9 +95,20 @@ pub struct OperationLogArgs {
     ///
     /// If the previous version has different parents, it will be temporarily
     /// rebased to the parents of the new version, so the diff is not
-    /// contaminated by unrelated changes.
+    /// contaminated by unrelated changes. For "snapshot working copy"
+    /// operations, this shows the working-copy changes that were recorded.
     #[arg(long, short = 'p')]
     patch: bool,
+    /// Show only operations that modified the given paths
+    ///
+    /// An operation is shown if any of the changes it created, rewrote, or
+    /// abandoned modified a matching path. For "snapshot working copy"
+    /// operations, this means the working-copy changes touched the path.
+    ///
+    /// In the graph, the edges skip the operations in between. If no matching
+    /// ancestor is found within 100 operations, the edge is shown as elided.
+    #[arg(long, value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
+    paths: Vec<String>,
     #[command(flatten)]
     diff_format: DiffFormatArgs,
 }
@@ -153,6 +177,15 @@ fn do_op_log(
             .labeled("node");
     }
 
+    let mut path_filter = if args.paths.is_empty() {
+        None
+    } else {
+        let matcher = workspace_env
+            .parse_union_filesets(ui, &args.paths)?
+            .to_matcher();
+        Some(OpPathFilter::new(repo_loader, matcher))
+    };
+
     let diff_formats = diff_formats_for_log(settings, &args.diff_format, args.patch)?;
     let maybe_show_op_diff = if args.op_diff || !diff_formats.is_empty() {
         let template_text = settings.get_string("templates.commit_summary")?;
@@ -207,18 +240,28 @@ fn do_op_log(
     ui.request_pager();
     let mut formatter = ui.stdout_formatter();
     let formatter = formatter.as_mut();
-    let iter =
-        op_walk::walk_ancestors(slice::from_ref(current_op)).take(args.limit.unwrap_or(usize::MAX));
+    let limit = args.limit.unwrap_or(usize::MAX);
+    let iter = op_walk::walk_ancestors(slice::from_ref(current_op));
 
-    if !args.no_graph {
+    if !args.no_graph && !args.null_terminated {
         let mut raw_output = formatter.raw()?;
         let mut graph = get_graphlog(graph_style, raw_output.as_mut());
-        let iter = iter.map(|op| -> Result<_, OpStoreError> {
-            let op = op?;
-            let ids = op.parent_ids();
-            let edges = ids.iter().cloned().map(GraphEdge::direct).collect();
-            Ok((op, edges))
-        });
+        let iter = iter
+            .map(|op| -> Result<_, CommandError> {
+                let op = op?;
+                let Some(filter) = &mut path_filter else {
+                    let ids = op.parent_ids();
+                    let edges = ids.iter().cloned().map(GraphEdge::direct).collect();
+                    return Ok(Some((op, edges)));
+                };
+                if !filter.matches(&op)? {
+                    return Ok(None);
+                }
+                let edges = filter.matching_ancestor_edges(&op)?;
+                Ok(Some((op, edges)))
+            })
+            .flatten_ok()
+            .take(limit);
         let iter_nodes: Box<dyn Iterator<Item = _>> = if args.reversed {
             Box::new(reverse_graph(iter, Operation::id)?.into_iter().map(Ok))
         } else {
@@ -247,6 +290,16 @@ fn do_op_log(
             )?;
         }
     } else {
+        let iter = iter
+            .map(|op| -> Result<_, CommandError> {
+                let op = op?;
+                let Some(filter) = &mut path_filter else {
+                    return Ok(Some(op));
+                };
+                Ok(filter.matches(&op)?.then_some(op))
+            })
+            .flatten_ok()
+            .take(limit);
         let iter: Box<dyn Iterator<Item = _>> = if args.reversed {
             Box::new(iter.collect_vec().into_iter().rev())
         } else {
@@ -254,6 +307,10 @@ fn do_op_log(
         };
         for op in iter {
             let op = op?;
+            if args.null_terminated {
+                formatter.write_all(&format_template_null_terminated(&op, &template))?;
+                continue;
+            }
             with_content_format.write(formatter, |formatter| template.format(&op, formatter))?;
             if let Some(show) = &maybe_show_op_diff {
                 show(ui, formatter, &op, &with_content_format)?;
@@ -264,6 +321,79 @@ fn do_op_log(
     Ok(())
 }
 
+/// Maximum number of ancestors to search for the graph edges of an operation
+/// which matches the path filter.
+const MAX_EDGE_SEARCH_OPS: usize = 100;
+
+/// Selects operations which modified paths matching the given matcher.
+struct OpPathFilter<'a> {
+    repo_loader: &'a RepoLoader,
+    matcher: Box<dyn Matcher>,
+    cache: HashMap<OperationId, bool>,
+}
+
+impl<'a> OpPathFilter<'a> {
+    fn new(repo_loader: &'a RepoLoader, matcher: Box<dyn Matcher>) -> Self {
+        Self {
+            repo_loader,
+            matcher,
+            cache: HashMap::new(),
+        }
+    }
+
+    fn matches(&mut self, op: &Operation) -> Result<bool, CommandError> {
+        if let Some(&matched) = self.cache.get(op.id()) {
+            return Ok(matched);
+        }
+        let matched = if op.parent_ids().is_empty() {
+            false
+        } else {
+            let parents: Vec<_> = op.parents().try_collect()?;
+            let parent_op = self.repo_loader.merge_operations(parents, None)?;
+            let parent_repo = self.repo_loader.load_at(&parent_op)?;
+            let repo = self.repo_loader.load_at(op)?;
+            op_diff_touches_paths(repo.as_ref(), &parent_repo, &repo, self.matcher.as_ref())?
+        };
+        self.cache.insert(op.id().clone(), matched);
+        Ok(matched)
+    }
+
+    /// Returns edges to the nearest ancestors of `op` which match the filter.
+    ///
+    /// If not all of them are found within [`MAX_EDGE_SEARCH_OPS`] ancestors,
+    /// a missing edge is added instead of searching the rest of the history.
+    fn matching_ancestor_edges(
+        &mut self,
+        op: &Operation,
+    ) -> Result<Vec<GraphEdge<OperationId>>, CommandError> {
+        let mut edges = vec![];
+        let mut visited = HashSet::new();
+        let mut queue: VecDeque<_> = op.parents().map_ok(|parent| (parent, true)).try_collect()?;
+        while let Some((ancestor, direct)) = queue.pop_front() {
+            if !visited.insert(ancestor.id().clone()) {
+                continue;
+            }
+            if visited.len() > MAX_EDGE_SEARCH_OPS {
+                edges.push(GraphEdge::missing(ancestor.id().clone()));
+                break;
+            }
+            if self.matches(&ancestor)? {
+                let id = ancestor.id().clone();
+                edges.push(if direct {
+                    GraphEdge::direct(id)
+                } else {
+                    GraphEdge::indirect(id)
+                });
+            } else {
+                for parent in ancestor.parents() {
+                    queue.push_back((parent?, false));
+                }
+            }
+        }
+        Ok(edges)
+    }
+}
+
 fn get_node_template(style: GraphStyle, settings: &UserSettings) -> Result<String, ConfigGetError> {
     let symbol = settings.get_string("templates.op_log_node").optional()?;
     let default = if style.is_ascii() {
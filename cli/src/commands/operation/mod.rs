@@ -12,20 +12,30 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-mod abandon;
+pub mod abandon;
 mod diff;
 mod log;
+mod pull;
+mod push;
 mod restore;
 mod show;
 pub mod undo;
 
+use std::io::Write as _;
+
 use abandon::cmd_op_abandon;
 use abandon::OperationAbandonArgs;
 use clap::Subcommand;
 use diff::cmd_op_diff;
 use diff::OperationDiffArgs;
+use jj_lib::op_sync::OpSyncStats;
+use jj_lib::workspace::Workspace;
 use log::cmd_op_log;
 use log::OperationLogArgs;
+use pull::cmd_op_pull;
+use pull::OperationPullArgs;
+use push::cmd_op_push;
+use push::OperationPushArgs;
 use restore::cmd_op_restore;
 use restore::OperationRestoreArgs;
 use show::cmd_op_show;
@@ -48,6 +58,8 @@ pub enum OperationCommand {
     Abandon(OperationAbandonArgs),
     Diff(OperationDiffArgs),
     Log(OperationLogArgs),
+    Pull(OperationPullArgs),
+    Push(OperationPushArgs),
     Restore(OperationRestoreArgs),
     Show(OperationShowArgs),
     Undo(OperationUndoArgs),
@@ -62,12 +74,33 @@ pub fn cmd_operation(
         OperationCommand::Abandon(args) => cmd_op_abandon(ui, command, args),
         OperationCommand::Diff(args) => cmd_op_diff(ui, command, args),
         OperationCommand::Log(args) => cmd_op_log(ui, command, args),
+        OperationCommand::Pull(args) => cmd_op_pull(ui, command, args),
+        OperationCommand::Push(args) => cmd_op_push(ui, command, args),
         OperationCommand::Restore(args) => cmd_op_restore(ui, command, args),
         OperationCommand::Show(args) => cmd_op_show(ui, command, args),
         OperationCommand::Undo(args) => cmd_op_undo(ui, command, args),
     }
 }
 
+/// Loads the workspace at `remote` for `jj op push` and `jj op pull`.
+fn load_remote_workspace(command: &CommandHelper, remote: &str) -> Result<Workspace, CommandError> {
+    let workspace_root = command.cwd().join(remote);
+    let settings = command.settings_for_new_workspace(&workspace_root)?;
+    command.load_workspace_at(&workspace_root, &settings)
+}
+
+fn print_op_sync_stats(ui: &Ui, verb: &str, stats: &OpSyncStats) -> Result<(), CommandError> {
+    writeln!(
+        ui.status(),
+        "{verb} {} operation{} and {} commit{}.",
+        stats.num_operations,
+        if stats.num_operations == 1 { "" } else { "s" },
+        stats.num_commits,
+        if stats.num_commits == 1 { "" } else { "s" },
+    )?;
+    Ok(())
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
 enum UndoWhatToRestore {
     /// The jj repo state and local bookmarks
@@ -99,10 +132,12 @@ fn view_with_desired_portions_restored(
     jj_lib::op_store::View {
         head_ids: repo_source.head_ids.clone(),
         local_bookmarks: repo_source.local_bookmarks.clone(),
+        archived_bookmarks: repo_source.archived_bookmarks.clone(),
         tags: repo_source.tags.clone(),
         remote_views: remote_source.remote_views.clone(),
         git_refs: current_view.git_refs.clone(),
         git_head: current_view.git_head.clone(),
         wc_commit_ids: repo_source.wc_commit_ids.clone(),
+        change_metadata: repo_source.change_metadata.clone(),
     }
 }
@@ -0,0 +1,72 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use jj_lib::op_sync;
+use jj_lib::op_walk;
+
+use super::load_remote_workspace;
+use super::print_op_sync_stats;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Copy the operation log from another repository
+///
+/// The current operations of the repository at the given path and their
+/// ancestors are copied to this repository, together with all commits they
+/// reference. If this repository has operations the other one doesn't have,
+/// the operation log diverges and will be merged by the next command.
+///
+/// The repositories don't have to be related. If they don't share any
+/// operations, their operation logs are merged as if they had diverged at the
+/// root operation.
+///
+/// Working-copy commits are recorded per workspace name, so workspaces with
+/// the same name in both repositories share their working-copy commit. Run
+/// `jj workspace update-stale` if the working copy becomes stale.
+///
+/// Only repositories backed by Git are supported.
+#[derive(clap::Args, Clone, Debug)]
+pub struct OperationPullArgs {
+    /// Path to the workspace of the source repository
+    #[arg(value_hint = clap::ValueHint::DirPath)]
+    remote: String,
+}
+
+pub fn cmd_op_pull(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &OperationPullArgs,
+) -> Result<(), CommandError> {
+    let workspace = command.load_workspace()?;
+    let remote_workspace = load_remote_workspace(command, &args.remote)?;
+    let remote_loader = remote_workspace.repo_loader();
+    let remote_head_ids = op_walk::get_current_head_ops(
+        remote_loader.op_store(),
+        remote_loader.op_heads_store().as_ref(),
+    )?
+    .iter()
+    .map(|op| op.id().clone())
+    .collect::<Vec<_>>();
+    let stats =
+        op_sync::transfer_operations(remote_loader, workspace.repo_loader(), &remote_head_ids)?;
+    if stats.num_operations == 0 {
+        writeln!(ui.status(), "Nothing changed.")?;
+    } else {
+        print_op_sync_stats(ui, "Pulled", &stats)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,67 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use jj_lib::op_sync;
+
+use super::load_remote_workspace;
+use super::print_op_sync_stats;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Copy the operation log to another repository
+///
+/// The current operation and its ancestors are copied to the repository at
+/// the given path, together with all commits they reference. Operations that
+/// already exist there are skipped. If the other repository has operations
+/// this one doesn't have, the operation log diverges and will be merged the
+/// next time that repository is used.
+///
+/// The repositories don't have to be related. If they don't share any
+/// operations, their operation logs are merged as if they had diverged at the
+/// root operation.
+///
+/// Working-copy commits are recorded per workspace name, so workspaces with
+/// the same name in both repositories share their working-copy commit. Run
+/// `jj workspace update-stale` if the working copy becomes stale.
+///
+/// Only repositories backed by Git are supported.
+#[derive(clap::Args, Clone, Debug)]
+pub struct OperationPushArgs {
+    /// Path to the workspace of the destination repository
+    #[arg(value_hint = clap::ValueHint::DirPath)]
+    remote: String,
+}
+
+pub fn cmd_op_push(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &OperationPushArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let remote_workspace = load_remote_workspace(command, &args.remote)?;
+    let stats = op_sync::transfer_operations(
+        workspace_command.repo().loader(),
+        remote_workspace.repo_loader(),
+        &[workspace_command.repo().op_id().clone()],
+    )?;
+    if stats.num_operations == 0 {
+        writeln!(ui.status(), "Nothing changed.")?;
+    } else {
+        print_op_sync_stats(ui, "Pushed", &stats)?;
+    }
+    Ok(())
+}
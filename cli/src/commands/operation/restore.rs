@@ -12,14 +12,26 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeSet;
+
 use clap_complete::ArgValueCandidates;
+use itertools::Itertools as _;
 use jj_lib::object_id::ObjectId;
+use jj_lib::op_store::RefTarget;
+use jj_lib::op_store::RemoteRef;
+use jj_lib::op_store::WorkspaceId;
+use jj_lib::refs::RemoteRefSymbol;
+use jj_lib::repo::MutableRepo;
+use jj_lib::repo::Repo as _;
+use jj_lib::str_util::StringPattern;
 
 use super::view_with_desired_portions_restored;
 use super::UndoWhatToRestore;
 use super::DEFAULT_UNDO_WHAT;
 use crate::cli_util::CommandHelper;
+use crate::command_error::user_error;
 use crate::command_error::CommandError;
+use crate::commands::rebase::discard_paused_rebase_on_restore;
 use crate::complete;
 use crate::ui::Ui;
 
@@ -27,6 +39,11 @@ use crate::ui::Ui;
 ///
 /// This restores the repo to the state at the specified operation, effectively
 /// undoing all later operations. It does so by creating a new operation.
+///
+/// Use `--bookmark`, `--workspace`, or `--remote` to restore only the selected
+/// parts of the repo state and keep everything else as it is now. For example,
+/// `jj op restore <operation ID> --bookmark main` moves just the `main`
+/// bookmark back to where it was at that operation.
 #[derive(clap::Args, Clone, Debug)]
 pub struct OperationRestoreArgs {
     /// The operation to restore to
@@ -42,6 +59,52 @@ pub struct OperationRestoreArgs {
     /// This option is EXPERIMENTAL.
     #[arg(long, value_enum, default_values_t = DEFAULT_UNDO_WHAT)]
     what: Vec<UndoWhatToRestore>,
+
+    /// Restore only the positions of these local bookmarks (can be repeated)
+    ///
+    /// By default, the specified name matches exactly. Use `glob:` prefix to
+    /// select bookmarks by [wildcard pattern].
+    ///
+    /// [wildcard pattern]:
+    ///     https://jj-vcs.github.io/jj/latest/revsets/#string-patterns
+    #[arg(
+        long,
+        value_name = "NAME",
+        value_parser = StringPattern::parse,
+        conflicts_with = "what",
+        add = ArgValueCandidates::new(complete::local_bookmarks),
+    )]
+    bookmark: Vec<StringPattern>,
+
+    /// Restore only the working-copy commits of these workspaces (can be
+    /// repeated)
+    ///
+    /// If the working-copy commit has been rewritten since the operation, a
+    /// new commit with the same parents and contents is checked out instead,
+    /// so the change doesn't become divergent.
+    #[arg(
+        long,
+        value_name = "NAME",
+        conflicts_with = "what",
+        add = ArgValueCandidates::new(complete::workspaces),
+    )]
+    workspace: Vec<String>,
+
+    /// Restore only the remote-tracking bookmarks of these remotes (can be
+    /// repeated)
+    #[arg(
+        long,
+        value_name = "REMOTE",
+        conflicts_with = "what",
+        add = ArgValueCandidates::new(complete::git_remotes),
+    )]
+    remote: Vec<String>,
+}
+
+impl OperationRestoreArgs {
+    fn is_selective(&self) -> bool {
+        !self.bookmark.is_empty() || !self.workspace.is_empty() || !self.remote.is_empty()
+    }
 }
 
 pub fn cmd_op_restore(
@@ -51,13 +114,21 @@ pub fn cmd_op_restore(
 ) -> Result<(), CommandError> {
     let mut workspace_command = command.workspace_helper(ui)?;
     let target_op = workspace_command.resolve_single_op(&args.operation)?;
+    let target_view = target_op.view()?;
     let mut tx = workspace_command.start_transaction();
-    let new_view = view_with_desired_portions_restored(
-        target_op.view()?.store_view(),
-        tx.base_repo().view().store_view(),
-        &args.what,
-    );
-    tx.repo_mut().set_view(new_view);
+    if args.is_selective() {
+        let target_view = target_view.store_view();
+        restore_bookmarks(tx.repo_mut(), target_view, &args.bookmark)?;
+        restore_workspaces(tx.repo_mut(), target_view, &args.workspace)?;
+        restore_remotes(tx.repo_mut(), target_view, &args.remote);
+    } else {
+        let new_view = view_with_desired_portions_restored(
+            target_view.store_view(),
+            tx.base_repo().view().store_view(),
+            &args.what,
+        );
+        tx.repo_mut().set_view(new_view);
+    }
     if let Some(mut formatter) = ui.status_formatter() {
         write!(formatter, "Restored to operation: ")?;
         let template = tx.base_workspace_helper().operation_summary_template();
@@ -65,6 +136,117 @@ pub fn cmd_op_restore(
         writeln!(formatter)?;
     }
     tx.finish(ui, format!("restore to operation {}", target_op.id().hex()))?;
+    if !args.is_selective() {
+        discard_paused_rebase_on_restore(&workspace_command, &target_op)?;
+    }
 
     Ok(())
 }
+
+fn restore_bookmarks(
+    mut_repo: &mut MutableRepo,
+    target_view: &jj_lib::op_store::View,
+    patterns: &[StringPattern],
+) -> Result<(), CommandError> {
+    let all_names: BTreeSet<String> = target_view
+        .local_bookmarks
+        .keys()
+        .cloned()
+        .chain(
+            mut_repo
+                .view()
+                .local_bookmarks()
+                .map(|(name, _)| name.to_owned()),
+        )
+        .collect();
+    let mut unmatched_patterns = vec![];
+    for pattern in patterns {
+        let names = all_names
+            .iter()
+            .filter(|name| pattern.matches(name))
+            .collect_vec();
+        if names.is_empty() {
+            unmatched_patterns.push(pattern);
+        }
+        for name in names {
+            let target = target_view
+                .local_bookmarks
+                .get(name)
+                .cloned()
+                .unwrap_or_else(RefTarget::absent);
+            mut_repo.set_local_bookmark_target(name, target);
+            mut_repo.set_bookmark_archived(name, target_view.archived_bookmarks.contains(name));
+        }
+    }
+    if !unmatched_patterns.is_empty() {
+        return Err(user_error(format!(
+            "No matching bookmarks for names: {}",
+            unmatched_patterns.iter().join(", ")
+        )));
+    }
+    Ok(())
+}
+
+fn restore_workspaces(
+    mut_repo: &mut MutableRepo,
+    target_view: &jj_lib::op_store::View,
+    names: &[String],
+) -> Result<(), CommandError> {
+    for name in names {
+        let workspace_id = WorkspaceId::new(name.clone());
+        let Some(commit_id) = target_view.wc_commit_ids.get(&workspace_id) else {
+            return Err(user_error(format!(
+                "No working-copy commit for workspace {name} at the operation"
+            )));
+        };
+        let commit = mut_repo.store().get_commit(commit_id)?;
+        let visible_ids = mut_repo
+            .resolve_change_id(commit.change_id())
+            .unwrap_or_default();
+        let commit = if visible_ids.is_empty() || visible_ids.contains(commit.id()) {
+            commit
+        } else {
+            mut_repo
+                .new_commit(commit.parent_ids().to_vec(), commit.tree_id().clone())
+                .set_description(commit.description())
+                .write()?
+        };
+        mut_repo.edit(workspace_id, &commit)?;
+    }
+    Ok(())
+}
+
+fn restore_remotes(
+    mut_repo: &mut MutableRepo,
+    target_view: &jj_lib::op_store::View,
+    remotes: &[String],
+) {
+    for remote in remotes {
+        let target_bookmarks = target_view
+            .remote_views
+            .get(remote)
+            .map(|remote_view| remote_view.bookmarks.clone())
+            .unwrap_or_default();
+        let current_names = mut_repo
+            .view()
+            .remote_bookmarks(remote)
+            .map(|(name, _)| name.to_owned())
+            .collect_vec();
+        for name in current_names {
+            if !target_bookmarks.contains_key(&name) {
+                let symbol = RemoteRefSymbol {
+                    name: &name,
+                    remote,
+                };
+                mut_repo.set_remote_bookmark(symbol, RemoteRef::absent());
+            }
+        }
+        for (name, remote_ref) in target_bookmarks {
+            let symbol = RemoteRefSymbol {
+                name: &name,
+                remote,
+            };
+            mut_repo.set_remote_bookmark(symbol, remote_ref);
+        }
+    }
+}
@@ -40,7 +40,8 @@ pub struct OperationShowArgs {
     ///
     /// If the previous version has different parents, it will be temporarily
     /// rebased to the parents of the new version, so the diff is not
-    /// contaminated by unrelated changes.
+    /// contaminated by unrelated changes. For "snapshot working copy"
+    /// operations, this shows the working-copy changes that were recorded.
     #[arg(long, short = 'p')]
     patch: bool,
     #[command(flatten)]
@@ -25,6 +25,7 @@ use super::DEFAULT_UNDO_WHAT;
 use crate::cli_util::CommandHelper;
 use crate::command_error::user_error;
 use crate::command_error::CommandError;
+use crate::commands::rebase::discard_paused_rebase_on_restore;
 use crate::complete;
 use crate::ui::Ui;
 
@@ -89,6 +90,7 @@ pub fn cmd_op_undo(
         writeln!(formatter)?;
     }
     tx.finish(ui, format!("undo operation {}", bad_op.id().hex()))?;
+    discard_paused_rebase_on_restore(&workspace_command, &parent_op)?;
 
     if args.operation == "@" && is_undo(&bad_op, &parent_op)? {
         writeln!(
@@ -65,6 +65,8 @@ pub(crate) struct PrevArgs {
     #[arg(long, short, conflicts_with = "edit")]
     no_edit: bool,
     /// Jump to the previous conflicted ancestor
+    ///
+    /// This moves to the closest ancestors with conflicts.
     #[arg(long, conflicts_with = "offset")]
     conflict: bool,
 }
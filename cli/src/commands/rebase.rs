@@ -12,16 +12,25 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fs;
+use std::io;
 use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::slice;
 use std::sync::Arc;
 
 use clap::ArgGroup;
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools;
+use jj_lib::backend::ChangeId;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
 use jj_lib::commit::CommitIteratorExt;
 use jj_lib::object_id::ObjectId;
+use jj_lib::op_store::OperationId;
+use jj_lib::op_walk;
+use jj_lib::operation::Operation;
 use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo;
 use jj_lib::revset::RevsetExpression;
@@ -35,12 +44,16 @@ use jj_lib::rewrite::RewriteRefsOptions;
 use tracing::instrument;
 
 use crate::cli_util::compute_commit_location;
+use crate::cli_util::load_merge_strategies;
 use crate::cli_util::short_commit_hash;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::cli_util::WorkspaceCommandHelper;
+use crate::cli_util::WorkspaceCommandTransaction;
 use crate::command_error::cli_error;
+use crate::command_error::internal_error_with_message;
 use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
 use crate::command_error::CommandError;
 use crate::complete;
 use crate::ui::Ui;
@@ -325,6 +338,35 @@ pub(crate) struct RebaseArgs {
     /// parents.
     #[arg(long)]
     skip_emptied: bool,
+
+    /// Abandon rebased revisions whose changes are already in the
+    /// destination
+    ///
+    /// A revision is considered already applied if the destination contains
+    /// a revision that adds and removes the same lines, or a duplicate of it
+    /// made by `jj duplicate` (or the other way around) and the revision
+    /// becomes empty when rebased. Only the rebased revisions are checked,
+    /// not their descendants.
+    #[arg(long)]
+    skip_applied: bool,
+
+    /// Stop at the first rebased revision that becomes conflicted and edit it
+    ///
+    /// Resolve the conflicts in the working copy, then run `jj rebase
+    /// --continue` to move on to the next conflicted revision. Revisions that
+    /// were already conflicted before the rebase are not stopped at. Other
+    /// rebases can't be started while paused, unless the paused rebase is
+    /// undone by `jj undo` or `jj op restore`.
+    #[arg(long)]
+    pause_on_conflict: bool,
+
+    /// Resume a rebase that was paused by `--pause-on-conflict`
+    ///
+    /// The revision the rebase is paused at must no longer have conflicts.
+    /// Once no conflicted revisions remain, the working copy is moved back to
+    /// the revision it was at before the rebase.
+    #[arg(long = "continue", group = "RebaseDestinationArgs", exclusive = true)]
+    continue_: bool,
 }
 
 #[derive(clap::Args, Clone, Debug)]
@@ -374,6 +416,9 @@ pub(crate) fn cmd_rebase(
             "--skip-empty is deprecated, and has been renamed to --skip-emptied.",
         ));
     }
+    if args.continue_ {
+        return continue_paused_rebase(ui, command);
+    }
 
     let rebase_options = RebaseOptions {
         empty: match args.skip_emptied {
@@ -384,8 +429,18 @@ pub(crate) fn cmd_rebase(
             delete_abandoned_bookmarks: false,
         },
         simplify_ancestor_merge: false,
+        skip_applied: args.skip_applied,
+        merge_strategies: load_merge_strategies(command.settings())?,
     };
     let mut workspace_command = command.workspace_helper(ui)?;
+    if load_paused_rebase(&workspace_command)?.is_some() {
+        return Err(user_error_with_hint(
+            "A rebase is paused at a conflicted revision",
+            "Run `jj rebase --continue` once the conflicts are resolved, or use `jj undo` or `jj \
+             op restore` to go back to before the rebase.",
+        ));
+    }
+    let pause_on_conflict = args.pause_on_conflict;
     if !args.revisions.is_empty() {
         rebase_revisions(
             ui,
@@ -393,6 +448,7 @@ pub(crate) fn cmd_rebase(
             &args.revisions,
             &args.destination,
             &rebase_options,
+            pause_on_conflict,
         )?;
     } else if !args.source.is_empty() {
         rebase_source(
@@ -401,6 +457,7 @@ pub(crate) fn cmd_rebase(
             &args.source,
             &args.destination,
             &rebase_options,
+            pause_on_conflict,
         )?;
     } else {
         rebase_branch(
@@ -409,6 +466,7 @@ pub(crate) fn cmd_rebase(
             &args.branch,
             &args.destination,
             &rebase_options,
+            pause_on_conflict,
         )?;
     }
     Ok(())
@@ -420,6 +478,7 @@ fn rebase_revisions(
     revisions: &[RevisionArg],
     rebase_destination: &RebaseDestinationArgs,
     rebase_options: &RebaseOptions,
+    pause_on_conflict: bool,
 ) -> Result<(), CommandError> {
     let target_commits: Vec<_> = workspace_command
         .parse_union_revsets(ui, revisions)?
@@ -452,6 +511,7 @@ fn rebase_revisions(
         &new_children,
         target_commits,
         rebase_options,
+        pause_on_conflict,
     )
 }
 
@@ -461,6 +521,7 @@ fn rebase_source(
     source: &[RevisionArg],
     rebase_destination: &RebaseDestinationArgs,
     rebase_options: &RebaseOptions,
+    pause_on_conflict: bool,
 ) -> Result<(), CommandError> {
     let source_commits = workspace_command
         .resolve_some_revsets_default_single(ui, source)?
@@ -489,6 +550,7 @@ fn rebase_source(
         &new_children,
         source_commits,
         rebase_options,
+        pause_on_conflict,
     )
 }
 
@@ -498,6 +560,7 @@ fn rebase_branch(
     branch: &[RevisionArg],
     rebase_destination: &RebaseDestinationArgs,
     rebase_options: &RebaseOptions,
+    pause_on_conflict: bool,
 ) -> Result<(), CommandError> {
     let branch_commits: Vec<_> = if branch.is_empty() {
         vec![workspace_command.resolve_single_rev(ui, &RevisionArg::AT)?]
@@ -542,6 +605,7 @@ fn rebase_branch(
         &new_children,
         root_commits,
         rebase_options,
+        pause_on_conflict,
     )
 }
 
@@ -552,11 +616,18 @@ fn rebase_descendants_transaction(
     new_children: &[Commit],
     target_roots: Vec<Commit>,
     rebase_options: &RebaseOptions,
+    pause_on_conflict: bool,
 ) -> Result<(), CommandError> {
     if target_roots.is_empty() {
         writeln!(ui.status(), "Nothing changed.")?;
         return Ok(());
     }
+    let pause_candidates = if pause_on_conflict {
+        let roots = target_roots.iter().chain(new_children).ids().cloned();
+        Some(find_pause_candidates(workspace_command, roots.collect())?)
+    } else {
+        None
+    };
 
     let mut tx = workspace_command.start_transaction();
     let tx_description = if target_roots.len() == 1 {
@@ -579,7 +650,11 @@ fn rebase_descendants_transaction(
         rebase_options,
     )?;
     print_move_commits_stats(ui, &stats)?;
-    tx.finish(ui, tx_description)
+    let paused = finish_rebase_transaction(ui, tx, tx_description, pause_candidates)?;
+    if let Some(paused) = paused {
+        save_paused_rebase(ui, workspace_command, paused)?;
+    }
+    Ok(())
 }
 
 /// Creates a transaction for rebasing revisions.
@@ -590,11 +665,18 @@ fn rebase_revisions_transaction(
     new_children: &[Commit],
     target_commits: Vec<Commit>,
     rebase_options: &RebaseOptions,
+    pause_on_conflict: bool,
 ) -> Result<(), CommandError> {
     if target_commits.is_empty() {
         writeln!(ui.status(), "Nothing changed.")?;
         return Ok(());
     }
+    let pause_candidates = if pause_on_conflict {
+        let roots = target_commits.iter().chain(new_children).ids().cloned();
+        Some(find_pause_candidates(workspace_command, roots.collect())?)
+    } else {
+        None
+    };
 
     let mut tx = workspace_command.start_transaction();
     let tx_description = if target_commits.len() == 1 {
@@ -615,7 +697,285 @@ fn rebase_revisions_transaction(
         rebase_options,
     )?;
     print_move_commits_stats(ui, &stats)?;
-    tx.finish(ui, tx_description)
+    let paused = finish_rebase_transaction(ui, tx, tx_description, pause_candidates)?;
+    if let Some(paused) = paused {
+        save_paused_rebase(ui, workspace_command, paused)?;
+    }
+    Ok(())
+}
+
+/// Finishes the rebase transaction. If `pause_candidates` is set, the rebase
+/// is paused at the first of them that became conflicted, and the state to
+/// resume from is returned.
+fn finish_rebase_transaction(
+    ui: &Ui,
+    mut tx: WorkspaceCommandTransaction,
+    tx_description: String,
+    pause_candidates: Option<Vec<String>>,
+) -> Result<Option<PausedRebase>, CommandError> {
+    let Some(pause_candidates) = pause_candidates else {
+        tx.finish(ui, tx_description)?;
+        return Ok(None);
+    };
+    let working_copy = match tx.base_workspace_helper().get_wc_commit_id() {
+        Some(commit_id) => {
+            let commit = tx.repo().store().get_commit(commit_id)?;
+            let parent = match commit.parent_ids() {
+                [parent_id] => Some(tx.repo().store().get_commit(parent_id)?),
+                _ => None,
+            };
+            PausedWorkingCopy {
+                change_id: Some(commit.change_id().hex()),
+                parent_change_id: parent.map(|parent| parent.change_id().hex()),
+            }
+        }
+        None => PausedWorkingCopy::default(),
+    };
+    let paused = pause_at_first_conflict(&mut tx, working_copy, &pause_candidates)?;
+    tx.finish(ui, tx_description)?;
+    Ok(paused)
+}
+
+/// Resumes a rebase that was paused by `--pause-on-conflict`.
+fn continue_paused_rebase(ui: &mut Ui, command: &CommandHelper) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let Some(state) = load_paused_rebase(&workspace_command)? else {
+        return Err(user_error("No rebase is paused in this workspace"));
+    };
+    let repo = workspace_command.repo().clone();
+    if let Some(commit) = resolve_paused_change(repo.as_ref(), &state.paused)? {
+        if commit.has_conflict()? {
+            return Err(user_error_with_hint(
+                format!(
+                    "Commit {} still has conflicts",
+                    short_commit_hash(commit.id())
+                ),
+                "Resolve the conflicts, then run `jj rebase --continue` again.",
+            ));
+        }
+    }
+
+    let mut tx = workspace_command.start_transaction();
+    let paused = pause_at_first_conflict(&mut tx, state.working_copy.clone(), &state.remaining)?;
+    if paused.is_none() {
+        restore_working_copy(&mut tx, &state.working_copy)?;
+    }
+    if tx.repo().has_changes() {
+        tx.finish(ui, "continue paused rebase")?;
+    }
+    match paused {
+        Some(paused) => save_paused_rebase(ui, &workspace_command, paused)?,
+        None => {
+            remove_paused_rebase(&paused_rebase_path(&workspace_command))?;
+            writeln!(ui.status(), "Rebase finished")?;
+        }
+    }
+    Ok(())
+}
+
+/// State of a rebase paused by `--pause-on-conflict`.
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+struct PausedRebase {
+    /// Id of the operation that paused the rebase. The state is discarded if
+    /// the operation is no longer in the operation history.
+    operation: String,
+    working_copy: PausedWorkingCopy,
+    /// Change id of the conflicted commit the rebase is paused at.
+    paused: String,
+    /// Change ids of the rebased commits still to be checked, parents first.
+    remaining: Vec<String>,
+}
+
+/// Working-copy commit before a paused rebase.
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+struct PausedWorkingCopy {
+    /// Change id of the working-copy commit.
+    change_id: Option<String>,
+    /// Change id of its parent, to check out a new commit on if the
+    /// working-copy commit got abandoned when the rebase paused.
+    parent_change_id: Option<String>,
+}
+
+fn paused_rebase_path(workspace_command: &WorkspaceCommandHelper) -> PathBuf {
+    workspace_command
+        .workspace_root()
+        .join(".jj")
+        .join("rebase-state.json")
+}
+
+/// Loads the state of the rebase paused in this workspace, if any.
+///
+/// The state is discarded if the operation that paused the rebase was undone,
+/// or isn't in the history of the current operation for another reason.
+fn load_paused_rebase(
+    workspace_command: &WorkspaceCommandHelper,
+) -> Result<Option<PausedRebase>, CommandError> {
+    let path = paused_rebase_path(workspace_command);
+    let Some(state) = read_paused_rebase(&path)? else {
+        return Ok(None);
+    };
+    if is_in_op_history(workspace_command.repo().operation(), &state.operation)? {
+        Ok(Some(state))
+    } else {
+        remove_paused_rebase(&path)?;
+        Ok(None)
+    }
+}
+
+/// Records the paused rebase, along with the current operation.
+fn save_paused_rebase(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    mut state: PausedRebase,
+) -> Result<(), CommandError> {
+    state.operation = workspace_command.repo().op_id().hex();
+    write_paused_rebase(&paused_rebase_path(workspace_command), &state)?;
+    print_paused_rebase(ui)?;
+    Ok(())
+}
+
+/// Discards the rebase paused in this workspace if the repo was restored to
+/// `op`, and `op` precedes the pause.
+pub(crate) fn discard_paused_rebase_on_restore(
+    workspace_command: &WorkspaceCommandHelper,
+    op: &Operation,
+) -> Result<(), CommandError> {
+    let path = paused_rebase_path(workspace_command);
+    if let Some(state) = read_paused_rebase(&path)? {
+        if !is_in_op_history(op, &state.operation)? {
+            remove_paused_rebase(&path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns true if the operation with the hex id `op_id` is `op` or one of its
+/// ancestors.
+fn is_in_op_history(op: &Operation, op_id: &str) -> Result<bool, CommandError> {
+    let Ok(op_id) = OperationId::try_from_hex(op_id) else {
+        return Ok(false);
+    };
+    for ancestor in op_walk::walk_ancestors(slice::from_ref(op)) {
+        if *ancestor?.id() == op_id {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn read_paused_rebase(path: &Path) -> Result<Option<PausedRebase>, CommandError> {
+    let content = match fs::read(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(internal_error_with_message(
+                "Failed to read paused rebase state",
+                err,
+            ))
+        }
+    };
+    let state = serde_json::from_slice(&content)
+        .map_err(|err| internal_error_with_message("Invalid paused rebase state", err))?;
+    Ok(Some(state))
+}
+
+fn write_paused_rebase(path: &Path, state: &PausedRebase) -> Result<(), CommandError> {
+    let content = serde_json::to_vec(state).unwrap();
+    fs::write(path, content)
+        .map_err(|err| internal_error_with_message("Failed to write paused rebase state", err))
+}
+
+fn remove_paused_rebase(path: &Path) -> Result<(), CommandError> {
+    fs::remove_file(path)
+        .map_err(|err| internal_error_with_message("Failed to remove paused rebase state", err))
+}
+
+/// Returns the change ids of the descendants of `roots` that aren't conflicted
+/// yet, parents first.
+fn find_pause_candidates(
+    workspace_command: &WorkspaceCommandHelper,
+    roots: Vec<CommitId>,
+) -> Result<Vec<String>, CommandError> {
+    let repo = workspace_command.repo().as_ref();
+    let commits: Vec<_> = RevsetExpression::commits(roots)
+        .descendants()
+        .evaluate(repo)?
+        .iter()
+        .commits(repo.store())
+        .try_collect()?;
+    let mut change_ids = vec![];
+    for commit in commits.iter().rev() {
+        if !commit.has_conflict()? {
+            change_ids.push(commit.change_id().hex());
+        }
+    }
+    Ok(change_ids)
+}
+
+/// Edits the first commit among `candidates` that has conflicts, and returns
+/// the state to resume from.
+fn pause_at_first_conflict(
+    tx: &mut WorkspaceCommandTransaction,
+    working_copy: PausedWorkingCopy,
+    candidates: &[String],
+) -> Result<Option<PausedRebase>, CommandError> {
+    tx.rebase_descendants()?;
+    for (i, change_id) in candidates.iter().enumerate() {
+        let Some(commit) = resolve_paused_change(tx.repo(), change_id)? else {
+            continue;
+        };
+        if commit.has_conflict()? {
+            tx.edit(&commit)?;
+            return Ok(Some(PausedRebase {
+                // Set once the transaction is committed
+                operation: String::new(),
+                working_copy,
+                paused: change_id.clone(),
+                remaining: candidates[i + 1..].to_vec(),
+            }));
+        }
+    }
+    Ok(None)
+}
+
+/// Moves the working copy back to where it was before the paused rebase.
+fn restore_working_copy(
+    tx: &mut WorkspaceCommandTransaction,
+    working_copy: &PausedWorkingCopy,
+) -> Result<(), CommandError> {
+    if let Some(change_id) = &working_copy.change_id {
+        if let Some(commit) = resolve_paused_change(tx.repo(), change_id)? {
+            if tx.base_workspace_helper().get_wc_commit_id() != Some(commit.id()) {
+                tx.edit(&commit)?;
+            }
+            return Ok(());
+        }
+    }
+    if let Some(change_id) = &working_copy.parent_change_id {
+        if let Some(parent) = resolve_paused_change(tx.repo(), change_id)? {
+            tx.check_out(&parent)?;
+        }
+    }
+    Ok(())
+}
+
+/// Looks up the visible commit for a change id recorded in the paused rebase
+/// state. Abandoned and divergent changes are skipped.
+fn resolve_paused_change(repo: &dyn Repo, change_id: &str) -> Result<Option<Commit>, CommandError> {
+    let change_id = ChangeId::try_from_hex(change_id)
+        .map_err(|err| internal_error_with_message("Invalid paused rebase state", err))?;
+    match repo.resolve_change_id(&change_id).as_deref() {
+        Some([commit_id]) => Ok(Some(repo.store().get_commit(commit_id)?)),
+        _ => Ok(None),
+    }
+}
+
+fn print_paused_rebase(ui: &Ui) -> io::Result<()> {
+    writeln!(
+        ui.status(),
+        "Rebase paused at a conflicted commit. Resolve the conflicts, then run `jj rebase \
+         --continue`."
+    )
 }
 
 fn check_rebase_destinations(
@@ -645,6 +1005,7 @@ fn print_move_commits_stats(ui: &Ui, stats: &MoveCommitsStats) -> std::io::Resul
         num_rebased_descendants,
         num_skipped_rebases,
         num_abandoned,
+        num_skipped_applied,
     } = stats;
     if num_skipped_rebases > 0 {
         writeln!(
@@ -667,5 +1028,11 @@ fn print_move_commits_stats(ui: &Ui, stats: &MoveCommitsStats) -> std::io::Resul
     if num_abandoned > 0 {
         writeln!(formatter, "Abandoned {num_abandoned} newly emptied commits")?;
     }
+    if num_skipped_applied > 0 {
+        writeln!(
+            formatter,
+            "Abandoned {num_skipped_applied} commits that were already applied in destination"
+        )?;
+    }
     Ok(())
 }
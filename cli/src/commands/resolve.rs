@@ -12,18 +12,27 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io::Write as _;
+
 use clap_complete::ArgValueCandidates;
 use clap_complete::ArgValueCompleter;
 use itertools::Itertools;
+use jj_lib::backend::BackendResult;
+use jj_lib::commit::Commit;
+use jj_lib::merge::MergedTreeValue;
 use jj_lib::object_id::ObjectId;
+use jj_lib::repo_path::RepoPathBuf;
 use tracing::instrument;
 
+use crate::cli_util::conflict_term_commits;
 use crate::cli_util::print_conflicted_paths;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::command_error::cli_error;
 use crate::command_error::CommandError;
 use crate::complete;
+use crate::merge_tools::BUILTIN_3PANE_EDITOR_NAME;
 use crate::ui::Ui;
 
 /// Resolve conflicted files with an external merge tool
@@ -34,6 +43,16 @@ use crate::ui::Ui;
 /// resolved. To stop resolving conflicts, exit the merge tool without making
 /// any changes.
 ///
+/// Conflicts may be resolved partially. With the built-in merge tool, conflict
+/// regions in which no line is selected are left unresolved. External tools
+/// can leave conflict markers in the output file if they're configured with
+/// `merge-tool-edits-conflict-markers` or `merge-conflict-exit-codes`.
+///
+/// With `--interactive`, the conflict regions are shown one by one, and each
+/// can be resolved by taking the left, base, or right side, or by editing it,
+/// optionally starting from one of the sides. Only files in which every
+/// conflict region was resolved are marked as resolved.
+///
 /// Note that conflicts can also be resolved without using this command. You may
 /// edit the conflict markers in the conflicted file directly with a text
 /// editor.
@@ -58,9 +77,23 @@ pub(crate) struct ResolveArgs {
     // `diff --summary`, but should be more verbose.
     #[arg(long, short)]
     list: bool,
+    /// With `--list`, print the conflicts as JSON
+    ///
+    /// For each conflicted path, the output includes the number of sides, the
+    /// commits the sides and bases came from (if the revision merges
+    /// conflict-free parents), and the type of the conflict: `content` if the
+    /// file contents conflict, `executable` if only the executable bits
+    /// conflict, `delete` if some side deleted the file, or `other` if a side
+    /// isn't a regular file.
+    #[arg(long, requires = "list")]
+    json: bool,
     /// Specify 3-way merge tool to be used
     #[arg(long, conflicts_with = "list", value_name = "NAME")]
     tool: Option<String>,
+    /// Step through the conflict regions one by one in the built-in
+    /// `:builtin-3pane` merge tool
+    #[arg(long, short, conflicts_with_all = ["list", "tool"])]
+    interactive: bool,
     /// Only resolve conflicts in these paths. You can use the `--list` argument
     /// to find paths to use here.
     #[arg(
@@ -94,7 +127,9 @@ pub(crate) fn cmd_resolve(
             "No conflicts found at the given path(s)"
         }));
     }
-    if args.list {
+    if args.list && args.json {
+        return print_conflicts_json(ui, &workspace_command, &commit, conflicts);
+    } else if args.list {
         return print_conflicted_paths(
             conflicts,
             ui.stdout_formatter().as_mut(),
@@ -107,7 +142,12 @@ pub(crate) fn cmd_resolve(
         .map(|(path, _)| path.as_ref())
         .collect_vec();
     workspace_command.check_rewritable([commit.id()])?;
-    let merge_editor = workspace_command.merge_editor(ui, args.tool.as_deref())?;
+    let tool_name = if args.interactive {
+        Some(BUILTIN_3PANE_EDITOR_NAME)
+    } else {
+        args.tool.as_deref()
+    };
+    let merge_editor = workspace_command.merge_editor(ui, tool_name)?;
     let mut tx = workspace_command.start_transaction();
     let (new_tree_id, partial_resolution_error) =
         merge_editor.edit_files(ui, &tree, &repo_paths)?;
@@ -143,3 +183,63 @@ pub(crate) fn cmd_resolve(
     }
     Ok(())
 }
+
+fn print_conflicts_json(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    commit: &Commit,
+    conflicts: Vec<(RepoPathBuf, BackendResult<MergedTreeValue>)>,
+) -> Result<(), CommandError> {
+    let term_commits = conflict_term_commits(workspace_command.repo().as_ref(), commit)?;
+    let commit_json = |commit: Option<&Commit>| match commit {
+        Some(commit) => serde_json::json!({
+            "commit_id": commit.id().hex(),
+            "change_id": commit.change_id().reverse_hex(),
+        }),
+        None => serde_json::Value::Null,
+    };
+    let mut conflicts_json = vec![];
+    for (path, conflict) in conflicts {
+        let conflict = conflict?;
+        // Keep the commits in sync with the simplified conflict
+        let commits = term_commits
+            .as_ref()
+            .filter(|commits| commits.num_sides() == conflict.num_sides())
+            .map(|commits| conflict.simplify_other(commits));
+        let conflict = conflict.simplify();
+        let sides = (0..conflict.num_sides())
+            .map(|i| commit_json(commits.as_ref().and_then(|commits| commits.get_add(i))))
+            .collect_vec();
+        let bases = (0..conflict.num_sides() - 1)
+            .map(|i| commit_json(commits.as_ref().and_then(|commits| commits.get_remove(i))))
+            .collect_vec();
+        conflicts_json.push(serde_json::json!({
+            "path": path.as_internal_file_string(),
+            "num_sides": conflict.num_sides(),
+            "type": conflict_type(&conflict),
+            "sides": sides,
+            "bases": bases,
+        }));
+    }
+    let value = serde_json::json!({
+        "commit_id": commit.id().hex(),
+        "conflicts": conflicts_json,
+    });
+    writeln!(ui.stdout(), "{value}")?;
+    Ok(())
+}
+
+/// Classifies a simplified conflict by what makes it conflict.
+fn conflict_type(conflict: &MergedTreeValue) -> &'static str {
+    if conflict.adds().any(|term| term.is_none()) {
+        return "delete";
+    }
+    let Some(file_ids) = conflict.to_file_merge() else {
+        return "other";
+    };
+    if file_ids.resolve_trivial().is_none() {
+        "content"
+    } else {
+        "executable"
+    }
+}
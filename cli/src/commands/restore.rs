@@ -19,8 +19,10 @@ use clap_complete::ArgValueCompleter;
 use indoc::formatdoc;
 use itertools::Itertools as _;
 use jj_lib::object_id::ObjectId;
+use jj_lib::repo::Repo as _;
 use tracing::instrument;
 
+use crate::cli_util::short_operation_hash;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
 use crate::command_error::user_error;
@@ -37,13 +39,19 @@ use crate::ui::Ui;
 /// If only one of `--from` or `--to` is specified, the other one defaults to
 /// the working copy.
 ///
+/// With `--from-op`, the paths are restored from the working-copy commit as it
+/// was recorded by an earlier operation. This can be used to recover changes
+/// that were discarded since then without restoring the whole repo with `jj op
+/// restore`.
+///
 /// When neither `--from` nor `--to` is specified, the command restores into the
 /// working copy from its parent(s). `jj restore` without arguments is similar
 /// to `jj abandon`, except that it leaves an empty revision with its
 /// description and other metadata preserved.
 ///
-/// See `jj diffedit` if you'd like to restore portions of files rather than
-/// entire files.
+/// With `--interactive` (or `--tool`), the diff editor shows the changes to be
+/// restored from the source into the destination, and individual hunks can be
+/// selected instead of entire files.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct RestoreArgs {
     /// Restore only these paths (instead of all paths)
@@ -61,6 +69,14 @@ pub(crate) struct RestoreArgs {
         add = ArgValueCandidates::new(complete::all_revisions)
     )]
     from: Option<RevisionArg>,
+    /// Operation whose working-copy commit to restore from (source)
+    #[arg(
+        long,
+        value_name = "OPERATION",
+        conflicts_with = "from",
+        add = ArgValueCandidates::new(complete::operations)
+    )]
+    from_op: Option<String>,
     /// Revision to restore into (destination)
     #[arg(
         long, short = 't',
@@ -80,7 +96,7 @@ pub(crate) struct RestoreArgs {
     #[arg(
         long, short,
         value_name = "REVSET",
-        conflicts_with_all = ["into", "from"],
+        conflicts_with_all = ["into", "from", "from_op"],
         add = ArgValueCandidates::new(complete::all_revisions),
     )]
     changes_in: Option<RevisionArg>,
@@ -119,7 +135,26 @@ pub(crate) fn cmd_restore(
              revision,\nuse `--into` or `--changes-in`.",
         ));
     }
-    if args.from.is_some() || args.into.is_some() {
+    if let Some(op_str) = &args.from_op {
+        to_commit = workspace_command
+            .resolve_single_rev(ui, args.into.as_ref().unwrap_or(&RevisionArg::AT))?;
+        let op = workspace_command.resolve_single_op(op_str)?;
+        let op_repo = workspace_command.repo().loader().load_at(&op)?;
+        let workspace_id = workspace_command.workspace_id();
+        let from_commit_id = op_repo
+            .view()
+            .get_wc_commit_id(workspace_id)
+            .ok_or_else(|| {
+                user_error(format!(
+                    "Workspace {name} has no working-copy commit at operation {op_id}",
+                    name = workspace_id.as_str(),
+                    op_id = short_operation_hash(op.id()),
+                ))
+            })?;
+        let from_commit = op_repo.store().get_commit(from_commit_id)?;
+        from_tree = from_commit.tree()?;
+        from_commits = vec![from_commit];
+    } else if args.from.is_some() || args.into.is_some() {
         to_commit = workspace_command
             .resolve_single_rev(ui, args.into.as_ref().unwrap_or(&RevisionArg::AT))?;
         let from_commit = workspace_command
@@ -174,7 +209,7 @@ pub(crate) fn cmd_restore(
                 " (while preserving their content)",
             )
         } else {
-            (tx.repo_mut().rebase_descendants()?, "")
+            (tx.rebase_descendants()?, "")
         };
         if let Some(mut formatter) = ui.status_formatter() {
             write!(formatter, "Created ")?;
@@ -0,0 +1,296 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use clap_complete::ArgValueCompleter;
+use jj_lib::copies::CopyRecords;
+use jj_lib::repo::Repo as _;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::revset::RevsetFilterPredicate;
+use jj_lib::revset::RevsetIteratorExt as _;
+use jj_lib::settings::UserSettings;
+use pollster::FutureExt as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::diff_util::DiffStatOptions;
+use crate::diff_util::DiffStats;
+use crate::generic_templater::GenericTemplateLanguage;
+use crate::template_builder::TemplateLanguage as _;
+use crate::templater::Email;
+use crate::templater::TemplatePropertyExt as _;
+use crate::ui::Ui;
+
+/// Summarize revisions by author
+///
+/// Lists the subjects of the revisions grouped by author, oldest revision
+/// first. Authors are shown with their canonical names and emails as mapped by
+/// the `.mailmap` file of the working-copy commit and the file configured by
+/// `mailmap.file`.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct ShortlogArgs {
+    /// Which revisions to summarize [default: ::@ ~ root()]
+    #[arg(
+        long,
+        short,
+        value_name = "REVSETS",
+        add = ArgValueCandidates::new(complete::all_revisions)
+    )]
+    revisions: Vec<RevisionArg>,
+    /// Only summarize revisions modifying the given paths
+    ///
+    /// The line counts of `--stat` only include changes to these paths.
+    #[arg(
+        value_name = "FILESETS",
+        value_hint = clap::ValueHint::AnyPath,
+        add = ArgValueCompleter::new(complete::log_files),
+    )]
+    paths: Vec<String>,
+    /// Only show the number of revisions of each author
+    #[arg(long, short, conflicts_with = "template")]
+    summary: bool,
+    /// Sort authors by number of revisions instead of by name
+    #[arg(long, short)]
+    numbered: bool,
+    /// Show the email address of each author
+    ///
+    /// Authors with the same name but different emails are listed separately.
+    #[arg(long, short)]
+    email: bool,
+    /// Also count the lines added and removed by each author
+    #[arg(long)]
+    stat: bool,
+    /// Render each author using the given template
+    ///
+    /// The following keywords are available in the template expression:
+    ///
+    /// * `name: String`: Canonical name of the author.
+    /// * `email: Email`: Canonical email of the author's newest revision.
+    /// * `count: Integer`: Number of revisions.
+    /// * `subjects: List<String>`: Subjects of the revisions, oldest first.
+    /// * `added: Option<Integer>`: Number of added lines if `--stat` is set.
+    /// * `removed: Option<Integer>`: Number of removed lines if `--stat` is
+    ///   set.
+    ///
+    /// See [`jj help -k templates`] for more information.
+    ///
+    /// [`jj help -k templates`]:
+    ///     https://jj-vcs.github.io/jj/latest/templates/
+    #[arg(
+        long, short = 'T',
+        verbatim_doc_comment,
+        add = ArgValueCandidates::new(complete::template_aliases)
+    )]
+    template: Option<String>,
+    /// Print the summary as JSON
+    ///
+    /// The output is an array with an object per author, which has the same
+    /// fields as the template keywords.
+    #[arg(long, conflicts_with_all = ["summary", "template"])]
+    json: bool,
+}
+
+/// Revisions of one author.
+#[derive(Clone, Debug)]
+struct AuthorSummary {
+    name: String,
+    email: String,
+    /// Subjects of the revisions, oldest first.
+    subjects: Vec<String>,
+    /// Numbers of added and removed lines, if requested.
+    stats: Option<(usize, usize)>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_shortlog(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &ShortlogArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let revisions = if args.revisions.is_empty() {
+        vec![RevisionArg::from("::@ ~ root()".to_owned())]
+    } else {
+        args.revisions.clone()
+    };
+    let fileset_expression = workspace_command.parse_file_patterns(ui, &args.paths)?;
+    let matcher = fileset_expression.to_matcher();
+    let template = args
+        .template
+        .as_ref()
+        .map(|text| {
+            let language = shortlog_template_language(workspace_command.settings());
+            workspace_command.parse_template(
+                ui,
+                &language,
+                text,
+                GenericTemplateLanguage::wrap_self,
+            )
+        })
+        .transpose()?
+        .map(|template| template.labeled("shortlog"));
+
+    let repo = workspace_command.repo();
+    let mailmap = workspace_command.mailmap();
+    let mut expression = workspace_command.parse_union_revsets(ui, &revisions)?;
+    if !args.paths.is_empty() {
+        let predicate = RevsetFilterPredicate::File(fileset_expression.clone());
+        expression.intersect_with(&RevsetExpression::filter(predicate));
+    }
+    let revset = expression.evaluate()?;
+
+    let mut summaries: BTreeMap<(String, String), AuthorSummary> = BTreeMap::new();
+    for commit in revset.iter().commits(repo.store()) {
+        let commit = commit?;
+        let author = commit.author();
+        let (name, email) = mailmap.resolve(&author.name, &author.email);
+        // The email of the newest revision is kept if authors are grouped by
+        // name only.
+        let key_email = if args.email { email } else { "" };
+        let summary = summaries
+            .entry((name.to_owned(), key_email.to_owned()))
+            .or_insert_with(|| AuthorSummary {
+                name: name.to_owned(),
+                email: email.to_owned(),
+                subjects: vec![],
+                stats: args.stat.then_some((0, 0)),
+            });
+        let subject = commit.description().lines().next().unwrap_or_default();
+        summary.subjects.push(subject.to_owned());
+        if let Some((added, removed)) = &mut summary.stats {
+            let from_tree = commit.parent_tree(repo.as_ref())?;
+            let to_tree = commit.tree()?;
+            let copy_records = CopyRecords::default();
+            let tree_diff = from_tree.diff_stream_with_copies(&to_tree, &matcher, &copy_records);
+            let stats = DiffStats::calculate(
+                repo.store(),
+                tree_diff,
+                &DiffStatOptions::default(),
+                workspace_command.env().conflict_marker_style(),
+            )
+            .block_on()?;
+            *added += stats.count_total_added();
+            *removed += stats.count_total_removed();
+        }
+    }
+    let mut summaries: Vec<_> = summaries.into_values().collect();
+    for summary in &mut summaries {
+        // The revisions are iterated from newest to oldest.
+        summary.subjects.reverse();
+    }
+    if args.numbered {
+        // The sort is stable, so authors with the same count stay sorted by
+        // name.
+        summaries.sort_by_key(|summary| Reverse(summary.subjects.len()));
+    }
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    if args.json {
+        let value: Vec<_> = summaries.iter().map(summary_to_json).collect();
+        writeln!(formatter, "{}", serde_json::Value::from(value))?;
+        return Ok(());
+    }
+    for summary in &summaries {
+        if let Some(template) = &template {
+            template.format(summary, formatter.as_mut())?;
+            continue;
+        }
+        let author = if args.email {
+            format!("{} <{}>", summary.name, summary.email)
+        } else {
+            summary.name.clone()
+        };
+        let stats = summary
+            .stats
+            .map(|(added, removed)| format!(" +{added} -{removed}"))
+            .unwrap_or_default();
+        if args.summary {
+            writeln!(formatter, "{:6}\t{author}{stats}", summary.subjects.len())?;
+            continue;
+        }
+        writeln!(formatter, "{author} ({}):{stats}", summary.subjects.len())?;
+        for subject in &summary.subjects {
+            writeln!(formatter, "      {subject}")?;
+        }
+        writeln!(formatter)?;
+    }
+    Ok(())
+}
+
+fn summary_to_json(summary: &AuthorSummary) -> serde_json::Value {
+    let mut value = serde_json::json!({
+        "name": summary.name,
+        "email": summary.email,
+        "count": summary.subjects.len(),
+        "subjects": summary.subjects,
+    });
+    if let Some((added, removed)) = summary.stats {
+        value["added"] = added.into();
+        value["removed"] = removed.into();
+    }
+    value
+}
+
+// AuthorSummary will be cloned internally in the templater. If the cloning
+// cost matters, wrap it with Rc.
+fn shortlog_template_language(
+    settings: &UserSettings,
+) -> GenericTemplateLanguage<'static, AuthorSummary> {
+    type L = GenericTemplateLanguage<'static, AuthorSummary>;
+    let mut language = L::new(settings);
+    language.add_keyword("name", |self_property| {
+        let out_property = self_property.map(|summary| summary.name);
+        Ok(L::wrap_string(out_property))
+    });
+    language.add_keyword("email", |self_property| {
+        let out_property = self_property.map(|summary| Email(summary.email));
+        Ok(L::wrap_email(out_property))
+    });
+    language.add_keyword("count", |self_property| {
+        let out_property =
+            self_property.and_then(|summary| Ok(summary.subjects.len().try_into()?));
+        Ok(L::wrap_integer(out_property))
+    });
+    language.add_keyword("subjects", |self_property| {
+        let out_property = self_property.map(|summary| summary.subjects);
+        Ok(L::wrap_string_list(out_property))
+    });
+    language.add_keyword("added", |self_property| {
+        let out_property = self_property.and_then(|summary| {
+            Ok(summary
+                .stats
+                .map(|(added, _)| added.try_into())
+                .transpose()?)
+        });
+        Ok(L::wrap_integer_opt(out_property))
+    });
+    language.add_keyword("removed", |self_property| {
+        let out_property = self_property.and_then(|summary| {
+            Ok(summary
+                .stats
+                .map(|(_, removed)| removed.try_into())
+                .transpose()?)
+        });
+        Ok(L::wrap_integer_opt(out_property))
+    });
+    language
+}
@@ -24,6 +24,11 @@ use crate::diff_util::DiffFormatArgs;
 use crate::ui::Ui;
 
 /// Show commit description and changes in a revision
+///
+/// Merge commits are compared to the automatic merge of their parents, like
+/// `git show --remerge-diff`. Conflicts in that merge are shown with conflict
+/// markers, so the diff shows how they were resolved along with any other
+/// changes made in the merge commit.
 #[derive(clap::Args, Clone, Debug)]
 pub(crate) struct ShowArgs {
     /// Show changes in this revision, compared to its parent(s)
@@ -25,6 +25,7 @@ use crate::cli_util::RevisionArg;
 use crate::command_error::user_error_with_hint;
 use crate::command_error::CommandError;
 use crate::complete;
+use crate::progress::counter_progress;
 use crate::ui::Ui;
 
 /// Cryptographically sign a revision
@@ -84,7 +85,9 @@ pub fn cmd_sign(ui: &mut Ui, command: &CommandHelper, args: &SignArgs) -> Result
     let mut tx = workspace_command.start_transaction();
 
     let mut signed_commits = vec![];
+    let mut resigned_commits = vec![];
     let mut num_reparented = 0;
+    let mut progress = counter_progress(ui, "Signing commits", to_sign.len());
 
     tx.repo_mut().transform_descendants(
         to_sign.iter().ids().cloned().collect_vec(),
@@ -101,7 +104,14 @@ pub fn cmd_sign(ui: &mut Ui, command: &CommandHelper, args: &SignArgs) -> Result
                     .set_sign_behavior(SignBehavior::Force)
                     .write()?;
 
-                signed_commits.push(new_commit);
+                if old_commit.is_signed() {
+                    resigned_commits.push(new_commit);
+                } else {
+                    signed_commits.push(new_commit);
+                }
+                if let Some(progress) = &mut progress {
+                    progress(signed_commits.len() + resigned_commits.len());
+                }
             } else {
                 commit_builder.write()?;
                 num_reparented += 1;
@@ -110,12 +120,19 @@ pub fn cmd_sign(ui: &mut Ui, command: &CommandHelper, args: &SignArgs) -> Result
             Ok(())
         },
     )?;
+    drop(progress);
 
     if let Some(mut formatter) = ui.status_formatter() {
-        if !signed_commits.is_empty() {
-            let template = tx.commit_summary_template();
-            writeln!(formatter, "Signed {} commits:", signed_commits.len())?;
-            for commit in &signed_commits {
+        let template = tx.commit_summary_template();
+        for (verb, commits) in [
+            ("Signed", &signed_commits),
+            ("Re-signed", &resigned_commits),
+        ] {
+            if commits.is_empty() {
+                continue;
+            }
+            writeln!(formatter, "{verb} {} commits:", commits.len())?;
+            for commit in commits {
                 write!(formatter, "  ")?;
                 template.format(commit, formatter.as_mut())?;
                 writeln!(formatter)?;
@@ -123,6 +140,7 @@ pub fn cmd_sign(ui: &mut Ui, command: &CommandHelper, args: &SignArgs) -> Result
         }
     }
 
+    signed_commits.extend(resigned_commits);
     let num_not_authored_by_me = signed_commits
         .iter()
         .filter(|commit| commit.author().email != tx.settings().user_email())
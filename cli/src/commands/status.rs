@@ -14,19 +14,29 @@
 
 use std::io;
 
+use futures::executor::block_on_stream;
 use itertools::Itertools;
+use jj_lib::commit::Commit;
 use jj_lib::copies::CopyRecords;
+use jj_lib::local_working_copy::FileType;
+use jj_lib::local_working_copy::LocalWorkingCopy;
+use jj_lib::matchers::Matcher;
+use jj_lib::operation::Operation;
 use jj_lib::repo::Repo;
+use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::revset::RevsetFilterPredicate;
 use tracing::instrument;
 
+use crate::cli_util::conflict_term_commits;
 use crate::cli_util::print_conflicted_paths;
 use crate::cli_util::print_snapshot_stats;
 use crate::cli_util::CommandHelper;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::command_error::CommandError;
 use crate::diff_util::get_copy_records;
 use crate::diff_util::DiffFormat;
+use crate::formatter::Formatter;
 use crate::ui::Ui;
 
 /// Show high-level repo status
@@ -43,8 +53,18 @@ use crate::ui::Ui;
 #[command(visible_alias = "st")]
 pub(crate) struct StatusArgs {
     /// Restrict the status display to these paths
+    ///
+    /// If no paths are specified, the whole workspace is shown, or only the
+    /// current directory if `ui.path-scope` is set to `"cwd"`.
     #[arg(value_name = "FILESETS", value_hint = clap::ValueHint::AnyPath)]
     paths: Vec<String>,
+    /// Search further back for the operations that created the conflicts
+    ///
+    /// By default, only the last few operations are searched for the
+    /// operations after which the paths became conflicted, and the changes the
+    /// conflict sides came from.
+    #[arg(long)]
+    conflict_origins: bool,
 }
 
 #[instrument(skip_all)]
@@ -65,7 +85,7 @@ pub(crate) fn cmd_status(
         .map(|id| repo.store().get_commit(id))
         .transpose()?;
     let matcher = workspace_command
-        .parse_file_patterns(ui, &args.paths)?
+        .parse_file_patterns_in_scope(ui, &args.paths)?
         .to_matcher();
     ui.request_pager();
     let mut formatter = ui.stdout_formatter();
@@ -75,8 +95,18 @@ pub(crate) fn cmd_status(
         let parent_tree = wc_commit.parent_tree(repo.as_ref())?;
         let tree = wc_commit.tree()?;
 
-        let wc_has_changes = tree.id() != parent_tree.id();
-        let wc_has_untracked = !snapshot_stats.untracked_paths.is_empty();
+        let wc_has_changes = tree.id() != parent_tree.id()
+            && block_on_stream(parent_tree.diff_stream(&tree, matcher.as_ref()))
+                .next()
+                .is_some();
+        // Untracked paths aren't restricted by the given paths, but only by
+        // the current directory scope, which also applies to auto-tracking.
+        let untracked_paths = snapshot_stats
+            .untracked_paths
+            .keys()
+            .filter(|path| !args.paths.is_empty() || matcher.matches(path))
+            .collect_vec();
+        let wc_has_untracked = !untracked_paths.is_empty();
         if !wc_has_changes && !wc_has_untracked {
             writeln!(formatter, "The working copy has no changes.")?;
         } else {
@@ -103,7 +133,7 @@ pub(crate) fn cmd_status(
             if wc_has_untracked {
                 writeln!(formatter, "Untracked paths:")?;
                 formatter.with_label("diff", |formatter| {
-                    for path in snapshot_stats.untracked_paths.keys() {
+                    for path in untracked_paths {
                         let ui_path = workspace_command.path_converter().format_file_path(path);
                         writeln!(formatter.labeled("untracked"), "? {ui_path}")?;
                     }
@@ -123,6 +153,8 @@ pub(crate) fn cmd_status(
             writeln!(formatter)?;
         }
 
+        print_skipped_paths(formatter, &workspace_command, &matcher)?;
+
         if wc_commit.has_conflict()? {
             // TODO: Conflicts should also be filtered by the `matcher`. See the related
             // TODO on `MergedTree::conflicts()`.
@@ -131,7 +163,20 @@ pub(crate) fn cmd_status(
                 formatter.labeled("warning").with_heading("Warning: "),
                 "There are unresolved conflicts at these paths:"
             )?;
+            let conflicted_paths = conflicts.iter().map(|(path, _)| path.clone()).collect_vec();
             print_conflicted_paths(conflicts, formatter, &workspace_command)?;
+            let max_operations = if args.conflict_origins {
+                MAX_CONFLICT_ORIGIN_OPERATIONS
+            } else {
+                DEFAULT_CONFLICT_ORIGIN_OPERATIONS
+            };
+            print_conflict_origins(
+                formatter,
+                &workspace_command,
+                wc_commit,
+                conflicted_paths,
+                max_operations,
+            )?;
 
             let wc_revset = RevsetExpression::commit(wc_commit.id().clone());
 
@@ -208,5 +253,210 @@ pub(crate) fn cmd_status(
         )?;
     }
 
+    #[cfg(feature = "git")]
+    if let Some(wc_commit) = &maybe_wc_commit {
+        crate::git_util::write_remote_staleness_notice(
+            ui,
+            formatter,
+            &workspace_command,
+            wc_commit,
+        )?;
+    }
+    #[cfg(feature = "git")]
+    crate::git_util::maybe_spawn_background_fetch(
+        ui,
+        workspace_command.settings(),
+        workspace_command.workspace_root(),
+        workspace_command.repo_path(),
+    )?;
+
+    Ok(())
+}
+
+/// Number of operations to look through for the origins of conflicts by
+/// default.
+const DEFAULT_CONFLICT_ORIGIN_OPERATIONS: usize = 10;
+
+/// Maximum number of operations to look through for the origins of conflicts
+/// with `--conflict-origins`.
+const MAX_CONFLICT_ORIGIN_OPERATIONS: usize = 100;
+
+/// Where the conflicts at some paths came from.
+struct ConflictOrigin {
+    /// The operation after which the paths became conflicted.
+    operation: Operation,
+    paths: Vec<RepoPathBuf>,
+    /// Commits whose changes are the sides of the conflicts.
+    sides: Vec<Commit>,
+    /// The conflicted parent the conflicts were inherited from.
+    inherited_from: Option<Commit>,
+}
+
+/// Prints which operations created the conflicts in the working-copy commit,
+/// and which changes the sides came from.
+fn print_conflict_origins(
+    formatter: &mut dyn Formatter,
+    workspace_command: &WorkspaceCommandHelper,
+    wc_commit: &Commit,
+    conflicted_paths: Vec<RepoPathBuf>,
+    max_operations: usize,
+) -> Result<(), CommandError> {
+    let origins = find_conflict_origins(
+        workspace_command,
+        wc_commit,
+        conflicted_paths,
+        max_operations,
+    )?;
+    let op_template = workspace_command.operation_summary_template();
+    let change_id_template = workspace_command.short_change_id_template();
+    for origin in &origins {
+        let ui_paths = origin
+            .paths
+            .iter()
+            .map(|path| workspace_command.format_file_path(path))
+            .join(", ");
+        write!(formatter, "Conflicts in {ui_paths} came from operation ")?;
+        op_template.format(&origin.operation, formatter)?;
+        if let Some(parent) = &origin.inherited_from {
+            write!(formatter, ", inherited from ")?;
+            change_id_template.format(parent, formatter)?;
+        } else if let [sides @ .., last] = &origin.sides[..] {
+            write!(formatter, ", with sides from ")?;
+            for side in sides {
+                change_id_template.format(side, formatter)?;
+                write!(formatter, ", ")?;
+            }
+            change_id_template.format(last, formatter)?;
+        }
+        writeln!(formatter)?;
+    }
+    Ok(())
+}
+
+/// Walks the operation log back to find the operations after which the paths
+/// of the working-copy commit became conflicted.
+fn find_conflict_origins(
+    workspace_command: &WorkspaceCommandHelper,
+    wc_commit: &Commit,
+    mut remaining_paths: Vec<RepoPathBuf>,
+    max_operations: usize,
+) -> Result<Vec<ConflictOrigin>, CommandError> {
+    let repo_loader = workspace_command.workspace().repo_loader();
+    let mut repo = workspace_command.repo().clone();
+    let mut commit = wc_commit.clone();
+    let mut origins = vec![];
+    for _ in 0..max_operations {
+        let Some(parent_op) = repo.operation().parents().next().transpose()? else {
+            break;
+        };
+        let parent_repo = repo_loader.load_at(&parent_op)?;
+        // If the change is divergent, any of the commits will do.
+        let parent_commit = match parent_repo.resolve_change_id(commit.change_id()).as_deref() {
+            Some([id, ..]) => Some(parent_repo.store().get_commit(id)?),
+            _ => None,
+        };
+        let parent_tree = parent_commit.as_ref().map(|c| c.tree()).transpose()?;
+        let mut created_paths = vec![];
+        let mut still_conflicted_paths = vec![];
+        for path in remaining_paths {
+            let was_conflicted = match &parent_tree {
+                Some(tree) => !tree.path_value(&path)?.is_resolved(),
+                None => false,
+            };
+            if was_conflicted {
+                still_conflicted_paths.push(path);
+            } else {
+                created_paths.push(path);
+            }
+        }
+        if !created_paths.is_empty() {
+            origins.push(conflict_origin(
+                repo.as_ref(),
+                repo.operation().clone(),
+                &commit,
+                created_paths,
+            )?);
+        }
+        remaining_paths = still_conflicted_paths;
+        let Some(parent_commit) = parent_commit else {
+            break;
+        };
+        if remaining_paths.is_empty() {
+            break;
+        }
+        repo = parent_repo;
+        commit = parent_commit;
+    }
+    Ok(origins)
+}
+
+/// Finds the sides of the conflicts at `paths` in `commit`, which became
+/// conflicted in `operation`.
+fn conflict_origin(
+    repo: &dyn Repo,
+    operation: Operation,
+    commit: &Commit,
+    paths: Vec<RepoPathBuf>,
+) -> Result<ConflictOrigin, CommandError> {
+    let mut origin = ConflictOrigin {
+        operation,
+        paths,
+        sides: vec![],
+        inherited_from: None,
+    };
+    if let Some(term_commits) = conflict_term_commits(repo, commit)? {
+        origin.sides = term_commits.adds().cloned().collect();
+        return Ok(origin);
+    }
+    let parents: Vec<_> = commit.parents().try_collect()?;
+    for parent in &parents {
+        let parent_tree = parent.tree()?;
+        for path in &origin.paths {
+            if !parent_tree.path_value(path)?.is_resolved() {
+                origin.inherited_from = Some(parent.clone());
+                return Ok(origin);
+            }
+        }
+    }
+    // The commit was rebased onto its parents, or the parents were merged
+    // without a unique common ancestor.
+    origin.sides = parents;
+    if origin.sides.len() == 1 {
+        origin.sides.push(commit.clone());
+    }
+    Ok(origin)
+}
+
+/// Lists paths which weren't checked out because they can't be represented on
+/// the disk.
+fn print_skipped_paths(
+    formatter: &mut dyn Formatter,
+    workspace_command: &WorkspaceCommandHelper,
+    matcher: &dyn Matcher,
+) -> Result<(), CommandError> {
+    let Some(working_copy) = workspace_command
+        .working_copy()
+        .as_any()
+        .downcast_ref::<LocalWorkingCopy>()
+    else {
+        return Ok(());
+    };
+    let skipped_paths = working_copy
+        .file_states()?
+        .iter()
+        .filter(|(path, state)| state.file_type == FileType::Skipped && matcher.matches(path))
+        .map(|(path, _)| path.to_owned())
+        .collect_vec();
+    if skipped_paths.is_empty() {
+        return Ok(());
+    }
+    writeln!(
+        formatter.labeled("warning").with_heading("Warning: "),
+        "These paths can't be represented on this file system, and were not checked out:"
+    )?;
+    for path in &skipped_paths {
+        let ui_path = workspace_command.path_converter().format_file_path(path);
+        writeln!(formatter, "  {ui_path}")?;
+    }
     Ok(())
 }
@@ -15,6 +15,7 @@
 use clap_complete::ArgValueCandidates;
 use jj_lib::str_util::StringPattern;
 
+use crate::cli_util::format_template_null_terminated;
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::commit_templater::CommitTemplateLanguage;
@@ -54,6 +55,13 @@ pub struct TagListArgs {
     ///     https://jj-vcs.github.io/jj/latest/templates/
     #[arg(long, short = 'T', add = ArgValueCandidates::new(complete::template_aliases))]
     template: Option<String>,
+    /// Terminate each entry with a NUL byte instead of a newline
+    ///
+    /// The template output is printed without color, and its trailing newline
+    /// is replaced with a NUL byte, so the output can be safely consumed by
+    /// `xargs -0` and the like.
+    #[arg(long = "null", short = 'z')]
+    null_terminated: bool,
 }
 
 pub fn cmd_tag(
@@ -94,7 +102,11 @@ fn cmd_tag_list(
             continue;
         }
         let ref_name = RefName::local_only(name, target.clone());
-        template.format(&ref_name, formatter.as_mut())?;
+        if args.null_terminated {
+            formatter.write_all(&format_template_null_terminated(&ref_name, &template))?;
+        } else {
+            template.format(&ref_name, formatter.as_mut())?;
+        }
     }
 
     Ok(())
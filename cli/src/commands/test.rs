@@ -0,0 +1,195 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+use std::process::Stdio;
+use std::sync::Arc;
+
+use clap_complete::ArgValueCandidates;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::config::ConfigGetResultExt as _;
+use jj_lib::local_working_copy::TreeState;
+use jj_lib::merged_tree::MergedTree;
+use jj_lib::repo::Repo as _;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::store::Store;
+use jj_lib::working_copy::CheckoutOptions;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::internal_error_with_message;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
+use crate::command_error::user_error_with_message;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::config::CommandNameAndArgs;
+use crate::test_results::TestResult;
+use crate::test_results::TestResults;
+use crate::ui::Ui;
+
+/// Run tests against revisions and record the results
+///
+/// The command configured by `test.command` is run in a temporary checkout of
+/// each revision. A revision passes if the command exits successfully. The
+/// output of the command is shown if it fails.
+///
+/// Results are recorded by the contents of the revision, so revisions that
+/// were tested before, or that have the same contents as a tested revision,
+/// aren't tested again unless the test command changed. Recorded results can
+/// be queried with the `tested()` and `failing()` revset functions and the
+/// `test_result()` commit template method.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct TestArgs {
+    /// The revisions to test
+    ///
+    /// If no revisions are specified, this defaults to the `revsets.test`
+    /// setting.
+    #[arg(
+        long, short,
+        value_name = "REVSETS",
+        add = ArgValueCandidates::new(complete::all_revisions),
+    )]
+    revisions: Vec<RevisionArg>,
+    /// Run the tests even if a result is already recorded
+    #[arg(long)]
+    rerun: bool,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_test(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &TestArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let test_command: CommandNameAndArgs = workspace_command
+        .settings()
+        .get("test.command")
+        .optional()?
+        .ok_or_else(|| {
+            user_error_with_hint(
+                "No test command configured",
+                "Set `test.command` to the command to run, e.g. `jj config set --repo \
+                 test.command '[\"make\", \"check\"]'`",
+            )
+        })?;
+    let command_string = test_command.to_string();
+
+    let revset_expression = if args.revisions.is_empty() {
+        let revset_string = workspace_command.settings().get_string("revsets.test")?;
+        workspace_command.parse_revset(ui, &RevisionArg::from(revset_string))?
+    } else {
+        workspace_command.parse_union_revsets(ui, &args.revisions)?
+    };
+    let mut commits: Vec<Commit> = revset_expression.evaluate_to_commits()?.try_collect()?;
+    // Test older revisions first
+    commits.reverse();
+
+    let store = workspace_command.repo().store();
+    let test_results = TestResults::for_repo(workspace_command.repo_path());
+    let options = workspace_command.checkout_options();
+    let template = workspace_command.commit_summary_template();
+    let mut num_failed = 0;
+    for commit in &commits {
+        let recorded = test_results
+            .get(commit.tree_id())
+            .filter(|result| !args.rerun && result.command == command_string);
+        let is_recorded = recorded.is_some();
+        let result = if let Some(result) = recorded {
+            result
+        } else {
+            let passed = run_test(ui, &test_command, store, &commit.tree()?, &options)?;
+            let result = TestResult {
+                passed,
+                command: command_string.clone(),
+            };
+            test_results
+                .insert(commit.tree_id(), &result)
+                .map_err(|err| user_error_with_message("Failed to record test result", err))?;
+            result
+        };
+        if !result.passed {
+            num_failed += 1;
+        }
+        if let Some(mut formatter) = ui.status_formatter() {
+            if result.passed {
+                write!(formatter.labeled("test_passed"), "Passed")?;
+            } else {
+                write!(formatter.labeled("test_failed"), "Failed")?;
+            }
+            if is_recorded {
+                write!(formatter, " (recorded)")?;
+            }
+            write!(formatter, ": ")?;
+            template.format(commit, formatter.as_mut())?;
+            writeln!(formatter)?;
+        }
+    }
+
+    if num_failed > 0 {
+        return Err(user_error(format!(
+            "{num_failed} of {} revisions failed the tests",
+            commits.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Checks out the `tree` in a temporary directory and runs the test command in
+/// it. Returns whether the command succeeded.
+fn run_test(
+    ui: &Ui,
+    test_command: &CommandNameAndArgs,
+    store: &Arc<Store>,
+    tree: &MergedTree,
+    options: &CheckoutOptions,
+) -> Result<bool, CommandError> {
+    let temp_dir = tempfile::Builder::new()
+        .prefix("jj-test-")
+        .tempdir()
+        .map_err(|err| user_error_with_message("Failed to create temporary directory", err))?;
+    let wc_path = temp_dir.path().join("wc");
+    let state_path = temp_dir.path().join("state");
+    std::fs::create_dir(&wc_path)?;
+    std::fs::create_dir(&state_path)?;
+    let mut tree_state = TreeState::init(store.clone(), wc_path.clone(), state_path)
+        .map_err(|err| internal_error_with_message("Failed to set up test working copy", err))?;
+    tree_state
+        .set_sparse_patterns(vec![RepoPathBuf::root()], options)
+        .map_err(|err| internal_error_with_message("Failed to set up test working copy", err))?;
+    tree_state
+        .check_out(tree, options)
+        .map_err(|err| internal_error_with_message("Failed to check out test working copy", err))?;
+
+    let output = test_command
+        .to_command()
+        .current_dir(&wc_path)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|err| {
+            user_error_with_message(
+                format!("Failed to run test command '{}'", test_command.split_name()),
+                err,
+            )
+        })?;
+    if !output.status.success() {
+        let mut stderr = ui.stderr();
+        stderr.write_all(&output.stdout)?;
+        stderr.write_all(&output.stderr)?;
+    }
+    Ok(output.status.success())
+}
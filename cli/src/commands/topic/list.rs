@@ -0,0 +1,53 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use itertools::Itertools as _;
+use jj_lib::repo::Repo as _;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// List topics and the number of changes in them
+///
+/// Only changes with visible revisions are counted.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct TopicListArgs {}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_topic_list(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    _args: &TopicListArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo();
+    let topic_counts = repo
+        .view()
+        .all_topics()
+        .filter(|(change_id, _)| repo.resolve_change_id(change_id).is_some())
+        .map(|(_, topic)| topic)
+        .counts();
+
+    ui.request_pager();
+    let mut formatter = ui.stdout_formatter();
+    for (topic, count) in topic_counts.into_iter().sorted() {
+        write!(formatter.labeled("topic"), "{topic}")?;
+        writeln!(formatter, ": {count} changes")?;
+    }
+    Ok(())
+}
@@ -0,0 +1,57 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod list;
+mod remove;
+mod set;
+
+use clap::Subcommand;
+use tracing::instrument;
+
+use self::list::cmd_topic_list;
+use self::list::TopicListArgs;
+use self::remove::cmd_topic_remove;
+use self::remove::TopicRemoveArgs;
+use self::set::cmd_topic_set;
+use self::set::TopicSetArgs;
+use crate::cli_util::CommandHelper;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// Manage topics of changes
+///
+/// A topic groups a series of changes under a name. Topics are attached to
+/// change ids, so changes stay in their topic when they are rewritten or
+/// rebased. The changes in a topic can be selected with the `topic()` revset
+/// function and pushed together with `jj git push --topic`.
+#[derive(Subcommand, Clone, Debug)]
+pub(crate) enum TopicCommand {
+    #[command(visible_alias("l"))]
+    List(TopicListArgs),
+    Remove(TopicRemoveArgs),
+    Set(TopicSetArgs),
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_topic(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    subcommand: &TopicCommand,
+) -> Result<(), CommandError> {
+    match subcommand {
+        TopicCommand::List(args) => cmd_topic_list(ui, command, args),
+        TopicCommand::Remove(args) => cmd_topic_remove(ui, command, args),
+        TopicCommand::Set(args) => cmd_topic_set(ui, command, args),
+    }
+}
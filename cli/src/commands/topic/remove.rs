@@ -0,0 +1,70 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::view::TOPIC_METADATA_KEY;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Remove changes from their topics
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct TopicRemoveArgs {
+    /// The revisions whose changes to remove from their topics
+    #[arg(
+        long, short,
+        default_value = "@",
+        value_name = "REVSETS",
+        add = ArgValueCandidates::new(complete::mutable_revisions),
+    )]
+    revisions: Vec<RevisionArg>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_topic_remove(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &TopicRemoveArgs,
+) -> Result<(), CommandError> {
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let commits: Vec<Commit> = workspace_command
+        .parse_union_revsets(ui, &args.revisions)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    let view = workspace_command.repo().view();
+    let commits = commits
+        .into_iter()
+        .filter(|commit| view.get_topic(commit.change_id()).is_some())
+        .collect_vec();
+    if commits.is_empty() {
+        writeln!(ui.status(), "No changes to remove from topics.")?;
+        return Ok(());
+    }
+    let mut tx = workspace_command.start_transaction();
+    for commit in &commits {
+        tx.repo_mut()
+            .set_change_metadata(commit.change_id(), TOPIC_METADATA_KEY, None);
+    }
+    writeln!(ui.status(), "Removed {} changes from topics", commits.len())?;
+    tx.finish(ui, format!("remove topic of {} changes", commits.len()))?;
+    Ok(())
+}
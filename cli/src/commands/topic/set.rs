@@ -0,0 +1,80 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::view::TOPIC_METADATA_KEY;
+use tracing::instrument;
+
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::user_error;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::ui::Ui;
+
+/// Add changes to a topic
+///
+/// Changes that already belong to another topic are moved to this topic.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct TopicSetArgs {
+    /// The name of the topic
+    name: String,
+    /// The revisions whose changes to add to the topic
+    #[arg(
+        long, short,
+        default_value = "@",
+        value_name = "REVSETS",
+        add = ArgValueCandidates::new(complete::mutable_revisions),
+    )]
+    revisions: Vec<RevisionArg>,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_topic_set(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &TopicSetArgs,
+) -> Result<(), CommandError> {
+    if args.name.is_empty() {
+        return Err(user_error("Topic name cannot be empty"));
+    }
+    let mut workspace_command = command.workspace_helper(ui)?;
+    let commits: Vec<Commit> = workspace_command
+        .parse_union_revsets(ui, &args.revisions)?
+        .evaluate_to_commits()?
+        .try_collect()?;
+    let mut tx = workspace_command.start_transaction();
+    for commit in &commits {
+        tx.repo_mut().set_change_metadata(
+            commit.change_id(),
+            TOPIC_METADATA_KEY,
+            Some(args.name.clone()),
+        );
+    }
+    writeln!(
+        ui.status(),
+        "Added {} changes to topic {}",
+        commits.len(),
+        args.name
+    )?;
+    tx.finish(
+        ui,
+        format!("set topic {} of {} changes", args.name, commits.len()),
+    )?;
+    Ok(())
+}
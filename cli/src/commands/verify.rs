@@ -0,0 +1,233 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::io::Write as _;
+
+use clap_complete::ArgValueCandidates;
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use jj_lib::repo::Repo as _;
+use jj_lib::signing::SigStatus;
+use jj_lib::signing::SignError;
+use rayon::iter::IntoParallelIterator as _;
+use rayon::iter::ParallelIterator as _;
+use tracing::instrument;
+
+use crate::cli_util::short_commit_hash;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::command_error::CommandError;
+use crate::complete;
+use crate::formatter::Formatter;
+use crate::ui::Ui;
+
+/// Verify the cryptographic signatures of revisions
+///
+/// The signatures of all revisions are verified, and a summary of the results
+/// per author is printed. A signature is:
+///
+/// * `good` if it was made by a known key and matches the commit,
+/// * `unknown` if it can't be verified, e.g. because the key is unknown,
+/// * `bad` if it doesn't match the commit, or can't be read by any of the
+///   signing backends.
+///
+/// The command fails if any signature is bad. With `--require-signed`, it also
+/// fails if any revision isn't signed with a good signature, which can be used
+/// to enforce a policy such as `jj verify -r ::main --require-signed`.
+///
+/// Verifying signatures doesn't require configuring a signing backend, but the
+/// tools used by the backends (e.g. `gpg` or `ssh-keygen`) must be installed.
+/// See [commit signing] for details.
+///
+/// [commit signing]:
+///     https://jj-vcs.github.io/jj/latest/config/#commit-signing
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct VerifyArgs {
+    /// The revisions to verify
+    #[arg(
+        long,
+        short,
+        required = true,
+        value_name = "REVSETS",
+        add = ArgValueCandidates::new(complete::all_revisions),
+    )]
+    revisions: Vec<RevisionArg>,
+    /// Fail if any of the revisions doesn't have a good signature
+    #[arg(long)]
+    require_signed: bool,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum SignatureCheck {
+    Good,
+    Unknown,
+    Bad,
+    Unsigned,
+}
+
+impl SignatureCheck {
+    fn as_str(self) -> &'static str {
+        match self {
+            SignatureCheck::Good => "good",
+            SignatureCheck::Unknown => "unknown",
+            SignatureCheck::Bad => "bad",
+            SignatureCheck::Unsigned => "unsigned",
+        }
+    }
+}
+
+/// Number of revisions of an author per signature status, in the order of the
+/// columns of the summary.
+#[derive(Default)]
+struct AuthorSummary {
+    counts: [usize; 4],
+}
+
+const COLUMNS: [SignatureCheck; 4] = [
+    SignatureCheck::Good,
+    SignatureCheck::Unknown,
+    SignatureCheck::Bad,
+    SignatureCheck::Unsigned,
+];
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_verify(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &VerifyArgs,
+) -> Result<(), CommandError> {
+    let workspace_command = command.workspace_helper(ui)?;
+    let repo = workspace_command.repo();
+    let root_commit_id = repo.store().root_commit_id();
+    let commits: Vec<Commit> = workspace_command
+        .parse_union_revsets(ui, &args.revisions)?
+        .evaluate_to_commits()?
+        .filter_ok(|commit| commit.id() != root_commit_id)
+        .try_collect()?;
+
+    // Verification may run an external program per revision, so it's done in
+    // parallel.
+    let checks: Vec<SignatureCheck> = commits
+        .as_slice()
+        .into_par_iter()
+        .map(check_signature)
+        .collect::<Result<_, _>>()?;
+
+    let mut summaries: BTreeMap<String, AuthorSummary> = BTreeMap::new();
+    for (commit, check) in commits.iter().zip(&checks) {
+        let author = commit.author();
+        let summary = summaries
+            .entry(format!("{} <{}>", author.name, author.email))
+            .or_default();
+        let column = COLUMNS.iter().position(|c| c == check).unwrap();
+        summary.counts[column] += 1;
+    }
+    print_summary(ui, &summaries)?;
+
+    let failed = commits
+        .iter()
+        .zip(&checks)
+        .filter(|(_, check)| match check {
+            SignatureCheck::Good => false,
+            SignatureCheck::Unknown | SignatureCheck::Unsigned => args.require_signed,
+            SignatureCheck::Bad => true,
+        })
+        .collect_vec();
+    if failed.is_empty() {
+        return Ok(());
+    }
+    if let Some(mut formatter) = ui.status_formatter() {
+        writeln!(formatter, "Revisions failing verification:")?;
+        for (commit, check) in &failed {
+            write!(formatter, "  ")?;
+            write_status(
+                formatter.as_mut(),
+                **check,
+                &format!("{:<8}", check.as_str()),
+            )?;
+            write!(formatter, " ")?;
+            workspace_command.write_commit_summary(formatter.as_mut(), commit)?;
+            writeln!(formatter)?;
+        }
+    }
+    Err(user_error(format!(
+        "{} of {} revisions failed verification",
+        failed.len(),
+        commits.len()
+    )))
+}
+
+fn check_signature(commit: &Commit) -> Result<SignatureCheck, CommandError> {
+    match commit.verification() {
+        Ok(None) => Ok(SignatureCheck::Unsigned),
+        Ok(Some(verification)) => match verification.status {
+            SigStatus::Good => Ok(SignatureCheck::Good),
+            SigStatus::Unknown => Ok(SignatureCheck::Unknown),
+            SigStatus::Bad => Ok(SignatureCheck::Bad),
+        },
+        Err(SignError::InvalidSignatureFormat) => Ok(SignatureCheck::Bad),
+        Err(err) => Err(user_error_with_message(
+            format!(
+                "Failed to verify the signature of {}",
+                short_commit_hash(commit.id())
+            ),
+            err,
+        )),
+    }
+}
+
+fn print_summary(ui: &Ui, summaries: &BTreeMap<String, AuthorSummary>) -> Result<(), CommandError> {
+    const AUTHOR_HEADER: &str = "Author";
+    let author_width = summaries
+        .keys()
+        .map(|author| author.len())
+        .chain([AUTHOR_HEADER.len()])
+        .max()
+        .unwrap();
+    let mut formatter = ui.stdout_formatter();
+    write!(formatter, "{AUTHOR_HEADER:<author_width$}")?;
+    for column in COLUMNS {
+        write!(formatter, "  {:>8}", column.as_str())?;
+    }
+    writeln!(formatter)?;
+    for (author, summary) in summaries {
+        write!(formatter, "{author:<author_width$}")?;
+        for (column, count) in COLUMNS.iter().zip(summary.counts) {
+            write!(formatter, "  ")?;
+            if count > 0 {
+                write_status(formatter.as_mut(), *column, &format!("{count:>8}"))?;
+            } else {
+                write!(formatter, "{count:>8}")?;
+            }
+        }
+        writeln!(formatter)?;
+    }
+    Ok(())
+}
+
+/// Writes `text` with the label used for the signature status in templates.
+fn write_status(
+    formatter: &mut dyn Formatter,
+    check: SignatureCheck,
+    text: &str,
+) -> std::io::Result<()> {
+    formatter.with_label("signature", |formatter| {
+        formatter.with_label("status", |formatter| {
+            write!(formatter.labeled(check.as_str()), "{text}")
+        })
+    })
+}
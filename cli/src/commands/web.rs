@@ -0,0 +1,491 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read as _;
+use std::io::Write;
+use std::iter;
+use std::net::IpAddr;
+use std::net::TcpListener;
+use std::slice;
+use std::time::Duration;
+
+use itertools::Itertools as _;
+use jj_lib::graph::GraphEdge;
+use jj_lib::graph::GraphEdgeType;
+use jj_lib::graph::TopoGroupedGraphIterator;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::op_walk;
+use jj_lib::repo::Repo as _;
+use tracing::instrument;
+
+use super::log::get_node_template;
+use crate::cli_util::CommandHelper;
+use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::user_error_with_message;
+use crate::command_error::CommandError;
+use crate::commit_templater::CommitTemplateLanguage;
+use crate::diff_util::DiffFormatArgs;
+use crate::formatter::html_style_sheet;
+use crate::formatter::Formatter;
+use crate::formatter::HtmlFormatter;
+use crate::graphlog::get_graphlog;
+use crate::graphlog::GraphStyle;
+use crate::operation_templater::OperationTemplateLanguage;
+use crate::ui::Ui;
+
+/// Serve a read-only web interface for browsing the repo
+///
+/// The web interface shows the log graph, the changes in each revision, the
+/// operation log, and the revisions with conflicts. Pages are rendered by the
+/// same templates as `jj log`, `jj show`, and `jj op log`, and colorized as
+/// configured in the `colors` table.
+///
+/// The repo is reloaded on each request, so the pages reflect new operations
+/// when reloaded. The working copy isn't snapshotted.
+///
+/// To keep other websites from reading the pages through a domain name that
+/// resolves to the server's address, requests are only answered if their host
+/// is `localhost` or an IP address.
+#[derive(clap::Args, Clone, Debug)]
+pub(crate) struct WebArgs {
+    /// Address to listen on
+    ///
+    /// Use port 0 to pick an unused port.
+    #[arg(long, default_value = "127.0.0.1:8000", value_name = "ADDRESS")]
+    bind: String,
+    /// Which revisions to show in the log page
+    ///
+    /// If no revisions are specified, this defaults to the `revsets.log`
+    /// setting.
+    #[arg(long, short, value_name = "REVSETS")]
+    revisions: Vec<RevisionArg>,
+    /// Serve a single request read from stdin, and write the response to
+    /// stdout
+    ///
+    /// This can be used to run the server from inetd-style socket activation.
+    #[arg(long)]
+    stdio: bool,
+    #[command(flatten)]
+    format: DiffFormatArgs,
+}
+
+#[instrument(skip_all)]
+pub(crate) fn cmd_web(
+    ui: &mut Ui,
+    command: &CommandHelper,
+    args: &WebArgs,
+) -> Result<(), CommandError> {
+    if args.stdio {
+        let mut input = io::stdin().lock();
+        let mut output = io::stdout().lock();
+        serve_request(ui, command, args, &mut input, &mut output)?;
+        return Ok(());
+    }
+
+    let listener = TcpListener::bind(&args.bind).map_err(|err| {
+        user_error_with_message(format!("Failed to listen on {}", args.bind), err)
+    })?;
+    writeln!(
+        ui.status(),
+        "Listening on http://{}/",
+        listener.local_addr()?
+    )?;
+    writeln!(ui.hint_default(), "Press Ctrl-C to stop the server.")?;
+    for stream in listener.incoming() {
+        let result = stream.and_then(|stream| {
+            // Connections are served one at a time, so a client that stops
+            // sending the request or reading the response mustn't block the
+            // others.
+            stream.set_read_timeout(Some(CONNECTION_TIMEOUT))?;
+            stream.set_write_timeout(Some(CONNECTION_TIMEOUT))?;
+            let mut input = BufReader::new(&stream);
+            let mut output = &stream;
+            serve_request(ui, command, args, &mut input, &mut output)
+        });
+        if let Err(err) = result {
+            writeln!(ui.warning_default(), "Failed to serve request: {err}")?;
+        }
+    }
+    Ok(())
+}
+
+/// How long to wait for a client to send or receive more data.
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Limits of the request size, which are far more than any valid request for
+// the pages needs.
+const MAX_LINE_LEN: u64 = 8192;
+const MAX_HEADERS: usize = 100;
+
+/// Parsed request line and headers.
+struct Request {
+    request_line: String,
+    host: Option<String>,
+}
+
+/// Reads the request line and headers. Returns `None` if the request is too
+/// large.
+fn read_request(input: &mut dyn BufRead) -> io::Result<Option<Request>> {
+    let mut read_line = || -> io::Result<Option<String>> {
+        let mut line = String::new();
+        input.take(MAX_LINE_LEN).read_line(&mut line)?;
+        if line.len() as u64 >= MAX_LINE_LEN && !line.ends_with('\n') {
+            return Ok(None);
+        }
+        Ok(Some(line))
+    };
+    let Some(request_line) = read_line()? else {
+        return Ok(None);
+    };
+    let mut host = None;
+    for _ in 0..MAX_HEADERS {
+        let Some(line) = read_line()? else {
+            return Ok(None);
+        };
+        let line = line.trim_end();
+        if line.is_empty() {
+            return Ok(Some(Request { request_line, host }));
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("host") {
+                host = Some(value.trim().to_owned());
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Whether the `Host` header names `localhost` or an IP address, which can't
+/// be used for DNS rebinding.
+fn is_allowed_host(host: &str) -> bool {
+    let host_name = if let Some(rest) = host.strip_prefix('[') {
+        // IPv6 address, e.g. "[::1]:8000"
+        rest.split_once(']').map_or(rest, |(addr, _port)| addr)
+    } else {
+        host.split_once(':').map_or(host, |(name, _port)| name)
+    };
+    host_name.eq_ignore_ascii_case("localhost") || host_name.parse::<IpAddr>().is_ok()
+}
+
+/// Decodes the `%XX` escapes in the URL path. Returns `None` if the escapes
+/// are invalid or don't decode to UTF-8.
+fn percent_decode(path: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut rest = path.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        if b == b'%' {
+            let hex = tail.get(..2)?;
+            let hex = std::str::from_utf8(hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(b);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn serve_request(
+    ui: &Ui,
+    command: &CommandHelper,
+    args: &WebArgs,
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+) -> io::Result<()> {
+    let (status, body) = match read_request(input)? {
+        None => ("400 Bad Request", error_page("Request too large")),
+        Some(request) if !request.host.as_deref().is_some_and(is_allowed_host) => {
+            ("403 Forbidden", error_page("Host not allowed"))
+        }
+        Some(request) => respond(ui, command, args, &request.request_line),
+    };
+    write!(
+        output,
+        "HTTP/1.1 {status}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: \
+         {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    )?;
+    output.write_all(body.as_bytes())?;
+    output.flush()
+}
+
+/// Returns the status and body of the response to the request.
+fn respond(
+    ui: &Ui,
+    command: &CommandHelper,
+    args: &WebArgs,
+    request_line: &str,
+) -> (&'static str, String) {
+    match request_line.split_whitespace().collect_vec().as_slice() {
+        ["GET", target, _version] => {
+            let path = target
+                .split_once('?')
+                .map_or(*target, |(path, _query)| path);
+            let Some(path) = percent_decode(path) else {
+                return ("400 Bad Request", error_page("Bad request"));
+            };
+            match render_page(ui, command, args, &path) {
+                Ok(Some(body)) => ("200 OK", body),
+                Ok(None) => ("404 Not Found", error_page("Not found")),
+                Err(err) => {
+                    let message =
+                        iter::successors(Some(err.error.as_ref() as &dyn error::Error), |err| {
+                            err.source()
+                        })
+                        .join("\nCaused by: ");
+                    ("500 Internal Server Error", error_page(&message))
+                }
+            }
+        }
+        _ => ("400 Bad Request", error_page("Bad request")),
+    }
+}
+
+fn render_page(
+    ui: &Ui,
+    command: &CommandHelper,
+    args: &WebArgs,
+    path: &str,
+) -> Result<Option<String>, CommandError> {
+    let workspace_command = command.workspace_helper_no_snapshot(ui)?;
+    let mut content = vec![];
+    let title = match path {
+        "/" => {
+            render_log(ui, &workspace_command, args, &mut content)?;
+            "Log"
+        }
+        "/operations" => {
+            render_op_log(ui, &workspace_command, &mut content)?;
+            "Operations"
+        }
+        "/conflicts" => {
+            render_conflicts(ui, &workspace_command, &mut content)?;
+            "Conflicts"
+        }
+        _ => {
+            let Some(revision) = path.strip_prefix("/commit/") else {
+                return Ok(None);
+            };
+            render_commit(ui, &workspace_command, args, revision, &mut content)?;
+            "Revision"
+        }
+    };
+    let style_sheet = html_style_sheet(workspace_command.settings().config())?;
+    Ok(Some(html_page(
+        title,
+        &style_sheet,
+        &String::from_utf8_lossy(&content),
+    )))
+}
+
+fn render_log(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    args: &WebArgs,
+    output: &mut Vec<u8>,
+) -> Result<(), CommandError> {
+    let settings = workspace_command.settings();
+    let revset_expression = if args.revisions.is_empty() {
+        let revset_string = settings.get_string("revsets.log")?;
+        workspace_command.parse_revset(ui, &RevisionArg::from(revset_string))?
+    } else {
+        workspace_command.parse_union_revsets(ui, &args.revisions)?
+    };
+    let revset = revset_expression.evaluate()?;
+    let repo = workspace_command.repo();
+    let store = repo.store();
+    let graph_style = GraphStyle::from_settings(settings)?;
+
+    let template;
+    let node_template;
+    {
+        let language = workspace_command.commit_template_language();
+        template = workspace_command
+            .parse_template(
+                ui,
+                &language,
+                &settings.get_string("templates.log")?,
+                CommitTemplateLanguage::wrap_commit,
+            )?
+            .labeled("log");
+        node_template = workspace_command
+            .parse_template(
+                ui,
+                &language,
+                &get_node_template(graph_style, settings)?,
+                CommitTemplateLanguage::wrap_commit_opt,
+            )?
+            .labeled("node");
+    }
+
+    let mut graph = get_graphlog(graph_style, output);
+    let mut iter = TopoGroupedGraphIterator::new(revset.iter_graph());
+    if let Some(id) = workspace_command.get_wc_commit_id() {
+        let has_commit = revset.containing_fn();
+        if has_commit(id)? {
+            iter.prioritize_branch(id.clone());
+        }
+    }
+    for node in iter {
+        let (commit_id, edges) = node?;
+        let graphlog_edges = edges
+            .into_iter()
+            .map(|edge| match edge.edge_type {
+                GraphEdgeType::Missing => GraphEdge::missing(edge.target),
+                GraphEdgeType::Direct => GraphEdge::direct(edge.target),
+                GraphEdgeType::Indirect => GraphEdge::indirect(edge.target),
+            })
+            .collect_vec();
+        let commit = store.get_commit(&commit_id)?;
+        let mut buffer = vec![];
+        template.format(&commit, &mut HtmlFormatter::new(&mut buffer))?;
+        if !buffer.ends_with(b"\n") {
+            buffer.push(b'\n');
+        }
+        // The template output may contain links of its own, which can't be
+        // nested in another link, so only the node symbol is linked.
+        let mut node_symbol = vec![];
+        let url = format!("/commit/{}", commit_id.hex());
+        {
+            let formatter: &mut dyn Formatter = &mut HtmlFormatter::new(&mut node_symbol);
+            formatter.with_hyperlink(&url, |formatter| {
+                node_template.format(&Some(commit), formatter)
+            })?;
+        }
+        graph.add_node(
+            &commit_id,
+            &graphlog_edges,
+            &String::from_utf8_lossy(&node_symbol),
+            &String::from_utf8_lossy(&buffer),
+        )?;
+    }
+    Ok(())
+}
+
+fn render_commit(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    args: &WebArgs,
+    revision: &str,
+    output: &mut Vec<u8>,
+) -> Result<(), CommandError> {
+    let commit =
+        workspace_command.resolve_single_rev(ui, &RevisionArg::from(revision.to_owned()))?;
+    let template_string = workspace_command.settings().get_string("templates.show")?;
+    let template = workspace_command.parse_commit_template(ui, &template_string)?;
+    let diff_renderer = workspace_command.diff_renderer_for(&args.format)?;
+    let mut formatter = HtmlFormatter::new(output);
+    template.format(&commit, &mut formatter)?;
+    diff_renderer.show_patch(
+        ui,
+        &mut formatter,
+        &commit,
+        &EverythingMatcher,
+        ui.term_width(),
+    )?;
+    Ok(())
+}
+
+fn render_op_log(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    output: &mut Vec<u8>,
+) -> Result<(), CommandError> {
+    let template = {
+        let language = workspace_command.operation_template_language();
+        let text = workspace_command
+            .settings()
+            .get_string("templates.op_log")?;
+        workspace_command
+            .parse_template(
+                ui,
+                &language,
+                &text,
+                OperationTemplateLanguage::wrap_operation,
+            )?
+            .labeled("operation")
+            .labeled("op_log")
+    };
+    let mut formatter = HtmlFormatter::new(output);
+    let current_op = workspace_command.repo().operation();
+    for op in op_walk::walk_ancestors(slice::from_ref(current_op)) {
+        template.format(&op?, &mut formatter)?;
+    }
+    Ok(())
+}
+
+fn render_conflicts(
+    ui: &Ui,
+    workspace_command: &WorkspaceCommandHelper,
+    output: &mut Vec<u8>,
+) -> Result<(), CommandError> {
+    let template_string = workspace_command
+        .settings()
+        .get_string("templates.commit_summary")?;
+    let template = workspace_command.parse_commit_template(ui, &template_string)?;
+    let revset_expression =
+        workspace_command.parse_revset(ui, &RevisionArg::from("conflicts()".to_owned()))?;
+    let path_converter = workspace_command.path_converter();
+    let formatter: &mut dyn Formatter = &mut HtmlFormatter::new(output);
+    for commit in revset_expression.evaluate_to_commits()? {
+        let commit = commit?;
+        template.format(&commit, formatter)?;
+        writeln!(formatter)?;
+        // Like in the log page, the summary isn't linked since it may contain
+        // links of its own.
+        let url = format!("/commit/{}", commit.id().hex());
+        for (path, _value) in commit.tree()?.conflicts() {
+            write!(formatter, "  ")?;
+            formatter.with_hyperlink(&url, |formatter| {
+                write!(formatter, "{}", path_converter.format_file_path(&path))
+            })?;
+            writeln!(formatter)?;
+        }
+    }
+    Ok(())
+}
+
+fn html_page(title: &str, style_sheet: &str, content: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{title} - jj</title>
+<style>
+{style_sheet}</style>
+</head>
+<body>
+<nav><a href="/">Log</a> | <a href="/operations">Operations</a> | <a href="/conflicts">Conflicts</a></nav>
+<pre>
+{content}</pre>
+</body>
+</html>
+"#
+    )
+}
+
+fn error_page(message: &str) -> String {
+    let mut content = vec![];
+    writeln!(HtmlFormatter::new(&mut content), "{message}")
+        .expect("write() to vec backed formatter should never fail");
+    html_page("Error", "", &String::from_utf8_lossy(&content))
+}
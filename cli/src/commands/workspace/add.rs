@@ -16,19 +16,30 @@ use std::fs;
 
 use itertools::Itertools;
 use jj_lib::commit::CommitIteratorExt;
+use jj_lib::commit_builder::DetachedCommitBuilder;
+use jj_lib::config::ConfigGetResultExt as _;
 use jj_lib::file_util;
 use jj_lib::file_util::IoResultExt;
 use jj_lib::op_store::WorkspaceId;
 use jj_lib::repo::Repo;
-use jj_lib::rewrite::merge_commit_trees;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::rewrite::merge_commit_trees_with_strategies;
 use jj_lib::workspace::Workspace;
+use jj_lib::workspace_store::SimpleWorkspaceStore;
+use jj_lib::workspace_store::WorkspaceStore as _;
 use tracing::instrument;
 
+use crate::cli_util::load_merge_strategies;
 use crate::cli_util::CommandHelper;
 use crate::cli_util::RevisionArg;
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::cli_util::WorkspaceCommandTransaction;
+use crate::command_error::config_error_with_message;
 use crate::command_error::internal_error_with_message;
 use crate::command_error::user_error;
+use crate::command_error::user_error_with_hint;
 use crate::command_error::CommandError;
+use crate::formatter::PlainTextFormatter;
 use crate::ui::Ui;
 
 /// How to handle sparse patterns when creating a new workspace.
@@ -45,7 +56,9 @@ enum SparseInheritance {
 /// Add a workspace
 ///
 /// By default, the new workspace inherits the sparse patterns of the current
-/// workspace. You can override this with the `--sparse-patterns` option.
+/// workspace. You can override this with the `--sparse-patterns` option, or
+/// copy the patterns of another workspace or a sparse profile defined in the
+/// `sparse.profiles` config table with `--sparse-from`.
 #[derive(clap::Args, Clone, Debug)]
 pub struct WorkspaceAddArgs {
     /// Where to create the new workspace
@@ -73,6 +86,23 @@ pub struct WorkspaceAddArgs {
     /// How to handle sparse patterns when creating a new workspace.
     #[arg(long, value_enum, default_value_t = SparseInheritance::Copy)]
     sparse_patterns: SparseInheritance,
+    /// Use the sparse patterns of this profile or workspace
+    ///
+    /// The value is first looked up as a profile in the `sparse.profiles`
+    /// config table. Otherwise, it's the name of a workspace of the repo or the
+    /// path to another workspace of the same repo.
+    #[arg(
+        long,
+        value_name = "PROFILE|WORKSPACE",
+        conflicts_with = "sparse_patterns"
+    )]
+    sparse_from: Option<String>,
+    /// Template for the description of the new working-copy commit
+    ///
+    /// The template is evaluated against the new working-copy commit, so e.g.
+    /// `working_copies` expands to the name of the new workspace.
+    #[arg(long, value_name = "TEMPLATE")]
+    name_template: Option<String>,
 }
 
 #[instrument(skip_all)]
@@ -82,6 +112,31 @@ pub fn cmd_workspace_add(
     args: &WorkspaceAddArgs,
 ) -> Result<(), CommandError> {
     let old_workspace_command = command.workspace_helper(ui)?;
+    let sparsity = if let Some(source) = &args.sparse_from {
+        Some(sparse_patterns_from(
+            command,
+            &old_workspace_command,
+            source,
+        )?)
+    } else {
+        match args.sparse_patterns {
+            SparseInheritance::Full => None,
+            SparseInheritance::Empty => Some(vec![]),
+            SparseInheritance::Copy => {
+                let sparse_patterns = old_workspace_command
+                    .working_copy()
+                    .sparse_patterns()?
+                    .to_vec();
+                Some(sparse_patterns)
+            }
+        }
+    };
+
+    // Check the template before creating anything.
+    if let Some(template_text) = &args.name_template {
+        old_workspace_command.parse_commit_template(ui, template_text)?;
+    }
+
     let destination_path = command.cwd().join(&args.destination);
     if destination_path.exists() {
         return Err(user_error("Workspace already exists"));
@@ -134,18 +189,6 @@ pub fn cmd_workspace_add(
 
     let mut new_workspace_command = command.for_workable_repo(ui, new_workspace, repo)?;
 
-    let sparsity = match args.sparse_patterns {
-        SparseInheritance::Full => None,
-        SparseInheritance::Empty => Some(vec![]),
-        SparseInheritance::Copy => {
-            let sparse_patterns = old_workspace_command
-                .working_copy()
-                .sparse_patterns()?
-                .to_vec();
-            Some(sparse_patterns)
-        }
-    };
-
     if let Some(sparse_patterns) = sparsity {
         let checkout_options = new_workspace_command.checkout_options();
         let (mut locked_ws, _wc_commit) = new_workspace_command.start_working_copy_mutation()?;
@@ -184,9 +227,15 @@ pub fn cmd_workspace_add(
             .collect_vec()
     };
 
-    let tree = merge_commit_trees(tx.repo(), &parents)?;
+    let merge_strategies = load_merge_strategies(command.settings())?;
+    let tree = merge_commit_trees_with_strategies(tx.repo(), &parents, &merge_strategies)?;
     let parent_ids = parents.iter().ids().cloned().collect_vec();
-    let new_wc_commit = tx.repo_mut().new_commit(parent_ids, tree.id()).write()?;
+    let mut commit_builder = tx.repo_mut().new_commit(parent_ids, tree.id()).detach();
+    if let Some(template_text) = &args.name_template {
+        let description = expand_name_template(ui, &mut tx, &commit_builder, template_text)?;
+        commit_builder.set_description(description);
+    }
+    let new_wc_commit = commit_builder.write(tx.repo_mut())?;
 
     tx.edit(&new_wc_commit)?;
     tx.finish(
@@ -195,3 +244,88 @@ pub fn cmd_workspace_add(
     )?;
     Ok(())
 }
+
+/// Evaluates the `--name-template` against the commit to be written, as if it
+/// were already the working-copy commit of the new workspace.
+fn expand_name_template(
+    ui: &Ui,
+    tx: &mut WorkspaceCommandTransaction,
+    commit_builder: &DetachedCommitBuilder,
+    template_text: &str,
+) -> Result<String, CommandError> {
+    let workspace_id = tx.base_workspace_helper().workspace_id().clone();
+    let temp_commit = commit_builder.write_hidden()?;
+    let old_wc_commit_id = tx.repo().view().get_wc_commit_id(&workspace_id).cloned();
+    tx.repo_mut()
+        .set_wc_commit(workspace_id.clone(), temp_commit.id().clone())?;
+    let description = {
+        let template = tx.parse_commit_template(ui, template_text)?;
+        let mut output = Vec::new();
+        template.format(&temp_commit, &mut PlainTextFormatter::new(&mut output))?;
+        String::from_utf8_lossy(&output).into_owned()
+    };
+    // The temporary commit isn't visible, so point the workspace back at its
+    // initial working-copy commit, which is abandoned when the real one is
+    // checked out.
+    if let Some(commit_id) = old_wc_commit_id {
+        tx.repo_mut().set_wc_commit(workspace_id, commit_id)?;
+    }
+    Ok(description)
+}
+
+/// Looks up the sparse patterns for `--sparse-from`.
+fn sparse_patterns_from(
+    command: &CommandHelper,
+    workspace_command: &WorkspaceCommandHelper,
+    source: &str,
+) -> Result<Vec<RepoPathBuf>, CommandError> {
+    let profile_key = ["sparse", "profiles", source];
+    if let Some(paths) = command
+        .settings()
+        .get::<Vec<String>>(profile_key)
+        .optional()?
+    {
+        return paths
+            .iter()
+            .map(|path| {
+                RepoPathBuf::from_relative_path(path).map_err(|err| {
+                    config_error_with_message(
+                        format!("Invalid path in sparse profile '{source}'"),
+                        err,
+                    )
+                })
+            })
+            .try_collect();
+    }
+    let workspace_id = WorkspaceId::new(source.to_owned());
+    if workspace_id == *workspace_command.workspace_id() {
+        return Ok(workspace_command.working_copy().sparse_patterns()?.to_vec());
+    }
+    let named_workspace_root = if workspace_command
+        .repo()
+        .view()
+        .get_wc_commit_id(&workspace_id)
+        .is_some()
+    {
+        SimpleWorkspaceStore::load(workspace_command.repo_path())
+            .get_workspace_path(&workspace_id)?
+            .filter(|path| path.join(".jj").is_dir())
+    } else {
+        None
+    };
+    let workspace_root = named_workspace_root.unwrap_or_else(|| command.cwd().join(source));
+    if !workspace_root.join(".jj").is_dir() {
+        return Err(user_error_with_hint(
+            format!("No sparse profile or workspace named '{source}'"),
+            "Define a profile in the `sparse.profiles` config table, or pass the name or path of \
+             a workspace.",
+        ));
+    }
+    let workspace = command.load_workspace_at(&workspace_root, command.settings())?;
+    if workspace.repo_path() != workspace_command.repo_path() {
+        return Err(user_error(format!(
+            "Workspace '{source}' belongs to a different repository"
+        )));
+    }
+    Ok(workspace.working_copy().sparse_patterns()?.to_vec())
+}
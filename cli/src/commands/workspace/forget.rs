@@ -15,6 +15,8 @@
 use clap_complete::ArgValueCandidates;
 use itertools::Itertools;
 use jj_lib::op_store::WorkspaceId;
+use jj_lib::workspace_store::SimpleWorkspaceStore;
+use jj_lib::workspace_store::WorkspaceStore as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
@@ -78,5 +80,6 @@ pub fn cmd_workspace_forget(
     };
 
     tx.finish(ui, description)?;
+    SimpleWorkspaceStore::load(workspace_command.repo_path()).forget(&wss.iter().collect_vec())?;
     Ok(())
 }
@@ -16,19 +16,27 @@ use itertools::Itertools;
 use jj_lib::repo::Repo;
 use tracing::instrument;
 
+use crate::cli_util::format_template_null_terminated;
 use crate::cli_util::CommandHelper;
 use crate::command_error::CommandError;
 use crate::ui::Ui;
 
 /// List workspaces
 #[derive(clap::Args, Clone, Debug)]
-pub struct WorkspaceListArgs {}
+pub struct WorkspaceListArgs {
+    /// Terminate each entry with a NUL byte instead of a newline
+    ///
+    /// The output is printed without color, so it can be safely consumed by
+    /// `xargs -0` and the like.
+    #[arg(long = "null", short = 'z')]
+    null_terminated: bool,
+}
 
 #[instrument(skip_all)]
 pub fn cmd_workspace_list(
     ui: &mut Ui,
     command: &CommandHelper,
-    _args: &WorkspaceListArgs,
+    args: &WorkspaceListArgs,
 ) -> Result<(), CommandError> {
     let workspace_command = command.workspace_helper(ui)?;
     let repo = workspace_command.repo();
@@ -37,8 +45,12 @@ pub fn cmd_workspace_list(
     for (workspace_id, wc_commit_id) in repo.view().wc_commit_ids().iter().sorted() {
         write!(formatter, "{}: ", workspace_id.as_str())?;
         let commit = repo.store().get_commit(wc_commit_id)?;
-        template.format(&commit, formatter.as_mut())?;
-        writeln!(formatter)?;
+        if args.null_terminated {
+            formatter.write_all(&format_template_null_terminated(&commit, &template))?;
+        } else {
+            template.format(&commit, formatter.as_mut())?;
+            writeln!(formatter)?;
+        }
     }
     Ok(())
 }
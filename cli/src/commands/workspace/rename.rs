@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use jj_lib::op_store::WorkspaceId;
+use jj_lib::workspace_store::SimpleWorkspaceStore;
+use jj_lib::workspace_store::WorkspaceStore as _;
 use tracing::instrument;
 
 use crate::cli_util::CommandHelper;
@@ -66,7 +68,7 @@ pub fn cmd_workspace_rename(
         .rename_workspace(new_workspace_id.clone());
 
     tx.repo_mut()
-        .rename_workspace(&old_workspace_id, new_workspace_id)?;
+        .rename_workspace(&old_workspace_id, new_workspace_id.clone())?;
     let repo = tx.commit(format!(
         "Renamed workspace '{}' to '{}'",
         old_workspace_id.as_str(),
@@ -74,5 +76,9 @@ pub fn cmd_workspace_rename(
     ))?;
     locked_ws.finish(repo.op_id().clone())?;
 
+    let workspace_store = SimpleWorkspaceStore::load(workspace_command.repo_path());
+    workspace_store.forget(&[&old_workspace_id])?;
+    workspace_store.add(&new_workspace_id, workspace_command.workspace_root())?;
+
     Ok(())
 }
@@ -0,0 +1,246 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Terminal checklist for confirming the commits a command should operate on.
+
+use std::io;
+use std::io::IsTerminal as _;
+use std::ops::ControlFlow;
+
+use itertools::Itertools as _;
+use jj_lib::commit::Commit;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::crossterm::event::KeyEvent;
+use ratatui::widgets::Block;
+use ratatui::widgets::List;
+use ratatui::widgets::ListState;
+use ratatui::Frame;
+
+use crate::cli_util::WorkspaceCommandHelper;
+use crate::command_error::user_error;
+use crate::command_error::user_error_with_message;
+use crate::command_error::CommandError;
+use crate::tui;
+use crate::tui::Motion;
+
+const HELP_TEXT: &str = "j/k: next/prev  space: toggle  a: toggle all  enter: confirm  q: cancel";
+
+/// What the event loop should do after handling a key.
+#[derive(Debug, Eq, PartialEq)]
+enum Action {
+    Continue,
+    Confirm,
+    Cancel,
+}
+
+/// State of the checklist, independent of the terminal.
+struct PickerState {
+    title: String,
+    labels: Vec<String>,
+    selected: Vec<bool>,
+    current: usize,
+}
+
+impl PickerState {
+    /// Creates a checklist with all items selected.
+    fn new(title: String, labels: Vec<String>) -> Self {
+        let selected = vec![true; labels.len()];
+        PickerState {
+            title,
+            labels,
+            selected,
+            current: 0,
+        }
+    }
+
+    fn num_selected(&self) -> usize {
+        self.selected.iter().filter(|&&selected| selected).count()
+    }
+
+    fn move_selection(&mut self, motion: Motion) {
+        motion.apply(&mut self.current, self.labels.len());
+    }
+
+    fn toggle_current(&mut self) {
+        if let Some(selected) = self.selected.get_mut(self.current) {
+            *selected = !*selected;
+        }
+    }
+
+    /// Deselects all items if all of them are selected, and selects all items
+    /// otherwise.
+    fn toggle_all(&mut self) {
+        let new_value = self.num_selected() != self.labels.len();
+        self.selected.fill(new_value);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        if tui::is_interrupt(&key) {
+            return Action::Cancel;
+        }
+        if let Some(motion) = Motion::from_key(&key) {
+            self.move_selection(motion);
+            return Action::Continue;
+        }
+        match key.code {
+            KeyCode::Char(' ') => {
+                self.toggle_current();
+                self.move_selection(Motion::Next);
+            }
+            KeyCode::Char('a') => self.toggle_all(),
+            KeyCode::Enter | KeyCode::Char('y') => return Action::Confirm,
+            KeyCode::Esc | KeyCode::Char('q') => return Action::Cancel,
+            _ => {}
+        }
+        Action::Continue
+    }
+}
+
+fn render(frame: &mut Frame, state: &PickerState) {
+    let [list_area, status_area] = tui::split_status_line(frame.area());
+    let items = state
+        .labels
+        .iter()
+        .zip(&state.selected)
+        .map(|(label, &selected)| {
+            let mark = if selected { 'x' } else { ' ' };
+            format!("[{mark}] {label}")
+        });
+    let title = format!(
+        " {} ({} of {} selected) ",
+        state.title,
+        state.num_selected(),
+        state.labels.len()
+    );
+    let list = List::new(items)
+        .block(Block::bordered().title(title))
+        .highlight_style(tui::selected_style());
+    let mut list_state = ListState::default().with_selected(Some(state.current));
+    frame.render_stateful_widget(list, list_area, &mut list_state);
+    tui::render_status_line(frame, status_area, HELP_TEXT);
+}
+
+fn run_event_loop(state: &mut PickerState) -> io::Result<Action> {
+    tui::run_event_loop(state, render, |state, key, _| {
+        Ok(match state.handle_key(key) {
+            Action::Continue => ControlFlow::Continue(()),
+            action => ControlFlow::Break(action),
+        })
+    })
+}
+
+/// Shows `commits` as a checklist and lets the user deselect the ones that
+/// shouldn't be operated on. Returns the selected commits in the original
+/// order.
+pub fn pick_commits(
+    workspace_command: &WorkspaceCommandHelper,
+    title: &str,
+    commits: Vec<Commit>,
+) -> Result<Vec<Commit>, CommandError> {
+    if !io::stdout().is_terminal() {
+        return Err(user_error(
+            "Cannot select revisions interactively since the output is not connected to a terminal",
+        ));
+    }
+    let labels = commits
+        .iter()
+        .map(|commit| workspace_command.format_commit_summary(commit))
+        .collect();
+    let mut state = PickerState::new(title.to_owned(), labels);
+    match run_event_loop(&mut state) {
+        Ok(Action::Confirm) => {}
+        Ok(_) => return Err(user_error("Selection was cancelled")),
+        Err(err) => {
+            return Err(user_error_with_message(
+                "Failed to run the interactive selection in the terminal",
+                err,
+            ));
+        }
+    }
+    Ok(commits
+        .into_iter()
+        .zip_eq(state.selected)
+        .filter_map(|(commit, selected)| selected.then_some(commit))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    use super::*;
+
+    fn new_state() -> PickerState {
+        let labels = ["aaa first", "bbb second", "ccc third"]
+            .map(str::to_owned)
+            .to_vec();
+        PickerState::new("Abandon".to_owned(), labels)
+    }
+
+    fn press(state: &mut PickerState, c: char) -> Action {
+        state.handle_key(KeyEvent::from(KeyCode::Char(c)))
+    }
+
+    fn render_to_string(state: &PickerState) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(40, 6)).unwrap();
+        terminal.draw(|frame| render(frame, state)).unwrap();
+        terminal.backend().to_string()
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut state = new_state();
+        assert_eq!(state.num_selected(), 3);
+
+        // Toggling moves to the next item
+        assert_eq!(press(&mut state, ' '), Action::Continue);
+        assert_eq!(state.current, 1);
+        assert_eq!(state.selected, [false, true, true]);
+        press(&mut state, 'j');
+        press(&mut state, 'j');
+        assert_eq!(state.current, 2);
+        press(&mut state, ' ');
+        assert_eq!(state.current, 2);
+        assert_eq!(state.selected, [false, true, false]);
+
+        // Toggling all selects everything unless everything is selected
+        press(&mut state, 'a');
+        assert_eq!(state.selected, [true, true, true]);
+        press(&mut state, 'a');
+        assert_eq!(state.selected, [false, false, false]);
+
+        press(&mut state, 'k');
+        assert_eq!(state.current, 1);
+        assert_eq!(
+            state.handle_key(KeyEvent::from(KeyCode::Enter)),
+            Action::Confirm
+        );
+        assert_eq!(press(&mut state, 'q'), Action::Cancel);
+    }
+
+    #[test]
+    fn test_render() {
+        let mut state = new_state();
+        press(&mut state, ' ');
+        insta::assert_snapshot!(render_to_string(&state), @r#"
+        "┌ Abandon (2 of 3 selected) ───────────┐"
+        "│[ ] aaa first                         │"
+        "│[x] bbb second                        │"
+        "│[x] ccc third                         │"
+        "└──────────────────────────────────────┘"
+        "j/k: next/prev  space: toggle  a: toggle"
+        "#);
+    }
+}
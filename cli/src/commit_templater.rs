@@ -13,13 +13,16 @@
 // limitations under the License.
 
 use std::any::Any;
+use std::cell::RefCell;
 use std::cmp::max;
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::io;
+use std::path::Path;
 use std::rc::Rc;
 
 use bstr::BString;
+use clru::CLruCache;
 use futures::stream::BoxStream;
 use futures::StreamExt as _;
 use futures::TryStreamExt as _;
@@ -27,9 +30,12 @@ use itertools::Itertools as _;
 use jj_lib::backend::BackendResult;
 use jj_lib::backend::ChangeId;
 use jj_lib::backend::CommitId;
+use jj_lib::backend::Timestamp;
 use jj_lib::backend::TreeValue;
 use jj_lib::commit::Commit;
+use jj_lib::config::ConfigGetError;
 use jj_lib::conflicts::ConflictMarkerStyle;
+use jj_lib::conventional_commit::ConventionalCommit;
 use jj_lib::copies::CopiesTreeDiffEntry;
 use jj_lib::copies::CopiesTreeDiffEntryPath;
 use jj_lib::copies::CopyRecords;
@@ -37,6 +43,7 @@ use jj_lib::extensions_map::ExtensionsMap;
 use jj_lib::fileset;
 use jj_lib::fileset::FilesetDiagnostics;
 use jj_lib::fileset::FilesetExpression;
+use jj_lib::id_prefix;
 use jj_lib::id_prefix::IdPrefixContext;
 use jj_lib::id_prefix::IdPrefixIndex;
 use jj_lib::matchers::Matcher;
@@ -47,6 +54,7 @@ use jj_lib::op_store::RefTarget;
 use jj_lib::op_store::RemoteRef;
 use jj_lib::op_store::WorkspaceId;
 use jj_lib::repo::Repo;
+use jj_lib::repo_path::RepoPath;
 use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::repo_path::RepoPathUiConverter;
 use jj_lib::revset;
@@ -66,8 +74,10 @@ use once_cell::unsync::OnceCell;
 use pollster::FutureExt as _;
 
 use crate::diff_util;
+use crate::diff_util::DiffStatEntry;
 use crate::diff_util::DiffStats;
 use crate::formatter::Formatter;
+use crate::git_util;
 use crate::revset_util;
 use crate::template_builder;
 use crate::template_builder::merge_fn_map;
@@ -75,6 +85,7 @@ use crate::template_builder::BuildContext;
 use crate::template_builder::CoreTemplateBuildFnTable;
 use crate::template_builder::CoreTemplatePropertyKind;
 use crate::template_builder::IntoTemplateProperty;
+use crate::template_builder::TemplateBuildFunctionFnMap;
 use crate::template_builder::TemplateBuildMethodFnMap;
 use crate::template_builder::TemplateLanguage;
 use crate::template_parser;
@@ -84,6 +95,8 @@ use crate::template_parser::TemplateDiagnostics;
 use crate::template_parser::TemplateParseError;
 use crate::template_parser::TemplateParseResult;
 use crate::templater;
+use crate::templater::LabelTemplate;
+use crate::templater::Literal;
 use crate::templater::PlainTextFormattedProperty;
 use crate::templater::SizeHint;
 use crate::templater::Template;
@@ -91,6 +104,7 @@ use crate::templater::TemplateFormatter;
 use crate::templater::TemplateProperty;
 use crate::templater::TemplatePropertyError;
 use crate::templater::TemplatePropertyExt as _;
+use crate::templater::TimestampRange;
 use crate::text_util;
 
 pub trait CommitTemplateLanguageExtension {
@@ -103,6 +117,7 @@ pub struct CommitTemplateLanguage<'repo> {
     repo: &'repo dyn Repo,
     path_converter: &'repo RepoPathUiConverter,
     workspace_id: WorkspaceId,
+    repo_path: &'repo Path,
     // RevsetParseContext doesn't borrow a repo, but we'll need 'repo lifetime
     // anyway to capture it to evaluate dynamically-constructed user expression
     // such as `revset("ancestors(" ++ commit_id ++ ")")`.
@@ -111,6 +126,7 @@ pub struct CommitTemplateLanguage<'repo> {
     revset_parse_context: RevsetParseContext<'repo>,
     id_prefix_context: &'repo IdPrefixContext,
     immutable_expression: Rc<UserRevsetExpression>,
+    label_rules: &'repo [CommitLabelRule],
     conflict_marker_style: ConflictMarkerStyle,
     build_fn_table: CommitTemplateBuildFnTable<'repo>,
     keyword_cache: CommitKeywordCache<'repo>,
@@ -125,9 +141,11 @@ impl<'repo> CommitTemplateLanguage<'repo> {
         repo: &'repo dyn Repo,
         path_converter: &'repo RepoPathUiConverter,
         workspace_id: &WorkspaceId,
+        repo_path: &'repo Path,
         revset_parse_context: RevsetParseContext<'repo>,
         id_prefix_context: &'repo IdPrefixContext,
         immutable_expression: Rc<UserRevsetExpression>,
+        label_rules: &'repo [CommitLabelRule],
         conflict_marker_style: ConflictMarkerStyle,
         extensions: &[impl AsRef<dyn CommitTemplateLanguageExtension>],
     ) -> Self {
@@ -145,9 +163,11 @@ impl<'repo> CommitTemplateLanguage<'repo> {
             repo,
             path_converter,
             workspace_id: workspace_id.clone(),
+            repo_path,
             revset_parse_context,
             id_prefix_context,
             immutable_expression,
+            label_rules,
             conflict_marker_style,
             build_fn_table,
             keyword_cache: CommitKeywordCache::default(),
@@ -271,6 +291,19 @@ impl<'repo> TemplateLanguage<'repo> for CommitTemplateLanguage<'repo> {
                 let build = template_parser::lookup_method(type_name, table, function)?;
                 build(self, diagnostics, build_ctx, property, function)
             }
+            CommitTemplatePropertyKind::CommitOrChangeIdOpt(property) => {
+                let type_name = "CommitOrChangeId";
+                let table = &self.build_fn_table.commit_or_change_id_methods;
+                let build = template_parser::lookup_method(type_name, table, function)?;
+                let inner_property = property.try_unwrap(type_name);
+                build(
+                    self,
+                    diagnostics,
+                    build_ctx,
+                    Box::new(inner_property),
+                    function,
+                )
+            }
             CommitTemplatePropertyKind::ShortestIdPrefix(property) => {
                 let table = &self.build_fn_table.shortest_id_prefix_methods;
                 let build = template_parser::lookup_method(type_name, table, function)?;
@@ -303,6 +336,23 @@ impl<'repo> TemplateLanguage<'repo> for CommitTemplateLanguage<'repo> {
                 let build = template_parser::lookup_method(type_name, table, function)?;
                 build(self, diagnostics, build_ctx, property, function)
             }
+            CommitTemplatePropertyKind::DiffStatEntry(property) => {
+                let table = &self.build_fn_table.diff_stat_entry_methods;
+                let build = template_parser::lookup_method(type_name, table, function)?;
+                build(self, diagnostics, build_ctx, property, function)
+            }
+            CommitTemplatePropertyKind::DiffStatEntryList(property) => {
+                // TODO: migrate to table?
+                template_builder::build_unformattable_list_method(
+                    self,
+                    diagnostics,
+                    build_ctx,
+                    property,
+                    function,
+                    Self::wrap_diff_stat_entry,
+                    Self::wrap_diff_stat_entry_list,
+                )
+            }
             CommitTemplatePropertyKind::DiffStats(property) => {
                 let table = &self.build_fn_table.diff_stats_methods;
                 let build = template_parser::lookup_method(type_name, table, function)?;
@@ -332,6 +382,79 @@ impl<'repo> TemplateLanguage<'repo> for CommitTemplateLanguage<'repo> {
             }
         }
     }
+
+    fn wrap_top_level_template(
+        &self,
+        self_property: Self::Property,
+        template: Box<dyn Template + 'repo>,
+        span: pest::Span<'_>,
+    ) -> TemplateParseResult<Box<dyn Template + 'repo>> {
+        // Label rules apply to templates rendering a single commit.
+        let CommitTemplatePropertyKind::Commit(property) = self_property else {
+            return Ok(template);
+        };
+        if self.label_rules.is_empty() {
+            return Ok(template);
+        }
+        let rules = self
+            .label_rules
+            .iter()
+            .map(|rule| {
+                let revset = evaluate_revset_expression(self, span, &rule.expression)?;
+                Ok::<_, TemplateParseError>((rule, revset.containing_fn()))
+            })
+            .try_collect()?;
+        Ok(Box::new(CommitLabelsTemplate {
+            content: template,
+            commit: property,
+            rules,
+        }))
+    }
+}
+
+/// Rule to label commits in commit templates, loaded from the `commit-labels`
+/// config table.
+pub struct CommitLabelRule {
+    /// Label to apply to the matching commits.
+    pub label: String,
+    /// Commits to label.
+    pub expression: Rc<UserRevsetExpression>,
+    /// If set, the rule only applies to templates within this label.
+    pub context_label: Option<String>,
+}
+
+/// Labels the content with the labels of the rules matching the commit.
+struct CommitLabelsTemplate<'repo, T, P> {
+    content: T,
+    commit: P,
+    rules: Vec<(&'repo CommitLabelRule, Box<RevsetContainingFn<'repo>>)>,
+}
+
+impl<T, P> Template for CommitLabelsTemplate<'_, T, P>
+where
+    T: Template,
+    P: TemplateProperty<Output = Commit>,
+{
+    fn format(&self, formatter: &mut TemplateFormatter) -> io::Result<()> {
+        let commit = match self.commit.extract() {
+            Ok(commit) => commit,
+            Err(err) => return formatter.handle_error(err),
+        };
+        let mut labels = vec![];
+        for (rule, is_contained) in &self.rules {
+            if let Some(context_label) = &rule.context_label {
+                if !formatter.labels().contains(context_label) {
+                    continue;
+                }
+            }
+            match is_contained(commit.id()) {
+                Ok(true) => labels.push(rule.label.clone()),
+                Ok(false) => {}
+                Err(err) => return formatter.handle_error(err.into()),
+            }
+        }
+        LabelTemplate::new(&self.content, Literal(labels)).format(formatter)
+    }
 }
 
 // If we need to add multiple languages that support Commit types, this can be
@@ -345,6 +468,11 @@ impl<'repo> CommitTemplateLanguage<'repo> {
         &self.workspace_id
     }
 
+    /// Path to the `.jj/repo` directory.
+    pub fn repo_path(&self) -> &'repo Path {
+        self.repo_path
+    }
+
     pub fn keyword_cache(&self) -> &CommitKeywordCache<'repo> {
         &self.keyword_cache
     }
@@ -407,6 +535,12 @@ impl<'repo> CommitTemplateLanguage<'repo> {
         CommitTemplatePropertyKind::CommitOrChangeId(Box::new(property))
     }
 
+    pub fn wrap_commit_or_change_id_opt(
+        property: impl TemplateProperty<Output = Option<CommitOrChangeId>> + 'repo,
+    ) -> CommitTemplatePropertyKind<'repo> {
+        CommitTemplatePropertyKind::CommitOrChangeIdOpt(Box::new(property))
+    }
+
     pub fn wrap_shortest_id_prefix(
         property: impl TemplateProperty<Output = ShortestIdPrefix> + 'repo,
     ) -> CommitTemplatePropertyKind<'repo> {
@@ -443,6 +577,18 @@ impl<'repo> CommitTemplateLanguage<'repo> {
         CommitTemplatePropertyKind::DiffStats(Box::new(property))
     }
 
+    pub fn wrap_diff_stat_entry(
+        property: impl TemplateProperty<Output = DiffStatEntry> + 'repo,
+    ) -> CommitTemplatePropertyKind<'repo> {
+        CommitTemplatePropertyKind::DiffStatEntry(Box::new(property))
+    }
+
+    pub fn wrap_diff_stat_entry_list(
+        property: impl TemplateProperty<Output = Vec<DiffStatEntry>> + 'repo,
+    ) -> CommitTemplatePropertyKind<'repo> {
+        CommitTemplatePropertyKind::DiffStatEntryList(Box::new(property))
+    }
+
     fn wrap_cryptographic_signature_opt(
         property: impl TemplateProperty<Output = Option<CryptographicSignature>> + 'repo,
     ) -> CommitTemplatePropertyKind<'repo> {
@@ -467,12 +613,15 @@ pub enum CommitTemplatePropertyKind<'repo> {
     RepoPath(Box<dyn TemplateProperty<Output = RepoPathBuf> + 'repo>),
     RepoPathOpt(Box<dyn TemplateProperty<Output = Option<RepoPathBuf>> + 'repo>),
     CommitOrChangeId(Box<dyn TemplateProperty<Output = CommitOrChangeId> + 'repo>),
+    CommitOrChangeIdOpt(Box<dyn TemplateProperty<Output = Option<CommitOrChangeId>> + 'repo>),
     ShortestIdPrefix(Box<dyn TemplateProperty<Output = ShortestIdPrefix> + 'repo>),
     TreeDiff(Box<dyn TemplateProperty<Output = TreeDiff> + 'repo>),
     TreeDiffEntry(Box<dyn TemplateProperty<Output = TreeDiffEntry> + 'repo>),
     TreeDiffEntryList(Box<dyn TemplateProperty<Output = Vec<TreeDiffEntry>> + 'repo>),
     TreeEntry(Box<dyn TemplateProperty<Output = TreeEntry> + 'repo>),
     DiffStats(Box<dyn TemplateProperty<Output = DiffStatsFormatted<'repo>> + 'repo>),
+    DiffStatEntry(Box<dyn TemplateProperty<Output = DiffStatEntry> + 'repo>),
+    DiffStatEntryList(Box<dyn TemplateProperty<Output = Vec<DiffStatEntry>> + 'repo>),
     CryptographicSignatureOpt(
         Box<dyn TemplateProperty<Output = Option<CryptographicSignature>> + 'repo>,
     ),
@@ -492,12 +641,15 @@ impl<'repo> IntoTemplateProperty<'repo> for CommitTemplatePropertyKind<'repo> {
             CommitTemplatePropertyKind::RepoPath(_) => "RepoPath",
             CommitTemplatePropertyKind::RepoPathOpt(_) => "Option<RepoPath>",
             CommitTemplatePropertyKind::CommitOrChangeId(_) => "CommitOrChangeId",
+            CommitTemplatePropertyKind::CommitOrChangeIdOpt(_) => "Option<CommitOrChangeId>",
             CommitTemplatePropertyKind::ShortestIdPrefix(_) => "ShortestIdPrefix",
             CommitTemplatePropertyKind::TreeDiff(_) => "TreeDiff",
             CommitTemplatePropertyKind::TreeDiffEntry(_) => "TreeDiffEntry",
             CommitTemplatePropertyKind::TreeDiffEntryList(_) => "List<TreeDiffEntry>",
             CommitTemplatePropertyKind::TreeEntry(_) => "TreeEntry",
             CommitTemplatePropertyKind::DiffStats(_) => "DiffStats",
+            CommitTemplatePropertyKind::DiffStatEntry(_) => "DiffStatEntry",
+            CommitTemplatePropertyKind::DiffStatEntryList(_) => "List<DiffStatEntry>",
             CommitTemplatePropertyKind::CryptographicSignatureOpt(_) => {
                 "Option<CryptographicSignature>"
             }
@@ -527,6 +679,9 @@ impl<'repo> IntoTemplateProperty<'repo> for CommitTemplatePropertyKind<'repo> {
                 Some(Box::new(property.map(|opt| opt.is_some())))
             }
             CommitTemplatePropertyKind::CommitOrChangeId(_) => None,
+            CommitTemplatePropertyKind::CommitOrChangeIdOpt(property) => {
+                Some(Box::new(property.map(|opt| opt.is_some())))
+            }
             CommitTemplatePropertyKind::ShortestIdPrefix(_) => None,
             // TODO: boolean cast could be implemented, but explicit
             // diff.empty() method might be better.
@@ -537,6 +692,10 @@ impl<'repo> IntoTemplateProperty<'repo> for CommitTemplatePropertyKind<'repo> {
             }
             CommitTemplatePropertyKind::TreeEntry(_) => None,
             CommitTemplatePropertyKind::DiffStats(_) => None,
+            CommitTemplatePropertyKind::DiffStatEntry(_) => None,
+            CommitTemplatePropertyKind::DiffStatEntryList(property) => {
+                Some(Box::new(property.map(|l| !l.is_empty())))
+            }
             CommitTemplatePropertyKind::CryptographicSignatureOpt(property) => {
                 Some(Box::new(property.map(|sig| sig.is_some())))
             }
@@ -575,6 +734,9 @@ impl<'repo> IntoTemplateProperty<'repo> for CommitTemplatePropertyKind<'repo> {
             CommitTemplatePropertyKind::CommitOrChangeId(property) => {
                 Some(property.into_template())
             }
+            CommitTemplatePropertyKind::CommitOrChangeIdOpt(property) => {
+                Some(property.into_template())
+            }
             CommitTemplatePropertyKind::ShortestIdPrefix(property) => {
                 Some(property.into_template())
             }
@@ -583,6 +745,8 @@ impl<'repo> IntoTemplateProperty<'repo> for CommitTemplatePropertyKind<'repo> {
             CommitTemplatePropertyKind::TreeDiffEntryList(_) => None,
             CommitTemplatePropertyKind::TreeEntry(_) => None,
             CommitTemplatePropertyKind::DiffStats(property) => Some(property.into_template()),
+            CommitTemplatePropertyKind::DiffStatEntry(_) => None,
+            CommitTemplatePropertyKind::DiffStatEntryList(_) => None,
             CommitTemplatePropertyKind::CryptographicSignatureOpt(_) => None,
             CommitTemplatePropertyKind::AnnotationLine(_) => None,
         }
@@ -603,12 +767,15 @@ impl<'repo> IntoTemplateProperty<'repo> for CommitTemplatePropertyKind<'repo> {
             (CommitTemplatePropertyKind::RepoPath(_), _) => None,
             (CommitTemplatePropertyKind::RepoPathOpt(_), _) => None,
             (CommitTemplatePropertyKind::CommitOrChangeId(_), _) => None,
+            (CommitTemplatePropertyKind::CommitOrChangeIdOpt(_), _) => None,
             (CommitTemplatePropertyKind::ShortestIdPrefix(_), _) => None,
             (CommitTemplatePropertyKind::TreeDiff(_), _) => None,
             (CommitTemplatePropertyKind::TreeDiffEntry(_), _) => None,
             (CommitTemplatePropertyKind::TreeDiffEntryList(_), _) => None,
             (CommitTemplatePropertyKind::TreeEntry(_), _) => None,
             (CommitTemplatePropertyKind::DiffStats(_), _) => None,
+            (CommitTemplatePropertyKind::DiffStatEntry(_), _) => None,
+            (CommitTemplatePropertyKind::DiffStatEntryList(_), _) => None,
             (CommitTemplatePropertyKind::CryptographicSignatureOpt(_), _) => None,
             (CommitTemplatePropertyKind::AnnotationLine(_), _) => None,
         }
@@ -632,12 +799,15 @@ impl<'repo> IntoTemplateProperty<'repo> for CommitTemplatePropertyKind<'repo> {
             (CommitTemplatePropertyKind::RepoPath(_), _) => None,
             (CommitTemplatePropertyKind::RepoPathOpt(_), _) => None,
             (CommitTemplatePropertyKind::CommitOrChangeId(_), _) => None,
+            (CommitTemplatePropertyKind::CommitOrChangeIdOpt(_), _) => None,
             (CommitTemplatePropertyKind::ShortestIdPrefix(_), _) => None,
             (CommitTemplatePropertyKind::TreeDiff(_), _) => None,
             (CommitTemplatePropertyKind::TreeDiffEntry(_), _) => None,
             (CommitTemplatePropertyKind::TreeDiffEntryList(_), _) => None,
             (CommitTemplatePropertyKind::TreeEntry(_), _) => None,
             (CommitTemplatePropertyKind::DiffStats(_), _) => None,
+            (CommitTemplatePropertyKind::DiffStatEntry(_), _) => None,
+            (CommitTemplatePropertyKind::DiffStatEntryList(_), _) => None,
             (CommitTemplatePropertyKind::CryptographicSignatureOpt(_), _) => None,
             (CommitTemplatePropertyKind::AnnotationLine(_), _) => None,
         }
@@ -660,6 +830,7 @@ pub struct CommitTemplateBuildFnTable<'repo> {
     pub tree_diff_entry_methods: CommitTemplateBuildMethodFnMap<'repo, TreeDiffEntry>,
     pub tree_entry_methods: CommitTemplateBuildMethodFnMap<'repo, TreeEntry>,
     pub diff_stats_methods: CommitTemplateBuildMethodFnMap<'repo, DiffStats>,
+    pub diff_stat_entry_methods: CommitTemplateBuildMethodFnMap<'repo, DiffStatEntry>,
     pub cryptographic_signature_methods:
         CommitTemplateBuildMethodFnMap<'repo, CryptographicSignature>,
     pub annotation_line_methods: CommitTemplateBuildMethodFnMap<'repo, AnnotationLine>,
@@ -668,8 +839,10 @@ pub struct CommitTemplateBuildFnTable<'repo> {
 impl<'repo> CommitTemplateBuildFnTable<'repo> {
     /// Creates new symbol table containing the builtin methods.
     fn builtin() -> Self {
+        let mut core = CoreTemplateBuildFnTable::builtin();
+        merge_fn_map(&mut core.functions, builtin_commit_functions());
         CommitTemplateBuildFnTable {
-            core: CoreTemplateBuildFnTable::builtin(),
+            core,
             commit_methods: builtin_commit_methods(),
             ref_name_methods: builtin_ref_name_methods(),
             repo_path_methods: builtin_repo_path_methods(),
@@ -679,6 +852,7 @@ impl<'repo> CommitTemplateBuildFnTable<'repo> {
             tree_diff_entry_methods: builtin_tree_diff_entry_methods(),
             tree_entry_methods: builtin_tree_entry_methods(),
             diff_stats_methods: builtin_diff_stats_methods(),
+            diff_stat_entry_methods: builtin_diff_stat_entry_methods(),
             cryptographic_signature_methods: builtin_cryptographic_signature_methods(),
             annotation_line_methods: builtin_annotation_line_methods(),
         }
@@ -696,6 +870,7 @@ impl<'repo> CommitTemplateBuildFnTable<'repo> {
             tree_diff_entry_methods: HashMap::new(),
             tree_entry_methods: HashMap::new(),
             diff_stats_methods: HashMap::new(),
+            diff_stat_entry_methods: HashMap::new(),
             cryptographic_signature_methods: HashMap::new(),
             annotation_line_methods: HashMap::new(),
         }
@@ -713,6 +888,7 @@ impl<'repo> CommitTemplateBuildFnTable<'repo> {
             tree_diff_entry_methods,
             tree_entry_methods,
             diff_stats_methods,
+            diff_stat_entry_methods,
             cryptographic_signature_methods,
             annotation_line_methods,
         } = extension;
@@ -733,6 +909,7 @@ impl<'repo> CommitTemplateBuildFnTable<'repo> {
         merge_fn_map(&mut self.tree_diff_entry_methods, tree_diff_entry_methods);
         merge_fn_map(&mut self.tree_entry_methods, tree_entry_methods);
         merge_fn_map(&mut self.diff_stats_methods, diff_stats_methods);
+        merge_fn_map(&mut self.diff_stat_entry_methods, diff_stat_entry_methods);
         merge_fn_map(
             &mut self.cryptographic_signature_methods,
             cryptographic_signature_methods,
@@ -747,7 +924,10 @@ pub struct CommitKeywordCache<'repo> {
     bookmarks_index: OnceCell<Rc<RefNamesIndex>>,
     tags_index: OnceCell<Rc<RefNamesIndex>>,
     git_refs_index: OnceCell<Rc<RefNamesIndex>>,
+    #[cfg(feature = "git")]
+    git_notes_indexes: RefCell<HashMap<String, Rc<HashMap<CommitId, String>>>>,
     is_immutable_fn: OnceCell<Rc<RevsetContainingFn<'repo>>>,
+    tree_diff_caches: Rc<TreeDiffCacheMap>,
 }
 
 impl<'repo> CommitKeywordCache<'repo> {
@@ -766,6 +946,38 @@ impl<'repo> CommitKeywordCache<'repo> {
             .get_or_init(|| Rc::new(build_ref_names_index(repo.view().git_refs())))
     }
 
+    #[cfg(feature = "git")]
+    pub fn git_notes_index(
+        &self,
+        repo: &dyn Repo,
+        notes_ref: &str,
+        span: pest::Span<'_>,
+    ) -> TemplateParseResult<Rc<HashMap<CommitId, String>>> {
+        if let Some(index) = self.git_notes_indexes.borrow().get(notes_ref) {
+            return Ok(index.clone());
+        }
+        let notes = match jj_lib::git::read_notes(repo.store(), notes_ref) {
+            Ok(notes) => notes,
+            // Non-Git backends have no notes to show.
+            Err(jj_lib::git::GitNotesError::UnexpectedBackend(_)) => HashMap::new(),
+            Err(err) => {
+                return Err(
+                    TemplateParseError::expression("Failed to read Git notes", span)
+                        .with_source(err),
+                );
+            }
+        };
+        let index = Rc::new(notes);
+        self.git_notes_indexes
+            .borrow_mut()
+            .insert(notes_ref.to_owned(), index.clone());
+        Ok(index)
+    }
+
+    pub fn tree_diff_caches(&self) -> &Rc<TreeDiffCacheMap> {
+        &self.tree_diff_caches
+    }
+
     pub fn is_immutable_fn(
         &self,
         language: &CommitTemplateLanguage<'repo>,
@@ -782,6 +994,32 @@ impl<'repo> CommitKeywordCache<'repo> {
     }
 }
 
+fn builtin_commit_functions<'repo>(
+) -> TemplateBuildFunctionFnMap<'repo, CommitTemplateLanguage<'repo>> {
+    // Not using maplit::hashmap!{} or custom declarative macro here because
+    // code completion inside macro is quite restricted.
+    let mut map = TemplateBuildFunctionFnMap::<CommitTemplateLanguage>::new();
+    map.insert(
+        "remote_staleness",
+        |language, _diagnostics, _build_ctx, function| {
+            function.expect_no_arguments()?;
+            let last_fetch_time = git_util::read_last_fetch_time(language.repo_path);
+            let now = language
+                .settings()
+                .operation_timestamp()
+                .unwrap_or_else(Timestamp::now);
+            let out_property = Literal(last_fetch_time).and_then(move |start| {
+                let Some(start) = start else {
+                    return Ok(String::new());
+                };
+                Ok(TimestampRange { start, end: now }.duration()?)
+            });
+            Ok(CommitTemplateLanguage::wrap_string(out_property))
+        },
+    );
+    map
+}
+
 fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Commit> {
     type L<'repo> = CommitTemplateLanguage<'repo>;
     // Not using maplit::hashmap!{} or custom declarative macro here because
@@ -796,6 +1034,42 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             Ok(L::wrap_string(out_property))
         },
     );
+    map.insert(
+        "conventional_type",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|commit| {
+                ConventionalCommit::parse(commit.description())
+                    .map(|parsed| parsed.kind.to_owned())
+                    .unwrap_or_default()
+            });
+            Ok(L::wrap_string(out_property))
+        },
+    );
+    map.insert(
+        "conventional_scope",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|commit| {
+                ConventionalCommit::parse(commit.description())
+                    .and_then(|parsed| parsed.scope)
+                    .unwrap_or_default()
+                    .to_owned()
+            });
+            Ok(L::wrap_string(out_property))
+        },
+    );
+    map.insert(
+        "conventional_breaking",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|commit| {
+                ConventionalCommit::parse(commit.description())
+                    .is_some_and(|parsed| parsed.breaking)
+            });
+            Ok(L::wrap_boolean(out_property))
+        },
+    );
     map.insert(
         "change_id",
         |_language, _diagnostics, _build_ctx, self_property, function| {
@@ -805,6 +1079,18 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             Ok(L::wrap_commit_or_change_id(out_property))
         },
     );
+    map.insert(
+        "duplicated_from",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|commit| {
+                commit
+                    .duplicated_from()
+                    .map(|change_id| CommitOrChangeId::Change(change_id.clone()))
+            });
+            Ok(L::wrap_commit_or_change_id_opt(out_property))
+        },
+    );
     map.insert(
         "commit_id",
         |_language, _diagnostics, _build_ctx, self_property, function| {
@@ -825,17 +1111,25 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
     );
     map.insert(
         "author",
-        |_language, _diagnostics, _build_ctx, self_property, function| {
+        |language, _diagnostics, _build_ctx, self_property, function| {
             function.expect_no_arguments()?;
-            let out_property = self_property.map(|commit| commit.author().clone());
+            let mailmap = language.revset_parse_context.mailmap().cloned();
+            let out_property = self_property.map(move |commit| match &mailmap {
+                Some(mailmap) => mailmap.resolve_signature(commit.author()),
+                None => commit.author().clone(),
+            });
             Ok(L::wrap_signature(out_property))
         },
     );
     map.insert(
         "committer",
-        |_language, _diagnostics, _build_ctx, self_property, function| {
+        |language, _diagnostics, _build_ctx, self_property, function| {
             function.expect_no_arguments()?;
-            let out_property = self_property.map(|commit| commit.committer().clone());
+            let mailmap = language.revset_parse_context.mailmap().cloned();
+            let out_property = self_property.map(move |commit| match &mailmap {
+                Some(mailmap) => mailmap.resolve_signature(commit.committer()),
+                None => commit.committer().clone(),
+            });
             Ok(L::wrap_signature(out_property))
         },
     );
@@ -844,7 +1138,17 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
         |language, _diagnostics, _build_ctx, self_property, function| {
             function.expect_no_arguments()?;
             let user_email = language.revset_parse_context.user_email().to_owned();
-            let out_property = self_property.map(move |commit| commit.author().email == user_email);
+            let mailmap = language.revset_parse_context.mailmap().cloned();
+            let out_property = self_property.map(move |commit| {
+                let author = commit.author();
+                match &mailmap {
+                    Some(mailmap) => {
+                        mailmap.resolve(&author.name, &author.email).1
+                            == mailmap.resolve("", &user_email).1
+                    }
+                    None => author.email == user_email,
+                }
+            });
             Ok(L::wrap_boolean(out_property))
         },
     );
@@ -865,6 +1169,23 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             Ok(L::wrap_string(out_property))
         },
     );
+    map.insert(
+        "workspace_names",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let repo = language.repo;
+            let out_property = self_property.map(|commit| {
+                repo.view()
+                    .wc_commit_ids()
+                    .iter()
+                    .filter(|(_, wc_commit_id)| *wc_commit_id == commit.id())
+                    .map(|(workspace_id, _)| workspace_id.as_str().to_owned())
+                    .sorted()
+                    .collect_vec()
+            });
+            Ok(L::wrap_string_list(out_property))
+        },
+    );
     map.insert(
         "current_working_copy",
         |language, _diagnostics, _build_ctx, self_property, function| {
@@ -915,6 +1236,43 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             Ok(L::wrap_ref_name_list(out_property))
         },
     );
+    map.insert(
+        "containing_bookmarks",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let repo = language.repo;
+            let index = language.keyword_cache.bookmarks_index(repo).clone();
+            let out_property = self_property.map(move |commit| {
+                // The commit being drafted isn't indexed yet. Look up the
+                // descendants of the commits it rewrites instead.
+                let base_ids = if repo.index().has_id(commit.id()) {
+                    vec![commit.id()]
+                } else {
+                    commit
+                        .predecessor_ids()
+                        .iter()
+                        .filter(|id| repo.index().has_id(id))
+                        .collect()
+                };
+                let mut ref_names = index
+                    .iter()
+                    .filter(|(id, _)| {
+                        base_ids
+                            .iter()
+                            .any(|base_id| repo.index().is_ancestor(base_id, id))
+                    })
+                    .flat_map(|(_, ref_names)| ref_names)
+                    .filter(|ref_name| ref_name.is_local())
+                    .cloned()
+                    .collect_vec();
+                // Conflicted bookmark may point to multiple descendants
+                ref_names.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+                ref_names.dedup_by(|a, b| Rc::ptr_eq(a, b));
+                ref_names
+            });
+            Ok(L::wrap_ref_name_list(out_property))
+        },
+    );
     map.insert(
         "remote_bookmarks",
         |language, _diagnostics, _build_ctx, self_property, function| {
@@ -952,6 +1310,25 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             Ok(L::wrap_ref_name_list(out_property))
         },
     );
+    #[cfg(feature = "git")]
+    map.insert(
+        "git_note",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            let ([], [notes_ref_node]) = function.expect_arguments()?;
+            let cache = &language.keyword_cache;
+            let index = if let Some(node) = notes_ref_node {
+                template_parser::expect_string_literal_with(node, |notes_ref, span| {
+                    cache.git_notes_index(language.repo, notes_ref, span)
+                })?
+            } else {
+                let notes_ref = jj_lib::git::DEFAULT_NOTES_REF;
+                cache.git_notes_index(language.repo, notes_ref, function.name_span)?
+            };
+            let out_property = self_property
+                .map(move |commit| index.get(commit.id()).cloned().unwrap_or_default());
+            Ok(L::wrap_string(out_property))
+        },
+    );
     map.insert(
         "git_head",
         |language, _diagnostics, _build_ctx, self_property, function| {
@@ -1020,6 +1397,37 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             Ok(L::wrap_boolean(out_property))
         },
     );
+    map.insert(
+        "metadata",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            let [key_node] = function.expect_exact_arguments()?;
+            let key = template_parser::expect_string_literal_with(key_node, |key, _span| {
+                Ok(key.to_owned())
+            })?;
+            let repo = language.repo;
+            let out_property = self_property.map(move |commit| {
+                repo.view()
+                    .get_change_metadata(commit.change_id(), &key)
+                    .unwrap_or_default()
+                    .to_owned()
+            });
+            Ok(L::wrap_string(out_property))
+        },
+    );
+    map.insert(
+        "topic",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let repo = language.repo;
+            let out_property = self_property.map(|commit| {
+                repo.view()
+                    .get_topic(commit.change_id())
+                    .unwrap_or_default()
+                    .to_owned()
+            });
+            Ok(L::wrap_string(out_property))
+        },
+    );
     map.insert(
         "empty",
         |language, _diagnostics, _build_ctx, self_property, function| {
@@ -1042,8 +1450,16 @@ fn builtin_commit_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Comm
             };
             let repo = language.repo;
             let matcher: Rc<dyn Matcher> = files.to_matcher().into();
-            let out_property = self_property
-                .and_then(move |commit| Ok(TreeDiff::from_commit(repo, &commit, matcher.clone())?));
+            let caches = language.keyword_cache.tree_diff_caches().clone();
+            let out_property = self_property.and_then(move |commit| {
+                let cache = caches.get(&commit, &files);
+                Ok(TreeDiff::from_commit(
+                    repo,
+                    &commit,
+                    matcher.clone(),
+                    cache,
+                )?)
+            });
             Ok(L::wrap_tree_diff(out_property))
         },
     );
@@ -1148,6 +1564,10 @@ pub struct RefName {
     /// Local ref is synchronized with all tracking remotes, or tracking remote
     /// ref is synchronized with the local.
     synced: bool,
+    /// Local ref is archived.
+    archived: bool,
+    /// Remote configured for pushing the local ref.
+    push_remote: Option<String>,
 }
 
 #[derive(Debug)]
@@ -1170,6 +1590,18 @@ impl RefName {
         name: impl Into<String>,
         target: RefTarget,
         remote_refs: impl IntoIterator<Item = &'a RemoteRef>,
+    ) -> Rc<Self> {
+        Self::local_with_metadata(name, target, remote_refs, false, None)
+    }
+
+    /// Creates local ref representation which might be archived, or be
+    /// configured to be pushed to a specific remote.
+    pub fn local_with_metadata<'a>(
+        name: impl Into<String>,
+        target: RefTarget,
+        remote_refs: impl IntoIterator<Item = &'a RemoteRef>,
+        archived: bool,
+        push_remote: Option<String>,
     ) -> Rc<Self> {
         let synced = remote_refs
             .into_iter()
@@ -1180,6 +1612,8 @@ impl RefName {
             target,
             tracking_ref: None,
             synced,
+            archived,
+            push_remote,
         })
     }
 
@@ -1215,6 +1649,8 @@ impl RefName {
             target: remote_ref.target,
             tracking_ref,
             synced,
+            archived: false,
+            push_remote: None,
         })
     }
 
@@ -1230,6 +1666,8 @@ impl RefName {
             target,
             tracking_ref: None,
             synced: false, // has no local counterpart
+            archived: false,
+            push_remote: None,
         })
     }
 
@@ -1394,6 +1832,23 @@ fn builtin_ref_name_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, Rc
             Ok(L::wrap_commit_list(out_property))
         },
     );
+    map.insert(
+        "archived",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|ref_name| ref_name.archived);
+            Ok(L::wrap_boolean(out_property))
+        },
+    );
+    map.insert(
+        "push_remote",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property =
+                self_property.map(|ref_name| ref_name.push_remote.clone().unwrap_or_default());
+            Ok(L::wrap_string(out_property))
+        },
+    );
     map.insert(
         "tracked",
         |_language, _diagnostics, _build_ctx, self_property, function| {
@@ -1450,6 +1905,11 @@ impl RefNamesIndex {
     pub fn get(&self, id: &CommitId) -> &[Rc<RefName>] {
         self.index.get(id).map_or(&[], |names: &Vec<_>| names)
     }
+
+    /// Iterates commit ids and the refs pointing to them.
+    pub fn iter(&self) -> impl Iterator<Item = (&CommitId, &[Rc<RefName>])> {
+        self.index.iter().map(|(id, names)| (id, names.as_slice()))
+    }
 }
 
 fn build_bookmarks_index(repo: &dyn Repo) -> RefNamesIndex {
@@ -1457,7 +1917,9 @@ fn build_bookmarks_index(repo: &dyn Repo) -> RefNamesIndex {
     for (bookmark_name, bookmark_target) in repo.view().bookmarks() {
         let local_target = bookmark_target.local_target;
         let remote_refs = bookmark_target.remote_refs;
-        if local_target.is_present() {
+        // Archived bookmarks are hidden. Synchronized remote bookmarks will
+        // also be hidden by the "bookmarks" keyword.
+        if local_target.is_present() && !repo.view().is_bookmark_archived(bookmark_name) {
             let ref_name = RefName::local(
                 bookmark_name,
                 local_target.clone(),
@@ -1513,9 +1975,97 @@ fn builtin_repo_path_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, R
             Ok(L::wrap_repo_path_opt(out_property))
         },
     );
+    map.insert(
+        "file_name",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|path| {
+                path.components()
+                    .next_back()
+                    .map_or("", |name| name.as_internal_str())
+                    .to_owned()
+            });
+            Ok(L::wrap_string(out_property))
+        },
+    );
+    map.insert(
+        "extension",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|path| {
+                let name = path
+                    .components()
+                    .next_back()
+                    .map_or("", |name| name.as_internal_str());
+                match name.rsplit_once('.') {
+                    Some((stem, extension)) if !stem.is_empty() => extension.to_owned(),
+                    _ => String::new(),
+                }
+            });
+            Ok(L::wrap_string(out_property))
+        },
+    );
+    map.insert(
+        "relative_to",
+        |language, diagnostics, build_ctx, self_property, function| {
+            let [base_node] = function.expect_exact_arguments()?;
+            let base_property = template_builder::expect_plain_text_expression(
+                language,
+                diagnostics,
+                build_ctx,
+                base_node,
+            )?;
+            let out_property = (self_property, base_property).and_then(|(path, base)| {
+                let base = RepoPathBuf::from_relative_path(&base)
+                    .map_err(|err| TemplatePropertyError(err.into()))?;
+                Ok(relative_repo_path(&base, &path))
+            });
+            Ok(L::wrap_string(out_property))
+        },
+    );
+    map.insert(
+        "matches",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            let [pattern_node] = function.expect_exact_arguments()?;
+            let pattern =
+                template_parser::expect_string_literal_with(pattern_node, |pattern, span| {
+                    glob::Pattern::new(pattern).map_err(|err| {
+                        TemplateParseError::expression("Invalid glob pattern", span)
+                            .with_source(err)
+                    })
+                })?;
+            let options = glob::MatchOptions {
+                require_literal_separator: true,
+                ..Default::default()
+            };
+            let out_property = self_property
+                .map(move |path| pattern.matches_with(path.as_internal_file_string(), options));
+            Ok(L::wrap_boolean(out_property))
+        },
+    );
     map
 }
 
+/// Formats `path` relative to the `base` directory, using `/` as separator.
+fn relative_repo_path(base: &RepoPath, path: &RepoPath) -> String {
+    let common_len = base
+        .components()
+        .zip(path.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let parents = base.components().skip(common_len).map(|_| "..");
+    let children = path
+        .components()
+        .skip(common_len)
+        .map(|name| name.as_internal_str());
+    let relative = parents.chain(children).join("/");
+    if relative.is_empty() {
+        ".".to_owned()
+    } else {
+        relative
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum CommitOrChangeId {
     Commit(CommitId),
@@ -1616,29 +2166,58 @@ fn builtin_commit_or_change_id_methods<'repo>(
                 })
                 .transpose()?;
             let repo = language.repo;
-            let index = match language.id_prefix_context.populate(repo) {
-                Ok(index) => index,
-                Err(err) => {
-                    // Not an error because we can still produce somewhat
-                    // reasonable output.
-                    diagnostics.add_warning(
-                        TemplateParseError::expression(
-                            "Failed to load short-prefixes index",
-                            function.name_span,
-                        )
-                        .with_source(err),
-                    );
-                    IdPrefixIndex::empty()
-                }
-            };
+            let index = populate_id_prefix_index(language, diagnostics, function);
             let out_property = (self_property, len_property)
                 .map(move |(id, len)| id.shortest(repo, &index, len.unwrap_or(0)));
             Ok(L::wrap_shortest_id_prefix(out_property))
         },
     );
+    map.insert(
+        "abbreviated_len",
+        |language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let len = abbreviated_id_len(language.repo, language.settings()).map_err(|err| {
+                TemplateParseError::expression("Failed to get ID length", function.name_span)
+                    .with_source(err)
+            })?;
+            let out_property = self_property.and_then(move |_| Ok(len.try_into()?));
+            Ok(L::wrap_integer(out_property))
+        },
+    );
     map
 }
 
+fn populate_id_prefix_index<'repo>(
+    language: &CommitTemplateLanguage<'repo>,
+    diagnostics: &mut TemplateDiagnostics,
+    function: &FunctionCallNode,
+) -> IdPrefixIndex<'repo> {
+    match language.id_prefix_context.populate(language.repo) {
+        Ok(index) => index,
+        Err(err) => {
+            // Not an error because we can still produce somewhat
+            // reasonable output.
+            diagnostics.add_warning(
+                TemplateParseError::expression(
+                    "Failed to load short-prefixes index",
+                    function.name_span,
+                )
+                .with_source(err),
+            );
+            IdPrefixIndex::empty()
+        }
+    }
+}
+
+/// Returns the minimum length of abbreviated IDs, which is estimated from the
+/// number of commits in the index and clamped to the configured range.
+fn abbreviated_id_len(repo: &dyn Repo, settings: &UserSettings) -> Result<usize, ConfigGetError> {
+    let min_len: usize = settings.get("ui.short-id-min-length")?;
+    let max_len: usize = settings.get("ui.short-id-max-length")?;
+    let len = id_prefix::estimate_unambiguous_prefix_len(repo.index().num_commits());
+    Ok(len.min(max_len).max(min_len))
+}
+
 pub struct ShortestIdPrefix {
     pub prefix: String,
     pub rest: String,
@@ -1708,6 +2287,44 @@ fn builtin_shortest_id_prefix_methods<'repo>(
     map
 }
 
+// Commits are usually rendered one after another, so only the diffs of the
+// last few commits are worth keeping.
+const TREE_DIFF_CACHE_CAPACITY: usize = 16;
+
+/// Results of [`TreeDiff`]s shared by the `diff()` calls with the same commit
+/// and fileset.
+#[derive(Debug)]
+pub struct TreeDiffCacheMap {
+    caches: RefCell<CLruCache<(CommitId, FilesetExpression), Rc<TreeDiffCache>>>,
+}
+
+impl Default for TreeDiffCacheMap {
+    fn default() -> Self {
+        TreeDiffCacheMap {
+            caches: RefCell::new(CLruCache::new(TREE_DIFF_CACHE_CAPACITY.try_into().unwrap())),
+        }
+    }
+}
+
+impl TreeDiffCacheMap {
+    fn get(&self, commit: &Commit, files: &FilesetExpression) -> Rc<TreeDiffCache> {
+        let key = (commit.id().clone(), files.clone());
+        let mut caches = self.caches.borrow_mut();
+        if let Some(cache) = caches.get(&key) {
+            return cache.clone();
+        }
+        let cache = Rc::new(TreeDiffCache::default());
+        caches.put(key, cache.clone());
+        cache
+    }
+}
+
+#[derive(Debug, Default)]
+struct TreeDiffCache {
+    entries: OnceCell<Vec<TreeDiffEntry>>,
+    stats: OnceCell<DiffStats>,
+}
+
 /// Pair of trees to be diffed.
 #[derive(Debug)]
 pub struct TreeDiff {
@@ -1715,6 +2332,7 @@ pub struct TreeDiff {
     to_tree: MergedTree,
     matcher: Rc<dyn Matcher>,
     copy_records: CopyRecords,
+    cache: Rc<TreeDiffCache>,
 }
 
 impl TreeDiff {
@@ -1722,6 +2340,7 @@ impl TreeDiff {
         repo: &dyn Repo,
         commit: &Commit,
         matcher: Rc<dyn Matcher>,
+        cache: Rc<TreeDiffCache>,
     ) -> BackendResult<Self> {
         let mut copy_records = CopyRecords::default();
         for parent in commit.parent_ids() {
@@ -1734,6 +2353,7 @@ impl TreeDiff {
             to_tree: commit.tree()?,
             matcher,
             copy_records,
+            cache,
         })
     }
 
@@ -1742,11 +2362,25 @@ impl TreeDiff {
             .diff_stream_with_copies(&self.to_tree, &*self.matcher, &self.copy_records)
     }
 
-    async fn collect_entries(&self) -> BackendResult<Vec<TreeDiffEntry>> {
-        self.diff_stream()
-            .map(TreeDiffEntry::from_backend_entry_with_copies)
-            .try_collect()
-            .await
+    fn entries(&self) -> BackendResult<&[TreeDiffEntry]> {
+        let entries = self.cache.entries.get_or_try_init(|| {
+            self.diff_stream()
+                .map(TreeDiffEntry::from_backend_entry_with_copies)
+                .try_collect()
+                .block_on()
+        })?;
+        Ok(entries)
+    }
+
+    /// Calculates the stats with the default options. The result is cached.
+    fn stats(&self, conflict_marker_style: ConflictMarkerStyle) -> BackendResult<&DiffStats> {
+        self.cache.stats.get_or_try_init(|| {
+            // No user configuration exists for diff stat.
+            let options = diff_util::DiffStatOptions::default();
+            let store = self.from_tree.store();
+            DiffStats::calculate(store, self.diff_stream(), &options, conflict_marker_style)
+                .block_on()
+        })
     }
 
     fn into_formatted<F, E>(self, show: F) -> TreeDiffFormatted<F>
@@ -1786,9 +2420,7 @@ fn builtin_tree_diff_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, T
         "files",
         |_language, _diagnostics, _build_ctx, self_property, function| {
             function.expect_no_arguments()?;
-            // TODO: cache and reuse diff entries within the current evaluation?
-            let out_property =
-                self_property.and_then(|diff| Ok(diff.collect_entries().block_on()?));
+            let out_property = self_property.and_then(|diff| Ok(diff.entries()?.to_vec()));
             Ok(L::wrap_tree_diff_entry_list(out_property))
         },
     );
@@ -1889,15 +2521,9 @@ fn builtin_tree_diff_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, T
                 })
                 .transpose()?;
             let path_converter = language.path_converter;
-            // No user configuration exists for diff stat.
-            let options = diff_util::DiffStatOptions::default();
             let conflict_marker_style = language.conflict_marker_style;
-            // TODO: cache and reuse stats within the current evaluation?
             let out_property = (self_property, width_property).and_then(move |(diff, width)| {
-                let store = diff.from_tree.store();
-                let tree_diff = diff.diff_stream();
-                let stats = DiffStats::calculate(store, tree_diff, &options, conflict_marker_style)
-                    .block_on()?;
+                let stats = diff.stats(conflict_marker_style)?.clone();
                 Ok(DiffStatsFormatted {
                     stats,
                     path_converter,
@@ -2100,7 +2726,14 @@ fn builtin_diff_stats_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo,
     // Not using maplit::hashmap!{} or custom declarative macro here because
     // code completion inside macro is quite restricted.
     let mut map = CommitTemplateBuildMethodFnMap::<DiffStats>::new();
-    // TODO: add files() -> List<DiffStatEntry> ?
+    map.insert(
+        "files",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|stats| stats.entries().to_vec());
+            Ok(L::wrap_diff_stat_entry_list(out_property))
+        },
+    );
     map.insert(
         "total_added",
         |_language, _diagnostics, _build_ctx, self_property, function| {
@@ -2122,6 +2755,39 @@ fn builtin_diff_stats_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo,
     map
 }
 
+fn builtin_diff_stat_entry_methods<'repo>() -> CommitTemplateBuildMethodFnMap<'repo, DiffStatEntry>
+{
+    type L<'repo> = CommitTemplateLanguage<'repo>;
+    // Not using maplit::hashmap!{} or custom declarative macro here because
+    // code completion inside macro is quite restricted.
+    let mut map = CommitTemplateBuildMethodFnMap::<DiffStatEntry>::new();
+    map.insert(
+        "path",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|entry| entry.path.target);
+            Ok(L::wrap_repo_path(out_property))
+        },
+    );
+    map.insert(
+        "added",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.and_then(|entry| Ok(entry.added.try_into()?));
+            Ok(L::wrap_integer(out_property))
+        },
+    );
+    map.insert(
+        "removed",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.and_then(|entry| Ok(entry.removed.try_into()?));
+            Ok(L::wrap_integer(out_property))
+        },
+    );
+    map
+}
+
 #[derive(Debug)]
 pub struct CryptographicSignature {
     commit: Commit,
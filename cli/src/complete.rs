@@ -78,6 +78,28 @@ pub fn local_bookmarks() -> Vec<CompletionCandidate> {
     })
 }
 
+pub fn archived_bookmarks() -> Vec<CompletionCandidate> {
+    with_jj(|jj, _| {
+        let output = jj
+            .build()
+            .arg("bookmark")
+            .arg("list")
+            .arg("--include-archived")
+            .arg("--config")
+            .arg(BOOKMARK_HELP_TEMPLATE)
+            .arg("--template")
+            .arg(r#"if(!remote && archived, name ++ bookmark_help() ++ "\n")"#)
+            .output()
+            .map_err(user_error)?;
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(split_help_text)
+            .map(|(name, help)| CompletionCandidate::new(name).help(help))
+            .collect())
+    })
+}
+
 pub fn tracked_bookmarks() -> Vec<CompletionCandidate> {
     with_jj(|jj, _| {
         let output = jj
@@ -20,6 +20,7 @@ use std::fmt;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::Command;
+use std::sync::OnceLock;
 
 use itertools::Itertools;
 use jj_lib::config::ConfigFile;
@@ -40,6 +41,8 @@ use tracing::instrument;
 use crate::command_error::config_error;
 use crate::command_error::config_error_with_message;
 use crate::command_error::CommandError;
+use crate::theme;
+use crate::theme::Background;
 
 // TODO(#879): Consider generating entire schema dynamically vs. static file.
 pub const CONFIG_SCHEMA: &str = include_str!("config-schema.json");
@@ -264,6 +267,7 @@ pub struct ConfigEnv {
     user_config_path: ConfigPath,
     repo_config_path: ConfigPath,
     command: Option<String>,
+    terminal_background: OnceLock<Background>,
 }
 
 impl ConfigEnv {
@@ -283,6 +287,7 @@ impl ConfigEnv {
             user_config_path: env.resolve()?,
             repo_config_path: ConfigPath::Unavailable,
             command: None,
+            terminal_background: OnceLock::new(),
         })
     }
 
@@ -406,7 +411,9 @@ impl ConfigEnv {
             repo_path: self.repo_path.as_deref(),
             command: self.command.as_deref(),
         };
-        jj_lib::config::resolve(config.as_ref(), &context)
+        let mut config = jj_lib::config::resolve(config.as_ref(), &context)?;
+        theme::add_theme_layer(&mut config, &self.terminal_background)?;
+        Ok(config)
     }
 }
 
@@ -1396,6 +1403,7 @@ mod tests {
                 user_config_path: env.resolve()?,
                 repo_config_path: ConfigPath::Unavailable,
                 command: None,
+                terminal_background: OnceLock::new(),
             })
         }
 
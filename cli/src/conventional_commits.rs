@@ -0,0 +1,81 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Revset function selecting commits by the type of their description in the
+//! [Conventional Commits](https://www.conventionalcommits.org/) format.
+//!
+//! Types are compared case-insensitively, like the types checked by `jj
+//! describe` if `conventional-commits.enabled` is set.
+
+use std::any::Any;
+use std::rc::Rc;
+
+use jj_lib::commit::Commit;
+use jj_lib::conventional_commit::ConventionalCommit;
+use jj_lib::revset;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::revset::RevsetExtensions;
+use jj_lib::revset::RevsetFilterExtension;
+use jj_lib::revset::RevsetFilterPredicate;
+use jj_lib::revset::RevsetParseError;
+use jj_lib::str_util::StringPattern;
+
+/// Registers the `type()` revset function.
+pub(crate) fn add_revset_functions(extensions: &mut RevsetExtensions) {
+    extensions.add_custom_function("type", |diagnostics, function, _context| {
+        let [arg] = function.expect_exact_arguments()?;
+        let pattern = revset::expect_string_pattern(diagnostics, arg)?;
+        let pattern = to_case_insensitive(pattern).map_err(|err| {
+            RevsetParseError::expression(format!("Invalid string pattern: {err}"), arg.span)
+        })?;
+        Ok(RevsetExpression::filter(RevsetFilterPredicate::Extension(
+            Rc::new(ConventionalCommitTypeFilter { pattern }),
+        )))
+    });
+}
+
+/// Returns the case-insensitive variant of the `pattern`.
+fn to_case_insensitive(pattern: StringPattern) -> Result<StringPattern, regex::Error> {
+    Ok(match pattern {
+        StringPattern::Exact(literal) => StringPattern::ExactI(literal),
+        StringPattern::Substring(needle) => StringPattern::SubstringI(needle),
+        StringPattern::Glob(pattern) => StringPattern::GlobI(pattern),
+        StringPattern::Regex(pattern) => {
+            let pattern = regex::RegexBuilder::new(pattern.as_str())
+                .case_insensitive(true)
+                .build()?;
+            StringPattern::Regex(pattern)
+        }
+        pattern @ (StringPattern::ExactI(_)
+        | StringPattern::SubstringI(_)
+        | StringPattern::GlobI(_)) => pattern,
+    })
+}
+
+/// Matches commits whose conventional commit type matches the pattern.
+#[derive(Debug)]
+struct ConventionalCommitTypeFilter {
+    pattern: StringPattern,
+}
+
+impl RevsetFilterExtension for ConventionalCommitTypeFilter {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn matches_commit(&self, commit: &Commit) -> bool {
+        ConventionalCommit::parse(commit.description())
+            .is_some_and(|parsed| self.pattern.matches(parsed.kind))
+    }
+}
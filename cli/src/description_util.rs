@@ -14,15 +14,24 @@ use itertools::Itertools;
 use jj_lib::backend::CommitId;
 use jj_lib::commit::Commit;
 use jj_lib::config::ConfigGetError;
+use jj_lib::conventional_commit::ConventionalCommit;
 use jj_lib::file_util::IoResultExt as _;
 use jj_lib::file_util::PathError;
+use jj_lib::matchers::EverythingMatcher;
+use jj_lib::repo::Repo as _;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::revset::RevsetIteratorExt as _;
 use jj_lib::settings::UserSettings;
 use thiserror::Error;
 
 use crate::cli_util::short_commit_hash;
 use crate::cli_util::WorkspaceCommandTransaction;
+use crate::command_error::user_error_with_hint;
 use crate::command_error::CommandError;
 use crate::config::CommandNameAndArgs;
+use crate::diff_util::DiffFormat;
+use crate::diff_util::DiffRenderer;
+use crate::diff_util::UnifiedDiffOptions;
 use crate::formatter::PlainTextFormatter;
 use crate::text_util;
 use crate::ui::Ui;
@@ -352,10 +361,149 @@ pub fn description_template(
     template
         .format(commit, &mut PlainTextFormatter::new(&mut output))
         .expect("write() to vec backed formatter should never fail");
+    if tx.settings().get_bool("conventional-commits.enabled")? {
+        write_conventional_commit_hints(&mut output, tx, commit)?;
+    }
+    write_description_diff(ui, &mut output, tx, commit)?;
     // Template output is usually UTF-8, but it can contain file content.
     Ok(output.into_string_lossy())
 }
 
+/// Writes the diff of the `commit` below a "JJ: ignore-rest" line as
+/// configured by `ui.description-diff`, so it is removed from the edited
+/// description.
+fn write_description_diff(
+    ui: &Ui,
+    output: &mut Vec<u8>,
+    tx: &WorkspaceCommandTransaction,
+    commit: &Commit,
+) -> Result<(), CommandError> {
+    let settings = tx.settings();
+    let format = match settings.get_string("ui.description-diff")?.as_ref() {
+        "none" => return Ok(()),
+        "stat" => DiffFormat::Stat(Box::default()),
+        "git" => DiffFormat::Git(Box::new(UnifiedDiffOptions::from_settings(settings)?)),
+        name => {
+            return Err(ConfigGetError::Type {
+                name: "ui.description-diff".to_owned(),
+                error: format!("Invalid diff format: {name}").into(),
+                source_path: None,
+            }
+            .into());
+        }
+    };
+    let helper = tx.base_workspace_helper();
+    let renderer = DiffRenderer::new(
+        tx.repo(),
+        helper.path_converter(),
+        helper.env().conflict_marker_style(),
+        vec![format],
+    );
+    writeln!(output, "JJ: ignore-rest").unwrap();
+    renderer.show_patch(
+        ui,
+        &mut PlainTextFormatter::new(&mut *output),
+        commit,
+        &EverythingMatcher,
+        ui.term_width(),
+    )?;
+    Ok(())
+}
+
+/// Number of ancestors to look up scopes of conventional commits in.
+const CONVENTIONAL_COMMIT_SCOPE_LOOKUP_LIMIT: usize = 100;
+
+/// Writes "JJ: " comment lines listing the allowed conventional commit types
+/// and the scopes used by recent ancestors of the `commit`.
+fn write_conventional_commit_hints(
+    output: &mut Vec<u8>,
+    tx: &WorkspaceCommandTransaction,
+    commit: &Commit,
+) -> Result<(), CommandError> {
+    let types: Vec<String> = tx.settings().get("conventional-commits.types")?;
+    let repo = tx.repo();
+    let ancestors = RevsetExpression::commits(commit.parent_ids().to_vec())
+        .ancestors()
+        .evaluate(repo)?;
+    let mut scopes = vec![];
+    for ancestor in ancestors
+        .iter()
+        .commits(repo.store())
+        .take(CONVENTIONAL_COMMIT_SCOPE_LOOKUP_LIMIT)
+    {
+        let ancestor = ancestor?;
+        if let Some(scope) =
+            ConventionalCommit::parse(ancestor.description()).and_then(|parsed| parsed.scope)
+        {
+            scopes.push(scope.to_owned());
+        }
+    }
+    scopes.sort();
+    scopes.dedup();
+
+    writeln!(
+        output,
+        "JJ: Conventional commit format: <type>(<scope>)!: <subject>"
+    )
+    .unwrap();
+    if !types.is_empty() {
+        writeln!(output, "JJ: Types: {}", types.join(", ")).unwrap();
+    }
+    if !scopes.is_empty() {
+        writeln!(output, "JJ: Recent scopes: {}", scopes.join(", ")).unwrap();
+    }
+    Ok(())
+}
+
+/// Checks that the new description follows the conventional commit format if
+/// `conventional-commits.enabled` is set. Empty descriptions are accepted.
+pub fn check_conventional_commit(
+    settings: &UserSettings,
+    description: &str,
+) -> Result<(), CommandError> {
+    if description.is_empty() || !settings.get_bool("conventional-commits.enabled")? {
+        return Ok(());
+    }
+    let types: Vec<String> = settings.get("conventional-commits.types")?;
+    let Some(parsed) = ConventionalCommit::parse(description) else {
+        let subject = description.lines().next().unwrap_or_default();
+        return Err(user_error_with_hint(
+            format!("Description is not in the conventional commit format: {subject}"),
+            "Use the format `<type>(<scope>): <subject>`, for example `fix(cli): handle empty \
+             input`.",
+        ));
+    };
+    if !types.is_empty()
+        && !types
+            .iter()
+            .any(|kind| kind.eq_ignore_ascii_case(parsed.kind))
+    {
+        return Err(user_error_with_hint(
+            format!("Unknown conventional commit type `{}`", parsed.kind),
+            format!("Use one of the types: {}", types.join(", ")),
+        ));
+    }
+    Ok(())
+}
+
+/// Like [`check_conventional_commit()`], but for a description edited in the
+/// `editor`. A rejected description is saved to a file so that it isn't lost.
+pub fn check_edited_conventional_commit(
+    editor: &TextEditor,
+    settings: &UserSettings,
+    description: &str,
+) -> Result<(), CommandError> {
+    check_conventional_commit(settings, description).map_err(|err| {
+        match editor.write_temp_file(description.as_bytes(), Some(".jjdescription")) {
+            Ok(path) => err.hinted(format!(
+                "Edited description is left in {path}",
+                path = path.display()
+            )),
+            Err(_) => err,
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use indexmap::indexmap;
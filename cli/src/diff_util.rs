@@ -36,6 +36,7 @@ use jj_lib::commit::Commit;
 use jj_lib::config::ConfigGetError;
 use jj_lib::config::ConfigGetResultExt as _;
 use jj_lib::conflicts::materialize_merge_result_to_bytes;
+use jj_lib::conflicts::materialize_tree_value;
 use jj_lib::conflicts::materialized_diff_stream;
 use jj_lib::conflicts::ConflictMarkerStyle;
 use jj_lib::conflicts::MaterializedTreeDiffEntry;
@@ -55,6 +56,7 @@ use jj_lib::diff::DiffHunkKind;
 use jj_lib::files::DiffLineHunkSide;
 use jj_lib::files::DiffLineIterator;
 use jj_lib::files::DiffLineNumber;
+use jj_lib::matchers::FilesMatcher;
 use jj_lib::matchers::Matcher;
 use jj_lib::merge::MergedTreeValue;
 use jj_lib::merged_tree::MergedTree;
@@ -62,6 +64,7 @@ use jj_lib::object_id::ObjectId;
 use jj_lib::repo::Repo;
 use jj_lib::repo_path::InvalidRepoPathError;
 use jj_lib::repo_path::RepoPath;
+use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::repo_path::RepoPathUiConverter;
 use jj_lib::rewrite::rebase_to_dest_parent;
 use jj_lib::settings::UserSettings;
@@ -143,6 +146,21 @@ pub enum DiffFormat {
     Tool(Box<ExternalMergeTool>),
 }
 
+/// How to show changes in merge commits.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DiffMergesMode {
+    /// Compare to the automatic merge of the parents
+    #[default]
+    Remerge,
+    /// Compare to the first parent
+    FirstParent,
+    /// Show only changes that differ from every parent, as a combined diff
+    /// against all parents
+    Combined,
+    /// Don't show changes in merge commits
+    None,
+}
+
 /// Returns a list of requested diff formats, which will never be empty.
 pub fn diff_formats_for(
     settings: &UserSettings,
@@ -332,87 +350,106 @@ impl<'a> DiffRenderer<'a> {
         matcher: &dyn Matcher,
         copy_records: &CopyRecords,
         width: usize,
+    ) -> Result<(), DiffRenderError> {
+        for format in &self.formats {
+            self.show_diff_format(
+                ui,
+                formatter,
+                format,
+                from_tree,
+                to_tree,
+                matcher,
+                copy_records,
+                width,
+            )?;
+        }
+        Ok(())
+    }
+
+    #[expect(clippy::too_many_arguments)]
+    fn show_diff_format(
+        &self,
+        ui: &Ui,
+        formatter: &mut dyn Formatter,
+        format: &DiffFormat,
+        from_tree: &MergedTree,
+        to_tree: &MergedTree,
+        matcher: &dyn Matcher,
+        copy_records: &CopyRecords,
+        width: usize,
     ) -> Result<(), DiffRenderError> {
         let store = self.repo.store();
         let path_converter = self.path_converter;
-        for format in &self.formats {
-            match format {
-                DiffFormat::Summary => {
-                    let tree_diff =
-                        from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
-                    show_diff_summary(formatter, tree_diff, path_converter)?;
-                }
-                DiffFormat::Stat(options) => {
-                    let tree_diff =
-                        from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
-                    let stats =
-                        DiffStats::calculate(store, tree_diff, options, self.conflict_marker_style)
-                            .block_on()?;
-                    show_diff_stats(formatter, &stats, path_converter, width)?;
-                }
-                DiffFormat::Types => {
-                    let tree_diff =
-                        from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
-                    show_types(formatter, tree_diff, path_converter)?;
-                }
-                DiffFormat::NameOnly => {
-                    let tree_diff =
-                        from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
-                    show_names(formatter, tree_diff, path_converter)?;
-                }
-                DiffFormat::Git(options) => {
-                    let tree_diff =
-                        from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
-                    show_git_diff(
-                        formatter,
-                        store,
-                        tree_diff,
-                        options,
-                        self.conflict_marker_style,
-                    )?;
-                }
-                DiffFormat::ColorWords(options) => {
-                    let tree_diff =
-                        from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
-                    show_color_words_diff(
-                        formatter,
-                        store,
-                        tree_diff,
-                        path_converter,
-                        options,
-                        self.conflict_marker_style,
-                    )?;
-                }
-                DiffFormat::Tool(tool) => {
-                    match tool.diff_invocation_mode {
-                        DiffToolMode::FileByFile => {
-                            let tree_diff =
-                                from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
-                            show_file_by_file_diff(
-                                ui,
-                                formatter,
-                                store,
-                                tree_diff,
-                                path_converter,
-                                tool,
-                                self.conflict_marker_style,
-                            )
-                        }
-                        DiffToolMode::Dir => {
-                            let mut writer = formatter.raw()?;
-                            generate_diff(
-                                ui,
-                                writer.as_mut(),
-                                from_tree,
-                                to_tree,
-                                matcher,
-                                tool,
-                                self.conflict_marker_style,
-                            )
-                            .map_err(DiffRenderError::DiffGenerate)
-                        }
-                    }?;
-                }
+        match format {
+            DiffFormat::Summary => {
+                let tree_diff = from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
+                show_diff_summary(formatter, tree_diff, path_converter)?;
+            }
+            DiffFormat::Stat(options) => {
+                let tree_diff = from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
+                let stats =
+                    DiffStats::calculate(store, tree_diff, options, self.conflict_marker_style)
+                        .block_on()?;
+                show_diff_stats(formatter, &stats, path_converter, width)?;
+            }
+            DiffFormat::Types => {
+                let tree_diff = from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
+                show_types(formatter, tree_diff, path_converter)?;
+            }
+            DiffFormat::NameOnly => {
+                let tree_diff = from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
+                show_names(formatter, tree_diff, path_converter)?;
+            }
+            DiffFormat::Git(options) => {
+                let tree_diff = from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
+                show_git_diff(
+                    formatter,
+                    store,
+                    tree_diff,
+                    options,
+                    self.conflict_marker_style,
+                )?;
+            }
+            DiffFormat::ColorWords(options) => {
+                let tree_diff = from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
+                show_color_words_diff(
+                    formatter,
+                    store,
+                    tree_diff,
+                    path_converter,
+                    options,
+                    self.conflict_marker_style,
+                )?;
+            }
+            DiffFormat::Tool(tool) => {
+                match tool.diff_invocation_mode {
+                    DiffToolMode::FileByFile => {
+                        let tree_diff =
+                            from_tree.diff_stream_with_copies(to_tree, matcher, copy_records);
+                        show_file_by_file_diff(
+                            ui,
+                            formatter,
+                            store,
+                            tree_diff,
+                            path_converter,
+                            tool,
+                            self.conflict_marker_style,
+                        )
+                    }
+                    DiffToolMode::Dir => {
+                        let mut writer = formatter.raw()?;
+                        generate_diff(
+                            ui,
+                            writer.as_mut(),
+                            from_tree,
+                            to_tree,
+                            matcher,
+                            tool,
+                            self.conflict_marker_style,
+                        )
+                        .map_err(DiffRenderError::DiffGenerate)
+                    }
+                }?;
             }
         }
         Ok(())
@@ -470,6 +507,118 @@ impl<'a> DiffRenderer<'a> {
             width,
         )
     }
+
+    /// Generates diff of the given `commit` compared to its parents, showing
+    /// merge commits as specified by `diff_merges`.
+    pub fn show_patch_with_diff_merges(
+        &self,
+        ui: &Ui,
+        formatter: &mut dyn Formatter,
+        commit: &Commit,
+        matcher: &dyn Matcher,
+        width: usize,
+        diff_merges: DiffMergesMode,
+    ) -> Result<(), DiffRenderError> {
+        if commit.parent_ids().len() < 2 {
+            return self.show_patch(ui, formatter, commit, matcher, width);
+        }
+        match diff_merges {
+            DiffMergesMode::Remerge => self.show_patch(ui, formatter, commit, matcher, width),
+            DiffMergesMode::FirstParent => {
+                let parent_id = &commit.parent_ids()[0];
+                let from_tree = self.repo.store().get_commit(parent_id)?.tree()?;
+                let to_tree = commit.tree()?;
+                let mut copy_records = CopyRecords::default();
+                let records = get_copy_records(self.repo.store(), parent_id, commit.id(), matcher)?;
+                copy_records.add_records(records)?;
+                self.show_diff(
+                    ui,
+                    formatter,
+                    &from_tree,
+                    &to_tree,
+                    matcher,
+                    &copy_records,
+                    width,
+                )
+            }
+            DiffMergesMode::Combined => formatter.with_label("diff", |formatter| {
+                self.show_combined_patch(ui, formatter, commit, matcher, width)
+            }),
+            DiffMergesMode::None => Ok(()),
+        }
+    }
+
+    /// Generates diff of the merge `commit` limited to the paths that differ
+    /// from every parent. Patch formats are shown as a combined diff against
+    /// all parents, and other formats compare to the first parent.
+    fn show_combined_patch(
+        &self,
+        ui: &Ui,
+        formatter: &mut dyn Formatter,
+        commit: &Commit,
+        matcher: &dyn Matcher,
+        width: usize,
+    ) -> Result<(), DiffRenderError> {
+        let store = self.repo.store();
+        let parent_trees: Vec<_> = commit
+            .parents()
+            .map(|parent| parent?.tree())
+            .try_collect()?;
+        let to_tree = commit.tree()?;
+        let mut paths = vec![];
+        for entry in block_on_stream(parent_trees[0].diff_stream(&to_tree, matcher)) {
+            let (_, value) = entry.values?;
+            let changed_from_all = parent_trees[1..]
+                .iter()
+                .map(|tree| tree.path_value(&entry.path))
+                .process_results(|mut values| values.all(|parent_value| parent_value != value))?;
+            if changed_from_all {
+                paths.push(entry.path);
+            }
+        }
+        let paths_matcher = FilesMatcher::new(&paths);
+        for format in &self.formats {
+            match format {
+                DiffFormat::Git(options) => {
+                    show_combined_diff(
+                        formatter,
+                        store,
+                        &parent_trees,
+                        &to_tree,
+                        &paths,
+                        options.context,
+                        &options.line_diff,
+                        self.conflict_marker_style,
+                    )?;
+                }
+                DiffFormat::ColorWords(options) => {
+                    show_combined_diff(
+                        formatter,
+                        store,
+                        &parent_trees,
+                        &to_tree,
+                        &paths,
+                        options.context,
+                        &options.line_diff,
+                        self.conflict_marker_style,
+                    )?;
+                }
+                _ => {
+                    self.show_diff_format(
+                        ui,
+                        formatter,
+                        format,
+                        &parent_trees[0],
+                        &to_tree,
+                        &paths_matcher,
+                        &CopyRecords::default(),
+                        width,
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 pub fn get_copy_records<'a>(
@@ -1599,6 +1748,271 @@ pub fn show_git_diff(
     .block_on()
 }
 
+/// A line of a combined diff and the parents it differs from.
+struct CombinedDiffLine<'content> {
+    line_type: DiffLineType,
+    /// For removed lines, whether the line was removed from each parent. For
+    /// other lines, whether the line was added compared to each parent.
+    parents: Vec<bool>,
+    content: &'content [u8],
+}
+
+/// Computes the lines of the combined diff of `parent_contents` and
+/// `content`, with the removed lines placed before the line of `content`
+/// they precede.
+fn combined_diff_lines<'content>(
+    parent_contents: &[&'content [u8]],
+    content: &'content [u8],
+    options: &LineDiffOptions,
+) -> (
+    Vec<CombinedDiffLine<'content>>,
+    Vec<Vec<CombinedDiffLine<'content>>>,
+) {
+    let num_parents = parent_contents.len();
+    let mut lines = content
+        .split_inclusive(|b| *b == b'\n')
+        .map(|line| CombinedDiffLine {
+            line_type: DiffLineType::Context,
+            parents: vec![false; num_parents],
+            content: line,
+        })
+        .collect_vec();
+    let mut removed: Vec<Vec<CombinedDiffLine>> =
+        iter::repeat_with(Vec::new).take(lines.len() + 1).collect();
+    for (parent_index, parent_content) in parent_contents.iter().enumerate() {
+        let diff = diff_by_line([*parent_content, content], options);
+        let mut line_index = 0;
+        for hunk in diff.hunks() {
+            let [left, right] = hunk.contents[..].try_into().unwrap();
+            let num_right_lines = right.split_inclusive(|b| *b == b'\n').count();
+            if hunk.kind == DiffHunkKind::Different {
+                // Share the removed line with other parents if they removed the
+                // same line at the same place.
+                let slot = &mut removed[line_index];
+                let mut next_index = 0;
+                for left_line in left.split_inclusive(|b| *b == b'\n') {
+                    let existing = slot[next_index..]
+                        .iter()
+                        .position(|line| line.content == left_line && !line.parents[parent_index]);
+                    if let Some(offset) = existing {
+                        next_index += offset;
+                    } else {
+                        next_index = slot.len();
+                        slot.push(CombinedDiffLine {
+                            line_type: DiffLineType::Removed,
+                            parents: vec![false; num_parents],
+                            content: left_line,
+                        });
+                    }
+                    slot[next_index].parents[parent_index] = true;
+                    next_index += 1;
+                }
+                for line in &mut lines[line_index..line_index + num_right_lines] {
+                    line.line_type = DiffLineType::Added;
+                    line.parents[parent_index] = true;
+                }
+            }
+            line_index += num_right_lines;
+        }
+    }
+    (lines, removed)
+}
+
+/// Shows the hunks of the combined diff of `parent_contents` and `content`.
+/// Hunks which are the same as in one of the parents are omitted. Returns
+/// `false` if there were no hunks to show.
+fn show_combined_diff_hunks(
+    formatter: &mut dyn Formatter,
+    parent_contents: &[&[u8]],
+    content: &[u8],
+    context: usize,
+    options: &LineDiffOptions,
+    mut write_header: impl FnMut(&mut dyn Formatter) -> io::Result<()>,
+) -> io::Result<bool> {
+    fn to_line_number(start: usize, len: usize) -> usize {
+        if len == 0 {
+            start
+        } else {
+            start + 1
+        }
+    }
+
+    let num_parents = parent_contents.len();
+    let (lines, removed) = combined_diff_lines(parent_contents, content, options);
+    // Ranges of content lines with the changes and their context. The removed
+    // lines before the end of a range belong to the range.
+    let mut ranges: Vec<Range<usize>> = vec![];
+    for (index, slot) in removed.iter().enumerate() {
+        let is_added = lines
+            .get(index)
+            .is_some_and(|line| line.line_type == DiffLineType::Added);
+        if slot.is_empty() && !is_added {
+            continue;
+        }
+        let start = index.saturating_sub(context);
+        let end = (index + usize::from(is_added) + context).min(lines.len());
+        match ranges.last_mut() {
+            Some(last) if start <= last.end => last.end = end,
+            _ => ranges.push(start..end),
+        }
+    }
+
+    let mut has_hunks = false;
+    for range in ranges {
+        let slots = &removed[range.start..=range.end];
+        let hunk_lines = &lines[range.clone()];
+        // Skip hunks taken from one of the parents as is.
+        let differs_from_all =
+            (0..num_parents).all(|parent_index| {
+                slots.iter().flatten().chain(hunk_lines).any(|line| {
+                    line.line_type != DiffLineType::Context && line.parents[parent_index]
+                })
+            });
+        if !differs_from_all {
+            continue;
+        }
+        if !has_hunks {
+            write_header(formatter)?;
+            has_hunks = true;
+        }
+
+        write!(
+            formatter.labeled("hunk_header"),
+            "{}",
+            "@".repeat(num_parents + 1)
+        )?;
+        for parent_index in 0..num_parents {
+            let count_lines = |lines: &[CombinedDiffLine], removed: &[Vec<CombinedDiffLine>]| {
+                let kept = lines
+                    .iter()
+                    .filter(|line| !line.parents[parent_index])
+                    .count();
+                let removed = removed
+                    .iter()
+                    .flatten()
+                    .filter(|line| line.parents[parent_index])
+                    .count();
+                kept + removed
+            };
+            let start = count_lines(&lines[..range.start], &removed[..range.start]);
+            let len = count_lines(hunk_lines, slots);
+            write!(
+                formatter.labeled("hunk_header"),
+                " -{},{}",
+                to_line_number(start, len),
+                len
+            )?;
+        }
+        writeln!(
+            formatter.labeled("hunk_header"),
+            " +{},{} {}",
+            to_line_number(range.start, range.len()),
+            range.len(),
+            "@".repeat(num_parents + 1)
+        )?;
+
+        let hunk_lines = slots
+            .iter()
+            .zip(hunk_lines.iter().map(Some).chain([None]))
+            .flat_map(|(slot, line)| slot.iter().chain(line));
+        for line in hunk_lines {
+            let (label, sigil) = match line.line_type {
+                DiffLineType::Context => ("context", ' '),
+                DiffLineType::Removed => ("removed", '-'),
+                DiffLineType::Added => ("added", '+'),
+            };
+            let sigils: String = line
+                .parents
+                .iter()
+                .map(|&differs| if differs { sigil } else { ' ' })
+                .collect();
+            formatter.with_label(label, |formatter| {
+                write!(formatter, "{sigils}")?;
+                formatter.write_all(line.content)
+            })?;
+            if !line.content.ends_with(b"\n") {
+                write!(formatter, "\n\\ No newline at end of file\n")?;
+            }
+        }
+    }
+    Ok(has_hunks)
+}
+
+/// Shows a Git-style combined diff of `paths` between `parent_trees` and
+/// `to_tree`.
+#[expect(clippy::too_many_arguments)]
+fn show_combined_diff(
+    formatter: &mut dyn Formatter,
+    store: &Store,
+    parent_trees: &[MergedTree],
+    to_tree: &MergedTree,
+    paths: &[RepoPathBuf],
+    context: usize,
+    options: &LineDiffOptions,
+    conflict_marker_style: ConflictMarkerStyle,
+) -> Result<(), DiffRenderError> {
+    let git_diff_part_at = |tree: &MergedTree, path: &RepoPath| {
+        let value = materialize_tree_value(store, path, tree.path_value(path)?).block_on()?;
+        git_diff_part(path, value, conflict_marker_style)
+    };
+    for path in paths {
+        let path_string = path.as_internal_file_string();
+        let parent_parts: Vec<_> = parent_trees
+            .iter()
+            .map(|tree| git_diff_part_at(tree, path))
+            .try_collect()?;
+        let part = git_diff_part_at(to_tree, path)?;
+
+        let write_header = |formatter: &mut dyn Formatter| {
+            formatter.with_label("file_header", |formatter| {
+                writeln!(formatter, "diff --cc {path_string}")?;
+                let modes = parent_parts
+                    .iter()
+                    .chain([&part])
+                    .map(|part| part.mode.unwrap_or("000000"))
+                    .collect_vec();
+                let parent_hashes = parent_parts.iter().map(|part| &part.hash).join(",");
+                if modes.iter().all_equal() {
+                    writeln!(formatter, "index {parent_hashes}..{}", part.hash)?;
+                } else {
+                    let (mode, parent_modes) = modes.split_last().unwrap();
+                    writeln!(formatter, "mode {}..{mode}", parent_modes.join(","))?;
+                    writeln!(formatter, "index {parent_hashes}..{}", part.hash)?;
+                }
+                if parent_parts.iter().all(|part| part.mode.is_none()) {
+                    writeln!(formatter, "--- /dev/null")?;
+                } else {
+                    writeln!(formatter, "--- a/{path_string}")?;
+                }
+                match part.mode {
+                    Some(_) => writeln!(formatter, "+++ b/{path_string}"),
+                    None => writeln!(formatter, "+++ /dev/null"),
+                }
+            })
+        };
+        if part.content.is_binary || parent_parts.iter().any(|part| part.content.is_binary) {
+            formatter.with_label("file_header", |formatter| {
+                writeln!(formatter, "diff --cc {path_string}")
+            })?;
+            writeln!(formatter, "Binary files differ")?;
+            continue;
+        }
+        let parent_contents = parent_parts
+            .iter()
+            .map(|part| part.content.contents.as_slice())
+            .collect_vec();
+        show_combined_diff_hunks(
+            formatter,
+            &parent_contents,
+            &part.content.contents,
+            context,
+            options,
+            write_header,
+        )?;
+    }
+    Ok(())
+}
+
 #[instrument(skip_all)]
 pub fn show_diff_summary(
     formatter: &mut dyn Formatter,
@@ -1609,18 +2023,57 @@ pub fn show_diff_summary(
         while let Some(CopiesTreeDiffEntry { path, values }) = tree_diff.next().await {
             let (before, after) = values?;
             let (label, sigil) = diff_status_label_and_char(&path, &before, &after);
+            let url = file_url(path_converter, path.target());
             let path = if path.copy_operation().is_some() {
                 path_converter.format_copied_path(path.source(), path.target())
             } else {
                 path_converter.format_file_path(path.target())
             };
-            writeln!(formatter.labeled(label), "{sigil} {path}")?;
+            formatter.with_label(label, |formatter| {
+                write!(formatter, "{sigil} ")?;
+                formatter.with_hyperlink(&url, |formatter| write!(formatter, "{path}"))?;
+                writeln!(formatter)
+            })?;
         }
         Ok(())
     }
     .block_on()
 }
 
+/// Returns `file://` URL pointing to the workspace file.
+fn file_url(path_converter: &RepoPathUiConverter, path: &RepoPath) -> String {
+    match path_converter {
+        RepoPathUiConverter::Fs { base, .. } => {
+            let fs_path = path.to_fs_path_unchecked(base);
+            let fs_path = encode_url_path(&fs_path.to_string_lossy());
+            if std::path::MAIN_SEPARATOR == '/' {
+                format!("file://{fs_path}")
+            } else {
+                // Windows path such as "C:\\foo" should be "file:///C:/foo"
+                format!(
+                    "file:///{}",
+                    fs_path.replace(std::path::MAIN_SEPARATOR, "/")
+                )
+            }
+        }
+    }
+}
+
+/// Percent-encodes the characters that would otherwise end or escape the path
+/// part of a URL. Other characters are encoded when the URL is written out.
+fn encode_url_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for c in path.chars() {
+        match c {
+            '%' => encoded.push_str("%25"),
+            '#' => encoded.push_str("%23"),
+            '?' => encoded.push_str("%3F"),
+            _ => encoded.push(c),
+        }
+    }
+    encoded
+}
+
 pub fn diff_status_label_and_char(
     path: &CopiesTreeDiffEntryPath,
     before: &MergedTreeValue,
@@ -1778,9 +2231,11 @@ pub fn show_diff_stats(
         // replace start of path with ellipsis if the path is too long
         let (path, path_width) = text_util::elide_start(ui_path, "...", max_path_width);
         let path_pad_width = max_path_width - path_width;
+        let url = file_url(path_converter, stat.path.target());
+        formatter.with_hyperlink(&url, |formatter| write!(formatter, "{path}"))?;
         write!(
             formatter,
-            "{path}{:path_pad_width$} | {:>number_padding$}{}",
+            "{:path_pad_width$} | {:>number_padding$}{}",
             "", // pad to max_path_width
             stat.added + stat.removed,
             if bar_added + bar_removed > 0 { " " } else { "" },
@@ -1813,13 +2268,23 @@ pub fn show_types(
     async {
         while let Some(CopiesTreeDiffEntry { path, values }) = tree_diff.next().await {
             let (before, after) = values?;
-            writeln!(
-                formatter.labeled("modified"),
-                "{}{} {}",
-                diff_summary_char(&before),
-                diff_summary_char(&after),
-                path_converter.format_copied_path(path.source(), path.target())
-            )?;
+            let url = file_url(path_converter, path.target());
+            formatter.with_label("modified", |formatter| {
+                write!(
+                    formatter,
+                    "{}{} ",
+                    diff_summary_char(&before),
+                    diff_summary_char(&after),
+                )?;
+                formatter.with_hyperlink(&url, |formatter| {
+                    write!(
+                        formatter,
+                        "{}",
+                        path_converter.format_copied_path(path.source(), path.target())
+                    )
+                })?;
+                writeln!(formatter)
+            })?;
         }
         Ok(())
     }
@@ -1846,11 +2311,31 @@ pub fn show_names(
 ) -> io::Result<()> {
     async {
         while let Some(CopiesTreeDiffEntry { path, .. }) = tree_diff.next().await {
-            writeln!(
-                formatter,
-                "{}",
-                path_converter.format_file_path(path.target())
-            )?;
+            let url = file_url(path_converter, path.target());
+            formatter.with_hyperlink(&url, |formatter| {
+                write!(
+                    formatter,
+                    "{}",
+                    path_converter.format_file_path(path.target())
+                )
+            })?;
+            writeln!(formatter)?;
+        }
+        Ok(())
+    }
+    .block_on()
+}
+
+/// Prints the paths of the changed files, each terminated with a NUL byte.
+pub fn show_names_null_terminated(
+    formatter: &mut dyn Formatter,
+    mut tree_diff: BoxStream<CopiesTreeDiffEntry>,
+    path_converter: &RepoPathUiConverter,
+) -> io::Result<()> {
+    async {
+        while let Some(CopiesTreeDiffEntry { path, .. }) = tree_diff.next().await {
+            let ui_path = path_converter.format_file_path(path.target());
+            write!(formatter, "{ui_path}\0")?;
         }
         Ok(())
     }
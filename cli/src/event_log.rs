@@ -0,0 +1,90 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Machine-readable event stream for tools wrapping the CLI.
+
+use std::io;
+use std::io::Write;
+use std::sync::Mutex;
+
+use serde_json::Value;
+
+/// Destination of the events enabled by `--event-log`.
+///
+/// Each event is written as a JSON object on a single line. The `event` field
+/// of the object names the event.
+pub struct EventLog {
+    output: Mutex<Box<dyn Write + Send>>,
+}
+
+impl EventLog {
+    pub fn new(output: Box<dyn Write + Send>) -> Self {
+        EventLog {
+            output: Mutex::new(output),
+        }
+    }
+
+    /// Opens the file descriptor inherited from the parent process.
+    ///
+    /// The descriptor is opened again through `/dev/fd`, which fails if it
+    /// isn't open, so the descriptor itself is never closed by us. Events are
+    /// appended if it refers to a regular file.
+    #[cfg(unix)]
+    pub fn from_fd(fd: i32) -> io::Result<Self> {
+        use std::fs::OpenOptions;
+
+        let bad_fd = || io::Error::from_raw_os_error(libc::EBADF);
+        if fd < 0 {
+            return Err(bad_fd());
+        }
+        let file = OpenOptions::new()
+            .append(true)
+            .open(format!("/dev/fd/{fd}"))
+            .map_err(|err| match err.kind() {
+                io::ErrorKind::NotFound => bad_fd(),
+                _ => err,
+            })?;
+        Ok(Self::new(Box::new(file)))
+    }
+
+    #[cfg(not(unix))]
+    pub fn from_fd(_fd: i32) -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "file descriptors are only supported on Unix",
+        ))
+    }
+
+    /// Writes event of the given name. `data` should be a JSON object, whose
+    /// fields are merged into the event object.
+    pub fn emit(&self, event: &str, data: Value) -> io::Result<()> {
+        let mut object = serde_json::Map::new();
+        object.insert("event".to_owned(), event.into());
+        debug_assert!(
+            data.is_object() || data.is_null(),
+            "event data should be an object: {data}"
+        );
+        match data {
+            Value::Object(fields) => object.extend(fields),
+            Value::Null => {}
+            // Don't fail the command in release builds.
+            data => {
+                object.insert("data".to_owned(), data);
+            }
+        }
+        let mut output = self.output.lock().unwrap();
+        writeln!(output, "{}", Value::Object(object))?;
+        output.flush()
+    }
+}
@@ -15,6 +15,7 @@
 use std::borrow::BorrowMut;
 use std::collections::HashMap;
 use std::fmt;
+use std::fmt::Write as _;
 use std::io;
 use std::io::Error;
 use std::io::Write;
@@ -44,6 +45,12 @@ pub trait Formatter: Write {
     fn push_label(&mut self, label: &str) -> io::Result<()>;
 
     fn pop_label(&mut self) -> io::Result<()>;
+
+    /// Starts linking the subsequent output to the given `url`. The link
+    /// may be ignored if the formatter doesn't support hyperlinks.
+    fn push_hyperlink(&mut self, url: &str) -> io::Result<()>;
+
+    fn pop_hyperlink(&mut self) -> io::Result<()>;
 }
 
 impl dyn Formatter + '_ {
@@ -64,6 +71,15 @@ impl dyn Formatter + '_ {
         // its error replace the one from `write_inner()`.
         write_inner(self).and(self.pop_label().map_err(Into::into))
     }
+
+    pub fn with_hyperlink<E: From<io::Error>>(
+        &mut self,
+        url: &str,
+        write_inner: impl FnOnce(&mut dyn Formatter) -> Result<(), E>,
+    ) -> Result<(), E> {
+        self.push_hyperlink(url)?;
+        write_inner(self).and(self.pop_hyperlink().map_err(Into::into))
+    }
 }
 
 /// `Formatter` wrapper to write a labeled message with `write!()` or
@@ -149,7 +165,11 @@ pub struct FormatterFactory {
 enum FormatterFactoryKind {
     PlainText,
     Sanitized,
-    Color { rules: Arc<Rules>, debug: bool },
+    Color {
+        rules: Arc<Rules>,
+        debug: bool,
+        hyperlinks: bool,
+    },
 }
 
 impl FormatterFactory {
@@ -165,7 +185,12 @@ impl FormatterFactory {
 
     pub fn color(config: &StackedConfig, debug: bool) -> Result<Self, ConfigGetError> {
         let rules = Arc::new(rules_from_config(config)?);
-        let kind = FormatterFactoryKind::Color { rules, debug };
+        let hyperlinks = config.get("ui.hyperlinks")?;
+        let kind = FormatterFactoryKind::Color {
+            rules,
+            debug,
+            hyperlinks,
+        };
         Ok(FormatterFactory { kind })
     }
 
@@ -176,9 +201,13 @@ impl FormatterFactory {
         match &self.kind {
             FormatterFactoryKind::PlainText => Box::new(PlainTextFormatter::new(output)),
             FormatterFactoryKind::Sanitized => Box::new(SanitizingFormatter::new(output)),
-            FormatterFactoryKind::Color { rules, debug } => {
-                Box::new(ColorFormatter::new(output, rules.clone(), *debug))
-            }
+            FormatterFactoryKind::Color {
+                rules,
+                debug,
+                hyperlinks,
+            } => Box::new(
+                ColorFormatter::new(output, rules.clone(), *debug).with_hyperlinks(*hyperlinks),
+            ),
         }
     }
 
@@ -219,6 +248,14 @@ impl<W: Write> Formatter for PlainTextFormatter<W> {
     fn pop_label(&mut self) -> io::Result<()> {
         Ok(())
     }
+
+    fn push_hyperlink(&mut self, _url: &str) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn pop_hyperlink(&mut self) -> io::Result<()> {
+        Ok(())
+    }
 }
 
 pub struct SanitizingFormatter<W> {
@@ -254,6 +291,204 @@ impl<W: Write> Formatter for SanitizingFormatter<W> {
     fn pop_label(&mut self) -> io::Result<()> {
         Ok(())
     }
+
+    fn push_hyperlink(&mut self, _url: &str) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn pop_hyperlink(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Formatter that writes HTML-escaped text. Labels are translated to
+/// `<span class="..">` elements, which can be styled by the style sheet
+/// generated by [`html_style_sheet()`].
+pub struct HtmlFormatter<W: Write> {
+    output: W,
+    /// The stack of (opening, closing) tags of the currently applied labels
+    /// and hyperlinks.
+    elements: Vec<(String, &'static str)>,
+    /// The number of elements we've opened in the output.
+    opened_count: usize,
+}
+
+impl<W: Write> HtmlFormatter<W> {
+    pub fn new(output: W) -> HtmlFormatter<W> {
+        Self {
+            output,
+            elements: vec![],
+            opened_count: 0,
+        }
+    }
+
+    fn open_elements(&mut self) -> io::Result<()> {
+        for (open_tag, _) in &self.elements[self.opened_count..] {
+            self.output.write_all(open_tag.as_bytes())?;
+        }
+        self.opened_count = self.elements.len();
+        Ok(())
+    }
+
+    fn close_elements(&mut self) -> io::Result<()> {
+        for (_, close_tag) in self.elements[..self.opened_count].iter().rev() {
+            self.output.write_all(close_tag.as_bytes())?;
+        }
+        self.opened_count = 0;
+        Ok(())
+    }
+
+    fn pop_element(&mut self) -> io::Result<()> {
+        let Some((_, close_tag)) = self.elements.pop() else {
+            return Ok(());
+        };
+        if self.opened_count > self.elements.len() {
+            self.output.write_all(close_tag.as_bytes())?;
+            self.opened_count = self.elements.len();
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for HtmlFormatter<W> {
+    fn write(&mut self, data: &[u8]) -> Result<usize, Error> {
+        // Like ColorFormatter, elements are closed at the end of each line so
+        // that the lines can be decorated (e.g. by graph) independently.
+        for line in data.split_inclusive(|b| *b == b'\n') {
+            let (content, newline) = match line.strip_suffix(b"\n") {
+                Some(content) => (content, true),
+                None => (line, false),
+            };
+            if !content.is_empty() {
+                self.open_elements()?;
+                write_html_escaped(&mut self.output, content)?;
+            }
+            if newline {
+                self.close_elements()?;
+                self.output.write_all(b"\n")?;
+            }
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        self.output.flush()
+    }
+}
+
+impl<W: Write> Formatter for HtmlFormatter<W> {
+    fn raw(&mut self) -> io::Result<Box<dyn Write + '_>> {
+        Ok(Box::new(self.output.by_ref()))
+    }
+
+    fn push_label(&mut self, label: &str) -> io::Result<()> {
+        let open_tag = format!(r#"<span class="{}">"#, html_class_name(label));
+        self.elements.push((open_tag, "</span>"));
+        Ok(())
+    }
+
+    fn pop_label(&mut self) -> io::Result<()> {
+        self.pop_element()
+    }
+
+    fn push_hyperlink(&mut self, url: &str) -> io::Result<()> {
+        let mut open_tag = r#"<a href=""#.as_bytes().to_vec();
+        write_html_escaped(&mut open_tag, url.as_bytes())?;
+        open_tag.extend_from_slice(br#"">"#);
+        let open_tag = String::from_utf8(open_tag).unwrap();
+        self.elements.push((open_tag, "</a>"));
+        Ok(())
+    }
+
+    fn pop_hyperlink(&mut self) -> io::Result<()> {
+        self.pop_element()
+    }
+}
+
+impl<W: Write> Drop for HtmlFormatter<W> {
+    fn drop(&mut self) {
+        self.close_elements().ok();
+    }
+}
+
+/// Builds CSS rules that colorize the output of [`HtmlFormatter`] as
+/// configured in the `colors` table.
+pub fn html_style_sheet(config: &StackedConfig) -> Result<String, ConfigGetError> {
+    let mut css = String::new();
+    for (labels, style) in rules_from_config(config)? {
+        let selector = labels
+            .iter()
+            .map(|label| format!(".{}", html_class_name(label)))
+            .join(" ");
+        let mut declarations = vec![];
+        if let Some(color) = style.fg.and_then(css_color) {
+            declarations.push(format!("color: {color}"));
+        }
+        if let Some(color) = style.bg.and_then(css_color) {
+            declarations.push(format!("background-color: {color}"));
+        }
+        if let Some(bold) = style.bold {
+            declarations.push(format!(
+                "font-weight: {}",
+                if bold { "bold" } else { "normal" }
+            ));
+        }
+        if let Some(italic) = style.italic {
+            declarations.push(format!(
+                "font-style: {}",
+                if italic { "italic" } else { "normal" }
+            ));
+        }
+        if let Some(underline) = style.underline {
+            declarations.push(format!(
+                "text-decoration: {}",
+                if underline { "underline" } else { "none" }
+            ));
+        }
+        if !declarations.is_empty() {
+            writeln!(css, "{selector} {{ {}; }}", declarations.join("; ")).unwrap();
+        }
+    }
+    Ok(css)
+}
+
+/// Maps label to CSS class name by replacing unsupported characters.
+fn html_class_name(label: &str) -> String {
+    label
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+fn css_color(color: Color) -> Option<String> {
+    let name = match color {
+        Color::Reset => "inherit",
+        Color::Black => "black",
+        Color::DarkRed => "maroon",
+        Color::DarkGreen => "green",
+        Color::DarkYellow => "olive",
+        Color::DarkBlue => "navy",
+        Color::DarkMagenta => "purple",
+        Color::DarkCyan => "teal",
+        Color::Grey => "silver",
+        Color::DarkGrey => "gray",
+        Color::Red => "red",
+        Color::Green => "lime",
+        Color::Yellow => "yellow",
+        Color::Blue => "blue",
+        Color::Magenta => "fuchsia",
+        Color::Cyan => "aqua",
+        Color::White => "white",
+        Color::Rgb { r, g, b } => return Some(format!("#{r:02x}{g:02x}{b:02x}")),
+        Color::AnsiValue(_) => return None,
+    };
+    Some(name.to_owned())
 }
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize)]
@@ -291,6 +526,10 @@ pub struct ColorFormatter<W: Write> {
     /// The debug string (space-separated labels) we last wrote to the output.
     /// Initialize to None to turn debug strings off.
     current_debug: Option<String>,
+    /// Whether to emit OSC 8 hyperlinks.
+    hyperlinks: bool,
+    /// The stack of currently applied hyperlink URLs.
+    hyperlink_urls: Vec<String>,
 }
 
 impl<W: Write> ColorFormatter<W> {
@@ -302,9 +541,17 @@ impl<W: Write> ColorFormatter<W> {
             cached_styles: HashMap::new(),
             current_style: Style::default(),
             current_debug: debug.then(String::new),
+            hyperlinks: false,
+            hyperlink_urls: vec![],
         }
     }
 
+    /// Enables or disables emitting hyperlinks to the output.
+    pub fn with_hyperlinks(mut self, hyperlinks: bool) -> Self {
+        self.hyperlinks = hyperlinks;
+        self
+    }
+
     pub fn for_config(
         output: W,
         config: &StackedConfig,
@@ -572,6 +819,24 @@ impl<W: Write> Formatter for ColorFormatter<W> {
         }
         Ok(())
     }
+
+    fn push_hyperlink(&mut self, url: &str) -> io::Result<()> {
+        self.hyperlink_urls.push(url.to_owned());
+        if self.hyperlinks {
+            write_hyperlink_url(&mut self.output, url)?;
+        }
+        Ok(())
+    }
+
+    fn pop_hyperlink(&mut self) -> io::Result<()> {
+        self.hyperlink_urls.pop();
+        if self.hyperlinks {
+            // Hyperlinks can't be nested, so restore the outer link if any.
+            let url = self.hyperlink_urls.last().map_or("", |url| url);
+            write_hyperlink_url(&mut self.output, url)?;
+        }
+        Ok(())
+    }
 }
 
 impl<W: Write> Drop for ColorFormatter<W> {
@@ -580,10 +845,14 @@ impl<W: Write> Drop for ColorFormatter<W> {
         // because of an error), let's still try to reset any currently active style.
         self.labels.clear();
         self.write_new_style().ok();
+        if self.hyperlinks && !self.hyperlink_urls.is_empty() {
+            write_hyperlink_url(&mut self.output, "").ok();
+        }
     }
 }
 
-/// Like buffered formatter, but records `push`/`pop_label()` calls.
+/// Like buffered formatter, but records `push`/`pop_label()` and
+/// `push`/`pop_hyperlink()` calls.
 ///
 /// This allows you to manipulate the recorded data without losing labels.
 /// The recorded data and labels can be written to another formatter. If
@@ -601,6 +870,8 @@ enum FormatOp {
     PushLabel(String),
     PopLabel,
     RawEscapeSequence(Vec<u8>),
+    PushHyperlink(String),
+    PopHyperlink,
 }
 
 impl FormatRecorder {
@@ -651,6 +922,8 @@ impl FormatRecorder {
                 FormatOp::RawEscapeSequence(raw_escape_sequence) => {
                     formatter.raw()?.write_all(raw_escape_sequence)?;
                 }
+                FormatOp::PushHyperlink(url) => formatter.push_hyperlink(url)?,
+                FormatOp::PopHyperlink => formatter.pop_hyperlink()?,
             }
         }
         flush_data(formatter, self.data.len())
@@ -695,6 +968,50 @@ impl Formatter for FormatRecorder {
         self.push_op(FormatOp::PopLabel);
         Ok(())
     }
+
+    fn push_hyperlink(&mut self, url: &str) -> io::Result<()> {
+        self.push_op(FormatOp::PushHyperlink(url.to_owned()));
+        Ok(())
+    }
+
+    fn pop_hyperlink(&mut self) -> io::Result<()> {
+        self.push_op(FormatOp::PopHyperlink);
+        Ok(())
+    }
+}
+
+/// Writes OSC 8 escape sequence that starts linking to the `url`, or ends the
+/// current link if the `url` is empty.
+fn write_hyperlink_url(output: &mut impl Write, url: &str) -> io::Result<()> {
+    output.write_all(b"\x1b]8;;")?;
+    // Only printable ASCII characters are allowed in the URL.
+    for b in url.bytes() {
+        if (b'!'..=b'~').contains(&b) {
+            output.write_all(&[b])?;
+        } else {
+            write!(output, "%{b:02X}")?;
+        }
+    }
+    output.write_all(b"\x1b\\")
+}
+
+fn write_html_escaped(output: &mut impl Write, buf: &[u8]) -> io::Result<()> {
+    let mut last = 0;
+    for (i, b) in buf.iter().enumerate() {
+        let escaped = match b {
+            b'&' => "&amp;",
+            b'<' => "&lt;",
+            b'>' => "&gt;",
+            b'"' => "&quot;",
+            b'\'' => "&#39;",
+            b'\x1b' => "␛",
+            _ => continue,
+        };
+        output.write_all(&buf[last..i])?;
+        output.write_all(escaped.as_bytes())?;
+        last = i + 1;
+    }
+    output.write_all(&buf[last..])
 }
 
 fn write_sanitized(output: &mut impl Write, buf: &[u8]) -> Result<(), Error> {
@@ -1239,6 +1556,40 @@ mod tests {
             to_snapshot_string(output), @"[38;5;2m<<outer inner:: inside >>[39m[EOF]");
     }
 
+    #[test]
+    fn test_html_formatter() {
+        // Elements are closed at the end of each line, and reopened lazily.
+        let mut output: Vec<u8> = vec![];
+        let mut formatter = HtmlFormatter::new(&mut output);
+        formatter.push_hyperlink("/a?b&c").unwrap();
+        formatter.push_label("outer").unwrap();
+        write!(formatter, "<one>\ntwo\n").unwrap();
+        formatter.push_label("in ner").unwrap();
+        formatter.pop_label().unwrap();
+        formatter.pop_label().unwrap();
+        write!(formatter, "\x1b").unwrap();
+        drop(formatter);
+        insta::assert_snapshot!(to_snapshot_string(output), @r#"
+        <a href="/a?b&amp;c"><span class="outer">&lt;one&gt;</span></a>
+        <a href="/a?b&amp;c"><span class="outer">two</span></a>
+        <a href="/a?b&amp;c">␛</a>[EOF]
+        "#);
+    }
+
+    #[test]
+    fn test_html_style_sheet() {
+        let config = config_from_string(
+            r##"
+        colors."outer inner" = { fg = "green", bg = "#ff0000", bold = true }
+        colors.underlined = { underline = true, italic = true }
+        "##,
+        );
+        insta::assert_snapshot!(html_style_sheet(&config).unwrap(), @r"
+        .outer .inner { color: green; background-color: #ff0000; font-weight: bold; }
+        .underlined { font-style: italic; text-decoration: underline; }
+        ");
+    }
+
     #[test]
     fn test_heading_labeled_writer() {
         let config = config_from_string(
@@ -14,7 +14,9 @@
 
 //! Git utilities shared by various commands.
 
+use std::env;
 use std::error;
+use std::fs;
 use std::io;
 use std::io::Read;
 use std::io::Write;
@@ -22,13 +24,19 @@ use std::iter;
 use std::mem;
 use std::path::Path;
 use std::path::PathBuf;
+use std::process;
 use std::process::Stdio;
 use std::time::Duration;
 use std::time::Instant;
+use std::time::SystemTime;
 
 use crossterm::terminal::Clear;
 use crossterm::terminal::ClearType;
 use itertools::Itertools;
+use jj_lib::backend::MillisSinceEpoch;
+use jj_lib::backend::Timestamp;
+use jj_lib::commit::Commit;
+use jj_lib::config::ConfigGetError;
 use jj_lib::fmt_util::binary_prefix;
 use jj_lib::git;
 use jj_lib::git::FailedRefExport;
@@ -39,12 +47,17 @@ use jj_lib::op_store::RefTarget;
 use jj_lib::op_store::RemoteRef;
 use jj_lib::repo::ReadonlyRepo;
 use jj_lib::repo::Repo;
+use jj_lib::settings::UserSettings;
 use jj_lib::store::Store;
+use jj_lib::str_util::StringPattern;
 use jj_lib::workspace::Workspace;
 use unicode_width::UnicodeWidthStr;
 
 use crate::cleanup_guard::CleanupGuard;
+use crate::cli_util::short_commit_hash;
+use crate::cli_util::WorkspaceCommandHelper;
 use crate::command_error::cli_error;
+use crate::command_error::config_error;
 use crate::command_error::user_error;
 use crate::command_error::CommandError;
 use crate::formatter::Formatter;
@@ -56,6 +69,60 @@ pub(crate) fn get_git_repo(store: &Store) -> Result<git2::Repository, CommandErr
     Ok(git::get_git_backend(store)?.open_git_repo()?)
 }
 
+/// Remotes to push bookmarks to, as configured by `git.bookmark-push-remotes`.
+#[derive(Clone, Debug, Default)]
+pub struct BookmarkPushRemotes {
+    remotes: Vec<(String, Vec<StringPattern>)>,
+}
+
+impl BookmarkPushRemotes {
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, CommandError> {
+        const KEY: &str = "git.bookmark-push-remotes";
+        let remotes = settings
+            .table_keys(KEY)
+            .sorted()
+            .map(|remote| -> Result<_, CommandError> {
+                let name = ["git", "bookmark-push-remotes", remote];
+                let patterns = if let Ok(patterns) = settings.get::<Vec<String>>(name) {
+                    patterns
+                } else {
+                    vec![settings.get_string(name)?]
+                };
+                let patterns = patterns
+                    .iter()
+                    .map(|pattern| StringPattern::parse(pattern).map_err(config_error))
+                    .try_collect()?;
+                Ok((remote.to_owned(), patterns))
+            })
+            .try_collect()?;
+        Ok(BookmarkPushRemotes { remotes })
+    }
+
+    /// Returns the remote configured for pushing the bookmark, or `None` if
+    /// the default push remote should be used.
+    pub fn get(&self, bookmark_name: &str) -> Result<Option<&str>, CommandError> {
+        let matches = self
+            .remotes
+            .iter()
+            .filter(|(_, patterns)| {
+                patterns
+                    .iter()
+                    .any(|pattern| pattern.matches(bookmark_name))
+            })
+            .map(|(remote, _)| remote.as_str())
+            .collect_vec();
+        match matches[..] {
+            [] => Ok(None),
+            [remote] => Ok(Some(remote)),
+            _ => Err(config_error(format!(
+                "Bookmark {bookmark_name} matches `git.bookmark-push-remotes` of multiple \
+                 remotes: {}",
+                matches.join(", ")
+            ))),
+        }
+    }
+}
+
 pub fn is_colocated_git_workspace(workspace: &Workspace, repo: &ReadonlyRepo) -> bool {
     let Ok(git_backend) = git::get_git_backend(repo.store()) else {
         return false;
@@ -74,6 +141,126 @@ pub fn is_colocated_git_workspace(workspace: &Workspace, repo: &ReadonlyRepo) ->
     dunce::canonicalize(git_workdir).ok().as_deref() == dot_git_path.parent()
 }
 
+/// How `jj` fetches from Git remotes without being asked to, as configured by
+/// `fetch.auto`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AutoFetchMode {
+    /// Remotes are only fetched by `jj git fetch`.
+    Never,
+    /// `jj git fetch` is periodically spawned in the background.
+    Background,
+}
+
+impl AutoFetchMode {
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        settings.get("fetch.auto")
+    }
+}
+
+/// Spawns `jj git fetch` in the background if `fetch.auto` is `"background"`
+/// and no fetch has been spawned within `fetch.auto-interval-minutes`.
+///
+/// The spawned process isn't waited for, and its output is discarded.
+pub fn maybe_spawn_background_fetch(
+    ui: &Ui,
+    settings: &UserSettings,
+    workspace_root: &Path,
+    repo_path: &Path,
+) -> Result<(), CommandError> {
+    if AutoFetchMode::from_settings(settings)? != AutoFetchMode::Background {
+        return Ok(());
+    }
+    let interval_minutes: u64 = settings.get("fetch.auto-interval-minutes")?;
+    let interval = Duration::from_secs(interval_minutes.saturating_mul(60));
+    // The modification time of this file records when the last background
+    // fetch was spawned.
+    let stamp_path = repo_path.join("auto_fetch");
+    let elapsed = fs::metadata(&stamp_path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|time| time.elapsed().ok());
+    if elapsed.is_some_and(|elapsed| elapsed < interval) {
+        return Ok(());
+    }
+    // Update the stamp before spawning so concurrent commands don't start
+    // fetches of their own.
+    let result = fs::File::create(&stamp_path)
+        .and_then(|file| file.set_modified(SystemTime::now()))
+        .and_then(|()| spawn_background_fetch(workspace_root));
+    if let Err(err) = result {
+        writeln!(
+            ui.warning_default(),
+            "Failed to start fetching in the background: {err}"
+        )?;
+    }
+    Ok(())
+}
+
+fn spawn_background_fetch(workspace_root: &Path) -> io::Result<()> {
+    let mut cmd = process::Command::new(env::current_exe()?);
+    cmd.arg("--repository")
+        .arg(workspace_root)
+        // Don't snapshot the working copy concurrently with the command that
+        // spawned the fetch. This also prevents the fetch from spawning
+        // another one.
+        .args(["--ignore-working-copy", "--quiet", "git", "fetch"])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt as _;
+        // Don't let Ctrl-C in the terminal interrupt the fetch.
+        cmd.process_group(0);
+    }
+    cmd.spawn()?;
+    Ok(())
+}
+
+/// Records that the Git remotes were fetched at `time`.
+pub fn write_last_fetch_time(repo_path: &Path, time: &Timestamp) -> io::Result<()> {
+    let Timestamp {
+        timestamp: MillisSinceEpoch(millis),
+        tz_offset,
+    } = time;
+    fs::write(
+        repo_path.join("last_fetch"),
+        format!("{millis} {tz_offset}\n"),
+    )
+}
+
+/// Returns when the Git remotes were last fetched, if ever.
+pub fn read_last_fetch_time(repo_path: &Path) -> Option<Timestamp> {
+    let content = fs::read_to_string(repo_path.join("last_fetch")).ok()?;
+    let (millis, tz_offset) = content.trim_end().split_once(' ')?;
+    Some(Timestamp {
+        timestamp: MillisSinceEpoch(millis.parse().ok()?),
+        tz_offset: tz_offset.parse().ok()?,
+    })
+}
+
+/// Writes `templates.remote_staleness_notice` for the `commit` if remotes are
+/// fetched in the background.
+pub fn write_remote_staleness_notice(
+    ui: &Ui,
+    formatter: &mut dyn Formatter,
+    workspace_command: &WorkspaceCommandHelper,
+    commit: &Commit,
+) -> Result<(), CommandError> {
+    let settings = workspace_command.settings();
+    if AutoFetchMode::from_settings(settings)? != AutoFetchMode::Background {
+        return Ok(());
+    }
+    let template_text = settings.get_string("templates.remote_staleness_notice")?;
+    let template = workspace_command
+        .parse_commit_template(ui, &template_text)?
+        .labeled("remote_staleness_notice");
+    template.format(commit, formatter)?;
+    Ok(())
+}
+
 /// Parses user-specified remote URL or path to absolute form.
 pub fn absolute_git_url(cwd: &Path, source: &str) -> Result<String, CommandError> {
     // Git appears to turn URL-like source to absolute path if local git directory
@@ -325,6 +512,22 @@ pub fn print_git_import_stats(
         )?;
     }
 
+    if !stats.reconciled_commits.is_empty() {
+        writeln!(
+            formatter,
+            "Reconciled {} commits that were rewritten on the remote:",
+            stats.reconciled_commits.len()
+        )?;
+        for (old_id, new_id) in &stats.reconciled_commits {
+            writeln!(
+                formatter,
+                "  {} -> {}",
+                short_commit_hash(old_id),
+                short_commit_hash(new_id)
+            )?;
+        }
+    }
+
     Ok(())
 }
 
@@ -546,15 +749,20 @@ impl RefStatus {
         let pad_width = max_ref_name_width.saturating_sub(ref_name_display_width);
         let padded_ref_name = format!("{}{:>pad_width$}", self.ref_name, "", pad_width = pad_width);
 
-        let ref_kind = match self.ref_kind {
-            RefKind::Branch => "bookmark: ",
-            RefKind::Tag if !has_both_ref_kinds => "tag: ",
-            RefKind::Tag => "tag:    ",
+        let (ref_kind, ref_label) = match self.ref_kind {
+            RefKind::Branch => ("bookmark: ", "bookmark"),
+            RefKind::Tag if !has_both_ref_kinds => ("tag: ", "tag"),
+            RefKind::Tag => ("tag:      ", "tag"),
         };
 
         write!(out, "{ref_kind}")?;
-        write!(out.labeled("bookmark"), "{padded_ref_name}")?;
-        writeln!(out, " [{import_status}] {tracking_status}")
+        write!(out.labeled(ref_label), "{padded_ref_name}")?;
+        write!(out, " [{import_status}]")?;
+        if tracking_status.is_empty() {
+            writeln!(out)
+        } else {
+            writeln!(out, " {tracking_status}")
+        }
     }
 }
 
@@ -15,6 +15,7 @@
 use std::hash::Hash;
 use std::io;
 use std::io::Write;
+use std::rc::Rc;
 
 use jj_lib::config::ConfigGetError;
 use jj_lib::graph::GraphEdge;
@@ -24,6 +25,8 @@ use renderdag::Ancestor;
 use renderdag::GraphRowRenderer;
 use renderdag::Renderer;
 
+use crate::ui::Ui;
+
 pub trait GraphLog<K: Clone + Eq + Hash> {
     fn add_node(
         &mut self,
@@ -31,14 +34,69 @@ pub trait GraphLog<K: Clone + Eq + Hash> {
         edges: &[GraphEdge<K>],
         node_symbol: &str,
         text: &str,
+    ) -> io::Result<()> {
+        self.add_styled_node(id, edges, node_symbol, text, None)
+    }
+
+    /// Like `add_node()`, but the edges leading down from the node are drawn
+    /// with `edge_style`.
+    fn add_styled_node(
+        &mut self,
+        id: &K,
+        edges: &[GraphEdge<K>],
+        node_symbol: &str,
+        text: &str,
+        edge_style: Option<Rc<EdgeStyle>>,
     ) -> io::Result<()>;
 
     fn width(&self, id: &K, edges: &[GraphEdge<K>]) -> usize;
 }
 
+/// Escape sequences to write around the glyphs of graph edges.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EdgeStyle {
+    prefix: String,
+    suffix: String,
+}
+
+impl EdgeStyle {
+    /// Creates the style of the `"edge"` label combined with `labels`. Returns
+    /// `None` if the style wouldn't change the output.
+    pub fn new(ui: &Ui, labels: &[&str]) -> Option<Self> {
+        let mut output = vec![];
+        {
+            let mut formatter = ui.new_formatter(&mut output);
+            formatter.push_label("edge").ok()?;
+            for label in labels {
+                formatter.push_label(label).ok()?;
+            }
+            write!(formatter, "{NODE_PLACEHOLDER}").ok()?;
+            for _ in 0..=labels.len() {
+                formatter.pop_label().ok()?;
+            }
+        }
+        let output = String::from_utf8(output).ok()?;
+        let (prefix, suffix) = output.split_once(NODE_PLACEHOLDER)?;
+        if prefix.is_empty() && suffix.is_empty() {
+            return None;
+        }
+        Some(EdgeStyle {
+            prefix: prefix.to_owned(),
+            suffix: suffix.to_owned(),
+        })
+    }
+}
+
+/// Stands in for the node symbol while rendering, so the column of the node
+/// can be found.
+const NODE_PLACEHOLDER: char = '\0';
+
 pub struct SaplingGraphLog<'writer, R> {
     renderer: R,
     writer: &'writer mut dyn Write,
+    /// For each character position of the graph, `None` if there's no edge
+    /// drawn there, or the style of the edge.
+    edge_styles: Vec<Option<Option<Rc<EdgeStyle>>>>,
 }
 
 fn convert_graph_edge_into_ancestor<K: Clone>(e: &GraphEdge<K>) -> Ancestor<K> {
@@ -54,21 +112,90 @@ where
     K: Clone + Eq + Hash,
     R: Renderer<K, Output = String>,
 {
-    fn add_node(
+    fn add_styled_node(
         &mut self,
         id: &K,
         edges: &[GraphEdge<K>],
         node_symbol: &str,
         text: &str,
+        edge_style: Option<Rc<EdgeStyle>>,
     ) -> io::Result<()> {
+        let parents = edges.iter().map(convert_graph_edge_into_ancestor).collect();
+        // The last column of the width is the space before the text
+        let graph_width = usize::try_from(self.renderer.width(Some(id), Some(&parents)))
+            .unwrap()
+            .saturating_sub(1);
         let row = self.renderer.next_row(
             id.clone(),
-            edges.iter().map(convert_graph_edge_into_ancestor).collect(),
-            node_symbol.into(),
+            parents,
+            NODE_PLACEHOLDER.to_string(),
             text.into(),
         );
 
-        write!(self.writer, "{row}")
+        // Edges that start at or below the node line are drawn with the
+        // node's style until they end.
+        let mut output = String::with_capacity(row.len());
+        let mut seen_node = false;
+        for line in row.split_inclusive('\n') {
+            let is_node_line = !seen_node
+                && line
+                    .chars()
+                    .take(graph_width)
+                    .any(|c| c == NODE_PLACEHOLDER);
+            let line_graph_width = if seen_node || is_node_line {
+                graph_width
+            } else {
+                // Lines before the node line have no text
+                usize::MAX
+            };
+            let mut chars = line.chars().peekable();
+            let mut i = 0;
+            while let Some(c) = chars.next_if(|&c| c != '\n') {
+                if i >= line_graph_width {
+                    output.push(c);
+                    break;
+                }
+                if self.edge_styles.len() <= i {
+                    self.edge_styles.resize(i + 1, None);
+                }
+                if is_node_line && c == NODE_PLACEHOLDER {
+                    self.edge_styles[i] = Some(edge_style.clone());
+                    output.push_str(node_symbol);
+                } else if c == ' ' {
+                    self.edge_styles[i] = None;
+                    output.push(c);
+                } else {
+                    let style = self.edge_styles[i].get_or_insert_with(|| {
+                        if seen_node {
+                            edge_style.clone()
+                        } else {
+                            None
+                        }
+                    });
+                    match style {
+                        Some(style) => {
+                            output.push_str(&style.prefix);
+                            output.push(c);
+                            output.push_str(&style.suffix);
+                        }
+                        None => output.push(c),
+                    }
+                }
+                i += 1;
+            }
+            output.extend(chars);
+            // Edges past the end of the trimmed line have ended
+            let end = line_graph_width.min(self.edge_styles.len());
+            if i < end {
+                self.edge_styles[i..end].fill(None);
+            }
+            seen_node |= is_node_line;
+        }
+        if !seen_node {
+            output = output.replacen(NODE_PLACEHOLDER, node_symbol, 1);
+        }
+
+        write!(self.writer, "{output}")
     }
 
     fn width(&self, id: &K, edges: &[GraphEdge<K>]) -> usize {
@@ -90,6 +217,7 @@ impl<'writer, R> SaplingGraphLog<'writer, R> {
         Box::new(SaplingGraphLog {
             renderer,
             writer: formatter,
+            edge_styles: vec![],
         })
     }
 }
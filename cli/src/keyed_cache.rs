@@ -0,0 +1,247 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! On-disk cache of values keyed by commit or tree, such as test results and
+//! CI statuses.
+//!
+//! The caches can be made available to revset functions through the extension
+//! data of the parse context.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use jj_lib::extensions_map::ExtensionsMap;
+use jj_lib::revset::FunctionCallNode;
+use jj_lib::revset::RevsetParseContext;
+use jj_lib::revset::RevsetParseError;
+use tempfile::NamedTempFile;
+
+/// Value which can be stored in a [`KeyedCache`].
+pub trait CacheValue: Clone + Sized {
+    /// Serializes the value.
+    fn encode(&self) -> String;
+
+    /// Parses the serialized value. Returns `None` if it's malformed.
+    fn decode(data: &str) -> Option<Self>;
+}
+
+/// Stores values in a single file, which lists the hex-encoded key and the
+/// value of each entry on a separate line. Newlines and backslashes in the
+/// values are escaped.
+///
+/// The file is read once when a value is first looked up, and the loaded
+/// entries are kept up to date by the updates made through this cache.
+#[derive(Debug)]
+pub struct KeyedCache<V> {
+    path: PathBuf,
+    entries: Mutex<Option<HashMap<Vec<u8>, V>>>,
+}
+
+impl<V: CacheValue> KeyedCache<V> {
+    pub fn new(path: PathBuf) -> Self {
+        KeyedCache {
+            path,
+            entries: Mutex::new(None),
+        }
+    }
+
+    /// Looks up the last recorded value for the `key`.
+    pub fn get(&self, key: &[u8]) -> Option<V> {
+        let mut entries = self.entries.lock().unwrap();
+        entries
+            .get_or_insert_with(|| self.read().unwrap_or_default())
+            .get(key)
+            .cloned()
+    }
+
+    /// Records the values for the keys, replacing any previous values. `None`
+    /// removes the recorded value.
+    pub fn update(
+        &self,
+        updates: impl IntoIterator<Item = (Vec<u8>, Option<V>)>,
+    ) -> io::Result<()> {
+        // Re-read the file in case another process updated it.
+        let mut entries = self.read()?;
+        for (key, value) in updates {
+            if let Some(value) = value {
+                entries.insert(key, value);
+            } else {
+                entries.remove(&key);
+            }
+        }
+        let dir = self.path.parent().unwrap();
+        let mut temp_file = NamedTempFile::new_in(dir)?;
+        for (key, value) in &entries {
+            writeln!(
+                temp_file.as_file_mut(),
+                "{} {}",
+                hex::encode(key),
+                escape(&value.encode())
+            )?;
+        }
+        temp_file.persist(&self.path).map_err(|err| err.error)?;
+        *self.entries.lock().unwrap() = Some(entries);
+        Ok(())
+    }
+
+    /// Removes all recorded values.
+    pub fn clear(&self) -> io::Result<()> {
+        match fs::remove_file(&self.path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+        *self.entries.lock().unwrap() = Some(HashMap::new());
+        Ok(())
+    }
+
+    fn read(&self) -> io::Result<HashMap<Vec<u8>, V>> {
+        let data = match fs::read_to_string(&self.path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(HashMap::new()),
+            Err(err) => return Err(err),
+        };
+        // Malformed lines are ignored.
+        let entries = data
+            .lines()
+            .filter_map(|line| {
+                let (key, value) = line.split_once(' ')?;
+                Some((hex::decode(key).ok()?, V::decode(&unescape(value)?)?))
+            })
+            .collect();
+        Ok(entries)
+    }
+}
+
+fn escape(data: &str) -> String {
+    data.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(data: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(data.len());
+    let mut chars = data.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next()? {
+                '\\' => unescaped.push('\\'),
+                'n' => unescaped.push('\n'),
+                _ => return None,
+            }
+        } else {
+            unescaped.push(c);
+        }
+    }
+    Some(unescaped)
+}
+
+/// Makes the `cache` available to the revset functions.
+pub(crate) fn insert_revset_extension_data<T: Any + Send + Sync>(
+    data: &mut ExtensionsMap,
+    cache: T,
+) {
+    data.insert(Arc::new(cache));
+}
+
+/// Looks up the cache inserted by [`insert_revset_extension_data()`] for the
+/// revset `function`. The `description` of the cached values is used in the
+/// error message if the cache isn't available.
+pub(crate) fn get_revset_extension_data<T: Any + Send + Sync>(
+    function: &FunctionCallNode,
+    context: &RevsetParseContext,
+    description: &str,
+) -> Result<Arc<T>, RevsetParseError> {
+    context
+        .extension_data()
+        .and_then(|data| data.get::<Arc<T>>())
+        .cloned()
+        .ok_or_else(|| {
+            RevsetParseError::expression(
+                format!("{description} aren't available here"),
+                function.name_span,
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    impl CacheValue for String {
+        fn encode(&self) -> String {
+            self.clone()
+        }
+
+        fn decode(data: &str) -> Option<Self> {
+            Some(data.to_owned())
+        }
+    }
+
+    fn new_cache(path: &Path) -> KeyedCache<String> {
+        KeyedCache::new(path.to_owned())
+    }
+
+    #[test]
+    fn test_update_and_get() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("cache");
+        assert_eq!(new_cache(&path).get(b"a"), None);
+
+        new_cache(&path)
+            .update([
+                (b"a".to_vec(), Some("one".to_owned())),
+                (b"b".to_vec(), Some("two\nlines \\n".to_owned())),
+            ])
+            .unwrap();
+        let cache = new_cache(&path);
+        assert_eq!(cache.get(b"a").as_deref(), Some("one"));
+        assert_eq!(cache.get(b"b").as_deref(), Some("two\nlines \\n"));
+
+        // Values of other keys are kept
+        cache
+            .update([(b"a".to_vec(), Some("three".to_owned()))])
+            .unwrap();
+        assert_eq!(cache.get(b"a").as_deref(), Some("three"));
+        let cache = new_cache(&path);
+        assert_eq!(cache.get(b"a").as_deref(), Some("three"));
+        assert_eq!(cache.get(b"b").as_deref(), Some("two\nlines \\n"));
+
+        cache.update([(b"a".to_vec(), None)]).unwrap();
+        let cache = new_cache(&path);
+        assert_eq!(cache.get(b"a"), None);
+        assert_eq!(cache.get(b"b").as_deref(), Some("two\nlines \\n"));
+
+        cache.clear().unwrap();
+        assert_eq!(cache.get(b"b"), None);
+        assert_eq!(new_cache(&path).get(b"b"), None);
+    }
+
+    #[test]
+    fn test_malformed_lines() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("cache");
+        fs::write(&path, "61 one\nnot-hex two\n62\n63 bad\\escape\n").unwrap();
+        let cache = new_cache(&path);
+        assert_eq!(cache.get(b"a").as_deref(), Some("one"));
+        assert_eq!(cache.get(b"b"), None);
+        assert_eq!(cache.get(b"c"), None);
+    }
+}
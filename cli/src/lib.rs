@@ -14,15 +14,21 @@
 
 #![deny(unused_must_use)]
 
+pub mod alias_library;
+pub mod ci_provider;
+pub mod ci_status;
 pub mod cleanup_guard;
 pub mod cli_util;
 pub mod command_error;
 pub mod commands;
+pub mod commit_picker;
 pub mod commit_templater;
 pub mod complete;
 pub mod config;
+pub mod conventional_commits;
 pub mod description_util;
 pub mod diff_util;
+pub mod event_log;
 pub mod formatter;
 pub mod generic_templater;
 #[cfg(feature = "git")]
@@ -31,22 +37,35 @@ pub mod git_util;
 /// A stub module that provides a no-op implementation of some of the functions
 /// in the `git` module.
 pub mod git_util {
+    use std::path::Path;
+
+    use jj_lib::backend::Timestamp;
     use jj_lib::repo::ReadonlyRepo;
     use jj_lib::workspace::Workspace;
 
     pub fn is_colocated_git_workspace(_workspace: &Workspace, _repo: &ReadonlyRepo) -> bool {
         false
     }
+
+    pub fn read_last_fetch_time(_repo_path: &Path) -> Option<Timestamp> {
+        None
+    }
 }
 pub mod graphlog;
+mod keyed_cache;
 pub mod merge_tools;
 pub mod movement_util;
 pub mod operation_templater;
+mod pager_outline;
 mod progress;
 pub mod revset_util;
+pub mod secret_scan;
 pub mod template_builder;
 pub mod template_parser;
 pub mod templater;
+pub mod test_results;
 pub mod text_util;
+pub mod theme;
 pub mod time_util;
+mod tui;
 pub mod ui;
@@ -2,6 +2,7 @@ use std::borrow::Cow;
 use std::path::Path;
 use std::sync::Arc;
 
+use bstr::BString;
 use futures::StreamExt;
 use futures::TryFutureExt;
 use futures::TryStreamExt;
@@ -10,6 +11,7 @@ use jj_lib::backend::BackendResult;
 use jj_lib::backend::FileId;
 use jj_lib::backend::MergedTreeId;
 use jj_lib::backend::TreeValue;
+use jj_lib::conflicts;
 use jj_lib::conflicts::materialize_merge_result_to_bytes;
 use jj_lib::conflicts::materialize_tree_value;
 use jj_lib::conflicts::ConflictMarkerStyle;
@@ -651,6 +653,41 @@ fn make_merge_file(
     })
 }
 
+/// Collects the hunks of the merged file after editing. A conflict hunk in
+/// which some line was selected is resolved to the selected added lines and
+/// the unselected removed (base) lines. Conflict hunks in which no line was
+/// selected are left unresolved.
+fn get_merge_hunks(merge_result: MergeResult, file: &scm_record::File) -> Vec<Merge<BString>> {
+    let hunks = match merge_result {
+        MergeResult::Resolved(buf) => return vec![Merge::resolved(buf)],
+        MergeResult::Conflict(hunks) => hunks,
+    };
+    hunks
+        .into_iter()
+        .zip_eq(&file.sections)
+        .map(|(hunk, section)| {
+            if hunk.is_resolved() {
+                return hunk;
+            }
+            let scm_record::Section::Changed { lines } = section else {
+                panic!("conflicting hunk should be rendered as a changed section");
+            };
+            if lines.iter().all(|line| !line.is_checked) {
+                return hunk;
+            }
+            let contents: String = lines
+                .iter()
+                .filter(|line| match line.change_type {
+                    scm_record::ChangeType::Added => line.is_checked,
+                    scm_record::ChangeType::Removed => !line.is_checked,
+                })
+                .map(|line| line.line.as_ref())
+                .collect();
+            Merge::resolved(contents.into())
+        })
+        .collect()
+}
+
 pub fn edit_merge_builtin(
     tree: &MergedTree,
     merge_tool_files: &[MergeToolFile],
@@ -666,17 +703,27 @@ pub fn edit_merge_builtin(
     );
     let state = recorder.run()?;
 
-    apply_diff_builtin(
-        tree.store(),
-        tree,
-        tree,
-        merge_tool_files
-            .iter()
-            .map(|file| file.repo_path.clone())
-            .collect_vec(),
-        &state.files,
-    )
-    .map_err(BuiltinToolError::BackendError)
+    let store = tree.store();
+    let mut tree_builder = MergedTreeBuilder::new(tree.id());
+    for (merge_tool_file, file) in merge_tool_files.iter().zip_eq(&state.files) {
+        let merge_result = files::merge(&merge_tool_file.content);
+        let hunks = get_merge_hunks(merge_result, file);
+        let new_file_ids = conflicts::update_from_hunks(
+            &merge_tool_file.file_merge,
+            store,
+            &merge_tool_file.repo_path,
+            &hunks,
+        )
+        .block_on()
+        .map_err(BuiltinToolError::BackendError)?;
+        tree_builder.set_or_remove(
+            merge_tool_file.repo_path.clone(),
+            merge_tool_file.new_tree_value(new_file_ids),
+        );
+    }
+    tree_builder
+        .write_tree(store)
+        .map_err(BuiltinToolError::BackendError)
 }
 
 #[cfg(test)]
@@ -1148,4 +1195,70 @@ mod tests {
         ]
         "#);
     }
+
+    #[test]
+    fn test_edit_merge_builtin_partial_resolution() {
+        let test_repo = TestRepo::init();
+        let store = test_repo.repo.store();
+
+        let path = RepoPath::from_internal_string("file");
+        let base_tree = testutils::create_tree(
+            &test_repo.repo,
+            &[(path, "base 1\nbase 2\nbase 3\nbase 4\nbase 5\n")],
+        );
+        let left_tree = testutils::create_tree(
+            &test_repo.repo,
+            &[(path, "left 1\nbase 2\nbase 3\nbase 4\nleft 5\n")],
+        );
+        let right_tree = testutils::create_tree(
+            &test_repo.repo,
+            &[(path, "right 1\nbase 2\nbase 3\nbase 4\nright 5\n")],
+        );
+        let tree = left_tree.merge(&base_tree, &right_tree).unwrap();
+        let merge_tool_file = MergeToolFile::from_tree_and_path(&tree, path).unwrap();
+
+        // Select "left 1" over "base 1" in the first conflict, and leave the
+        // second conflict alone.
+        let mut file = make_merge_file(&merge_tool_file).unwrap();
+        let scm_record::Section::Changed { lines } = &mut file.sections[0] else {
+            panic!("first section should be a conflict");
+        };
+        lines[0].is_checked = true;
+        lines[1].is_checked = true;
+        let hunks = get_merge_hunks(files::merge(&merge_tool_file.content), &file);
+        insta::assert_debug_snapshot!(hunks, @r#"
+        [
+            Resolved(
+                "left 1\n",
+            ),
+            Resolved(
+                "base 2\nbase 3\nbase 4\n",
+            ),
+            Conflicted(
+                [
+                    "left 5\n",
+                    "base 5\n",
+                    "right 5\n",
+                ],
+            ),
+        ]
+        "#);
+
+        let new_file_ids =
+            conflicts::update_from_hunks(&merge_tool_file.file_merge, store, path, &hunks)
+                .block_on()
+                .unwrap();
+        let new_content = extract_as_single_hunk(&new_file_ids, store, path)
+            .block_on()
+            .unwrap();
+        insta::assert_debug_snapshot!(new_content, @r#"
+        Conflicted(
+            [
+                "left 1\nbase 2\nbase 3\nbase 4\nleft 5\n",
+                "left 1\nbase 2\nbase 3\nbase 4\nbase 5\n",
+                "left 1\nbase 2\nbase 3\nbase 4\nright 5\n",
+            ],
+        )
+        "#);
+    }
 }
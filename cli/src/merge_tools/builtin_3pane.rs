@@ -0,0 +1,646 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Built-in merge tool showing the left, base, and right sides of each
+//! conflict next to the merged output.
+
+use std::io;
+use std::ops::ControlFlow;
+use std::path::Path;
+
+use bstr::BString;
+use itertools::Itertools;
+use jj_lib::backend::BackendError;
+use jj_lib::backend::MergedTreeId;
+use jj_lib::conflicts;
+use jj_lib::conflicts::choose_materialized_conflict_marker_len;
+use jj_lib::conflicts::materialize_merge_result_to_bytes_with_marker_len;
+use jj_lib::conflicts::ConflictMarkerStyle;
+use jj_lib::files;
+use jj_lib::files::MergeResult;
+use jj_lib::merge::Merge;
+use jj_lib::merged_tree::MergedTree;
+use jj_lib::merged_tree::MergedTreeBuilder;
+use pollster::FutureExt;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::crossterm::event::KeyEvent;
+use ratatui::layout::Constraint;
+use ratatui::layout::Layout;
+use ratatui::style::Color;
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::text::Text;
+use ratatui::widgets::Block;
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+use thiserror::Error;
+
+use super::MergeToolFile;
+use crate::description_util::TempTextEditError;
+use crate::description_util::TextEditor;
+use crate::tui;
+use crate::tui::Motion;
+
+#[derive(Debug, Error)]
+pub enum ThreePaneToolError {
+    #[error("Merge was cancelled")]
+    Cancelled,
+    #[error("Failed to run the merge tool in the terminal")]
+    Terminal(#[from] io::Error),
+    #[error(transparent)]
+    TextEdit(#[from] TempTextEditError),
+    #[error("Backend error")]
+    Backend(#[from] BackendError),
+}
+
+const HELP_TEXT: &str = "j/k: next/prev conflict  l/b/r: take left/base/right  a/A: take both  \
+                         L/B/R: take side and edit  e: edit  u: unresolve  q: save and quit  Q: \
+                         cancel";
+
+/// How a conflict hunk was resolved.
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum HunkResolution {
+    Left,
+    Base,
+    Right,
+    LeftThenRight,
+    RightThenLeft,
+    Edited(BString),
+}
+
+impl HunkResolution {
+    fn contents(&self, hunk: &Merge<BString>) -> BString {
+        let left = hunk.get_add(0).unwrap();
+        let base = hunk.get_remove(0).unwrap();
+        let right = hunk.get_add(1).unwrap();
+        match self {
+            HunkResolution::Left => left.clone(),
+            HunkResolution::Base => base.clone(),
+            HunkResolution::Right => right.clone(),
+            HunkResolution::LeftThenRight => [left.as_slice(), right].concat().into(),
+            HunkResolution::RightThenLeft => [right.as_slice(), left].concat().into(),
+            HunkResolution::Edited(contents) => contents.clone(),
+        }
+    }
+}
+
+/// A conflicted file being merged.
+struct MergeFile {
+    /// Path for displaying purposes.
+    path: String,
+    hunks: Vec<Merge<BString>>,
+    /// Resolution of each hunk. Always `None` for hunks that weren't
+    /// conflicted in the first place.
+    resolutions: Vec<Option<HunkResolution>>,
+    conflict_marker_style: ConflictMarkerStyle,
+    /// Length of the conflict markers, chosen for the whole file so that
+    /// markers in the output are the same as in the working copy.
+    conflict_marker_len: usize,
+}
+
+impl MergeFile {
+    fn new(
+        path: String,
+        content: &Merge<BString>,
+        conflict_marker_style: ConflictMarkerStyle,
+    ) -> Self {
+        let hunks = match files::merge(content) {
+            MergeResult::Resolved(buf) => vec![Merge::resolved(buf)],
+            MergeResult::Conflict(hunks) => hunks,
+        };
+        let resolutions = vec![None; hunks.len()];
+        MergeFile {
+            path,
+            hunks,
+            resolutions,
+            conflict_marker_style,
+            conflict_marker_len: choose_materialized_conflict_marker_len(content),
+        }
+    }
+
+    /// Returns the conflict markers for an unresolved hunk.
+    fn materialize_hunk(&self, hunk: &Merge<BString>) -> BString {
+        materialize_merge_result_to_bytes_with_marker_len(
+            hunk,
+            self.conflict_marker_style,
+            self.conflict_marker_len,
+            None,
+        )
+    }
+
+    fn is_changed(&self) -> bool {
+        self.resolutions.iter().any(Option::is_some)
+    }
+
+    /// Returns the hunks with the chosen resolutions applied.
+    fn resolved_hunks(&self) -> Vec<Merge<BString>> {
+        self.hunks
+            .iter()
+            .zip_eq(&self.resolutions)
+            .map(|(hunk, resolution)| match resolution {
+                Some(resolution) => Merge::resolved(resolution.contents(hunk)),
+                None => hunk.clone(),
+            })
+            .collect()
+    }
+}
+
+/// What the event loop should do after handling a key.
+#[derive(Debug, Eq, PartialEq)]
+enum Action {
+    Continue,
+    Edit,
+    Save,
+    Cancel,
+}
+
+/// State of the merge tool, independent of the terminal.
+struct MergeState {
+    files: Vec<MergeFile>,
+    /// File and hunk index of each conflict.
+    conflicts: Vec<(usize, usize)>,
+    /// Index into `conflicts` of the selected conflict.
+    current: usize,
+    /// Message shown in the status line, e.g. after editing a conflict.
+    message: Option<String>,
+}
+
+impl MergeState {
+    fn new(files: Vec<MergeFile>) -> Self {
+        let conflicts = files
+            .iter()
+            .enumerate()
+            .flat_map(|(file_index, file)| {
+                file.hunks
+                    .iter()
+                    .positions(|hunk| !hunk.is_resolved())
+                    .map(move |hunk_index| (file_index, hunk_index))
+            })
+            .collect();
+        MergeState {
+            files,
+            conflicts,
+            current: 0,
+            message: None,
+        }
+    }
+
+    fn current_hunk(&self) -> Option<(&MergeFile, usize)> {
+        let &(file_index, hunk_index) = self.conflicts.get(self.current)?;
+        Some((&self.files[file_index], hunk_index))
+    }
+
+    fn set_resolution(&mut self, resolution: Option<HunkResolution>) {
+        if let Some(&(file_index, hunk_index)) = self.conflicts.get(self.current) {
+            self.files[file_index].resolutions[hunk_index] = resolution;
+        }
+    }
+
+    fn num_unresolved(&self) -> usize {
+        self.conflicts
+            .iter()
+            .filter(|&&(file_index, hunk_index)| {
+                self.files[file_index].resolutions[hunk_index].is_none()
+            })
+            .count()
+    }
+
+    fn move_selection(&mut self, motion: Motion) {
+        motion.apply(&mut self.current, self.conflicts.len());
+    }
+
+    fn resolve_current(&mut self, resolution: HunkResolution) {
+        self.set_resolution(Some(resolution));
+        self.move_selection(Motion::Next);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        self.message = None;
+        if tui::is_interrupt(&key) {
+            return Action::Cancel;
+        }
+        if let Some(motion) = Motion::from_key(&key) {
+            self.move_selection(motion);
+            return Action::Continue;
+        }
+        match key.code {
+            KeyCode::Char('l' | '1') => self.resolve_current(HunkResolution::Left),
+            KeyCode::Char('b' | '2') => self.resolve_current(HunkResolution::Base),
+            KeyCode::Char('r' | '3') => self.resolve_current(HunkResolution::Right),
+            KeyCode::Char('a') => self.resolve_current(HunkResolution::LeftThenRight),
+            KeyCode::Char('A') => self.resolve_current(HunkResolution::RightThenLeft),
+            // Start editing from one side, e.g. to take it with small changes
+            KeyCode::Char('L') => return self.edit_from(HunkResolution::Left),
+            KeyCode::Char('B') => return self.edit_from(HunkResolution::Base),
+            KeyCode::Char('R') => return self.edit_from(HunkResolution::Right),
+            KeyCode::Char('u') => self.set_resolution(None),
+            KeyCode::Char('e') => return Action::Edit,
+            KeyCode::Char('q') => return Action::Save,
+            KeyCode::Char('Q') => return Action::Cancel,
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    /// Resolves the selected conflict with `resolution` and requests editing
+    /// it further.
+    fn edit_from(&mut self, resolution: HunkResolution) -> Action {
+        if self.current_hunk().is_none() {
+            return Action::Continue;
+        }
+        self.set_resolution(Some(resolution));
+        Action::Edit
+    }
+
+    /// Returns the text to edit for the selected conflict: its current
+    /// resolution, or conflict markers if it's unresolved.
+    fn text_to_edit(&self) -> Option<BString> {
+        let (file, hunk_index) = self.current_hunk()?;
+        let hunk = &file.hunks[hunk_index];
+        let text = match &file.resolutions[hunk_index] {
+            Some(resolution) => resolution.contents(hunk),
+            None => file.materialize_hunk(hunk),
+        };
+        Some(text)
+    }
+
+    /// Applies the edited text to the selected conflict. The conflict is left
+    /// unresolved if the text still contains conflict markers.
+    fn apply_edited_text(&mut self, text: BString) {
+        let Some((file, _)) = self.current_hunk() else {
+            return;
+        };
+        if conflicts::parse_conflict(&text, 2, file.conflict_marker_len).is_some() {
+            self.set_resolution(None);
+            self.message =
+                Some("The conflict markers remain, so the conflict is unresolved".into());
+        } else {
+            self.resolve_current(HunkResolution::Edited(text));
+        }
+    }
+}
+
+fn text_lines(contents: &[u8]) -> Vec<Line<'static>> {
+    String::from_utf8_lossy(contents)
+        .lines()
+        .map(|line| Line::raw(line.to_owned()))
+        .collect()
+}
+
+/// Renders the merged output of `file`. Returns the lines and the index of the
+/// first line of the selected hunk.
+fn render_output(file: &MergeFile, selected_hunk: usize) -> (Vec<Line<'static>>, usize) {
+    let mut lines = vec![];
+    let mut selected_line = 0;
+    for (hunk_index, (hunk, resolution)) in file.hunks.iter().zip(&file.resolutions).enumerate() {
+        let is_selected = hunk_index == selected_hunk;
+        if is_selected {
+            selected_line = lines.len();
+        }
+        let style = if is_selected {
+            tui::selected_style()
+        } else if hunk.is_resolved() {
+            Style::new()
+        } else if resolution.is_some() {
+            Style::new().fg(Color::Green)
+        } else {
+            Style::new().fg(Color::Red)
+        };
+        let hunk_lines = if let Some(contents) = hunk.as_resolved() {
+            text_lines(contents)
+        } else if let Some(resolution) = resolution {
+            text_lines(&resolution.contents(hunk))
+        } else {
+            text_lines(&file.materialize_hunk(hunk))
+        };
+        lines.extend(hunk_lines.into_iter().map(|line| line.style(style)));
+    }
+    (lines, selected_line)
+}
+
+fn render(frame: &mut Frame, state: &MergeState) {
+    let [main_area, status_area] = tui::split_status_line(frame.area());
+    let [sides_area, output_area] =
+        Layout::vertical([Constraint::Percentage(40), Constraint::Min(3)]).areas(main_area);
+    let side_areas: [_; 3] = Layout::horizontal([Constraint::Ratio(1, 3); 3]).areas(sides_area);
+
+    let Some((file, hunk_index)) = state.current_hunk() else {
+        return;
+    };
+    let hunk = &file.hunks[hunk_index];
+    let sides = [
+        ("Left", hunk.get_add(0).unwrap()),
+        ("Base", hunk.get_remove(0).unwrap()),
+        ("Right", hunk.get_add(1).unwrap()),
+    ];
+    for ((title, contents), area) in sides.into_iter().zip(side_areas) {
+        let paragraph = Paragraph::new(Text::from(text_lines(contents)))
+            .block(Block::bordered().title(format!(" {title} ")));
+        frame.render_widget(paragraph, area);
+    }
+
+    let (lines, selected_line) = render_output(file, hunk_index);
+    // Keep a few lines of context above the selected hunk.
+    let scroll = selected_line.saturating_sub(3);
+    let title = format!(
+        " {} - conflict {} of {} ({} unresolved) ",
+        file.path,
+        state.current + 1,
+        state.conflicts.len(),
+        state.num_unresolved(),
+    );
+    let output = Paragraph::new(Text::from(lines))
+        .block(Block::bordered().title(title))
+        .scroll((scroll.try_into().unwrap_or(u16::MAX), 0));
+    frame.render_widget(output, output_area);
+
+    let status = state.message.as_deref().unwrap_or(HELP_TEXT);
+    tui::render_status_line(frame, status_area, status);
+}
+
+fn run_event_loop(
+    state: &mut MergeState,
+    text_editor: &TextEditor,
+) -> Result<(), ThreePaneToolError> {
+    tui::run_event_loop(state, render, |state, key, session| {
+        match state.handle_key(key) {
+            Action::Continue => {}
+            Action::Save => return Ok(ControlFlow::Break(())),
+            Action::Cancel => return Err(ThreePaneToolError::Cancelled),
+            Action::Edit => {
+                if let Some(text) = state.text_to_edit() {
+                    let edited = session
+                        .suspend(|| text_editor.edit_str(text, None))?
+                        .map_err(|err| err.with_name("conflict"))?;
+                    state.apply_edited_text(edited.into());
+                }
+            }
+        }
+        Ok(ControlFlow::Continue(()))
+    })
+}
+
+pub fn edit_merge_builtin_3pane(
+    tree: &MergedTree,
+    merge_tool_files: &[MergeToolFile],
+    text_editor: &TextEditor,
+    conflict_marker_style: ConflictMarkerStyle,
+) -> Result<MergedTreeId, ThreePaneToolError> {
+    let files = merge_tool_files
+        .iter()
+        .map(|file| {
+            // Path for displaying purposes, not for file access.
+            let path = file.repo_path.to_fs_path_unchecked(Path::new(""));
+            MergeFile::new(
+                path.display().to_string(),
+                &file.content,
+                conflict_marker_style,
+            )
+        })
+        .collect();
+    let mut state = MergeState::new(files);
+    run_event_loop(&mut state, text_editor)?;
+
+    let store = tree.store();
+    let mut tree_builder = MergedTreeBuilder::new(tree.id());
+    for (merge_tool_file, file) in merge_tool_files.iter().zip_eq(&state.files) {
+        if !file.is_changed() {
+            continue;
+        }
+        let new_file_ids = conflicts::update_from_hunks(
+            &merge_tool_file.file_merge,
+            store,
+            &merge_tool_file.repo_path,
+            &file.resolved_hunks(),
+        )
+        .block_on()?;
+        tree_builder.set_or_remove(
+            merge_tool_file.repo_path.clone(),
+            merge_tool_file.new_tree_value(new_file_ids),
+        );
+    }
+    Ok(tree_builder.write_tree(store)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    use super::*;
+
+    fn new_state() -> MergeState {
+        let content = Merge::from_vec(vec![
+            BString::from("left 1\nbase 2\nbase 3\nleft 4\n"),
+            BString::from("base 1\nbase 2\nbase 3\nbase 4\n"),
+            BString::from("right 1\nbase 2\nbase 3\nright 4\n"),
+        ]);
+        MergeState::new(vec![MergeFile::new(
+            "file".to_owned(),
+            &content,
+            ConflictMarkerStyle::Diff,
+        )])
+    }
+
+    fn press(state: &mut MergeState, c: char) -> Action {
+        state.handle_key(KeyEvent::from(KeyCode::Char(c)))
+    }
+
+    fn render_to_string(state: &MergeState) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(60, 20)).unwrap();
+        terminal.draw(|frame| render(frame, state)).unwrap();
+        terminal.backend().to_string()
+    }
+
+    #[test]
+    fn test_resolve_hunks() {
+        let mut state = new_state();
+        assert_eq!(state.conflicts.len(), 2);
+        assert_eq!(state.num_unresolved(), 2);
+
+        // Taking a side moves to the next conflict
+        assert_eq!(press(&mut state, 'l'), Action::Continue);
+        assert_eq!(state.current, 1);
+        assert_eq!(press(&mut state, 'A'), Action::Continue);
+        assert_eq!(state.num_unresolved(), 0);
+        insta::assert_debug_snapshot!(state.files[0].resolved_hunks(), @r#"
+        [
+            Resolved(
+                "left 1\n",
+            ),
+            Resolved(
+                "base 2\nbase 3\n",
+            ),
+            Resolved(
+                "right 4\nleft 4\n",
+            ),
+        ]
+        "#);
+
+        // Unresolve the first conflict again
+        press(&mut state, 'k');
+        press(&mut state, 'u');
+        assert_eq!(state.num_unresolved(), 1);
+        insta::assert_debug_snapshot!(state.files[0].resolved_hunks(), @r#"
+        [
+            Conflicted(
+                [
+                    "left 1\n",
+                    "base 1\n",
+                    "right 1\n",
+                ],
+            ),
+            Resolved(
+                "base 2\nbase 3\n",
+            ),
+            Resolved(
+                "right 4\nleft 4\n",
+            ),
+        ]
+        "#);
+
+        assert_eq!(press(&mut state, 'e'), Action::Edit);
+        assert_eq!(press(&mut state, 'q'), Action::Save);
+        assert_eq!(press(&mut state, 'Q'), Action::Cancel);
+    }
+
+    #[test]
+    fn test_edit_from_side() {
+        let mut state = new_state();
+        // The side is taken, but the selection stays until editing is done
+        assert_eq!(press(&mut state, 'R'), Action::Edit);
+        assert_eq!(state.current, 0);
+        assert_eq!(state.num_unresolved(), 1);
+        let text = state.text_to_edit().unwrap();
+        assert_eq!(text, "right 1\n");
+
+        state.apply_edited_text("right 1 edited\n".into());
+        assert_eq!(state.current, 1);
+        insta::assert_debug_snapshot!(state.files[0].resolved_hunks()[0], @r#"
+        Resolved(
+            "right 1 edited\n",
+        )
+        "#);
+    }
+
+    #[test]
+    fn test_edit_hunk() {
+        let mut state = new_state();
+        let text = state.text_to_edit().unwrap();
+        insta::assert_snapshot!(text, @r"
+        <<<<<<< Conflict 1 of 1
+        %%%%%%% Changes from base to side #1
+        -base 1
+        +left 1
+        +++++++ Contents of side #2
+        right 1
+        >>>>>>> Conflict 1 of 1 ends
+        ");
+
+        // Leaving the conflict markers in place keeps the conflict
+        state.apply_edited_text(text);
+        assert_eq!(state.num_unresolved(), 2);
+        assert!(state.message.is_some());
+
+        state.apply_edited_text("edited 1\n".into());
+        assert_eq!(state.num_unresolved(), 1);
+        assert_eq!(state.current, 1);
+        insta::assert_debug_snapshot!(state.files[0].resolved_hunks()[0], @r#"
+        Resolved(
+            "edited 1\n",
+        )
+        "#);
+    }
+
+    #[test]
+    fn test_edit_hunk_long_markers() {
+        // Marker-like lines outside the conflict still make the markers longer,
+        // as they would be in the working copy
+        let content = Merge::from_vec(vec![
+            BString::from("left\n=======\n"),
+            BString::from("base\n=======\n"),
+            BString::from("right\n=======\n"),
+        ]);
+        let mut state = MergeState::new(vec![MergeFile::new(
+            "file".to_owned(),
+            &content,
+            ConflictMarkerStyle::Snapshot,
+        )]);
+        let text = state.text_to_edit().unwrap();
+        insta::assert_snapshot!(text, @r"
+        <<<<<<<<<<< Conflict 1 of 1
+        +++++++++++ Contents of side #1
+        left
+        ----------- Contents of base
+        base
+        +++++++++++ Contents of side #2
+        right
+        >>>>>>>>>>> Conflict 1 of 1 ends
+        ");
+
+        state.apply_edited_text(text);
+        assert_eq!(state.num_unresolved(), 1);
+    }
+
+    #[test]
+    fn test_render() {
+        let mut state = new_state();
+        insta::assert_snapshot!(render_to_string(&state), @r#"
+        "┌ Left ────────────┐┌ Base ────────────┐┌ Right ───────────┐"
+        "│left 1            ││base 1            ││right 1           │"
+        "│                  ││                  ││                  │"
+        "│                  ││                  ││                  │"
+        "│                  ││                  ││                  │"
+        "│                  ││                  ││                  │"
+        "│                  ││                  ││                  │"
+        "└──────────────────┘└──────────────────┘└──────────────────┘"
+        "┌ file - conflict 1 of 2 (2 unresolved) ───────────────────┐"
+        "│<<<<<<< Conflict 1 of 1                                   │"
+        "│%%%%%%% Changes from base to side #1                      │"
+        "│-base 1                                                   │"
+        "│+left 1                                                   │"
+        "│+++++++ Contents of side #2                               │"
+        "│right 1                                                   │"
+        "│>>>>>>> Conflict 1 of 1 ends                              │"
+        "│base 2                                                    │"
+        "│base 3                                                    │"
+        "└──────────────────────────────────────────────────────────┘"
+        "j/k: next/prev conflict  l/b/r: take left/base/right  a/A: t"
+        "#);
+
+        press(&mut state, 'r');
+        insta::assert_snapshot!(render_to_string(&state), @r#"
+        "┌ Left ────────────┐┌ Base ────────────┐┌ Right ───────────┐"
+        "│left 4            ││base 4            ││right 4           │"
+        "│                  ││                  ││                  │"
+        "│                  ││                  ││                  │"
+        "│                  ││                  ││                  │"
+        "│                  ││                  ││                  │"
+        "│                  ││                  ││                  │"
+        "└──────────────────┘└──────────────────┘└──────────────────┘"
+        "┌ file - conflict 2 of 2 (1 unresolved) ───────────────────┐"
+        "│right 1                                                   │"
+        "│base 2                                                    │"
+        "│base 3                                                    │"
+        "│<<<<<<< Conflict 1 of 1                                   │"
+        "│%%%%%%% Changes from base to side #1                      │"
+        "│-base 4                                                   │"
+        "│+left 4                                                   │"
+        "│+++++++ Contents of side #2                               │"
+        "│right 4                                                   │"
+        "└──────────────────────────────────────────────────────────┘"
+        "j/k: next/prev conflict  l/b/r: take left/base/right  a/A: t"
+        "#);
+    }
+}
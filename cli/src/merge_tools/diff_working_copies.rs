@@ -9,6 +9,7 @@ use std::sync::Arc;
 use futures::StreamExt;
 use jj_lib::backend::MergedTreeId;
 use jj_lib::conflicts::ConflictMarkerStyle;
+use jj_lib::conflicts::ConflictMarkerStyleOverrides;
 use jj_lib::fsmonitor::FsmonitorSettings;
 use jj_lib::gitignore::GitIgnoreFile;
 use jj_lib::local_working_copy::TreeState;
@@ -299,6 +300,8 @@ diff editing in mind and be a little inaccurate.
             start_tracking_matcher: &EverythingMatcher,
             max_new_file_size: u64::MAX,
             conflict_marker_style,
+            conflict_marker_style_overrides: ConflictMarkerStyleOverrides::default(),
+            content_check: None,
         })?;
         Ok(output_tree_state.current_tree_id().clone())
     }
@@ -9,17 +9,20 @@ use std::sync::Arc;
 use bstr::BString;
 use itertools::Itertools;
 use jj_lib::backend::MergedTreeId;
-use jj_lib::backend::TreeValue;
 use jj_lib::conflicts;
 use jj_lib::conflicts::choose_materialized_conflict_marker_len;
 use jj_lib::conflicts::materialize_merge_result_to_bytes_with_marker_len;
 use jj_lib::conflicts::ConflictMarkerStyle;
+use jj_lib::conflicts::ConflictMarkerStyleOverrides;
 use jj_lib::conflicts::MIN_CONFLICT_MARKER_LEN;
 use jj_lib::gitignore::GitIgnoreFile;
 use jj_lib::matchers::Matcher;
 use jj_lib::merge::Merge;
+use jj_lib::merge_strategy::MergeDriver;
+use jj_lib::merge_strategy::MergeDriverRunner;
 use jj_lib::merged_tree::MergedTree;
 use jj_lib::merged_tree::MergedTreeBuilder;
+use jj_lib::repo_path::RepoPath;
 use jj_lib::repo_path::RepoPathUiConverter;
 use jj_lib::store::Store;
 use jj_lib::working_copy::CheckoutOptions;
@@ -183,7 +186,7 @@ fn run_mergetool_external_single_file(
 ) -> Result<(), ConflictResolveError> {
     let MergeToolFile {
         repo_path,
-        conflict,
+        conflict: _,
         file_merge,
         content,
     } = merge_tool_file;
@@ -208,6 +211,7 @@ fn run_mergetool_external_single_file(
             content,
             conflict_marker_style,
             conflict_marker_len,
+            None,
         )
     } else {
         BString::default()
@@ -304,18 +308,7 @@ fn run_mergetool_external_single_file(
         ));
     }
 
-    let new_tree_value = match new_file_ids.into_resolved() {
-        Ok(new_file_id) => Merge::normal(TreeValue::File {
-            id: new_file_id.unwrap(),
-            executable: conflict
-                .to_executable_merge()
-                .as_ref()
-                .and_then(Merge::resolve_trivial)
-                .copied()
-                .unwrap_or_default(),
-        }),
-        Err(new_file_ids) => conflict.with_new_file_ids(&new_file_ids),
-    };
+    let new_tree_value = merge_tool_file.new_tree_value(new_file_ids);
     tree_builder.set_or_remove(repo_path.to_owned(), new_tree_value);
     Ok(())
 }
@@ -379,6 +372,8 @@ pub fn edit_diff_external(
         .unwrap_or(default_conflict_marker_style);
     let options = CheckoutOptions {
         conflict_marker_style,
+        conflict_marker_style_overrides: ConflictMarkerStyleOverrides::default(),
+        conflict_labels: None,
     };
 
     let got_output_field = find_all_variables(&editor.edit_args).contains(&"output");
@@ -427,6 +422,8 @@ pub fn generate_diff(
         .unwrap_or(default_conflict_marker_style);
     let options = CheckoutOptions {
         conflict_marker_style,
+        conflict_marker_style_overrides: ConflictMarkerStyleOverrides::default(),
+        conflict_labels: None,
     };
     let store = left_tree.store();
     let diff_wc = check_out_trees(store, left_tree, right_tree, matcher, None, &options)?;
@@ -476,6 +473,88 @@ pub fn invoke_external_diff(
     Ok(())
 }
 
+/// Error from running a merge driver configured in `merge.strategies`.
+#[derive(Debug, Error)]
+#[error("Failed to run the merge driver for {path}")]
+pub struct MergeDriverError {
+    path: String,
+    #[source]
+    source: ExternalToolError,
+}
+
+/// Runs merge drivers as subprocesses.
+#[derive(Debug)]
+pub struct ExternalMergeDriverRunner;
+
+impl ExternalMergeDriverRunner {
+    fn run_in_temp_dir(
+        driver: &MergeDriver,
+        path: &RepoPath,
+        contents: &Merge<BString>,
+    ) -> Result<Option<BString>, ExternalToolError> {
+        let left = contents.get_add(0).unwrap();
+        let files: [(&str, &[u8]); 4] = [
+            ("base", contents.get_remove(0).unwrap()),
+            ("left", left),
+            ("right", contents.get_add(1).unwrap()),
+            ("output", left),
+        ];
+        let temp_dir = new_utf8_temp_dir("jj-merge-").map_err(ExternalToolError::SetUpDir)?;
+        let mut variables: HashMap<&str, String> = files
+            .iter()
+            .map(|&(role, content)| {
+                let file_path = temp_dir.path().join(role);
+                std::fs::write(&file_path, content).map_err(ExternalToolError::SetUpDir)?;
+                let file_path = file_path
+                    .into_os_string()
+                    .into_string()
+                    .expect("temp_dir should be valid utf-8");
+                Ok((role, file_path))
+            })
+            .try_collect()?;
+        variables.insert("path", path.as_internal_file_string().to_owned());
+
+        let (program, args) = driver.command.split_first().unwrap();
+        let mut cmd = Command::new(program);
+        cmd.args(interpolate_variables(args, &variables))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null());
+        tracing::info!(?cmd, "Invoking the merge driver:");
+        let exit_status = cmd
+            .status()
+            .map_err(|source| ExternalToolError::FailedToExecute {
+                tool_binary: program.clone(),
+                source,
+            })?;
+        if !exit_status.success() {
+            tracing::info!(
+                ?exit_status,
+                "The merge driver left the conflict unresolved"
+            );
+            return Ok(None);
+        }
+        let output = std::fs::read(&variables["output"]).map_err(ExternalToolError::Io)?;
+        Ok(Some(output.into()))
+    }
+}
+
+impl MergeDriverRunner for ExternalMergeDriverRunner {
+    fn run(
+        &self,
+        driver: &MergeDriver,
+        path: &RepoPath,
+        contents: &Merge<BString>,
+    ) -> Result<Option<BString>, Box<dyn std::error::Error + Send + Sync>> {
+        Self::run_in_temp_dir(driver, path, contents).map_err(|source| {
+            MergeDriverError {
+                path: path.as_internal_file_string().to_owned(),
+                source,
+            }
+            .into()
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
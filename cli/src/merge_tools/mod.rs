@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod builtin;
+mod builtin_3pane;
 mod diff_working_copies;
 mod external;
 
@@ -22,6 +23,7 @@ use bstr::BString;
 use itertools::Itertools;
 use jj_lib::backend::FileId;
 use jj_lib::backend::MergedTreeId;
+use jj_lib::backend::TreeValue;
 use jj_lib::config::ConfigGetError;
 use jj_lib::config::ConfigGetResultExt as _;
 use jj_lib::config::ConfigNamePathBuf;
@@ -44,18 +46,24 @@ use thiserror::Error;
 use self::builtin::edit_diff_builtin;
 use self::builtin::edit_merge_builtin;
 use self::builtin::BuiltinToolError;
+use self::builtin_3pane::edit_merge_builtin_3pane;
+use self::builtin_3pane::ThreePaneToolError;
 pub(crate) use self::diff_working_copies::new_utf8_temp_dir;
 use self::diff_working_copies::DiffCheckoutError;
 use self::external::edit_diff_external;
 pub use self::external::generate_diff;
 pub use self::external::invoke_external_diff;
 pub use self::external::DiffToolMode;
+pub use self::external::ExternalMergeDriverRunner;
 pub use self::external::ExternalMergeTool;
 use self::external::ExternalToolError;
+pub use self::external::MergeDriverError;
 use crate::config::CommandNameAndArgs;
+use crate::description_util::TextEditor;
 use crate::ui::Ui;
 
 const BUILTIN_EDITOR_NAME: &str = ":builtin";
+pub(crate) const BUILTIN_3PANE_EDITOR_NAME: &str = ":builtin-3pane";
 
 #[derive(Debug, Error)]
 pub enum DiffEditError {
@@ -84,6 +92,8 @@ pub enum ConflictResolveError {
     #[error(transparent)]
     InternalTool(#[from] Box<BuiltinToolError>),
     #[error(transparent)]
+    ThreePaneTool(#[from] Box<ThreePaneToolError>),
+    #[error(transparent)]
     ExternalTool(#[from] ExternalToolError),
     #[error(transparent)]
     InvalidRepoPath(#[from] InvalidRepoPathError),
@@ -122,11 +132,16 @@ pub enum MergeToolConfigError {
     Config(#[from] ConfigGetError),
     #[error("The tool `{tool_name}` cannot be used as a merge tool with `jj resolve`")]
     MergeArgsNotConfigured { tool_name: String },
+    #[error("The tool `{tool_name}` can only be used as a merge tool with `jj resolve`")]
+    MergeOnlyTool { tool_name: String },
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum MergeTool {
     Builtin,
+    /// Built-in tool showing the sides of each conflict next to the output.
+    /// Only usable for merges.
+    Builtin3Pane,
     // Boxed because ExternalMergeTool is big compared to the Builtin variant.
     External(Box<ExternalMergeTool>),
 }
@@ -167,6 +182,8 @@ fn get_tool_config(
 ) -> Result<Option<MergeTool>, ConfigGetError> {
     if name == BUILTIN_EDITOR_NAME {
         Ok(Some(MergeTool::Builtin))
+    } else if name == BUILTIN_3PANE_EDITOR_NAME {
+        Ok(Some(MergeTool::Builtin3Pane))
     } else {
         Ok(get_external_tool_config(settings, name)?.map(MergeTool::external))
     }
@@ -207,7 +224,7 @@ impl DiffEditor {
     ) -> Result<Self, MergeToolConfigError> {
         let tool = get_tool_config(settings, name)?
             .unwrap_or_else(|| MergeTool::external(ExternalMergeTool::with_program(name)));
-        Self::new_inner(tool, settings, base_ignores, conflict_marker_style)
+        Self::new_inner(name, tool, settings, base_ignores, conflict_marker_style)
     }
 
     /// Loads the default diff editor from the settings.
@@ -224,15 +241,21 @@ impl DiffEditor {
             None
         }
         .unwrap_or_else(|| MergeTool::external(ExternalMergeTool::with_edit_args(&args)));
-        Self::new_inner(tool, settings, base_ignores, conflict_marker_style)
+        Self::new_inner(&args, tool, settings, base_ignores, conflict_marker_style)
     }
 
     fn new_inner(
+        name: impl ToString,
         tool: MergeTool,
         settings: &UserSettings,
         base_ignores: Arc<GitIgnoreFile>,
         conflict_marker_style: ConflictMarkerStyle,
     ) -> Result<Self, MergeToolConfigError> {
+        if tool == MergeTool::Builtin3Pane {
+            return Err(MergeToolConfigError::MergeOnlyTool {
+                tool_name: name.to_string(),
+            });
+        }
         Ok(DiffEditor {
             tool,
             base_ignores,
@@ -263,6 +286,7 @@ impl DiffEditor {
                         .map_err(Box::new)?,
                 )
             }
+            MergeTool::Builtin3Pane => unreachable!("merge-only tool shouldn't be a diff editor"),
             MergeTool::External(editor) => {
                 let instructions = self.use_instructions.then(format_instructions);
                 edit_diff_external(
@@ -318,6 +342,24 @@ impl MergeToolFile {
             content,
         })
     }
+
+    /// Builds the tree value for this file with the new (possibly still
+    /// conflicted) file contents.
+    fn new_tree_value(&self, new_file_ids: Merge<Option<FileId>>) -> MergedTreeValue {
+        match new_file_ids.into_resolved() {
+            Ok(new_file_id) => Merge::normal(TreeValue::File {
+                id: new_file_id.unwrap(),
+                executable: self
+                    .conflict
+                    .to_executable_merge()
+                    .as_ref()
+                    .and_then(Merge::resolve_trivial)
+                    .copied()
+                    .unwrap_or_default(),
+            }),
+            Err(new_file_ids) => self.conflict.with_new_file_ids(&new_file_ids),
+        }
+    }
 }
 
 /// Configured 3-way merge editor.
@@ -326,6 +368,7 @@ pub struct MergeEditor {
     tool: MergeTool,
     path_converter: RepoPathUiConverter,
     conflict_marker_style: ConflictMarkerStyle,
+    text_editor: TextEditor,
 }
 
 impl MergeEditor {
@@ -339,7 +382,14 @@ impl MergeEditor {
     ) -> Result<Self, MergeToolConfigError> {
         let tool = get_tool_config(settings, name)?
             .unwrap_or_else(|| MergeTool::external(ExternalMergeTool::with_program(name)));
-        Self::new_inner(name, tool, path_converter, conflict_marker_style)
+        let text_editor = TextEditor::from_settings(settings)?;
+        Self::new_inner(
+            name,
+            tool,
+            path_converter,
+            conflict_marker_style,
+            text_editor,
+        )
     }
 
     /// Loads the default 3-way merge editor from the settings.
@@ -356,7 +406,14 @@ impl MergeEditor {
             None
         }
         .unwrap_or_else(|| MergeTool::external(ExternalMergeTool::with_merge_args(&args)));
-        Self::new_inner(&args, tool, path_converter, conflict_marker_style)
+        let text_editor = TextEditor::from_settings(settings)?;
+        Self::new_inner(
+            &args,
+            tool,
+            path_converter,
+            conflict_marker_style,
+            text_editor,
+        )
     }
 
     fn new_inner(
@@ -364,6 +421,7 @@ impl MergeEditor {
         tool: MergeTool,
         path_converter: RepoPathUiConverter,
         conflict_marker_style: ConflictMarkerStyle,
+        text_editor: TextEditor,
     ) -> Result<Self, MergeToolConfigError> {
         if matches!(&tool, MergeTool::External(mergetool) if mergetool.merge_args.is_empty()) {
             return Err(MergeToolConfigError::MergeArgsNotConfigured {
@@ -374,6 +432,7 @@ impl MergeEditor {
             tool,
             path_converter,
             conflict_marker_style,
+            text_editor,
         })
     }
 
@@ -394,6 +453,16 @@ impl MergeEditor {
                 let tree_id = edit_merge_builtin(tree, &merge_tool_files).map_err(Box::new)?;
                 Ok((tree_id, None))
             }
+            MergeTool::Builtin3Pane => {
+                let tree_id = edit_merge_builtin_3pane(
+                    tree,
+                    &merge_tool_files,
+                    &self.text_editor,
+                    self.conflict_marker_style,
+                )
+                .map_err(Box::new)?;
+                Ok((tree_id, None))
+            }
             MergeTool::External(editor) => external::run_mergetool_external(
                 ui,
                 &self.path_converter,
@@ -437,6 +506,11 @@ mod tests {
         };
 
         insta::assert_debug_snapshot!(get(":builtin", "").unwrap(), @"Builtin");
+        insta::assert_debug_snapshot!(get(":builtin-3pane", "").unwrap_err(), @r#"
+        MergeOnlyTool {
+            tool_name: ":builtin-3pane",
+        }
+        "#);
 
         // Just program name, edit_args are filled by default
         insta::assert_debug_snapshot!(get("my diff", "").unwrap(), @r#"
@@ -701,6 +775,7 @@ mod tests {
         };
 
         insta::assert_debug_snapshot!(get(":builtin", "").unwrap(), @"Builtin");
+        insta::assert_debug_snapshot!(get(":builtin-3pane", "").unwrap(), @"Builtin3Pane");
 
         // Just program name
         insta::assert_debug_snapshot!(get("my diff", "").unwrap_err(), @r#"
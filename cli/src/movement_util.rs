@@ -157,6 +157,23 @@ fn get_target_commit(
     args: &MovementArgsInternal,
 ) -> Result<Commit, CommandError> {
     let wc_revset = RevsetExpression::commit(working_commit_id.clone());
+    // An empty working-copy commit without descendants, such as the one created
+    // by `jj new main`, has no conflicted descendants to edit. Search from its
+    // parents instead, as if `--edit` wasn't given.
+    let search_args;
+    let args = if direction == Direction::Next
+        && args.should_edit
+        && args.conflict
+        && is_scratch_commit(workspace_command, working_commit_id, &wc_revset)?
+    {
+        search_args = MovementArgsInternal {
+            should_edit: false,
+            ..args.clone()
+        };
+        &search_args
+    } else {
+        args
+    };
     // If we're editing, start at the working-copy commit. Otherwise, start from
     // its direct parent(s).
     let start_revset = if args.should_edit {
@@ -190,6 +207,21 @@ fn get_target_commit(
     Ok(target.clone())
 }
 
+/// Returns true if the working-copy commit is discardable and has no children.
+fn is_scratch_commit(
+    workspace_command: &WorkspaceCommandHelper,
+    working_commit_id: &CommitId,
+    wc_revset: &Rc<ResolvedRevsetExpression>,
+) -> Result<bool, CommandError> {
+    let repo = workspace_command.repo().as_ref();
+    let commit = repo.store().get_commit(working_commit_id)?;
+    if !commit.is_discardable(repo)? {
+        return Ok(false);
+    }
+    let has_children = wc_revset.children().evaluate(repo)?.iter().next().is_some();
+    Ok(!has_children)
+}
+
 fn choose_commit<'a>(
     ui: &mut Ui,
     workspace_command: &WorkspaceCommandHelper,
@@ -0,0 +1,382 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Diff navigation for the builtin pager.
+//!
+//! The output sent to the pager is scanned for diff file and hunk headers so
+//! the pager can jump between them. The scan works on the rendered text (with
+//! ANSI escapes removed), so it works regardless of the diff format.
+
+use std::io;
+use std::io::Write;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use streampager::action::Action;
+use streampager::action::ActionSender;
+use streampager::bindings::Binding;
+use streampager::bindings::Category;
+use streampager::bindings::KeyCode;
+use streampager::bindings::Keymap;
+use streampager::bindings::Modifiers;
+use streampager::FileIndex;
+
+/// Prefixes of the file headers emitted by the "color-words" diff format.
+const COLOR_WORDS_FILE_HEADER_PREFIXES: &[&str] = &[
+    "Added ",
+    "Removed ",
+    "Modified ",
+    "Resolved conflict in ",
+    "Created conflict in ",
+    "Symlink target changed at ",
+    "Executable file became ",
+    "Non-executable file became ",
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum LineKind {
+    /// File header of a Git-format diff.
+    GitFileHeader,
+    /// File header of a color-words diff, which also starts the first hunk.
+    FileHeader,
+    HunkHeader,
+    Other,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Targets {
+    Files,
+    Hunks,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Direction {
+    Forward,
+    Backward,
+}
+
+#[derive(Debug, Default)]
+struct OutlineState {
+    /// Number of complete lines seen so far.
+    line_count: usize,
+    /// Bytes of the line currently being written.
+    partial_line: Vec<u8>,
+    /// Line numbers (0-based) of file headers.
+    files: Vec<usize>,
+    /// Line numbers (0-based) of hunk headers.
+    hunks: Vec<usize>,
+    /// Line the pager was last moved to by a navigation key.
+    cursor: usize,
+}
+
+impl OutlineState {
+    fn feed(&mut self, data: &[u8]) {
+        let mut rest = data;
+        while let Some(pos) = rest.iter().position(|&b| b == b'\n') {
+            self.partial_line.extend_from_slice(&rest[..pos]);
+            match classify_line(&self.partial_line) {
+                LineKind::GitFileHeader => self.files.push(self.line_count),
+                LineKind::FileHeader => {
+                    self.files.push(self.line_count);
+                    self.hunks.push(self.line_count);
+                }
+                LineKind::HunkHeader => self.hunks.push(self.line_count),
+                LineKind::Other => {}
+            }
+            self.partial_line.clear();
+            self.line_count += 1;
+            rest = &rest[pos + 1..];
+        }
+        self.partial_line.extend_from_slice(rest);
+    }
+
+    /// Moves the cursor to the next or previous target line, and returns the
+    /// new cursor position if it moved.
+    fn advance(&mut self, targets: Targets, direction: Direction) -> Option<usize> {
+        let lines = match targets {
+            Targets::Files => &self.files,
+            Targets::Hunks => &self.hunks,
+        };
+        let cursor = self.cursor;
+        let line = match direction {
+            Direction::Forward => lines.iter().copied().find(|&line| line > cursor),
+            Direction::Backward => lines.iter().copied().rfind(|&line| line < cursor),
+        }?;
+        self.cursor = line;
+        Some(line)
+    }
+}
+
+/// Positions of diff headers in the output sent to the builtin pager.
+#[derive(Debug, Default)]
+pub struct DiffOutline {
+    state: Mutex<OutlineState>,
+}
+
+impl DiffOutline {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    fn feed(&self, data: &[u8]) {
+        self.state.lock().unwrap().feed(data);
+    }
+
+    fn advance(&self, targets: Targets, direction: Direction) -> Option<usize> {
+        self.state.lock().unwrap().advance(targets, direction)
+    }
+
+    /// Returns the default keymap extended with bindings that jump between
+    /// the diff files and hunks in the stream `file_index`.
+    pub fn keymap(self: &Arc<Self>, file_index: FileIndex, sender: ActionSender) -> Keymap {
+        let mut keymap = Keymap::default();
+        let bindings = [
+            (
+                '}',
+                Targets::Files,
+                Direction::Forward,
+                "Move to the next file in diff",
+            ),
+            (
+                '{',
+                Targets::Files,
+                Direction::Backward,
+                "Move to the previous file in diff",
+            ),
+            (
+                'J',
+                Targets::Hunks,
+                Direction::Forward,
+                "Move to the next hunk in diff",
+            ),
+            (
+                'K',
+                Targets::Hunks,
+                Direction::Backward,
+                "Move to the previous hunk in diff",
+            ),
+        ];
+        for (key, targets, direction, description) in bindings {
+            let outline = self.clone();
+            let sender = sender.clone();
+            let callback = move |index: FileIndex| {
+                if index != file_index {
+                    return;
+                }
+                if let Some(line) = outline.advance(targets, direction) {
+                    scroll_to_line(&sender, line);
+                }
+            };
+            keymap.bind(
+                Modifiers::NONE,
+                KeyCode::Char(key),
+                Binding::custom(Category::Navigation, description, callback),
+            );
+        }
+        keymap
+    }
+}
+
+/// Scrolls the pager so the 0-based `line` is at the top of the screen.
+fn scroll_to_line(sender: &ActionSender, line: usize) {
+    // The pager has no action to go to a line, but a repeat count followed by
+    // "scroll to top" does the same. The repeat count is 1-based.
+    for digit in (line + 1).to_string().bytes() {
+        let digit = usize::from(digit - b'0');
+        if sender
+            .send(Action::AppendDigitToRepeatCount(digit))
+            .is_err()
+        {
+            return;
+        }
+    }
+    sender.send(Action::ScrollToTop).ok();
+}
+
+/// Writer that passes data through to the pager while recording the
+/// positions of diff headers.
+pub struct OutlineWriter<'a, W> {
+    inner: W,
+    outline: &'a DiffOutline,
+}
+
+impl<'a, W> OutlineWriter<'a, W> {
+    pub fn new(inner: W, outline: &'a DiffOutline) -> Self {
+        OutlineWriter { inner, outline }
+    }
+}
+
+impl<W: Write> Write for OutlineWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.outline.feed(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn classify_line(line: &[u8]) -> LineKind {
+    let text = strip_ansi_escapes(line);
+    let text = String::from_utf8_lossy(&text);
+    // Skip graph edges in front of the diff in `jj log -p`.
+    let text = text.trim_start_matches(|c: char| {
+        c.is_whitespace() || c == '|' || ('\u{2500}'..='\u{257f}').contains(&c)
+    });
+    let text = text.trim_end();
+    if text.starts_with("diff --git ") {
+        LineKind::GitFileHeader
+    } else if text.starts_with("@@ -") || text == "..." {
+        LineKind::HunkHeader
+    } else if text.ends_with(':')
+        && COLOR_WORDS_FILE_HEADER_PREFIXES
+            .iter()
+            .any(|prefix| text.starts_with(prefix))
+    {
+        LineKind::FileHeader
+    } else {
+        LineKind::Other
+    }
+}
+
+/// Removes CSI (e.g. colors) and OSC (e.g. hyperlinks) escape sequences.
+fn strip_ansi_escapes(line: &[u8]) -> Vec<u8> {
+    let mut output = Vec::with_capacity(line.len());
+    let mut bytes = line.iter().copied().peekable();
+    while let Some(b) = bytes.next() {
+        if b != b'\x1b' {
+            output.push(b);
+            continue;
+        }
+        match bytes.next() {
+            Some(b'[') => {
+                // Parameters and intermediate bytes, then a final byte.
+                for b in bytes.by_ref() {
+                    if (0x40..=0x7e).contains(&b) {
+                        break;
+                    }
+                }
+            }
+            Some(b']') => {
+                // Terminated by BEL or ST (ESC \).
+                while let Some(b) = bytes.next() {
+                    if b == b'\x07' {
+                        break;
+                    } else if b == b'\x1b' && bytes.peek() == Some(&b'\\') {
+                        bytes.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    #[test]
+    fn test_strip_ansi_escapes() {
+        assert_eq!(strip_ansi_escapes(b"plain"), b"plain");
+        assert_eq!(
+            strip_ansi_escapes(b"\x1b[1m\x1b[38;5;3mdiff --git\x1b[39m\x1b[0m"),
+            b"diff --git"
+        );
+        assert_eq!(
+            strip_ansi_escapes(b"\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x07!"),
+            b"link!"
+        );
+    }
+
+    #[test]
+    fn test_classify_line() {
+        assert_eq!(
+            classify_line(b"diff --git a/file b/file"),
+            LineKind::GitFileHeader
+        );
+        assert_eq!(
+            classify_line("\u{2502} diff --git a/file b/file".as_bytes()),
+            LineKind::GitFileHeader
+        );
+        assert_eq!(classify_line(b"@@ -1,2 +1,3 @@"), LineKind::HunkHeader);
+        assert_eq!(classify_line(b"    ..."), LineKind::HunkHeader);
+        assert_eq!(
+            classify_line(b"\x1b[38;5;3mModified regular file foo:\x1b[39m"),
+            LineKind::FileHeader
+        );
+        assert_eq!(
+            classify_line(b"| Added executable file bin/tool:"),
+            LineKind::FileHeader
+        );
+        assert_eq!(classify_line(b"Modified regular file"), LineKind::Other);
+        assert_eq!(classify_line(b"+@@ -1 +1 @@ not a header"), LineKind::Other);
+        assert_eq!(classify_line(b"   1    1: foo"), LineKind::Other);
+    }
+
+    #[test]
+    fn test_outline_navigation() {
+        let mut state = OutlineState::default();
+        // Feed in arbitrary chunks to exercise line reassembly.
+        let input = indoc! {"
+            Commit ID: abc
+            diff --git a/a b/a
+            index 0000000..1111111 100644
+            @@ -1,1 +1,1 @@
+            -a
+            +b
+            @@ -5,1 +5,1 @@
+             c
+            diff --git a/b b/b
+            @@ -1,1 +1,1 @@
+            -x
+        "};
+        for chunk in input.as_bytes().chunks(7) {
+            state.feed(chunk);
+        }
+        assert_eq!(state.line_count, 11);
+        assert_eq!(state.files, [1, 8]);
+        assert_eq!(state.hunks, [3, 6, 9]);
+
+        assert_eq!(state.advance(Targets::Files, Direction::Forward), Some(1));
+        assert_eq!(state.advance(Targets::Hunks, Direction::Forward), Some(3));
+        assert_eq!(state.advance(Targets::Hunks, Direction::Forward), Some(6));
+        assert_eq!(state.advance(Targets::Files, Direction::Forward), Some(8));
+        assert_eq!(state.advance(Targets::Files, Direction::Forward), None);
+        assert_eq!(state.advance(Targets::Hunks, Direction::Backward), Some(6));
+        assert_eq!(state.advance(Targets::Files, Direction::Backward), Some(1));
+        assert_eq!(state.advance(Targets::Files, Direction::Backward), None);
+    }
+
+    #[test]
+    fn test_outline_color_words() {
+        let mut state = OutlineState::default();
+        state.feed(indoc! {b"
+            Modified regular file a:
+               1    1: a
+                ...
+              10   10: b
+            Added regular file b:
+                    1: c
+        "});
+        assert_eq!(state.files, [0, 4]);
+        assert_eq!(state.hunks, [0, 2, 4]);
+    }
+}
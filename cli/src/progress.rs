@@ -1,12 +1,15 @@
 use std::path::Path;
+use std::sync::Arc;
 use std::sync::Mutex;
 use std::time::Duration;
 use std::time::Instant;
 
 use crossterm::terminal::Clear;
 use crossterm::terminal::ClearType;
+use jj_lib::repo::IndexingProgress;
 use jj_lib::repo_path::RepoPath;
 
+use crate::event_log::EventLog;
 use crate::text_util;
 use crate::ui::OutputGuard;
 use crate::ui::ProgressOutput;
@@ -64,3 +67,81 @@ pub fn snapshot_progress(ui: &Ui) -> Option<impl Fn(&RepoPath) + use<>> {
         _ = state.output.flush();
     })
 }
+
+/// Throttled progress output displaying a counter, e.g. "Copying commits:
+/// 1/10".
+///
+/// The progress is also reported as `progress` events if the event log is
+/// enabled, even if the progress isn't displayed in the terminal.
+struct CounterProgress {
+    output: Option<ProgressOutput<std::io::Stderr>>,
+    guard: Option<OutputGuard>,
+    event_log: Option<Arc<EventLog>>,
+    next_display_time: Instant,
+}
+
+impl CounterProgress {
+    fn new(ui: &Ui) -> Option<Self> {
+        let output = ui.progress_output();
+        let event_log = ui.event_log().cloned();
+        if output.is_none() && event_log.is_none() {
+            return None;
+        }
+        Some(CounterProgress {
+            output,
+            guard: None,
+            event_log,
+            // Don't clutter the output during fast operations.
+            next_display_time: Instant::now() + INITIAL_DELAY,
+        })
+    }
+
+    fn update(&mut self, label: &str, count: usize, total: Option<usize>) {
+        let now = Instant::now();
+        if now < self.next_display_time {
+            return;
+        }
+        self.next_display_time = now + Duration::from_secs(1) / UPDATE_HZ;
+
+        if let Some(event_log) = &self.event_log {
+            // Progress is informational, so a failure to report it is ignored.
+            _ = event_log.emit(
+                "progress",
+                serde_json::json!({ "label": label, "count": count, "total": total }),
+            );
+        }
+        let Some(output) = &mut self.output else {
+            return;
+        };
+        self.guard.get_or_insert_with(|| {
+            output.output_guard(format!("\r{}", Clear(ClearType::CurrentLine)))
+        });
+        let clear = Clear(ClearType::CurrentLine);
+        _ = match total {
+            Some(total) => write!(output, "\r{clear}{label}: {count}/{total}"),
+            None => write!(output, "\r{clear}{label}: {count}"),
+        };
+        _ = output.flush();
+    }
+}
+
+pub fn counter_progress(ui: &Ui, label: &str, total: usize) -> Option<impl FnMut(usize) + use<>> {
+    let mut progress = CounterProgress::new(ui)?;
+    let label = label.to_owned();
+    Some(move |count: usize| progress.update(&label, count, Some(total)))
+}
+
+pub fn indexing_progress(ui: &Ui) -> Option<impl FnMut(&IndexingProgress) + use<>> {
+    let mut counter = CounterProgress::new(ui)?;
+    Some(move |progress: &IndexingProgress| {
+        let IndexingProgress {
+            found_commits,
+            indexed_commits,
+        } = *progress;
+        if indexed_commits == 0 {
+            counter.update("Finding new commits", found_commits, None);
+        } else {
+            counter.update("Indexing commits", indexed_commits, Some(found_commits));
+        }
+    })
+}
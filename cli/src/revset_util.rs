@@ -44,6 +44,8 @@ use jj_lib::revset::SymbolResolverExtension;
 use jj_lib::revset::UserRevsetExpression;
 use thiserror::Error;
 
+use crate::alias_library::load_included_libraries;
+use crate::alias_library::INCLUDE_KEY;
 use crate::command_error::user_error;
 use crate::command_error::CommandError;
 use crate::formatter::Formatter;
@@ -184,18 +186,29 @@ pub fn load_revset_aliases(
                 .into());
             }
         };
-        for (decl, item) in table.iter() {
-            warn_user_redefined_builtin(ui, layer.source, decl)?;
+        // Included libraries are loaded first so the aliases of this layer
+        // override them.
+        let libraries = load_included_libraries(layer, &table_name)?;
+        let library_tables = libraries
+            .iter()
+            .filter_map(|library| library.look_up_table(&table_name).ok().flatten());
+        for table in library_tables.chain([table]) {
+            for (decl, item) in table.iter() {
+                if decl == INCLUDE_KEY && !item.is_str() {
+                    continue;
+                }
+                warn_user_redefined_builtin(ui, layer.source, decl)?;
 
-            let r = item
-                .as_str()
-                .ok_or_else(|| format!("Expected a string, but is {}", item.type_name()))
-                .and_then(|v| aliases_map.insert(decl, v).map_err(|e| e.to_string()));
-            if let Err(s) = r {
-                writeln!(
-                    ui.warning_default(),
-                    "Failed to load `{table_name}.{decl}`: {s}"
-                )?;
+                let r = item
+                    .as_str()
+                    .ok_or_else(|| format!("Expected a string, but is {}", item.type_name()))
+                    .and_then(|v| aliases_map.insert(decl, v).map_err(|e| e.to_string()));
+                if let Err(s) = r {
+                    writeln!(
+                        ui.warning_default(),
+                        "Failed to load `{table_name}.{decl}`: {s}"
+                    )?;
+                }
             }
         }
     }
@@ -0,0 +1,161 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Scanning of snapshotted content for credentials.
+
+use std::sync::Mutex;
+
+use itertools::Itertools as _;
+use jj_lib::fileset;
+use jj_lib::fileset::FilesetDiagnostics;
+use jj_lib::matchers::Matcher;
+use jj_lib::repo_path::RepoPath;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::repo_path::RepoPathUiConverter;
+use jj_lib::settings::UserSettings;
+use regex::bytes::Regex;
+
+use crate::command_error::config_error_with_message;
+use crate::command_error::print_parse_diagnostics;
+use crate::command_error::user_error_with_hint;
+use crate::command_error::CommandError;
+use crate::ui::Ui;
+
+/// What to do when a secret is found in the snapshotted content.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SecretScanAction {
+    /// Print a warning, but snapshot the content anyway.
+    Warn,
+    /// Refuse to snapshot the working copy.
+    Block,
+}
+
+/// Secret found in a snapshotted file.
+#[derive(Clone, Debug)]
+pub struct SecretFinding {
+    /// Path to the file containing the secret.
+    pub path: RepoPathBuf,
+    /// Name of the pattern that matched.
+    pub pattern_name: String,
+}
+
+/// Checks snapshotted content against the patterns configured in
+/// `snapshot.secret-scan`.
+pub struct SecretScanner {
+    action: SecretScanAction,
+    patterns: Vec<(String, Regex)>,
+    allowlist: Box<dyn Matcher>,
+    findings: Mutex<Vec<SecretFinding>>,
+}
+
+impl SecretScanner {
+    /// Loads the scanner from the settings. Returns `None` if scanning is
+    /// disabled.
+    pub fn from_settings(ui: &Ui, settings: &UserSettings) -> Result<Option<Self>, CommandError> {
+        if !settings.get_bool("snapshot.secret-scan.enabled")? {
+            return Ok(None);
+        }
+        let action = settings.get("snapshot.secret-scan.action")?;
+        let patterns = settings
+            .table_keys("snapshot.secret-scan.patterns")
+            .sorted()
+            .map(|name| -> Result<_, CommandError> {
+                let pattern: String =
+                    settings.get(["snapshot", "secret-scan", "patterns", name])?;
+                let regex = Regex::new(&pattern).map_err(|err| {
+                    config_error_with_message(
+                        format!("Invalid regex in `snapshot.secret-scan.patterns.{name}`"),
+                        err,
+                    )
+                })?;
+                Ok((name.to_owned(), regex))
+            })
+            .try_collect()?;
+        let mut diagnostics = FilesetDiagnostics::new();
+        let allowlist = fileset::parse(
+            &mut diagnostics,
+            &settings.get_string("snapshot.secret-scan.allowlist")?,
+            &RepoPathUiConverter::Fs {
+                cwd: "".into(),
+                base: "".into(),
+            },
+        )?;
+        print_parse_diagnostics(ui, "In `snapshot.secret-scan.allowlist`", &diagnostics)?;
+        Ok(Some(SecretScanner {
+            action,
+            patterns,
+            allowlist: allowlist.to_matcher(),
+            findings: Mutex::new(vec![]),
+        }))
+    }
+
+    /// Checks the `content` of the file at `path` before it's snapshotted, and
+    /// records the findings. Files matching the allowlist are skipped. Returns
+    /// an error if the snapshot should be blocked.
+    pub fn check_content(
+        &self,
+        path: &RepoPath,
+        content: &[u8],
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if self.allowlist.matches(path) {
+            return Ok(());
+        }
+        let Some((name, _)) = self
+            .patterns
+            .iter()
+            .find(|(_, regex)| regex.is_match(content))
+        else {
+            return Ok(());
+        };
+        self.findings.lock().unwrap().push(SecretFinding {
+            path: path.to_owned(),
+            pattern_name: name.clone(),
+        });
+        match self.action {
+            SecretScanAction::Warn => Ok(()),
+            SecretScanAction::Block => Err(format!("Possible secret ({name})").into()),
+        }
+    }
+
+    /// Reports the findings recorded while snapshotting. Returns an error if
+    /// the snapshot was blocked.
+    pub fn report_findings(
+        &self,
+        ui: &Ui,
+        path_converter: &RepoPathUiConverter,
+    ) -> Result<(), CommandError> {
+        let mut findings = std::mem::take(&mut *self.findings.lock().unwrap());
+        if findings.is_empty() {
+            return Ok(());
+        }
+        // Files are snapshotted in parallel
+        findings.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+        for finding in &findings {
+            writeln!(
+                ui.warning_default(),
+                "Possible secret ({}) in {}",
+                finding.pattern_name,
+                path_converter.format_file_path(&finding.path)
+            )?;
+        }
+        match self.action {
+            SecretScanAction::Warn => Ok(()),
+            SecretScanAction::Block => Err(user_error_with_hint(
+                "Refusing to snapshot the working copy since it contains possible secrets",
+                "Remove the secrets, or add the paths to `snapshot.secret-scan.allowlist`.",
+            )),
+        }
+    }
+}
@@ -20,10 +20,12 @@ use std::iter;
 use itertools::Itertools as _;
 use jj_lib::backend::Signature;
 use jj_lib::backend::Timestamp;
+use jj_lib::config::ConfigGetError;
 use jj_lib::config::ConfigNamePathBuf;
 use jj_lib::config::ConfigValue;
 use jj_lib::dsl_util::AliasExpandError as _;
 use jj_lib::settings::UserSettings;
+use jj_lib::str_util::StringPattern;
 use jj_lib::time_util::DatePattern;
 use serde::de::IntoDeserializer as _;
 use serde::Deserialize;
@@ -46,6 +48,7 @@ use crate::templater::CoalesceTemplate;
 use crate::templater::ConcatTemplate;
 use crate::templater::ConditionalTemplate;
 use crate::templater::Email;
+use crate::templater::HyperlinkTemplate;
 use crate::templater::LabelTemplate;
 use crate::templater::ListPropertyTemplate;
 use crate::templater::ListTemplate;
@@ -112,6 +115,19 @@ pub trait TemplateLanguage<'a> {
         property: Self::Property,
         function: &FunctionCallNode,
     ) -> TemplateParseResult<Self::Property>;
+
+    /// Wraps the top-level `template` evaluated against the `self_property`.
+    ///
+    /// This can be used to label the whole output depending on the `self`
+    /// value. The default implementation returns the `template` as is.
+    fn wrap_top_level_template(
+        &self,
+        _self_property: Self::Property,
+        template: Box<dyn Template + 'a>,
+        _span: pest::Span<'_>,
+    ) -> TemplateParseResult<Box<dyn Template + 'a>> {
+        Ok(template)
+    }
 }
 
 /// Implements `TemplateLanguage::wrap_<type>()` functions.
@@ -814,6 +830,22 @@ fn builtin_string_methods<'a, L: TemplateLanguage<'a> + ?Sized>(
             Ok(L::wrap_boolean(out_property))
         },
     );
+    map.insert(
+        "match",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            let [pattern_node] = function.expect_exact_arguments()?;
+            let pattern =
+                template_parser::expect_string_literal_with(pattern_node, |pattern, span| {
+                    StringPattern::parse(pattern).map_err(|err| {
+                        TemplateParseError::expression("Invalid string pattern", span)
+                            .with_source(err)
+                    })
+                })?;
+            let out_property =
+                self_property.map(move |haystack| pattern.find(&haystack).unwrap_or("").to_owned());
+            Ok(L::wrap_string(out_property))
+        },
+    );
     map.insert(
         "remove_prefix",
         |language, diagnostics, build_ctx, self_property, function| {
@@ -919,6 +951,19 @@ fn builtin_string_methods<'a, L: TemplateLanguage<'a> + ?Sized>(
             Ok(L::wrap_string(out_property))
         },
     );
+    map.insert(
+        "slug",
+        |_language, _diagnostics, _build_ctx, self_property, function| {
+            function.expect_no_arguments()?;
+            let out_property = self_property.map(|s| {
+                s.to_lowercase()
+                    .split(|c: char| !c.is_alphanumeric())
+                    .filter(|word| !word.is_empty())
+                    .join("-")
+            });
+            Ok(L::wrap_string(out_property))
+        },
+    );
     map.insert(
         "escape_json",
         |_language, _diagnostics, _build_ctx, self_property, function| {
@@ -1566,6 +1611,14 @@ fn builtin_functions<'a, L: TemplateLanguage<'a> + ?Sized>() -> TemplateBuildFun
             content, labels,
         ))))
     });
+    map.insert("hyperlink", |language, diagnostics, build_ctx, function| {
+        let [content_node, url_node] = function.expect_exact_arguments()?;
+        let content = expect_template_expression(language, diagnostics, build_ctx, content_node)?;
+        let url = expect_plain_text_expression(language, diagnostics, build_ctx, url_node)?;
+        Ok(L::wrap_template(Box::new(HyperlinkTemplate::new(
+            content, url,
+        ))))
+    });
     map.insert(
         "raw_escape_sequence",
         |language, diagnostics, build_ctx, function| {
@@ -1641,7 +1694,7 @@ fn builtin_functions<'a, L: TemplateLanguage<'a> + ?Sized>() -> TemplateBuildFun
     map.insert("config", |language, _diagnostics, _build_ctx, function| {
         // Dynamic lookup can be implemented if needed. The name is literal
         // string for now so the error can be reported early.
-        let [name_node] = function.expect_exact_arguments()?;
+        let ([name_node], [default_node]) = function.expect_arguments()?;
         let name: ConfigNamePathBuf =
             template_parser::expect_string_literal_with(name_node, |name, span| {
                 name.parse().map_err(|err| {
@@ -1649,16 +1702,46 @@ fn builtin_functions<'a, L: TemplateLanguage<'a> + ?Sized>() -> TemplateBuildFun
                         .with_source(err)
                 })
             })?;
-        let value = language.settings().get_value(&name).map_err(|err| {
-            TemplateParseError::expression("Failed to get config value", function.name_span)
-                .with_source(err)
-        })?;
+        let default = default_node.map(expect_config_value_literal).transpose()?;
+        let value = match (language.settings().get_value(&name), default) {
+            (Ok(value), _) => value,
+            (Err(ConfigGetError::NotFound { .. }), Some(default)) => default,
+            (Err(err), _) => {
+                return Err(TemplateParseError::expression(
+                    "Failed to get config value",
+                    function.name_span,
+                )
+                .with_source(err));
+            }
+        };
         // .decorated("", "") to trim leading/trailing whitespace
         Ok(L::wrap_config_value(Literal(value.decorated("", ""))))
     });
     map
 }
 
+/// Converts boolean, integer, or string literal to config value.
+fn expect_config_value_literal(node: &ExpressionNode) -> TemplateParseResult<ConfigValue> {
+    match &node.kind {
+        ExpressionKind::Boolean(value) => Ok((*value).into()),
+        ExpressionKind::Integer(value) => Ok((*value).into()),
+        ExpressionKind::String(value) => Ok(value.as_str().into()),
+        ExpressionKind::Identifier(_)
+        | ExpressionKind::Unary(..)
+        | ExpressionKind::Binary(..)
+        | ExpressionKind::Concat(_)
+        | ExpressionKind::FunctionCall(_)
+        | ExpressionKind::MethodCall(_)
+        | ExpressionKind::Lambda(_) => Err(TemplateParseError::expression(
+            "Expected boolean, integer, or string literal",
+            node.span,
+        )),
+        ExpressionKind::AliasExpanded(id, subst) => {
+            expect_config_value_literal(subst).map_err(|e| e.within_alias_expansion(*id, node.span))
+        }
+    }
+}
+
 fn new_pad_template<'a, W>(
     content: Box<dyn Template + 'a>,
     fill_char: Option<Box<dyn Template + 'a>>,
@@ -1831,6 +1914,8 @@ pub fn build<'a, C: Clone + 'a, L: TemplateLanguage<'a> + ?Sized>(
         self_variable: &|| wrap_self(self_placeholder.clone()),
     };
     let template = expect_template_expression(language, diagnostics, &build_ctx, node)?;
+    let template =
+        language.wrap_top_level_template((build_ctx.self_variable)(), template, node.span)?;
     Ok(TemplateRenderer::new(template, self_placeholder))
 }
 
@@ -2045,7 +2130,8 @@ mod tests {
             let template = self.parse(template).unwrap();
             let mut output = Vec::new();
             let mut formatter =
-                ColorFormatter::new(&mut output, self.color_rules.clone().into(), false);
+                ColorFormatter::new(&mut output, self.color_rules.clone().into(), false)
+                    .with_hyperlinks(true);
             template.format(&(), &mut formatter).unwrap();
             drop(formatter);
             String::from_utf8(output).unwrap()
@@ -2648,6 +2734,11 @@ mod tests {
         insta::assert_snapshot!(env.render_ok(r#""".lines()"#), @"");
         insta::assert_snapshot!(env.render_ok(r#""a\nb\nc\n".lines()"#), @"a b c");
 
+        insta::assert_snapshot!(env.render_ok(r#""".slug()"#), @"");
+        insta::assert_snapshot!(env.render_ok(r#""Fix the Bug!".slug()"#), @"fix-the-bug");
+        insta::assert_snapshot!(
+            env.render_ok(r#""  feat(cli): add `--foo` ".slug()"#), @"feat-cli-add-foo");
+
         insta::assert_snapshot!(env.render_ok(r#""".starts_with("")"#), @"true");
         insta::assert_snapshot!(env.render_ok(r#""everything".starts_with("")"#), @"true");
         insta::assert_snapshot!(env.render_ok(r#""".starts_with("foo")"#), @"false");
@@ -2662,6 +2753,30 @@ mod tests {
         insta::assert_snapshot!(env.render_ok(r#""foobar".ends_with("foo")"#), @"false");
         insta::assert_snapshot!(env.render_ok(r#""foobar".ends_with("bar")"#), @"true");
 
+        insta::assert_snapshot!(env.render_ok(r#""foo".match("foo")"#), @"foo");
+        insta::assert_snapshot!(env.render_ok(r#""foobar".match("foo")"#), @"");
+        insta::assert_snapshot!(env.render_ok(r#""foobar".match("substring:ob")"#), @"ob");
+        insta::assert_snapshot!(env.render_ok(r#""foobar".match("glob:f*r")"#), @"foobar");
+        insta::assert_snapshot!(
+            env.render_ok(r#""push-PROJ-123-fix".match("regex:[A-Z]+-[0-9]+")"#), @"PROJ-123");
+        insta::assert_snapshot!(env.render_ok(r#""foobar".match("regex:[0-9]+")"#), @"");
+        insta::assert_snapshot!(env.parse_err(r#""foo".match("bad:foo")"#), @r#"
+         --> 1:13
+          |
+        1 | "foo".match("bad:foo")
+          |             ^-------^
+          |
+          = Invalid string pattern
+        "#);
+        insta::assert_snapshot!(env.parse_err(r#""foo".match(description)"#), @r#"
+         --> 1:13
+          |
+        1 | "foo".match(description)
+          |             ^---------^
+          |
+          = Expected string literal
+        "#);
+
         insta::assert_snapshot!(env.render_ok(r#""".remove_prefix("wip: ")"#), @"");
         insta::assert_snapshot!(
             env.render_ok(r#""wip: testing".remove_prefix("wip: ")"#),
@@ -3174,6 +3289,45 @@ mod tests {
             @r"]8;;http://example.com\Example]8;;\");
     }
 
+    #[test]
+    fn test_hyperlink_function() {
+        let mut env = TestTemplateEnv::new();
+        env.add_color("error", crossterm::style::Color::DarkRed);
+        env.add_keyword("url", || {
+            L::wrap_string(Literal("http://example.com".to_owned()))
+        });
+        env.add_keyword("bad_url", || L::wrap_string(new_error_property("Bad")));
+        let render = |template| env.render_ok(template).replace('\x1b', "␛");
+
+        insta::assert_snapshot!(
+            render(r#"hyperlink("Example", url)"#),
+            @r"
+        ␛]8;;http://example.com␛\Example␛]8;;␛\
+        ");
+        insta::assert_snapshot!(
+            render(r#"hyperlink(label("error", "Example"), url)"#),
+            @r"
+        ␛]8;;http://example.com␛\␛[38;5;1mExample␛[39m␛]8;;␛\
+        ");
+        insta::assert_snapshot!(
+            render(r#"hyperlink("Example", "http://example.com/a b\e")"#),
+            @r"
+        ␛]8;;http://example.com/a%20b%1B␛\Example␛]8;;␛\
+        ");
+        insta::assert_snapshot!(
+            render(r#"hyperlink("outer " ++ hyperlink("inner", "b") ++ " outer", "a")"#),
+            @r"
+        ␛]8;;a␛\outer ␛]8;;b␛\inner␛]8;;a␛\ outer␛]8;;␛\
+        ");
+        insta::assert_snapshot!(render(r#"hyperlink("Example", "")"#), @"Example");
+        insta::assert_snapshot!(render(r#"hyperlink("Example", bad_url)"#), @"␛[38;5;1m<Error: Bad>␛[39m");
+        insta::assert_snapshot!(
+            render(r#"separate(" ", hyperlink("a", url), "b")"#),
+            @r"
+        ␛]8;;http://example.com␛\a␛]8;;␛\ b
+        ");
+    }
+
     #[test]
     fn test_stringify_function() {
         let mut env = TestTemplateEnv::new();
@@ -229,6 +229,41 @@ where
     }
 }
 
+/// Links content to the URL. If the URL is empty, the content is rendered
+/// as is.
+pub struct HyperlinkTemplate<T, P> {
+    content: T,
+    url: P,
+}
+
+impl<T, P> HyperlinkTemplate<T, P> {
+    pub fn new(content: T, url: P) -> Self
+    where
+        T: Template,
+        P: TemplateProperty<Output = String>,
+    {
+        HyperlinkTemplate { content, url }
+    }
+}
+
+impl<T, P> Template for HyperlinkTemplate<T, P>
+where
+    T: Template,
+    P: TemplateProperty<Output = String>,
+{
+    fn format(&self, formatter: &mut TemplateFormatter) -> io::Result<()> {
+        match self.url.extract() {
+            Ok(url) if url.is_empty() => self.content.format(formatter),
+            Ok(url) => {
+                formatter.push_hyperlink(&url)?;
+                self.content.format(formatter)?;
+                formatter.pop_hyperlink()
+            }
+            Err(err) => formatter.handle_error(err),
+        }
+    }
+}
+
 pub struct RawEscapeSequenceTemplate<T>(pub T);
 
 impl<T: Template> Template for RawEscapeSequenceTemplate<T> {
@@ -732,6 +767,8 @@ impl<'a, C: Clone> TemplateRenderer<'a, C> {
 pub struct TemplateFormatter<'a> {
     formatter: &'a mut dyn Formatter,
     error_handler: PropertyErrorHandler,
+    /// The stack of labels pushed by the template.
+    labels: Vec<String>,
 }
 
 impl<'a> TemplateFormatter<'a> {
@@ -739,17 +776,29 @@ impl<'a> TemplateFormatter<'a> {
         TemplateFormatter {
             formatter,
             error_handler,
+            labels: vec![],
         }
     }
 
     /// Returns function that wraps another `Formatter` with the current error
-    /// handling strategy.
+    /// handling strategy and labels.
     ///
     /// This does not borrow `self` so the underlying formatter can be mutably
     /// borrowed.
     pub fn rewrap_fn(&self) -> impl Fn(&mut dyn Formatter) -> TemplateFormatter<'_> + use<> {
         let error_handler = self.error_handler;
-        move |formatter| TemplateFormatter::new(formatter, error_handler)
+        let labels = self.labels.clone();
+        move |formatter| TemplateFormatter {
+            formatter,
+            error_handler,
+            labels: labels.clone(),
+        }
+    }
+
+    /// Labels pushed by the template (and its renderer) so far, outermost
+    /// first.
+    pub fn labels(&self) -> &[String] {
+        &self.labels
     }
 
     pub fn raw(&mut self) -> io::Result<Box<dyn Write + '_>> {
@@ -764,13 +813,23 @@ impl<'a> TemplateFormatter<'a> {
     }
 
     pub fn push_label(&mut self, label: &str) -> io::Result<()> {
+        self.labels.push(label.to_owned());
         self.formatter.push_label(label)
     }
 
     pub fn pop_label(&mut self) -> io::Result<()> {
+        self.labels.pop();
         self.formatter.pop_label()
     }
 
+    pub fn push_hyperlink(&mut self, url: &str) -> io::Result<()> {
+        self.formatter.push_hyperlink(url)
+    }
+
+    pub fn pop_hyperlink(&mut self) -> io::Result<()> {
+        self.formatter.pop_hyperlink()
+    }
+
     pub fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> io::Result<()> {
         self.formatter.write_fmt(args)
     }
@@ -0,0 +1,229 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Persistent store of test results recorded by `jj test`.
+//!
+//! The outcome of running a test command only depends on the files it runs
+//! against, so results are recorded by tree id. Commits that are rewritten
+//! without changing their contents (e.g. by rewording the description) keep
+//! their test results.
+//!
+//! The results can be queried by the `tested()` and `failing()` revset
+//! functions, and by the `commit.test_result()` template method.
+
+use std::any::Any;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use jj_lib::backend::MergedTreeId;
+use jj_lib::commit::Commit;
+use jj_lib::content_hash::blake2b_hash;
+use jj_lib::extensions_map::ExtensionsMap;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::revset::RevsetExtensions;
+use jj_lib::revset::RevsetFilterExtension;
+use jj_lib::revset::RevsetFilterPredicate;
+use jj_lib::revset::UserRevsetExpression;
+
+use crate::commit_templater::CommitTemplateBuildFnTable;
+use crate::commit_templater::CommitTemplateLanguage;
+use crate::commit_templater::CommitTemplateLanguageExtension;
+use crate::keyed_cache;
+use crate::keyed_cache::CacheValue;
+use crate::keyed_cache::KeyedCache;
+use crate::template_builder::TemplateLanguage as _;
+use crate::templater::TemplatePropertyExt as _;
+
+/// Result of running a test command against a tree.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TestResult {
+    pub passed: bool,
+    /// The command that was run, used to tell whether the result is stale.
+    pub command: String,
+}
+
+impl CacheValue for TestResult {
+    fn encode(&self) -> String {
+        let outcome = if self.passed { "passed" } else { "failed" };
+        format!("{outcome} {}", self.command)
+    }
+
+    fn decode(data: &str) -> Option<Self> {
+        let (outcome, command) = data.split_once(' ')?;
+        let passed = match outcome {
+            "passed" => true,
+            "failed" => false,
+            _ => return None,
+        };
+        Some(TestResult {
+            passed,
+            command: command.to_owned(),
+        })
+    }
+}
+
+/// Stores test results by tree id.
+#[derive(Debug)]
+pub struct TestResults {
+    cache: KeyedCache<TestResult>,
+}
+
+impl TestResults {
+    pub fn new(path: PathBuf) -> Self {
+        TestResults {
+            cache: KeyedCache::new(path),
+        }
+    }
+
+    /// Test results of the repo at `repo_path`.
+    pub fn for_repo(repo_path: &Path) -> Self {
+        Self::new(repo_path.join("test_results"))
+    }
+
+    /// Looks up the last recorded result for the `tree`.
+    pub fn get(&self, tree: &MergedTreeId) -> Option<TestResult> {
+        self.cache.get(&tree_key(tree))
+    }
+
+    /// Records the result for the `tree`, replacing any previous result.
+    pub fn insert(&self, tree: &MergedTreeId, result: &TestResult) -> io::Result<()> {
+        self.cache.update([(tree_key(tree), Some(result.clone()))])
+    }
+
+    /// Removes all recorded results.
+    pub fn clear(&self) -> io::Result<()> {
+        self.cache.clear()
+    }
+}
+
+fn tree_key(tree: &MergedTreeId) -> Vec<u8> {
+    // Legacy and merge tree ids of the same tree are considered equal
+    blake2b_hash(&tree.to_merge()).to_vec()
+}
+
+/// Registers the `tested()` and `failing()` revset functions.
+///
+/// The functions look up the [`TestResults`] in the extension data of the
+/// parse context, which is populated by [`insert_revset_extension_data()`].
+pub(crate) fn add_revset_functions(extensions: &mut RevsetExtensions) {
+    extensions.add_custom_function("tested", |_diagnostics, function, context| {
+        function.expect_no_arguments()?;
+        let results = keyed_cache::get_revset_extension_data(function, context, "Test results")?;
+        Ok(test_result_filter(results, false))
+    });
+    extensions.add_custom_function("failing", |_diagnostics, function, context| {
+        function.expect_no_arguments()?;
+        let results = keyed_cache::get_revset_extension_data(function, context, "Test results")?;
+        Ok(test_result_filter(results, true))
+    });
+}
+
+/// Makes the test results of the repo at `repo_path` available to the revset
+/// functions.
+pub(crate) fn insert_revset_extension_data(data: &mut ExtensionsMap, repo_path: &Path) {
+    keyed_cache::insert_revset_extension_data(data, TestResults::for_repo(repo_path));
+}
+
+fn test_result_filter(results: Arc<TestResults>, failing_only: bool) -> Rc<UserRevsetExpression> {
+    RevsetExpression::filter(RevsetFilterPredicate::Extension(Rc::new(
+        TestResultFilter {
+            results,
+            failing_only,
+        },
+    )))
+}
+
+/// Matches commits whose tree has a recorded (failing) test result.
+#[derive(Debug)]
+struct TestResultFilter {
+    results: Arc<TestResults>,
+    failing_only: bool,
+}
+
+impl RevsetFilterExtension for TestResultFilter {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn matches_commit(&self, commit: &Commit) -> bool {
+        self.results
+            .get(commit.tree_id())
+            .is_some_and(|result| !self.failing_only || !result.passed)
+    }
+}
+
+/// Provides the `commit.test_result()` template method.
+pub(crate) struct TestResultTemplateExtension;
+
+impl CommitTemplateLanguageExtension for TestResultTemplateExtension {
+    fn build_fn_table<'repo>(&self) -> CommitTemplateBuildFnTable<'repo> {
+        type L<'repo> = CommitTemplateLanguage<'repo>;
+        let mut table = CommitTemplateBuildFnTable::empty();
+        table.commit_methods.insert(
+            "test_result",
+            |language, _diagnostics, _build_ctx, self_property, function| {
+                function.expect_no_arguments()?;
+                let results = TestResults::for_repo(language.repo_path());
+                let out_property =
+                    self_property.map(move |commit| match results.get(commit.tree_id()) {
+                        Some(result) if result.passed => "passed".to_owned(),
+                        Some(_) => "failed".to_owned(),
+                        None => String::new(),
+                    });
+                Ok(L::wrap_string(out_property))
+            },
+        );
+        table
+    }
+
+    fn build_cache_extensions(&self, _extensions: &mut ExtensionsMap) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use jj_lib::backend::TreeId;
+
+    use super::*;
+
+    #[test]
+    fn test_insert_and_get() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let results = TestResults::new(temp_dir.path().join("test_results"));
+        let tree1 = MergedTreeId::resolved(TreeId::new(vec![1]));
+        let tree2 = MergedTreeId::resolved(TreeId::new(vec![2]));
+        assert_eq!(results.get(&tree1), None);
+
+        let passed = TestResult {
+            passed: true,
+            command: "make check".to_owned(),
+        };
+        results.insert(&tree1, &passed).unwrap();
+        assert_eq!(results.get(&tree1), Some(passed));
+        assert_eq!(results.get(&tree2), None);
+
+        // A new result replaces the old one
+        let failed = TestResult {
+            passed: false,
+            command: "make check".to_owned(),
+        };
+        results.insert(&tree1, &failed).unwrap();
+        assert_eq!(results.get(&tree1), Some(failed));
+
+        results.clear().unwrap();
+        assert_eq!(results.get(&tree1), None);
+    }
+}
@@ -0,0 +1,336 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Built-in color themes and terminal background detection.
+
+use std::env;
+use std::io;
+use std::io::IsTerminal as _;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use jj_lib::config::ConfigGetError;
+use jj_lib::config::ConfigLayer;
+use jj_lib::config::ConfigSource;
+use jj_lib::config::StackedConfig;
+
+use crate::ui::ColorChoice;
+
+/// Built-in color theme consisting of config layers for dark and light
+/// terminal backgrounds.
+#[derive(Clone, Copy, Debug)]
+pub struct Theme {
+    pub name: &'static str,
+    pub description: &'static str,
+    dark: &'static str,
+    light: &'static str,
+}
+
+impl Theme {
+    /// Returns the `[colors]` config text of the variant for the given
+    /// `background`.
+    pub fn variant(&self, background: Background) -> &'static str {
+        match background {
+            Background::Dark => self.dark,
+            Background::Light => self.light,
+        }
+    }
+
+    /// Whether the dark and light variants differ.
+    pub fn has_variants(&self) -> bool {
+        self.dark != self.light
+    }
+
+    /// Parses the variant for the given `background` as a default config
+    /// layer.
+    pub fn to_layer(&self, background: Background) -> ConfigLayer {
+        // Syntax error in built-in themes isn't a user error.
+        ConfigLayer::parse(ConfigSource::Default, self.variant(background)).unwrap()
+    }
+}
+
+/// Themes shipped with `jj`. The colors of the `default` theme are defined
+/// by the default config.
+pub const BUILTIN_THEMES: &[Theme] = &[
+    Theme {
+        name: "default",
+        description: "The default colors, which work on dark and light backgrounds",
+        dark: "",
+        light: "",
+    },
+    Theme {
+        name: "high-contrast",
+        description: "Bright colors on dark backgrounds, and bold dark colors on light backgrounds",
+        dark: include_str!("config/themes/high-contrast-dark.toml"),
+        light: include_str!("config/themes/high-contrast-light.toml"),
+    },
+    Theme {
+        name: "solarized",
+        description: "Colors from the Solarized palette",
+        dark: include_str!("config/themes/solarized-dark.toml"),
+        light: include_str!("config/themes/solarized-light.toml"),
+    },
+];
+
+/// Looks up built-in theme by `name`.
+pub fn find_theme(name: &str) -> Option<&'static Theme> {
+    BUILTIN_THEMES.iter().find(|theme| theme.name == name)
+}
+
+/// Terminal background brightness.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Background {
+    Dark,
+    Light,
+}
+
+impl Background {
+    pub fn name(self) -> &'static str {
+        match self {
+            Background::Dark => "dark",
+            Background::Light => "light",
+        }
+    }
+}
+
+/// Value of `ui.theme-background`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum BackgroundChoice {
+    Auto,
+    Dark,
+    Light,
+}
+
+/// Adds config layer of the theme selected by `ui.theme` to the `config`.
+///
+/// If `ui.theme-background` is `auto`, the terminal background is detected
+/// once and cached in `detected_background`.
+pub fn add_theme_layer(
+    config: &mut StackedConfig,
+    detected_background: &OnceLock<Background>,
+) -> Result<(), ConfigGetError> {
+    let name: String = config.get("ui.theme")?;
+    let theme = find_theme(&name).ok_or_else(|| ConfigGetError::Type {
+        name: "ui.theme".to_owned(),
+        error: format!("Unknown theme {name:?}").into(),
+        source_path: None,
+    })?;
+    if !theme.has_variants() {
+        if !theme.dark.is_empty() {
+            config.add_layer(theme.to_layer(Background::Dark));
+        }
+        return Ok(());
+    }
+    let background = match config.get("ui.theme-background")? {
+        BackgroundChoice::Dark => Background::Dark,
+        BackgroundChoice::Light => Background::Light,
+        BackgroundChoice::Auto => *detected_background.get_or_init(|| {
+            let query_terminal = uses_terminal_colors(config);
+            detect_background(query_terminal).unwrap_or(Background::Dark)
+        }),
+    };
+    config.add_layer(theme.to_layer(background));
+    Ok(())
+}
+
+/// Returns true if colored output will be printed to terminal.
+fn uses_terminal_colors(config: &StackedConfig) -> bool {
+    match config.get("ui.color") {
+        Ok(ColorChoice::Always | ColorChoice::Debug | ColorChoice::Auto) => {
+            io::stdout().is_terminal() && io::stdin().is_terminal()
+        }
+        Ok(ColorChoice::Never) | Err(_) => false,
+    }
+}
+
+/// Detects terminal background from the `COLORFGBG` environment variable, or
+/// by querying the terminal if `query_terminal` is true.
+pub fn detect_background(query_terminal: bool) -> Option<Background> {
+    if let Some(background) = env::var("COLORFGBG")
+        .ok()
+        .and_then(|value| parse_colorfgbg(&value))
+    {
+        return Some(background);
+    }
+    if query_terminal && term_answers_queries(env::var("TERM").ok().as_deref()) {
+        query_terminal_background()
+    } else {
+        None
+    }
+}
+
+/// Returns true if the terminal identified by `TERM` is expected to answer
+/// the background color query. Terminals that ignore the query might not
+/// answer the device attributes query either, in which case a late response
+/// would end up in the shell's input.
+fn term_answers_queries(term: Option<&str>) -> bool {
+    match term {
+        None | Some("" | "dumb" | "linux" | "cons25" | "emacs") => false,
+        Some(term) => !term.starts_with("vt1") && !term.starts_with("vt2"),
+    }
+}
+
+/// Parses `COLORFGBG` value such as `15;0`, in which the last field is the
+/// ANSI color index of the background.
+fn parse_colorfgbg(value: &str) -> Option<Background> {
+    let index: u8 = value.rsplit(';').next()?.parse().ok()?;
+    match index {
+        0..=6 | 8 => Some(Background::Dark),
+        7 | 9..=15 => Some(Background::Light),
+        _ => None,
+    }
+}
+
+/// Parses response to the `OSC 11` query, which looks like
+/// `ESC ] 11 ; rgb:RRRR/GGGG/BBBB ESC \`.
+fn parse_osc_background(response: &[u8]) -> Option<Background> {
+    let response = std::str::from_utf8(response).ok()?;
+    let (_, rgb) = response.split_once("rgb:")?;
+    let rgb = rgb.split(['\x07', '\x1b']).next()?;
+    let mut components = rgb.split('/').map(|hex| {
+        if hex.is_empty() || hex.len() > 4 {
+            return None;
+        }
+        let value = u32::from_str_radix(hex, 16).ok()?;
+        let max = (1_u32 << (4 * hex.len())) - 1;
+        Some(f64::from(value) / f64::from(max))
+    });
+    let (r, g, b) = (
+        components.next()??,
+        components.next()??,
+        components.next()??,
+    );
+    let luminance = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    if luminance < 0.5 {
+        Some(Background::Dark)
+    } else {
+        Some(Background::Light)
+    }
+}
+
+/// Maximum time to wait for the device attributes response, which is sent
+/// after the background color response. The response usually arrives within a
+/// few milliseconds, but can be delayed over slow connections. We wait for it
+/// in full so that it doesn't leak into the shell after we exit.
+const QUERY_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[cfg(unix)]
+fn query_terminal_background() -> Option<Background> {
+    use std::fs::File;
+    use std::io::Read as _;
+    use std::io::Write as _;
+    use std::os::fd::AsRawFd as _;
+    use std::time::Instant;
+
+    let mut tty = File::options()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+    crossterm::terminal::enable_raw_mode().ok()?;
+    // Request the background color followed by the primary device
+    // attributes. Since most terminals respond to the latter, we don't have
+    // to wait for the timeout if the former isn't supported.
+    let mut response = Vec::new();
+    if tty.write_all(b"\x1b]11;?\x1b\\\x1b[c").is_ok() && tty.flush().is_ok() {
+        let deadline = Instant::now() + QUERY_TIMEOUT;
+        let mut buf = [0; 64];
+        loop {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            let mut pollfd = libc::pollfd {
+                fd: tty.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            let timeout_ms = libc::c_int::try_from(timeout.as_millis()).unwrap_or(0);
+            // SAFETY: pollfd is a valid pointer to a single pollfd struct.
+            if timeout.is_zero() || unsafe { libc::poll(&mut pollfd, 1, timeout_ms) } <= 0 {
+                break;
+            }
+            match tty.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => response.extend_from_slice(&buf[..n]),
+            }
+            // The device attributes response is terminated by "c".
+            if let Some(pos) = response.windows(3).position(|w| w == b"\x1b[?") {
+                if response[pos..].contains(&b'c') {
+                    break;
+                }
+            }
+        }
+    }
+    // Discard any unread part of the responses.
+    // SAFETY: tty is an open file descriptor.
+    unsafe { libc::tcflush(tty.as_raw_fd(), libc::TCIFLUSH) };
+    crossterm::terminal::disable_raw_mode().ok();
+    parse_osc_background(&response)
+}
+
+#[cfg(not(unix))]
+fn query_terminal_background() -> Option<Background> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_themes_parse() {
+        for theme in BUILTIN_THEMES {
+            theme.to_layer(Background::Dark);
+            theme.to_layer(Background::Light);
+        }
+    }
+
+    #[test]
+    fn test_parse_colorfgbg() {
+        assert_eq!(parse_colorfgbg("15;0"), Some(Background::Dark));
+        assert_eq!(parse_colorfgbg("0;15"), Some(Background::Light));
+        assert_eq!(parse_colorfgbg("0;default;7"), Some(Background::Light));
+        assert_eq!(parse_colorfgbg("15;default"), None);
+        assert_eq!(parse_colorfgbg(""), None);
+    }
+
+    #[test]
+    fn test_term_answers_queries() {
+        assert!(term_answers_queries(Some("xterm-256color")));
+        assert!(term_answers_queries(Some("tmux-256color")));
+        assert!(!term_answers_queries(Some("dumb")));
+        assert!(!term_answers_queries(Some("linux")));
+        assert!(!term_answers_queries(Some("vt100")));
+        assert!(!term_answers_queries(Some("")));
+        assert!(!term_answers_queries(None));
+    }
+
+    #[test]
+    fn test_parse_osc_background() {
+        assert_eq!(
+            parse_osc_background(b"\x1b]11;rgb:0000/0000/0000\x1b\\\x1b[?62c"),
+            Some(Background::Dark)
+        );
+        assert_eq!(
+            parse_osc_background(b"\x1b]11;rgb:ffff/ffff/ffff\x07"),
+            Some(Background::Light)
+        );
+        assert_eq!(
+            parse_osc_background(b"\x1b]11;rgb:fd/f6/e3\x07"),
+            Some(Background::Light)
+        );
+        assert_eq!(parse_osc_background(b"\x1b[?62c"), None);
+        assert_eq!(parse_osc_background(b"\x1b]11;rgb:zz/00/00\x07"), None);
+    }
+}
@@ -0,0 +1,164 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Scaffolding shared by the full-screen terminal UIs, such as the builtin
+//! 3-pane merge tool and the commit picker.
+//!
+//! The UIs keep their state independent of the terminal, so that key handling
+//! and rendering can be tested without one.
+
+use std::io;
+use std::ops::ControlFlow;
+
+use ratatui::crossterm::event;
+use ratatui::crossterm::event::Event;
+use ratatui::crossterm::event::KeyCode;
+use ratatui::crossterm::event::KeyEvent;
+use ratatui::crossterm::event::KeyEventKind;
+use ratatui::crossterm::event::KeyModifiers;
+use ratatui::layout::Constraint;
+use ratatui::layout::Layout;
+use ratatui::layout::Rect;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::widgets::Paragraph;
+use ratatui::DefaultTerminal;
+use ratatui::Frame;
+
+/// Movement of the selection between items.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Motion {
+    Next,
+    Prev,
+}
+
+impl Motion {
+    /// Returns the motion bound to `key`, if any.
+    pub fn from_key(key: &KeyEvent) -> Option<Self> {
+        match key.code {
+            KeyCode::Char('j' | 'n') | KeyCode::Down => Some(Motion::Next),
+            KeyCode::Char('k' | 'p') | KeyCode::Up => Some(Motion::Prev),
+            _ => None,
+        }
+    }
+
+    /// Moves the `current` index within `0..len`, stopping at either end.
+    pub fn apply(self, current: &mut usize, len: usize) {
+        match self {
+            Motion::Next => {
+                if *current + 1 < len {
+                    *current += 1;
+                }
+            }
+            Motion::Prev => *current = current.saturating_sub(1),
+        }
+    }
+}
+
+/// Returns true if `key` is Ctrl-C, which always cancels the UI.
+pub fn is_interrupt(key: &KeyEvent) -> bool {
+    key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
+/// Style of the selected item.
+pub fn selected_style() -> Style {
+    Style::new().add_modifier(Modifier::REVERSED)
+}
+
+/// Splits `area` into the main area and a one-line status area below it.
+pub fn split_status_line(area: Rect) -> [Rect; 2] {
+    Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(area)
+}
+
+/// Renders `text`, e.g. the key bindings, in the status line.
+pub fn render_status_line(frame: &mut Frame, area: Rect, text: &str) {
+    frame.render_widget(
+        Paragraph::new(text).style(Style::new().add_modifier(Modifier::DIM)),
+        area,
+    );
+}
+
+/// Terminal in which the UI is running.
+pub struct TerminalSession {
+    terminal: DefaultTerminal,
+}
+
+impl TerminalSession {
+    /// Leaves the UI while `f` runs, e.g. to launch an editor, and redraws it
+    /// from scratch afterwards.
+    pub fn suspend<T>(&mut self, f: impl FnOnce() -> T) -> io::Result<T> {
+        ratatui::restore();
+        let value = f();
+        self.terminal = ratatui::try_init()?;
+        self.terminal.clear()?;
+        Ok(value)
+    }
+}
+
+/// Runs the UI in the terminal until `handle_key` breaks out of the loop.
+///
+/// The `state` is rendered by `render` before each key press. The terminal is
+/// restored even if an error occurs.
+pub fn run_event_loop<S, T, E>(
+    state: &mut S,
+    render: impl Fn(&mut Frame, &S),
+    mut handle_key: impl FnMut(&mut S, KeyEvent, &mut TerminalSession) -> Result<ControlFlow<T>, E>,
+) -> Result<T, E>
+where
+    E: From<io::Error>,
+{
+    let mut session = TerminalSession {
+        terminal: ratatui::try_init()?,
+    };
+    let result = loop {
+        if let Err(err) = session.terminal.draw(|frame| render(frame, state)) {
+            break Err(err.into());
+        }
+        let key = match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => key,
+            Ok(_) => continue,
+            Err(err) => break Err(err.into()),
+        };
+        match handle_key(state, key, &mut session) {
+            Ok(ControlFlow::Continue(())) => {}
+            Ok(ControlFlow::Break(value)) => break Ok(value),
+            Err(err) => break Err(err),
+        }
+    };
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_motion() {
+        let motion = |code| Motion::from_key(&KeyEvent::from(code));
+        assert_eq!(motion(KeyCode::Char('j')), Some(Motion::Next));
+        assert_eq!(motion(KeyCode::Up), Some(Motion::Prev));
+        assert_eq!(motion(KeyCode::Char('x')), None);
+
+        // The selection stops at either end
+        let mut current = 0;
+        Motion::Prev.apply(&mut current, 2);
+        assert_eq!(current, 0);
+        Motion::Next.apply(&mut current, 2);
+        Motion::Next.apply(&mut current, 2);
+        assert_eq!(current, 1);
+        Motion::Next.apply(&mut current, 0);
+        assert_eq!(current, 1);
+    }
+}
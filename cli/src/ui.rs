@@ -27,6 +27,7 @@ use std::mem;
 use std::process::Child;
 use std::process::ChildStdin;
 use std::process::Stdio;
+use std::sync::Arc;
 use std::thread;
 use std::thread::JoinHandle;
 
@@ -38,11 +39,14 @@ use tracing::instrument;
 
 use crate::command_error::CommandError;
 use crate::config::CommandNameAndArgs;
+use crate::event_log::EventLog;
 use crate::formatter::Formatter;
 use crate::formatter::FormatterFactory;
 use crate::formatter::HeadingLabeledWriter;
 use crate::formatter::LabeledWriter;
 use crate::formatter::PlainTextFormatter;
+use crate::pager_outline::DiffOutline;
+use crate::pager_outline::OutlineWriter;
 
 const BUILTIN_PAGER_NAME: &str = ":builtin";
 
@@ -58,6 +62,7 @@ enum UiOutput {
     BuiltinPaged {
         out_wr: PipeWriter,
         err_wr: PipeWriter,
+        outline: Arc<DiffOutline>,
         pager_thread: JoinHandle<streampager::Result<()>>,
     },
     Null,
@@ -97,12 +102,18 @@ impl UiOutput {
         // wouldn't matter.
         let (out_rd, out_wr) = os_pipe::pipe()?;
         let (err_rd, err_wr) = os_pipe::pipe()?;
-        pager.add_stream(out_rd, "")?;
+        let out_index = pager.add_stream(out_rd, "")?;
         pager.add_error_stream(err_rd, "stderr")?;
 
+        // Keep track of diff headers written to stdout so the pager can jump
+        // between files and hunks.
+        let outline = DiffOutline::new();
+        pager.set_keymap(outline.keymap(out_index, pager.action_sender()));
+
         Ok(UiOutput::BuiltinPaged {
             out_wr,
             err_wr,
+            outline,
             pager_thread: thread::spawn(|| pager.run()),
         })
     }
@@ -131,6 +142,7 @@ impl UiOutput {
                 out_wr,
                 err_wr,
                 pager_thread,
+                ..
             } => {
                 drop(out_wr);
                 drop(err_wr);
@@ -157,7 +169,7 @@ impl UiOutput {
 pub enum UiStdout<'a> {
     Terminal(StdoutLock<'static>),
     Paged(&'a ChildStdin),
-    Builtin(&'a PipeWriter),
+    Builtin(OutlineWriter<'a, &'a PipeWriter>),
     Null(io::Sink),
 }
 
@@ -213,6 +225,7 @@ pub struct Ui {
     progress_indicator: bool,
     formatter_factory: FormatterFactory,
     output: UiOutput,
+    event_log: Option<Arc<EventLog>>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, clap::ValueEnum)]
@@ -343,6 +356,7 @@ impl Ui {
             progress_indicator: false,
             formatter_factory: FormatterFactory::plain_text(),
             output: UiOutput::Null,
+            event_log: None,
         }
     }
 
@@ -354,6 +368,7 @@ impl Ui {
             pager: PagerConfig::from_config(config)?,
             progress_indicator: config.get("ui.progress-indicator")?,
             output: UiOutput::new_terminal(),
+            event_log: None,
         })
     }
 
@@ -365,6 +380,16 @@ impl Ui {
         Ok(())
     }
 
+    /// Sets the destination of the machine-readable events.
+    pub fn set_event_log(&mut self, event_log: EventLog) {
+        self.event_log = Some(Arc::new(event_log));
+    }
+
+    /// Returns the event log if enabled by `--event-log`.
+    pub fn event_log(&self) -> Option<&Arc<EventLog>> {
+        self.event_log.as_ref()
+    }
+
     /// Switches the output to use the pager, if allowed.
     #[instrument(skip_all)]
     pub fn request_pager(&mut self) {
@@ -425,7 +450,9 @@ impl Ui {
         match &self.output {
             UiOutput::Terminal { stdout, .. } => UiStdout::Terminal(stdout.lock()),
             UiOutput::Paged { child_stdin, .. } => UiStdout::Paged(child_stdin),
-            UiOutput::BuiltinPaged { out_wr, .. } => UiStdout::Builtin(out_wr),
+            UiOutput::BuiltinPaged {
+                out_wr, outline, ..
+            } => UiStdout::Builtin(OutlineWriter::new(out_wr, outline)),
             UiOutput::Null => UiStdout::Null(io::sink()),
         }
     }
@@ -13,9 +13,13 @@ mod test_absorb_command;
 mod test_acls;
 mod test_advance_bookmarks;
 mod test_alias;
+mod test_api_command;
 mod test_backout_command;
+mod test_backup_command;
 mod test_bookmark_command;
 mod test_builtin_aliases;
+mod test_changelog_command;
+mod test_ci_status_command;
 mod test_commit_command;
 mod test_commit_template;
 mod test_completion;
@@ -28,30 +32,40 @@ mod test_debug_init_local_command;
 mod test_describe_command;
 mod test_diff_command;
 mod test_diffedit_command;
+mod test_doctor_command;
 mod test_duplicate_command;
 mod test_edit_command;
 mod test_evolog_command;
+mod test_evolve_command;
 mod test_file_annotate_command;
+mod test_file_attr_command;
 mod test_file_chmod_command;
 mod test_file_show_command;
 mod test_file_track_untrack_commands;
+mod test_filter_command;
 mod test_fix_command;
+mod test_fsck_command;
 mod test_generate_md_cli_help;
 mod test_git_clone;
 mod test_git_colocated;
 mod test_git_fetch;
 mod test_git_import_export;
 mod test_git_init;
+mod test_git_notes;
 mod test_git_private_commits;
 mod test_git_push;
 mod test_git_remotes;
+mod test_git_stash;
 mod test_git_submodule;
+mod test_git_worktree;
 mod test_gitignores;
 mod test_global_opts;
 mod test_help_command;
 mod test_immutable_commits;
 mod test_interdiff_command;
 mod test_log_command;
+mod test_maintenance_command;
+mod test_meta_command;
 mod test_new_command;
 mod test_next_prev_commands;
 mod test_operations;
@@ -63,6 +77,7 @@ mod test_restore_command;
 mod test_revset_output;
 mod test_root;
 mod test_shell_completion;
+mod test_shortlog_command;
 mod test_show_command;
 mod test_sign_unsign_commands;
 mod test_simplify_parents_command;
@@ -72,7 +87,11 @@ mod test_squash_command;
 mod test_status_command;
 mod test_tag_command;
 mod test_templater;
+mod test_test_command;
+mod test_topic_command;
 mod test_undo;
 mod test_util_command;
+mod test_verify_command;
+mod test_web_command;
 mod test_working_copy;
 mod test_workspaces;
@@ -457,6 +457,36 @@ fn test_abandon_restore_descendants() {
     ");
 }
 
+#[test]
+fn test_abandon_interactive_without_terminal() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    create_commit(&test_env, &repo_path, "a", &[]);
+
+    // The checklist can't be shown, so nothing is abandoned
+    let output = test_env.run_jj_in(&repo_path, ["abandon", "--interactive", "a"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Cannot select revisions interactively since the output is not connected to a terminal
+    [EOF]
+    [exit status: 1]
+    ");
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r"
+    @  [rlv] a
+    ◆  [zzz]
+    [EOF]
+    ");
+
+    // Nothing to choose from
+    let output = test_env.run_jj_in(&repo_path, ["abandon", "-i", "none()"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No revisions to abandon.
+    [EOF]
+    ");
+}
+
 #[must_use]
 fn get_log_output(test_env: &TestEnvironment, repo_path: &Path) -> CommandOutput {
     let template = r#"separate(" ", "[" ++ change_id.short(3) ++ "]", bookmarks)"#;
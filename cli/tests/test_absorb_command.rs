@@ -458,16 +458,16 @@ fn test_absorb_conflict() {
 
     let conflict_content =
         String::from_utf8(std::fs::read(repo_path.join("file1")).unwrap()).unwrap();
-    insta::assert_snapshot!(conflict_content, @r"
+    insta::assert_snapshot!(conflict_content, @r#"
     <<<<<<< Conflict 1 of 1
-    %%%%%%% Changes from base to side #1
+    %%%%%%% Changes from base (zzzzzzzz) to side #1 (qpvuntsm "1")
     +1a
     +1b
-    +++++++ Contents of side #2
+    +++++++ Contents of side #2 (kkmpptxz)
     2a
     2b
     >>>>>>> Conflict 1 of 1 ends
-    ");
+    "#);
 
     // Cannot absorb from conflict
     let output = test_env.run_jj_in(&repo_path, ["absorb"]);
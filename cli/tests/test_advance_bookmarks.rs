@@ -296,6 +296,63 @@ fn test_advance_bookmarks_overrides(make_commit: CommitFn) {
     }
 }
 
+// Check that --no-advance keeps eligible bookmarks in place.
+#[test]
+fn test_advance_bookmarks_no_advance() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let workspace_path = test_env.env_root().join("repo");
+
+    test_env.add_config(
+        r#"[experimental-advance-branches]
+        enabled-branches = ["glob:feature-*"]
+        "#,
+    );
+    test_env
+        .run_jj_in(
+            &workspace_path,
+            ["bookmark", "create", "-r", "@-", "feature-a", "main"],
+        )
+        .success();
+
+    // Neither command moves the bookmarks with --no-advance.
+    test_env
+        .run_jj_in(&workspace_path, ["commit", "-m", "first", "--no-advance"])
+        .success();
+    test_env
+        .run_jj_in(&workspace_path, ["describe", "-m", "second"])
+        .success();
+    test_env
+        .run_jj_in(&workspace_path, ["new", "--no-advance"])
+        .success();
+    insta::assert_snapshot!(get_log_output_with_bookmarks(&test_env, &workspace_path), @r"
+    @  bookmarks{} desc:
+    ○  bookmarks{} desc: second
+    ○  bookmarks{} desc: first
+    ◆  bookmarks{feature-a main} desc:
+    [EOF]
+    ");
+
+    // Only the bookmark matching the pattern advances otherwise.
+    test_env
+        .run_jj_in(
+            &workspace_path,
+            ["bookmark", "set", "-r", "@-", "feature-a", "main"],
+        )
+        .success();
+    test_env
+        .run_jj_in(&workspace_path, ["commit", "-m", "third"])
+        .success();
+    insta::assert_snapshot!(get_log_output_with_bookmarks(&test_env, &workspace_path), @r"
+    @  bookmarks{} desc:
+    ○  bookmarks{feature-a} desc: third
+    ○  bookmarks{main} desc: second
+    ○  bookmarks{} desc: first
+    ◆  bookmarks{} desc:
+    [EOF]
+    ");
+}
+
 // If multiple eligible bookmarks point to @-, all of them will be advanced.
 #[test_case(commit_cmd ; "commit")]
 #[test_case(describe_new_cmd; "new")]
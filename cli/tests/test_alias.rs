@@ -485,3 +485,121 @@ fn test_aliases_overriding_friendly_errors() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_command_default_args() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env.add_config(
+        r#"
+        commands.log.default-args = ["--no-graph", "-T", "commit_id.short() ++ '\n'"]
+        commands.file.list.default-args = ["-T", "'file: ' ++ path ++ '\n'"]
+        commands.status.default-args = ["--color=always"]
+        aliases.l = ["log", "-r", "@"]
+        "#,
+    );
+    std::fs::write(repo_path.join("file"), "").unwrap();
+
+    let output = test_env.run_jj_in(&repo_path, ["log"]);
+    insta::assert_snapshot!(output, @r"
+    2ab63171b7da
+    000000000000
+    [EOF]
+    ");
+
+    // Arguments on the command line take precedence
+    let output = test_env.run_jj_in(&repo_path, ["log", "-r@", "-T", "change_id.short()"]);
+    insta::assert_snapshot!(output, @r"
+    qpvuntsmwlqt[EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["log", "--no-graph", "--template=''"]);
+    insta::assert_snapshot!(output, @"");
+
+    // Default arguments apply to commands invoked through aliases, and after
+    // global arguments
+    let output = test_env.run_jj_in(&repo_path, ["--at-op=@", "l"]);
+    insta::assert_snapshot!(output, @r"
+    2ab63171b7da
+    [EOF]
+    ");
+
+    // Subcommands and command aliases are looked up by name
+    let output = test_env.run_jj_in(&repo_path, ["file", "list"]);
+    insta::assert_snapshot!(output, @r"
+    file: file
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["st"]);
+    insta::assert_snapshot!(output, @r"
+    Working copy changes:
+    [38;5;2mA file[39m
+    Working copy : [1m[38;5;13mq[38;5;8mpvuntsm[39m [38;5;12m2[38;5;8mab63171[39m [38;5;3m(no description set)[0m
+    Parent commit: [1m[38;5;5mz[0m[38;5;8mzzzzzzz[39m [1m[38;5;4m0[0m[38;5;8m0000000[39m [38;5;2m(empty)[39m [38;5;2m(no description set)[39m
+    [EOF]
+    ");
+
+    // Default arguments can be disabled
+    let output = test_env.run_jj_in(&repo_path, ["log", "--no-default-args", "-r@"]);
+    insta::assert_snapshot!(output, @r"
+    @  qpvuntsm test.user@example.com 2001-02-03 08:05:08 2ab63171
+    │  (no description set)
+    ~
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_alias_positional_args() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env.add_config(
+        r#"
+        aliases.one = ["log", "--no-graph", "-r", "$1", "-T", "commit_id.short() ++ '\n'"]
+        aliases.two = ["log", "--no-graph", "-T", "$2", "-r", "$1"]
+        aliases.all = ["log", "--no-graph", "-r", "@", "-T", "$@"]
+        aliases.literal = ["util", "exec", "--", "echo", "$$1", "$$@", "$x", "a $1"]
+        aliases.nested = ["two", "$1", "change_id.short() ++ '\n'"]
+        "#,
+    );
+
+    let output = test_env.run_jj_in(&repo_path, ["one", "root()"]);
+    insta::assert_snapshot!(output, @r"
+    000000000000
+    [EOF]
+    ");
+
+    // Arguments which aren't referenced are appended
+    let output = test_env.run_jj_in(&repo_path, ["two", "root()", "commit_id", "--color=debug"]);
+    insta::assert_snapshot!(output, @r"
+    [38;5;4m<<log commit_id::0000000000000000000000000000000000000000>>[39m[EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["all", "change_id.short()"]);
+    insta::assert_snapshot!(output, @r"
+    qpvuntsmwlqt[EOF]
+    ");
+
+    // Only whole items are substituted
+    let output = test_env.run_jj_in(&repo_path, ["literal", "foo"]);
+    insta::assert_snapshot!(output, @r"
+    $1 $@ $x a $1 foo
+    [EOF]
+    ");
+
+    // Aliases can be nested
+    let output = test_env.run_jj_in(&repo_path, ["nested", "@"]);
+    insta::assert_snapshot!(output, @r"
+    qpvuntsmwlqt
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["two", "root()"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Error: Missing argument $2 for alias `two`
+    Hint: The alias is defined as ["log", "--no-graph", "-T", "$2", "-r", "$1"]
+    [EOF]
+    [exit status: 1]
+    "#);
+}
@@ -0,0 +1,173 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use itertools::Itertools as _;
+use regex::Regex;
+
+use crate::common::CommandOutput;
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_api_protocol() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    let output = run_api(
+        &test_env,
+        &repo_path,
+        &[
+            r#"{"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {"version": 1}}"#,
+            r#"{"jsonrpc": "2.0", "id": 2, "method": "initialize", "params": {"version": 2}}"#,
+            r#"{"jsonrpc": "2.0", "id": 3, "method": "unknown"}"#,
+            r#"{"jsonrpc": "2.0", "id": 4, "method": "log", "params": {"bad": 1}}"#,
+            r#"{"jsonrpc": "1.0", "id": 5, "method": "log"}"#,
+            r#"{"id": 6}"#,
+            r#"not json"#,
+            r#"{"jsonrpc": "2.0", "id": 7, "method": "log", "params": {"revset": "unknown"}}"#,
+            // Notification doesn't get response
+            r#"{"jsonrpc": "2.0", "method": "status"}"#,
+            // Null id is still a request
+            r#"{"jsonrpc": "2.0", "id": null, "method": "unknown"}"#,
+        ],
+    );
+    insta::assert_snapshot!(output, @r#"
+    {"id":1,"jsonrpc":"2.0","result":{"jj_version":"<version>","version":1,"workspace_root":"$TEST_ENV/repo"}}
+    {"error":{"code":-32001,"message":"Unsupported protocol version 2 (supported: 1)"},"id":2,"jsonrpc":"2.0"}
+    {"error":{"code":-32601,"message":"Unknown method: unknown"},"id":3,"jsonrpc":"2.0"}
+    {"error":{"code":-32602,"message":"unknown field `bad`, expected `revset` or `limit`"},"id":4,"jsonrpc":"2.0"}
+    {"error":{"code":-32600,"message":"Unsupported JSON-RPC version"},"id":null,"jsonrpc":"2.0"}
+    {"error":{"code":-32600,"message":"missing field `jsonrpc`"},"id":null,"jsonrpc":"2.0"}
+    {"error":{"code":-32700,"message":"expected ident at line 1 column 2"},"id":null,"jsonrpc":"2.0"}
+    {"error":{"code":-32000,"message":"Revision `unknown` doesn't exist"},"id":7,"jsonrpc":"2.0"}
+    {"error":{"code":-32601,"message":"Unknown method: unknown"},"id":null,"jsonrpc":"2.0"}
+    [EOF]
+    "#);
+}
+
+#[test]
+fn test_api_queries() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(repo_path.join("file"), "a\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "first"])
+        .success();
+    std::fs::write(repo_path.join("file"), "a\nb\n").unwrap();
+    std::fs::write(repo_path.join("other"), "other\n").unwrap();
+
+    let output = run_api(
+        &test_env,
+        &repo_path,
+        &[
+            r#"{"jsonrpc": "2.0", "id": 1, "method": "status"}"#,
+            r#"{"jsonrpc": "2.0", "id": 2, "method": "log", "params": {"revset": "all()", "limit": 2}}"#,
+            r#"{"jsonrpc": "2.0", "id": 3, "method": "diff", "params": {"paths": ["file"]}}"#,
+            r#"{"jsonrpc": "2.0", "id": 4, "method": "annotate", "params": {"path": "file"}}"#,
+            r#"{"jsonrpc": "2.0", "id": 5, "method": "annotate", "params": {"path": "missing"}}"#,
+        ],
+    );
+    insta::assert_snapshot!(output, @r#"
+    {"id":1,"jsonrpc":"2.0","result":{"changes":[{"path":"file","status":"modified"},{"path":"other","status":"added"}],"conflicts":[],"parents":[{"author":{"email":"test.user@example.com","name":"Test User","timestamp":"2001-02-03T04:05:08+07:00"},"change_id":"qpvuntsmwlqtpsluzzsnyyzlmlwvmlnu","commit_id":"2d4fa21a09467d5b1046c5427720b46d296485d3","committer":{"email":"test.user@example.com","name":"Test User","timestamp":"2001-02-03T04:05:08+07:00"},"conflict":false,"description":"first\n","empty":false,"parent_ids":["0000000000000000000000000000000000000000"]}],"working_copy":{"author":{"email":"test.user@example.com","name":"Test User","timestamp":"2001-02-03T04:05:09+07:00"},"change_id":"rlvkpnrzqnoowoytxnquwvuryrwnrmlp","commit_id":"73110df15af2ef253e06e7fd1e2d6e4cbfa7d532","committer":{"email":"test.user@example.com","name":"Test User","timestamp":"2001-02-03T04:05:09+07:00"},"conflict":false,"description":"","empty":false,"parent_ids":["2d4fa21a09467d5b1046c5427720b46d296485d3"]}}}
+    {"jsonrpc":"2.0","method":"op_heads_changed","params":{"operation_ids":["886df3d07225fd24fb4569fb7ecb7509c550e01aa88ce4f2846a38c674ef3b22e834b9aee90b727b70ee5219d5c596a4f0fde56564b6226d2c7e5eb5da6dc5cd"]}}
+    {"id":2,"jsonrpc":"2.0","result":{"commits":[{"author":{"email":"test.user@example.com","name":"Test User","timestamp":"2001-02-03T04:05:09+07:00"},"change_id":"rlvkpnrzqnoowoytxnquwvuryrwnrmlp","commit_id":"73110df15af2ef253e06e7fd1e2d6e4cbfa7d532","committer":{"email":"test.user@example.com","name":"Test User","timestamp":"2001-02-03T04:05:09+07:00"},"conflict":false,"description":"","empty":false,"parent_ids":["2d4fa21a09467d5b1046c5427720b46d296485d3"]},{"author":{"email":"test.user@example.com","name":"Test User","timestamp":"2001-02-03T04:05:08+07:00"},"change_id":"qpvuntsmwlqtpsluzzsnyyzlmlwvmlnu","commit_id":"2d4fa21a09467d5b1046c5427720b46d296485d3","committer":{"email":"test.user@example.com","name":"Test User","timestamp":"2001-02-03T04:05:08+07:00"},"conflict":false,"description":"first\n","empty":false,"parent_ids":["0000000000000000000000000000000000000000"]}]}}
+    {"id":3,"jsonrpc":"2.0","result":{"commit":{"author":{"email":"test.user@example.com","name":"Test User","timestamp":"2001-02-03T04:05:09+07:00"},"change_id":"rlvkpnrzqnoowoytxnquwvuryrwnrmlp","commit_id":"73110df15af2ef253e06e7fd1e2d6e4cbfa7d532","committer":{"email":"test.user@example.com","name":"Test User","timestamp":"2001-02-03T04:05:09+07:00"},"conflict":false,"description":"","empty":false,"parent_ids":["2d4fa21a09467d5b1046c5427720b46d296485d3"]},"files":[{"path":"file","status":"modified"}],"patch":"diff --git a/file b/file\nindex 7898192261..422c2b7ab3 100644\n--- a/file\n+++ b/file\n@@ -1,1 +1,2 @@\n a\n+b\n"}}
+    {"id":4,"jsonrpc":"2.0","result":{"lines":[{"change_id":"qpvuntsmwlqtpsluzzsnyyzlmlwvmlnu","commit_id":"2d4fa21a09467d5b1046c5427720b46d296485d3","content":"a\n","line_number":1},{"change_id":"rlvkpnrzqnoowoytxnquwvuryrwnrmlp","commit_id":"73110df15af2ef253e06e7fd1e2d6e4cbfa7d532","content":"b\n","line_number":2}]}}
+    {"error":{"code":-32000,"message":"No such path: missing"},"id":5,"jsonrpc":"2.0"}
+    [EOF]
+    "#);
+}
+
+#[test]
+fn test_api_mutations() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(repo_path.join("file"), "a\n").unwrap();
+
+    let output = run_api(
+        &test_env,
+        &repo_path,
+        &[
+            r#"{"jsonrpc": "2.0", "id": 1, "method": "describe", "params": {"message": "first"}}"#,
+            r#"{"jsonrpc": "2.0", "id": 2, "method": "new", "params": {"message": "second"}}"#,
+            r#"{"jsonrpc": "2.0", "id": 3, "method": "squash"}"#,
+            r#"{"jsonrpc": "2.0", "id": 4, "method": "describe", "params": {"revision": "root()", "message": "root"}}"#,
+        ],
+    );
+    insta::assert_snapshot!(output, @r#"
+    {"id":1,"jsonrpc":"2.0","result":{"commit":{"author":{"email":"test.user@example.com","name":"Test User","timestamp":"2001-02-03T04:05:08+07:00"},"change_id":"qpvuntsmwlqtpsluzzsnyyzlmlwvmlnu","commit_id":"2d4fa21a09467d5b1046c5427720b46d296485d3","committer":{"email":"test.user@example.com","name":"Test User","timestamp":"2001-02-03T04:05:08+07:00"},"conflict":false,"description":"first\n","empty":false,"parent_ids":["0000000000000000000000000000000000000000"]}}}
+    {"jsonrpc":"2.0","method":"op_heads_changed","params":{"operation_ids":["fe494934bf66d4949b3398261ac0ab8218aa5fff7fa0379c7dd0f587347a2735d053dcc2c98bdc60094e9625e3a11f30ced52217a50856ce1f517dd12f336078"]}}
+    {"id":2,"jsonrpc":"2.0","result":{"commit":{"author":{"email":"test.user@example.com","name":"Test User","timestamp":"2001-02-03T04:05:08+07:00"},"change_id":"rlvkpnrzqnoowoytxnquwvuryrwnrmlp","commit_id":"90ce050a60963a319cad01f3e656f5de1c61bf0f","committer":{"email":"test.user@example.com","name":"Test User","timestamp":"2001-02-03T04:05:08+07:00"},"conflict":false,"description":"second\n","empty":true,"parent_ids":["2d4fa21a09467d5b1046c5427720b46d296485d3"]}}}
+    {"jsonrpc":"2.0","method":"op_heads_changed","params":{"operation_ids":["266e43efb14a79357daa4f8f914db087b14ff0784a7d9ed713d45436a39a91d9970090d4ad2ae24cc35172c75e1597f33d592519129db7c3b3749672ac1f7c92"]}}
+    {"error":{"code":-32000,"data":{"hints":["Use `message` to specify the description of the combined commit"]},"message":"Both source and destination have descriptions"},"id":3,"jsonrpc":"2.0"}
+    {"error":{"code":-32000,"message":"The root commit 000000000000 is immutable"},"id":4,"jsonrpc":"2.0"}
+    [EOF]
+    ------- stderr -------
+    Working copy now at: qpvuntsm 2d4fa21a first
+    Parent commit      : zzzzzzzz 00000000 (empty) (no description set)
+    Working copy now at: rlvkpnrz 90ce050a (empty) second
+    Parent commit      : qpvuntsm 2d4fa21a first
+    [EOF]
+    "#);
+
+    std::fs::write(repo_path.join("file"), "b\n").unwrap();
+    let output = run_api(
+        &test_env,
+        &repo_path,
+        &[
+            r#"{"jsonrpc": "2.0", "id": 1, "method": "squash"}"#,
+            r#"{"jsonrpc": "2.0", "id": 2, "method": "squash", "params": {"message": "combined"}}"#,
+        ],
+    );
+    insta::assert_snapshot!(output, @r#"
+    {"error":{"code":-32000,"data":{"hints":["Use `message` to specify the description of the combined commit"]},"message":"Both source and destination have descriptions"},"id":1,"jsonrpc":"2.0"}
+    {"jsonrpc":"2.0","method":"op_heads_changed","params":{"operation_ids":["38575e23ddddfcd5864c846d338d85fae013bac1a803138118f1451d39dc6e3299680c76720382bc807129129cdcbe5386e0b8c16bb299ea3af8ea7ce6a2a360"]}}
+    {"id":2,"jsonrpc":"2.0","result":{"commit":{"author":{"email":"test.user@example.com","name":"Test User","timestamp":"2001-02-03T04:05:08+07:00"},"change_id":"qpvuntsmwlqtpsluzzsnyyzlmlwvmlnu","commit_id":"ceb35990bd8a04805471d2a2212e0c602718c7e7","committer":{"email":"test.user@example.com","name":"Test User","timestamp":"2001-02-03T04:05:09+07:00"},"conflict":false,"description":"combined\n","empty":false,"parent_ids":["0000000000000000000000000000000000000000"]}}}
+    {"jsonrpc":"2.0","method":"op_heads_changed","params":{"operation_ids":["4a65d49728ca89a199cee180efd7ab851b91b2a9dc9ad028217a722c704585454e395bb3d5a027fe442c0f10b5bac7aa4e0555caad3d0db1cb598b43a46517bb"]}}
+    [EOF]
+    ------- stderr -------
+    Working copy now at: kkmpptxz 45e0b4d3 (empty) (no description set)
+    Parent commit      : qpvuntsm ceb35990 combined
+    [EOF]
+    "#);
+
+    let output = test_env.run_jj_in(&repo_path, ["log", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    @
+    ○  combined
+    ◆
+    [EOF]
+    ");
+}
+
+/// Sends the request messages to `jj api`.
+#[must_use]
+fn run_api(test_env: &TestEnvironment, repo_path: &Path, messages: &[&str]) -> CommandOutput {
+    let input = messages
+        .iter()
+        .map(|message| format!("{message}\n"))
+        .join("");
+    let version_regex = Regex::new(r#""jj_version":"[^"]*""#).unwrap();
+    test_env
+        .run_jj_with(|cmd| cmd.current_dir(repo_path).arg("api").write_stdin(input))
+        .normalize_stdout_with(|stdout| {
+            version_regex
+                .replace_all(&stdout, r#""jj_version":"<version>""#)
+                .into_owned()
+        })
+}
@@ -0,0 +1,158 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use crate::common::CommandOutput;
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_backup_create_restore() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(repo_path.join("file"), "a\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "first"])
+        .success();
+    std::fs::write(repo_path.join("file"), "b\n").unwrap();
+
+    let output = test_env.run_jj_in(&repo_path, ["backup", "create", "../backup.tar"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Backed up the repo at operation 83c318e1e285 to ../backup.tar
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["backup", "create", "../backup.tar"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to create ../backup.tar
+    Caused by: File exists (os error 17)
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Changes made after the backup are lost by restoring it
+    test_env.run_jj_in(&repo_path, ["abandon", "@-"]).success();
+    let output = test_env.run_jj_in(".", ["backup", "restore", "backup.tar", "restored"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Restored the repo at operation 83c318e1e285 into "restored"
+    Working copy now at: rlvkpnrz ee9ffcfa (no description set)
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    "#);
+    let restored_path = test_env.env_root().join("restored");
+    insta::assert_snapshot!(get_log_output(&test_env, &restored_path), @r"
+    @  ee9ffcfa176c
+    ○  2d4fa21a0946 first
+    ◆  000000000000
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&restored_path, ["op", "log", "-Tdescription"]);
+    insta::assert_snapshot!(output, @r"
+    @  snapshot working copy
+    ○  commit 6b1027d2770cd0a39c468e525e52bf8c47e1464a
+    ○  snapshot working copy
+    ○  add workspace 'default'
+    ○
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&restored_path, ["status"]);
+    insta::assert_snapshot!(output, @r"
+    Working copy changes:
+    M file
+    Working copy : rlvkpnrz ee9ffcfa (no description set)
+    Parent commit: qpvuntsm 2d4fa21a first
+    [EOF]
+    ");
+    assert_eq!(
+        std::fs::read_to_string(restored_path.join("file")).unwrap(),
+        "b\n"
+    );
+
+    // The whole undo history is restored
+    test_env
+        .run_jj_in(&restored_path, ["op", "restore", "@--"])
+        .success();
+    insta::assert_snapshot!(get_log_output(&test_env, &restored_path), @r"
+    @  6b1027d2770c
+    ◆  000000000000
+    [EOF]
+    ");
+
+    // Can't restore into a directory with files
+    let output = test_env.run_jj_in(".", ["backup", "restore", "backup.tar", "restored"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Destination path exists and is not an empty directory
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_backup_colocated() {
+    let test_env = TestEnvironment::default();
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "repo"])
+        .success();
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(repo_path.join("file"), "a\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "first"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["backup", "create", "../backup.tar"])
+        .success();
+
+    // The Git repo is restored as an internal one
+    let output = test_env.run_jj_in(".", ["backup", "restore", "backup.tar", "restored"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Restored the repo at operation 53c42fe54f1c into "restored"
+    Working copy now at: rlvkpnrz 41fb3659 (empty) (no description set)
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    "#);
+    let restored_path = test_env.env_root().join("restored");
+    assert!(!restored_path.join(".git").exists());
+    assert!(restored_path.join(".jj/repo/store/git").is_dir());
+    insta::assert_snapshot!(get_log_output(&test_env, &restored_path), @r"
+    @  41fb365969bd
+    ○  2d4fa21a0946 first
+    ◆  000000000000
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_backup_restore_invalid() {
+    let test_env = TestEnvironment::default();
+    std::fs::write(test_env.env_root().join("file"), "").unwrap();
+    let output = test_env.run_jj_in(".", ["backup", "restore", "file", "restored"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: The backup is empty
+    [EOF]
+    [exit status: 1]
+    ");
+    assert!(!test_env.env_root().join("restored").exists());
+}
+
+#[must_use]
+fn get_log_output(test_env: &TestEnvironment, cwd: &Path) -> CommandOutput {
+    let template = r#"separate(" ", commit_id.short(), working_copies, description)"#;
+    test_env.run_jj_in(cwd, ["log", "-T", template, "-r", "all()"])
+}
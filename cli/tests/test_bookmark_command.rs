@@ -16,6 +16,7 @@ use std::path::Path;
 
 use testutils::git;
 
+use crate::common::force_interactive;
 use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
 
@@ -2397,6 +2398,260 @@ fn test_bookmark_list_conflicted() {
     ");
 }
 
+#[test]
+fn test_bookmark_archive() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env
+        .run_jj_in(
+            &repo_path,
+            [
+                "bookmark",
+                "create",
+                "-r@",
+                "feature-a",
+                "feature-b",
+                "main",
+            ],
+        )
+        .success();
+    let output = test_env.run_jj_in(&repo_path, ["bookmark", "archive", "glob:feature-*"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Archived 2 bookmarks.
+    [EOF]
+    ");
+
+    // Archived bookmarks are hidden by default
+    insta::assert_snapshot!(get_bookmark_output(&test_env, &repo_path), @r"
+    main: qpvuntsm 230dd059 (empty) (no description set)
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r"
+    @  main 230dd059e1b0
+    ◆   000000000000
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["bookmark", "list", "--include-archived"]);
+    insta::assert_snapshot!(output, @r"
+    feature-a (archived): qpvuntsm 230dd059 (empty) (no description set)
+    feature-b (archived): qpvuntsm 230dd059 (empty) (no description set)
+    main: qpvuntsm 230dd059 (empty) (no description set)
+    [EOF]
+    ");
+    let template = r#"if(archived, name ++ "\n")"#;
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["bookmark", "list", "--include-archived", "-T", template],
+    );
+    insta::assert_snapshot!(output, @r"
+    feature-a
+    feature-b
+    [EOF]
+    ");
+
+    // Archiving again does nothing
+    let output = test_env.run_jj_in(&repo_path, ["bookmark", "archive", "feature-a"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+
+    // Only archived bookmarks can be unarchived
+    let output = test_env.run_jj_in(&repo_path, ["bookmark", "unarchive", "main"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No such bookmark: main
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["bookmark", "unarchive", "feature-a"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Unarchived 1 bookmarks.
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&test_env, &repo_path), @r"
+    feature-a: qpvuntsm 230dd059 (empty) (no description set)
+    main: qpvuntsm 230dd059 (empty) (no description set)
+    [EOF]
+    ");
+
+    // Deleted bookmark is no longer archived
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "delete", "feature-b"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "create", "-r@", "feature-b"])
+        .success();
+    insta::assert_snapshot!(get_bookmark_output(&test_env, &repo_path), @r"
+    feature-a: qpvuntsm 230dd059 (empty) (no description set)
+    feature-b: qpvuntsm 230dd059 (empty) (no description set)
+    main: qpvuntsm 230dd059 (empty) (no description set)
+    [EOF]
+    ");
+
+    // Archiving can be undone
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "archive", "main"])
+        .success();
+    test_env.run_jj_in(&repo_path, ["undo"]).success();
+    insta::assert_snapshot!(get_bookmark_output(&test_env, &repo_path), @r"
+    feature-a: qpvuntsm 230dd059 (empty) (no description set)
+    feature-b: qpvuntsm 230dd059 (empty) (no description set)
+    main: qpvuntsm 230dd059 (empty) (no description set)
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_bookmark_list_tree() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env
+        .run_jj_in(
+            &repo_path,
+            [
+                "bookmark",
+                "create",
+                "-r@",
+                "a/b/c",
+                "a/d",
+                "feature/x",
+                "feature/y",
+                "main",
+            ],
+        )
+        .success();
+    let output = test_env.run_jj_in(&repo_path, ["bookmark", "list", "--tree"]);
+    insta::assert_snapshot!(output, @r"
+    a/
+      b/
+        a/b/c: qpvuntsm 230dd059 (empty) (no description set)
+      a/d: qpvuntsm 230dd059 (empty) (no description set)
+    feature/
+      feature/x: qpvuntsm 230dd059 (empty) (no description set)
+      feature/y: qpvuntsm 230dd059 (empty) (no description set)
+    main: qpvuntsm 230dd059 (empty) (no description set)
+    [EOF]
+    ");
+
+    // Filtered bookmarks don't create empty groups
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["bookmark", "list", "--tree", "glob:feature/*", "main"],
+    );
+    insta::assert_snapshot!(output, @r"
+    feature/
+      feature/x: qpvuntsm 230dd059 (empty) (no description set)
+      feature/y: qpvuntsm 230dd059 (empty) (no description set)
+    main: qpvuntsm 230dd059 (empty) (no description set)
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_bookmark_list_null_terminated() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "create", "-r@", "bar", "foo"])
+        .success();
+    let output = test_env
+        .run_jj_in(&repo_path, ["bookmark", "list", "-z", "--color=always"])
+        .success();
+    insta::assert_debug_snapshot!(output.stdout.normalized(), @r#"
+    "bar: qpvuntsm 230dd059 (empty) (no description set)\0foo: qpvuntsm 230dd059 (empty) (no description set)\0"
+    "#);
+
+    // Can't be combined with the tree layout
+    let output = test_env.run_jj_in(&repo_path, ["bookmark", "list", "-z", "--tree"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--null' cannot be used with '--tree'
+
+    Usage: jj bookmark list --null [NAMES]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_bookmark_glob_confirmation() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env
+        .run_jj_in(
+            &repo_path,
+            ["bookmark", "create", "-r@", "foo-1", "foo-2", "bar"],
+        )
+        .success();
+
+    // Declining leaves the bookmarks alone
+    let output = test_env.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .current_dir(&repo_path)
+            .args(["bookmark", "delete", "glob:foo-*"])
+            .write_stdin("n\n")
+    });
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    The following 2 bookmarks will be deleted:
+      foo-1
+      foo-2
+    Continue? (yN): Nothing changed.
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&test_env, &repo_path), @r"
+    bar: qpvuntsm 230dd059 (empty) (no description set)
+    foo-1: qpvuntsm 230dd059 (empty) (no description set)
+    foo-2: qpvuntsm 230dd059 (empty) (no description set)
+    [EOF]
+    ");
+
+    // Exact names don't ask
+    let output = test_env.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .current_dir(&repo_path)
+            .args(["bookmark", "forget", "foo-1", "foo-2"])
+    });
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Forgot 2 local bookmarks.
+    [EOF]
+    ");
+
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "create", "-r@", "baz"])
+        .success();
+    let output = test_env.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .current_dir(&repo_path)
+            .args(["bookmark", "delete", "glob:ba*"])
+            .write_stdin("y\n")
+    });
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    The following 2 bookmarks will be deleted:
+      bar
+      baz
+    Continue? (yN): Deleted 2 bookmarks.
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_bookmark_output(&test_env, &repo_path), @"");
+}
+
 #[test]
 fn test_bookmark_create_with_default_target_revision() {
     let test_env = TestEnvironment::default();
@@ -159,6 +159,54 @@ fn test_builtin_alias_trunk_no_match_only_exact() {
     ");
 }
 
+#[test]
+fn test_builtin_alias_stack() {
+    let (test_env, workspace_root) = set_up("main");
+
+    test_env
+        .run_jj_in(&workspace_root, ["new", "main", "-m=a"])
+        .success();
+    test_env
+        .run_jj_in(&workspace_root, ["new", "-m=b"])
+        .success();
+    test_env
+        .run_jj_in(&workspace_root, ["new", "description(a)", "-m=c"])
+        .success();
+    test_env
+        .run_jj_in(&workspace_root, ["new", "main", "-m=other"])
+        .success();
+
+    let output = test_env.run_jj_in(
+        &workspace_root,
+        ["log", "-r", "stack(description(b))", "-T", "description"],
+    );
+    insta::assert_snapshot!(output, @r"
+    ○  c
+    │ ○  b
+    ├─╯
+    ○  a
+    │
+    ~
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(
+        &workspace_root,
+        ["log", "-r", "stack(@)", "-T", "description"],
+    );
+    insta::assert_snapshot!(output, @r"
+    @  other
+    │
+    ~
+    [EOF]
+    ");
+    // Immutable commits aren't part of any stack
+    let output = test_env.run_jj_in(
+        &workspace_root,
+        ["log", "-r", "stack(main)", "-T", "description"],
+    );
+    insta::assert_snapshot!(output, @"");
+}
+
 #[test]
 fn test_builtin_user_redefines_builtin_immutable_heads() {
     let (test_env, workspace_root) = set_up("main");
@@ -0,0 +1,195 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use crate::common::TestEnvironment;
+
+/// Creates a commit per (description, file) pair on top of `@`.
+fn create_commits(test_env: &TestEnvironment, repo_path: &Path, commits: &[(&str, &str)]) {
+    for (description, file) in commits {
+        let path = repo_path.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, description).unwrap();
+        test_env
+            .run_jj_in(repo_path, ["commit", "-m", description])
+            .success();
+    }
+}
+
+#[test]
+fn test_changelog() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    create_commits(&test_env, &repo_path, &[("initial release", "README")]);
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "create", "-r@-", "v1.0"])
+        .success();
+    create_commits(
+        &test_env,
+        &repo_path,
+        &[
+            ("feat(cli): add flag", "src/cli"),
+            ("fix: crash on start", "src/main"),
+            ("update docs", "docs/index"),
+            (
+                "feat!: remove flag\n\nBREAKING CHANGE: the flag is gone",
+                "src/cli",
+            ),
+            ("Feat: another feature", "src/lib"),
+            ("fix!: reject bad input", "src/input"),
+        ],
+    );
+
+    let output = test_env.run_jj_in(&repo_path, ["changelog", "-r", "v1.0..@-"]);
+    insta::assert_snapshot!(output, @r"
+    ### Breaking changes
+
+    * feat!: remove flag (979b63816fe0)
+    * fix!: reject bad input (5b2cb1f338b6)
+
+    ### New features
+
+    * feat(cli): add flag (5bf207abcbb1)
+    * Feat: another feature (0db879a1cc56)
+
+    ### Fixed bugs
+
+    * fix: crash on start (392a06164f09)
+
+    ### Other changes
+
+    * update docs (3210fdf11c1d)
+    [EOF]
+    ");
+
+    // Custom template
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "changelog",
+            "-r",
+            "v1.0..@-",
+            "-T",
+            r#""- " ++ description.first_line() ++ "\n""#,
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    ### Breaking changes
+
+    - feat!: remove flag
+    - fix!: reject bad input
+
+    ### New features
+
+    - feat(cli): add flag
+    - Feat: another feature
+
+    ### Fixed bugs
+
+    - fix: crash on start
+
+    ### Other changes
+
+    - update docs
+    [EOF]
+    ");
+
+    // Revisions are required
+    let output = test_env.run_jj_in(&repo_path, ["changelog"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the following required arguments were not provided:
+      --revisions <REVSETS>
+
+    Usage: jj changelog --revisions <REVSETS>
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_changelog_custom_categories() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env.add_config(
+        r#"
+        [[changelog.categories]]
+        title = "Documentation"
+        paths = ["docs"]
+
+        [[changelog.categories]]
+        title = "Security"
+        trailers = ["Security"]
+
+        [[changelog.categories]]
+        title = "Features"
+        types = ["feat"]
+        "#,
+    );
+    create_commits(
+        &test_env,
+        &repo_path,
+        &[
+            ("feat: document the flag", "docs/flag"),
+            ("validate input\n\nSecurity: CVE-0000-0000", "src/input"),
+            ("feat: add flag", "src/flag"),
+            ("not categorized", "src/other"),
+            // Not trailers
+            (
+                "mention security\n\nSecurity: this paragraph is prose,\nnot trailers.",
+                "src/prose",
+            ),
+            (
+                "link advisory\n\nSecurity advisory: https://example.com",
+                "src/link",
+            ),
+        ],
+    );
+
+    // Uncategorized revisions are omitted without a catch-all category
+    let output = test_env.run_jj_in(&repo_path, ["changelog", "-r", "::@- ~ root()"]);
+    insta::assert_snapshot!(output, @r"
+    ### Documentation
+
+    * feat: document the flag (261e70cb8998)
+
+    ### Security
+
+    * validate input (30fb6e9b570e)
+
+    ### Features
+
+    * feat: add flag (363b0574eedb)
+    [EOF]
+    ");
+
+    // Invalid category
+    test_env.add_config(r#"changelog.categories = [{ name = "Oops" }]"#);
+    let output = test_env.run_jj_in(&repo_path, ["changelog", "-r", "@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Config error: Invalid type or value for changelog.categories
+    Caused by: unknown field `name`, expected one of `title`, `types`, `paths`, `trailers`, `breaking`
+
+    Hint: Check the config file: $TEST_ENV/config/config0003.toml
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+}
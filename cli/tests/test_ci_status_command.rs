@@ -0,0 +1,163 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_ci_status_no_provider() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    let output = test_env.run_jj_in(&repo_path, ["ci-status", "refresh"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No CI provider configured
+    Hint: Set `ci.provider` to the CI system to fetch statuses from, e.g. `jj config set --repo ci.provider github`
+    [EOF]
+    [exit status: 1]
+    ");
+
+    test_env.add_config(r#"ci.provider = "jenkins""#);
+    let output = test_env.run_jj_in(&repo_path, ["ci-status", "refresh"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Config error: Unknown CI provider 'jenkins'
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_ci_status_refresh() {
+    use std::os::unix::fs::PermissionsExt as _;
+
+    use crate::common::to_toml_value;
+
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "good"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "bad"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "running"])
+        .success();
+    let commit_id = |rev: &str| {
+        test_env
+            .run_jj_in(
+                &repo_path,
+                ["log", "--no-graph", "-r", rev, "-T", "commit_id"],
+            )
+            .success()
+            .stdout
+            .into_raw()
+    };
+
+    // Fake GitHub CLI serving check runs from files named by commit id
+    let checks_dir = test_env.env_root().join("checks");
+    std::fs::create_dir(&checks_dir).unwrap();
+    std::fs::write(
+        checks_dir.join(commit_id("@--")),
+        r#"{"check_runs": [{"status": "completed", "conclusion": "success"}]}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        checks_dir.join(commit_id("@-")),
+        r#"{"check_runs": [
+            {"status": "completed", "conclusion": "success"},
+            {"status": "completed", "conclusion": "failure"}
+        ]}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        checks_dir.join(commit_id("@")),
+        r#"{"check_runs": [{"status": "in_progress", "conclusion": null}]}"#,
+    )
+    .unwrap();
+    let gh_path = test_env.env_root().join("fake-gh");
+    std::fs::write(
+        &gh_path,
+        format!(
+            "#!/bin/sh\nsha=$(echo \"$2\" | cut -d/ -f5)\ncat {}/$sha 2>/dev/null || {{ echo \
+             'HTTP 404: No commit found' >&2; exit 1; }}\n",
+            checks_dir.display()
+        ),
+    )
+    .unwrap();
+    std::fs::set_permissions(&gh_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    test_env.add_config(format!(
+        "ci.provider = \"github\"\nci.github.program = {}\nci.github.repository = \"owner/repo\"",
+        to_toml_value(gh_path.to_str().unwrap())
+    ));
+
+    let output = test_env.run_jj_in(&repo_path, ["ci-status", "refresh", "-r", "::@"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    pending: kkmpptxz 8f532fb3 (empty) running
+    failure: rlvkpnrz ae5d45dd (empty) bad
+    success: qpvuntsm a1ba2672 (empty) good
+    Warning: Failed to fetch CI status of zzzzzzzz 00000000 (empty) (no description set): $TEST_ENV/fake-gh failed: HTTP 404: No commit found
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "log",
+            "-T",
+            r#"description.first_line() ++ ": " ++ ci_status ++ "\n""#,
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    @  running: pending
+    ○  bad: failure
+    ○  good: success
+    ◆  :
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["log", "-r", "ci_failed()", "-T", "description"],
+    );
+    insta::assert_snapshot!(output, @r"
+    ○  bad
+    │
+    ~
+    [EOF]
+    ");
+
+    // Statuses are replaced on refresh
+    std::fs::write(
+        checks_dir.join(commit_id("@-")),
+        r#"{"check_runs": [{"status": "completed", "conclusion": "success"}]}"#,
+    )
+    .unwrap();
+    let output = test_env.run_jj_in(&repo_path, ["ci-status", "refresh", "-r", "@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    success: rlvkpnrz ae5d45dd (empty) bad
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["log", "-r", "ci_failed()", "-T", "description"],
+    );
+    insta::assert_snapshot!(output, @"");
+}
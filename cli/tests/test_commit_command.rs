@@ -273,6 +273,50 @@ fn test_commit_with_default_description() {
     "#);
 }
 
+#[test]
+fn test_commit_with_description_template_from_bookmark() {
+    let mut test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config(
+        r#"
+        [templates]
+        draft_commit_description = '''
+        concat(
+          coalesce(
+            description,
+            concat(
+              containing_bookmarks.map(|b| b.name().match("regex:[A-Z]+-[0-9]+")),
+              ": update ",
+              diff.files().map(|e| e.path()).join(", "),
+              "\n",
+            ),
+          ),
+        )
+        '''
+        "#,
+    );
+    let workspace_path = test_env.env_root().join("repo");
+
+    let edit_script = test_env.set_up_fake_editor();
+    std::fs::write(edit_script, ["dump editor"].join("\0")).unwrap();
+
+    std::fs::write(workspace_path.join("file1"), "foo\n").unwrap();
+    std::fs::write(workspace_path.join("file2"), "bar\n").unwrap();
+    test_env
+        .run_jj_in(
+            &workspace_path,
+            ["bookmark", "create", "-r@", "push-PROJ-123-fix-bug"],
+        )
+        .success();
+    test_env.run_jj_in(&workspace_path, ["commit"]).success();
+    insta::assert_snapshot!(
+        std::fs::read_to_string(test_env.env_root().join("editor")).unwrap(), @r#"
+    PROJ-123: update file1, file2
+
+    JJ: Lines starting with "JJ:" (like this one) will be removed.
+    "#);
+}
+
 #[test]
 fn test_commit_with_description_template() {
     let mut test_env = TestEnvironment::default();
@@ -748,6 +748,21 @@ fn test_log_bookmarks() {
     [EOF]
     ");
 
+    let template = r#"containing_bookmarks"#;
+    let output = test_env.run_jj_in(&workspace_root, ["log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    @  bookmark2* new-bookmark
+    ○  bookmark2* new-bookmark unchanged
+    │ ○  bookmark3??
+    ├─╯
+    │ ○  bookmark3??
+    ├─╯
+    │ ○  bookmark1*
+    ├─╯
+    ◆  bookmark1* bookmark2* bookmark3?? new-bookmark unchanged
+    [EOF]
+    ");
+
     let template = r#"
     remote_bookmarks.map(|ref| concat(
       ref,
@@ -907,6 +922,61 @@ fn test_log_customize_short_id() {
     ");
 }
 
+#[test]
+fn test_log_abbreviated_id() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "first"])
+        .success();
+
+    let template = r#"
+    commit_id.shortest(commit_id.abbreviated_len()) ++ " "
+      ++ change_id.shortest(change_id.abbreviated_len()) ++ "\n"
+    "#;
+    let output = test_env.run_jj_in(&repo_path, ["log", "--no-graph", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    fa15625b qpvuntsm
+    00000000 zzzzzzzz
+    [EOF]
+    ");
+
+    // The estimated length is clamped to the configured bounds, but the ids
+    // are never shorter than their unique prefixes
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "log",
+            "--no-graph",
+            "-T",
+            template,
+            "--config=ui.short-id-min-length=0",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    f q
+    0 z
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "log",
+            "--no-graph",
+            "-T",
+            template,
+            "--config=ui.short-id-min-length=16",
+            "--config=ui.short-id-max-length=4",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    fa15625b4a986997 qpvuntsmwlqtpslu
+    0000000000000000 zzzzzzzzzzzzzzzz
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_log_immutable() {
     let test_env = TestEnvironment::default();
@@ -1523,20 +1593,33 @@ fn test_log_diff_predefined_formats() {
     let template = indoc! {r#"
         concat(
           "=== " ++ commit_id.short() ++ " ===\n",
+          diff.stat().files().map(|e| separate(" ",
+            e.path(),
+            "+" ++ e.added(),
+            "-" ++ e.removed(),
+          ) ++ "\n").join(""),
           "* " ++ separate(" ",
             "total_added=" ++ diff.stat().total_added(),
             "total_removed=" ++ diff.stat().total_removed(),
+            "len=" ++ diff.stat().files().len(),
+            "file1_added=" ++ self.diff("file1").stat().total_added(),
           ) ++ "\n",
         )
     "#};
     let output = test_env.run_jj_in(&repo_path, ["log", "--no-graph", "-T", template]);
     insta::assert_snapshot!(output, @r"
     === fbad2dd53d06 ===
-    * total_added=3 total_removed=1
+    file1 +1 -0
+    file2 +2 -1
+    rename-target +0 -0
+    * total_added=3 total_removed=1 len=3 file1_added=1
     === 3c9b3178609b ===
-    * total_added=4 total_removed=0
+    file1 +2 -0
+    file2 +1 -0
+    rename-source +1 -0
+    * total_added=4 total_removed=0 len=3 file1_added=2
     === 000000000000 ===
-    * total_added=0 total_removed=0
+    * total_added=0 total_removed=0 len=0 file1_added=0
     [EOF]
     ");
 }
@@ -1605,6 +1688,33 @@ fn test_file_list_symlink() {
     ");
 }
 
+#[test]
+fn test_file_list_null_terminated() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::create_dir(repo_path.join("dir")).unwrap();
+    std::fs::write(repo_path.join("dir").join("file"), "").unwrap();
+    std::fs::write(repo_path.join("file with\nnewline"), "").unwrap();
+
+    let output = test_env
+        .run_jj_in(&repo_path, ["file", "list", "-z", "--color=always"])
+        .success();
+    insta::assert_debug_snapshot!(output.stdout.normalized(), @r#"
+    "dir/file\0file with\nnewline\0"
+    "#);
+
+    // Only the trailing newline of the template output is replaced
+    let template = r#"path ++ "\n" ++ file_type ++ "\n""#;
+    let output = test_env
+        .run_jj_in(&repo_path, ["file", "list", "--null", "-T", template])
+        .success();
+    insta::assert_debug_snapshot!(output.stdout.normalized(), @r#"
+    "dir/file\nfile\0file with\nnewline\nfile\0"
+    "#);
+}
+
 #[test]
 fn test_repo_path() {
     let test_env = TestEnvironment::default();
@@ -1639,6 +1749,189 @@ fn test_repo_path() {
     ");
 }
 
+#[test]
+fn test_repo_path_manipulation() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::create_dir_all(repo_path.join("dir").join("sub")).unwrap();
+    std::fs::write(repo_path.join("dir").join(".bashrc"), "").unwrap();
+    std::fs::write(repo_path.join("dir").join("sub").join("a.tar.gz"), "").unwrap();
+    std::fs::write(repo_path.join("file"), "").unwrap();
+
+    let template = indoc! {r#"
+        separate(" ",
+          path,
+          "name=" ++ path.file_name(),
+          "ext=" ++ path.extension(),
+          "rel=" ++ path.relative_to("dir/sub"),
+          if(path.matches("dir/*"), "dir/*"),
+          if(path.matches("**/*.gz"), "**/*.gz"),
+        ) ++ "\n"
+    "#};
+    let output = test_env.run_jj_in(&repo_path, ["file", "list", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    dir/.bashrc name=.bashrc ext= rel=../.bashrc dir/*
+    dir/sub/a.tar.gz name=a.tar.gz ext=gz rel=a.tar.gz **/*.gz
+    file name=file ext= rel=../../file
+    [EOF]
+    ");
+
+    // The root directory can be specified as an empty string or "."
+    let template = r#"separate(" ", path.relative_to(""), path.relative_to(".")) ++ "\n""#;
+    let output = test_env.run_jj_in(&repo_path, ["file", "list", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    dir/.bashrc dir/.bashrc
+    dir/sub/a.tar.gz dir/sub/a.tar.gz
+    file file
+    [EOF]
+    ");
+
+    let template = r#"path.relative_to("../dir") ++ "\n""#;
+    let output = test_env.run_jj_in(&repo_path, ["file", "list", "-T", template]);
+    insta::assert_snapshot!(output, @r#"
+    <Error: Invalid component ".." in repo-relative path "../dir">
+    <Error: Invalid component ".." in repo-relative path "../dir">
+    <Error: Invalid component ".." in repo-relative path "../dir">
+    [EOF]
+    "#);
+
+    let output = test_env.run_jj_in(&repo_path, ["file", "list", "-T", r#"path.matches("[")"#]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Error: Failed to parse template: Invalid glob pattern
+    Caused by:
+    1:  --> 1:14
+      |
+    1 | path.matches("[")
+      |              ^-^
+      |
+      = Invalid glob pattern
+    2: Pattern syntax error near position 0: invalid range pattern
+    [EOF]
+    [exit status: 1]
+    "#);
+}
+
+#[test]
+fn test_commit_labels() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "first"])
+        .success();
+    test_env
+        .run_jj_in(
+            &repo_path,
+            ["new", "-m", "second", "--config=user.name=Someone Else"],
+        )
+        .success();
+    test_env.add_config(indoc! {r#"
+        [commit-labels]
+        first-rule = { revset = "description(first)" }
+        other-user = { author = "Someone" }
+        both = { revset = "description(first)", author = "glob:*@example.com" }
+    "#});
+
+    let template = r#"label("desc", description.first_line()) ++ "\n""#;
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["log", "--no-graph", "--color=debug", "-T", template],
+    );
+    insta::assert_snapshot!(output, @r"
+    <<log other-user desc description first_line::second>><<log other-user::>>
+    <<log first-rule both desc description first_line::first>><<log first-rule both::>>
+    <<log::>>
+    [EOF]
+    ");
+
+    // Labels apply to the commit summaries printed by commands
+    let output = test_env.run_jj_in(&repo_path, ["edit", "--color=debug", "@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy now at: [1m[38;5;13m<<working_copy first-rule both change_id shortest prefix::q>>[38;5;8m<<working_copy first-rule both change_id shortest rest::pvuntsm>>[39m<<working_copy first-rule both:: >>[38;5;12m<<working_copy first-rule both commit_id shortest prefix::f>>[38;5;8m<<working_copy first-rule both commit_id shortest rest::a15625b>>[39m<<working_copy first-rule both:: >>[38;5;10m<<working_copy first-rule both empty::(empty)>>[39m<<working_copy first-rule both:: >><<working_copy first-rule both description first_line::first>>[0m
+    Parent commit      : [1m[38;5;5m<<change_id shortest prefix::z>>[0m[38;5;8m<<change_id shortest rest::zzzzzzz>>[39m [1m[38;5;4m<<commit_id shortest prefix::0>>[0m[38;5;8m<<commit_id shortest rest::0000000>>[39m [38;5;2m<<empty::(empty)>>[39m [38;5;2m<<empty description placeholder::(no description set)>>[39m
+    [EOF]
+    ");
+
+    // Labels can be styled by the colors table
+    test_env.add_config(r#"colors."first-rule desc" = "red""#);
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["log", "--no-graph", "--color=always", "-T", template],
+    );
+    insta::assert_snapshot!(output, @r"
+    second
+    [38;5;1mfirst[39m
+
+    [EOF]
+    ");
+
+    // Rules with a label condition only apply within the labeled templates
+    test_env.add_config(indoc! {r#"
+        [commit-labels]
+        first-rule = { revset = "description(first)", label = "log" }
+    "#});
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["log", "--no-graph", "--color=debug", "-r@", "-T", template],
+    );
+    insta::assert_snapshot!(output, @r"
+    [38;5;1m<<log first-rule both desc description first_line::first>>[39m<<log first-rule both::>>
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["show", "--color=debug", "-T", template, "@"]);
+    insta::assert_snapshot!(output, @r"
+    <<both desc description first_line::first>><<both::>>
+    [EOF]
+    ");
+
+    // Invalid rules are reported
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "log",
+            "--config=commit-labels.bad.revset=bad(",
+            "-T",
+            template,
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Config error: Invalid `commit-labels.bad`
+    Caused by:  --> 1:5
+      |
+    1 | bad(
+      |     ^---
+      |
+      = expected <strict_identifier> or <expression>
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "log",
+            "--config=commit-labels.bad.unknown=1",
+            "-T",
+            template,
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Config error: Invalid type or value for commit-labels.bad
+    Caused by: unknown field `unknown`, expected one of `revset`, `author`, `label`
+
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_signature_templates() {
     let test_env = TestEnvironment::default();
@@ -100,8 +100,11 @@ fn test_bookmark_names() {
     --repository	Path to repository to operate on
     --ignore-working-copy	Don't snapshot the working copy, and don't update it
     --ignore-immutable	Allow rewriting immutable commits
+    --allow-protected	Allow rewriting protected commits
     --at-operation	Operation to load the repo at
+    --event-log	Write machine-readable events to the given file descriptor
     --debug	Enable debug logging
+    --no-default-args	Ignore the `commands.<name>.default-args` setting
     --color	When to colorize output
     --quiet	Silence non-primary command output
     --no-pager	Disable the pager
@@ -679,16 +682,39 @@ fn test_config() {
 
     let output = test_env.run_jj_in(dir, ["--", "jj", "config", "get", "c"]);
     insta::assert_snapshot!(output, @r"
+    changelog.categories	Categories to group revisions by, in order. Each revision is put in the first matching category.
+    ci.github.program	Path to the GitHub CLI executable
+    ci.github.repository	GitHub repository in `owner/name` form. If unset, the GitHub CLI detects it from the Git remotes.
+    ci.provider	CI system to fetch statuses from
+    conventional-commits.enabled	Whether `jj describe` and `jj commit` should check that new descriptions are in the conventional commit format, and list the types and recent scopes in the editor
+    conventional-commits.types	Allowed conventional commit types. If empty, any type is allowed.
     core.fsmonitor	Whether to use an external filesystem monitor, useful for large repos
+    core.tree-diff-cache.enabled	Whether to cache tree diffs and conflicts in the repo directory
+    core.tree-diff-cache.max-entries	Maximum number of cached diffs, and of cached conflict lists
     core.watchman.register-snapshot-trigger	Whether to use triggers to monitor for changes in the background.
     [EOF]
     ");
 
     let output = test_env.run_jj_in(dir, ["--", "jj", "config", "list", "c"]);
     insta::assert_snapshot!(output, @r"
+    changelog	Settings for jj changelog
+    changelog.categories	Categories to group revisions by, in order. Each revision is put in the first matching category.
+    ci	Settings for fetching CI statuses of revisions
+    ci.github	Settings for fetching GitHub check runs
+    ci.github.program	Path to the GitHub CLI executable
+    ci.github.repository	GitHub repository in `owner/name` form. If unset, the GitHub CLI detects it from the Git remotes.
+    ci.provider	CI system to fetch statuses from
     colors	Mapping from jj formatter labels to colors
+    commands	Settings of individual commands. Subcommands are nested tables, e.g. `commands.file.list`
+    commit-labels	Labels applied to commit templates for commits matching the given conditions
+    conventional-commits	Settings for descriptions in the Conventional Commits format
+    conventional-commits.enabled	Whether `jj describe` and `jj commit` should check that new descriptions are in the conventional commit format, and list the types and recent scopes in the editor
+    conventional-commits.types	Allowed conventional commit types. If empty, any type is allowed.
     core
     core.fsmonitor	Whether to use an external filesystem monitor, useful for large repos
+    core.tree-diff-cache
+    core.tree-diff-cache.enabled	Whether to cache tree diffs and conflicts in the repo directory
+    core.tree-diff-cache.max-entries	Maximum number of cached diffs, and of cached conflict lists
     core.watchman
     core.watchman.register-snapshot-trigger	Whether to use triggers to monitor for changes in the background.
     [EOF]
@@ -696,7 +722,15 @@ fn test_config() {
 
     let output = test_env.run_jj_in(dir, ["--", "jj", "log", "--config", "c"]);
     insta::assert_snapshot!(output, @r"
+    changelog.categories=	Categories to group revisions by, in order. Each revision is put in the first matching category.
+    ci.github.program=	Path to the GitHub CLI executable
+    ci.github.repository=	GitHub repository in `owner/name` form. If unset, the GitHub CLI detects it from the Git remotes.
+    ci.provider=	CI system to fetch statuses from
+    conventional-commits.enabled=	Whether `jj describe` and `jj commit` should check that new descriptions are in the conventional commit format, and list the types and recent scopes in the editor
+    conventional-commits.types=	Allowed conventional commit types. If empty, any type is allowed.
     core.fsmonitor=	Whether to use an external filesystem monitor, useful for large repos
+    core.tree-diff-cache.enabled=	Whether to cache tree diffs and conflicts in the repo directory
+    core.tree-diff-cache.max-entries=	Maximum number of cached diffs, and of cached conflict lists
     core.watchman.register-snapshot-trigger=	Whether to use triggers to monitor for changes in the background.
     [EOF]
     ");
@@ -1457,3 +1457,91 @@ fn find_stdout_lines(keyname_pattern: &str, stdout: &str) -> String {
     let key_line_re = Regex::new(&format!(r"(?m)^{keyname_pattern} = .*\n")).unwrap();
     key_line_re.find_iter(stdout).map(|m| m.as_str()).collect()
 }
+
+#[test]
+fn test_config_theme() {
+    let mut test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    let output = test_env.run_jj_in(&repo_path, ["config", "theme", "list"]);
+    insta::assert_snapshot!(output, @r"
+    default (current): The default colors, which work on dark and light backgrounds
+      @  qpvuntsm user@example.com 2001-02-03 08:05:07 main 230dd059 (empty) (no description set)
+    high-contrast: Bright colors on dark backgrounds, and bold dark colors on light backgrounds
+      dark:  @  qpvuntsm user@example.com 2001-02-03 08:05:07 main 230dd059 (empty) (no description set)
+      light: @  qpvuntsm user@example.com 2001-02-03 08:05:07 main 230dd059 (empty) (no description set)
+    solarized: Colors from the Solarized palette
+      dark:  @  qpvuntsm user@example.com 2001-02-03 08:05:07 main 230dd059 (empty) (no description set)
+      light: @  qpvuntsm user@example.com 2001-02-03 08:05:07 main 230dd059 (empty) (no description set)
+    [EOF]
+    ");
+
+    // The theme's colors apply to all output
+    let render = |test_env: &TestEnvironment, args: &[&str]| {
+        let mut args = args.to_vec();
+        args.extend(["log", "-r@", "-T", "commit_id.short()", "--color=always"]);
+        test_env.run_jj_in(&repo_path, args)
+    };
+    insta::assert_snapshot!(render(&test_env, &[]), @r"
+    [1m[38;5;2m@[0m  [38;5;4m230dd059e1b0[39m
+    │
+    ~
+    [EOF]
+    ");
+    insta::assert_snapshot!(render(&test_env, &["--config=ui.theme=high-contrast"]), @r"
+    [1m[4m[38;5;10m@[0m  [38;5;12m230dd059e1b0[39m
+    │
+    ~
+    [EOF]
+    ");
+    insta::assert_snapshot!(
+        render(
+            &test_env,
+            &[
+                "--config=ui.theme=high-contrast",
+                "--config=ui.theme-background=light",
+            ],
+        ),
+        @r"
+    [1m[4m[38;5;2m@[0m  [1m[38;5;4m230dd059e1b0[0m
+    │
+    ~
+    [EOF]
+    ");
+
+    // The background is detected from the environment
+    test_env.add_env_var("COLORFGBG", "0;15");
+    insta::assert_snapshot!(render(&test_env, &["--config=ui.theme=high-contrast"]), @r"
+    [1m[4m[38;5;2m@[0m  [1m[38;5;4m230dd059e1b0[0m
+    │
+    ~
+    [EOF]
+    ");
+
+    // User colors take precedence over the theme
+    insta::assert_snapshot!(
+        render(
+            &test_env,
+            &[
+                "--config=ui.theme=high-contrast",
+                "--config=colors.commit_id=red",
+            ],
+        ),
+        @r"
+    [1m[4m[38;5;2m@[0m  [38;5;1m230dd059e1b0[39m
+    │
+    ~
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["log", "--config=ui.theme=unknown"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Config error: Invalid type or value for ui.theme
+    Caused by: Unknown theme "unknown"
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    "#);
+}
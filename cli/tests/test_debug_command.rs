@@ -89,6 +89,50 @@ fn test_debug_revset() {
     });
 }
 
+#[test]
+fn test_debug_revset_explain() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let workspace_path = test_env.env_root().join("repo");
+    test_env
+        .run_jj_in(&workspace_path, ["new", "-m", "a"])
+        .success();
+    test_env
+        .run_jj_in(&workspace_path, ["new", "-m", "b"])
+        .success();
+
+    let output = test_env.run_jj_in(
+        &workspace_path,
+        [
+            "debug",
+            "revset",
+            "--explain",
+            "(::@ ~ root()) & description(a) | heads(all())",
+        ],
+    );
+    let output = output.normalize_stdout_with(|s| {
+        let profile = s.split_once("-- Profile:\n").unwrap().1;
+        let time_re = regex::Regex::new(r"[0-9.]+(ns|µs|ms|s)\b").unwrap();
+        time_re.replace_all(profile, "<time>").into_owned()
+    });
+    assert_snapshot!(output, @r#"
+    FilterWithin: evaluated in <time>, yielded 2 in <time>
+      Ancestors: evaluated in <time>, yielded 4 in <time>
+        Commits count=1: evaluated in <time>, yielded 1 in <time>
+      Union: evaluated in <time>, matched 2/4 in <time>
+        FilterWithin: evaluated in <time>, matched 1/4 in <time>
+          Difference: evaluated in <time>, matched 3/4 in <time>
+            Ancestors: evaluated in <time>, matched 4/4 in <time>
+              Commits count=1: evaluated in <time>, yielded 1 in <time>
+            Commits count=1: evaluated in <time>, matched 1/4 in <time>
+          Filter Description(Substring("a")): evaluated in <time>, matched 1/3 in <time>
+        Heads: evaluated in <time>, matched 1/3 in <time>
+          Ancestors: evaluated in <time>, yielded 4 in <time>
+            Commits count=1: evaluated in <time>, yielded 1 in <time>
+    [EOF]
+    "#);
+}
+
 #[test]
 fn test_debug_index() {
     let test_env = TestEnvironment::default();
@@ -252,6 +296,586 @@ fn test_debug_operation_id() {
     ");
 }
 
+#[test]
+fn test_debug_unlock() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let workspace_path = test_env.env_root().join("repo");
+    let output = test_env.run_jj_in(&workspace_path, ["debug", "unlock"]);
+    assert_snapshot!(output, @r"
+    ------- stderr -------
+    No lock files found.
+    [EOF]
+    ");
+
+    // Locks are only removed with --force, even if their lease has expired
+    let op_heads_lock = workspace_path.join(".jj/repo/op_heads/lock");
+    std::fs::write(&op_heads_lock, "pid 1\nhost elsewhere\nacquired 0\n").unwrap();
+    let output = test_env.run_jj_in(&workspace_path, ["debug", "unlock"]);
+    assert_snapshot!(output, @r"
+    ------- stderr -------
+    Lock .jj/repo/op_heads/lock is held by process 1 on elsewhere, but looks stale (the lock lease has expired); use --force to remove it
+    [EOF]
+    ");
+    assert!(op_heads_lock.exists());
+
+    let output = test_env.run_jj_in(&workspace_path, ["debug", "unlock", "--force"]);
+    assert_snapshot!(output, @r"
+    ------- stderr -------
+    Removed lock .jj/repo/op_heads/lock held by process 1 on elsewhere
+    [EOF]
+    ");
+    assert!(!op_heads_lock.exists());
+
+    // Lock taken on this host by a process which is no longer running is kept
+    #[cfg(unix)]
+    {
+        let current = jj_lib::lock::LockOwner::current();
+        let hostname = current.hostname;
+        let wc_lock = workspace_path.join(".jj/working_copy/working_copy.lock");
+        std::fs::write(
+            &wc_lock,
+            format!("pid 0\nhost {hostname}\nacquired {}\n", current.acquired),
+        )
+        .unwrap();
+        let output = test_env
+            .run_jj_in(&workspace_path, ["debug", "unlock"])
+            .normalize_stderr_with(|stderr| {
+                stderr.replace(&format!(" on {hostname},"), " on <hostname>,")
+            });
+        assert_snapshot!(output, @r"
+        ------- stderr -------
+        Lock .jj/working_copy/working_copy.lock is held by process 0 on <hostname>, but looks stale (the owner process is not running); use --force to remove it
+        [EOF]
+        ");
+        assert!(wc_lock.exists());
+    }
+}
+
+#[test]
+fn test_debug_fast_export() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(repo_path.join("file"), "a\n").unwrap();
+    std::fs::create_dir(repo_path.join("dir")).unwrap();
+    std::fs::write(repo_path.join("dir").join("other"), "other\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "first"])
+        .success();
+    std::fs::write(repo_path.join("file"), "b\n").unwrap();
+    std::fs::remove_dir_all(repo_path.join("dir")).unwrap();
+    std::fs::write(repo_path.join("dir"), "other\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["file", "chmod", "x", "file"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "second"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "create", "-r@-", "main"])
+        .success();
+
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["debug", "fast-export", "-r=::@-", "--export-marks=../marks"],
+    );
+    assert_snapshot!(output, @r"
+    blob
+    mark :1
+    data 6
+    other
+
+    blob
+    mark :2
+    data 2
+    a
+
+    reset refs/jj/fast-export
+    commit refs/jj/fast-export
+    mark :3
+    original-oid 66db2d0f9d83aab70630c8f5b21b568a1e3180f5
+    author Test User <test.user@example.com> 981147908 +0700
+    committer Test User <test.user@example.com> 981147908 +0700
+    data 6
+    first
+
+    M 100644 :1 dir/other
+    M 100644 :2 file
+
+    blob
+    mark :4
+    data 2
+    b
+
+    commit refs/jj/fast-export
+    mark :5
+    original-oid fdc805e69846ac9b473e768a02be4ca35109ef24
+    author Test User <test.user@example.com> 981147909 +0700
+    committer Test User <test.user@example.com> 981147910 +0700
+    data 7
+    second
+
+    from :3
+    D dir/other
+    M 100644 :1 dir
+    M 100755 :4 file
+
+    reset refs/heads/main
+    from :5
+
+    [EOF]
+    ");
+    let marks = std::fs::read_to_string(test_env.env_root().join("marks")).unwrap();
+    assert_snapshot!(marks, @r"
+    :3 66db2d0f9d83aab70630c8f5b21b568a1e3180f5
+    :5 fdc805e69846ac9b473e768a02be4ca35109ef24
+    ");
+
+    // Previously exported commits are skipped and referred to by their marks
+    std::fs::write(repo_path.join("file"), "<<<<<<< conflict\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "third"])
+        .success();
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "debug",
+            "fast-export",
+            "-r=::@",
+            "--import-marks=../marks",
+            "--export-marks=../marks",
+        ],
+    );
+    assert_snapshot!(output, @r"
+    blob
+    mark :6
+    data 17
+    <<<<<<< conflict
+
+    commit refs/jj/fast-export
+    mark :7
+    original-oid 1e224cea1cb65ff1ddc0c38aea29006460a32b01
+    author Test User <test.user@example.com> 981147913 +0700
+    committer Test User <test.user@example.com> 981147913 +0700
+    data 6
+    third
+
+    from :5
+    M 100755 :6 file
+
+    reset refs/heads/main
+    from :5
+
+    [EOF]
+    ");
+    let marks = std::fs::read_to_string(test_env.env_root().join("marks")).unwrap();
+    assert_snapshot!(marks, @r"
+    :3 66db2d0f9d83aab70630c8f5b21b568a1e3180f5
+    :5 fdc805e69846ac9b473e768a02be4ca35109ef24
+    :7 1e224cea1cb65ff1ddc0c38aea29006460a32b01
+    ");
+
+    // Parents which aren't exported are omitted
+    let output = test_env.run_jj_in(&repo_path, ["debug", "fast-export", "-r=@"]);
+    assert_snapshot!(output, @r"
+    blob
+    mark :1
+    data 6
+    other
+
+    blob
+    mark :2
+    data 17
+    <<<<<<< conflict
+
+    reset refs/jj/fast-export
+    commit refs/jj/fast-export
+    mark :3
+    original-oid 1e224cea1cb65ff1ddc0c38aea29006460a32b01
+    author Test User <test.user@example.com> 981147913 +0700
+    committer Test User <test.user@example.com> 981147913 +0700
+    data 6
+    third
+
+    M 100644 :1 dir
+    M 100755 :2 file
+
+    [EOF]
+    ");
+
+    std::fs::write(test_env.env_root().join("marks"), "1 abc\n").unwrap();
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["debug", "fast-export", "--import-marks=../marks"],
+    );
+    assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Invalid line in marks file: 1 abc
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Identities which would corrupt the stream are rejected. The Git backend
+    // doesn't accept them in the first place.
+    test_env
+        .run_jj_in(".", ["debug", "init-local", "local"])
+        .success();
+    let local_path = test_env.env_root().join("local");
+    test_env
+        .run_jj_in(
+            &local_path,
+            [
+                "describe",
+                "-m=bad",
+                "--config=user.name='Bad <Name>'",
+                "--reset-author",
+            ],
+        )
+        .success();
+    let output = test_env.run_jj_in(&local_path, ["debug", "fast-export", "-r=@"]);
+    assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Cannot export commit ecebb58f8e2239f9f2b1d7c20593b026b2ac37b62774fc2df695b45316a70b2fc5b36ce375950e5c896942c1dc20efe2f8a02810aa0845bbaeebfd5ad4419113 with invalid author name or email
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_debug_migrate_backend() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(repo_path.join("file"), "base\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "base"])
+        .success();
+    std::fs::write(repo_path.join("file"), "left\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "left"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "create", "-r@", "left"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "@-", "-m", "right"])
+        .success();
+    std::fs::write(repo_path.join("file"), "right\n").unwrap();
+    std::fs::create_dir(repo_path.join("dir")).unwrap();
+    std::fs::write(repo_path.join("dir/other"), "other\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["new", "left", "@", "-m", "merge"])
+        .success();
+    let template = r#"change_id.short() ++ " " ++ description.first_line() ++ " " ++ bookmarks ++ if(conflict, " conflict") ++ "\n""#;
+    let log_before = test_env
+        .run_jj_in(&repo_path, ["log", "-T", template])
+        .to_string();
+    let op_log_template = r#"description ++ "\n""#;
+    let op_log_before = test_env
+        .run_jj_in(&repo_path, ["op", "log", "-T", op_log_template])
+        .to_string();
+    assert_snapshot!(log_before, @r"
+    @    royxmykxtrkr merge  conflict
+    ├─╮
+    │ ○  mzvwutvlkqwt right
+    ○ │  rlvkpnrzqnoo left left
+    ├─╯
+    ○  qpvuntsmwlqt base
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["debug", "migrate-backend", "--to", "native"]);
+    assert_snapshot!(output, @r"
+    ------- stderr -------
+    Migrated 10 commits and 10 operations to the local backend.
+    Hint: The old store was moved to .jj/repo/store.old.
+    [EOF]
+    ");
+    assert!(repo_path.join(".jj/repo/store.old/git").is_dir());
+    assert!(!repo_path.join(".jj/repo/migrate_backend").exists());
+    let output = test_env.run_jj_in(&repo_path, ["debug", "migrate-backend", "--to", "native"]);
+    assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: The repo already uses the local backend
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Change ids, bookmarks, and the operation log are preserved
+    assert_eq!(
+        test_env
+            .run_jj_in(&repo_path, ["log", "-T", template])
+            .to_string(),
+        log_before
+    );
+    assert_eq!(
+        test_env
+            .run_jj_in(&repo_path, ["op", "log", "-T", op_log_template])
+            .to_string(),
+        op_log_before
+    );
+    let output = test_env.run_jj_in(&repo_path, ["file", "show", "-r", "left", "file"]);
+    assert_snapshot!(output, @r"
+    left
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["status"]);
+    assert_snapshot!(output, @r"
+    The working copy has no changes.
+    Working copy : royxmykx 641f25fe (conflict) (empty) merge
+    Parent commit: rlvkpnrz a773d877 left | left
+    Parent commit: mzvwutvl 12d0c929 right
+    Warning: There are unresolved conflicts at these paths:
+    file    2-sided conflict
+    Conflicts in file came from operation 20e5f790184b (2001-02-03 08:05:12) new empty commit, with sides from rlvkpnrz, mzvwutvl
+    [EOF]
+    ");
+
+    // The backup of the old store must be removed before migrating again
+    let output = test_env.run_jj_in(&repo_path, ["debug", "migrate-backend", "--to", "git"]);
+    assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: .jj/repo/store.old already exists
+    Hint: Remove the backup of the previous migration first.
+    [EOF]
+    [exit status: 1]
+    ");
+    std::fs::remove_dir_all(repo_path.join(".jj/repo/store.old")).unwrap();
+    let output = test_env.run_jj_in(&repo_path, ["debug", "migrate-backend", "--to", "git"]);
+    assert_snapshot!(output, @r"
+    ------- stderr -------
+    Migrated 10 commits and 10 operations to the git backend.
+    Hint: The old store was moved to .jj/repo/store.old.
+    [EOF]
+    ");
+    assert_eq!(
+        test_env
+            .run_jj_in(&repo_path, ["log", "-T", template])
+            .to_string(),
+        log_before
+    );
+    let output = test_env.run_jj_in(&repo_path, ["diff", "-r", "description(right)", "--git"]);
+    assert_snapshot!(output, @r"
+    diff --git a/dir/other b/dir/other
+    new file mode 100644
+    index 0000000000..e45c9c2666
+    --- /dev/null
+    +++ b/dir/other
+    @@ -0,0 +1,1 @@
+    +other
+    diff --git a/file b/file
+    index df967b96a5..c376d892e8 100644
+    --- a/file
+    +++ b/file
+    @@ -1,1 +1,1 @@
+    -base
+    +right
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_debug_migrate_backend_interrupted() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(repo_path.join("file"), "a\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "a"])
+        .success();
+    std::fs::write(repo_path.join("file"), "b\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "b"])
+        .success();
+
+    // Simulate a migration interrupted after copying the first commit by
+    // reusing the store of a previous migration. The incomplete last line of
+    // the commit map is ignored.
+    test_env
+        .run_jj_in(&repo_path, ["debug", "migrate-backend", "--to", "native"])
+        .success();
+    let native_id = test_env
+        .run_jj_in(&repo_path, ["log", "--no-graph", "-r=@--", "-T=commit_id"])
+        .success()
+        .stdout
+        .into_raw();
+    std::fs::remove_dir_all(repo_path.join(".jj/repo/store.old")).unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["debug", "migrate-backend", "--to", "git"])
+        .success();
+    let git_id = test_env
+        .run_jj_in(&repo_path, ["log", "--no-graph", "-r=@--", "-T=commit_id"])
+        .success()
+        .stdout
+        .into_raw();
+    let migration_dir = repo_path.join(".jj/repo/migrate_backend");
+    std::fs::create_dir(&migration_dir).unwrap();
+    std::fs::rename(
+        repo_path.join(".jj/repo/store.old"),
+        migration_dir.join("store"),
+    )
+    .unwrap();
+    std::fs::write(
+        migration_dir.join("commit_map"),
+        format!("{git_id} {native_id}\n{git_id}"),
+    )
+    .unwrap();
+    let output = test_env.run_jj_in(&repo_path, ["debug", "migrate-backend", "--to", "native"]);
+    assert_snapshot!(output, @r"
+    ------- stderr -------
+    Resuming migration with 1 commits already copied.
+    Migrated 8 commits and 6 operations to the local backend.
+    Hint: The old store was moved to .jj/repo/store.old.
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["log", "-T", "description"]);
+    assert_snapshot!(output, @r"
+    @
+    ○  b
+    ○  a
+    ◆
+    [EOF]
+    ");
+
+    // A migration to another backend can't be started until the interrupted
+    // one is resumed or removed.
+    std::fs::remove_dir_all(repo_path.join(".jj/repo/store.old")).unwrap();
+    let migration_store = migration_dir.join("store");
+    std::fs::create_dir_all(&migration_store).unwrap();
+    std::fs::write(migration_store.join("type"), "local").unwrap();
+    let output = test_env.run_jj_in(&repo_path, ["debug", "migrate-backend", "--to", "git"]);
+    assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: A migration to the local backend is in progress
+    Hint: Resume it, or remove .jj/repo/migrate_backend to start over.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Simulate a migration interrupted after moving the old store away, but
+    // before moving the new store into place and updating the op heads.
+    std::fs::remove_dir_all(&migration_dir).unwrap();
+    let get_op_id = || {
+        test_env
+            .run_jj_in(&repo_path, ["op", "log", "--no-graph", "-n1", "-T=id"])
+            .success()
+            .stdout
+            .into_raw()
+    };
+    let old_head_id = get_op_id();
+    test_env
+        .run_jj_in(&repo_path, ["debug", "migrate-backend", "--to", "git"])
+        .success();
+    let new_head_id = get_op_id();
+    std::fs::create_dir(&migration_dir).unwrap();
+    std::fs::rename(
+        repo_path.join(".jj/repo/store"),
+        migration_dir.join("store"),
+    )
+    .unwrap();
+    let op_heads_dir = repo_path.join(".jj/repo/op_heads/heads");
+    std::fs::remove_file(op_heads_dir.join(&new_head_id)).unwrap();
+    std::fs::write(op_heads_dir.join(&old_head_id), "").unwrap();
+    std::fs::write(
+        migration_dir.join("swap"),
+        format!("{old_head_id} {new_head_id}\n"),
+    )
+    .unwrap();
+    let output = test_env.run_jj_in(&repo_path, ["debug", "migrate-backend", "--to", "native"]);
+    assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: A migration to the git backend is in progress
+    Hint: Run this command with `--to git` to finish it.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["debug", "migrate-backend", "--to", "git"]);
+    assert_snapshot!(output, @r"
+    ------- stderr -------
+    Finishing the interrupted migration to the git backend.
+    Hint: The old store was moved to .jj/repo/store.old.
+    [EOF]
+    ");
+    assert!(!migration_dir.exists());
+    let output = test_env.run_jj_in(&repo_path, ["log", "-T", "description"]);
+    assert_snapshot!(output, @r"
+    @
+    ○  b
+    ○  a
+    ◆
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_debug_conflict_show() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    let create_commit = |name: &str, parents: &[&str]| {
+        test_env
+            .run_jj_with(|cmd| {
+                cmd.current_dir(&repo_path)
+                    .args(["new", "-m", name])
+                    .args(parents)
+            })
+            .success();
+        test_env
+            .run_jj_in(&repo_path, ["bookmark", "create", "-r@", name])
+            .success();
+    };
+    create_commit("base", &["root()"]);
+    std::fs::write(repo_path.join("file"), "base\n").unwrap();
+    std::fs::write(repo_path.join("other"), "base\n").unwrap();
+    create_commit("a", &["base"]);
+    std::fs::write(repo_path.join("file"), "a\n").unwrap();
+    std::fs::write(repo_path.join("other"), "a\n").unwrap();
+    create_commit("b", &["base"]);
+    std::fs::write(repo_path.join("file"), "b\n").unwrap();
+    std::fs::remove_file(repo_path.join("other")).unwrap();
+    create_commit("merge", &["a", "b"]);
+
+    let output = test_env.run_jj_in(&repo_path, ["debug", "conflict", "show"]);
+    assert_snapshot!(output, @r#"
+    Tree: 2 sides
+      + tree 59dea67f6a7b3f769e8820fa6bf21b00ff9849e7 (zsuskuln "a")
+      - tree d5489f09c36472095c7468b2d5fae0fc3f8f5cc1 (rlvkpnrz "base")
+      + tree 84bf061d017459b4be45a49b8d8dc945e7a7fdf5 (royxmykx "b")
+    file: 2-sided conflict
+      + file 78981922613b2afb6025042ff6bd878ac1994e85 (zsuskuln "a")
+      - file df967b96a579e45a18b8251732d16804b2e56a55 (rlvkpnrz "base")
+      + file 61780798228d17af2d34fce4cfbdf35556832472 (royxmykx "b")
+    other: 2-sided conflict
+      + file 78981922613b2afb6025042ff6bd878ac1994e85 (zsuskuln "a")
+      - file df967b96a579e45a18b8251732d16804b2e56a55 (rlvkpnrz "base")
+      + absent (royxmykx "b")
+    [EOF]
+    "#);
+
+    let output = test_env.run_jj_in(&repo_path, ["debug", "conflict", "show", "--json", "file"]);
+    assert_snapshot!(output, @r#"
+    {"commit_id":"22dfe60011ea83a62a2498783c0312dd9c0c9c21","conflicts":[{"num_sides":2,"path":"file","terms":[{"commit":{"change_id":"zsuskulnrvyrovkzqrwmxqlsskqntxvp","commit_id":"297a313add43488b2691a86a6ac0cae5bebfc0af"},"executable":false,"id":"78981922613b2afb6025042ff6bd878ac1994e85","sign":"+","type":"file"},{"commit":{"change_id":"rlvkpnrzqnoowoytxnquwvuryrwnrmlp","commit_id":"7fe54172059206122ac5d4100ac71f6da934800f"},"executable":false,"id":"df967b96a579e45a18b8251732d16804b2e56a55","sign":"-","type":"file"},{"commit":{"change_id":"royxmykxtrkrqppotnrvutxlvrvqyxmy","commit_id":"58807a1d8cdecafbd4ef5210683ce7ae4eba88e6"},"executable":false,"id":"61780798228d17af2d34fce4cfbdf35556832472","sign":"+","type":"file"}]}],"tree":[{"commit":{"change_id":"zsuskulnrvyrovkzqrwmxqlsskqntxvp","commit_id":"297a313add43488b2691a86a6ac0cae5bebfc0af"},"sign":"+","tree_id":"59dea67f6a7b3f769e8820fa6bf21b00ff9849e7"},{"commit":{"change_id":"rlvkpnrzqnoowoytxnquwvuryrwnrmlp","commit_id":"7fe54172059206122ac5d4100ac71f6da934800f"},"sign":"-","tree_id":"d5489f09c36472095c7468b2d5fae0fc3f8f5cc1"},{"commit":{"change_id":"royxmykxtrkrqppotnrvutxlvrvqyxmy","commit_id":"58807a1d8cdecafbd4ef5210683ce7ae4eba88e6"},"sign":"+","tree_id":"84bf061d017459b4be45a49b8d8dc945e7a7fdf5"}]}
+    [EOF]
+    "#);
+
+    // The originating commits are unknown if the conflict was inherited
+    test_env.run_jj_in(&repo_path, ["new"]).success();
+    let output = test_env.run_jj_in(&repo_path, ["debug", "conflict", "show", "other"]);
+    assert_snapshot!(output, @r"
+    Tree: 2 sides
+      + tree 59dea67f6a7b3f769e8820fa6bf21b00ff9849e7
+      - tree d5489f09c36472095c7468b2d5fae0fc3f8f5cc1
+      + tree 84bf061d017459b4be45a49b8d8dc945e7a7fdf5
+    other: 2-sided conflict
+      + file 78981922613b2afb6025042ff6bd878ac1994e85
+      - file df967b96a579e45a18b8251732d16804b2e56a55
+      + absent
+    [EOF]
+    ");
+}
+
 fn filter_index_stats(output: CommandOutput) -> CommandOutput {
     let regex = Regex::new(r"    Name: [0-9a-z]+").unwrap();
     output.normalize_stdout_with(|text| regex.replace_all(&text, "    Name: [hash]").into_owned())
@@ -651,6 +651,126 @@ fn test_describe_default_description() {
     "#);
 }
 
+#[test]
+fn test_describe_conventional_commits() {
+    let mut test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    test_env.add_config("conventional-commits.enabled = true");
+    let repo_path = test_env.env_root().join("repo");
+
+    // Descriptions not in the conventional commit format are rejected
+    let output = test_env.run_jj_in(&repo_path, ["describe", "-m", "add flag"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Description is not in the conventional commit format: add flag
+    Hint: Use the format `<type>(<scope>): <subject>`, for example `fix(cli): handle empty input`.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["describe", "-m", "feature: add flag"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Unknown conventional commit type `feature`
+    Hint: Use one of the types: build, chore, ci, docs, feat, fix, perf, refactor, revert, style, test
+    [EOF]
+    [exit status: 1]
+    ");
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "feat(cli): add flag"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "fix(lib)!: crash"])
+        .success();
+
+    // Empty descriptions are accepted
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", ""])
+        .success();
+
+    // The editor lists the types and the scopes of the ancestors
+    let edit_script = test_env.set_up_fake_editor();
+    std::fs::write(
+        &edit_script,
+        ["dump editor", "write\nfix(cli): handle empty input"].join("\0"),
+    )
+    .unwrap();
+    let output = test_env.run_jj_in(&repo_path, ["commit"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy now at: yqosqzyt 027d7518 (empty) (no description set)
+    Parent commit      : mzvwutvl 536b3a74 (empty) fix(cli): handle empty input
+    [EOF]
+    ");
+    insta::assert_snapshot!(
+        std::fs::read_to_string(test_env.env_root().join("editor")).unwrap(), @r#"
+    JJ: Conventional commit format: <type>(<scope>)!: <subject>
+    JJ: Types: build, chore, ci, docs, feat, fix, perf, refactor, revert, style, test
+    JJ: Recent scopes: lib
+
+    JJ: Lines starting with "JJ:" (like this one) will be removed.
+    "#);
+
+    // A rejected description from the editor isn't lost
+    std::fs::write(&edit_script, "write\nadd flag").unwrap();
+    let output = test_env.run_jj_in(&repo_path, ["describe"]);
+    insta::with_settings!({
+        filters => [
+            (r"\b(editor-)[^.]*(\.jjdescription)\b", "$1<redacted>$2"),
+        ],
+    }, {
+        insta::assert_snapshot!(output, @r"
+        ------- stderr -------
+        Error: Description is not in the conventional commit format: add flag
+        Hint: Use the format `<type>(<scope>): <subject>`, for example `fix(cli): handle empty input`.
+        Hint: Edited description is left in $TEST_ENV/repo/.jj/repo/editor-<redacted>.jjdescription
+        [EOF]
+        [exit status: 1]
+        ");
+    });
+    let saved_path = output
+        .stderr
+        .raw()
+        .lines()
+        .find_map(|line| line.strip_prefix("Hint: Edited description is left in "))
+        .unwrap();
+    assert_eq!(std::fs::read_to_string(saved_path).unwrap(), "add flag\n");
+
+    // Custom types
+    test_env.add_config(r#"conventional-commits.types = ["change"]"#);
+    let output = test_env.run_jj_in(&repo_path, ["describe", "-m", "fix: crash"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Unknown conventional commit type `fix`
+    Hint: Use one of the types: change
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // The parsed description is available to templates and revsets
+    let template =
+        r#"separate(" ", conventional_type, conventional_scope, conventional_breaking) ++ "\n""#;
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["log", "--no-graph", "-T", template, "-r", "type(fix)"],
+    );
+    insta::assert_snapshot!(output, @r"
+    fix cli false
+    fix lib true
+    [EOF]
+    ");
+
+    // Types are matched case-insensitively
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["log", "--no-graph", "-T", template, "-r", "type(exact:FIX)"],
+    );
+    insta::assert_snapshot!(output, @r"
+    fix cli false
+    fix lib true
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_describe_author() {
     let mut test_env = TestEnvironment::default();
@@ -935,3 +1055,70 @@ fn get_log_output(test_env: &TestEnvironment, repo_path: &Path) -> CommandOutput
     let template = r#"commit_id.short() ++ " " ++ description"#;
     test_env.run_jj_in(repo_path, ["log", "-T", template])
 }
+
+#[test]
+fn test_describe_description_diff() {
+    let mut test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(repo_path.join("file1"), "foo\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "add file1"])
+        .success();
+
+    // The diff is shown below the ignore-rest line, and removed on save
+    test_env.add_config(r#"ui.description-diff = "git""#);
+    let edit_script = test_env.set_up_fake_editor();
+    std::fs::write(&edit_script, "dump editor").unwrap();
+    test_env.run_jj_in(&repo_path, ["describe"]).success();
+    insta::assert_snapshot!(
+        std::fs::read_to_string(test_env.env_root().join("editor")).unwrap(), @r#"
+        add file1
+
+        JJ: This commit contains the following changes:
+        JJ:     A file1
+        JJ: ignore-rest
+        diff --git a/file1 b/file1
+        new file mode 100644
+        index 0000000000..257cc5642c
+        --- /dev/null
+        +++ b/file1
+        @@ -0,0 +1,1 @@
+        +foo
+
+        JJ: Lines starting with "JJ:" (like this one) will be removed.
+        "#);
+    let output = test_env.run_jj_in(&repo_path, ["log", "-r@", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    @  add file1
+    │
+    ~
+    [EOF]
+    ");
+
+    test_env.add_config(r#"ui.description-diff = "stat""#);
+    test_env.run_jj_in(&repo_path, ["describe"]).success();
+    insta::assert_snapshot!(
+        std::fs::read_to_string(test_env.env_root().join("editor")).unwrap(), @r#"
+        add file1
+
+        JJ: This commit contains the following changes:
+        JJ:     A file1
+        JJ: ignore-rest
+        file1 | 1 +
+        1 file changed, 1 insertion(+), 0 deletions(-)
+
+        JJ: Lines starting with "JJ:" (like this one) will be removed.
+        "#);
+
+    test_env.add_config(r#"ui.description-diff = "patch""#);
+    let output = test_env.run_jj_in(&repo_path, ["describe"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Config error: Invalid type or value for ui.description-diff
+    Caused by: Invalid diff format: patch
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+}
@@ -534,6 +534,85 @@ fn test_diff_name_only() {
     sub/added
     [EOF]
     ");
+    let output = test_env
+        .run_jj_in(&repo_path, ["diff", "--name-only", "-z", "--color=always"])
+        .success();
+    insta::assert_debug_snapshot!(output.stdout.normalized(), @r#"
+    "added\0deleted\0modified\0sub/added\0"
+    "#);
+}
+
+// Windows paths would be formatted differently in file URLs
+#[cfg(unix)]
+#[test]
+fn test_diff_hyperlinks() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env.add_config("ui.hyperlinks = true");
+
+    std::fs::write(repo_path.join("file1"), "foo\n").unwrap();
+    std::fs::create_dir(repo_path.join("dir")).unwrap();
+    std::fs::write(repo_path.join("dir").join("file 2"), "bar\n").unwrap();
+
+    let output = test_env.run_jj_in(&repo_path, ["diff", "--color=always", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    [38;5;2mA ]8;;file://$TEST_ENV/repo/dir/file%202/dir/file 2]8;;\[39m
+    [38;5;2mA ]8;;file://$TEST_ENV/repo/file1/file1]8;;/[39m
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["diff", "--color=always", "--types"]);
+    insta::assert_snapshot!(output, @r"
+    [38;5;6m-F ]8;;file://$TEST_ENV/repo/dir/file%202/dir/file 2]8;;\[39m
+    [38;5;6m-F ]8;;file://$TEST_ENV/repo/file1/file1]8;;/[39m
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["diff", "--color=always", "--name-only"]);
+    insta::assert_snapshot!(output, @r"
+    ]8;;file://$TEST_ENV/repo/dir/file%202/dir/file 2]8;;\
+    ]8;;file://$TEST_ENV/repo/file1/file1]8;;/
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["diff", "--color=always", "--stat"]);
+    insta::assert_snapshot!(output, @r"
+    ]8;;file://$TEST_ENV/repo/dir/file%202/dir/file 2]8;;\ | 1 [38;5;2m+[38;5;1m[39m
+    ]8;;file://$TEST_ENV/repo/file1/file1]8;;/      | 1 [38;5;2m+[38;5;1m[39m
+    2 files changed, 2 insertions(+), 0 deletions(-)
+    [EOF]
+    ");
+
+    // Hyperlinks are emitted only if the output is colorized
+    let output = test_env.run_jj_in(&repo_path, ["diff", "--color=never", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    A dir/file 2
+    A file1
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "diff",
+            "--color=always",
+            "--summary",
+            "--config=ui.hyperlinks=false",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    [38;5;2mA dir/file 2[39m
+    [38;5;2mA file1[39m
+    [EOF]
+    ");
+
+    // Characters with special meaning in URLs are encoded
+    std::fs::write(repo_path.join("100%#1?"), "baz\n").unwrap();
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["diff", "--color=always", "--name-only", "glob:100*"],
+    );
+    insta::assert_snapshot!(output, @r"
+    ]8;;file://$TEST_ENV/repo/100%25%231%3F/100%#1?]8;;/
+    [EOF]
+    ");
 }
 
 #[test]
@@ -709,6 +788,82 @@ fn test_diff_relative_paths() {
     ");
 }
 
+#[test]
+fn test_diff_path_scope_cwd() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env.add_config(r#"ui.path-scope = "cwd""#);
+
+    std::fs::create_dir_all(repo_path.join("dir1").join("subdir1")).unwrap();
+    std::fs::create_dir(repo_path.join("dir2")).unwrap();
+    std::fs::write(repo_path.join("file1"), "foo1\n").unwrap();
+    std::fs::write(repo_path.join("dir1").join("file2"), "foo2\n").unwrap();
+    std::fs::write(
+        repo_path.join("dir1").join("subdir1").join("file3"),
+        "foo3\n",
+    )
+    .unwrap();
+    std::fs::write(repo_path.join("dir2").join("file4"), "foo4\n").unwrap();
+    test_env.run_jj_in(&repo_path, ["new"]).success();
+    std::fs::write(repo_path.join("file1"), "bar1\n").unwrap();
+    std::fs::write(repo_path.join("dir1").join("file2"), "bar2\n").unwrap();
+    std::fs::write(
+        repo_path.join("dir1").join("subdir1").join("file3"),
+        "bar3\n",
+    )
+    .unwrap();
+    std::fs::write(repo_path.join("dir2").join("file4"), "bar4\n").unwrap();
+
+    // Only the current directory is diffed by default
+    let output = test_env.run_jj_in(&repo_path.join("dir1"), ["diff", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    M file2
+    M subdir1/file3
+    [EOF]
+    ");
+
+    // Explicit paths override the scope
+    let output = test_env.run_jj_in(
+        &repo_path.join("dir1"),
+        ["diff", "--summary", "../file1", "../dir2"],
+    );
+    #[cfg(unix)]
+    insta::assert_snapshot!(output, @r"
+    M ../dir2/file4
+    M ../file1
+    [EOF]
+    ");
+    #[cfg(windows)]
+    insta::assert_snapshot!(output, @r"
+    M ..\dir2\file4
+    M ..\file1
+    [EOF]
+    ");
+
+    // The setting can be overridden back to the whole workspace
+    let output = test_env.run_jj_in(
+        &repo_path.join("dir1"),
+        ["diff", "--summary", "--config=ui.path-scope=workspace"],
+    );
+    #[cfg(unix)]
+    insta::assert_snapshot!(output, @r"
+    M file2
+    M subdir1/file3
+    M ../dir2/file4
+    M ../file1
+    [EOF]
+    ");
+    #[cfg(windows)]
+    insta::assert_snapshot!(output, @r"
+    M file2
+    M subdir1/file3
+    M ..\dir2\file4
+    M ..\file1
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_diff_hunks() {
     let test_env = TestEnvironment::default();
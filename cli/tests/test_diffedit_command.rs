@@ -413,27 +413,27 @@ fn test_diffedit_external_tool_conflict_marker_style() {
     ");
     // Conflicts should be materialized using "diff" format in working copy
     insta::assert_snapshot!(
-        std::fs::read_to_string(&file_path).unwrap(), @r"
+        std::fs::read_to_string(&file_path).unwrap(), @r#"
     line 1
     <<<<<<< Conflict 1 of 2
-    +++++++ Contents of side #1
+    +++++++ Contents of side #1 (rlvkpnrz "side-a")
     line 2.1
     line 2.2
-    %%%%%%% Changes from base to side #2
+    %%%%%%% Changes from base (qpvuntsm "base") to side #2 (zsuskuln "side-b")
     -line 2
     +line 2.3
     >>>>>>> Conflict 1 of 2 ends
     line 3
     <<<<<<< Conflict 2 of 2
-    %%%%%%% Changes from base to side #1
+    %%%%%%% Changes from base (qpvuntsm "base") to side #1 (rlvkpnrz "side-a")
     -line 4
     +line 4.1
-    +++++++ Contents of side #2
+    +++++++ Contents of side #2 (zsuskuln "side-b")
     line 4.2
     line 4.3
     >>>>>>> Conflict 2 of 2 ends
     line 5
-    ");
+    "#);
 
     // File should be conflicted with no changes
     let output = test_env.run_jj_in(&repo_path, ["st"]);
@@ -444,6 +444,7 @@ fn test_diffedit_external_tool_conflict_marker_style() {
     Parent commit: zsuskuln 8b3de837 side-b
     Warning: There are unresolved conflicts at these paths:
     file    2-sided conflict
+    Conflicts in file came from operation a3c95afc5f2d (2001-02-03 08:05:11) new empty commit, with sides from rlvkpnrz, zsuskuln
     [EOF]
     ");
 }
@@ -0,0 +1,250 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+use std::path::Path;
+
+use testutils::git;
+
+use crate::common::CommandOutput;
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_doctor_healthy_repo() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    let output = run_doctor(&test_env, &repo_path, &[]);
+    insta::assert_snapshot!(output, @r"
+    jj version: <version>
+    platform: <platform>
+    commit backend: git
+    operation store: simple_op_store
+    commit index: default
+    working copy: local
+    fsmonitor: none
+
+    ok locks: no lock files found
+    ok index: commit index is up to date
+    ok workspaces: 1 workspaces
+    ok git-head: not colocated with Git
+    ok watchman: not enabled
+    ok index-files: no index files of deleted operations
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_doctor_fix() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    // Lock taken long ago on another host, which might still be running
+    let op_heads_lock = repo_path.join(".jj/repo/op_heads/lock");
+    fs::write(&op_heads_lock, "pid 1\nhost elsewhere\nacquired 0\n").unwrap();
+    // Index file of an operation which doesn't exist
+    let operations_dir = repo_path.join(".jj/repo/index/operations");
+    fs::write(operations_dir.join("1".repeat(128)), "").unwrap();
+
+    let output = run_doctor(&test_env, &repo_path, &[]);
+    insta::assert_snapshot!(output, @r"
+    jj version: <version>
+    platform: <platform>
+    commit backend: git
+    operation store: simple_op_store
+    commit index: default
+    working copy: local
+    fsmonitor: none
+
+    problem locks: stale lock .jj/repo/op_heads/lock held by process 1 on elsewhere (the lock lease has expired)
+    ok index: commit index is up to date
+    ok workspaces: 1 workspaces
+    ok git-head: not colocated with Git
+    ok watchman: not enabled
+    problem index-files: 1 index files of deleted operations
+    [EOF]
+    ------- stderr -------
+    Error: Found 2 problems
+    Hint: Run `jj doctor --fix` to repair 1 of them.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = run_doctor(&test_env, &repo_path, &["--fix"]);
+    insta::assert_snapshot!(output, @r"
+    jj version: <version>
+    platform: <platform>
+    commit backend: git
+    operation store: simple_op_store
+    commit index: default
+    working copy: local
+    fsmonitor: none
+
+    problem locks: stale lock .jj/repo/op_heads/lock held by process 1 on elsewhere (the lock lease has expired)
+    ok index: commit index is up to date
+    ok workspaces: 1 workspaces
+    ok git-head: not colocated with Git
+    ok watchman: not enabled
+    fixed index-files: removed 1 index files of deleted operations
+    [EOF]
+    ------- stderr -------
+    Error: Found 1 problem
+    [EOF]
+    [exit status: 1]
+    ");
+    assert!(op_heads_lock.exists());
+    fs::remove_file(&op_heads_lock).unwrap();
+
+    // Corrupt the index files of all operations
+    for entry in fs::read_dir(&operations_dir).unwrap() {
+        fs::write(entry.unwrap().path(), "0").unwrap();
+    }
+    let output = run_doctor(&test_env, &repo_path, &[]);
+    insta::assert_snapshot!(output, @r"
+    jj version: <version>
+    platform: <platform>
+    commit backend: git
+    operation store: simple_op_store
+    commit index: default
+    working copy: local
+    fsmonitor: none
+
+    ok locks: no lock files found
+    problem index: commit index is corrupted (Failed to load commit index file '0')
+    warning repo: skipped the remaining checks since the commit index is corrupted
+    [EOF]
+    ------- stderr -------
+    Error: Found 1 problem
+    Hint: Run `jj doctor --fix` to repair 1 of them.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = run_doctor(&test_env, &repo_path, &["--fix"]);
+    insta::assert_snapshot!(output, @r"
+    jj version: <version>
+    platform: <platform>
+    commit backend: git
+    operation store: simple_op_store
+    commit index: default
+    working copy: local
+    fsmonitor: none
+
+    ok locks: no lock files found
+    fixed index: rebuilt corrupted commit index (Failed to load commit index file '0')
+    ok workspaces: 1 workspaces
+    ok git-head: not colocated with Git
+    ok watchman: not enabled
+    ok index-files: no index files of deleted operations
+    [EOF]
+    ");
+    let output = run_doctor(&test_env, &repo_path, &[]);
+    insta::assert_snapshot!(output, @r"
+    jj version: <version>
+    platform: <platform>
+    commit backend: git
+    operation store: simple_op_store
+    commit index: default
+    working copy: local
+    fsmonitor: none
+
+    ok locks: no lock files found
+    ok index: commit index is up to date
+    ok workspaces: 1 workspaces
+    ok git-head: not colocated with Git
+    ok watchman: not enabled
+    ok index-files: no index files of deleted operations
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_doctor_deleted_worktrees() {
+    let test_env = TestEnvironment::default();
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "repo"])
+        .success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "first"])
+        .success();
+    test_env
+        .run_jj_in(
+            &repo_path,
+            ["bookmark", "create", "-r@-", "mapped", "other"],
+        )
+        .success();
+    let mapped_path = test_env.env_root().join("mapped");
+    git::add_worktree(&repo_path, &mapped_path, "mapped");
+    test_env
+        .run_jj_in(&repo_path, ["git", "worktree", "map", "mapped"])
+        .success();
+    // Workspace which happens to have the name of another worktree
+    let other_path = test_env.env_root().join("other");
+    git::add_worktree(&repo_path, &other_path, "other");
+    test_env
+        .run_jj_in(
+            &repo_path,
+            ["workspace", "add", "--name", "other", "../elsewhere"],
+        )
+        .success();
+    fs::remove_dir_all(&mapped_path).unwrap();
+    fs::remove_dir_all(&other_path).unwrap();
+
+    let output = run_doctor(&test_env, &repo_path, &["--fix"]);
+    insta::assert_snapshot!(output, @r"
+    jj version: <version>
+    platform: <platform>
+    commit backend: git
+    operation store: simple_op_store
+    commit index: default
+    working copy: local
+    fsmonitor: none
+
+    ok locks: no lock files found
+    ok index: commit index is up to date
+    fixed workspaces: forgot workspace mapped belongs to a Git worktree which no longer exists
+    ok git-head: Git HEAD is in sync
+    ok watchman: not enabled
+    ok index-files: no index files of deleted operations
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["workspace", "list"]);
+    insta::assert_snapshot!(output, @r"
+    default: rlvkpnrz e8ea92a8 (empty) (no description set)
+    other: zxsnswpr 982e532e (empty) (no description set)
+    [EOF]
+    ");
+}
+
+#[must_use]
+fn run_doctor(test_env: &TestEnvironment, repo_path: &Path, args: &[&str]) -> CommandOutput {
+    test_env
+        .run_jj_with(|cmd| cmd.current_dir(repo_path).arg("doctor").args(args))
+        .normalize_stdout_with(|stdout| {
+            stdout
+                .lines()
+                .map(|line| {
+                    if line.starts_with("jj version: ") {
+                        "jj version: <version>"
+                    } else if line.starts_with("platform: ") {
+                        "platform: <platform>"
+                    } else {
+                        line
+                    }
+                })
+                .map(|line| format!("{line}\n"))
+                .collect()
+        })
+}
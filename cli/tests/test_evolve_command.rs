@@ -0,0 +1,263 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use crate::common::force_interactive;
+use crate::common::CommandOutput;
+use crate::common::TestEnvironment;
+
+/// Creates a change with two divergent commits, "one" and "two", which changed
+/// different lines of "file" in the parent commit "base".
+fn create_divergent_change(test_env: &TestEnvironment, repo_path: &Path) {
+    std::fs::write(repo_path.join("file"), "1\n2\n3\n").unwrap();
+    test_env
+        .run_jj_in(repo_path, ["commit", "-m=base"])
+        .success();
+    test_env
+        .run_jj_in(repo_path, ["describe", "-m=one"])
+        .success();
+    test_env
+        .run_jj_in(repo_path, ["describe", "-m=two", "--at-op=@-"])
+        .success();
+    test_env
+        .run_jj_in(repo_path, ["edit", "description(one)"])
+        .success();
+    std::fs::write(repo_path.join("file"), "1 one\n2\n3\n").unwrap();
+    test_env
+        .run_jj_in(repo_path, ["edit", "description(two)"])
+        .success();
+    std::fs::write(repo_path.join("file"), "1\n2\n3 two\n").unwrap();
+    test_env.run_jj_in(repo_path, ["status"]).success();
+}
+
+#[test]
+fn test_evolve_list() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    let output = test_env.run_jj_in(&repo_path, ["evolve", "--list"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No divergent changes.
+    [EOF]
+    ");
+
+    create_divergent_change(&test_env, &repo_path);
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r"
+    @  kkmpptxzrspx two divergent
+    │ ○  kkmpptxzrspx one divergent
+    ├─╯
+    ○  qpvuntsmwlqt base
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["evolve", "--list"]);
+    insta::assert_snapshot!(output, @r"
+    Change kkmpptxzrspx has 2 commits:
+      kkmpptxz?? 1ec20692 two
+      kkmpptxz?? ecaef976 one
+    [EOF]
+    ");
+
+    // Only changes of the given revisions are listed
+    let output = test_env.run_jj_in(&repo_path, ["evolve", "--list", "root()"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No divergent changes.
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_evolve_keep() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    create_divergent_change(&test_env, &repo_path);
+    test_env
+        .run_jj_in(
+            &repo_path,
+            ["bookmark", "create", "-r=description(one)", "b1"],
+        )
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "description(one)", "-m=child"])
+        .success();
+
+    // The child and the bookmark of the other commit move to the kept commit
+    let output = test_env.run_jj_in(&repo_path, ["evolve", "--keep=description(two)"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Kept rlvkpnrz f1798955 b1 | two
+    Rebased 1 descendant commits
+    Working copy now at: yostqsxw 21d6fa1f (empty) child
+    Parent commit      : rlvkpnrz f1798955 b1 | two
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r"
+    @  yostqsxwqrlt child
+    ○  rlvkpnrzqnoo two b1
+    ○  qpvuntsmwlqt base
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["evolve", "--keep=description(two)"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Change rlvkpnrzqnoo is not divergent
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_evolve_merge() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    create_divergent_change(&test_env, &repo_path);
+
+    let output = test_env.run_jj_in(&repo_path, ["evolve", "--merge=description(one)"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Merged 2 commits into rlvkpnrz f603475f two
+    Working copy now at: rlvkpnrz f603475f two
+    Parent commit      : qpvuntsm 7cd8e40a base
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r"
+    @  rlvkpnrzqnoo two
+    ○  qpvuntsmwlqt base
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["file", "show", "file"]);
+    insta::assert_snapshot!(output, @r"
+    1 one
+    2
+    3 two
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_evolve_renumber() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    create_divergent_change(&test_env, &repo_path);
+
+    let output = test_env.run_jj_in(&repo_path, ["evolve", "--renumber=description(one)"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Gave a new change ID to vruxwmqv a8174468 one
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r"
+    @  rlvkpnrzqnoo two divergent
+    │ ○  vruxwmqvtpmx one
+    ├─╯
+    │ ○  rlvkpnrzqnoo one divergent
+    ├─╯
+    ○  qpvuntsmwlqt base
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+
+    // Each change can only be resolved once
+    test_env.run_jj_in(&repo_path, ["undo"]).success();
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "evolve",
+            "--renumber=description(one)",
+            "--keep=description(two)",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Change rlvkpnrzqnoo was given more than one resolution
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_evolve_prompt() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    create_divergent_change(&test_env, &repo_path);
+
+    let output = test_env.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .current_dir(&repo_path)
+            .arg("evolve")
+            .write_stdin("s\n")
+    });
+    insta::assert_snapshot!(output, @r"
+    Change rlvkpnrzqnoo is divergent:
+    1: rlvkpnrz?? f1798955 two
+    2: rlvkpnrz?? a5fc4881 one
+    k1-k2: keep that commit and abandon the others
+    m: merge the contents of the commits
+    r1-r2: give that commit a new change ID
+    s: skip this change
+    [EOF]
+    ------- stderr -------
+    choose how to resolve the divergence: Nothing changed.
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .current_dir(&repo_path)
+            .arg("evolve")
+            .write_stdin("k2\n")
+    });
+    insta::assert_snapshot!(output, @r"
+    Change rlvkpnrzqnoo is divergent:
+    1: rlvkpnrz?? f1798955 two
+    2: rlvkpnrz?? a5fc4881 one
+    k1-k2: keep that commit and abandon the others
+    m: merge the contents of the commits
+    r1-r2: give that commit a new change ID
+    s: skip this change
+    [EOF]
+    ------- stderr -------
+    choose how to resolve the divergence: Kept rlvkpnrz a5fc4881 one
+    Working copy now at: rlvkpnrz a5fc4881 one
+    Parent commit      : qpvuntsm 7cd8e40a base
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r"
+    @  rlvkpnrzqnoo one
+    ○  qpvuntsmwlqt base
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+}
+
+#[must_use]
+fn get_log_output(test_env: &TestEnvironment, repo_path: &Path) -> CommandOutput {
+    let template = r#"separate(" ", change_id.short(), description.first_line(), bookmarks,
+        if(divergent, "divergent"))"#;
+    test_env.run_jj_in(repo_path, ["log", "-T", template])
+}
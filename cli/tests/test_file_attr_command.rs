@@ -0,0 +1,112 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_file_attr() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("script"), "#!/bin/sh\n").unwrap();
+    std::fs::write(repo_path.join("link"), "script").unwrap();
+    test_env.run_jj_in(&repo_path, ["new"]).success();
+
+    // Record the files as an executable file and a symlink
+    let output = test_env.run_jj_in(&repo_path, ["file", "attr", "set", "x", "script"]);
+    insta::assert_snapshot!(output, @"");
+    let output = test_env.run_jj_in(&repo_path, ["file", "attr", "set", "symlink", "link"]);
+    insta::assert_snapshot!(output, @"");
+    let output = test_env.run_jj_in(&repo_path, ["file", "attr", "list"]);
+    insta::assert_snapshot!(output, @r"
+    l link
+    x script
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["debug", "tree"]);
+    insta::assert_snapshot!(output, @r#"
+    link: Ok(Resolved(Some(Symlink(SymlinkId("2b804bee2703da5c8a12fa49a758b7cb427f30f5")))))
+    script: Ok(Resolved(Some(File { id: FileId("1a2485251c33a70432394c93fb89330ef214bfc9"), executable: true })))
+    [EOF]
+    "#);
+
+    // The overrides still apply after the files are modified
+    std::fs::write(repo_path.join("script"), "#!/bin/sh\necho\n").unwrap();
+    std::fs::write(repo_path.join("link"), "other").unwrap();
+    let output = test_env.run_jj_in(&repo_path, ["debug", "tree"]);
+    insta::assert_snapshot!(output, @r#"
+    link: Ok(Resolved(Some(Symlink(SymlinkId("27fa34919ae70aa0d7eaccdfbf393cfc440e7d25")))))
+    script: Ok(Resolved(Some(File { id: FileId("82a76d395279c1b91a944ba15fc843894d7a0a3b"), executable: true })))
+    [EOF]
+    "#);
+
+    // The list can be filtered
+    let output = test_env.run_jj_in(&repo_path, ["file", "attr", "list", "link"]);
+    insta::assert_snapshot!(output, @r"
+    l link
+    [EOF]
+    ");
+
+    // Paths which aren't tracked can't be overridden
+    let output = test_env.run_jj_in(&repo_path, ["file", "attr", "set", "x", "nonexistent"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: No matching entries for paths: nonexistent
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["file", "attr", "list"]);
+    insta::assert_snapshot!(output, @r"
+    l link
+    x script
+    [EOF]
+    ");
+
+    // Remove the overrides
+    let output = test_env.run_jj_in(&repo_path, ["file", "attr", "unset", "script", "link"]);
+    insta::assert_snapshot!(output, @"");
+    let output = test_env.run_jj_in(&repo_path, ["file", "attr", "list"]);
+    insta::assert_snapshot!(output, @"");
+    let output = test_env.run_jj_in(&repo_path, ["debug", "tree"]);
+    insta::assert_snapshot!(output, @r#"
+    link: Ok(Resolved(Some(File { id: FileId("27fa34919ae70aa0d7eaccdfbf393cfc440e7d25"), executable: false })))
+    script: Ok(Resolved(Some(File { id: FileId("82a76d395279c1b91a944ba15fc843894d7a0a3b"), executable: false })))
+    [EOF]
+    "#);
+}
+
+#[test]
+fn test_file_attr_rebase_descendants() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("script"), "#!/bin/sh\n").unwrap();
+    test_env.run_jj_in(&repo_path, ["new"]).success();
+    std::fs::write(repo_path.join("file"), "contents\n").unwrap();
+    test_env.run_jj_in(&repo_path, ["edit", "@-"]).success();
+
+    let output = test_env.run_jj_in(&repo_path, ["file", "attr", "set", "x", "script"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 1 descendant commits
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["debug", "tree", "-r", "@+"]);
+    insta::assert_snapshot!(output, @r#"
+    file: Ok(Resolved(Some(File { id: FileId("12f00e90b6ef79117ce6e650416b8cf517099b78"), executable: false })))
+    script: Ok(Resolved(Some(File { id: FileId("1a2485251c33a70432394c93fb89330ef214bfc9"), executable: true })))
+    [EOF]
+    "#);
+}
@@ -62,7 +62,7 @@ fn test_track_untrack() {
     ------- stderr -------
     Error: 'file1' is not ignored.
     Hint: Files that are not ignored will be added back by the next command.
-    Make sure they're ignored, then try again.
+    Make sure they're ignored, then try again, or use `--keep` to keep them untracked.
     [EOF]
     [exit status: 1]
     ");
@@ -89,7 +89,7 @@ fn test_track_untrack() {
     ------- stderr -------
     Error: 'target/file2' and 1 other files are not ignored.
     Hint: Files that are not ignored will be added back by the next command.
-    Make sure they're ignored, then try again.
+    Make sure they're ignored, then try again, or use `--keep` to keep them untracked.
     [EOF]
     [exit status: 1]
     ");
@@ -102,6 +102,99 @@ fn test_track_untrack() {
     assert!(!files_after.stdout.raw().contains("target"));
 }
 
+#[test]
+fn test_untrack_keep() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file1"), "initial").unwrap();
+    std::fs::write(repo_path.join("file2"), "initial").unwrap();
+    test_env.run_jj_in(&repo_path, ["new"]).success();
+
+    // Untrack a path which isn't ignored
+    let output = test_env.run_jj_in(&repo_path, ["file", "untrack", "--keep", "file1"]);
+    insta::assert_snapshot!(output, @"");
+    assert!(repo_path.join("file1").exists());
+    let output = test_env.run_jj_in(&repo_path, ["file", "list"]);
+    insta::assert_snapshot!(output, @r"
+    file2
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["file", "list", "--untracked"]);
+    insta::assert_snapshot!(output, @r"
+    file1
+    [EOF]
+    ");
+    let output = test_env
+        .run_jj_in(&repo_path, ["file", "list", "--untracked", "-z"])
+        .success();
+    insta::assert_debug_snapshot!(output.stdout.normalized(), @r#"
+    "file1\0"
+    "#);
+
+    // The path stays untracked even if it's modified
+    std::fs::write(repo_path.join("file1"), "modified").unwrap();
+    let output = test_env.run_jj_in(&repo_path, ["status"]);
+    insta::assert_snapshot!(output, @r"
+    Working copy changes:
+    D file1
+    Untracked paths:
+    ? file1
+    Working copy : rlvkpnrz 4580b2e8 (no description set)
+    Parent commit: qpvuntsm d1adadce (no description set)
+    [EOF]
+    ");
+
+    // The list can be filtered
+    let output = test_env.run_jj_in(&repo_path, ["file", "list", "--untracked", "file2"]);
+    insta::assert_snapshot!(output, @"");
+
+    // Can't be combined with --revision
+    let output = test_env.run_jj_in(&repo_path, ["file", "list", "--untracked", "-r@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--untracked' cannot be used with '--revision <REVSET>'
+
+    Usage: jj file list --untracked [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+
+    // The path can be tracked again
+    let output = test_env.run_jj_in(&repo_path, ["file", "track", "file1"]);
+    insta::assert_snapshot!(output, @"");
+    let output = test_env.run_jj_in(&repo_path, ["file", "list"]);
+    insta::assert_snapshot!(output, @r"
+    file1
+    file2
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["file", "list", "--untracked"]);
+    insta::assert_snapshot!(output, @"");
+
+    // Checking out a commit containing an untracked path tracks it again
+    test_env
+        .run_jj_in(&repo_path, ["file", "untrack", "--keep", "file1"])
+        .success();
+    let output = test_env.run_jj_in(&repo_path, ["new", "@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy now at: lylxulpl 8bf6a510 (empty) (no description set)
+    Parent commit      : qpvuntsm d1adadce (no description set)
+    Added 1 files, modified 0 files, removed 0 files
+    Warning: 1 of those updates were skipped because there were conflicting changes in the working copy.
+    Hint: Inspect the changes compared to the intended target with `jj diff --from 8bf6a510bd0a`.
+    Discard the conflicting changes with `jj restore --from 8bf6a510bd0a`.
+    Warning: 1 untracked paths are tracked again because the new working-copy commit contains them.
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["file", "list", "--untracked"]);
+    insta::assert_snapshot!(output, @"");
+}
+
 #[test]
 fn test_track_untrack_sparse() {
     let test_env = TestEnvironment::default();
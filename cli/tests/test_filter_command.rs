@@ -0,0 +1,277 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::common::CommandOutput;
+use crate::common::TestEnvironment;
+
+fn init_repo(test_env: &TestEnvironment) -> PathBuf {
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::create_dir(repo_path.join("src")).unwrap();
+    std::fs::write(repo_path.join("src").join("main"), "password=hunter2\n").unwrap();
+    std::fs::write(repo_path.join("secret"), "hunter2\n").unwrap();
+    std::fs::write(repo_path.join("big"), "x".repeat(2000)).unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "add files with hunter2"])
+        .success();
+    std::fs::write(repo_path.join("src").join("lib"), "lib\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "add lib"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "create", "-r@-", "main"])
+        .success();
+    repo_path
+}
+
+fn get_log_output(test_env: &TestEnvironment, repo_path: &Path) -> CommandOutput {
+    let template = r#"change_id.short() ++ " " ++ description.first_line() ++ " " ++ bookmarks"#;
+    test_env.run_jj_in(repo_path, ["log", "-T", template])
+}
+
+fn get_files(test_env: &TestEnvironment, repo_path: &Path, rev: &str) -> CommandOutput {
+    test_env.run_jj_in(repo_path, ["file", "list", "-r", rev])
+}
+
+#[test]
+fn test_filter_paths() {
+    let test_env = TestEnvironment::default();
+    let repo_path = init_repo(&test_env);
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r"
+    @  kkmpptxzrspx
+    ○  rlvkpnrzqnoo add lib main
+    ○  qpvuntsmwlqt add files with hunter2
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["filter", "--remove", "secret"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rewrote 3 commits
+    Working copy now at: kkmpptxz 701cb75c (empty) (no description set)
+    Parent commit      : rlvkpnrz 92d6ba0f main | add lib
+    Added 0 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+    // The change ids and bookmarks are preserved
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r"
+    @  kkmpptxzrspx
+    ○  rlvkpnrzqnoo add lib main
+    ○  qpvuntsmwlqt add files with hunter2
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_files(&test_env, &repo_path, "main-"), @r"
+    big
+    src/main
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["filter", "--keep", "src", "--rename", "src:lib/sub"],
+    );
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rewrote 3 commits
+    Working copy now at: kkmpptxz 7c1f9975 (empty) (no description set)
+    Parent commit      : rlvkpnrz 14028c8f main | add lib
+    Added 2 files, modified 0 files, removed 3 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_files(&test_env, &repo_path, "main"), @r"
+    lib/sub/lib
+    lib/sub/main
+    [EOF]
+    ");
+
+    // Nothing to change
+    let output = test_env.run_jj_in(&repo_path, ["filter", "--remove", "secret"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_filter_descendants() {
+    let test_env = TestEnvironment::default();
+    let repo_path = init_repo(&test_env);
+
+    // Descendants outside of the revset are rebased
+    let output = test_env.run_jj_in(&repo_path, ["filter", "-r", "main-", "--remove", "big"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rewrote 1 commits
+    Rebased 2 descendant commits
+    Working copy now at: kkmpptxz 465cac57 (empty) (no description set)
+    Parent commit      : rlvkpnrz 379b7609 main | add lib
+    Added 0 files, modified 0 files, removed 1 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_files(&test_env, &repo_path, "main"), @r"
+    secret
+    src/lib
+    src/main
+    [EOF]
+    ");
+
+    // Immutable commits can't be rewritten
+    let output = test_env.run_jj_in(&repo_path, ["filter", "-r", "root()", "--remove", "big"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: The root commit 000000000000 is immutable
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_filter_contents() {
+    let test_env = TestEnvironment::default();
+    let repo_path = init_repo(&test_env);
+    std::fs::write(
+        test_env.env_root().join("expressions"),
+        "# comment\nhunter2\nregex:password=(\\w+)==>password=<$1>\n",
+    )
+    .unwrap();
+    std::fs::write(test_env.env_root().join("messages"), "hunter2==>secret\n").unwrap();
+
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "filter",
+            "--strip-files-bigger-than=1k",
+            "--replace-text=../expressions",
+            "--replace-message=../messages",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rewrote 3 commits
+    Working copy now at: kkmpptxz 8669f977 (empty) (no description set)
+    Parent commit      : rlvkpnrz adef0e2c main | add lib
+    Added 0 files, modified 2 files, removed 1 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r"
+    @  kkmpptxzrspx
+    ○  rlvkpnrzqnoo add lib main
+    ○  qpvuntsmwlqt add files with secret
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_files(&test_env, &repo_path, "main"), @r"
+    secret
+    src/lib
+    src/main
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["file", "show", "-r", "main", "src/main", "secret"],
+    );
+    insta::assert_snapshot!(output, @r"
+    ***REMOVED***
+    password=***REMOVED***
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["filter", "--strip-files-bigger-than=1x"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    error: invalid value '1x' for '--strip-files-bigger-than <SIZE>': invalid size "1x"
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    "#);
+    let output = test_env.run_jj_in(&repo_path, ["filter", "--rename", "src"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Error: Invalid rename "src", expected OLD:NEW
+    [EOF]
+    [exit status: 1]
+    "#);
+
+    // Renames must not overwrite other files
+    let output = test_env.run_jj_in(&repo_path, ["filter", "--rename", "src/main:secret"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Cannot write both secret and src/main to secret
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "filter",
+            "--rename",
+            "src/main:new",
+            "--rename",
+            "secret:new",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Cannot write both secret and src/main to new
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_filter_mailmap() {
+    let test_env = TestEnvironment::default();
+    let repo_path = init_repo(&test_env);
+    std::fs::write(
+        test_env.env_root().join("mailmap"),
+        "# comment\nNew User <new.user@example.com> <test.user@example.com>\n",
+    )
+    .unwrap();
+
+    let output = test_env.run_jj_in(&repo_path, ["filter", "-r=..main", "--mailmap=../mailmap"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rewrote 2 commits
+    Rebased 1 descendant commits
+    Working copy now at: kkmpptxz efe5391e (empty) (no description set)
+    Parent commit      : rlvkpnrz 4dbb9857 main | add lib
+    [EOF]
+    ");
+    let template = r#"
+    separate(" ", change_id.short(), author, committer, committer.timestamp())
+    "#;
+    let output = test_env.run_jj_in(&repo_path, ["log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    @  kkmpptxzrspx Test User <test.user@example.com> Test User <test.user@example.com> 2001-02-03 04:05:11.000 +07:00
+    ○  rlvkpnrzqnoo New User <new.user@example.com> New User <new.user@example.com> 2001-02-03 04:05:09.000 +07:00
+    ○  qpvuntsmwlqt New User <new.user@example.com> New User <new.user@example.com> 2001-02-03 04:05:08.000 +07:00
+    ◆  zzzzzzzzzzzz 1970-01-01 00:00:00.000 +00:00
+    [EOF]
+    ");
+
+    // Already mapped identities are left unchanged.
+    let output = test_env.run_jj_in(&repo_path, ["filter", "-r=..main", "--mailmap=../mailmap"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+}
@@ -0,0 +1,123 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_fsck_healthy_repo() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    fs::create_dir(repo_path.join("dir")).unwrap();
+    fs::write(repo_path.join("dir").join("file"), "foo\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "add file"])
+        .success();
+
+    let output = test_env.run_jj_in(&repo_path, ["fsck"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Checked 4 operations, 5 commits, 2 trees, and 1 file.
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["fsck", "--json"]);
+    insta::assert_snapshot!(output, @r#"
+    {"commits":5,"files":1,"operations":4,"problems":[],"trees":2}
+    [EOF]
+    "#);
+
+    let output = test_env.run_jj_in(&repo_path, ["fsck", "--repair"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebuilt commit index.
+    Checked 4 operations, 5 commits, 2 trees, and 1 file.
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_fsck_missing_object() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    fs::write(repo_path.join("file"), "foo\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "add file"])
+        .success();
+
+    // Blob id of "foo\n"
+    let object_path = repo_path
+        .join(".jj/repo/store/git/objects/25")
+        .join("7cc5642cb1a054f08cc83f2d943e56fd3ebe99");
+    fs::remove_file(object_path).unwrap();
+
+    let output = test_env.run_jj_in(&repo_path, ["fsck"]);
+    insta::assert_snapshot!(output, @r"
+    file 257cc5642cb1a054f08cc83f2d943e56fd3ebe99: Object 257cc5642cb1a054f08cc83f2d943e56fd3ebe99 of type file not found
+    [EOF]
+    ------- stderr -------
+    Checked 4 operations, 5 commits, 1 tree, and 1 file.
+    Error: Found 1 integrity problem
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["fsck", "--json"]);
+    insta::assert_snapshot!(output, @r#"
+    {"commits":5,"files":1,"operations":4,"problems":[{"id":"257cc5642cb1a054f08cc83f2d943e56fd3ebe99","kind":"file","message":"Object 257cc5642cb1a054f08cc83f2d943e56fd3ebe99 of type file not found"}],"trees":1}
+    [EOF]
+    ------- stderr -------
+    Error: Found 1 integrity problem
+    [EOF]
+    [exit status: 1]
+    "#);
+}
+
+#[test]
+fn test_fsck_corrupt_index() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    fs::write(repo_path.join("file"), "foo\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "add file"])
+        .success();
+
+    let segments_dir = repo_path.join(".jj/repo/index/segments");
+    for entry in fs::read_dir(&segments_dir).unwrap() {
+        fs::write(entry.unwrap().path(), "garbage").unwrap();
+    }
+
+    let output = test_env.run_jj_in(&repo_path, ["fsck"]);
+    insta::assert_snapshot!(output, @r"
+    index 1966556e5eaa5ac7db0485787654374458642103a894d611321969852c819e5db6589b9fba9d1a7fe0a02ef93259d80a2812630e38a21c2555f8ed44766fab01: Unexpected index version
+    [EOF]
+    ------- stderr -------
+    Checked 4 operations, 5 commits, 1 tree, and 1 file.
+    Error: Found 1 integrity problem
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["fsck", "--repair"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebuilt commit index.
+    Checked 4 operations, 5 commits, 1 tree, and 1 file.
+    [EOF]
+    ");
+}
@@ -598,6 +598,76 @@ fn test_git_clone_remote_default_bookmark(subprocess: bool) {
     }
 }
 
+#[test_case(false; "use git2 for remote calls")]
+#[test_case(true; "spawn a git subprocess for remote calls")]
+fn test_git_clone_with_bookmark(subprocess: bool) {
+    let test_env = TestEnvironment::default();
+    if !subprocess {
+        test_env.add_config("git.subprocess = false");
+    }
+    let git_repo_path = test_env.env_root().join("source");
+    let git_repo = git::init(git_repo_path.clone());
+    set_up_non_empty_git_repo(&git_repo);
+    let head_id = git_repo.head_id().unwrap().detach();
+    git::add_commit(
+        &git_repo,
+        "refs/heads/feature1",
+        "file",
+        b"feature",
+        "feature",
+        &[head_id],
+    );
+
+    // The given bookmark is checked out, but trunk() is still set to the
+    // remote's default bookmark
+    let output = test_env.run_jj_in(
+        ".",
+        [
+            "git",
+            "clone",
+            "--colocate",
+            "source",
+            "clone",
+            "-b",
+            "feature1",
+        ],
+    );
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Fetching into new repo in "$TEST_ENV/clone"
+    bookmark: feature1@origin [new] untracked
+    bookmark: main@origin     [new] untracked
+    Setting the revset alias `trunk()` to `main@origin`
+    Working copy now at: sqpuoqvx 2337f2c1 (empty) (no description set)
+    Parent commit      : pxykmzwk d94c8cca feature1 | feature
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    "#);
+    }
+    let clone_git_repo = git::open(test_env.env_root().join("clone"));
+    let remote_head = clone_git_repo
+        .find_reference("refs/remotes/origin/HEAD")
+        .unwrap();
+    assert_eq!(
+        remote_head.target().try_name().unwrap().as_bstr(),
+        "refs/remotes/origin/main"
+    );
+
+    let output = test_env.run_jj_in(".", ["git", "clone", "source", "clone2", "-b", "unknown"]);
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Fetching into new repo in "$TEST_ENV/clone2"
+    bookmark: feature1@origin [new] untracked
+    bookmark: main@origin     [new] untracked
+    Setting the revset alias `trunk()` to `main@origin`
+    Warning: No bookmark unknown@origin to check out in the cloned repo
+    [EOF]
+    "#);
+    }
+}
+
 // A branch with a strange name should get quoted in the config. Windows doesn't
 // like the strange name, so we don't run the test there.
 #[cfg(unix)]
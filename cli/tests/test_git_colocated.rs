@@ -1311,6 +1311,143 @@ fn test_git_colocated_update_index_3_sided_conflict() {
     ");
 }
 
+#[test]
+fn test_git_colocate_command() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let workspace_root = test_env.env_root().join("repo");
+    std::fs::write(workspace_root.join("file"), "contents").unwrap();
+    test_env
+        .run_jj_in(&workspace_root, ["commit", "-m", "initial"])
+        .success();
+    test_env
+        .run_jj_in(&workspace_root, ["bookmark", "create", "-r@-", "main"])
+        .success();
+    std::fs::write(workspace_root.join("file"), "modified").unwrap();
+
+    let output = test_env.run_jj_in(&workspace_root, ["git", "colocate"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Moved the Git repo to ".git". The workspace is now colocated with Git.
+    [EOF]
+    "#);
+    insta::assert_snapshot!(
+        std::fs::read_to_string(workspace_root.join(".jj/repo/store/git_target")).unwrap(),
+        @"../../../.git"
+    );
+    // Git HEAD and refs are set up, and nothing is imported from Git
+    insta::assert_snapshot!(get_log_output(&test_env, &workspace_root), @r"
+    @  4be96b2133cee35413e7c8f9c3fb54ca968f943c
+    ○  751b12b7b9817f1688295ed393079df5a3305550 main git_head() initial
+    ◆  0000000000000000000000000000000000000000
+    [EOF]
+    ");
+    let git_repo = git::open(&workspace_root);
+    assert!(!git_repo.is_bare());
+    insta::assert_snapshot!(
+        git_repo.head_id().unwrap().to_string(),
+        @"751b12b7b9817f1688295ed393079df5a3305550"
+    );
+    insta::assert_snapshot!(get_index_state(&workspace_root), @r"
+    Unconflicted Mode(FILE) 0839b2e9412b ctime=0:0 mtime=0:0 size=0 file
+    ");
+    let output = test_env.run_jj_in(&workspace_root, ["op", "log", "-n2", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    @  colocate git repo
+    ○  snapshot working copy
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&workspace_root, ["git", "colocate"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: The workspace is already colocated with Git
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = test_env.run_jj_in(&workspace_root, ["git", "colocate", "--disable"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Moved the Git repo into the jj repo. The workspace is no longer colocated with Git.
+    [EOF]
+    ");
+    assert!(!workspace_root.join(".git").exists());
+    assert!(!workspace_root.join(".jj/.gitignore").exists());
+    assert!(git::open(workspace_root.join(".jj/repo/store/git")).is_bare());
+    insta::assert_snapshot!(get_log_output(&test_env, &workspace_root), @r"
+    @  4be96b2133cee35413e7c8f9c3fb54ca968f943c
+    ○  751b12b7b9817f1688295ed393079df5a3305550 main initial
+    ◆  0000000000000000000000000000000000000000
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&workspace_root, ["git", "colocate", "--disable"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: The workspace isn't colocated with Git
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_git_colocate_command_errors() {
+    let test_env = TestEnvironment::default();
+
+    // Git repo outside of the jj repo
+    let workspace_root = test_env.env_root().join("external");
+    git::init_bare(test_env.env_root().join("git-repo"));
+    test_env
+        .run_jj_in(".", ["git", "init", "external", "--git-repo", "git-repo"])
+        .success();
+    let output = test_env.run_jj_in(&workspace_root, ["git", "colocate"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: The Git repo isn't managed by jj
+    Hint: The repo is backed by the Git repo at $TEST_ENV/git-repo.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Existing .git in the workspace
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let workspace_root = test_env.env_root().join("repo");
+    std::fs::create_dir(workspace_root.join(".git")).unwrap();
+    let output = test_env.run_jj_in(&workspace_root, ["git", "colocate"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Cannot colocate the Git repo because .git already exists in the workspace
+    [EOF]
+    [exit status: 1]
+    ");
+    std::fs::remove_dir(workspace_root.join(".git")).unwrap();
+
+    // Secondary workspace
+    test_env
+        .run_jj_in(&workspace_root, ["workspace", "add", "../secondary"])
+        .success();
+    let output = test_env.run_jj_in(&test_env.env_root().join("secondary"), ["git", "colocate"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: This workspace doesn't contain the repo
+    Hint: Run this command in the workspace created by `jj git init` or `jj git clone`.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Linked worktree
+    std::fs::create_dir_all(workspace_root.join(".jj/repo/store/git/worktrees/foo")).unwrap();
+    let output = test_env.run_jj_in(&workspace_root, ["git", "colocate"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: The Git repo has linked worktrees: foo
+    Hint: Remove them by `git worktree remove` first.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[must_use]
 fn get_log_output_divergence(test_env: &TestEnvironment, repo_path: &Path) -> CommandOutput {
     let template = r#"
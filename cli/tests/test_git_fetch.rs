@@ -2198,3 +2198,220 @@ fn test_git_fetch_preserve_commits_across_repos(subprocess: bool) {
     ");
     }
 }
+
+#[test_case(false; "use git2 for remote calls")]
+#[test_case(true; "spawn a git subprocess for remote calls")]
+fn test_git_fetch_tags(subprocess: bool) {
+    let test_env = TestEnvironment::default();
+    if !subprocess {
+        test_env.add_config("git.subprocess = false");
+    }
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    let git_repo = add_git_remote(&test_env, &repo_path, "origin");
+    let set_tag = |name: &str, id: gix::ObjectId| {
+        git_repo
+            .reference(
+                format!("refs/tags/{name}"),
+                id,
+                gix::refs::transaction::PreviousValue::Any,
+                "set tag",
+            )
+            .unwrap();
+    };
+    let get_tag_output = || test_env.run_jj_in(&repo_path, ["tag", "list"]);
+    let main_id = git_repo
+        .find_reference("refs/heads/origin")
+        .unwrap()
+        .peel_to_id_in_place()
+        .unwrap()
+        .detach();
+    let other_id = add_commit_to_branch(&git_repo, "other");
+    set_tag("v1", main_id);
+    set_tag("unrelated", other_id);
+
+    // No tags are fetched
+    let output = test_env.run_jj_in(&repo_path, ["git", "fetch", "-b=origin", "--tags=none"]);
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    bookmark: origin@origin [new] untracked
+    [EOF]
+    ");
+    }
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(get_tag_output(), @"");
+    }
+
+    // Only tags pointing to fetched commits are fetched by default
+    let output = test_env.run_jj_in(&repo_path, ["git", "fetch", "-b=origin"]);
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    tag: v1 [new]
+    [EOF]
+    ");
+    }
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(get_tag_output(), @r"
+    v1: qmyrypzk ab8b299e message
+    [EOF]
+    ");
+    }
+
+    // Moved tags aren't updated by default
+    set_tag("v1", other_id);
+    let output = test_env.run_jj_in(&repo_path, ["git", "fetch"]);
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    bookmark: other@origin [new] untracked
+    tag:      unrelated    [new]
+    [EOF]
+    ");
+    }
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(get_tag_output(), @r"
+    unrelated: qtzmqslk 8820d88a message
+    v1: qmyrypzk ab8b299e message
+    [EOF]
+    ");
+    }
+
+    // All tags are fetched and updated
+    test_env.add_config("git.fetch-tags = 'none'");
+    let output = test_env.run_jj_in(&repo_path, ["git", "fetch", "--tags"]);
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    tag: v1 [updated]
+    [EOF]
+    ");
+    }
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(get_tag_output(), @r"
+    unrelated: qtzmqslk 8820d88a message
+    v1: qtzmqslk 8820d88a message
+    [EOF]
+    ");
+    }
+}
+
+#[test]
+fn test_git_fetch_remote_staleness() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    add_git_remote(&test_env, &repo_path, "origin");
+
+    // Nothing has been fetched yet
+    let template = r#""staleness: " ++ remote_staleness() ++ "\n""#;
+    let output = test_env.run_jj_in(&repo_path, ["log", "-r@", "--no-graph", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    staleness: 
+    [EOF]
+    ");
+
+    test_env.run_jj_in(&repo_path, ["git", "fetch"]).success();
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "after fetch"])
+        .success();
+    let output = test_env.run_jj_in(&repo_path, ["log", "-r@", "--no-graph", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    staleness: 2 seconds
+    [EOF]
+    ");
+
+    // The notice is only shown if remotes are fetched in the background. Pretend
+    // that a background fetch has just been started so that no fetch is spawned.
+    let output = test_env.run_jj_in(&repo_path, ["status"]);
+    insta::assert_snapshot!(output, @r"
+    The working copy has no changes.
+    Working copy : qpvuntsm 7efae4e2 (empty) after fetch
+    Parent commit: zzzzzzzz 00000000 (empty) (no description set)
+    [EOF]
+    ");
+    test_env.add_config(r#"fetch.auto = "background""#);
+    std::fs::write(repo_path.join(".jj").join("repo").join("auto_fetch"), "").unwrap();
+    let output = test_env.run_jj_in(&repo_path, ["status"]);
+    insta::assert_snapshot!(output, @r"
+    The working copy has no changes.
+    Working copy : qpvuntsm 7efae4e2 (empty) after fetch
+    Parent commit: zzzzzzzz 00000000 (empty) (no description set)
+    Remote data is 4 seconds old
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["log", "-r@"]);
+    insta::assert_snapshot!(output, @r"
+    @  qpvuntsm test.user@example.com 2001-02-03 08:05:11 7efae4e2
+    │  (empty) after fetch
+    ~
+    [EOF]
+    ------- stderr -------
+    Remote data is 5 seconds old
+    [EOF]
+    ");
+
+    // The notice can be customized
+    test_env.add_config(
+        r#"templates.remote_staleness_notice = '"fetched " ++ remote_staleness() ++ " ago\n"'"#,
+    );
+    let output = test_env.run_jj_in(&repo_path, ["log", "-r@"]);
+    insta::assert_snapshot!(output, @r"
+    @  qpvuntsm test.user@example.com 2001-02-03 08:05:11 7efae4e2
+    │  (empty) after fetch
+    ~
+    [EOF]
+    ------- stderr -------
+    fetched 6 seconds ago
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_git_fetch_auto_background() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    add_git_remote(&test_env, &repo_path, "origin");
+    test_env.add_config(r#"fetch.auto = "background""#);
+    let stamp_path = repo_path.join(".jj").join("repo").join("auto_fetch");
+
+    // Only the commands which show the staleness notice fetch
+    let output = test_env.run_jj_in(&repo_path, ["bookmark", "list", "--all-remotes"]);
+    insta::assert_snapshot!(output, @"");
+    assert!(!stamp_path.exists());
+
+    // The fetch is started once the command is done, so the command itself
+    // doesn't see the fetched bookmarks
+    let output = test_env.run_jj_in(&repo_path, ["log", "-r@", "-Tdescription"]);
+    insta::assert_snapshot!(output, @r"
+    @
+    │
+    ~
+    [EOF]
+    ");
+    assert!(stamp_path.exists());
+
+    let mut output = get_bookmark_output(&test_env, &repo_path);
+    for _ in 0..300 {
+        if !output.stdout.raw().is_empty() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        output = get_bookmark_output(&test_env, &repo_path);
+    }
+    insta::assert_snapshot!(output, @r"
+    origin@origin: qmyrypzk ab8b299e message
+    [EOF]
+    ");
+    // The time of the fetch is recorded once the fetch is done
+    let last_fetch_path = repo_path.join(".jj").join("repo").join("last_fetch");
+    for _ in 0..300 {
+        if last_fetch_path.exists() {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    assert!(last_fetch_path.exists());
+}
@@ -0,0 +1,112 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_git_notes_add_show() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "first"])
+        .success();
+
+    let output = test_env.run_jj_in(&repo_path, ["git", "notes", "show"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No note found for commit fa15625b4a986997697639dfc2844138900c79f2
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["git", "notes", "add", "-m", "ci: passed"]);
+    insta::assert_snapshot!(output, @"");
+    let output = test_env.run_jj_in(&repo_path, ["git", "notes", "show"]);
+    insta::assert_snapshot!(output, @r"
+    ci: passed
+    [EOF]
+    ");
+
+    // Adding a second note requires --force
+    let output = test_env.run_jj_in(&repo_path, ["git", "notes", "add", "-m", "ci: failed"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Commit fa15625b4a986997697639dfc2844138900c79f2 already has a note in refs/notes/commits
+    Hint: Use --force to replace the existing note.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["git", "notes", "add", "--force", "-m", "ci: failed"],
+    );
+    insta::assert_snapshot!(output, @"");
+
+    // Notes in another ref are independent
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "git",
+            "notes",
+            "add",
+            "--ref",
+            "refs/notes/review",
+            "-m",
+            "LGTM",
+        ],
+    );
+    insta::assert_snapshot!(output, @"");
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["git", "notes", "add", "--ref", "bad", "-m", "x"],
+    );
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Invalid notes ref name: bad
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let template = r#"
+    separate(" ",
+      description.first_line(),
+      self.git_note().first_line(),
+      self.git_note("refs/notes/review").first_line(),
+    ) ++ "\n"
+    "#;
+    let output = test_env.run_jj_in(&repo_path, ["log", "--no-graph", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    first ci: failed LGTM
+
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_git_notes_add_editor() {
+    let mut test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    let edit_script = test_env.set_up_fake_editor();
+
+    std::fs::write(&edit_script, "write\nnote from editor").unwrap();
+    let output = test_env.run_jj_in(&repo_path, ["git", "notes", "add"]);
+    insta::assert_snapshot!(output, @"");
+    let output = test_env.run_jj_in(&repo_path, ["git", "notes", "show"]);
+    insta::assert_snapshot!(output, @r"
+    note from editor
+    [EOF]
+    ");
+}
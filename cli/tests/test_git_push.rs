@@ -18,6 +18,7 @@ use std::path::PathBuf;
 use test_case::test_case;
 use testutils::git;
 
+use crate::common::force_interactive;
 use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
 
@@ -91,6 +92,93 @@ fn test_git_push_nothing(subprocess: bool) {
     }
 }
 
+#[test_case(false; "use git2 for remote calls")]
+#[test_case(true; "spawn a git subprocess for remote calls")]
+fn test_git_push_notes(subprocess: bool) {
+    let (test_env, workspace_root) = set_up();
+    if !subprocess {
+        test_env.add_config("git.subprocess = false");
+    }
+    let origin_path = test_env.env_root().join("origin");
+
+    // Nothing to push if there are no notes
+    let output = test_env.run_jj_in(&workspace_root, ["git", "push", "--notes"]);
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: No bookmarks found in the default push revset: remote_bookmarks(remote=origin)..@
+    Nothing changed.
+    [EOF]
+    ");
+    }
+
+    test_env
+        .run_jj_in(
+            &workspace_root,
+            ["git", "notes", "add", "-r", "bookmark1", "-m", "ci: passed"],
+        )
+        .success();
+    // Notes aren't pushed by default
+    let output = test_env.run_jj_in(&workspace_root, ["git", "push"]);
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: No bookmarks found in the default push revset: remote_bookmarks(remote=origin)..@
+    Nothing changed.
+    [EOF]
+    ");
+    }
+    let output = test_env.run_jj_in(&workspace_root, ["git", "push", "--notes"]);
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: No bookmarks found in the default push revset: remote_bookmarks(remote=origin)..@
+    Pushed notes refs/notes/commits to origin
+    [EOF]
+    ");
+    }
+    let output = test_env.run_jj_in(&origin_path, ["git", "notes", "show", "-r", "bookmark1"]);
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ci: passed
+    [EOF]
+    ");
+    }
+
+    // Notes added on the remote aren't overwritten
+    test_env
+        .run_jj_in(
+            &origin_path,
+            ["git", "notes", "add", "-r", "bookmark2", "-m", "ci: failed"],
+        )
+        .success();
+    test_env
+        .run_jj_in(
+            &workspace_root,
+            ["git", "notes", "add", "-r", "bookmark2", "-m", "ci: passed"],
+        )
+        .success();
+    test_env.add_config("git.push-notes = true");
+    let output = test_env.run_jj_in(&workspace_root, ["git", "push"]);
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: No bookmarks found in the default push revset: remote_bookmarks(remote=origin)..@
+    Error: Failed to push notes refs/notes/commits to origin, which has notes that aren't in the local ref
+    Hint: Fetch the remote notes and combine them with `git notes merge` first.
+    [EOF]
+    [exit status: 1]
+    ");
+    }
+    let output = test_env.run_jj_in(&origin_path, ["git", "notes", "show", "-r", "bookmark2"]);
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ci: failed
+    [EOF]
+    ");
+    }
+}
+
 #[test_case(false; "use git2 for remote calls")]
 #[test_case(true; "spawn a git subprocess for remote calls")]
 fn test_git_push_current_bookmark(subprocess: bool) {
@@ -354,6 +442,233 @@ fn test_git_push_other_remote_has_bookmark(subprocess: bool) {
     }
 }
 
+#[test_case(false; "use git2 for remote calls")]
+#[test_case(true; "spawn a git subprocess for remote calls")]
+fn test_git_push_bookmark_push_remotes(subprocess: bool) {
+    let (test_env, workspace_root) = set_up();
+    if !subprocess {
+        test_env.add_config("git.subprocess = false");
+    }
+    test_env.add_config(r#"git.bookmark-push-remotes.upstream = "glob:release/*""#);
+    let upstream_path = test_env
+        .env_root()
+        .join("origin")
+        .join(".jj")
+        .join("repo")
+        .join("store")
+        .join("git");
+    test_env
+        .run_jj_in(
+            &workspace_root,
+            [
+                "git",
+                "remote",
+                "add",
+                "upstream",
+                upstream_path.to_str().unwrap(),
+            ],
+        )
+        .success();
+    test_env
+        .run_jj_in(&workspace_root, ["describe", "-m=release"])
+        .success();
+    test_env
+        .run_jj_in(&workspace_root, ["bookmark", "create", "-r@", "release/1"])
+        .success();
+
+    // The configured remote is shown by `jj bookmark list`
+    let output = test_env.run_jj_in(&workspace_root, ["bookmark", "list"]);
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    bookmark1: xtvrqkyv d13ecdbd (empty) description 1
+    bookmark2: rlzusymt 8476341e (empty) description 2
+    release/1 (pushed to upstream): yqosqzyt 7b446418 (empty) release
+    [EOF]
+    ");
+    }
+
+    // The configured remote is used if --remote isn't specified
+    let output = test_env.run_jj_in(
+        &workspace_root,
+        [
+            "git",
+            "push",
+            "--allow-new",
+            "--bookmark=release/1",
+            "--dry-run",
+        ],
+    );
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to upstream:
+      Add bookmark release/1 to 7b446418af8c
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+    }
+
+    // Bookmarks pushed to different remotes can't be pushed at once
+    let output = test_env.run_jj_in(
+        &workspace_root,
+        [
+            "git",
+            "push",
+            "--allow-new",
+            "--bookmark=release/1",
+            "--bookmark=bookmark1",
+            "--dry-run",
+        ],
+    );
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: The selected bookmarks are configured to be pushed to different remotes
+    Hint: Bookmark release/1 is pushed to upstream
+    Hint: Bookmark bookmark1 is pushed to the default remote
+    Hint: Use --remote to select the remote, or push the bookmarks separately.
+    [EOF]
+    [exit status: 1]
+    ");
+    }
+
+    // --remote takes precedence
+    let output = test_env.run_jj_in(
+        &workspace_root,
+        [
+            "git",
+            "push",
+            "--allow-new",
+            "--bookmark=release/1",
+            "--remote=origin",
+            "--dry-run",
+        ],
+    );
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Add bookmark release/1 to 7b446418af8c
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+    }
+}
+
+#[test_case(false; "use git2 for remote calls")]
+#[test_case(true; "spawn a git subprocess for remote calls")]
+fn test_git_push_multiple_remotes(subprocess: bool) {
+    let (test_env, workspace_root) = set_up();
+    if !subprocess {
+        test_env.add_config("git.subprocess = false");
+    }
+    let backup_path = test_env.env_root().join("backup");
+    git::init_bare(&backup_path);
+    test_env
+        .run_jj_in(
+            &workspace_root,
+            [
+                "git",
+                "remote",
+                "add",
+                "backup",
+                backup_path.to_str().unwrap(),
+            ],
+        )
+        .success();
+    test_env
+        .run_jj_in(
+            &workspace_root,
+            ["git", "remote", "add", "missing", "nonexistent"],
+        )
+        .success();
+    test_env
+        .run_jj_in(&workspace_root, ["describe", "-m=foo"])
+        .success();
+    test_env
+        .run_jj_in(
+            &workspace_root,
+            ["bookmark", "create", "-r@", "my-bookmark"],
+        )
+        .success();
+
+    // The same bookmarks are pushed to each remote
+    let output = test_env.run_jj_in(
+        &workspace_root,
+        [
+            "git",
+            "push",
+            "--allow-new",
+            "--bookmark=my-bookmark",
+            "--remote=origin",
+            "--remote=backup",
+        ],
+    );
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Add bookmark my-bookmark to b0f827bb35de
+    Changes to push to backup:
+      Add bookmark my-bookmark to b0f827bb35de
+    [EOF]
+    ");
+    }
+    let output = test_env.run_jj_in(&workspace_root, ["bookmark", "list", "--all-remotes"]);
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    bookmark1: xtvrqkyv d13ecdbd (empty) description 1
+      @origin: xtvrqkyv d13ecdbd (empty) description 1
+    bookmark2: rlzusymt 8476341e (empty) description 2
+      @origin: rlzusymt 8476341e (empty) description 2
+    my-bookmark: yqosqzyt b0f827bb (empty) foo
+      @backup: yqosqzyt b0f827bb (empty) foo
+      @origin: yqosqzyt b0f827bb (empty) foo
+    [EOF]
+    ");
+    }
+
+    // A failure to push to one remote doesn't prevent pushing to the others,
+    // and remote groups can be configured by `git.push`
+    test_env
+        .run_jj_in(&workspace_root, ["describe", "-m=bar"])
+        .success();
+    let output = test_env.run_jj_in(
+        &workspace_root,
+        [
+            "git",
+            "push",
+            "--bookmark=my-bookmark",
+            "--config=git.push=['missing', 'backup']",
+        ],
+    );
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: Failed to push to missing: Refusing to create new remote bookmark my-bookmark@missing
+    Changes to push to backup:
+      Move sideways bookmark my-bookmark from b0f827bb35de to ede783e32923
+    Error: Failed to push to 1 of 2 remotes: missing
+    Hint: Use --allow-new to push new bookmark. Use --remote to specify the remote to push to.
+    [EOF]
+    [exit status: 1]
+    ");
+    }
+    let output = test_env.run_jj_in(&workspace_root, ["bookmark", "list", "--all-remotes"]);
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    bookmark1: xtvrqkyv d13ecdbd (empty) description 1
+      @origin: xtvrqkyv d13ecdbd (empty) description 1
+    bookmark2: rlzusymt 8476341e (empty) description 2
+      @origin: rlzusymt 8476341e (empty) description 2
+    my-bookmark: yqosqzyt ede783e3 (empty) bar
+      @backup: yqosqzyt ede783e3 (empty) bar
+      @origin (ahead by 1 commits, behind by 1 commits): yqosqzyt hidden b0f827bb (empty) foo
+    [EOF]
+    ");
+    }
+}
+
 #[test_case(false; "use git2 for remote calls")]
 #[test_case(true; "spawn a git subprocess for remote calls")]
 fn test_git_push_forward_unexpectedly_moved(subprocess: bool) {
@@ -510,7 +825,10 @@ fn test_git_push_deletion_unexpectedly_moved(subprocess: bool) {
     ");
     }
 
-    let output = test_env.run_jj_in(&workspace_root, ["git", "push", "--bookmark", "bookmark1"]);
+    let output = test_env.run_jj_in(
+        &workspace_root,
+        ["git", "push", "--bookmark", "bookmark1", "--allow-delete"],
+    );
     insta::allow_duplicates! {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
@@ -601,7 +919,10 @@ fn test_git_push_unexpectedly_deleted(subprocess: bool) {
     if subprocess {
         // git does not allow to push a deleted bookmark if we expect it to exist even
         // though it was already deleted
-        let output = test_env.run_jj_in(&workspace_root, ["git", "push", "-bbookmark1"]);
+        let output = test_env.run_jj_in(
+            &workspace_root,
+            ["git", "push", "-bbookmark1", "--allow-delete"],
+        );
         insta::assert_snapshot!(output, @r"
         ------- stderr -------
         Changes to push to origin:
@@ -614,11 +935,17 @@ fn test_git_push_unexpectedly_deleted(subprocess: bool) {
     } else {
         // Pushing a *deleted* bookmark succeeds if deleted on remote, even if we expect
         // bookmark1@origin to exist and point somewhere.
-        let output = test_env.run_jj_in(&workspace_root, ["git", "push", "-bbookmark1"]);
+        let output = test_env.run_jj_in(
+            &workspace_root,
+            ["git", "push", "-bbookmark1", "--allow-delete"],
+        );
         insta::assert_snapshot!(output, @r"
         ------- stderr -------
         Changes to push to origin:
           Delete bookmark bookmark1 from d13ecdbda2a2
+        Hint: To restore the deleted remote bookmarks, recreate and push them:
+          jj bookmark create -r d13ecdbda2a2 bookmark1
+          jj git push --remote origin --allow-new --bookmark bookmark1
         [EOF]
         ");
     }
@@ -891,6 +1218,9 @@ fn test_git_push_multiple(subprocess: bool) {
       Delete bookmark bookmark1 from d13ecdbda2a2
       Move sideways bookmark bookmark2 from 8476341eb395 to c4a3c3105d92
       Add bookmark my-bookmark to c4a3c3105d92
+    Hint: To restore the deleted remote bookmarks, recreate and push them:
+      jj bookmark create -r d13ecdbda2a2 bookmark1
+      jj git push --remote origin --allow-new --bookmark bookmark1
     [EOF]
     ");
     }
@@ -1066,13 +1396,9 @@ fn test_git_push_changes(subprocess: bool) {
     }
 }
 
-#[test_case(false; "use git2 for remote calls")]
-#[test_case(true; "spawn a git subprocess for remote calls")]
-fn test_git_push_revisions(subprocess: bool) {
+#[test]
+fn test_git_push_topic() {
     let (test_env, workspace_root) = set_up();
-    if !subprocess {
-        test_env.add_config("git.subprocess = false");
-    }
     test_env
         .run_jj_in(&workspace_root, ["describe", "-m", "foo"])
         .success();
@@ -1080,8 +1406,224 @@ fn test_git_push_revisions(subprocess: bool) {
     test_env
         .run_jj_in(&workspace_root, ["new", "-m", "bar"])
         .success();
-    test_env
-        .run_jj_in(&workspace_root, ["bookmark", "create", "-r@", "bookmark-1"])
+    std::fs::write(workspace_root.join("file"), "modified").unwrap();
+
+    let output = test_env.run_jj_in(&workspace_root, ["git", "push", "--topic", "feature"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No changes in topic feature
+    [EOF]
+    [exit status: 1]
+    ");
+
+    test_env
+        .run_jj_in(&workspace_root, ["topic", "set", "feature", "-r", "@-|@"])
+        .success();
+    let output = test_env.run_jj_in(&workspace_root, ["git", "push", "--topic", "feature"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Creating bookmark push-feature for topic feature
+    Changes to push to origin:
+      Add bookmark push-feature to cf1a53a8800a
+    [EOF]
+    ");
+
+    // The bookmark follows the head of the topic
+    test_env
+        .run_jj_in(&workspace_root, ["new", "-m", "baz"])
+        .success();
+    test_env
+        .run_jj_in(&workspace_root, ["topic", "set", "feature"])
+        .success();
+    let output = test_env.run_jj_in(&workspace_root, ["git", "push", "--topic", "feature"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Move forward bookmark push-feature from cf1a53a8800a to 868618a01c4f
+    [EOF]
+    ");
+
+    test_env
+        .run_jj_in(&workspace_root, ["new", "-r", "@-", "-m", "qux"])
+        .success();
+    test_env
+        .run_jj_in(&workspace_root, ["topic", "set", "feature"])
+        .success();
+    let output = test_env.run_jj_in(&workspace_root, ["git", "push", "--topic", "feature"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Topic feature has 2 heads
+    Hint: Rebase the changes in the topic onto each other, or push the heads separately with --change.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Bookmarks which weren't created for the topic aren't moved
+    test_env
+        .run_jj_in(&workspace_root, ["topic", "set", "other", "-r", "@"])
+        .success();
+    test_env
+        .run_jj_in(
+            &workspace_root,
+            ["bookmark", "create", "push-other", "-r", "root()"],
+        )
+        .success();
+    let output = test_env.run_jj_in(&workspace_root, ["git", "push", "--topic", "other"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Bookmark push-other already exists and doesn't point to a change in topic other
+    Hint: Rename the topic, or push it with --bookmark instead.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // The bookmark name must be a valid ref name
+    test_env
+        .run_jj_in(&workspace_root, ["topic", "set", "a..b", "-r", "@"])
+        .success();
+    let output = test_env.run_jj_in(&workspace_root, ["git", "push", "--topic", "a..b"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Invalid bookmark name push-a..b for topic a..b
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test_case(false; "use git2 for remote calls")]
+#[test_case(true; "spawn a git subprocess for remote calls")]
+fn test_git_push_changes_with_bookmark_template(subprocess: bool) {
+    let (test_env, workspace_root) = set_up();
+    if !subprocess {
+        test_env.add_config("git.subprocess = false");
+    }
+    test_env.add_config(
+        r#"templates.git_push_bookmark = '"test-user/" ++ change_id.short(4) ++ "-" ++ description.first_line().slug()'"#,
+    );
+    test_env
+        .run_jj_in(&workspace_root, ["describe", "-m=Fix the Bug!"])
+        .success();
+    test_env
+        .run_jj_in(&workspace_root, ["new", "-m=Add feature"])
+        .success();
+
+    let output = test_env.run_jj_in(&workspace_root, ["git", "push", "--change=@-"]);
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Creating bookmark test-user/yqos-fix-the-bug for revision yqosqzytrlsw
+    Changes to push to origin:
+      Add bookmark test-user/yqos-fix-the-bug to b7704105663c
+    [EOF]
+    ");
+    }
+
+    // Generated names must be unique
+    let output = test_env.run_jj_in(
+        &workspace_root,
+        [
+            "git",
+            "push",
+            "--change=@",
+            "--change=@-",
+            "--config=templates.git_push_bookmark='\"same\"'",
+        ],
+    );
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Generated bookmark name same for revision yqosqzytrlsw is also used for revision yostqsxwqrlt
+    [EOF]
+    [exit status: 1]
+    ");
+    }
+
+    // Generated names must not collide with untracked remote bookmarks
+    let origin_path = test_env.env_root().join("origin");
+    test_env
+        .run_jj_in(&origin_path, ["bookmark", "create", "-r@", "taken"])
+        .success();
+    test_env
+        .run_jj_in(&origin_path, ["git", "export"])
+        .success();
+    test_env
+        .run_jj_in(&workspace_root, ["git", "fetch"])
+        .success();
+    let output = test_env.run_jj_in(
+        &workspace_root,
+        [
+            "git",
+            "push",
+            "--change=@",
+            "--config=templates.git_push_bookmark='\"taken\"'",
+        ],
+    );
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Generated bookmark name taken for revision yostqsxwqrlt already exists on remote origin
+    Hint: Create a bookmark with a different name, and push it with --bookmark instead.
+    [EOF]
+    [exit status: 1]
+    ");
+    }
+
+    // Existing bookmarks of other changes aren't moved
+    let output = test_env.run_jj_in(
+        &workspace_root,
+        [
+            "git",
+            "push",
+            "--change=@",
+            "--config=templates.git_push_bookmark='\"bookmark1\"'",
+        ],
+    );
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Generated bookmark name bookmark1 for revision yostqsxwqrlt is already used by another change
+    Hint: Create a bookmark with a different name, and push it with --bookmark instead.
+    [EOF]
+    [exit status: 1]
+    ");
+    }
+
+    // Generated names must be valid
+    let output = test_env.run_jj_in(
+        &workspace_root,
+        [
+            "git",
+            "push",
+            "--change=@",
+            "--config=templates.git_push_bookmark='\"a..b\"'",
+        ],
+    );
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Invalid generated bookmark name a..b for revision yostqsxwqrlt
+    [EOF]
+    [exit status: 1]
+    ");
+    }
+}
+
+#[test_case(false; "use git2 for remote calls")]
+#[test_case(true; "spawn a git subprocess for remote calls")]
+fn test_git_push_revisions(subprocess: bool) {
+    let (test_env, workspace_root) = set_up();
+    if !subprocess {
+        test_env.add_config("git.subprocess = false");
+    }
+    test_env
+        .run_jj_in(&workspace_root, ["describe", "-m", "foo"])
+        .success();
+    std::fs::write(workspace_root.join("file"), "contents").unwrap();
+    test_env
+        .run_jj_in(&workspace_root, ["new", "-m", "bar"])
+        .success();
+    test_env
+        .run_jj_in(&workspace_root, ["bookmark", "create", "-r@", "bookmark-1"])
         .success();
     std::fs::write(workspace_root.join("file"), "modified").unwrap();
     test_env
@@ -1401,6 +1943,116 @@ fn test_git_push_no_description(subprocess: bool) {
         .success();
 }
 
+#[test_case(false; "use git2 for remote calls")]
+#[test_case(true; "spawn a git subprocess for remote calls")]
+fn test_git_push_checks(subprocess: bool) {
+    let (test_env, workspace_root) = set_up();
+    if !subprocess {
+        test_env.add_config("git.subprocess = false");
+    }
+    test_env.add_config(
+        r#"
+        [git.push-checks]
+        description = { pattern = '^[a-z]+: ' }
+        forbidden-paths = { patterns = ["glob:'**/*.pem'"] }
+        max-file-size = { size = "1KiB", severity = "warning" }
+        foreign-author = {}
+        merge-commits = { severity = "warning" }
+        "#,
+    );
+    test_env
+        .run_jj_in(
+            &workspace_root,
+            ["new", "bookmark1", "bookmark2", "-m=merge: combine"],
+        )
+        .success();
+    std::fs::write(workspace_root.join("key.pem"), "secret").unwrap();
+    std::fs::write(workspace_root.join("big"), "x".repeat(2048)).unwrap();
+    test_env
+        .run_jj_in(
+            &workspace_root,
+            ["new", "-m=wip", "--config=user.email=someone@example.com"],
+        )
+        .success();
+    test_env
+        .run_jj_in(
+            &workspace_root,
+            ["bookmark", "create", "-r@", "my-bookmark"],
+        )
+        .success();
+
+    // All failures are reported at once, and nothing is pushed
+    let output = test_env.run_jj_in(
+        &workspace_root,
+        ["git", "push", "--allow-new", "--bookmark", "my-bookmark"],
+    );
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: Push check `max-file-size` failed for commit be5ca370dfe9 since file big is larger than 1.0KiB
+    Warning: Push check `merge-commits` failed for commit be5ca370dfe9 since it is a merge commit
+    Error: Won't push since 3 of the configured git.push-checks failed
+    Hint: Check `description` failed for yostqsxw 5a6c4233 my-bookmark | (empty) wip: its description doesn't match `^[a-z]+: `
+    Hint: Check `foreign-author` failed for yostqsxw 5a6c4233 my-bookmark | (empty) wip: it is authored by someone@example.com
+    Hint: Check `forbidden-paths` failed for vruxwmqv be5ca370 merge: combine: it modifies forbidden path key.pem
+    [EOF]
+    [exit status: 1]
+    ");
+    }
+
+    // Checks with "warning" severity don't prevent the push
+    let output = test_env.run_jj_in(
+        &workspace_root,
+        [
+            "git",
+            "push",
+            "--allow-new",
+            "--bookmark",
+            "my-bookmark",
+            "--dry-run",
+            "--config=git.push-checks.description.severity='warning'",
+            "--config=git.push-checks.forbidden-paths.severity='warning'",
+            "--config=git.push-checks.foreign-author.severity='warning'",
+        ],
+    );
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: Push check `description` failed for commit 5a6c42330a62 since its description doesn't match `^[a-z]+: `
+    Warning: Push check `foreign-author` failed for commit 5a6c42330a62 since it is authored by someone@example.com
+    Warning: Push check `forbidden-paths` failed for commit be5ca370dfe9 since it modifies forbidden path key.pem
+    Warning: Push check `max-file-size` failed for commit be5ca370dfe9 since file big is larger than 1.0KiB
+    Warning: Push check `merge-commits` failed for commit be5ca370dfe9 since it is a merge commit
+    Changes to push to origin:
+      Add bookmark my-bookmark to 5a6c42330a62
+    Dry-run requested, not pushing.
+    [EOF]
+    ");
+    }
+
+    // Unknown checks are rejected
+    let output = test_env.run_jj_in(
+        &workspace_root,
+        [
+            "git",
+            "push",
+            "--allow-new",
+            "--bookmark",
+            "my-bookmark",
+            "--config=git.push-checks.unknown={}",
+        ],
+    );
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Config error: Unknown push check `git.push-checks.unknown`
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+    }
+}
+
 #[test_case(false; "use git2 for remote calls")]
 #[test_case(true; "spawn a git subprocess for remote calls")]
 fn test_git_push_no_description_in_immutable(subprocess: bool) {
@@ -1743,6 +2395,9 @@ fn test_git_push_deleted(subprocess: bool) {
     ------- stderr -------
     Changes to push to origin:
       Delete bookmark bookmark1 from d13ecdbda2a2
+    Hint: To restore the deleted remote bookmarks, recreate and push them:
+      jj bookmark create -r d13ecdbda2a2 bookmark1
+      jj git push --remote origin --allow-new --bookmark bookmark1
     [EOF]
     ");
     }
@@ -1769,6 +2424,97 @@ fn test_git_push_deleted(subprocess: bool) {
     }
 }
 
+#[test_case(false; "use git2 for remote calls")]
+#[test_case(true; "spawn a git subprocess for remote calls")]
+fn test_git_push_deleted_requires_confirmation(subprocess: bool) {
+    let (test_env, workspace_root) = set_up();
+    if !subprocess {
+        test_env.add_config("git.subprocess = false");
+    }
+
+    test_env
+        .run_jj_in(&workspace_root, ["bookmark", "delete", "bookmark1"])
+        .success();
+
+    // Deleting a remote bookmark without --allow-delete is refused if we can't
+    // prompt
+    let output = test_env.run_jj_in(&workspace_root, ["git", "push", "-bbookmark1"]);
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Delete bookmark bookmark1 from d13ecdbda2a2
+    Error: Refusing to delete bookmark bookmark1 on remote origin without --allow-delete
+    Hint: The remote bookmarks point to:
+      bookmark1@origin: xtvrqkyv d13ecdbd bookmark1@origin | (empty) description 1
+    Hint: Use --allow-delete to delete the remote bookmarks.
+    [EOF]
+    [exit status: 1]
+    ");
+    }
+
+    // Declining the prompt aborts the push
+    let output = test_env.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .current_dir(&workspace_root)
+            .args(["git", "push", "-bbookmark1"])
+            .write_stdin("n\n")
+    });
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Delete bookmark bookmark1 from d13ecdbda2a2
+    The following remote bookmarks will be deleted:
+      bookmark1@origin: xtvrqkyv d13ecdbd bookmark1@origin | (empty) description 1
+    Delete bookmark bookmark1 on remote origin? (yn): Error: Push aborted
+    [EOF]
+    [exit status: 1]
+    ");
+    }
+
+    // Accepting the prompt deletes the bookmark
+    let output = test_env.run_jj_with(|cmd| {
+        force_interactive(cmd)
+            .current_dir(&workspace_root)
+            .args(["git", "push", "-bbookmark1"])
+            .write_stdin("y\n")
+    });
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Delete bookmark bookmark1 from d13ecdbda2a2
+    The following remote bookmarks will be deleted:
+      bookmark1@origin: xtvrqkyv d13ecdbd bookmark1@origin | (empty) description 1
+    Delete bookmark bookmark1 on remote origin? (yn): Hint: To restore the deleted remote bookmarks, recreate and push them:
+      jj bookmark create -r d13ecdbda2a2 bookmark1
+      jj git push --remote origin --allow-new --bookmark bookmark1
+    [EOF]
+    ");
+    }
+
+    // The restore hint can be followed to push the bookmark again
+    test_env
+        .run_jj_in(
+            &workspace_root,
+            ["bookmark", "create", "-rdescription(1)", "bookmark1"],
+        )
+        .success();
+    let output = test_env.run_jj_in(
+        &workspace_root,
+        ["git", "push", "--allow-new", "-bbookmark1"],
+    );
+    insta::allow_duplicates! {
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Changes to push to origin:
+      Add bookmark bookmark1 to d13ecdbda2a2
+    [EOF]
+    ");
+    }
+}
+
 #[test_case(false; "use git2 for remote calls")]
 #[test_case(true; "spawn a git subprocess for remote calls")]
 fn test_git_push_conflicting_bookmarks(subprocess: bool) {
@@ -167,6 +167,143 @@ fn test_git_remote_set_url() {
     ");
 }
 
+#[test]
+fn test_git_remote_set_head() {
+    let test_env = TestEnvironment::default();
+    let git_repo = git::init(test_env.env_root().join("source"));
+    let commit_id =
+        git::add_commit(&git_repo, "refs/heads/main", "file", b"", "main", &[]).commit_id;
+    git::add_commit(
+        &git_repo,
+        "refs/heads/feature",
+        "file",
+        b"1",
+        "feature",
+        &[commit_id],
+    );
+    git::set_symbolic_reference(&git_repo, "HEAD", "refs/heads/main");
+
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env
+        .run_jj_in(&repo_path, ["git", "remote", "add", "origin", "../source"])
+        .success();
+    test_env.run_jj_in(&repo_path, ["git", "fetch"]).success();
+    let get_remote_head = || {
+        let git_repo = git::open(repo_path.join(".jj/repo/store/git"));
+        git_repo
+            .find_reference("refs/remotes/origin/HEAD")
+            .ok()
+            .map(|r| r.target().try_name().unwrap().as_bstr().to_string())
+    };
+
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["git", "remote", "set-head", "origin", "feature"],
+    );
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Setting the revset alias `trunk()` to `feature@origin`
+    [EOF]
+    ");
+    assert_eq!(
+        get_remote_head().as_deref(),
+        Some("refs/remotes/origin/feature")
+    );
+    let output = test_env.run_jj_in(&repo_path, ["log", "-r", "trunk()", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    ◆  feature
+    │
+    ~
+    [EOF]
+    ");
+
+    // The remote bookmark has to exist
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["git", "remote", "set-head", "origin", "unknown"],
+    );
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No such remote bookmark: unknown@origin
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["git", "remote", "set-head", "unknown", "main"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No git remote named 'unknown'
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Query the default bookmark of the remote
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["git", "remote", "set-head", "origin", "--auto"],
+    );
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Setting the revset alias `trunk()` to `main@origin`
+    [EOF]
+    ");
+    assert_eq!(
+        get_remote_head().as_deref(),
+        Some("refs/remotes/origin/main")
+    );
+
+    // trunk() isn't updated if it's set to something else
+    test_env
+        .run_jj_in(
+            &repo_path,
+            [
+                "config",
+                "set",
+                "--repo",
+                "revset-aliases.'trunk()'",
+                "root()",
+            ],
+        )
+        .success();
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["git", "remote", "set-head", "origin", "feature"],
+    );
+    insta::assert_snapshot!(output, @"");
+
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["git", "remote", "set-head", "origin", "--delete"],
+    );
+    insta::assert_snapshot!(output, @"");
+    assert_eq!(get_remote_head(), None);
+
+    // trunk() isn't updated if the user configured it
+    test_env
+        .run_jj_in(
+            &repo_path,
+            ["config", "unset", "--repo", "revset-aliases.'trunk()'"],
+        )
+        .success();
+    test_env.add_config("revset-aliases.'trunk()' = 'main@origin'");
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["git", "remote", "set-head", "origin", "feature"],
+    );
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: Not setting the revset alias `trunk()` to `feature@origin` since it is configured in the user config
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["log", "-r", "trunk()", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    ◆  main
+    │
+    ~
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_git_remote_relative_path() {
     let test_env = TestEnvironment::default();
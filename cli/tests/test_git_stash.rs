@@ -0,0 +1,160 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use testutils::git;
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_git_stash_list_and_apply() {
+    let test_env = TestEnvironment::default();
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "repo"])
+        .success();
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(repo_path.join("file"), "1\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "first"])
+        .success();
+
+    let output = test_env.run_jj_in(&repo_path, ["git", "stash", "list"]);
+    insta::assert_snapshot!(output, @"");
+
+    std::fs::write(repo_path.join("file"), "2\n").unwrap();
+    git::stash_push(&repo_path, "modified", false);
+    std::fs::write(repo_path.join("untracked"), "untracked\n").unwrap();
+    git::stash_push(&repo_path, "added", true);
+    let output = test_env.run_jj_in(&repo_path, ["git", "stash", "list"]);
+    insta::assert_snapshot!(output, @r"
+    stash@{0}: 3b96e669fb20 On (no branch): added
+    stash@{1}: e44a971bd56d On (no branch): modified
+    [EOF]
+    ");
+
+    // The working copy was reset by `git stash`
+    let output = test_env.run_jj_in(&repo_path, ["diff", "--git"]);
+    insta::assert_snapshot!(output, @"");
+
+    let output = test_env.run_jj_in(&repo_path, ["git", "stash", "apply", "1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy now at: rlvkpnrz ea873b41 (no description set)
+    Parent commit      : qpvuntsm dca1429b first
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["git", "stash", "apply"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy now at: rlvkpnrz f3240f2f (no description set)
+    Parent commit      : qpvuntsm dca1429b first
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["diff", "--git"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/file b/file
+    index d00491fd7e..0cfbf08886 100644
+    --- a/file
+    +++ b/file
+    @@ -1,1 +1,1 @@
+    -1
+    +2
+    diff --git a/untracked b/untracked
+    new file mode 100644
+    index 0000000000..5a72eb2edc
+    --- /dev/null
+    +++ b/untracked
+    @@ -0,0 +1,1 @@
+    +untracked
+    [EOF]
+    ");
+
+    // Applying again doesn't change anything
+    let output = test_env.run_jj_in(&repo_path, ["git", "stash", "apply", "1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["git", "stash", "apply", "2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No stash entry stash@{2}
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_git_stash_import() {
+    let test_env = TestEnvironment::default();
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "repo"])
+        .success();
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(repo_path.join("file"), "1\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "first"])
+        .success();
+    std::fs::write(repo_path.join("file"), "2\n").unwrap();
+    std::fs::write(repo_path.join("untracked"), "untracked\n").unwrap();
+    git::stash_push(&repo_path, "stashed", true);
+
+    // The stash isn't imported by default
+    let output = test_env.run_jj_in(&repo_path, ["git", "import"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+
+    test_env.add_config("git.import-stash = true");
+    let output = test_env.run_jj_in(&repo_path, ["git", "import"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Imported 1 Git stash entries as hidden commits:
+      nsqtyvyz 969a3948 On (no branch): stashed
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["git", "import"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+
+    // The imported commit is hidden, and is a child of the stashed-on commit
+    let output = test_env.run_jj_in(&repo_path, ["log", "-r", "all()"]);
+    insta::assert_snapshot!(output, @r"
+    @  rlvkpnrz test.user@example.com 2001-02-03 08:05:08 331ff78c
+    │  (empty) (no description set)
+    ○  qpvuntsm test.user@example.com 2001-02-03 08:05:08 git_head() dca1429b
+    │  first
+    ◆  zzzzzzzz root() 00000000
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["log", "-r", "969a3948-|969a3948", "--summary"]);
+    insta::assert_snapshot!(output, @r"
+    ○  nsqtyvyz hidden test.user@example.com 2001-02-03 08:05:06 969a3948
+    │  On (no branch): stashed
+    │  M file
+    │  A untracked
+    ○  qpvuntsm test.user@example.com 2001-02-03 08:05:08 git_head() dca1429b
+    │  first
+    ~  A file
+    [EOF]
+    ");
+}
@@ -0,0 +1,175 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use testutils::git;
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_git_worktree_export_detaches_head() {
+    let test_env = TestEnvironment::default();
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "repo"])
+        .success();
+    let repo_path = test_env.env_root().join("repo");
+    let worktree_path = test_env.env_root().join("feature");
+    std::fs::write(repo_path.join("file"), "1\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "first"])
+        .success();
+    test_env
+        .run_jj_in(
+            &repo_path,
+            ["bookmark", "create", "-r@-", "feature", "gone"],
+        )
+        .success();
+    git::add_worktree(&repo_path, &worktree_path, "feature");
+    git::add_worktree(&repo_path, &test_env.env_root().join("gone"), "gone");
+
+    let output = test_env.run_jj_in(&repo_path, ["git", "worktree", "list"]);
+    insta::assert_snapshot!(output, @r"
+    feature: ../feature (branch feature)
+    gone: ../gone (branch gone)
+    [EOF]
+    ");
+
+    // Moving or deleting the checked-out branches detaches the HEADs of the
+    // worktrees instead of changing their checked-out commits
+    std::fs::write(repo_path.join("file"), "2\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "second"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "set", "-r@-", "feature"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "delete", "gone"])
+        .success();
+    let output = test_env.run_jj_in(&repo_path, ["git", "worktree", "list"]);
+    insta::assert_snapshot!(output, @r"
+    feature: ../feature (detached at dca1429b466b)
+    gone: ../gone (detached at dca1429b466b)
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["bookmark", "list", "--all-remotes"]);
+    insta::assert_snapshot!(output, @r"
+    feature: rlvkpnrz 73d44724 second
+      @git: rlvkpnrz 73d44724 second
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_git_worktree_map() {
+    let test_env = TestEnvironment::default();
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "repo"])
+        .success();
+    let repo_path = test_env.env_root().join("repo");
+    let worktree_path = test_env.env_root().join("feature");
+    std::fs::write(repo_path.join("file"), "1\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "first"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "create", "-r@-", "feature"])
+        .success();
+    git::add_worktree(&repo_path, &worktree_path, "feature");
+    std::fs::write(worktree_path.join("file"), "modified\n").unwrap();
+
+    let output = test_env.run_jj_in(&repo_path, ["git", "worktree", "map", "unknown"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No Git worktree named 'unknown'
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["git", "worktree", "map"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Mapped worktree feature to workspace in "../feature"
+    [EOF]
+    "#);
+    let output = test_env.run_jj_in(&repo_path, ["git", "worktree", "list"]);
+    insta::assert_snapshot!(output, @r"
+    feature: ../feature (branch feature, workspace feature)
+    [EOF]
+    ");
+
+    // The changes made in the worktree are snapshotted
+    let output = test_env.run_jj_in(&worktree_path, ["status"]);
+    insta::assert_snapshot!(output, @r"
+    Working copy changes:
+    M file
+    Working copy : zxsnswpr d5a694bb (no description set)
+    Parent commit: qpvuntsm dca1429b feature | first
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["workspace", "list"]);
+    insta::assert_snapshot!(output, @r"
+    default: rlvkpnrz 331ff78c (empty) (no description set)
+    feature: zxsnswpr d5a694bb (no description set)
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["git", "worktree", "map"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["git", "worktree", "map", "feature"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Warning: Worktree feature is already mapped to workspace feature
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_git_worktree_map_multiple() {
+    let test_env = TestEnvironment::default();
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "repo"])
+        .success();
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(repo_path.join("file"), "1\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "first"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "create", "-r@-", "a", "b"])
+        .success();
+    git::add_worktree(&repo_path, &test_env.env_root().join("a"), "a");
+    git::add_worktree(&repo_path, &test_env.env_root().join("b"), "b");
+
+    let output = test_env.run_jj_in(&repo_path, ["git", "worktree", "map"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Mapped worktree a to workspace in "../a"
+    Mapped worktree b to workspace in "../b"
+    [EOF]
+    "#);
+
+    // The workspaces are created by sequential operations
+    let output = test_env.run_jj_in(&repo_path, ["op", "log", "-n4", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    @  create workspace b in git worktree
+    ○  add workspace 'b'
+    ○  create workspace a in git worktree
+    ○  add workspace 'a'
+    [EOF]
+    ");
+}
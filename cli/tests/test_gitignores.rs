@@ -104,6 +104,36 @@ fn test_gitignores_relative_excludes_file_path() {
     ");
 }
 
+// "~user" can't be expanded on Windows.
+#[cfg(unix)]
+#[test]
+fn test_gitignores_unexpandable_excludes_file_path() {
+    let test_env = TestEnvironment::default();
+    let workspace_root = test_env.env_root().join("repo");
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "repo"])
+        .success();
+
+    let mut file = std::fs::OpenOptions::new()
+        .append(true)
+        .open(workspace_root.join(".git").join("config"))
+        .unwrap();
+    file.write_all(b"[core]\nexcludesFile=~no-such-user-for-jj/my-ignores\n")
+        .unwrap();
+    drop(file);
+    std::fs::write(workspace_root.join("file"), "").unwrap();
+
+    // The path can't be expanded, so the user is warned
+    let output = test_env.run_jj_in(&workspace_root, ["diff", "-s"]);
+    insta::assert_snapshot!(output, @r"
+    A file
+    [EOF]
+    ------- stderr -------
+    Warning: Failed to expand the path of core.excludesFile: pwd user info is missing
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_gitignores_ignored_file_in_target_commit() {
     let test_env = TestEnvironment::default();
@@ -161,3 +191,47 @@ fn test_gitignores_ignored_file_in_target_commit() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_gitignores_info_exclude_in_worktree() {
+    let test_env = TestEnvironment::default();
+    test_env
+        .run_jj_in(".", ["git", "init", "--colocate", "repo"])
+        .success();
+    let repo_path = test_env.env_root().join("repo");
+    let worktree_path = test_env.env_root().join("feature");
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "first"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "create", "-r@-", "feature"])
+        .success();
+    git::add_worktree(&repo_path, &worktree_path, "feature");
+    test_env
+        .run_jj_in(&repo_path, ["git", "worktree", "map"])
+        .success();
+
+    // info/exclude in the common git directory applies to all worktrees
+    let mut file = std::fs::OpenOptions::new()
+        .append(true)
+        .open(repo_path.join(".git").join("info").join("exclude"))
+        .unwrap();
+    file.write_all(b"ignored\n").unwrap();
+    drop(file);
+
+    std::fs::write(repo_path.join("ignored"), "").unwrap();
+    std::fs::write(repo_path.join("not-ignored"), "").unwrap();
+    std::fs::write(worktree_path.join("ignored"), "").unwrap();
+    std::fs::write(worktree_path.join("not-ignored"), "").unwrap();
+
+    let output = test_env.run_jj_in(&repo_path, ["diff", "-s"]);
+    insta::assert_snapshot!(output, @r"
+    A not-ignored
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&worktree_path, ["diff", "-s"]);
+    insta::assert_snapshot!(output, @r"
+    A not-ignored
+    [EOF]
+    ");
+}
@@ -1080,8 +1080,11 @@ fn test_help() {
       -R, --repository <REPOSITORY>      Path to repository to operate on
           --ignore-working-copy          Don't snapshot the working copy, and don't update it
           --ignore-immutable             Allow rewriting immutable commits
+          --allow-protected              Allow rewriting protected commits
           --at-operation <AT_OPERATION>  Operation to load the repo at [aliases: at-op]
+          --event-log <FD>               Write machine-readable events to the given file descriptor
           --debug                        Enable debug logging
+          --no-default-args              Ignore the `commands.<name>.default-args` setting
           --color <WHEN>                 When to colorize output [possible values: always, never, debug,
                                          auto]
           --quiet                        Silence non-primary command output
@@ -1092,6 +1095,89 @@ fn test_help() {
     ");
 }
 
+#[cfg(unix)]
+#[test]
+fn test_event_log() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    // Events are written to stdout (fd 1) for testing.
+    std::fs::write(repo_path.join("file"), "a\n").unwrap();
+    let output = test_env.run_jj_in(&repo_path, ["describe", "-m", "a", "--event-log", "1"]);
+    insta::assert_snapshot!(output, @r#"
+    {"args":["describe","-m","a","--event-log","1"],"command":"describe","event":"command_started"}
+    {"description":"snapshot working copy","event":"operation_started"}
+    {"abandoned":[],"created":[],"event":"commits_rewritten","rewritten":{"230dd059e1b059aefc0da06a2e5a7dbf22362f22":["6b1027d2770cd0a39c468e525e52bf8c47e1464a"]}}
+    {"description":"snapshot working copy","event":"operation_finished","operation_id":"7938828ee4a210b20e4c7b719e0eeb6ef3ee89e8f7b4eca0778bb4cb00e8f13b967b4dd55fbcdbfaf0932594c4e0e6c9f5a3d3a7a4663339de02c1ef6ca3d5f2"}
+    {"description":"describe commit 6b1027d2770cd0a39c468e525e52bf8c47e1464a","event":"operation_started"}
+    {"abandoned":[],"created":[],"event":"commits_rewritten","rewritten":{"6b1027d2770cd0a39c468e525e52bf8c47e1464a":["b5eb8ca5f824968be8347b76dda2a56a6e1658bc"]}}
+    {"description":"describe commit 6b1027d2770cd0a39c468e525e52bf8c47e1464a","event":"operation_finished","operation_id":"0ed73fba5093a0ebf2d15bcc4951e561c6ddd8d3db34069a770d188402c9d64dc79afb8670afd7adaa6d2a0468c5ea2b5b6e06d1252f08604c8b8520043476b9"}
+    {"event":"command_finished","success":true}
+    [EOF]
+    ------- stderr -------
+    Working copy now at: qpvuntsm b5eb8ca5 a
+    Parent commit      : zzzzzzzz 00000000 (empty) (no description set)
+    [EOF]
+    "#);
+
+    test_env
+        .run_jj_in(&repo_path, ["new", "root()", "-m", "b"])
+        .success();
+    std::fs::write(repo_path.join("file"), "b\n").unwrap();
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["rebase", "-r", "description(a)", "-d", "@", "--event-log=1"],
+    );
+    insta::assert_snapshot!(output, @r#"
+    {"args":["rebase","-r","description(a)","-d","@","--event-log=1"],"command":"rebase","event":"command_started"}
+    {"description":"snapshot working copy","event":"operation_started"}
+    {"abandoned":[],"created":[],"event":"commits_rewritten","rewritten":{"06a973bcb57fc824c8214f55c07436ec9197dd33":["28610a3c7d0f344dae394da99ff0b897f388be19"]}}
+    {"description":"snapshot working copy","event":"operation_finished","operation_id":"bd7836574cf0b3b4e662f43761c5574eaa29a7ffd361bfedaa659e35c3a415f8f8d403a5d5c353faf065ea7ef1c694e6d22f743b99473992aaf98678bb9b650e"}
+    {"description":"rebase commit b5eb8ca5f824968be8347b76dda2a56a6e1658bc","event":"operation_started"}
+    {"abandoned":[],"created":[],"event":"commits_rewritten","rewritten":{"b5eb8ca5f824968be8347b76dda2a56a6e1658bc":["9315a131b3a044f6110c301ffe44a55f0a566187"]}}
+    {"commit_ids":["9315a131b3a044f6110c301ffe44a55f0a566187"],"event":"conflicts_created"}
+    {"description":"rebase commit b5eb8ca5f824968be8347b76dda2a56a6e1658bc","event":"operation_finished","operation_id":"3cc4d70532c3387e665ca0fc81194af20bd4f18b92404b8c88335e2b21af3ba5604a757a2c87784cb3d685e16b2a0b10476c932f6c99b0af127831ab82f7a60c"}
+    {"event":"command_finished","success":true}
+    [EOF]
+    ------- stderr -------
+    Rebased 1 commits onto destination
+    New conflicts appeared in these commits:
+      qpvuntsm 9315a131 (conflict) a
+    Hint: To resolve the conflicts, start by updating to it:
+      jj new qpvuntsm
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you may want to inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    "#);
+
+    // Failed command
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["describe", "-r", "root()", "-m", "root", "--event-log=1"],
+    );
+    insta::assert_snapshot!(output, @r#"
+    {"args":["describe","-r","root()","-m","root","--event-log=1"],"command":"describe","event":"command_started"}
+    {"error":"The root commit 000000000000 is immutable","event":"command_finished","success":false}
+    [EOF]
+    ------- stderr -------
+    Error: The root commit 000000000000 is immutable
+    [EOF]
+    [exit status: 1]
+    "#);
+
+    // Invalid file descriptor
+    let output = test_env.run_jj_in(&repo_path, ["log", "--event-log=99"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to open event log fd 99
+    Caused by: Bad file descriptor (os error 9)
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_debug_logging_enabled() {
     // Test that the debug flag enabled debug logging
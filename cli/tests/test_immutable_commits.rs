@@ -566,3 +566,84 @@ fn test_rewrite_immutable_commands() {
     [exit status: 1]
     "#);
 }
+
+#[test]
+fn test_rewrite_protected() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m=a"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "-m=b\n\nReviewed-on: 123"])
+        .success();
+    test_env.run_jj_in(&repo_path, ["new", "-m=c"]).success();
+    test_env.add_config(r#"revsets.protected = "description(glob:'*Reviewed-on:*')""#);
+
+    // Cannot rewrite a commit in the configured set
+    let output = test_env.run_jj_in(&repo_path, ["describe", "-r@-", "-m=b2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Commit 5e1a6bc28570 is protected
+    Hint: Could not modify commit: kkmpptxz 5e1a6bc2 (empty) b
+    Hint: Protected commits are configured by `revsets.protected`.
+    Hint: Use `--allow-protected` to rewrite it anyway.
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["abandon", "@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Commit 5e1a6bc28570 is protected
+    Hint: Could not modify commit: kkmpptxz 5e1a6bc2 (empty) b
+    Hint: Protected commits are configured by `revsets.protected`.
+    Hint: Use `--allow-protected` to rewrite it anyway.
+    [EOF]
+    [exit status: 1]
+    ");
+    // Cannot rewrite an ancestor of the configured set
+    let output = test_env.run_jj_in(&repo_path, ["describe", "-r@--", "-m=a2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Commit 5e1a6bc28570 is protected
+    Hint: Could not modify commit: kkmpptxz 5e1a6bc2 (empty) b
+    Hint: Protected commits are configured by `revsets.protected`.
+    Hint: Use `--allow-protected` to rewrite it anyway.
+    [EOF]
+    [exit status: 1]
+    ");
+    // Descendants can be rewritten
+    let output = test_env.run_jj_in(&repo_path, ["describe", "-m=c2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy now at: zsuskuln 5b71d952 (empty) c2
+    Parent commit      : kkmpptxz 5e1a6bc2 (empty) b
+    [EOF]
+    ");
+
+    // Can use --allow-protected to override
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["--allow-protected", "describe", "-r@-", "-m=b2"],
+    );
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 1 descendant commits
+    Working copy now at: zsuskuln ce322653 (empty) c2
+    Parent commit      : kkmpptxz 09a4e185 (empty) b2
+    [EOF]
+    ");
+
+    // Invalid expression is reported
+    test_env.add_config(r#"revsets.protected = "bookmark_that_does_not_exist""#);
+    let output = test_env.run_jj_in(&repo_path, ["describe", "-m=c3"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Config error: Invalid `revsets.protected`
+    Caused by: Revision `bookmark_that_does_not_exist` doesn't exist
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+}
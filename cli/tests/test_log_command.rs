@@ -362,6 +362,52 @@ fn test_log_null_terminate_multiline_descriptions() {
     );
 }
 
+#[test]
+fn test_log_null_terminated() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "first\n\nbody"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "second"])
+        .success();
+
+    let template = r#"commit_id.short() ++ " " ++ description"#;
+    let output = test_env
+        .run_jj_in(
+            &repo_path,
+            [
+                "log",
+                "-r",
+                "~root()",
+                "-T",
+                template,
+                "-z",
+                "--color=always",
+            ],
+        )
+        .success();
+    insta::assert_debug_snapshot!(output.stdout.normalized(), @r#"
+    "4116505196e1 second\008f6e9e8f9f6 first\n\nbody\0"
+    "#);
+
+    // Can't be combined with the graph export
+    let output = test_env.run_jj_in(&repo_path, ["log", "-z", "--format=dot"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--null' cannot be used with '--format <FORMAT>'
+
+    Usage: jj log --null [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_log_shortest_accessors() {
     let test_env = TestEnvironment::default();
@@ -985,6 +1031,85 @@ fn test_log_filtered_by_path() {
     ");
 }
 
+#[test]
+fn test_log_filtered_by_diff_contains() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file1"), "foo\n").unwrap();
+    std::fs::write(repo_path.join("file2"), "foo\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "first"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "-m", "second"])
+        .success();
+    std::fs::write(repo_path.join("file1"), "foo\nbar\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["new", "-m", "third"])
+        .success();
+    std::fs::write(repo_path.join("file2"), "").unwrap();
+
+    // Lines matching the pattern are searched in all revisions
+    let output = test_env.run_jj_in(&repo_path, ["log", "-T", "description", "-G", "^fo+$"]);
+    insta::assert_snapshot!(output, @r"
+    @  third
+    ~  (elided revisions)
+    ○  first
+    │
+    ~
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["log", "-T", "description", "-Gba"]);
+    insta::assert_snapshot!(output, @r"
+    ○  second
+    │
+    ~
+    [EOF]
+    ");
+
+    // Paths limit the diffs to search
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["log", "-T", "description", "--diff-contains=foo", "file2"],
+    );
+    insta::assert_snapshot!(output, @r"
+    @  third
+    ~  (elided revisions)
+    ○  first
+    │
+    ~
+    [EOF]
+    ");
+
+    // Revisions are intersected with the matching ones
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["log", "-T", "description", "-Gfoo", "-r", "@-::"],
+    );
+    insta::assert_snapshot!(output, @r"
+    @  third
+    │
+    ~
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["log", "-G", "("]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: invalid value '(' for '--diff-contains <REGEX>': regex parse error:
+        (
+        ^
+    error: unclosed group
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_log_limit() {
     let test_env = TestEnvironment::default();
@@ -1081,6 +1206,379 @@ fn test_log_limit() {
     ");
 }
 
+#[test]
+fn test_log_order() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    // Create the commits of two branches alternately
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "a"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "root()", "-m", "c"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "description(a)", "-m", "b"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "description(c)", "-m", "d"])
+        .success();
+    // Update the committer date of "b", but not the author date
+    test_env
+        .run_jj_in(&repo_path, ["describe", "description(b)", "-m", "b2"])
+        .success();
+
+    let template = r#"description.first_line() ++ " " ++ author.timestamp().format("%H:%M:%S") ++ " " ++ committer.timestamp().format("%H:%M:%S") ++ "\n""#;
+    let output = test_env.run_jj_in(&repo_path, ["log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    @  d 04:05:11 04:05:11
+    ○  c 04:05:09 04:05:09
+    │ ○  b2 04:05:10 04:05:12
+    │ ○  a 04:05:08 04:05:08
+    ├─╯
+    ◆   00:00:00 00:00:00
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["log", "-T", template, "--order=date"]);
+    insta::assert_snapshot!(output, @r"
+    ○  b2 04:05:10 04:05:12
+    │ @  d 04:05:11 04:05:11
+    │ ○  c 04:05:09 04:05:09
+    ○ │  a 04:05:08 04:05:08
+    ├─╯
+    ◆   00:00:00 00:00:00
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["log", "-T", template, "--order=author-date"]);
+    insta::assert_snapshot!(output, @r"
+    @  d 04:05:11 04:05:11
+    │ ○  b2 04:05:10 04:05:12
+    ○ │  c 04:05:09 04:05:09
+    │ ○  a 04:05:08 04:05:08
+    ├─╯
+    ◆   00:00:00 00:00:00
+    [EOF]
+    ");
+
+    // Applied before the limit and reversing
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "log",
+            "-T",
+            template,
+            "--order=date",
+            "--limit=3",
+            "--reversed",
+            "--no-graph",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    c 04:05:09 04:05:09
+    d 04:05:11 04:05:11
+    b2 04:05:10 04:05:12
+    [EOF]
+    ");
+
+    // Children are shown before their parents regardless of the dates
+    test_env
+        .run_jj_in(
+            &repo_path,
+            ["describe", "description(a)", "-m", "a2", "--reset-author"],
+        )
+        .success();
+    test_env.add_config(r#"ui.log-order = "author-date""#);
+    let output = test_env.run_jj_in(&repo_path, ["log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    @  d 04:05:11 04:05:11
+    │ ○  b2 04:05:10 04:05:17
+    │ ○  a2 04:05:17 04:05:17
+    ○ │  c 04:05:09 04:05:09
+    ├─╯
+    ◆   00:00:00 00:00:00
+    [EOF]
+    ");
+
+    // The command-line option takes precedence over the config
+    let output = test_env.run_jj_in(&repo_path, ["log", "-T", template, "--order=topo"]);
+    insta::assert_snapshot!(output, @r"
+    @  d 04:05:11 04:05:11
+    ○  c 04:05:09 04:05:09
+    │ ○  b2 04:05:10 04:05:17
+    │ ○  a2 04:05:17 04:05:17
+    ├─╯
+    ◆   00:00:00 00:00:00
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_log_first_parent() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "a"])
+        .success();
+    test_env.run_jj_in(&repo_path, ["new", "-m", "b"]).success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "description(a)", "-m", "c"])
+        .success();
+    test_env.run_jj_in(&repo_path, ["new", "-m", "d"]).success();
+    test_env
+        .run_jj_in(
+            &repo_path,
+            ["new", "description(d)", "description(b)", "-m", "merge"],
+        )
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "description(c)", "-m", "side"])
+        .success();
+
+    let output = test_env.run_jj_in(&repo_path, ["log", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    @  side
+    │ ○    merge
+    │ ├─╮
+    │ │ ○  b
+    │ ○ │  d
+    ├─╯ │
+    ○   │  c
+    ├───╯
+    ○  a
+    ◆
+    [EOF]
+    ");
+
+    // The branches merged as the second parent are omitted, but the other
+    // heads are still shown
+    let output = test_env.run_jj_in(&repo_path, ["log", "-T", "description", "--first-parent"]);
+    insta::assert_snapshot!(output, @r"
+    @  side
+    │ ○  merge
+    │ ○  d
+    ├─╯
+    ○  c
+    ○  a
+    ◆
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "log",
+            "-T",
+            "description",
+            "--first-parent",
+            "--no-graph",
+            "-r",
+            "::description(merge)",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    merge
+    d
+    c
+    a
+    [EOF]
+    ");
+
+    // Indirect first parents are followed
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "log",
+            "-T",
+            "description",
+            "--first-parent",
+            "-r",
+            "description(merge) | description(a) | description(b)",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    ○  merge
+    ~  (elided revisions)
+    ○  a
+    │
+    ~
+    [EOF]
+    ");
+
+    // Revisions only reachable through the second parents are omitted
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "log",
+            "-T",
+            "description",
+            "--first-parent",
+            "-r",
+            "description(merge) | description(b)",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    ○  merge
+    │
+    ~
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_log_diff_merges() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    let base: String = (1..=10).map(|i| format!("line {i}\n")).collect();
+    std::fs::write(repo_path.join("file1"), &base).unwrap();
+    std::fs::write(repo_path.join("file2"), "base\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "create", "-r@", "base"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "base", "-m=left"])
+        .success();
+    let left = base
+        .replace("line 1\n", "left 1\n")
+        .replace("line 10\n", "left 10\n");
+    std::fs::write(repo_path.join("file1"), left).unwrap();
+    std::fs::write(repo_path.join("file2"), "left\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "create", "-r@", "left"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "base", "-m=right"])
+        .success();
+    std::fs::write(
+        repo_path.join("file1"),
+        base.replace("line 1\n", "right 1\n"),
+    )
+    .unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "create", "-r@", "right"])
+        .success();
+
+    // The merge resolves the conflict in the first line, takes the last line
+    // and file2 from the left side, and adds file3
+    test_env
+        .run_jj_in(&repo_path, ["new", "left", "right", "-m=merge"])
+        .success();
+    let merged = base
+        .replace("line 1\n", "resolved 1\n")
+        .replace("line 10\n", "left 10\n");
+    std::fs::write(repo_path.join("file1"), merged).unwrap();
+    std::fs::write(repo_path.join("file3"), "new\n").unwrap();
+
+    let run_log = |args: &[&str]| {
+        test_env.run_jj_with(|cmd| {
+            cmd.current_dir(&repo_path)
+                .args(["log", "--no-graph", "-T=description"])
+                .args(args)
+        })
+    };
+    let output = run_log(&["-r=@", "--git"]);
+    insta::assert_snapshot!(output, @r"
+    merge
+    diff --git a/file1 b/file1
+    index 0000000000..fed4eb9bd4 100644
+    --- a/file1
+    +++ b/file1
+    @@ -1,10 +1,4 @@
+    -<<<<<<< Conflict 1 of 1
+    -%%%%%%% Changes from base to side #1
+    --line 1
+    -+left 1
+    -+++++++ Contents of side #2
+    -right 1
+    ->>>>>>> Conflict 1 of 1 ends
+    +resolved 1
+     line 2
+     line 3
+     line 4
+    diff --git a/file3 b/file3
+    new file mode 100644
+    index 0000000000..3e757656cf
+    --- /dev/null
+    +++ b/file3
+    @@ -0,0 +1,1 @@
+    +new
+    [EOF]
+    ");
+    let output = run_log(&["-r=@", "--git", "--diff-merges=first-parent"]);
+    insta::assert_snapshot!(output, @r"
+    merge
+    diff --git a/file1 b/file1
+    index 92a9b8f2ad..fed4eb9bd4 100644
+    --- a/file1
+    +++ b/file1
+    @@ -1,4 +1,4 @@
+    -left 1
+    +resolved 1
+     line 2
+     line 3
+     line 4
+    diff --git a/file3 b/file3
+    new file mode 100644
+    index 0000000000..3e757656cf
+    --- /dev/null
+    +++ b/file3
+    @@ -0,0 +1,1 @@
+    +new
+    [EOF]
+    ");
+    let output = run_log(&["-r=@", "--git", "--diff-merges=combined"]);
+    insta::assert_snapshot!(output, @r"
+    merge
+    diff --cc file1
+    index 92a9b8f2ad,b14f5dec7f..fed4eb9bd4
+    --- a/file1
+    +++ b/file1
+    @@@ -1,4 -1,4 +1,4 @@@
+    - left 1
+     -right 1
+    ++resolved 1
+      line 2
+      line 3
+      line 4
+    diff --cc file3
+    mode 000000,000000..100644
+    index 0000000000,0000000000..3e757656cf
+    --- /dev/null
+    +++ b/file3
+    @@@ -0,0 -0,0 +1,1 @@@
+    ++new
+    [EOF]
+    ");
+    let output = run_log(&["-r=@", "--summary", "--diff-merges=combined"]);
+    insta::assert_snapshot!(output, @r"
+    merge
+    M file1
+    A file3
+    [EOF]
+    ");
+    let output = run_log(&["-r=@", "--git", "--diff-merges=none"]);
+    insta::assert_snapshot!(output, @r"
+    merge
+    [EOF]
+    ");
+
+    // Commits with a single parent are shown as usual
+    let output = run_log(&["-r=left", "--summary", "--diff-merges=none"]);
+    insta::assert_snapshot!(output, @r"
+    left
+    M file1
+    M file2
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_log_warn_path_might_be_revset() {
     let test_env = TestEnvironment::default();
@@ -1293,6 +1791,105 @@ fn test_graph_template_color() {
     ");
 }
 
+#[test]
+fn test_graph_edge_template_color() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "base"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "-m", "main"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "@-", "-m", "side"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "@", "@-", "-m", "merge"])
+        .success();
+
+    test_env.add_config(
+        r#"
+        templates.log_edge = 'if(description.starts_with("side"), "side")'
+        colors."edge side" = "red"
+        colors.edge = "blue"
+        "#,
+    );
+    let template = r#"description.first_line()"#;
+
+    // Without color, the graph is unchanged
+    let output = test_env.run_jj_in(&repo_path, ["log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    @    merge
+    ├─╮
+    ○ │  side
+    ├─╯
+    │ ○  main
+    ├─╯
+    ○  base
+    ◆
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["--color=always", "log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    [1m[38;5;2m@[0m    merge
+    [38;5;4m├[39m[38;5;4m─[39m[38;5;4m╮[39m
+    ○ [38;5;4m│[39m  side
+    [38;5;1m├[39m[38;5;1m─[39m[38;5;4m╯[39m
+    [38;5;1m│[39m ○  main
+    [38;5;1m├[39m[38;5;4m─[39m[38;5;4m╯[39m
+    ○  base
+    [1m[38;5;14m◆[0m
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["--color=debug", "log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    [1m[38;5;2m<<node working_copy::@>>[0m    <<log description first_line::merge>>
+    [38;5;4m<<edge::├>>[39m[38;5;4m<<edge::─>>[39m[38;5;4m<<edge::╮>>[39m
+    <<node::○>> [38;5;4m<<edge::│>>[39m  <<log description first_line::side>>
+    [38;5;1m<<edge side::├>>[39m[38;5;1m<<edge side::─>>[39m[38;5;4m<<edge::╯>>[39m
+    [38;5;1m<<edge side::│>>[39m <<node::○>>  <<log description first_line::main>>
+    [38;5;1m<<edge side::├>>[39m[38;5;4m<<edge::─>>[39m[38;5;4m<<edge::╯>>[39m
+    <<node::○>>  <<log description first_line::base>>
+    [1m[38;5;14m<<node immutable::◆>>[0m
+    [EOF]
+    ");
+
+    // Elided nodes are styled with the template evaluated without a commit
+    test_env.add_config(
+        r#"
+        ui.log-synthetic-elided-nodes = true
+        templates.log_edge = 'if(!self, "elided")'
+        colors."edge elided" = "yellow"
+        "#,
+    );
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "--color=always",
+            "log",
+            "-T",
+            r#"description ++ "\n""#,
+            "-r",
+            "@ | description(base)",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    [1m[38;5;2m@[0m    merge
+    [38;5;4m├[39m[38;5;4m─[39m[38;5;4m╮[39m
+    [38;5;8m~[39m [38;5;4m│[39m  [38;5;8m(elided revisions)[39m
+    [38;5;3m├[39m[38;5;3m─[39m[38;5;4m╯[39m
+    ○  base
+    [38;5;4m│[39m
+    [38;5;4m~[39m
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_graph_styles() {
     // Test that different graph styles are available.
@@ -1830,3 +2427,95 @@ fn test_log_full_description_template() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_log_graph_export() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m=base"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "-m=left \"quoted\""])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "-m=elided", "description(base)"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "-m=right\nsecond line"])
+        .success();
+    test_env
+        .run_jj_in(
+            &repo_path,
+            ["new", "-m=merge", "description(left)", "description(right)"],
+        )
+        .success();
+    let template = r#"commit_id.short() ++ " " ++ description"#;
+    let revisions = "~description(elided)";
+
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["log", "-r", revisions, "-T", template, "--format=dot"],
+    );
+    insta::assert_snapshot!(output, @r#"
+    digraph {
+        node [shape=box];
+        "cba5a988338970d57f06144c21fa29b64927311b" [label="cba5a9883389 merge\l"];
+        "dbf220f4d573d03dc4bc1863bd6f5b46f1f0a1f3" [label="dbf220f4d573 right\lsecond line\l"];
+        "15c5686e24bf949657ac6154a4721b462cc51935" [label="15c5686e24bf left \"quoted\"\l"];
+        "494c7b8309856d8f7b04a92eb5c13a19ade0271f" [label="494c7b830985 base\l"];
+        "0000000000000000000000000000000000000000" [label="000000000000 \l"];
+        "cba5a988338970d57f06144c21fa29b64927311b" -> "15c5686e24bf949657ac6154a4721b462cc51935";
+        "cba5a988338970d57f06144c21fa29b64927311b" -> "dbf220f4d573d03dc4bc1863bd6f5b46f1f0a1f3";
+        "dbf220f4d573d03dc4bc1863bd6f5b46f1f0a1f3" -> "494c7b8309856d8f7b04a92eb5c13a19ade0271f" [style=dashed];
+        "15c5686e24bf949657ac6154a4721b462cc51935" -> "494c7b8309856d8f7b04a92eb5c13a19ade0271f";
+        "494c7b8309856d8f7b04a92eb5c13a19ade0271f" -> "0000000000000000000000000000000000000000";
+    }
+    [EOF]
+    "#);
+
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["log", "-r", revisions, "-T", template, "--format=mermaid"],
+    );
+    insta::assert_snapshot!(output, @r#"
+    flowchart TD
+        cba5a988338970d57f06144c21fa29b64927311b["cba5a9883389 merge"]
+        dbf220f4d573d03dc4bc1863bd6f5b46f1f0a1f3["dbf220f4d573 right<br>second line"]
+        15c5686e24bf949657ac6154a4721b462cc51935["15c5686e24bf left #quot;quoted#quot;"]
+        494c7b8309856d8f7b04a92eb5c13a19ade0271f["494c7b830985 base"]
+        0000000000000000000000000000000000000000["000000000000 "]
+        cba5a988338970d57f06144c21fa29b64927311b --> 15c5686e24bf949657ac6154a4721b462cc51935
+        cba5a988338970d57f06144c21fa29b64927311b --> dbf220f4d573d03dc4bc1863bd6f5b46f1f0a1f3
+        dbf220f4d573d03dc4bc1863bd6f5b46f1f0a1f3 -.-> 494c7b8309856d8f7b04a92eb5c13a19ade0271f
+        15c5686e24bf949657ac6154a4721b462cc51935 --> 494c7b8309856d8f7b04a92eb5c13a19ade0271f
+        494c7b8309856d8f7b04a92eb5c13a19ade0271f --> 0000000000000000000000000000000000000000
+    [EOF]
+    "#);
+
+    // Edges to revisions outside of the limit aren't emitted
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["log", "-T", template, "--format=mermaid", "--limit=2"],
+    );
+    insta::assert_snapshot!(output, @r#"
+    flowchart TD
+        cba5a988338970d57f06144c21fa29b64927311b["cba5a9883389 merge"]
+        dbf220f4d573d03dc4bc1863bd6f5b46f1f0a1f3["dbf220f4d573 right<br>second line"]
+        cba5a988338970d57f06144c21fa29b64927311b --> dbf220f4d573d03dc4bc1863bd6f5b46f1f0a1f3
+    [EOF]
+    "#);
+
+    let output = test_env.run_jj_in(&repo_path, ["log", "--format=dot", "--no-graph"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--format <FORMAT>' cannot be used with '--no-graph'
+
+    Usage: jj log --format <FORMAT> [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
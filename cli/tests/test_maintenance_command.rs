@@ -0,0 +1,277 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use testutils::git;
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_maintenance_run() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "first"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "second"])
+        .success();
+
+    let output = test_env.run_jj_in(&repo_path, ["maintenance", "run"]);
+    insta::assert_snapshot!(output, @r"
+    prefetch: skipped (disabled)
+    op-log-compaction: skipped (disabled)
+    gc: pruned unreachable objects older than 14 days
+    index: 6 commits indexed
+    search-index: 6 commits indexed
+    [EOF]
+    ");
+
+    // Operations older than the configured number of days are abandoned
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "maintenance",
+            "run",
+            "--config=maintenance.op-log-compaction.enabled=true",
+            "--config=maintenance.op-log-compaction.keep-days=0",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    prefetch: skipped (disabled)
+    op-log-compaction: abandoned 2 operations older than 0 days
+    gc: pruned unreachable objects older than 14 days
+    index: 6 commits indexed
+    search-index: 0 commits indexed
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["op", "log", "-Tdescription"]);
+    insta::assert_snapshot!(output, @r"
+    @  commit e8ea92a8b6b35dd30842d619e124ca9cc4a3c0cc
+    ○
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["log", "-Tdescription"]);
+    insta::assert_snapshot!(output, @r"
+    @
+    ○  second
+    ○  first
+    ◆
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_maintenance_run_tasks() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    // Explicitly selected tasks are run even if they're disabled
+    test_env.add_config("maintenance.index.enabled = false");
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "maintenance",
+            "run",
+            "--task=index",
+            "--task=op-log-compaction",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    op-log-compaction: no operations older than 90 days
+    index: 2 commits indexed
+    [EOF]
+    ");
+
+    // Building the search index enables it. New commits are indexed as they
+    // are created, so running the task again has nothing to do.
+    let output = test_env.run_jj_in(&repo_path, ["maintenance", "run", "--task=search-index"]);
+    insta::assert_snapshot!(output, @r"
+    search-index: 2 commits indexed
+    [EOF]
+    ");
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "first"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "second"])
+        .success();
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["log", "-r=description(first)", "-Tdescription"],
+    );
+    insta::assert_snapshot!(output, @r"
+    ○  first
+    │
+    ~
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["maintenance", "run", "--task=search-index"]);
+    insta::assert_snapshot!(output, @r"
+    search-index: 0 commits indexed
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["maintenance", "run", "--at-op=@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Cannot run maintenance from a non-head operation
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_maintenance_run_prefetch() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env.add_config("maintenance.prefetch.enabled = true");
+
+    // A failing task doesn't prevent the other tasks from running
+    let output = test_env.run_jj_in(&repo_path, ["maintenance", "run"]);
+    insta::assert_snapshot!(output, @r"
+    prefetch: failed
+    op-log-compaction: skipped (disabled)
+    gc: pruned unreachable objects older than 14 days
+    index: 2 commits indexed
+    search-index: 2 commits indexed
+    [EOF]
+    ------- stderr -------
+    Warning: Maintenance task prefetch failed: No git remote named 'origin'
+    Error: 1 of 4 maintenance tasks failed: prefetch
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let git_repo = git::init(test_env.env_root().join("origin"));
+    git::add_commit(
+        &git_repo,
+        "refs/heads/main",
+        "file",
+        b"content",
+        "message",
+        &[],
+    );
+    test_env
+        .run_jj_in(&repo_path, ["git", "remote", "add", "origin", "../origin"])
+        .success();
+    let output = test_env.run_jj_in(&repo_path, ["maintenance", "run"]);
+    insta::assert_snapshot!(output, @r"
+    prefetch: fetched from origin
+    op-log-compaction: skipped (disabled)
+    gc: pruned unreachable objects older than 14 days
+    index: 3 commits indexed
+    search-index: 0 commits indexed
+    [EOF]
+    ------- stderr -------
+    bookmark: main@origin [new] untracked
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["bookmark", "list", "--all-remotes"]);
+    insta::assert_snapshot!(output, @r"
+    main@origin: qomsplrm ebeb70d8 message
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_maintenance_register() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    let jj_path = assert_cmd::cargo::cargo_bin("jj");
+    let jj_path = jj_path.to_str().unwrap();
+
+    let output = test_env
+        .run_jj_in(&repo_path, ["maintenance", "register", "--scheduler=cron"])
+        .normalize_stdout_with(|s| s.replace(jj_path, "<jj>"));
+    insta::assert_snapshot!(output, @r"
+    # jj maintenance for $TEST_ENV/repo
+    0 3 * * * '<jj>' '--repository' '$TEST_ENV/repo' 'maintenance' 'run' '--quiet'
+    [EOF]
+    ------- stderr -------
+    Hint: Add the line above to your crontab, for example with `crontab -e`.
+    [EOF]
+    ");
+
+    let output = test_env
+        .run_jj_in(
+            &repo_path,
+            ["maintenance", "register", "--scheduler=systemd"],
+        )
+        .normalize_stdout_with(|s| s.replace(jj_path, "<jj>"));
+    insta::assert_snapshot!(output, @r#"
+    # jj-maintenance-repo.service
+    [Unit]
+    Description=jj maintenance for $TEST_ENV/repo
+
+    [Service]
+    Type=oneshot
+    ExecStart="<jj>" "--repository" "$TEST_ENV/repo" "maintenance" "run" "--quiet"
+
+    # jj-maintenance-repo.timer
+    [Unit]
+    Description=Daily jj maintenance for $TEST_ENV/repo
+
+    [Timer]
+    OnCalendar=daily
+    Persistent=true
+
+    [Install]
+    WantedBy=timers.target
+    [EOF]
+    ------- stderr -------
+    Hint: Save the units above to ~/.config/systemd/user/, then run `systemctl --user enable --now jj-maintenance-repo.timer`.
+    [EOF]
+    "#);
+
+    let output = test_env
+        .run_jj_in(
+            &repo_path,
+            ["maintenance", "register", "--scheduler=launchd"],
+        )
+        .normalize_stdout_with(|s| s.replace(jj_path, "<jj>"));
+    insta::assert_snapshot!(output, @r#"
+    <?xml version="1.0" encoding="UTF-8"?>
+    <!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+    <plist version="1.0">
+    <dict>
+      <key>Label</key>
+      <string>dev.jj-vcs.jj-maintenance-repo</string>
+      <key>ProgramArguments</key>
+      <array>
+        <string><jj></string>
+        <string>--repository</string>
+        <string>$TEST_ENV/repo</string>
+        <string>maintenance</string>
+        <string>run</string>
+        <string>--quiet</string>
+      </array>
+      <key>StartCalendarInterval</key>
+      <dict>
+        <key>Hour</key>
+        <integer>3</integer>
+        <key>Minute</key>
+        <integer>0</integer>
+      </dict>
+    </dict>
+    </plist>
+    [EOF]
+    ------- stderr -------
+    Hint: Save the property list above to ~/Library/LaunchAgents/dev.jj-vcs.jj-maintenance-repo.plist, then run `launchctl load ~/Library/LaunchAgents/dev.jj-vcs.jj-maintenance-repo.plist`.
+    [EOF]
+    "#);
+}
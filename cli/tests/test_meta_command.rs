@@ -0,0 +1,115 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_meta_set_get_list() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    test_env
+        .run_jj_in(&repo_path, ["meta", "set", "ticket", "ABC-1"])
+        .success();
+    test_env
+        .run_jj_in(
+            &repo_path,
+            ["meta", "set", "review", "https://example.com/r/1"],
+        )
+        .success();
+    let output = test_env.run_jj_in(&repo_path, ["meta", "get", "ticket"]);
+    insta::assert_snapshot!(output, @r"
+    ABC-1
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["meta", "list"]);
+    insta::assert_snapshot!(output, @r"
+    review=https://example.com/r/1
+    ticket=ABC-1
+    [EOF]
+    ");
+
+    // Metadata is kept when the change is rewritten
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "first"])
+        .success();
+    test_env.run_jj_in(&repo_path, ["new"]).success();
+    test_env
+        .run_jj_in(&repo_path, ["meta", "set", "ticket", "ABC-2"])
+        .success();
+    let output = test_env.run_jj_in(&repo_path, ["meta", "list", "-r", "@-"]);
+    insta::assert_snapshot!(output, @r"
+    review=https://example.com/r/1
+    ticket=ABC-1
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "log",
+            "-T",
+            r#"description.first_line() ++ " " ++ self.metadata("ticket") ++ "\n""#,
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    @   ABC-2
+    ○  first ABC-1
+    ◆
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "log",
+            "-r",
+            r#"metadata("ticket", "ABC-1")"#,
+            "-T",
+            "description",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    ○  first
+    │
+    ~
+    [EOF]
+    ");
+
+    test_env
+        .run_jj_in(&repo_path, ["meta", "remove", "-r", "@-", "ticket"])
+        .success();
+    let output = test_env.run_jj_in(&repo_path, ["meta", "get", "-r", "@-", "ticket"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No metadata entry named ticket
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["meta", "remove", "-r", "@-", "ticket"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No metadata entry named ticket
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Metadata changes can be undone
+    test_env.run_jj_in(&repo_path, ["undo"]).success();
+    let output = test_env.run_jj_in(&repo_path, ["meta", "get", "-r", "@-", "ticket"]);
+    insta::assert_snapshot!(output, @r"
+    ABC-1
+    [EOF]
+    ");
+}
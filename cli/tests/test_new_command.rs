@@ -174,6 +174,62 @@ fn test_new_merge() {
     ");
 }
 
+#[test]
+fn test_new_merge_with_merge_strategies() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env.add_config(
+        r#"
+        [merge.strategies]
+        "*.md" = "union"
+        "#,
+    );
+
+    std::fs::write(repo_path.join("CHANGELOG.md"), "* base\n").unwrap();
+    std::fs::write(repo_path.join("src.txt"), "base\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "base"])
+        .success();
+    std::fs::write(repo_path.join("CHANGELOG.md"), "* base\n* left\n").unwrap();
+    std::fs::write(repo_path.join("src.txt"), "left\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "left"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "description(base)", "-m", "right"])
+        .success();
+    std::fs::write(repo_path.join("CHANGELOG.md"), "* base\n* right\n").unwrap();
+    std::fs::write(repo_path.join("src.txt"), "right\n").unwrap();
+
+    // Only the file without a strategy is left conflicted in the merge commit
+    let output = test_env.run_jj_in(&repo_path, ["new", "description(left)", "@"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy now at: mzvwutvl 5a92d186 (conflict) (no description set)
+    Parent commit      : rlvkpnrz 8ea6eaf2 left
+    Parent commit      : zsuskuln 00894f2b right
+    Added 0 files, modified 2 files, removed 0 files
+    Warning: There are unresolved conflicts at these paths:
+    src.txt    2-sided conflict
+    New conflicts appeared in these commits:
+      mzvwutvl 5a92d186 (conflict) (no description set)
+    Hint: To resolve the conflicts, start by updating to it:
+      jj new mzvwutvl
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you may want to inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["file", "show", "CHANGELOG.md"]);
+    insta::assert_snapshot!(output, @r"
+    * base
+    * left
+    * right
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_new_insert_after() {
     let test_env = TestEnvironment::default();
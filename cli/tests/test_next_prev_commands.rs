@@ -1116,6 +1116,159 @@ fn test_next_conflict_editing() {
     ");
 }
 
+#[test]
+fn test_next_conflict_stack_bottom_up() {
+    // After rewriting the bottom of a stack, `jj next --conflict --edit` visits
+    // the conflicted commits from the bottom up.
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    let file_path = repo_path.join("content.txt");
+    std::fs::write(&file_path, "first").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "first"])
+        .success();
+    std::fs::write(&file_path, "second").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "second"])
+        .success();
+    std::fs::write(&file_path, "third").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "third"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["edit", "description(first)"])
+        .success();
+    std::fs::write(&file_path, "first v2").unwrap();
+    // Test the setup
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r"
+    ×  kkmpptxzrspx conflict third
+    ×  rlvkpnrzqnoo conflict second
+    @  qpvuntsmwlqt first
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ------- stderr -------
+    Rebased 2 descendant commits onto updated working copy
+    [EOF]
+    ");
+
+    test_env
+        .run_jj_in(&repo_path, ["next", "--conflict", "--edit"])
+        .success();
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r"
+    ×  kkmpptxzrspx conflict third
+    @  rlvkpnrzqnoo conflict second
+    ○  qpvuntsmwlqt first
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+
+    // Resolve the conflict and move on to the next one
+    std::fs::write(&file_path, "second v2").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["next", "--conflict", "--edit"])
+        .success();
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r"
+    @  kkmpptxzrspx conflict third
+    ○  rlvkpnrzqnoo second
+    ○  qpvuntsmwlqt first
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+
+    // There are no conflicts left once the last one is resolved
+    std::fs::write(&file_path, "third v2").unwrap();
+    let output = test_env.run_jj_in(&repo_path, ["next", "--conflict", "--edit"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: The working copy has no descendants with conflicts
+    Hint: Working copy: kkmpptxz 6d3b3601 third
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_next_conflict_stack_after_rebase() {
+    // The sequence in docs/conflicts.md: start from a new commit on top of the
+    // destination, and edit the conflicted commits in the rebased stack.
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    let file_path = repo_path.join("content.txt");
+    std::fs::write(&file_path, "base").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "base"])
+        .success();
+    std::fs::write(&file_path, "first").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "first"])
+        .success();
+    std::fs::write(&file_path, "second").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "second"])
+        .success();
+    test_env
+        .run_jj_in(
+            &repo_path,
+            ["bookmark", "create", "-r", "description(first)", "my-stack"],
+        )
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "description(base)", "-m", "main"])
+        .success();
+    std::fs::write(&file_path, "main").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "create", "-r@", "main"])
+        .success();
+
+    test_env
+        .run_jj_in(&repo_path, ["rebase", "-s", "my-stack", "-d", "main"])
+        .success();
+    test_env.run_jj_in(&repo_path, ["new", "main"]).success();
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r"
+    @  yostqsxwqrlt
+    │ ×  kkmpptxzrspx conflict second
+    │ ×  rlvkpnrzqnoo my-stack conflict first
+    ├─╯
+    ○  royxmykxtrkr main main
+    ○  qpvuntsmwlqt base
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["next", "--conflict", "--edit"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy now at: rlvkpnrz 3ba8902c my-stack | (conflict) first
+    Parent commit      : royxmykx 37d7fad3 main | main
+    Added 0 files, modified 1 files, removed 0 files
+    Warning: There are unresolved conflicts at these paths:
+    content.txt    2-sided conflict
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r"
+    ×  kkmpptxzrspx conflict second
+    @  rlvkpnrzqnoo my-stack conflict first
+    ○  royxmykxtrkr main main
+    ○  qpvuntsmwlqt base
+    ◆  zzzzzzzzzzzz
+    [EOF]
+    ");
+
+    // Resolving the conflict also resolves the one in the descendant
+    std::fs::write(&file_path, "first").unwrap();
+    let output = test_env.run_jj_in(&repo_path, ["next", "--conflict", "--edit"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 1 descendant commits onto updated working copy
+    Error: The working copy has no descendants with conflicts
+    Hint: Working copy: rlvkpnrz 21b37723 my-stack | first
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_next_conflict_head() {
     // When editing a head with conflicts, `jj next --conflict [--edit]` errors out.
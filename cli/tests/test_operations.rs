@@ -220,6 +220,16 @@ fn test_op_log_no_graph() {
     000000000000 root()
     [EOF]
     ");
+
+    let output = test_env
+        .run_jj_in(
+            &repo_path,
+            ["op", "log", "-z", "--color=always", "-T", "id.short()"],
+        )
+        .success();
+    insta::assert_debug_snapshot!(output.stdout.normalized(), @r#"
+    "eac759b9ab75\0000000000000\0"
+    "#);
 }
 
 #[test]
@@ -319,6 +329,112 @@ fn test_op_log_reversed() {
     ");
 }
 
+#[test]
+fn test_op_log_paths() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    let template = r#"id.short() ++ " " ++ description ++ "\n""#;
+
+    std::fs::write(repo_path.join("file1"), "a\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "add file1"])
+        .success();
+    std::fs::write(repo_path.join("file2"), "a\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "add file2"])
+        .success();
+    std::fs::write(repo_path.join("file1"), "b\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["squash", "--into=@-", "-u"])
+        .success();
+
+    // Without filter
+    let output = test_env.run_jj_in(&repo_path, ["op", "log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    @  a48d30f825b3 squash commits into ae85d9d1a195fe68470e887b20f14a5c7e4cb5b4
+    ○  463b641a3cde snapshot working copy
+    ○  70a099d3c0b0 describe commit 2f7ea06863c0fb2cf6384331612b58b0cbc8ff18
+    ○  d77042038365 snapshot working copy
+    ○  d3907fdd5bec commit b739eb4695e92fa2c403d65fddc8354ba753821f
+    ○  e3e82ef6f029 snapshot working copy
+    ○  eac759b9ab75 add workspace 'default'
+    ○  000000000000
+    [EOF]
+    ");
+
+    // Snapshots and rewrites which modified file1
+    let output = test_env.run_jj_in(&repo_path, ["op", "log", "-T", template, "--paths=file1"]);
+    insta::assert_snapshot!(output, @r"
+    @  a48d30f825b3 squash commits into ae85d9d1a195fe68470e887b20f14a5c7e4cb5b4
+    ○  463b641a3cde snapshot working copy
+    ○  e3e82ef6f029 snapshot working copy
+    [EOF]
+    ");
+
+    // Combined with --limit and --no-graph
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "op",
+            "log",
+            "-T",
+            template,
+            "--paths=file2",
+            "--no-graph",
+            "-n1",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    a48d30f825b3 squash commits into ae85d9d1a195fe68470e887b20f14a5c7e4cb5b4
+    [EOF]
+    ");
+
+    // Reversed graph
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["op", "log", "-T", template, "--paths=file2", "--reversed"],
+    );
+    insta::assert_snapshot!(output, @r"
+    ○  d77042038365 snapshot working copy
+    @  a48d30f825b3 squash commits into ae85d9d1a195fe68470e887b20f14a5c7e4cb5b4
+    [EOF]
+    ");
+
+    // No operation touched the path
+    let output = test_env.run_jj_in(&repo_path, ["op", "log", "-T", template, "--paths=file3"]);
+    insta::assert_snapshot!(output, @"");
+}
+
+#[test]
+fn test_op_log_paths_distant_ancestor() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    let template = r#"id.short() ++ " " ++ description ++ "\n""#;
+
+    std::fs::write(repo_path.join("file1"), "a\n").unwrap();
+    test_env.run_jj_in(&repo_path, ["status"]).success();
+    for i in 0..100 {
+        test_env
+            .run_jj_in(&repo_path, ["describe", "-m", &format!("message {i}")])
+            .success();
+    }
+    std::fs::write(repo_path.join("file1"), "b\n").unwrap();
+    test_env.run_jj_in(&repo_path, ["status"]).success();
+
+    // The matching ancestor is too far away to be connected
+    let output = test_env.run_jj_in(&repo_path, ["op", "log", "-T", template, "--paths=file1"]);
+    insta::assert_snapshot!(output, @r"
+    @  4b249048dc45 snapshot working copy
+    │
+    ~
+
+    ○  78f148d38ba2 snapshot working copy
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_op_log_no_graph_null_terminated() {
     let test_env = TestEnvironment::default();
@@ -1501,7 +1617,7 @@ fn test_op_diff() {
     ");
 
     // Test pushing to Git remote.
-    let output = test_env.run_jj_in(&repo_path, ["git", "push", "--tracked"]);
+    let output = test_env.run_jj_in(&repo_path, ["git", "push", "--tracked", "--allow-delete"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
@@ -1510,12 +1626,15 @@ fn test_op_diff() {
     Warning: The working-copy commit in workspace 'default' became immutable, so a new commit has been created on top of it.
     Working copy now at: oupztwtk fe3ad088 (empty) (no description set)
     Parent commit      : wvuyspvk fefb1e17 bookmark-1 | (empty) new commit
+    Hint: To restore the deleted remote bookmarks, recreate and push them:
+      jj bookmark create -r e1a239a57eb1 bookmark-2
+      jj git push --remote origin --allow-new --bookmark bookmark-2
     [EOF]
     ");
     let output = test_env.run_jj_in(&repo_path, ["op", "diff"]);
     insta::assert_snapshot!(output, @r"
     From operation: 9969a6088fd3 (2001-02-03 08:05:32) delete bookmark bookmark-2
-      To operation: ff305cfe0aca (2001-02-03 08:05:34) push all tracked bookmarks to git remote origin
+      To operation: 77456f0d98df (2001-02-03 08:05:34) push all tracked bookmarks to git remote origin
 
     Changed commits:
     ○  + oupztwtk fe3ad088 (empty) (no description set)
@@ -2198,7 +2317,7 @@ fn test_op_show() {
     ");
 
     // Test pushing to Git remote.
-    let output = test_env.run_jj_in(&repo_path, ["git", "push", "--tracked"]);
+    let output = test_env.run_jj_in(&repo_path, ["git", "push", "--tracked", "--allow-delete"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     Changes to push to origin:
@@ -2207,13 +2326,16 @@ fn test_op_show() {
     Warning: The working-copy commit in workspace 'default' became immutable, so a new commit has been created on top of it.
     Working copy now at: pzsxstzt 91310b51 (empty) (no description set)
     Parent commit      : xznxytkn 560df364 bookmark-1 | (empty) new commit
+    Hint: To restore the deleted remote bookmarks, recreate and push them:
+      jj bookmark create -r e1a239a57eb1 bookmark-2
+      jj git push --remote origin --allow-new --bookmark bookmark-2
     [EOF]
     ");
     let output = test_env.run_jj_in(&repo_path, ["op", "show"]);
     insta::assert_snapshot!(output, @r"
-    2a7821341a99 test-username@host.example.com 2001-02-03 04:05:30.000 +07:00 - 2001-02-03 04:05:30.000 +07:00
+    a4bfe127273f test-username@host.example.com 2001-02-03 04:05:30.000 +07:00 - 2001-02-03 04:05:30.000 +07:00
     push all tracked bookmarks to git remote origin
-    args: jj git push --tracked
+    args: jj git push --tracked --allow-delete
 
     Changed commits:
     ○  + pzsxstzt 91310b51 (empty) (no description set)
@@ -2408,6 +2530,298 @@ fn test_op_show_patch() {
     ");
 }
 
+#[test]
+fn test_op_restore_selective() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "first"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "create", "-r@-", "a", "b"])
+        .success();
+    let output = test_env.run_jj_in(&repo_path, ["op", "log", "--no-graph", "-n1", "-T", "id"]);
+    let op_id = output.stdout.raw().to_owned();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "second"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "move", "--to=@-", "a", "b"])
+        .success();
+
+    let output = test_env.run_jj_in(&repo_path, ["op", "restore", &op_id, "--bookmark", "a"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Restored to operation: 67636d2f9090 (2001-02-03 08:05:09) create bookmark a, b pointing to commit fa15625b4a986997697639dfc2844138900c79f2
+    [EOF]
+    ");
+    let template = r#"description.first_line() ++ " " ++ bookmarks"#;
+    let output = test_env.run_jj_in(&repo_path, ["log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    @
+    ○  second b
+    ○  first a
+    ◆
+    [EOF]
+    ");
+
+    // Move the working copy back, leaving bookmarks alone
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["op", "restore", &op_id, "--workspace", "default"],
+    );
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Restored to operation: 67636d2f9090 (2001-02-03 08:05:09) create bookmark a, b pointing to commit fa15625b4a986997697639dfc2844138900c79f2
+    Working copy now at: yostqsxw a33ee5c2 (empty) (no description set)
+    Parent commit      : qpvuntsm fa15625b a | (empty) first
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["log", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    @
+    │ ○  second b
+    ├─╯
+    ○  first a
+    ◆
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["op", "restore", &op_id, "--bookmark", "c"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No matching bookmarks for names: c
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["op", "restore", &op_id, "--workspace", "other"],
+    );
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No working-copy commit for workspace other at the operation
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["op", "restore", &op_id, "--bookmark", "a", "--what", "repo"],
+    );
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--bookmark <NAME>' cannot be used with '--what <WHAT>'
+
+    Usage: jj operation restore --bookmark <NAME> <OPERATION>
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
+#[test]
+fn test_op_log_patch_lost_working_copy_change() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    // The edit is discarded right after being snapshotted
+    std::fs::write(repo_path.join("file"), "precious\n").unwrap();
+    test_env.run_jj_in(&repo_path, ["restore"]).success();
+
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "op",
+            "log",
+            "-p",
+            "--git",
+            "-n2",
+            "--no-graph",
+            "-T",
+            "description ++ \"\\n\"",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    restore into commit de33d4d8b2074f0355f9fea56230d40411e8b1c4
+
+    Changed commits:
+    + qpvuntsm 6acbecf3 (empty) (no description set)
+    - qpvuntsm hidden de33d4d8 (no description set)
+    diff --git a/file b/file
+    deleted file mode 100644
+    index fbbdf22a34..0000000000
+    --- a/file
+    +++ /dev/null
+    @@ -1,1 +0,0 @@
+    -precious
+    snapshot working copy
+
+    Changed commits:
+    + qpvuntsm de33d4d8 (no description set)
+    - qpvuntsm hidden 230dd059 (empty) (no description set)
+    diff --git a/file b/file
+    new file mode 100644
+    index 0000000000..fbbdf22a34
+    --- /dev/null
+    +++ b/file
+    @@ -0,0 +1,1 @@
+    +precious
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_op_push_pull() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo1"]).success();
+    let repo1_path = test_env.env_root().join("repo1");
+    let repo2_path = test_env.env_root().join("repo2");
+    copy_directory(&repo1_path, &repo2_path);
+    std::fs::write(repo1_path.join("file"), "a\n").unwrap();
+    test_env
+        .run_jj_in(&repo1_path, ["commit", "-m", "first"])
+        .success();
+    test_env
+        .run_jj_in(&repo1_path, ["bookmark", "create", "-r@-", "main"])
+        .success();
+
+    let output = test_env.run_jj_in(&repo1_path, ["op", "push", "../repo2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Pushed 3 operations and 3 commits.
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo1_path, ["op", "push", "../repo2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Nothing changed.
+    [EOF]
+    ");
+
+    // The working-copy commit of the "default" workspace is shared
+    let output = test_env.run_jj_in(&repo2_path, ["workspace", "update-stale"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy now at: rlvkpnrz 41fb3659 (empty) (no description set)
+    Added 1 files, modified 0 files, removed 0 files
+    Updated working copy to fresh commit 41fb365969bd
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo2_path, ["log", "-T", "description ++ bookmarks"]);
+    insta::assert_snapshot!(output, @r"
+    @
+    ○  first
+    │  main
+    ◆
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo2_path, ["file", "show", "-r", "main", "file"]);
+    insta::assert_snapshot!(output, @r"
+    a
+    [EOF]
+    ");
+
+    test_env
+        .run_jj_in(
+            &repo2_path,
+            ["describe", "-r", "main", "-m", "first (edited)"],
+        )
+        .success();
+    let output = test_env.run_jj_in(&repo1_path, ["op", "pull", "../repo2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Pulled 1 operation and 2 commits.
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo1_path, ["log", "-T", "description ++ bookmarks"]);
+    insta::assert_snapshot!(output, @r"
+    @
+    ○  first (edited)
+    │  main
+    ◆
+    [EOF]
+    ");
+
+    // The op logs of repos that only share the root operation are merged
+    test_env.run_jj_in(".", ["git", "init", "repo3"]).success();
+    let repo3_path = test_env.env_root().join("repo3");
+    std::fs::write(repo3_path.join("other"), "b\n").unwrap();
+    test_env
+        .run_jj_in(&repo3_path, ["commit", "-m", "unrelated"])
+        .success();
+    let output = test_env.run_jj_in(&repo1_path, ["op", "pull", "../repo3"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Pulled 3 operations and 4 commits.
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo1_path, ["log", "-T", "description ++ bookmarks"]);
+    insta::assert_snapshot!(output, @r"
+    @
+    ○  first (edited)
+    │  main
+    │ ○
+    │ ○  unrelated
+    ├─╯
+    ◆
+    [EOF]
+    ------- stderr -------
+    Concurrent modification detected, resolving automatically.
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo1_path, ["op", "push", "../repo3"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Pushed 6 operations and 6 commits.
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(
+        &repo3_path,
+        ["op", "log", "--ignore-working-copy", "-T", "description"],
+    );
+    insta::assert_snapshot!(output, @r"
+    @    reconcile divergent operations
+    ├─╮
+    ○ │  describe commit 2d4fa21a09467d5b1046c5427720b46d296485d3
+    ○ │  create bookmark main pointing to commit 2d4fa21a09467d5b1046c5427720b46d296485d3
+    ○ │  commit 6b1027d2770cd0a39c468e525e52bf8c47e1464a
+    ○ │  snapshot working copy
+    ○ │  add workspace 'default'
+    │ ○  commit 1a4b0de01504f5b1d0929c77d7a3f2c6e031e61d
+    │ ○  snapshot working copy
+    │ ○  add workspace 'default'
+    ├─╯
+    ○
+    [EOF]
+    ");
+
+    // Unrelated directories are rejected
+    std::fs::create_dir(test_env.env_root().join("not-a-repo")).unwrap();
+    let output = test_env.run_jj_in(&repo1_path, ["op", "push", "../not-a-repo"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Error: There is no jj repo in "$TEST_ENV/repo1/../not-a-repo"
+    [EOF]
+    [exit status: 1]
+    "#);
+}
+
+fn copy_directory(src: &Path, dst: &Path) {
+    std::fs::create_dir(dst).ok();
+    for entry in std::fs::read_dir(src).unwrap() {
+        let child_src = entry.unwrap().path();
+        let child_dst = dst.join(child_src.file_name().unwrap());
+        if child_src.is_dir() {
+            copy_directory(&child_src, &child_dst);
+        } else {
+            std::fs::copy(&child_src, &child_dst).unwrap();
+        }
+    }
+}
+
 fn init_bare_git_repo(git_repo_path: &Path) -> gix::Repository {
     let git_repo = git::init_bare(git_repo_path);
     let commit_result = git::add_commit(
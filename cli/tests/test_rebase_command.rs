@@ -14,6 +14,8 @@
 
 use std::path::Path;
 
+use crate::common::fake_editor_path;
+use crate::common::to_toml_value;
 use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
 
@@ -49,9 +51,9 @@ fn test_rebase_invalid() {
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
     error: the following required arguments were not provided:
-      <--destination <REVSETS>|--insert-after <REVSETS>|--insert-before <REVSETS>>
+      <--destination <REVSETS>|--insert-after <REVSETS>|--insert-before <REVSETS>|--continue>
 
-    Usage: jj rebase <--destination <REVSETS>|--insert-after <REVSETS>|--insert-before <REVSETS>>
+    Usage: jj rebase <--destination <REVSETS>|--insert-after <REVSETS>|--insert-before <REVSETS>|--continue>
 
     For more information, try '--help'.
     [EOF]
@@ -64,7 +66,7 @@ fn test_rebase_invalid() {
     ------- stderr -------
     error: the argument '--revisions <REVSETS>' cannot be used with '--source <REVSETS>'
 
-    Usage: jj rebase --revisions <REVSETS> <--destination <REVSETS>|--insert-after <REVSETS>|--insert-before <REVSETS>>
+    Usage: jj rebase --revisions <REVSETS> <--destination <REVSETS>|--insert-after <REVSETS>|--insert-before <REVSETS>|--continue>
 
     For more information, try '--help'.
     [EOF]
@@ -77,7 +79,7 @@ fn test_rebase_invalid() {
     ------- stderr -------
     error: the argument '--branch <REVSETS>' cannot be used with '--source <REVSETS>'
 
-    Usage: jj rebase --branch <REVSETS> <--destination <REVSETS>|--insert-after <REVSETS>|--insert-before <REVSETS>>
+    Usage: jj rebase --branch <REVSETS> <--destination <REVSETS>|--insert-after <REVSETS>|--insert-before <REVSETS>|--continue>
 
     For more information, try '--help'.
     [EOF]
@@ -90,7 +92,7 @@ fn test_rebase_invalid() {
     ------- stderr -------
     error: the argument '--destination <REVSETS>' cannot be used with '--insert-after <REVSETS>'
 
-    Usage: jj rebase --revisions <REVSETS> <--destination <REVSETS>|--insert-after <REVSETS>|--insert-before <REVSETS>>
+    Usage: jj rebase --revisions <REVSETS> <--destination <REVSETS>|--insert-after <REVSETS>|--insert-before <REVSETS>|--continue>
 
     For more information, try '--help'.
     [EOF]
@@ -106,7 +108,7 @@ fn test_rebase_invalid() {
     ------- stderr -------
     error: the argument '--destination <REVSETS>' cannot be used with '--insert-before <REVSETS>'
 
-    Usage: jj rebase --revisions <REVSETS> <--destination <REVSETS>|--insert-after <REVSETS>|--insert-before <REVSETS>>
+    Usage: jj rebase --revisions <REVSETS> <--destination <REVSETS>|--insert-after <REVSETS>|--insert-before <REVSETS>|--continue>
 
     For more information, try '--help'.
     [EOF]
@@ -2885,6 +2887,190 @@ fn test_rebase_after_before() {
     ");
 }
 
+#[test]
+fn test_rebase_with_merge_strategies() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env.add_config(
+        r#"
+        [merge.strategies]
+        "*.md" = "union"
+        "*.lock" = "theirs"
+        "#,
+    );
+
+    std::fs::write(repo_path.join("CHANGELOG.md"), "* base\n").unwrap();
+    std::fs::write(repo_path.join("deps.lock"), "version = 1\n").unwrap();
+    std::fs::write(repo_path.join("src.txt"), "base\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "base"])
+        .success();
+    std::fs::write(repo_path.join("CHANGELOG.md"), "* base\n* main\n").unwrap();
+    std::fs::write(repo_path.join("deps.lock"), "version = 2\n").unwrap();
+    std::fs::write(repo_path.join("src.txt"), "main\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "main"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "description(base)", "-m", "feature"])
+        .success();
+    std::fs::write(repo_path.join("CHANGELOG.md"), "* base\n* feature\n").unwrap();
+    std::fs::write(repo_path.join("deps.lock"), "version = 3\n").unwrap();
+    std::fs::write(repo_path.join("src.txt"), "feature\n").unwrap();
+
+    // Only the file without a strategy is left conflicted
+    let output = test_env.run_jj_in(&repo_path, ["rebase", "-d", "description(main)"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 1 commits onto destination
+    Working copy now at: zsuskuln ae491161 (conflict) feature
+    Parent commit      : rlvkpnrz 29d2175e main
+    Added 0 files, modified 2 files, removed 0 files
+    Warning: There are unresolved conflicts at these paths:
+    src.txt    2-sided conflict
+    New conflicts appeared in these commits:
+      zsuskuln ae491161 (conflict) feature
+    Hint: To resolve the conflicts, start by updating to it:
+      jj new zsuskuln
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you may want to inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["file", "show", "CHANGELOG.md", "deps.lock"]);
+    insta::assert_snapshot!(output, @r"
+    * base
+    * main
+    * feature
+    version = 3
+    [EOF]
+    ");
+
+    // Invalid strategies are reported
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "rebase",
+            "-d",
+            "description(base)",
+            "--config=merge.strategies.'*.md'=\"mine\"",
+        ],
+    );
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Config error: Invalid type or value for merge.strategies."*.md"
+    Caused by: Expected one of "ours", "theirs", or "union"
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    "#);
+}
+
+#[test]
+fn test_rebase_with_merge_driver() {
+    let mut test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    let edit_script = test_env.set_up_fake_editor();
+    test_env.add_config(format!(
+        "merge.strategies.'*.lock' = {{ command = [{}, \"$output\"] }}",
+        to_toml_value(fake_editor_path())
+    ));
+
+    std::fs::write(repo_path.join("deps.lock"), "version = 1\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "base"])
+        .success();
+    std::fs::write(repo_path.join("deps.lock"), "version = 2\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "main"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "description(base)", "-m", "feature"])
+        .success();
+    std::fs::write(repo_path.join("deps.lock"), "version = 3\n").unwrap();
+
+    // A failing driver leaves the conflict
+    std::fs::write(&edit_script, "fail").unwrap();
+    let output = test_env.run_jj_in(&repo_path, ["rebase", "-d", "description(main)"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 1 commits onto destination
+    Working copy now at: zsuskuln 5461f5da (conflict) feature
+    Parent commit      : rlvkpnrz 14e5225d main
+    Added 0 files, modified 1 files, removed 0 files
+    Warning: There are unresolved conflicts at these paths:
+    deps.lock    2-sided conflict
+    New conflicts appeared in these commits:
+      zsuskuln 5461f5da (conflict) feature
+    Hint: To resolve the conflicts, start by updating to it:
+      jj new zsuskuln
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you may want to inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+    test_env.run_jj_in(&repo_path, ["undo"]).success();
+
+    // The output of a successful driver is taken as the resolution
+    std::fs::write(&edit_script, "write\nversion = 4\n").unwrap();
+    let output = test_env.run_jj_in(&repo_path, ["rebase", "-d", "description(main)"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 1 commits onto destination
+    Working copy now at: zsuskuln 1f3bc5d5 feature
+    Parent commit      : rlvkpnrz 14e5225d main
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["file", "show", "deps.lock"]);
+    insta::assert_snapshot!(output, @r"
+    version = 4
+    [EOF]
+    ");
+
+    // A driver that can't be run is an error
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "rebase",
+            "-r@",
+            "-d",
+            "description(base)",
+            "--config=merge.strategies.'*.lock'.command=['nonexistent-merge-driver']",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Failed to run the merge driver for deps.lock
+    Caused by:
+    1: Error executing 'nonexistent-merge-driver' (run with --debug to see the exact invocation)
+    2: No such file or directory (os error 2)
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // A driver without a command is reported
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "rebase",
+            "-d",
+            "description(base)",
+            "--config=merge.strategies.'*.lock'.command=[]",
+        ],
+    );
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Config error: Invalid type or value for merge.strategies."*.lock"
+    Caused by: The merge driver command is empty
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    "#);
+}
+
 #[test]
 fn test_rebase_skip_emptied() {
     let test_env = TestEnvironment::default();
@@ -3049,6 +3235,206 @@ fn test_rebase_skip_emptied_descendants() {
     ");
 }
 
+#[test]
+fn test_rebase_skip_applied() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    create_commit(&test_env, &repo_path, "a", &[]);
+    create_commit(&test_env, &repo_path, "b", &["a"]);
+    create_commit(&test_env, &repo_path, "c", &["a"]);
+    create_commit(&test_env, &repo_path, "d", &["c"]);
+    test_env
+        .run_jj_in(&repo_path, ["duplicate", "c", "-d", "b"])
+        .success();
+    let template = r#"separate(" ",
+      description.first_line(),
+      if(duplicated_from, "from " ++ duplicated_from.short()),
+    )"#;
+    insta::assert_snapshot!(test_env.run_jj_in(&repo_path, ["log", "-T", template]), @r"
+    @  d
+    ○  c
+    │ ○  c from royxmykxtrkr
+    │ ○  b
+    ├─╯
+    ○  a
+    ◆
+    [EOF]
+    ");
+
+    // The duplicate of c is already in b's descendants, so c is abandoned
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "rebase",
+            "-s",
+            "c",
+            "-d",
+            "description(c) & b::",
+            "--skip-applied",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 1 commits onto destination
+    Abandoned 1 commits that were already applied in destination
+    Working copy now at: vruxwmqv 8f310995 d | d
+    Parent commit      : znkkpsqq ce5f4eeb c | c
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    ");
+    insta::assert_snapshot!(test_env.run_jj_in(&repo_path, ["log", "-T", template]), @r"
+    @  d
+    ○  c from royxmykxtrkr
+    ○  b
+    ○  a
+    ◆
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_rebase_pause_on_conflict() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    // b and d modify the same file as e, c doesn't
+    for (name, parent) in [("a", "root()"), ("b", "a"), ("c", "b"), ("d", "c")] {
+        create_commit(&test_env, &repo_path, name, &[parent]);
+        if name != "c" {
+            std::fs::write(repo_path.join("file"), format!("{name}\n")).unwrap();
+        }
+    }
+    create_commit(&test_env, &repo_path, "e", &["a"]);
+    std::fs::write(repo_path.join("file"), "e\n").unwrap();
+    test_env.run_jj_in(&repo_path, ["new", "d"]).success();
+
+    let output = test_env.run_jj_in(&repo_path, ["rebase", "--continue"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No rebase is paused in this workspace
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["rebase", "-s", "b", "-d", "e", "--pause-on-conflict"],
+    );
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 4 commits onto destination
+    Working copy now at: zsuskuln 6a6b5b4e b | (conflict) b
+    Parent commit      : znkkpsqq 91c6e3d9 e | e
+    Added 1 files, modified 1 files, removed 2 files
+    Warning: There are unresolved conflicts at these paths:
+    file    2-sided conflict
+    New conflicts appeared in these commits:
+      vruxwmqv 543cd9cb d | (conflict) d
+      royxmykx 583c8208 c | (conflict) c
+      zsuskuln 6a6b5b4e b | (conflict) b
+    Hint: To resolve the conflicts, start by updating to the first one:
+      jj new zsuskuln
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you may want to inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    Rebase paused at a conflicted commit. Resolve the conflicts, then run `jj rebase --continue`.
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r"
+    ×  d: c
+    ×  c: b
+    @  b: e
+    ○  e: a
+    ○  a
+    ◆
+    [EOF]
+    ");
+
+    // Other rebases can't be started while paused
+    let output = test_env.run_jj_in(&repo_path, ["rebase", "-r", "d", "-d", "a"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: A rebase is paused at a conflicted revision
+    Hint: Run `jj rebase --continue` once the conflicts are resolved, or use `jj undo` or `jj op restore` to go back to before the rebase.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Undoing the rebase discards the pause
+    test_env.run_jj_in(&repo_path, ["undo"]).success();
+    let output = test_env.run_jj_in(&repo_path, ["rebase", "--continue"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No rebase is paused in this workspace
+    [EOF]
+    [exit status: 1]
+    ");
+    test_env
+        .run_jj_in(
+            &repo_path,
+            ["rebase", "-s", "b", "-d", "e", "--pause-on-conflict"],
+        )
+        .success();
+
+    // The conflicts have to be resolved first
+    let output = test_env.run_jj_in(&repo_path, ["rebase", "--continue"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Commit 05fe8f2c0070 still has conflicts
+    Hint: Resolve the conflicts, then run `jj rebase --continue` again.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Resolving b also resolves c, so the rebase stops at d next
+    std::fs::write(repo_path.join("file"), "e\n").unwrap();
+    let output = test_env.run_jj_in(&repo_path, ["rebase", "--continue"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 2 descendant commits onto updated working copy
+    Working copy now at: vruxwmqv a08553ad d | (conflict) d
+    Parent commit      : royxmykx 56704436 c | c
+    Added 2 files, modified 1 files, removed 0 files
+    Warning: There are unresolved conflicts at these paths:
+    file    2-sided conflict
+    Rebase paused at a conflicted commit. Resolve the conflicts, then run `jj rebase --continue`.
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r"
+    @  d: c
+    ○  c: b
+    ○  b: e
+    ○  e: a
+    ○  a
+    ◆
+    [EOF]
+    ");
+
+    // Once nothing is conflicted, the working copy is restored
+    std::fs::write(repo_path.join("file"), "d\n").unwrap();
+    let output = test_env.run_jj_in(&repo_path, ["rebase", "--continue"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy now at: nlrtlrxv 4eb9cd5a (empty) (no description set)
+    Parent commit      : vruxwmqv 77f497af d | d
+    Rebase finished
+    [EOF]
+    ");
+    insta::assert_snapshot!(get_log_output(&test_env, &repo_path), @r"
+    @  : d
+    ○  d: c
+    ○  c: b
+    ○  b: e
+    ○  e: a
+    ○  a
+    ◆
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_rebase_skip_if_on_destination() {
     let test_env = TestEnvironment::default();
@@ -77,15 +77,15 @@ fn test_resolution() {
     ");
     insta::assert_snapshot!(
     std::fs::read_to_string(repo_path.join("file")).unwrap()
-        , @r"
+        , @r#"
     <<<<<<< Conflict 1 of 1
-    %%%%%%% Changes from base to side #1
+    %%%%%%% Changes from base (rlvkpnrz "base") to side #1 (zsuskuln "a")
     -base
     +a
-    +++++++ Contents of side #2
+    +++++++ Contents of side #2 (royxmykx "b")
     b
     >>>>>>> Conflict 1 of 1 ends
-    ");
+    "#);
 
     let editor_script = test_env.set_up_fake_editor();
     // Check that output file starts out empty and resolve the conflict
@@ -585,6 +585,63 @@ fn check_resolve_produces_input_file(
     }
 }
 
+#[test]
+fn test_resolve_list_json() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    create_commit(
+        &test_env,
+        &repo_path,
+        "base",
+        &[],
+        &[("content", "base\n"), ("deleted", "base\n")],
+    );
+    create_commit(
+        &test_env,
+        &repo_path,
+        "a",
+        &["base"],
+        &[("content", "a\n"), ("deleted", "a\n")],
+    );
+    create_commit(&test_env, &repo_path, "b", &["base"], &[("content", "b\n")]);
+    std::fs::remove_file(repo_path.join("deleted")).unwrap();
+    create_commit(&test_env, &repo_path, "conflict", &["a", "b"], &[]);
+    insta::assert_snapshot!(test_env.run_jj_in(&repo_path, ["resolve", "--list"]), @r"
+    content    2-sided conflict
+    deleted    2-sided conflict including 1 deletion
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["resolve", "--list", "--json"]);
+    insta::assert_snapshot!(output, @r#"
+    {"commit_id":"4ef6633087178bfda780d1243e76900a58119cd3","conflicts":[{"bases":[{"change_id":"rlvkpnrzqnoowoytxnquwvuryrwnrmlp","commit_id":"f3f7148d25097aaba316be8754451dae7a6ba1a3"}],"num_sides":2,"path":"content","sides":[{"change_id":"zsuskulnrvyrovkzqrwmxqlsskqntxvp","commit_id":"bf379816d87c26309dc6fec2249a0c4519de4d12"},{"change_id":"royxmykxtrkrqppotnrvutxlvrvqyxmy","commit_id":"0dbffcddbacd0c267d1afad0ad07744df7fe651d"}],"type":"content"},{"bases":[{"change_id":"rlvkpnrzqnoowoytxnquwvuryrwnrmlp","commit_id":"f3f7148d25097aaba316be8754451dae7a6ba1a3"}],"num_sides":2,"path":"deleted","sides":[{"change_id":"zsuskulnrvyrovkzqrwmxqlsskqntxvp","commit_id":"bf379816d87c26309dc6fec2249a0c4519de4d12"},{"change_id":"royxmykxtrkrqppotnrvutxlvrvqyxmy","commit_id":"0dbffcddbacd0c267d1afad0ad07744df7fe651d"}],"type":"delete"}]}
+    [EOF]
+    "#);
+
+    // The sides of inherited conflicts can't be attributed to commits
+    test_env.run_jj_in(&repo_path, ["new"]).success();
+    let output = test_env.run_jj_in(&repo_path, ["resolve", "--list", "--json", "content"]);
+    insta::assert_snapshot!(output, @r#"
+    {"commit_id":"3057737a0803c1c5c563fac21f33290745eb3743","conflicts":[{"bases":[null],"num_sides":2,"path":"content","sides":[null,null],"type":"content"}]}
+    [EOF]
+    "#);
+
+    let output = test_env.run_jj_in(&repo_path, ["resolve", "--json"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the following required arguments were not provided:
+      --list
+
+    Usage: jj resolve --list --json [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_normal_conflict_input_files() {
     let mut test_env = TestEnvironment::default();
@@ -612,15 +669,15 @@ fn test_normal_conflict_input_files() {
     ");
     insta::assert_snapshot!(
     std::fs::read_to_string(repo_path.join("file")).unwrap()
-        , @r"
+        , @r#"
     <<<<<<< Conflict 1 of 1
-    %%%%%%% Changes from base to side #1
+    %%%%%%% Changes from base (rlvkpnrz "base") to side #1 (zsuskuln "a")
     -base
     +a
-    +++++++ Contents of side #2
+    +++++++ Contents of side #2 (royxmykx "b")
     b
     >>>>>>> Conflict 1 of 1 ends
-    ");
+    "#);
 
     check_resolve_produces_input_file(&mut test_env, &repo_path, "file", "base", "base\n");
     check_resolve_produces_input_file(&mut test_env, &repo_path, "file", "left", "a\n");
@@ -654,14 +711,14 @@ fn test_baseless_conflict_input_files() {
     ");
     insta::assert_snapshot!(
     std::fs::read_to_string(repo_path.join("file")).unwrap()
-        , @r"
+        , @r#"
     <<<<<<< Conflict 1 of 1
-    %%%%%%% Changes from base to side #1
+    %%%%%%% Changes from base (rlvkpnrz "base") to side #1 (zsuskuln "a")
     +a
-    +++++++ Contents of side #2
+    +++++++ Contents of side #2 (royxmykx "b")
     b
     >>>>>>> Conflict 1 of 1 ends
-    ");
+    "#);
 
     check_resolve_produces_input_file(&mut test_env, &repo_path, "file", "base", "");
     check_resolve_produces_input_file(&mut test_env, &repo_path, "file", "left", "a\n");
@@ -860,14 +917,14 @@ fn test_edit_delete_conflict_input_files() {
     ");
     insta::assert_snapshot!(
     std::fs::read_to_string(repo_path.join("file")).unwrap()
-        , @r"
+        , @r#"
     <<<<<<< Conflict 1 of 1
-    +++++++ Contents of side #1
+    +++++++ Contents of side #1 (zsuskuln "a")
     a
-    %%%%%%% Changes from base to side #2
+    %%%%%%% Changes from base (rlvkpnrz "base") to side #2 (royxmykx "b")
     -base
     >>>>>>> Conflict 1 of 1 ends
-    ");
+    "#);
 
     check_resolve_produces_input_file(&mut test_env, &repo_path, "file", "base", "base\n");
     check_resolve_produces_input_file(&mut test_env, &repo_path, "file", "left", "a\n");
@@ -1023,26 +1080,26 @@ fn test_resolve_conflicts_with_executable() {
     [EOF]
     ");
     insta::assert_snapshot!(
-        std::fs::read_to_string(repo_path.join("file1")).unwrap(), @r"
+        std::fs::read_to_string(repo_path.join("file1")).unwrap(), @r#"
     <<<<<<< Conflict 1 of 1
-    %%%%%%% Changes from base to side #1
+    %%%%%%% Changes from base (rlvkpnrz "base") to side #1 (mzvwutvl "a")
     -base1
     +a1
-    +++++++ Contents of side #2
+    +++++++ Contents of side #2 (yqosqzyt "b")
     b1
     >>>>>>> Conflict 1 of 1 ends
-    "
+    "#
     );
     insta::assert_snapshot!(
-        std::fs::read_to_string(repo_path.join("file2")).unwrap(), @r"
+        std::fs::read_to_string(repo_path.join("file2")).unwrap(), @r#"
     <<<<<<< Conflict 1 of 1
-    %%%%%%% Changes from base to side #1
+    %%%%%%% Changes from base (rlvkpnrz "base") to side #1 (mzvwutvl "a")
     -base2
     +a2
-    +++++++ Contents of side #2
+    +++++++ Contents of side #2 (yqosqzyt "b")
     b2
     >>>>>>> Conflict 1 of 1 ends
-    "
+    "#
     );
     let editor_script = test_env.set_up_fake_editor();
 
@@ -1171,16 +1228,16 @@ fn test_resolve_long_conflict_markers() {
     [EOF]
     ");
     insta::assert_snapshot!(
-        std::fs::read_to_string(repo_path.join("file")).unwrap(), @r"
+        std::fs::read_to_string(repo_path.join("file")).unwrap(), @r#"
     <<<<<<<<<<< Conflict 1 of 1
-    +++++++++++ Contents of side #1
+    +++++++++++ Contents of side #1 (zsuskuln "a")
     <<<<<<< a
-    ----------- Contents of base
+    ----------- Contents of base (rlvkpnrz "base")
     ======= base
-    +++++++++++ Contents of side #2
+    +++++++++++ Contents of side #2 (royxmykx "b")
     >>>>>>> b
     >>>>>>>>>>> Conflict 1 of 1 ends
-    "
+    "#
     );
     let editor_script = test_env.set_up_fake_editor();
     // Allow signaling that conflict markers were produced even if not editing
@@ -1459,25 +1516,25 @@ fn test_multiple_conflicts() {
     ");
     insta::assert_snapshot!(
     std::fs::read_to_string(
-        repo_path.join("this_file_has_a_very_long_name_to_test_padding")).unwrap(), @r"
+        repo_path.join("this_file_has_a_very_long_name_to_test_padding")).unwrap(), @r#"
     <<<<<<< Conflict 1 of 1
-    %%%%%%% Changes from base to side #1
+    %%%%%%% Changes from base (rlvkpnrz "base") to side #1 (zsuskuln "a")
     -first base
     +first a
-    +++++++ Contents of side #2
+    +++++++ Contents of side #2 (royxmykx "b")
     first b
     >>>>>>> Conflict 1 of 1 ends
-    ");
+    "#);
     insta::assert_snapshot!(
-        std::fs::read_to_string(repo_path.join("another_file")).unwrap(), @r"
+        std::fs::read_to_string(repo_path.join("another_file")).unwrap(), @r#"
     <<<<<<< Conflict 1 of 1
-    %%%%%%% Changes from base to side #1
+    %%%%%%% Changes from base (rlvkpnrz "base") to side #1 (zsuskuln "a")
     -second base
     +second a
-    +++++++ Contents of side #2
+    +++++++ Contents of side #2 (royxmykx "b")
     second b
     >>>>>>> Conflict 1 of 1 ends
-    ");
+    "#);
     insta::assert_snapshot!(test_env.run_jj_in(&repo_path, ["resolve", "--list"]), @r"
     another_file                        2-sided conflict
     this_file_has_a_very_long_name_to_test_padding 2-sided conflict
@@ -1640,26 +1697,26 @@ fn test_multiple_conflicts_with_error() {
     [EOF]
     ");
     insta::assert_snapshot!(
-        std::fs::read_to_string(repo_path.join("file1")).unwrap(), @r"
+        std::fs::read_to_string(repo_path.join("file1")).unwrap(), @r#"
     <<<<<<< Conflict 1 of 1
-    %%%%%%% Changes from base to side #1
+    %%%%%%% Changes from base (rlvkpnrz "base") to side #1 (zsuskuln "a")
     -base1
     +a1
-    +++++++ Contents of side #2
+    +++++++ Contents of side #2 (royxmykx "b")
     b1
     >>>>>>> Conflict 1 of 1 ends
-    "
+    "#
     );
     insta::assert_snapshot!(
-        std::fs::read_to_string(repo_path.join("file2")).unwrap(), @r"
+        std::fs::read_to_string(repo_path.join("file2")).unwrap(), @r#"
     <<<<<<< Conflict 1 of 1
-    %%%%%%% Changes from base to side #1
+    %%%%%%% Changes from base (rlvkpnrz "base") to side #1 (zsuskuln "a")
     -base2
     +a2
-    +++++++ Contents of side #2
+    +++++++ Contents of side #2 (royxmykx "b")
     b2
     >>>>>>> Conflict 1 of 1 ends
-    "
+    "#
     );
     let editor_script = test_env.set_up_fake_editor();
 
@@ -185,15 +185,15 @@ fn test_restore_conflicted_merge() {
     ");
     insta::assert_snapshot!(
     std::fs::read_to_string(repo_path.join("file")).unwrap()
-        , @r"
+        , @r#"
     <<<<<<< Conflict 1 of 1
-    %%%%%%% Changes from base to side #1
+    %%%%%%% Changes from base (rlvkpnrz "base") to side #1 (zsuskuln "a")
     -base
     +a
-    +++++++ Contents of side #2
+    +++++++ Contents of side #2 (royxmykx "b")
     b
     >>>>>>> Conflict 1 of 1 ends
-    ");
+    "#);
 
     // Overwrite the file...
     std::fs::write(repo_path.join("file"), "resolution").unwrap();
@@ -225,15 +225,15 @@ fn test_restore_conflicted_merge() {
     ");
     insta::assert_snapshot!(
     std::fs::read_to_string(repo_path.join("file")).unwrap()
-        , @r"
+        , @r#"
     <<<<<<< Conflict 1 of 1
-    %%%%%%% Changes from base to side #1
+    %%%%%%% Changes from base (rlvkpnrz "base") to side #1 (zsuskuln "a")
     -base
     +a
-    +++++++ Contents of side #2
+    +++++++ Contents of side #2 (royxmykx "b")
     b
     >>>>>>> Conflict 1 of 1 ends
-    ");
+    "#);
     let output = test_env.run_jj_in(&repo_path, ["diff"]);
     insta::assert_snapshot!(output, @"");
 
@@ -267,15 +267,15 @@ fn test_restore_conflicted_merge() {
     ");
     insta::assert_snapshot!(
     std::fs::read_to_string(repo_path.join("file")).unwrap()
-        , @r"
+        , @r#"
     <<<<<<< Conflict 1 of 1
-    %%%%%%% Changes from base to side #1
+    %%%%%%% Changes from base (rlvkpnrz "base") to side #1 (zsuskuln "a")
     -base
     +a
-    +++++++ Contents of side #2
+    +++++++ Contents of side #2 (royxmykx "b")
     b
     >>>>>>> Conflict 1 of 1 ends
-    ");
+    "#);
 }
 
 #[test]
@@ -361,6 +361,69 @@ fn test_restore_restore_descendants() {
     ");
 }
 
+#[test]
+fn test_restore_from_operation() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file1"), "edit\n").unwrap();
+    std::fs::write(repo_path.join("file2"), "edit\n").unwrap();
+    test_env.run_jj_in(&repo_path, ["status"]).success();
+    let output = test_env
+        .run_jj_in(
+            &repo_path,
+            ["op", "log", "--no-graph", "-n1", r#"-Tid.short() ++ "\n""#],
+        )
+        .success();
+    let edit_op_id = output.stdout.raw().trim_end().to_owned();
+
+    // Discard the edits, then make other changes
+    test_env.run_jj_in(&repo_path, ["restore"]).success();
+    std::fs::write(repo_path.join("file2"), "other\n").unwrap();
+
+    // Only the given paths are restored from the old working-copy commit
+    let output = test_env.run_jj_in(&repo_path, ["restore", "--from-op", &edit_op_id, "file1"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Created qpvuntsm e873ad51 (no description set)
+    Working copy now at: qpvuntsm e873ad51 (no description set)
+    Parent commit      : zzzzzzzz 00000000 (empty) (no description set)
+    Added 1 files, modified 0 files, removed 0 files
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["diff", "--git"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/file1 b/file1
+    new file mode 100644
+    index 0000000000..366226fb97
+    --- /dev/null
+    +++ b/file1
+    @@ -0,0 +1,1 @@
+    +edit
+    diff --git a/file2 b/file2
+    new file mode 100644
+    index 0000000000..e45c9c2666
+    --- /dev/null
+    +++ b/file2
+    @@ -0,0 +1,1 @@
+    +other
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["restore", "--from-op=@", "--from=@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the argument '--from-op <OPERATION>' cannot be used with '--from <REVSET>'
+
+    Usage: jj restore --from-op <OPERATION> [FILESETS]...
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
+
 #[test]
 fn test_restore_interactive() {
     let mut test_env = TestEnvironment::default();
@@ -615,6 +678,62 @@ fn test_restore_interactive_with_paths() {
     ");
 }
 
+#[test]
+fn test_restore_interactive_partial_file() {
+    let mut test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    create_commit(
+        &test_env,
+        &repo_path,
+        "a",
+        &[],
+        &[("file1", "a1\nshared\na3\n")],
+    );
+    create_commit(
+        &test_env,
+        &repo_path,
+        "b",
+        &["a"],
+        &[("file1", "b1\nshared\nb3\n")],
+    );
+    create_commit(&test_env, &repo_path, "c", &["b"], &[]);
+
+    // Take only the first hunk from the source revision
+    let diff_editor = test_env.set_up_fake_diff_editor();
+    let diff_script = [
+        "files-before file1",
+        "files-after JJ-INSTRUCTIONS file1",
+        "write file1\na1\nshared\nb3\n",
+    ]
+    .join("\0");
+    std::fs::write(diff_editor, diff_script).unwrap();
+    let output = test_env.run_jj_in(&repo_path, ["restore", "-i", "--from=a", "--into=b"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Created zsuskuln f927e526 b | b
+    Rebased 1 descendant commits
+    Working copy now at: royxmykx aa0f0e39 c | (empty) c
+    Parent commit      : zsuskuln f927e526 b | b
+    Added 0 files, modified 1 files, removed 0 files
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["diff", "--git", "-r=b"]);
+    insta::assert_snapshot!(output, @r"
+    diff --git a/file1 b/file1
+    index f938b90b0a..7f47a43739 100644
+    --- a/file1
+    +++ b/file1
+    @@ -1,3 +1,3 @@
+     a1
+     shared
+    -a3
+    +b3
+    [EOF]
+    ");
+}
+
 fn create_commit(
     test_env: &TestEnvironment,
     repo_path: &Path,
@@ -575,6 +575,39 @@ fn test_alias_override() {
     ");
 }
 
+#[test]
+fn test_alias_include() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(
+        repo_path.join(".jj/repo/my-aliases.toml"),
+        r#"
+    [revset-aliases]
+    'my_root' = 'root()'
+    'my_wc' = 'root()'
+    "#,
+    )
+    .unwrap();
+    std::fs::write(
+        repo_path.join(".jj/repo/config.toml"),
+        r#"
+    [revset-aliases]
+    include = ["my-aliases.toml"]
+    'my_wc' = '@'
+    "#,
+    )
+    .unwrap();
+
+    let output = test_env.run_jj_in(&repo_path, ["log", "-r", "my_root | my_wc", "-Tcommit_id"]);
+    insta::assert_snapshot!(output, @r"
+    @  230dd059e1b059aefc0da06a2e5a7dbf22362f22
+    ◆  0000000000000000000000000000000000000000
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_bad_alias_decl() {
     let test_env = TestEnvironment::default();
@@ -738,6 +771,41 @@ fn test_all_modifier() {
     ");
 }
 
+#[test]
+fn test_exactly() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    let output = test_env.run_jj_in(&repo_path, ["log", "-r", "exactly(@)"]);
+    insta::assert_snapshot!(output, @r"
+    @  qpvuntsm test.user@example.com 2001-02-03 08:05:07 230dd059
+    │  (empty) (no description set)
+    ~
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["log", "-r", "exactly(all())"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Revset was expected to contain exactly 1 commits, but contains 2
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // Fails even if the evaluated set isn't used
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["log", "-r", "@ | (exactly(none(), 2) & none())"],
+    );
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: Revset was expected to contain exactly 2 commits, but contains 0
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 /// Verifies that the committer_date revset honors the local time zone.
 /// This test cannot run on Windows because The TZ env var does not control
 /// chrono::Local on that platform.
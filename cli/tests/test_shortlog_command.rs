@@ -0,0 +1,241 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use crate::common::TestEnvironment;
+
+/// Creates commits by several authors, and a working-copy commit with a
+/// `.mailmap` file which maps some of them.
+fn create_commits(test_env: &TestEnvironment, repo_path: &Path) {
+    for (message, author) in [
+        ("first", Some("Old Name <old@example.com>")),
+        ("second", Some("Proper Name <proper@example.com>")),
+        ("third", None),
+        ("fourth", Some("Me Elsewhere <me@elsewhere.com>")),
+    ] {
+        let mut args = vec!["describe", "-m", message];
+        if let Some(author) = author {
+            args.extend(["--author", author]);
+        }
+        test_env.run_jj_in(repo_path, args).success();
+        test_env.run_jj_in(repo_path, ["new"]).success();
+    }
+    std::fs::write(
+        repo_path.join(".mailmap"),
+        "\
+# Comments and malformed lines are ignored
+Proper Name <proper@example.com> Old Name <old@example.com>
+<test.user@example.com> <me@elsewhere.com>
+",
+    )
+    .unwrap();
+    test_env
+        .run_jj_in(repo_path, ["describe", "-m", "add mailmap"])
+        .success();
+}
+
+#[test]
+fn test_mailmap_templates_and_revsets() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    create_commits(&test_env, &repo_path);
+
+    let template = r#"description.first_line() ++ ": " ++ author ++ if(mine, " (mine)") ++ "\n""#;
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["log", "--no-graph", "-T", template, "-r", "~root()"],
+    );
+    insta::assert_snapshot!(output, @r"
+    add mailmap: Test User <test.user@example.com> (mine)
+    fourth: Me Elsewhere <test.user@example.com> (mine)
+    third: Test User <test.user@example.com> (mine)
+    second: Proper Name <proper@example.com>
+    first: Proper Name <proper@example.com>
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "log",
+            "--no-graph",
+            "-T",
+            template,
+            "-r",
+            "author(exact:'Proper Name')",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    second: Proper Name <proper@example.com>
+    first: Proper Name <proper@example.com>
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["log", "--no-graph", "-T", template, "-r", "mine()"],
+    );
+    insta::assert_snapshot!(output, @r"
+    add mailmap: Test User <test.user@example.com> (mine)
+    fourth: Me Elsewhere <test.user@example.com> (mine)
+    third: Test User <test.user@example.com> (mine)
+    [EOF]
+    ");
+
+    // Entries of the configured mailmap file are added. Like the other
+    // entries, they match the recorded identity.
+    std::fs::write(
+        test_env.env_root().join("extra.mailmap"),
+        "Canonical Name <proper@example.com>\n",
+    )
+    .unwrap();
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "log",
+            "--no-graph",
+            "-T",
+            template,
+            "-r",
+            "author_name(exact:'Canonical Name')",
+            "--config=mailmap.file=../extra.mailmap",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    second: Canonical Name <proper@example.com>
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["log", "--config=mailmap.file=missing.mailmap"]);
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    ------- stderr -------
+    Error: Failed to read mailmap file $TEST_ENV/repo/missing.mailmap
+    Caused by: No such file or directory (os error 2)
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+#[test]
+fn test_shortlog() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    create_commits(&test_env, &repo_path);
+
+    let output = test_env.run_jj_in(&repo_path, ["shortlog"]);
+    insta::assert_snapshot!(output, @r"
+    Me Elsewhere (1):
+          fourth
+
+    Proper Name (2):
+          first
+          second
+
+    Test User (2):
+          third
+          add mailmap
+
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["shortlog", "-sne"]);
+    insta::assert_snapshot!(output, @r"
+         2	Proper Name <proper@example.com>
+         2	Test User <test.user@example.com>
+         1	Me Elsewhere <test.user@example.com>
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["shortlog", "-s", "-r", "description(first)"]);
+    insta::assert_snapshot!(output, @r"
+         1	Proper Name
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_shortlog_stats() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(repo_path.join("file1"), "a\nb\nc\n").unwrap();
+    std::fs::write(repo_path.join("file2"), "x\n").unwrap();
+    test_env
+        .run_jj_in(
+            &repo_path,
+            [
+                "commit",
+                "-m=add files",
+                "--author=Other <other@example.com>",
+            ],
+        )
+        .success();
+    std::fs::write(repo_path.join("file1"), "a\nB\nc\nd\n").unwrap();
+    std::fs::write(repo_path.join("file2"), "y\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m=edit files"])
+        .success();
+    std::fs::write(repo_path.join("file2"), "z\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m=edit file2"])
+        .success();
+
+    let output = test_env.run_jj_in(&repo_path, ["shortlog", "--stat"]);
+    insta::assert_snapshot!(output, @r"
+    Other (1): +4 -0
+          add files
+
+    Test User (2): +4 -3
+          edit files
+          edit file2
+
+    [EOF]
+    ");
+
+    // Only revisions and lines in the given paths are counted
+    let output = test_env.run_jj_in(&repo_path, ["shortlog", "--stat", "-s", "file1"]);
+    insta::assert_snapshot!(output, @r"
+         1	Other +3 -0
+         1	Test User +2 -1
+    [EOF]
+    ");
+
+    let template = r#"name ++ " " ++ email.domain() ++ ": " ++ count ++ " " ++ if(added, "+" ++ added) ++ " " ++ subjects.join(", ") ++ "\n""#;
+    let output = test_env.run_jj_in(&repo_path, ["shortlog", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    Other example.com: 1  add files
+    Test User example.com: 2  edit files, edit file2
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["shortlog", "-n", "--stat", "-T", template]);
+    insta::assert_snapshot!(output, @r"
+    Test User example.com: 2 +4 edit files, edit file2
+    Other example.com: 1 +4 add files
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["shortlog", "--json"]);
+    insta::assert_snapshot!(output, @r#"
+    [{"count":1,"email":"other@example.com","name":"Other","subjects":["add files"]},{"count":2,"email":"test.user@example.com","name":"Test User","subjects":["edit files","edit file2"]}]
+    [EOF]
+    "#);
+    let output = test_env.run_jj_in(&repo_path, ["shortlog", "--json", "--stat", "-e", "file2"]);
+    insta::assert_snapshot!(output, @r#"
+    [{"added":1,"count":1,"email":"other@example.com","name":"Other","removed":0,"subjects":["add files"]},{"added":2,"count":2,"email":"test.user@example.com","name":"Test User","removed":2,"subjects":["edit files","edit file2"]}]
+    [EOF]
+    "#);
+}
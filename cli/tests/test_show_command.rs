@@ -240,6 +240,91 @@ fn test_show_basic() {
     ");
 }
 
+#[test]
+fn test_show_merge_resolution() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    std::fs::write(repo_path.join("file"), "base\n").unwrap();
+    std::fs::write(repo_path.join("other"), "base\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "create", "-r@", "base"])
+        .success();
+    test_env.run_jj_in(&repo_path, ["new", "base"]).success();
+    std::fs::write(repo_path.join("file"), "left\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "create", "-r@", "left"])
+        .success();
+    test_env.run_jj_in(&repo_path, ["new", "base"]).success();
+    std::fs::write(repo_path.join("file"), "right\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["bookmark", "create", "-r@", "right"])
+        .success();
+
+    // The merge resolves the conflict and makes an unrelated change
+    test_env
+        .run_jj_in(&repo_path, ["new", "left", "right", "-m=merge"])
+        .success();
+    std::fs::write(repo_path.join("file"), "resolved\n").unwrap();
+    std::fs::write(repo_path.join("other"), "edited\n").unwrap();
+
+    // Only the resolution and the manual edit are shown, not the changes
+    // merged from the parents
+    let output = test_env.run_jj_in(&repo_path, ["show", "-T=description", "--git"]);
+    insta::assert_snapshot!(output, @r"
+    merge
+    diff --git a/file b/file
+    index 0000000000..2ab19ae607 100644
+    --- a/file
+    +++ b/file
+    @@ -1,7 +1,1 @@
+    -<<<<<<< Conflict 1 of 1
+    -%%%%%%% Changes from base to side #1
+    --base
+    -+left
+    -+++++++ Contents of side #2
+    -right
+    ->>>>>>> Conflict 1 of 1 ends
+    +resolved
+    diff --git a/other b/other
+    index df967b96a5..7663aa741b 100644
+    --- a/other
+    +++ b/other
+    @@ -1,1 +1,1 @@
+    -base
+    +edited
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "log",
+            "-r=@",
+            "-T=description",
+            "--no-graph",
+            "-p",
+            "--summary",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    merge
+    M file
+    M other
+    Resolved conflict in file:
+       1     : <<<<<<< Conflict 1 of 1
+       2     : %%%%%%% Changes from base to side #1
+       3     : -base
+       4     : +left
+       5     : +++++++ Contents of side #2
+       6     : right
+       7    1: >>>>>>> Conflict 1 of 1 endsresolved
+    Modified regular file other:
+       1    1: baseedited
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_show_with_template() {
     let test_env = TestEnvironment::default();
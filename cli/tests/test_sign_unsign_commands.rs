@@ -87,7 +87,7 @@ backend = "test"
     let output = test_env.run_jj_in(&repo_path, ["sign", "-r", "..@"]);
     insta::assert_snapshot!(output, @r"
     ------- stderr -------
-    Signed 4 commits:
+    Re-signed 4 commits:
       qpvuntsm dabebf30 (empty) one
       rlvkpnrz 2085a464 (empty) two
       kkmpptxz 227f5e15 (empty) three
@@ -98,6 +98,40 @@ backend = "test"
     ");
 }
 
+#[test]
+fn test_sign_previously_signed_commits() {
+    let test_env = TestEnvironment::default();
+
+    test_env.add_config(
+        r#"
+[signing]
+behavior = "keep"
+backend = "test"
+"#,
+    );
+
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "one"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["sign", "-r", "@-"])
+        .success();
+
+    let output = test_env.run_jj_in(&repo_path, ["sign", "-r", "@-|@"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Signed 1 commits:
+      rlvkpnrz 35698fba (empty) (no description set)
+    Re-signed 1 commits:
+      qpvuntsm 012424d3 (empty) one
+    Working copy now at: rlvkpnrz 35698fba (empty) (no description set)
+    Parent commit      : qpvuntsm 012424d3 (empty) one
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_sign_default_revset() {
     let test_env = TestEnvironment::default();
@@ -311,6 +345,81 @@ backend = "test"
     ");
 }
 
+#[test]
+fn test_sign_on_rewrite() {
+    let test_env = TestEnvironment::default();
+
+    test_env.add_config(
+        r#"
+[ui]
+show-cryptographic-signatures = true
+
+[signing]
+behavior = "drop"
+backend = "test"
+sign-on-rewrite = true
+"#,
+    );
+
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "A"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "B"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["desc", "-m", "C"])
+        .success();
+    test_env
+        .run_jj_in(
+            &repo_path,
+            [
+                "desc",
+                "--author",
+                "Someone Else <someone@else.com>",
+                "--no-edit",
+                "-r@-",
+            ],
+        )
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["sign", "-r", "all() ~ root()"])
+        .success();
+
+    // Rewritten commits are re-signed only if they're authored by the user
+    let output = test_env.run_jj_in(&repo_path, ["desc", "-r@--", "-m", "A2"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Rebased 2 descendant commits
+    Working copy now at: kkmpptxz e2404dc2 (empty) C
+    Parent commit      : rlvkpnrz 049f1aee (empty) B
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["log", "-r", "all()"]);
+    insta::assert_snapshot!(output, @r"
+    @  kkmpptxz test.user@example.com 2001-02-03 08:05:13 e2404dc2 [✓︎]
+    │  (empty) C
+    ○  rlvkpnrz someone@else.com 2001-02-03 08:05:13 049f1aee
+    │  (empty) B
+    ○  qpvuntsm test.user@example.com 2001-02-03 08:05:13 71779157 [✓︎]
+    │  (empty) A2
+    ◆  zzzzzzzz root() 00000000
+    [EOF]
+    ");
+
+    // New commits aren't signed
+    test_env.run_jj_in(&repo_path, ["new"]).success();
+    let output = test_env.run_jj_in(&repo_path, ["log", "-r", "@"]);
+    insta::assert_snapshot!(output, @r"
+    @  yostqsxw test.user@example.com 2001-02-03 08:05:15 00315aa7
+    │  (empty) (no description set)
+    ~
+    [EOF]
+    ");
+}
+
 #[test]
 fn test_abort_with_error_if_no_signing_backend_is_configured() {
     let test_env = TestEnvironment::default();
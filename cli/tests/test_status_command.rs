@@ -14,6 +14,9 @@
 
 use std::path::Path;
 
+use testutils::git;
+
+use crate::common::CommandOutput;
 use crate::common::TestEnvironment;
 
 fn create_commit(
@@ -153,6 +156,165 @@ fn test_status_filtered() {
     ");
 }
 
+#[test]
+fn test_status_path_scope_cwd() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env.add_config(r#"snapshot.auto-track = "none()""#);
+
+    std::fs::create_dir(repo_path.join("dir1")).unwrap();
+    std::fs::create_dir(repo_path.join("dir2")).unwrap();
+    std::fs::write(repo_path.join("dir1").join("file"), "a").unwrap();
+    std::fs::write(repo_path.join("dir2").join("file"), "a").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["file", "track", "dir1", "dir2"])
+        .success();
+    test_env.run_jj_in(&repo_path, ["new"]).success();
+    std::fs::write(repo_path.join("dir1").join("file"), "b").unwrap();
+    std::fs::write(repo_path.join("dir2").join("file"), "b").unwrap();
+    std::fs::write(repo_path.join("dir1").join("untracked"), "c").unwrap();
+    std::fs::write(repo_path.join("dir2").join("untracked"), "c").unwrap();
+
+    // By default, the whole workspace is shown
+    let output = test_env.run_jj_in(&repo_path.join("dir1"), ["status"]);
+    #[cfg(unix)]
+    insta::assert_snapshot!(output, @r"
+    Working copy changes:
+    M file
+    M ../dir2/file
+    Untracked paths:
+    ? untracked
+    ? ../dir2/untracked
+    Working copy : kkmpptxz 97451b93 (no description set)
+    Parent commit: qpvuntsm f96fe6a8 (no description set)
+    [EOF]
+    ");
+    #[cfg(windows)]
+    insta::assert_snapshot!(output, @r"
+    Working copy changes:
+    M file
+    M ..\dir2\file
+    Untracked paths:
+    ? untracked
+    ? ..\dir2\untracked
+    Working copy : kkmpptxz 97451b93 (no description set)
+    Parent commit: qpvuntsm f96fe6a8 (no description set)
+    [EOF]
+    ");
+
+    // With the cwd scope, only changes in the current directory are shown
+    let output = test_env.run_jj_in(
+        &repo_path.join("dir1"),
+        ["status", "--config=ui.path-scope=cwd"],
+    );
+    insta::assert_snapshot!(output, @r"
+    Working copy changes:
+    M file
+    Untracked paths:
+    ? untracked
+    Working copy : kkmpptxz 97451b93 (no description set)
+    Parent commit: qpvuntsm f96fe6a8 (no description set)
+    [EOF]
+    ");
+
+    // Explicit paths are not restricted to the current directory, and untracked
+    // paths are not filtered by them
+    let output = test_env.run_jj_in(
+        &repo_path.join("dir1"),
+        ["status", "--config=ui.path-scope=cwd", "../dir2"],
+    );
+    #[cfg(unix)]
+    insta::assert_snapshot!(output, @r"
+    Working copy changes:
+    M ../dir2/file
+    Untracked paths:
+    ? untracked
+    ? ../dir2/untracked
+    Working copy : kkmpptxz 97451b93 (no description set)
+    Parent commit: qpvuntsm f96fe6a8 (no description set)
+    [EOF]
+    ");
+    #[cfg(windows)]
+    insta::assert_snapshot!(output, @r"
+    Working copy changes:
+    M ..\dir2\file
+    Untracked paths:
+    ? untracked
+    ? ..\dir2\untracked
+    Working copy : kkmpptxz 97451b93 (no description set)
+    Parent commit: qpvuntsm f96fe6a8 (no description set)
+    [EOF]
+    ");
+
+    // Changes outside the current directory aren't counted
+    std::fs::create_dir(repo_path.join("dir3")).unwrap();
+    let output = test_env.run_jj_in(
+        &repo_path.join("dir3"),
+        ["status", "--config=ui.path-scope=cwd"],
+    );
+    insta::assert_snapshot!(output, @r"
+    The working copy has no changes.
+    Working copy : kkmpptxz 97451b93 (no description set)
+    Parent commit: qpvuntsm f96fe6a8 (no description set)
+    [EOF]
+    ");
+
+    // At the workspace root, the scope is the whole workspace
+    let output = test_env.run_jj_in(&repo_path, ["status", "--config=ui.path-scope=cwd"]);
+    insta::assert_snapshot!(output, @r"
+    Working copy changes:
+    M dir1/file
+    M dir2/file
+    Untracked paths:
+    ? dir1/untracked
+    ? dir2/untracked
+    Working copy : kkmpptxz 97451b93 (no description set)
+    Parent commit: qpvuntsm f96fe6a8 (no description set)
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_status_path_scope_auto_track() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env.add_config(r#"ui.path-scope = "cwd""#);
+
+    std::fs::create_dir(repo_path.join("dir1")).unwrap();
+    std::fs::create_dir(repo_path.join("dir2")).unwrap();
+    std::fs::write(repo_path.join("dir1").join("file"), "a").unwrap();
+    std::fs::write(repo_path.join("dir2").join("file"), "a").unwrap();
+
+    // New files outside the current directory are not auto-tracked
+    let output = test_env.run_jj_in(&repo_path.join("dir1"), ["status"]);
+    insta::assert_snapshot!(output, @r"
+    Working copy changes:
+    A file
+    Working copy : qpvuntsm 6aee4cb3 (no description set)
+    Parent commit: zzzzzzzz 00000000 (empty) (no description set)
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["file", "list"]);
+    insta::assert_snapshot!(output, @r"
+    dir1/file
+    dir2/file
+    [EOF]
+    ");
+
+    // They are tracked once a command runs from a directory containing them
+    let output = test_env.run_jj_in(&repo_path, ["status"]);
+    insta::assert_snapshot!(output, @r"
+    Working copy changes:
+    A dir1/file
+    A dir2/file
+    Working copy : qpvuntsm 4b461b6a (no description set)
+    Parent commit: zzzzzzzz 00000000 (empty) (no description set)
+    [EOF]
+    ");
+}
+
 // See <https://github.com/jj-vcs/jj/issues/3108>
 // See <https://github.com/jj-vcs/jj/issues/4147>
 #[test]
@@ -231,6 +393,7 @@ fn test_status_display_relevant_working_commit_conflict_hints() {
     Parent commit: royxmykx 664a4c6c (conflict) (empty) boom-cont
     Warning: There are unresolved conflicts at these paths:
     conflicted.txt    2-sided conflict
+    Conflicts in conflicted.txt came from operation 6c93cff24411 (2001-02-03 08:05:13) new empty commit, inherited from royxmykx
     Hint: To resolve the conflicts, start by updating to the first one:
       jj new mzvwutvl
     Then use `jj resolve`, or edit the conflict markers in the file directly.
@@ -246,6 +409,7 @@ fn test_status_display_relevant_working_commit_conflict_hints() {
     Parent commit: [1m[38;5;5mr[0m[38;5;8moyxmykx[39m [1m[38;5;4m6[0m[38;5;8m64a4c6c[39m [38;5;1m(conflict)[39m [38;5;2m(empty)[39m boom-cont
     [1m[38;5;3mWarning: [39mThere are unresolved conflicts at these paths:[0m
     conflicted.txt    [38;5;3m2-sided conflict[39m
+    Conflicts in conflicted.txt came from operation [38;5;4m6c93cff24411[39m ([38;5;6m2001-02-03 08:05:13[39m) new empty commit, inherited from [1m[38;5;5mr[0m[38;5;8moyxmykx[39m
     [1m[38;5;6mHint: [0m[39mTo resolve the conflicts, start by updating to the first one:[39m
     [39m  jj new [1m[38;5;5mm[0m[38;5;8mzvwutvl[39m[39m
     [39mThen use `jj resolve`, or edit the conflict markers in the file directly.[39m
@@ -264,6 +428,7 @@ fn test_status_display_relevant_working_commit_conflict_hints() {
     Parent commit: royxmykx 664a4c6c (conflict) (empty) boom-cont
     Warning: There are unresolved conflicts at these paths:
     conflicted.txt    2-sided conflict
+    Conflicts in conflicted.txt came from operation 6c93cff24411 (2001-02-03 08:05:13) new empty commit, inherited from royxmykx
     [EOF]
     ");
 
@@ -429,6 +594,7 @@ fn test_status_simplify_conflict_sides() {
     Warning: There are unresolved conflicts at these paths:
     fileA    2-sided conflict
     fileB    2-sided conflict
+    Conflicts in fileA, fileB came from operation 8f3c3dcc1a8e (2001-02-03 08:05:22) new empty commit, inherited from kmkuslsw
     Hint: To resolve the conflicts, start by updating to one of the first ones:
       jj new lylxulpl
       jj new kmkuslsw
@@ -542,3 +708,161 @@ fn test_status_untracked_files() {
     [EOF]
     ");
 }
+
+#[test]
+fn test_status_unrepresentable_paths() {
+    let test_env = TestEnvironment::default();
+    let git_repo_path = test_env.env_root().join("git-repo");
+    let git_repo = git::init(&git_repo_path);
+    // File names longer than 255 bytes can't be created on most file systems.
+    let long_name = "x".repeat(300);
+    let commit_id = git::add_commit(
+        &git_repo,
+        "refs/heads/main",
+        "file",
+        b"contents",
+        "initial",
+        &[],
+    )
+    .commit_id;
+    git::add_commit(
+        &git_repo,
+        "refs/heads/main",
+        &format!("sub/{long_name}"),
+        b"contents",
+        "add long name",
+        &[commit_id],
+    );
+    test_env
+        .run_jj_in(".", ["git", "init", "--git-repo=git-repo", "repo"])
+        .success();
+    let repo_path = test_env.env_root().join("repo");
+    let normalize = |output: CommandOutput| {
+        output
+            .normalize_backslash()
+            .normalize_stdout_with(|s| s.replace(&long_name, "<long name>"))
+    };
+
+    let output = test_env.run_jj_in(&repo_path, ["new", "main"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Working copy now at: rlvkpnrz b5322c73 (empty) (no description set)
+    Parent commit      : kyppluss c16c73a6 main | add long name
+    Added 1 files, modified 0 files, removed 0 files
+    Warning: 1 paths were not checked out because they can't be represented on this file system.
+    Hint: Use `jj status` to list the skipped paths. They are left unchanged in the working-copy commit.
+    [EOF]
+    ");
+    assert!(repo_path.join("file").is_file());
+
+    // The skipped path isn't reported as deleted
+    let output = test_env.run_jj_in(&repo_path, ["status"]);
+    insta::assert_snapshot!(normalize(output), @r"
+    The working copy has no changes.
+    Working copy : rlvkpnrz b5322c73 (empty) (no description set)
+    Parent commit: kyppluss c16c73a6 main | add long name
+    Warning: These paths can't be represented on this file system, and were not checked out:
+      sub/<long name>
+    [EOF]
+    ");
+
+    // The skipped path can be filtered out
+    let output = test_env.run_jj_in(&repo_path, ["status", "file"]);
+    insta::assert_snapshot!(normalize(output), @r"
+    The working copy has no changes.
+    Working copy : rlvkpnrz b5322c73 (empty) (no description set)
+    Parent commit: kyppluss c16c73a6 main | add long name
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_status_conflict_origins() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    create_commit(&test_env, &repo_path, "base", &[], &[("file", "base\n")]);
+    create_commit(&test_env, &repo_path, "a", &["base"], &[("file", "a\n")]);
+    create_commit(&test_env, &repo_path, "b", &["base"], &[("file", "b\n")]);
+
+    // Conflicts created by merging the parents
+    create_commit(&test_env, &repo_path, "merge", &["a", "b"], &[]);
+    insta::assert_snapshot!(test_env.run_jj_in(&repo_path, ["status"]), @r"
+    The working copy has no changes.
+    Working copy : vruxwmqv f52ce604 merge | (conflict) (empty) merge
+    Parent commit: zsuskuln aa493daf a | a
+    Parent commit: royxmykx db6a4daf b | b
+    Warning: There are unresolved conflicts at these paths:
+    file    2-sided conflict
+    Conflicts in file came from operation e79c34df15a9 (2001-02-03 08:05:14) new empty commit, with sides from zsuskuln, royxmykx
+    [EOF]
+    ");
+
+    // Conflicts created by rebasing the working-copy commit
+    test_env
+        .run_jj_in(&repo_path, ["rebase", "-r=b", "-d=a"])
+        .success();
+    test_env.run_jj_in(&repo_path, ["edit", "b"]).success();
+    insta::assert_snapshot!(test_env.run_jj_in(&repo_path, ["status"]), @r"
+    Working copy changes:
+    M file
+    Working copy : royxmykx 477dfc58 b | (conflict) b
+    Parent commit: zsuskuln aa493daf a | a
+    Warning: There are unresolved conflicts at these paths:
+    file    2-sided conflict
+    Conflicts in file came from operation e09a3c0488d1 (2001-02-03 08:05:17) rebase commit db6a4daf6ee7561ec6b755d0f05b68d1929827a5, with sides from zsuskuln, royxmykx
+    [EOF]
+    ");
+
+    // Conflicts inherited from the parent
+    test_env.run_jj_in(&repo_path, ["new", "b"]).success();
+    insta::assert_snapshot!(test_env.run_jj_in(&repo_path, ["status", "--conflict-origins"]), @r"
+    The working copy has no changes.
+    Working copy : lylxulpl 1618dff0 (conflict) (empty) (no description set)
+    Parent commit: royxmykx 477dfc58 b | (conflict) b
+    Warning: There are unresolved conflicts at these paths:
+    file    2-sided conflict
+    Conflicts in file came from operation 453e081f1c8c (2001-02-03 08:05:20) new empty commit, inherited from royxmykx
+    Hint: To resolve the conflicts, start by updating to it:
+      jj new royxmykx
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you may want to inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+
+    // Only the last few operations are searched by default
+    for i in 0..10 {
+        test_env
+            .run_jj_in(&repo_path, ["describe", &format!("-m={i}")])
+            .success();
+    }
+    insta::assert_snapshot!(test_env.run_jj_in(&repo_path, ["status"]), @r"
+    The working copy has no changes.
+    Working copy : lylxulpl ada2365f (conflict) (empty) 9
+    Parent commit: royxmykx 477dfc58 b | (conflict) b
+    Warning: There are unresolved conflicts at these paths:
+    file    2-sided conflict
+    Hint: To resolve the conflicts, start by updating to it:
+      jj new royxmykx
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you may want to inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+    insta::assert_snapshot!(test_env.run_jj_in(&repo_path, ["status", "--conflict-origins"]), @r"
+    The working copy has no changes.
+    Working copy : lylxulpl ada2365f (conflict) (empty) 9
+    Parent commit: royxmykx 477dfc58 b | (conflict) b
+    Warning: There are unresolved conflicts at these paths:
+    file    2-sided conflict
+    Conflicts in file came from operation 453e081f1c8c (2001-02-03 08:05:20) new empty commit, inherited from royxmykx
+    Hint: To resolve the conflicts, start by updating to it:
+      jj new royxmykx
+    Then use `jj resolve`, or edit the conflict markers in the file directly.
+    Once the conflicts are resolved, you may want to inspect the result with `jj diff`.
+    Then run `jj squash` to move the resolution into the conflicted commit.
+    [EOF]
+    ");
+}
@@ -94,6 +94,16 @@ fn test_tag_list() {
     [EOF]
     ");
 
+    let output = test_env
+        .run_jj_in(
+            &repo_path,
+            ["tag", "list", "-z", "--color=always", "-T", "name"],
+        )
+        .success();
+    insta::assert_debug_snapshot!(output.stdout.normalized(), @r#"
+    "conflicted_tag\0test_tag\0test_tag2\0"
+    "#);
+
     // Test pattern matching.
     insta::assert_snapshot!(
         test_env.run_jj_in(&repo_path, ["tag", "list", "test_tag2"]),
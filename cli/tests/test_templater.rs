@@ -457,6 +457,91 @@ fn test_templater_alias_override() {
     insta::assert_snapshot!(output, @"arg[EOF]");
 }
 
+#[test]
+fn test_templater_alias_include() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    let render = |template| get_template_output(&test_env, &repo_path, "@-", template);
+
+    std::fs::write(
+        repo_path.join(".jj/repo/my-aliases.toml"),
+        r#"
+    [template-aliases]
+    'greet(x)' = '"hello " ++ x'
+    'name' = '"library"'
+    "#,
+    )
+    .unwrap();
+    std::fs::write(
+        repo_path.join(".jj/repo/config.toml"),
+        r#"
+    [template-aliases]
+    include = ["my-aliases.toml", "compact-log", "forge-links"]
+    'name' = '"config"'
+    "#,
+    )
+    .unwrap();
+
+    // Aliases of the config file override the included ones
+    insta::assert_snapshot!(render("greet(name)"), @r"
+    hello config[EOF]
+    ");
+    insta::assert_snapshot!(render("format_short_id(commit_id)"), @r"
+    0[EOF]
+    ");
+    insta::assert_snapshot!(render("format_commit_id_url(commit_id)"), @"");
+
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "log",
+            "--no-graph",
+            "-r@-",
+            "-Tformat_commit_id_url(commit_id)",
+            r#"--config=template-aliases.forge_repo_url='"https://example.com/repo"'"#,
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    https://example.com/repo/commit/0000000000000000000000000000000000000000[EOF]
+    ");
+
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "log",
+            "-Tname",
+            r#"--config=template-aliases.include=["no-such-library"]"#,
+        ],
+    );
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Config error: Invalid type or value for template-aliases.include
+    Caused by: Unknown alias library "no-such-library"
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    "#);
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "log",
+            "-Tname",
+            r#"--config=template-aliases.include=["no-such-file.toml"]"#,
+        ],
+    );
+    insta::assert_snapshot!(output.normalize_backslash(), @r"
+    ------- stderr -------
+    Config error: Failed to read configuration file
+    Caused by:
+    1: Cannot access no-such-file.toml
+    2: No such file or directory (os error 2)
+    For help, see https://jj-vcs.github.io/jj/latest/config/ or use `jj help -k config`.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
 #[test]
 fn test_templater_bad_alias_decl() {
     let test_env = TestEnvironment::default();
@@ -499,6 +584,30 @@ fn test_templater_config_function() {
     insta::assert_snapshot!(
         render("config('user')"),
         @r#"{ email = "test.user@example.com", name = "Test User" }[EOF]"#);
+    insta::assert_snapshot!(
+        render("config('user.name', 'default')"),
+        @r#""Test User"[EOF]"#);
+    insta::assert_snapshot!(
+        render("config('unknown', 'default')"),
+        @r#""default"[EOF]"#);
+    insta::assert_snapshot!(
+        render("if(config('unknown', true).as_boolean(), 'yes', 'no')"),
+        @"yes[EOF]");
+    insta::assert_snapshot!(
+        render("config('unknown', 42).as_integer() > 40"),
+        @"true[EOF]");
+    insta::assert_snapshot!(render("config('unknown', description)"), @r"
+    ------- stderr -------
+    Error: Failed to parse template: Expected boolean, integer, or string literal
+    Caused by:  --> 1:19
+      |
+    1 | config('unknown', description)
+      |                   ^---------^
+      |
+      = Expected boolean, integer, or string literal
+    [EOF]
+    [exit status: 1]
+    ");
     insta::assert_snapshot!(render("config('invalid name')"), @r"
     ------- stderr -------
     Error: Failed to parse template: Failed to parse config name
@@ -534,6 +643,48 @@ fn test_templater_config_function() {
     ");
 }
 
+#[test]
+fn test_templater_id_hyperlinks() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env.add_config(
+        r#"
+        ui.hyperlinks = true
+        [template-aliases]
+        'format_commit_id_url(id)' = '"https://example.com/commit/" ++ id'
+        "#,
+    );
+
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "log",
+            "--color=always",
+            "--no-graph",
+            "-r@",
+            "-Tformat_short_commit_id(commit_id) ++ \" \" ++ format_short_change_id(change_id)",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    ]8;;https://example.com/commit/230dd059e1b059aefc0da06a2e5a7dbf22362f22\[1m[38;5;4m2[0m[38;5;8m30dd059]8;;\[39m [1m[38;5;5mq[0m[38;5;8mpvuntsm[39m[EOF]
+    ");
+
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "log",
+            "--color=always",
+            "--no-graph",
+            "-r@",
+            "-Thyperlink(\"view\", \"https://example.com/\" ++ change_id.short())",
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    ]8;;https://example.com/qpvuntsmwlqt\view]8;;\[EOF]
+    ");
+}
+
 #[must_use]
 fn get_template_output(
     test_env: &TestEnvironment,
@@ -0,0 +1,119 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs;
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_test_no_command() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    let output = test_env.run_jj_in(&repo_path, ["test"]);
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Error: No test command configured
+    Hint: Set `test.command` to the command to run, e.g. `jj config set --repo test.command '["make", "check"]'`
+    [EOF]
+    [exit status: 1]
+    "#);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_test_record_results() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env.add_config(r#"test.command = ["sh", "-c", "echo testing; grep -q ok file"]"#);
+
+    fs::write(repo_path.join("file"), "ok\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "good"])
+        .success();
+    fs::write(repo_path.join("file"), "bad\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "bad"])
+        .success();
+    fs::write(repo_path.join("file"), "ok\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "fixed"])
+        .success();
+
+    let output = test_env.run_jj_in(&repo_path, ["test", "-r", "all:@--::@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Passed: qpvuntsm d91897f8 good
+    testing
+    Failed: rlvkpnrz 73011521 bad
+    Error: 1 of 2 revisions failed the tests
+    [EOF]
+    [exit status: 1]
+    ");
+
+    // The working-copy commit has the same contents as the first commit, so
+    // its result is already recorded
+    let output = test_env.run_jj_in(&repo_path, ["test"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Passed (recorded): kkmpptxz b48ea319 fixed
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["test", "--rerun"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Passed: kkmpptxz b48ea319 fixed
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "log",
+            "-r",
+            "tested()",
+            "-T",
+            r#"description.first_line() ++ ": " ++ test_result ++ "\n""#,
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    @  fixed: passed
+    ○  bad: failed
+    ○  good: passed
+    │
+    ~
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["log", "-r", "failing()", "-T", "description"]);
+    insta::assert_snapshot!(output, @r"
+    ○  bad
+    │
+    ~
+    [EOF]
+    ");
+
+    // Changing the test command invalidates the recorded results
+    test_env.add_config(r#"test.command = ["sh", "-c", "grep -q bad file"]"#);
+    let output = test_env.run_jj_in(&repo_path, ["test"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Failed: kkmpptxz b48ea319 fixed
+    Error: 1 of 1 revisions failed the tests
+    [EOF]
+    [exit status: 1]
+    ");
+}
@@ -0,0 +1,100 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_topic_set_list_remove() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "a"])
+        .success();
+    test_env.run_jj_in(&repo_path, ["new", "-m", "b"]).success();
+    test_env.run_jj_in(&repo_path, ["new", "-m", "c"]).success();
+
+    let output = test_env.run_jj_in(&repo_path, ["topic", "set", "feature", "-r", "@--|@-"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Added 2 changes to topic feature
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["topic", "set", "other"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Added 1 changes to topic other
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["topic", "list"]);
+    insta::assert_snapshot!(output, @r"
+    feature: 2 changes
+    other: 1 changes
+    [EOF]
+    ");
+
+    // Topics are kept when the changes are rebased
+    test_env
+        .run_jj_in(&repo_path, ["rebase", "-r", "@-", "-d", "root()"])
+        .success();
+    let output = test_env.run_jj_in(
+        &repo_path,
+        [
+            "log",
+            "-T",
+            r#"description.first_line() ++ " " ++ topic ++ "\n""#,
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    @  c other
+    ○  a feature
+    │ ○  b feature
+    ├─╯
+    ◆
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["log", "-r", "topic(exact:feature)", "-T", "description"],
+    );
+    insta::assert_snapshot!(output, @r"
+    ○  b
+    │
+    ~
+
+    ○  a
+    │
+    ~
+    [EOF]
+    ");
+
+    let output = test_env.run_jj_in(&repo_path, ["topic", "remove", "-r", "description(a)"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Removed 1 changes from topics
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["topic", "remove", "-r", "description(a)"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    No changes to remove from topics.
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(&repo_path, ["topic", "list"]);
+    insta::assert_snapshot!(output, @r"
+    feature: 1 changes
+    other: 1 changes
+    [EOF]
+    ");
+}
@@ -0,0 +1,96 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_verify() {
+    let test_env = TestEnvironment::default();
+    test_env.add_config(
+        r#"
+[signing]
+behavior = "drop"
+backend = "test"
+"#,
+    );
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "one"])
+        .success();
+    test_env
+        .run_jj_in(
+            &repo_path,
+            [
+                "commit",
+                "-m",
+                "two",
+                "--config=user.name=Someone Else",
+                "--config=user.email=someone@example.com",
+            ],
+        )
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "three"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["sign", "-r", "description(one) | @"])
+        .success();
+
+    let output = test_env.run_jj_in(&repo_path, ["verify", "-r", "::@"]);
+    insta::assert_snapshot!(output, @r"
+    Author                                  good   unknown       bad  unsigned
+    Someone Else <someone@example.com>         1         0         0         0
+    Test User <test.user@example.com>          1         0         0         1
+    [EOF]
+    ");
+
+    // All revisions must be signed
+    let output = test_env.run_jj_in(&repo_path, ["verify", "-r", "::@", "--require-signed"]);
+    insta::assert_snapshot!(output, @r"
+    Author                                  good   unknown       bad  unsigned
+    Someone Else <someone@example.com>         1         0         0         0
+    Test User <test.user@example.com>          1         0         0         1
+    [EOF]
+    ------- stderr -------
+    Revisions failing verification:
+      unsigned rlvkpnrz 94d13e62 (empty) two
+    Error: 1 of 3 revisions failed verification
+    [EOF]
+    [exit status: 1]
+    ");
+    let output = test_env.run_jj_in(
+        &repo_path,
+        ["verify", "-r", "description(one)", "--require-signed"],
+    );
+    insta::assert_snapshot!(output, @r"
+    Author                                 good   unknown       bad  unsigned
+    Test User <test.user@example.com>         1         0         0         0
+    [EOF]
+    ");
+
+    // A revset is required
+    let output = test_env.run_jj_in(&repo_path, ["verify"]);
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    error: the following required arguments were not provided:
+      --revisions <REVSETS>
+
+    Usage: jj verify --revisions <REVSETS>
+
+    For more information, try '--help'.
+    [EOF]
+    [exit status: 2]
+    ");
+}
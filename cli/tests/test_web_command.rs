@@ -0,0 +1,235 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::Path;
+
+use crate::common::CommandOutput;
+use crate::common::TestEnvironment;
+
+#[test]
+fn test_web_pages() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(repo_path.join("file"), "a\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "first <html>"])
+        .success();
+    std::fs::write(repo_path.join("file"), "b\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "second"])
+        .success();
+
+    let output = get_page(&test_env, &repo_path, "/");
+    insta::assert_snapshot!(output, @r#"
+    HTTP/1.1 200 OK
+    <a href="/commit/886f5dbdf663d12c214e089a60654d209ef97c89"><span class="node"><span class="working_copy">@</span></span></a>  <span class="log"><span class="working_copy"><span class="change_id"><span class="shortest"><span class="prefix">r</span><span class="rest">lvkpnrz</span></span></span> <span class="author"><span class="email"><span class="local">test.user</span>@<span class="domain">example.com</span></span></span> <span class="committer"><span class="timestamp"><span class="local"><span class="format">2001-02-03 08:05:09</span></span></span></span> <span class="commit_id"><span class="shortest"><span class="prefix">8</span><span class="rest">86f5dbd</span></span></span></span></span>
+    │  <span class="log"><span class="working_copy"><span class="description"><span class="first_line">second</span></span></span></span>
+    <a href="/commit/5991d993fb0c1ded2cf4af08137fda2d8a611866"><span class="node">○</span></a>  <span class="log"><span class="change_id"><span class="shortest"><span class="prefix">q</span><span class="rest">pvuntsm</span></span></span> <span class="author"><span class="email"><span class="local">test.user</span>@<span class="domain">example.com</span></span></span> <span class="committer"><span class="timestamp"><span class="local"><span class="format">2001-02-03 08:05:08</span></span></span></span> <span class="commit_id"><span class="shortest"><span class="prefix">5</span><span class="rest">991d993</span></span></span></span>
+    │  <span class="log"><span class="description"><span class="first_line">first &lt;html&gt;</span></span></span>
+    <a href="/commit/0000000000000000000000000000000000000000"><span class="node"><span class="immutable">◆</span></span></a>  <span class="log"><span class="change_id"><span class="shortest"><span class="prefix">z</span><span class="rest">zzzzzzz</span></span></span> <span class="root">root()</span> <span class="commit_id"><span class="shortest"><span class="prefix">0</span><span class="rest">0000000</span></span></span></span>
+    [EOF]
+    "#);
+
+    let output = get_page(&test_env, &repo_path, "/commit/@-");
+    insta::assert_snapshot!(output, @r#"
+    HTTP/1.1 200 OK
+    Commit ID: <span class="commit_id">5991d993fb0c1ded2cf4af08137fda2d8a611866</span>
+    Change ID: <span class="change_id">qpvuntsmwlqtpsluzzsnyyzlmlwvmlnu</span>
+    Author   : <span class="author"><span class="name">Test User</span></span> &lt;<span class="author"><span class="email"><span class="local">test.user</span>@<span class="domain">example.com</span></span></span>&gt; (<span class="author"><span class="timestamp"><span class="local"><span class="format">2001-02-03 08:05:08</span></span></span></span>)
+    Committer: <span class="committer"><span class="name">Test User</span></span> &lt;<span class="committer"><span class="email"><span class="local">test.user</span>@<span class="domain">example.com</span></span></span>&gt; (<span class="committer"><span class="timestamp"><span class="local"><span class="format">2001-02-03 08:05:08</span></span></span></span>)
+
+    <span class="description">    first &lt;html&gt;</span>
+
+    <span class="diff"><span class="header">Added regular file file:</span></span>
+    <span class="diff">     <span class="added"><span class="line_number">   1</span></span>: <span class="added"><span class="token">a</span></span></span>
+    [EOF]
+    "#);
+
+    let output = get_page(&test_env, &repo_path, "/operations?ignored=query");
+    insta::assert_snapshot!(output, @r#"
+    HTTP/1.1 200 OK
+    <span class="op_log"><span class="operation"><span class="current_operation"><span class="id"><span class="short">178747942e6e</span></span> <span class="user">test-username@host.example.com</span> <span class="time"><span class="start">2001-02-03 04:05:09.000 +07:00</span></span> - <span class="time"><span class="end">2001-02-03 04:05:09.000 +07:00</span></span></span></span></span>
+    <span class="op_log"><span class="operation"><span class="current_operation"><span class="description"><span class="first_line">describe commit a39f9b390fa2fbd31b54022862709331a7417e2b</span></span></span></span></span>
+    <span class="op_log"><span class="operation"><span class="current_operation"><span class="tags">args: jj describe -m second</span></span></span></span>
+    <span class="op_log"><span class="operation"><span class="id"><span class="short">09c96c48cd3a</span></span> <span class="user">test-username@host.example.com</span> <span class="time"><span class="start">2001-02-03 04:05:09.000 +07:00</span></span> - <span class="time"><span class="end">2001-02-03 04:05:09.000 +07:00</span></span></span></span>
+    <span class="op_log"><span class="operation"><span class="description"><span class="first_line">snapshot working copy</span></span></span></span>
+    <span class="op_log"><span class="operation"><span class="tags">args: jj describe -m second</span></span></span>
+    <span class="op_log"><span class="operation"><span class="id"><span class="short">aa56459c98af</span></span> <span class="user">test-username@host.example.com</span> <span class="time"><span class="start">2001-02-03 04:05:08.000 +07:00</span></span> - <span class="time"><span class="end">2001-02-03 04:05:08.000 +07:00</span></span></span></span>
+    <span class="op_log"><span class="operation"><span class="description"><span class="first_line">commit 6b1027d2770cd0a39c468e525e52bf8c47e1464a</span></span></span></span>
+    <span class="op_log"><span class="operation"><span class="tags">args: jj commit -m &#39;first &lt;html&gt;&#39;</span></span></span>
+    <span class="op_log"><span class="operation"><span class="id"><span class="short">bcaa48580d05</span></span> <span class="user">test-username@host.example.com</span> <span class="time"><span class="start">2001-02-03 04:05:08.000 +07:00</span></span> - <span class="time"><span class="end">2001-02-03 04:05:08.000 +07:00</span></span></span></span>
+    <span class="op_log"><span class="operation"><span class="description"><span class="first_line">snapshot working copy</span></span></span></span>
+    <span class="op_log"><span class="operation"><span class="tags">args: jj commit -m &#39;first &lt;html&gt;&#39;</span></span></span>
+    <span class="op_log"><span class="operation"><span class="id"><span class="short">eac759b9ab75</span></span> <span class="user">test-username@host.example.com</span> <span class="time"><span class="start">2001-02-03 04:05:07.000 +07:00</span></span> - <span class="time"><span class="end">2001-02-03 04:05:07.000 +07:00</span></span></span></span>
+    <span class="op_log"><span class="operation"><span class="description"><span class="first_line">add workspace &#39;default&#39;</span></span></span></span>
+    <span class="op_log"><span class="operation"><span class="id"><span class="short">000000000000</span></span> <span class="root">root()</span></span></span>
+    [EOF]
+    "#);
+
+    let output = get_page(&test_env, &repo_path, "/conflicts");
+    insta::assert_snapshot!(output, @r"
+    HTTP/1.1 200 OK
+    [EOF]
+    ");
+}
+
+#[test]
+fn test_web_conflicts() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+    std::fs::write(repo_path.join("file"), "base\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["commit", "-m", "base"])
+        .success();
+    std::fs::write(repo_path.join("file"), "left\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["describe", "-m", "left"])
+        .success();
+    test_env
+        .run_jj_in(&repo_path, ["new", "description(base)", "-m", "right"])
+        .success();
+    std::fs::write(repo_path.join("file"), "right\n").unwrap();
+    test_env
+        .run_jj_in(&repo_path, ["new", "description(left)", "@", "-m", "merge"])
+        .success();
+
+    let output = get_page(&test_env, &repo_path, "/conflicts");
+    insta::assert_snapshot!(output, @r#"
+    HTTP/1.1 200 OK
+    <span class="change_id"><span class="shortest"><span class="prefix">m</span><span class="rest">zvwutvl</span></span></span> <span class="commit_id"><span class="shortest"><span class="prefix">d</span><span class="rest">9e1612a</span></span></span> <span class="conflict">(conflict)</span> <span class="empty">(empty)</span> <span class="description"><span class="first_line">merge</span></span>
+      <a href="/commit/d9e1612a94bedf080885dd894874c9aa30c97579">file</a>
+    [EOF]
+    "#);
+}
+
+#[test]
+fn test_web_errors() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "repo"]).success();
+    let repo_path = test_env.env_root().join("repo");
+
+    let output = get_page(&test_env, &repo_path, "/nonexistent");
+    insta::assert_snapshot!(output, @r"
+    HTTP/1.1 404 Not Found
+    Not found
+    [EOF]
+    ");
+
+    let output = get_page(&test_env, &repo_path, "/commit/unknown");
+    insta::assert_snapshot!(output, @r"
+    HTTP/1.1 500 Internal Server Error
+    Revision `unknown` doesn&#39;t exist
+    [EOF]
+    ");
+
+    let output = send_request(
+        &test_env,
+        &repo_path,
+        "POST / HTTP/1.1\r\nHost: localhost\r\n\r\n",
+    );
+    insta::assert_snapshot!(output, @r"
+    HTTP/1.1 400 Bad Request
+    Bad request
+    [EOF]
+    ");
+
+    // Paths are percent-decoded
+    let output = get_page(&test_env, &repo_path, "/commit/%40-");
+    insta::assert_snapshot!(output.normalize_stdout_with(status_line), @r"
+    HTTP/1.1 200 OK
+    [EOF]
+    ");
+    let output = get_page(&test_env, &repo_path, "/commit/%4");
+    insta::assert_snapshot!(output, @r"
+    HTTP/1.1 400 Bad Request
+    Bad request
+    [EOF]
+    ");
+
+    // Only requests for localhost or an IP address are answered
+    let output = send_request(
+        &test_env,
+        &repo_path,
+        "GET / HTTP/1.1\r\nHost: example.com\r\n\r\n",
+    );
+    insta::assert_snapshot!(output, @r"
+    HTTP/1.1 403 Forbidden
+    Host not allowed
+    [EOF]
+    ");
+    let output = send_request(&test_env, &repo_path, "GET / HTTP/1.1\r\n\r\n");
+    insta::assert_snapshot!(output, @r"
+    HTTP/1.1 403 Forbidden
+    Host not allowed
+    [EOF]
+    ");
+    let output = send_request(
+        &test_env,
+        &repo_path,
+        "GET /operations HTTP/1.1\r\nHost: [::1]:8000\r\n\r\n",
+    );
+    insta::assert_snapshot!(output.normalize_stdout_with(status_line), @r"
+    HTTP/1.1 200 OK
+    [EOF]
+    ");
+
+    // Overly long requests are rejected
+    let request = format!(
+        "GET /{} HTTP/1.1\r\nHost: localhost\r\n\r\n",
+        "a".repeat(10000)
+    );
+    let output = send_request(&test_env, &repo_path, &request);
+    insta::assert_snapshot!(output, @r"
+    HTTP/1.1 400 Bad Request
+    Request too large
+    [EOF]
+    ");
+}
+
+/// Requests the page by `jj web --stdio`.
+#[must_use]
+fn get_page(test_env: &TestEnvironment, repo_path: &Path, path: &str) -> CommandOutput {
+    let request = format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n");
+    send_request(test_env, repo_path, &request)
+}
+
+/// Sends the raw `request` to `jj web --stdio`.
+#[must_use]
+fn send_request(test_env: &TestEnvironment, repo_path: &Path, request: &str) -> CommandOutput {
+    test_env
+        .run_jj_with(|cmd| {
+            cmd.current_dir(repo_path)
+                .args(["web", "--stdio"])
+                .write_stdin(request)
+        })
+        .normalize_stdout_with(extract_content)
+}
+
+/// Keeps only the status line of the extracted content.
+fn status_line(content: String) -> String {
+    let status = content.lines().next().unwrap_or_default();
+    format!("{status}\n")
+}
+
+/// Extracts the status line and the preformatted content from the response.
+fn extract_content(response: String) -> String {
+    let status = response.lines().next().unwrap_or_default();
+    let content = response
+        .split_once("<pre>\n")
+        .and_then(|(_, rest)| rest.split_once("</pre>"))
+        .map_or("", |(content, _)| content);
+    format!("{status}\n{content}")
+}
@@ -77,6 +77,74 @@ fn test_workspaces_add_second_workspace() {
     second: rzvqmyuk 5ed2222c (empty) (no description set)
     [EOF]
     ");
+    let output = test_env
+        .run_jj_in(&main_path, ["workspace", "list", "-z", "--color=always"])
+        .success();
+    insta::assert_debug_snapshot!(output.stdout.normalized(), @r#"
+    "default: rlvkpnrz 8183d0fc (empty) (no description set)\0second: rzvqmyuk 5ed2222c (empty) (no description set)\0"
+    "#);
+}
+
+/// Test selecting and showing working-copy commits by workspace name
+#[test]
+fn test_workspaces_working_copies_by_name() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_path = test_env.env_root().join("main");
+    test_env
+        .run_jj_in(&main_path, ["workspace", "add", "--name=alice-1", "../a1"])
+        .success();
+    test_env
+        .run_jj_in(
+            &main_path,
+            ["workspace", "add", "--name=alice-2", "-r=@", "../a2"],
+        )
+        .success();
+    test_env
+        .run_jj_in(
+            &main_path,
+            ["workspace", "add", "--name=bob", "-r=@", "../b"],
+        )
+        .success();
+
+    let template = r#"workspace_names.map(|name| "[" ++ name ++ "]").join(" ") ++ "\n""#;
+    let output = test_env.run_jj_in(
+        &main_path,
+        [
+            "log",
+            "-r",
+            "working_copies(glob:'alice-*')",
+            "-T",
+            template,
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    ○  [alice-2]
+    │
+    ~
+
+    ○  [alice-1]
+    │
+    ~
+    [EOF]
+    ");
+    let output = test_env.run_jj_in(
+        &main_path,
+        ["log", "-r", "working_copies()", "-T", template],
+    );
+    insta::assert_snapshot!(output, @r"
+    ○  [bob]
+    │ ○  [alice-2]
+    ├─╯
+    @  [default]
+    │
+    ~
+
+    ○  [alice-1]
+    │
+    ~
+    [EOF]
+    ");
 }
 
 /// Test how sparse patterns are inherited
@@ -148,6 +216,177 @@ fn test_workspaces_sparse_patterns() {
     insta::assert_snapshot!(output, @"");
 }
 
+/// Test copying sparse patterns from a profile or another workspace
+#[test]
+fn test_workspaces_add_sparse_from() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "ws1"]).success();
+    let ws1_path = test_env.env_root().join("ws1");
+    test_env.add_config(
+        r#"
+        [sparse.profiles]
+        docs = ["docs", "README.md"]
+        "#,
+    );
+
+    test_env
+        .run_jj_in(&ws1_path, ["sparse", "set", "--clear", "--add=foo"])
+        .success();
+    test_env
+        .run_jj_in(
+            &ws1_path,
+            ["workspace", "add", "--sparse-from=docs", "../ws2"],
+        )
+        .success();
+    let output = test_env.run_jj_in(test_env.env_root().join("ws2"), ["sparse", "list"]);
+    insta::assert_snapshot!(output, @r"
+    docs
+    README.md
+    [EOF]
+    ");
+
+    // From another workspace, by path
+    test_env
+        .run_jj_in(
+            &ws1_path,
+            ["workspace", "add", "--sparse-from=../ws2", "../ws3"],
+        )
+        .success();
+    let output = test_env.run_jj_in(test_env.env_root().join("ws3"), ["sparse", "list"]);
+    insta::assert_snapshot!(output, @r"
+    docs
+    README.md
+    [EOF]
+    ");
+
+    // From the current workspace, by name
+    test_env
+        .run_jj_in(
+            &ws1_path,
+            ["workspace", "add", "--sparse-from=default", "../ws4"],
+        )
+        .success();
+    let output = test_env.run_jj_in(test_env.env_root().join("ws4"), ["sparse", "list"]);
+    insta::assert_snapshot!(output, @r"
+    foo
+    [EOF]
+    ");
+
+    // From another workspace, by name
+    test_env
+        .run_jj_in(
+            &ws1_path,
+            ["workspace", "add", "--sparse-from=ws2", "../ws5"],
+        )
+        .success();
+    let output = test_env.run_jj_in(test_env.env_root().join("ws5"), ["sparse", "list"]);
+    insta::assert_snapshot!(output, @r"
+    docs
+    README.md
+    [EOF]
+    ");
+
+    // Renamed workspaces are found by their new name
+    test_env
+        .run_jj_in(
+            test_env.env_root().join("ws4"),
+            ["workspace", "rename", "renamed"],
+        )
+        .success();
+    test_env
+        .run_jj_in(
+            &ws1_path,
+            ["workspace", "add", "--sparse-from=renamed", "../ws6"],
+        )
+        .success();
+    let output = test_env.run_jj_in(test_env.env_root().join("ws6"), ["sparse", "list"]);
+    insta::assert_snapshot!(output, @r"
+    foo
+    [EOF]
+    ");
+
+    // Forgotten workspaces can't be found by name
+    test_env
+        .run_jj_in(&ws1_path, ["workspace", "forget", "ws2"])
+        .success();
+    let output = test_env.run_jj_in(
+        &ws1_path,
+        ["workspace", "add", "--sparse-from=ws2", "../ws7"],
+    );
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No sparse profile or workspace named 'ws2'
+    Hint: Define a profile in the `sparse.profiles` config table, or pass the name or path of a workspace.
+    [EOF]
+    [exit status: 1]
+    ");
+
+    let output = test_env.run_jj_in(
+        &ws1_path,
+        ["workspace", "add", "--sparse-from=unknown", "../ws7"],
+    );
+    insta::assert_snapshot!(output, @r"
+    ------- stderr -------
+    Error: No sparse profile or workspace named 'unknown'
+    Hint: Define a profile in the `sparse.profiles` config table, or pass the name or path of a workspace.
+    [EOF]
+    [exit status: 1]
+    ");
+}
+
+/// Test setting the description of the new working-copy commit
+#[test]
+fn test_workspaces_add_name_template() {
+    let test_env = TestEnvironment::default();
+    test_env.run_jj_in(".", ["git", "init", "main"]).success();
+    let main_path = test_env.env_root().join("main");
+    test_env
+        .run_jj_in(&main_path, ["commit", "-m", "base"])
+        .success();
+
+    let output = test_env.run_jj_in(
+        &main_path,
+        [
+            "workspace",
+            "add",
+            "-r=@-",
+            "--name-template",
+            r#""wip in " ++ working_copies ++ " on " ++ parents.map(|c| c.description().first_line())"#,
+            "../second",
+        ],
+    );
+    insta::assert_snapshot!(output, @r#"
+    ------- stderr -------
+    Created workspace in "../second"
+    Working copy now at: pmmvwywv 9fd47319 (empty) wip in second@ on base
+    Parent commit      : qpvuntsm 494c7b83 (empty) base
+    [EOF]
+    "#);
+    let output = test_env.run_jj_in(&main_path, ["log", "-T", "description ++ working_copies"]);
+    insta::assert_snapshot!(output, @r"
+    @  default@
+    │ ○  wip in second@ on base
+    ├─╯  second@
+    ○  base
+    ◆
+    [EOF]
+    ");
+    // Only one working-copy commit is written
+    let output = test_env.run_jj_in(
+        &main_path,
+        [
+            "evolog",
+            "-r=second@",
+            "--no-graph",
+            r#"-T=commit_id.short() ++ "\n""#,
+        ],
+    );
+    insta::assert_snapshot!(output, @r"
+    9fd473197b21
+    [EOF]
+    ");
+}
+
 /// Test adding a second workspace while the current workspace is editing a
 /// merge
 #[test]
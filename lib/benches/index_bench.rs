@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+use jj_lib::backend::CommitId;
+use jj_lib::object_id::HexPrefix;
+use jj_lib::object_id::ObjectId as _;
+use jj_lib::object_id::PrefixResolution;
+use jj_lib::repo::ReadonlyRepo;
+use jj_lib::repo::Repo as _;
+use testutils::TestRepo;
+
+/// Creates a linear history of `num_ops * commits_per_op` commits. Each
+/// operation adds one index segment.
+fn create_linear_history(
+    test_repo: &TestRepo,
+    num_ops: usize,
+    commits_per_op: usize,
+) -> (Arc<ReadonlyRepo>, Vec<CommitId>) {
+    let mut repo = test_repo.repo.clone();
+    let tree_id = repo.store().empty_merged_tree_id();
+    let mut parent_id = repo.store().root_commit_id().clone();
+    let mut commit_ids = vec![];
+    for _ in 0..num_ops {
+        let mut tx = repo.start_transaction();
+        for _ in 0..commits_per_op {
+            let commit = tx
+                .repo_mut()
+                .new_commit(vec![parent_id], tree_id.clone())
+                .write()
+                .unwrap();
+            parent_id = commit.id().clone();
+            commit_ids.push(parent_id.clone());
+        }
+        repo = tx.commit("test").unwrap();
+    }
+    (repo, commit_ids)
+}
+
+fn bench_index(c: &mut Criterion) {
+    let test_repo = TestRepo::init();
+    let (repo, commit_ids) = create_linear_history(&test_repo, 10, 1000);
+    let index_store = repo.loader().index_store().clone();
+    let root_commit_id = repo.store().root_commit_id().clone();
+    let head_id = commit_ids.last().unwrap().clone();
+    // Every 100th commit, spread across all segments
+    let sampled_ids = commit_ids.iter().step_by(100).cloned().collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("bench_index");
+    group.bench_function("load", |b| {
+        b.iter(|| {
+            index_store
+                .get_index_at_op(repo.operation(), repo.store())
+                .unwrap()
+        });
+    });
+    group.bench_function("load_and_lookup", |b| {
+        b.iter(|| {
+            let index = index_store
+                .get_index_at_op(repo.operation(), repo.store())
+                .unwrap();
+            index.as_index().has_id(&head_id)
+        });
+    });
+
+    let index = repo.index();
+    group.bench_function("has_id", |b| {
+        b.iter(|| sampled_ids.iter().all(|id| index.has_id(id)));
+    });
+    group.bench_function("resolve_commit_id_prefix", |b| {
+        let prefixes = sampled_ids
+            .iter()
+            .map(|id| HexPrefix::new(&id.hex()[..8]).unwrap())
+            .collect::<Vec<_>>();
+        b.iter(|| {
+            prefixes
+                .iter()
+                .filter(|prefix| {
+                    matches!(
+                        index.resolve_commit_id_prefix(prefix),
+                        PrefixResolution::SingleMatch(_)
+                    )
+                })
+                .count()
+        });
+    });
+    group.bench_function("shortest_unique_commit_id_prefix_len", |b| {
+        b.iter(|| {
+            sampled_ids
+                .iter()
+                .map(|id| index.shortest_unique_commit_id_prefix_len(id))
+                .sum::<usize>()
+        });
+    });
+    group.bench_function("is_ancestor", |b| {
+        b.iter(|| index.is_ancestor(&root_commit_id, &head_id));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_index);
+criterion_main!(benches);
@@ -20,7 +20,10 @@ fn main() -> Result<()> {
         "git_store.proto",
         "local_store.proto",
         "op_store.proto",
+        "search_index.proto",
+        "tree_diff_cache.proto",
         "working_copy.proto",
+        "workspace_store.proto",
     ];
 
     let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
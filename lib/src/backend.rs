@@ -25,6 +25,7 @@ use futures::stream::BoxStream;
 use thiserror::Error;
 
 use crate::content_hash::ContentHash;
+use crate::content_hash::DigestUpdate;
 use crate::hex_util;
 use crate::index::Index;
 use crate::merge::Merge;
@@ -139,7 +140,7 @@ impl MergedTreeId {
     }
 }
 
-#[derive(ContentHash, Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Commit {
     pub parents: Vec<CommitId>,
     pub predecessors: Vec<CommitId>,
@@ -148,9 +149,48 @@ pub struct Commit {
     pub description: String,
     pub author: Signature,
     pub committer: Signature,
+    /// Change id of the commit this one was duplicated from, if any.
+    pub duplicated_from: Option<ChangeId>,
+    /// Commits whose trees the terms of the conflicted `root_tree` came from,
+    /// in the same order as the terms. Empty if unknown or if the tree is
+    /// resolved.
+    pub conflict_sources: Vec<CommitId>,
     pub secure_sig: Option<SecureSig>,
 }
 
+impl ContentHash for Commit {
+    fn hash(&self, state: &mut impl DigestUpdate) {
+        let Commit {
+            parents,
+            predecessors,
+            root_tree,
+            change_id,
+            description,
+            author,
+            committer,
+            duplicated_from,
+            conflict_sources,
+            secure_sig,
+        } = self;
+        parents.hash(state);
+        predecessors.hash(state);
+        root_tree.hash(state);
+        change_id.hash(state);
+        description.hash(state);
+        author.hash(state);
+        committer.hash(state);
+        secure_sig.hash(state);
+        // Appended only if present to keep the ids of the commits which
+        // weren't duplicated unchanged.
+        if let Some(duplicated_from) = duplicated_from {
+            duplicated_from.hash(state);
+        }
+        if !conflict_sources.is_empty() {
+            conflict_sources.hash(state);
+        }
+    }
+}
+
 #[derive(ContentHash, Debug, PartialEq, Eq, Clone)]
 pub struct ConflictTerm {
     pub value: TreeValue,
@@ -387,6 +427,8 @@ pub fn make_root_commit(root_change_id: ChangeId, empty_tree_id: TreeId) -> Comm
         description: String::new(),
         author: signature.clone(),
         committer: signature,
+        duplicated_from: None,
+        conflict_sources: vec![],
         secure_sig: None,
     }
 }
@@ -30,6 +30,7 @@ use crate::backend::ChangeId;
 use crate::backend::CommitId;
 use crate::backend::MergedTreeId;
 use crate::backend::Signature;
+use crate::merge::Merge;
 use crate::merged_tree::MergedTree;
 use crate::repo::Repo;
 use crate::rewrite::merge_commit_trees;
@@ -141,6 +142,17 @@ impl Commit {
         &self.data.change_id
     }
 
+    /// Change id of the commit this commit was duplicated from, if any.
+    pub fn duplicated_from(&self) -> Option<&ChangeId> {
+        self.data.duplicated_from.as_ref()
+    }
+
+    /// Ids of the commits whose trees the terms of this commit's conflicted
+    /// tree came from, if known.
+    pub fn conflict_sources(&self) -> Option<Merge<CommitId>> {
+        conflict_sources_for_tree(&self.data.root_tree, &self.data.conflict_sources)
+    }
+
     pub fn store_commit(&self) -> &backend::Commit {
         &self.data
     }
@@ -184,6 +196,22 @@ impl Commit {
     }
 }
 
+/// Returns `sources` as a merge if they match the terms of the conflicted tree
+/// `tree_id`.
+pub(crate) fn conflict_sources_for_tree(
+    tree_id: &MergedTreeId,
+    sources: &[CommitId],
+) -> Option<Merge<CommitId>> {
+    match tree_id {
+        MergedTreeId::Merge(tree_ids)
+            if !tree_ids.is_resolved() && tree_ids.as_slice().len() == sources.len() =>
+        {
+            Some(Merge::from_vec(sources.to_vec()))
+        }
+        _ => None,
+    }
+}
+
 pub(crate) fn is_backend_commit_empty(
     repo: &dyn Repo,
     store: &Arc<Store>,
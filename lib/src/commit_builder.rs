@@ -24,8 +24,10 @@ use crate::backend::ChangeId;
 use crate::backend::CommitId;
 use crate::backend::MergedTreeId;
 use crate::backend::Signature;
+use crate::commit::conflict_sources_for_tree;
 use crate::commit::is_backend_commit_empty;
 use crate::commit::Commit;
+use crate::merge::Merge;
 use crate::repo::MutableRepo;
 use crate::repo::Repo;
 use crate::settings::JJRng;
@@ -74,6 +76,15 @@ impl CommitBuilder<'_> {
         self
     }
 
+    pub fn conflict_sources(&self) -> Option<Merge<CommitId>> {
+        self.inner.conflict_sources()
+    }
+
+    pub fn set_conflict_sources(mut self, sources: Option<Merge<CommitId>>) -> Self {
+        self.inner.set_conflict_sources(sources);
+        self
+    }
+
     /// [`Commit::is_empty()`] for the new commit.
     pub fn is_empty(&self) -> BackendResult<bool> {
         self.inner.is_empty(self.mut_repo)
@@ -93,6 +104,15 @@ impl CommitBuilder<'_> {
         self
     }
 
+    pub fn duplicated_from(&self) -> Option<&ChangeId> {
+        self.inner.duplicated_from()
+    }
+
+    pub fn set_duplicated_from(mut self, change_id: Option<ChangeId>) -> Self {
+        self.inner.set_duplicated_from(change_id);
+        self
+    }
+
     pub fn description(&self) -> &str {
         self.inner.description()
     }
@@ -186,6 +206,8 @@ impl DetachedCommitBuilder {
             description: String::new(),
             author: signature.clone(),
             committer: signature,
+            duplicated_from: None,
+            conflict_sources: vec![],
             secure_sig: None,
         };
         DetachedCommitBuilder {
@@ -271,11 +293,34 @@ impl DetachedCommitBuilder {
         &self.commit.root_tree
     }
 
+    /// Sets the tree. The conflict sources are cleared if the tree changes.
     pub fn set_tree_id(&mut self, tree_id: MergedTreeId) -> &mut Self {
+        if self.commit.root_tree != tree_id {
+            self.commit.conflict_sources.clear();
+        }
         self.commit.root_tree = tree_id;
         self
     }
 
+    /// Ids of the commits whose trees the terms of the conflicted tree came
+    /// from, if known.
+    pub fn conflict_sources(&self) -> Option<Merge<CommitId>> {
+        conflict_sources_for_tree(&self.commit.root_tree, &self.commit.conflict_sources)
+    }
+
+    /// Records where the terms of the conflicted tree came from. Must be called
+    /// after setting the tree. Ignored unless the tree has the same number of
+    /// terms.
+    pub fn set_conflict_sources(&mut self, sources: Option<Merge<CommitId>>) -> &mut Self {
+        let sources = sources.map_or_else(Vec::new, |sources| sources.into_iter().collect());
+        self.commit.conflict_sources =
+            match conflict_sources_for_tree(&self.commit.root_tree, &sources) {
+                Some(_) => sources,
+                None => vec![],
+            };
+        self
+    }
+
     /// [`Commit::is_empty()`] for the new commit.
     pub fn is_empty(&self, repo: &dyn Repo) -> BackendResult<bool> {
         is_backend_commit_empty(repo, &self.store, &self.commit)
@@ -295,6 +340,15 @@ impl DetachedCommitBuilder {
         self
     }
 
+    pub fn duplicated_from(&self) -> Option<&ChangeId> {
+        self.commit.duplicated_from.as_ref()
+    }
+
+    pub fn set_duplicated_from(&mut self, change_id: Option<ChangeId>) -> &mut Self {
+        self.commit.duplicated_from = change_id;
+        self
+    }
+
     pub fn description(&self) -> &str {
         &self.commit.description
     }
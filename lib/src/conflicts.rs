@@ -14,6 +14,7 @@
 
 #![allow(missing_docs)]
 
+use std::cmp::Reverse;
 use std::io;
 use std::io::Read;
 use std::io::Write;
@@ -239,6 +240,37 @@ pub enum ConflictMarkerStyle {
     Git,
 }
 
+/// Conflict marker styles to use instead of the default style for paths
+/// matching glob patterns.
+#[derive(Clone, Debug, Default)]
+pub struct ConflictMarkerStyleOverrides {
+    /// Patterns and their styles, most specific (longest) pattern first.
+    rules: Vec<(glob::Pattern, ConflictMarkerStyle)>,
+}
+
+impl ConflictMarkerStyleOverrides {
+    /// Creates overrides from pairs of glob patterns relative to the repo root
+    /// and styles.
+    pub fn new(mut rules: Vec<(glob::Pattern, ConflictMarkerStyle)>) -> Self {
+        rules.sort_by_key(|(glob, _)| (Reverse(glob.as_str().len()), glob.as_str().to_owned()));
+        ConflictMarkerStyleOverrides { rules }
+    }
+
+    /// Returns the style of the most specific pattern matching `path`, or
+    /// `default_style` if no pattern matches.
+    pub fn get(&self, path: &RepoPath, default_style: ConflictMarkerStyle) -> ConflictMarkerStyle {
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        };
+        let path = path.as_internal_file_string();
+        self.rules
+            .iter()
+            .find(|(glob, _)| glob.matches_with(path, options))
+            .map_or(default_style, |&(_, style)| style)
+    }
+}
+
 /// Characters which can be repeated to form a conflict marker line when
 /// materializing and parsing conflicts.
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -348,7 +380,13 @@ pub fn materialize_merge_result<T: AsRef<[u8]>>(
         MergeResult::Resolved(content) => output.write_all(content),
         MergeResult::Conflict(hunks) => {
             let conflict_marker_len = choose_materialized_conflict_marker_len(single_hunk);
-            materialize_conflict_hunks(hunks, conflict_marker_style, conflict_marker_len, output)
+            materialize_conflict_hunks(
+                hunks,
+                conflict_marker_style,
+                conflict_marker_len,
+                None,
+                output,
+            )
         }
     }
 }
@@ -362,9 +400,13 @@ pub fn materialize_merge_result_with_marker_len<T: AsRef<[u8]>>(
     let merge_result = files::merge(single_hunk);
     match &merge_result {
         MergeResult::Resolved(content) => output.write_all(content),
-        MergeResult::Conflict(hunks) => {
-            materialize_conflict_hunks(hunks, conflict_marker_style, conflict_marker_len, output)
-        }
+        MergeResult::Conflict(hunks) => materialize_conflict_hunks(
+            hunks,
+            conflict_marker_style,
+            conflict_marker_len,
+            None,
+            output,
+        ),
     }
 }
 
@@ -382,6 +424,7 @@ pub fn materialize_merge_result_to_bytes<T: AsRef<[u8]>>(
                 &hunks,
                 conflict_marker_style,
                 conflict_marker_len,
+                None,
                 &mut output,
             )
             .expect("writing to an in-memory buffer should never fail");
@@ -390,10 +433,14 @@ pub fn materialize_merge_result_to_bytes<T: AsRef<[u8]>>(
     }
 }
 
+/// Like `materialize_merge_result_to_bytes()`, but with the given conflict
+/// marker length. If `labels` are given, they describe where each term of
+/// `single_hunk` came from, and are shown next to the conflict markers.
 pub fn materialize_merge_result_to_bytes_with_marker_len<T: AsRef<[u8]>>(
     single_hunk: &Merge<T>,
     conflict_marker_style: ConflictMarkerStyle,
     conflict_marker_len: usize,
+    labels: Option<&Merge<String>>,
 ) -> BString {
     let merge_result = files::merge(single_hunk);
     match merge_result {
@@ -404,6 +451,7 @@ pub fn materialize_merge_result_to_bytes_with_marker_len<T: AsRef<[u8]>>(
                 &hunks,
                 conflict_marker_style,
                 conflict_marker_len,
+                labels,
                 &mut output,
             )
             .expect("writing to an in-memory buffer should never fail");
@@ -416,8 +464,15 @@ fn materialize_conflict_hunks(
     hunks: &[Merge<BString>],
     conflict_marker_style: ConflictMarkerStyle,
     conflict_marker_len: usize,
+    labels: Option<&Merge<String>>,
     output: &mut dyn Write,
 ) -> io::Result<()> {
+    // Labels can only be matched with the terms if every hunk has all terms
+    let labels = labels.filter(|labels| {
+        hunks
+            .iter()
+            .all(|hunk| hunk.is_resolved() || hunk.num_sides() == labels.num_sides())
+    });
     let num_conflicts = hunks
         .iter()
         .filter(|hunk| hunk.as_resolved().is_none())
@@ -439,6 +494,7 @@ fn materialize_conflict_hunks(
                         right,
                         &conflict_info,
                         conflict_marker_len,
+                        labels,
                         output,
                     )?;
                 }
@@ -448,6 +504,7 @@ fn materialize_conflict_hunks(
                         &conflict_info,
                         conflict_marker_style,
                         conflict_marker_len,
+                        labels,
                         output,
                     )?;
                 }
@@ -463,13 +520,16 @@ fn materialize_git_style_conflict(
     right: &[u8],
     conflict_info: &str,
     conflict_marker_len: usize,
+    labels: Option<&Merge<String>>,
     output: &mut dyn Write,
 ) -> io::Result<()> {
+    let add_label =
+        |add_index: usize| term_label(labels.and_then(|labels| labels.get_add(add_index)));
     write_conflict_marker(
         output,
         ConflictMarkerLineChar::ConflictStart,
         conflict_marker_len,
-        &format!("Side #1 ({conflict_info})"),
+        &format!("Side #1{} ({conflict_info})", add_label(0)),
     )?;
     write_and_ensure_newline(output, left)?;
 
@@ -477,7 +537,10 @@ fn materialize_git_style_conflict(
         output,
         ConflictMarkerLineChar::GitAncestor,
         conflict_marker_len,
-        "Base",
+        &format!(
+            "Base{}",
+            term_label(labels.and_then(|labels| labels.get_remove(0)))
+        ),
     )?;
     write_and_ensure_newline(output, base)?;
 
@@ -494,19 +557,30 @@ fn materialize_git_style_conflict(
         output,
         ConflictMarkerLineChar::ConflictEnd,
         conflict_marker_len,
-        &format!("Side #2 ({conflict_info} ends)"),
+        &format!("Side #2{} ({conflict_info} ends)", add_label(1)),
     )?;
 
     Ok(())
 }
 
+/// Formats the label of a conflict term to be appended to a marker line.
+fn term_label(label: Option<&String>) -> String {
+    label.map_or_else(String::new, |label| format!(" ({label})"))
+}
+
 fn materialize_jj_style_conflict(
     hunk: &Merge<BString>,
     conflict_info: &str,
     conflict_marker_style: ConflictMarkerStyle,
     conflict_marker_len: usize,
+    labels: Option<&Merge<String>>,
     output: &mut dyn Write,
 ) -> io::Result<()> {
+    let add_label =
+        |add_index: usize| term_label(labels.and_then(|labels| labels.get_add(add_index)));
+    let remove_label =
+        |base_index: usize| term_label(labels.and_then(|labels| labels.get_remove(base_index)));
+
     // Write a positive snapshot (side) of a conflict
     let write_side = |add_index: usize, data: &[u8], output: &mut dyn Write| {
         write_conflict_marker(
@@ -514,8 +588,9 @@ fn materialize_jj_style_conflict(
             ConflictMarkerLineChar::Add,
             conflict_marker_len,
             &format!(
-                "Contents of side #{}{}",
+                "Contents of side #{}{}{}",
                 add_index + 1,
+                add_label(add_index),
                 maybe_no_eol_comment(data)
             ),
         )?;
@@ -553,8 +628,9 @@ fn materialize_jj_style_conflict(
                 ConflictMarkerLineChar::Diff,
                 conflict_marker_len,
                 &format!(
-                    "Changes from {base_str} to side #{}{no_eol_comment}",
-                    add_index + 1
+                    "Changes from {base_str} to side #{}{}{no_eol_comment}",
+                    add_index + 1,
+                    add_label(add_index)
                 ),
             )?;
             write_diff_hunks(diff, output)
@@ -571,9 +647,9 @@ fn materialize_jj_style_conflict(
         // The vast majority of conflicts one actually tries to resolve manually have 1
         // base.
         let base_str = if hunk.removes().len() == 1 {
-            "base".to_string()
+            format!("base{}", remove_label(base_index))
         } else {
-            format!("base #{}", base_index + 1)
+            format!("base #{}{}", base_index + 1, remove_label(base_index))
         };
 
         let Some(right1) = hunk.get_add(add_index) else {
@@ -969,6 +1045,55 @@ pub async fn update_from_content(
 
     // Now write the new files contents we found by parsing the file with conflict
     // markers.
+    write_file_terms(file_ids, &simplified_file_ids, store, path, &contents)
+}
+
+/// Updates `file_ids` with the given hunks, some of which may have been
+/// resolved. The hunks are in terms of the simplified `file_ids`. If all hunks
+/// are resolved, a single resolved `FileId` will be returned.
+///
+/// Resolved hunks aren't added to sides in which the file is absent, so e.g. a
+/// modify/delete conflict remains one.
+pub async fn update_from_hunks(
+    file_ids: &Merge<Option<FileId>>,
+    store: &Store,
+    path: &RepoPath,
+    hunks: &[Merge<BString>],
+) -> BackendResult<Merge<Option<FileId>>> {
+    if hunks.iter().all(|hunk| hunk.is_resolved()) {
+        let content = hunks
+            .iter()
+            .flat_map(|hunk| hunk.as_resolved().unwrap().iter().copied())
+            .collect_vec();
+        let file_id = store.write_file(path, &mut content.as_slice()).await?;
+        return Ok(Merge::normal(file_id));
+    }
+
+    let simplified_file_ids = file_ids.clone().simplify();
+    let mut contents = simplified_file_ids.map(|_| vec![]);
+    for hunk in hunks {
+        if let Some(slice) = hunk.as_resolved() {
+            for content in contents.iter_mut() {
+                content.extend_from_slice(slice);
+            }
+        } else {
+            for (content, slice) in zip(contents.iter_mut(), hunk.iter()) {
+                content.extend_from_slice(slice);
+            }
+        }
+    }
+    write_file_terms(file_ids, &simplified_file_ids, store, path, &contents)
+}
+
+/// Writes the `contents` of each term of `simplified_file_ids`, and expands the
+/// result to the shape of `file_ids`.
+fn write_file_terms(
+    file_ids: &Merge<Option<FileId>>,
+    simplified_file_ids: &Merge<Option<FileId>>,
+    store: &Store,
+    path: &RepoPath,
+    contents: &Merge<Vec<u8>>,
+) -> BackendResult<Merge<Option<FileId>>> {
     // TODO: Write these concurrently
     let new_file_ids: Vec<Option<FileId>> = zip(contents.iter(), simplified_file_ids.iter())
         .map(|(content, file_id)| -> BackendResult<Option<FileId>> {
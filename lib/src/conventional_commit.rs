@@ -0,0 +1,115 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parsing of commit descriptions following the
+//! [Conventional Commits](https://www.conventionalcommits.org/) format, such
+//! as `feat(cli)!: remove flag`.
+
+/// Parsed header of a conventional commit description.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConventionalCommit<'a> {
+    /// Type of the change, such as `feat` or `fix`.
+    pub kind: &'a str,
+    /// Scope of the change, which is enclosed in parentheses after the type.
+    pub scope: Option<&'a str>,
+    /// Whether the change is marked as breaking by `!` after the type and
+    /// scope, or by a `BREAKING CHANGE:` footer.
+    pub breaking: bool,
+    /// Rest of the first line after the `: ` separator.
+    pub subject: &'a str,
+}
+
+impl<'a> ConventionalCommit<'a> {
+    /// Parses the given description. Returns `None` if the first line doesn't
+    /// follow the `<type>[(<scope>)][!]: <subject>` format.
+    pub fn parse(description: &'a str) -> Option<Self> {
+        let (first_line, body) = description.split_once('\n').unwrap_or((description, ""));
+        let (header, subject) = first_line.split_once(": ")?;
+        let subject = subject.trim();
+        if subject.is_empty() {
+            return None;
+        }
+        let (header, bang) = match header.strip_suffix('!') {
+            Some(header) => (header, true),
+            None => (header, false),
+        };
+        let (kind, scope) = match header.strip_suffix(')') {
+            Some(header) => {
+                let (kind, scope) = header.split_once('(')?;
+                if scope.is_empty() || scope.contains(['(', ')']) {
+                    return None;
+                }
+                (kind, Some(scope))
+            }
+            None => (header, None),
+        };
+        if kind.is_empty() || !kind.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return None;
+        }
+        let breaking = bang
+            || body.lines().any(|line| {
+                line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:")
+            });
+        Some(ConventionalCommit {
+            kind,
+            scope,
+            breaking,
+            subject,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            ConventionalCommit::parse("feat: add flag\n"),
+            Some(ConventionalCommit {
+                kind: "feat",
+                scope: None,
+                breaking: false,
+                subject: "add flag",
+            })
+        );
+        assert_eq!(
+            ConventionalCommit::parse("fix(cli)!: remove flag"),
+            Some(ConventionalCommit {
+                kind: "fix",
+                scope: Some("cli"),
+                breaking: true,
+                subject: "remove flag",
+            })
+        );
+        assert_eq!(
+            ConventionalCommit::parse("chore(deps): bump\n\nBREAKING CHANGE: MSRV is 1.84\n"),
+            Some(ConventionalCommit {
+                kind: "chore",
+                scope: Some("deps"),
+                breaking: true,
+                subject: "bump",
+            })
+        );
+        assert_eq!(ConventionalCommit::parse(""), None);
+        assert_eq!(ConventionalCommit::parse("add flag"), None);
+        assert_eq!(ConventionalCommit::parse("feat:add flag"), None);
+        assert_eq!(ConventionalCommit::parse("feat: "), None);
+        assert_eq!(ConventionalCommit::parse("cli, lib: add flag"), None);
+        assert_eq!(ConventionalCommit::parse("feat(): add flag"), None);
+        assert_eq!(ConventionalCommit::parse("feat(cli: add flag"), None);
+        assert_eq!(ConventionalCommit::parse(": add flag"), None);
+    }
+}
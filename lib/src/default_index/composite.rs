@@ -50,6 +50,7 @@ use crate::object_id::PrefixResolution;
 use crate::revset::ResolvedExpression;
 use crate::revset::Revset;
 use crate::revset::RevsetEvaluationError;
+use crate::search_index::SearchIndex;
 use crate::store::Store;
 
 pub(super) trait IndexSegment: Send + Sync {
@@ -417,8 +418,9 @@ impl CompositeIndex {
         &self,
         expression: &ResolvedExpression,
         store: &Arc<Store>,
+        search_index: Option<&SearchIndex>,
     ) -> Result<Box<dyn Revset + '_>, RevsetEvaluationError> {
-        let revset_impl = revset_engine::evaluate(expression, store, self)?;
+        let revset_impl = revset_engine::evaluate(expression, store, search_index, self)?;
         Ok(Box::new(revset_impl))
     }
 }
@@ -445,6 +447,10 @@ impl Index for &CompositeIndex {
             .unwrap_or(0)
     }
 
+    fn num_commits(&self) -> u32 {
+        CompositeIndex::num_commits(self)
+    }
+
     fn resolve_commit_id_prefix(&self, prefix: &HexPrefix) -> PrefixResolution<CommitId> {
         self.ancestor_index_segments()
             .fold(PrefixResolution::NoMatch, |acc_match, segment| {
@@ -508,7 +514,7 @@ impl Index for &CompositeIndex {
         expression: &ResolvedExpression,
         store: &Arc<Store>,
     ) -> Result<Box<dyn Revset + 'index>, RevsetEvaluationError> {
-        CompositeIndex::evaluate_revset(self, expression, store)
+        CompositeIndex::evaluate_revset(self, expression, store, None)
     }
 }
 
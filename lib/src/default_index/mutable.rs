@@ -60,6 +60,7 @@ use crate::object_id::PrefixResolution;
 use crate::revset::ResolvedExpression;
 use crate::revset::Revset;
 use crate::revset::RevsetEvaluationError;
+use crate::search_index::SearchIndex;
 use crate::store::Store;
 
 #[derive(Debug)]
@@ -369,7 +370,8 @@ impl MutableIndexSegment {
         persist_content_addressed_temp_file(temp_file, index_file_path)?;
 
         Ok(ReadonlyIndexSegment::load_with_parent_file(
-            &mut &buf[local_entries_offset..],
+            buf,
+            local_entries_offset,
             index_file_id_hex,
             self.parent_file,
             self.commit_id_length,
@@ -456,17 +458,37 @@ impl IndexSegment for MutableIndexSegment {
 }
 
 /// In-memory mutable records for the on-disk commit index backend.
-pub struct DefaultMutableIndex(MutableIndexSegment);
+pub struct DefaultMutableIndex {
+    segment: MutableIndexSegment,
+    search_index: Option<Arc<SearchIndex>>,
+    /// Store of the added commits, used to update the search index when the
+    /// segment is saved.
+    store: Option<Arc<Store>>,
+}
 
 impl DefaultMutableIndex {
     pub(crate) fn full(commit_id_length: usize, change_id_length: usize) -> Self {
         let mutable_segment = MutableIndexSegment::full(commit_id_length, change_id_length);
-        DefaultMutableIndex(mutable_segment)
+        DefaultMutableIndex {
+            segment: mutable_segment,
+            search_index: None,
+            store: None,
+        }
     }
 
     pub(super) fn incremental(parent_file: Arc<ReadonlyIndexSegment>) -> Self {
         let mutable_segment = MutableIndexSegment::incremental(parent_file);
-        DefaultMutableIndex(mutable_segment)
+        DefaultMutableIndex {
+            segment: mutable_segment,
+            search_index: None,
+            store: None,
+        }
+    }
+
+    /// Uses the search index to speed up revset evaluation.
+    pub(super) fn with_search_index(mut self, search_index: Option<Arc<SearchIndex>>) -> Self {
+        self.search_index = search_index;
+        self
     }
 
     #[cfg(test)]
@@ -476,17 +498,23 @@ impl DefaultMutableIndex {
         change_id: ChangeId,
         parent_ids: &[CommitId],
     ) {
-        self.0.add_commit_data(commit_id, change_id, parent_ids);
+        self.segment
+            .add_commit_data(commit_id, change_id, parent_ids);
+    }
+
+    /// Returns the store of the commits added to this index, if any.
+    pub(super) fn store(&self) -> Option<&Arc<Store>> {
+        self.store.as_ref()
     }
 
     pub(super) fn squash_and_save_in(self, dir: &Path) -> io::Result<Arc<ReadonlyIndexSegment>> {
-        self.0.maybe_squash_with_ancestors().save_in(dir)
+        self.segment.maybe_squash_with_ancestors().save_in(dir)
     }
 }
 
 impl AsCompositeIndex for DefaultMutableIndex {
     fn as_composite(&self) -> &CompositeIndex {
-        self.0.as_composite()
+        self.segment.as_composite()
     }
 }
 
@@ -496,6 +524,10 @@ impl Index for DefaultMutableIndex {
             .shortest_unique_commit_id_prefix_len(commit_id)
     }
 
+    fn num_commits(&self) -> u32 {
+        self.as_composite().num_commits()
+    }
+
     fn resolve_commit_id_prefix(&self, prefix: &HexPrefix) -> PrefixResolution<CommitId> {
         self.as_composite().resolve_commit_id_prefix(prefix)
     }
@@ -530,7 +562,8 @@ impl Index for DefaultMutableIndex {
         expression: &ResolvedExpression,
         store: &Arc<Store>,
     ) -> Result<Box<dyn Revset + 'index>, RevsetEvaluationError> {
-        self.as_composite().evaluate_revset(expression, store)
+        self.as_composite()
+            .evaluate_revset(expression, store, self.search_index.as_deref())
     }
 }
 
@@ -555,7 +588,10 @@ impl MutableIndex for DefaultMutableIndex {
     }
 
     fn add_commit(&mut self, commit: &Commit) {
-        self.0.add_commit(commit);
+        if self.store.is_none() {
+            self.store = Some(commit.store().clone());
+        }
+        self.segment.add_commit(commit);
     }
 
     fn merge_in(&mut self, other: &dyn ReadonlyIndex) {
@@ -563,7 +599,7 @@ impl MutableIndex for DefaultMutableIndex {
             .as_any()
             .downcast_ref::<DefaultReadonlyIndex>()
             .expect("index to merge in must be a DefaultReadonlyIndex");
-        self.0.merge_in(other.as_segment().clone());
+        self.segment.merge_in(other.as_segment().clone());
     }
 }
 
@@ -50,6 +50,7 @@ use crate::object_id::PrefixResolution;
 use crate::revset::ResolvedExpression;
 use crate::revset::Revset;
 use crate::revset::RevsetEvaluationError;
+use crate::search_index::SearchIndex;
 use crate::store::Store;
 
 /// Error while loading index segment file.
@@ -176,6 +177,9 @@ impl CommitGraphEntry<'_> {
 
 /// Commit index segment backed by immutable file.
 ///
+/// Entries are decoded on access. Only the header is parsed when loading the
+/// segment.
+///
 /// File format:
 /// ```text
 /// u32: file format version
@@ -229,6 +233,7 @@ pub(super) struct ReadonlyIndexSegment {
     num_local_change_ids: u32,
     num_change_overflow_entries: u32,
     // Base data offsets in bytes:
+    graph_base: usize,
     commit_lookup_base: usize,
     change_id_table_base: usize,
     change_pos_table_base: usize,
@@ -254,36 +259,41 @@ impl ReadonlyIndexSegment {
         commit_id_length: usize,
         change_id_length: usize,
     ) -> Result<Arc<ReadonlyIndexSegment>, ReadonlyIndexLoadError> {
-        let mut file = File::open(dir.join(&name))
-            .map_err(|err| ReadonlyIndexLoadError::from_io_err(&name, err))?;
-        Self::load_from(&mut file, dir, name, commit_id_length, change_id_length)
+        let from_io_err = |err| ReadonlyIndexLoadError::from_io_err(&name, err);
+        let mut file = File::open(dir.join(&name)).map_err(from_io_err)?;
+        let mut data = vec![];
+        file.read_to_end(&mut data).map_err(from_io_err)?;
+        Self::load_from(data, dir, name, commit_id_length, change_id_length)
     }
 
-    /// Loads both parent segments and local entries from the given `file`.
+    /// Loads both parent segments and local entries from the given file
+    /// `data`.
     pub(super) fn load_from(
-        file: &mut dyn Read,
+        data: Vec<u8>,
         dir: &Path,
         name: String,
         commit_id_length: usize,
         change_id_length: usize,
     ) -> Result<Arc<ReadonlyIndexSegment>, ReadonlyIndexLoadError> {
         let from_io_err = |err| ReadonlyIndexLoadError::from_io_err(&name, err);
-        let read_u32 = |file: &mut dyn Read| {
-            let mut buf = [0; 4];
-            file.read_exact(&mut buf).map_err(from_io_err)?;
-            Ok(u32::from_le_bytes(buf))
+        let read_u32 = |buf: &mut &[u8]| {
+            let mut bytes = [0; 4];
+            buf.read_exact(&mut bytes).map_err(from_io_err)?;
+            Ok(u32::from_le_bytes(bytes))
         };
-        let format_version = read_u32(file)?;
+        let mut header: &[u8] = &data;
+        let format_version = read_u32(&mut header)?;
         if format_version != INDEX_SEGMENT_FILE_FORMAT_VERSION {
             return Err(ReadonlyIndexLoadError::UnexpectedVersion {
                 found_version: format_version,
                 expected_version: INDEX_SEGMENT_FILE_FORMAT_VERSION,
             });
         }
-        let parent_filename_len = read_u32(file)?;
+        let parent_filename_len = read_u32(&mut header)?;
         let maybe_parent_file = if parent_filename_len > 0 {
             let mut parent_filename_bytes = vec![0; parent_filename_len as usize];
-            file.read_exact(&mut parent_filename_bytes)
+            header
+                .read_exact(&mut parent_filename_bytes)
                 .map_err(from_io_err)?;
             let parent_filename = String::from_utf8(parent_filename_bytes).map_err(|_| {
                 ReadonlyIndexLoadError::invalid_data(&name, "parent file name is not valid UTF-8")
@@ -298,8 +308,10 @@ impl ReadonlyIndexSegment {
         } else {
             None
         };
+        let local_entries_offset = data.len() - header.len();
         Self::load_with_parent_file(
-            file,
+            data,
+            local_entries_offset,
             name,
             maybe_parent_file,
             commit_id_length,
@@ -307,30 +319,30 @@ impl ReadonlyIndexSegment {
         )
     }
 
-    /// Loads local entries from the given `file`, returns new segment linked to
-    /// the given `parent_file`.
+    /// Loads local entries starting at `local_entries_offset` of the file
+    /// `data`, returns new segment linked to the given `parent_file`.
     pub(super) fn load_with_parent_file(
-        file: &mut dyn Read,
+        data: Vec<u8>,
+        local_entries_offset: usize,
         name: String,
         parent_file: Option<Arc<ReadonlyIndexSegment>>,
         commit_id_length: usize,
         change_id_length: usize,
     ) -> Result<Arc<ReadonlyIndexSegment>, ReadonlyIndexLoadError> {
         let from_io_err = |err| ReadonlyIndexLoadError::from_io_err(&name, err);
-        let read_u32 = |file: &mut dyn Read| {
-            let mut buf = [0; 4];
-            file.read_exact(&mut buf).map_err(from_io_err)?;
-            Ok(u32::from_le_bytes(buf))
+        let read_u32 = |buf: &mut &[u8]| {
+            let mut bytes = [0; 4];
+            buf.read_exact(&mut bytes).map_err(from_io_err)?;
+            Ok(u32::from_le_bytes(bytes))
         };
         let num_parent_commits = parent_file
             .as_ref()
             .map_or(0, |segment| segment.as_composite().num_commits());
-        let num_local_commits = read_u32(file)?;
-        let num_local_change_ids = read_u32(file)?;
-        let num_parent_overflow_entries = read_u32(file)?;
-        let num_change_overflow_entries = read_u32(file)?;
-        let mut data = vec![];
-        file.read_to_end(&mut data).map_err(from_io_err)?;
+        let mut header = &data[local_entries_offset..];
+        let num_local_commits = read_u32(&mut header)?;
+        let num_local_change_ids = read_u32(&mut header)?;
+        let num_parent_overflow_entries = read_u32(&mut header)?;
+        let num_change_overflow_entries = read_u32(&mut header)?;
 
         let commit_graph_entry_size = CommitGraphEntry::size(commit_id_length);
         let graph_size = (num_local_commits as usize) * commit_graph_entry_size;
@@ -340,7 +352,7 @@ impl ReadonlyIndexSegment {
         let parent_overflow_size = (num_parent_overflow_entries as usize) * 4;
         let change_overflow_size = (num_change_overflow_entries as usize) * 4;
 
-        let graph_base = 0;
+        let graph_base = data.len() - header.len();
         let commit_lookup_base = graph_base + graph_size;
         let change_id_table_base = commit_lookup_base + commit_lookup_size;
         let change_pos_table_base = change_id_table_base + change_id_table_size;
@@ -364,6 +376,7 @@ impl ReadonlyIndexSegment {
             num_local_commits,
             num_local_change_ids,
             num_change_overflow_entries,
+            graph_base,
             commit_lookup_base,
             change_id_table_base,
             change_pos_table_base,
@@ -390,7 +403,7 @@ impl ReadonlyIndexSegment {
     }
 
     fn graph_entry(&self, local_pos: LocalPosition) -> CommitGraphEntry {
-        let table = &self.data[..self.commit_lookup_base];
+        let table = &self.data[self.graph_base..self.commit_lookup_base];
         let entry_size = CommitGraphEntry::size(self.commit_id_length);
         let offset = (local_pos.0 as usize) * entry_size;
         CommitGraphEntry {
@@ -591,21 +604,35 @@ impl IndexSegment for ReadonlyIndexSegment {
 
 /// Commit index backend which stores data on local disk.
 #[derive(Clone, Debug)]
-pub struct DefaultReadonlyIndex(Arc<ReadonlyIndexSegment>);
+pub struct DefaultReadonlyIndex {
+    segment: Arc<ReadonlyIndexSegment>,
+    search_index: Option<Arc<SearchIndex>>,
+}
 
 impl DefaultReadonlyIndex {
-    pub(super) fn from_segment(segment: Arc<ReadonlyIndexSegment>) -> Self {
-        DefaultReadonlyIndex(segment)
+    pub(super) fn from_segment(
+        segment: Arc<ReadonlyIndexSegment>,
+        search_index: Option<Arc<SearchIndex>>,
+    ) -> Self {
+        DefaultReadonlyIndex {
+            segment,
+            search_index,
+        }
     }
 
     pub(super) fn as_segment(&self) -> &Arc<ReadonlyIndexSegment> {
-        &self.0
+        &self.segment
+    }
+
+    /// Search index used by revset evaluation, if enabled.
+    pub fn search_index(&self) -> Option<&SearchIndex> {
+        self.search_index.as_deref()
     }
 }
 
 impl AsCompositeIndex for DefaultReadonlyIndex {
     fn as_composite(&self) -> &CompositeIndex {
-        self.0.as_composite()
+        self.segment.as_composite()
     }
 }
 
@@ -615,6 +642,10 @@ impl Index for DefaultReadonlyIndex {
             .shortest_unique_commit_id_prefix_len(commit_id)
     }
 
+    fn num_commits(&self) -> u32 {
+        self.as_composite().num_commits()
+    }
+
     fn resolve_commit_id_prefix(&self, prefix: &HexPrefix) -> PrefixResolution<CommitId> {
         self.as_composite().resolve_commit_id_prefix(prefix)
     }
@@ -649,7 +680,8 @@ impl Index for DefaultReadonlyIndex {
         expression: &ResolvedExpression,
         store: &Arc<Store>,
     ) -> Result<Box<dyn Revset + 'index>, RevsetEvaluationError> {
-        self.as_composite().evaluate_revset(expression, store)
+        self.as_composite()
+            .evaluate_revset(expression, store, self.search_index.as_deref())
     }
 }
 
@@ -670,7 +702,10 @@ impl ReadonlyIndex for DefaultReadonlyIndex {
     }
 
     fn start_modification(&self) -> Box<dyn MutableIndex> {
-        Box::new(DefaultMutableIndex::incremental(self.0.clone()))
+        Box::new(
+            DefaultMutableIndex::incremental(self.segment.clone())
+                .with_search_index(self.search_index.clone()),
+        )
     }
 }
 
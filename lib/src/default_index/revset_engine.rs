@@ -19,6 +19,7 @@ use std::cmp::Ordering;
 use std::cmp::Reverse;
 use std::collections::BTreeSet;
 use std::collections::BinaryHeap;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt;
 use std::iter;
@@ -26,6 +27,8 @@ use std::ops::Range;
 use std::rc::Rc;
 use std::str;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 
 use futures::StreamExt as _;
 use itertools::Itertools;
@@ -49,6 +52,7 @@ use crate::conflicts::MaterializedTreeValue;
 use crate::default_index::AsCompositeIndex;
 use crate::default_index::CompositeIndex;
 use crate::default_index::IndexPosition;
+use crate::fileset::FilesetExpression;
 use crate::graph::GraphNode;
 use crate::matchers::Matcher;
 use crate::matchers::Visit;
@@ -63,6 +67,10 @@ use crate::revset::RevsetEvaluationError;
 use crate::revset::RevsetFilterPredicate;
 use crate::revset::GENERATION_RANGE_FULL;
 use crate::rewrite;
+use crate::rewrite::AppliedChanges;
+use crate::search_index::SearchCandidates;
+use crate::search_index::SearchField;
+use crate::search_index::SearchIndex;
 use crate::store::Store;
 use crate::str_util::StringPattern;
 use crate::union_find;
@@ -142,6 +150,15 @@ impl<I: AsCompositeIndex + Clone> RevsetImpl<I> {
         let mut graph_walk = RevsetGraphWalk::new(walk, skip_transitive_edges);
         iter::from_fn(move || graph_walk.next(index.as_composite()))
     }
+
+    pub fn iter_first_parent_graph_impl(
+        &self,
+    ) -> impl Iterator<Item = Result<GraphNode<CommitId>, RevsetEvaluationError>> + use<I> {
+        let index = self.index.clone();
+        let walk = self.inner.positions();
+        let mut graph_walk = RevsetGraphWalk::new_first_parent(walk);
+        iter::from_fn(move || graph_walk.next(index.as_composite()))
+    }
 }
 
 impl<I> fmt::Debug for RevsetImpl<I> {
@@ -189,6 +206,15 @@ impl<I: AsCompositeIndex + Clone> Revset for RevsetImpl<I> {
         Box::new(self.iter_graph_impl(skip_transitive_edges))
     }
 
+    fn iter_first_parent_graph<'a>(
+        &self,
+    ) -> Box<dyn Iterator<Item = Result<GraphNode<CommitId>, RevsetEvaluationError>> + 'a>
+    where
+        Self: 'a,
+    {
+        Box::new(self.iter_first_parent_graph_impl())
+    }
+
     fn is_empty(&self) -> bool {
         self.positions().next().is_none()
     }
@@ -766,19 +792,270 @@ where
 pub fn evaluate<I: AsCompositeIndex + Clone>(
     expression: &ResolvedExpression,
     store: &Arc<Store>,
+    search_index: Option<&SearchIndex>,
     index: I,
 ) -> Result<RevsetImpl<I>, RevsetEvaluationError> {
     let context = EvaluationContext {
         store: store.clone(),
+        search_index,
         index: index.as_composite(),
+        profile: None,
     };
     let internal_revset = context.evaluate(expression)?;
     Ok(RevsetImpl::new(internal_revset, index))
 }
 
+/// Like [`evaluate()`], but also returns profile which collects statistics of
+/// each expression node.
+///
+/// The statistics are updated as the returned revset is iterated.
+pub fn evaluate_with_profile<I: AsCompositeIndex + Clone>(
+    expression: &ResolvedExpression,
+    store: &Arc<Store>,
+    search_index: Option<&SearchIndex>,
+    index: I,
+) -> Result<(RevsetImpl<I>, RevsetProfile), RevsetEvaluationError> {
+    let profile = RevsetProfile::default();
+    let context = EvaluationContext {
+        store: store.clone(),
+        search_index,
+        index: index.as_composite(),
+        profile: Some(profile.clone()),
+    };
+    let internal_revset = context.evaluate(expression)?;
+    Ok((RevsetImpl::new(internal_revset, index), profile))
+}
+
+/// Evaluation statistics of revset expression node.
+///
+/// Times include the time spent on the child nodes.
+#[derive(Clone, Debug, Default)]
+pub struct RevsetProfileNode {
+    /// Depth of the node in the expression tree.
+    pub depth: usize,
+    /// Short description of the node.
+    pub label: String,
+    /// Time spent on building the set. Since most sets are iterated lazily,
+    /// this doesn't include the time spent on the iteration.
+    pub evaluation_time: Duration,
+    /// Number of commits yielded from the set.
+    pub yielded_count: usize,
+    /// Time spent on iterating over the set.
+    pub iteration_time: Duration,
+    /// Number of commits tested for membership of the set.
+    pub tested_count: usize,
+    /// Number of tested commits included in the set.
+    pub matched_count: usize,
+    /// Time spent on testing commits.
+    pub predicate_time: Duration,
+}
+
+/// Collects evaluation statistics of revset expression nodes.
+#[derive(Clone, Debug, Default)]
+pub struct RevsetProfile {
+    state: Rc<RefCell<RevsetProfileState>>,
+}
+
+#[derive(Debug, Default)]
+struct RevsetProfileState {
+    nodes: Vec<RevsetProfileNode>,
+    depth: usize,
+}
+
+impl RevsetProfile {
+    /// Returns statistics of the expression nodes in pre-order.
+    pub fn nodes(&self) -> Vec<RevsetProfileNode> {
+        self.state.borrow().nodes.clone()
+    }
+
+    fn start_node(&self, label: String) -> usize {
+        let mut state = self.state.borrow_mut();
+        let node = RevsetProfileNode {
+            depth: state.depth,
+            label,
+            ..Default::default()
+        };
+        state.nodes.push(node);
+        state.depth += 1;
+        state.nodes.len() - 1
+    }
+
+    fn finish_node(&self, index: usize, evaluation_time: Duration) {
+        let mut state = self.state.borrow_mut();
+        state.depth -= 1;
+        state.nodes[index].evaluation_time = evaluation_time;
+    }
+
+    fn update_node(&self, index: usize, f: impl FnOnce(&mut RevsetProfileNode)) {
+        f(&mut self.state.borrow_mut().nodes[index]);
+    }
+}
+
+/// Wrapper that records statistics of the inner revset or predicate.
+struct ProfiledRevset<S> {
+    inner: S,
+    profile: RevsetProfile,
+    node: usize,
+}
+
+impl<S: fmt::Debug> fmt::Debug for ProfiledRevset<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<S: InternalRevset> InternalRevset for ProfiledRevset<S> {
+    fn positions<'a>(&self) -> BoxedRevWalk<'a>
+    where
+        Self: 'a,
+    {
+        Box::new(ProfiledRevWalk {
+            walk: self.inner.positions(),
+            profile: self.profile.clone(),
+            node: self.node,
+        })
+    }
+
+    fn into_predicate<'a>(self: Box<Self>) -> Box<dyn ToPredicateFn + 'a>
+    where
+        Self: 'a,
+    {
+        Box::new(ProfiledRevset {
+            inner: Box::new(self.inner).into_predicate(),
+            profile: self.profile,
+            node: self.node,
+        })
+    }
+}
+
+impl<S: ToPredicateFn> ToPredicateFn for ProfiledRevset<S> {
+    fn to_predicate_fn<'a>(&self) -> BoxedPredicateFn<'a>
+    where
+        Self: 'a,
+    {
+        let mut p = self.inner.to_predicate_fn();
+        let profile = self.profile.clone();
+        let node = self.node;
+        Box::new(move |index, pos| {
+            let start = Instant::now();
+            let result = p(index, pos);
+            let elapsed = start.elapsed();
+            profile.update_node(node, |stats| {
+                stats.tested_count += 1;
+                stats.matched_count += matches!(result, Ok(true)) as usize;
+                stats.predicate_time += elapsed;
+            });
+            result
+        })
+    }
+}
+
+struct ProfiledRevWalk<W> {
+    walk: W,
+    profile: RevsetProfile,
+    node: usize,
+}
+
+impl<W: RevWalk<CompositeIndex>> RevWalk<CompositeIndex> for ProfiledRevWalk<W> {
+    type Item = W::Item;
+
+    fn next(&mut self, index: &CompositeIndex) -> Option<Self::Item> {
+        let start = Instant::now();
+        let item = self.walk.next(index);
+        let elapsed = start.elapsed();
+        self.profile.update_node(self.node, |stats| {
+            stats.yielded_count += item.is_some() as usize;
+            stats.iteration_time += elapsed;
+        });
+        item
+    }
+}
+
+fn expression_label(expression: &ResolvedExpression) -> String {
+    let format_generation = |generation: &Range<u64>| {
+        if *generation == GENERATION_RANGE_FULL {
+            "".to_owned()
+        } else {
+            format!(" generation={generation:?}")
+        }
+    };
+    match expression {
+        ResolvedExpression::Commits(commit_ids) => format!("Commits count={}", commit_ids.len()),
+        ResolvedExpression::Ancestors { generation, .. } => {
+            format!("Ancestors{}", format_generation(generation))
+        }
+        ResolvedExpression::Range { generation, .. } => {
+            format!("Range{}", format_generation(generation))
+        }
+        ResolvedExpression::DagRange {
+            generation_from_roots,
+            ..
+        } => format!("DagRange{}", format_generation(generation_from_roots)),
+        ResolvedExpression::Reachable { .. } => "Reachable".to_owned(),
+        ResolvedExpression::Heads(_) => "Heads".to_owned(),
+        ResolvedExpression::Roots(_) => "Roots".to_owned(),
+        ResolvedExpression::ForkPoint(_) => "ForkPoint".to_owned(),
+        ResolvedExpression::BisectMidpoint(_) => "BisectMidpoint".to_owned(),
+        ResolvedExpression::AppliedUpstream { .. } => "AppliedUpstream".to_owned(),
+        ResolvedExpression::Latest { count, .. } => format!("Latest count={count}"),
+        ResolvedExpression::Exactly { count, .. } => format!("Exactly count={count}"),
+        ResolvedExpression::Coalesce(_, _) => "Coalesce".to_owned(),
+        ResolvedExpression::Union(_, _) => "Union".to_owned(),
+        ResolvedExpression::FilterWithin { .. } => "FilterWithin".to_owned(),
+        ResolvedExpression::Intersection(_, _) => "Intersection".to_owned(),
+        ResolvedExpression::Difference(_, _) => "Difference".to_owned(),
+    }
+}
+
+fn predicate_expression_label(expression: &ResolvedPredicateExpression) -> Option<String> {
+    match expression {
+        ResolvedPredicateExpression::Filter(predicate) => Some(format!("Filter {predicate:?}")),
+        // Profiled as set expression
+        ResolvedPredicateExpression::Set(_) => None,
+        ResolvedPredicateExpression::NotIn(_) => Some("NotIn".to_owned()),
+        ResolvedPredicateExpression::Union(_, _) => Some("Union".to_owned()),
+    }
+}
+
+/// Splits nested `FilterWithin` into the innermost candidates and the pure
+/// filter predicates, in order of evaluation.
+fn split_pure_filters(
+    expression: &ResolvedExpression,
+) -> (&ResolvedExpression, Vec<&ResolvedPredicateExpression>) {
+    fn is_pure(predicate: &ResolvedPredicateExpression) -> bool {
+        match predicate {
+            ResolvedPredicateExpression::Filter(_) => true,
+            // Set predicate has to be tested in order of index position.
+            ResolvedPredicateExpression::Set(_) => false,
+            ResolvedPredicateExpression::NotIn(complement) => is_pure(complement),
+            ResolvedPredicateExpression::Union(predicate1, predicate2) => {
+                is_pure(predicate1) && is_pure(predicate2)
+            }
+        }
+    }
+
+    let mut candidates = expression;
+    let mut predicates = vec![];
+    while let ResolvedExpression::FilterWithin {
+        candidates: inner_candidates,
+        predicate,
+    } = candidates
+    {
+        if !is_pure(predicate) {
+            break;
+        }
+        predicates.push(predicate);
+        candidates = inner_candidates;
+    }
+    predicates.reverse();
+    (candidates, predicates)
+}
+
 struct EvaluationContext<'index> {
     store: Arc<Store>,
+    search_index: Option<&'index SearchIndex>,
     index: &'index CompositeIndex,
+    profile: Option<RevsetProfile>,
 }
 
 fn to_u32_generation_range(range: &Range<u64>) -> Result<Range<u32>, RevsetEvaluationError> {
@@ -795,6 +1072,24 @@ impl EvaluationContext<'_> {
     fn evaluate(
         &self,
         expression: &ResolvedExpression,
+    ) -> Result<Box<dyn InternalRevset>, RevsetEvaluationError> {
+        let Some(profile) = &self.profile else {
+            return self.evaluate_expression(expression);
+        };
+        let node = profile.start_node(expression_label(expression));
+        let start = Instant::now();
+        let result = self.evaluate_expression(expression);
+        profile.finish_node(node, start.elapsed());
+        Ok(Box::new(ProfiledRevset {
+            inner: result?,
+            profile: profile.clone(),
+            node,
+        }))
+    }
+
+    fn evaluate_expression(
+        &self,
+        expression: &ResolvedExpression,
     ) -> Result<Box<dyn InternalRevset>, RevsetEvaluationError> {
         let index = self.index;
         match expression {
@@ -972,9 +1267,52 @@ impl EvaluationContext<'_> {
                 positions.reverse();
                 Ok(Box::new(EagerRevset { positions }))
             }
+            ResolvedExpression::BisectMidpoint(candidates) => {
+                let candidate_set = self.evaluate(candidates)?;
+                Ok(Box::new(self.take_bisect_midpoint_revset(&*candidate_set)?))
+            }
+            ResolvedExpression::AppliedUpstream {
+                candidates,
+                upstream,
+            } => {
+                let candidate_set = self.evaluate(candidates)?;
+                let upstream_set = self.evaluate(upstream)?;
+                Ok(Box::new(self.take_applied_upstream_revset(
+                    &*candidate_set,
+                    &*upstream_set,
+                )?))
+            }
             ResolvedExpression::Latest { candidates, count } => {
+                let (candidates, predicates) = split_pure_filters(candidates);
+                let candidate_set = self.evaluate(candidates)?;
+                if predicates.is_empty() {
+                    Ok(Box::new(self.take_latest_revset(&*candidate_set, *count)?))
+                } else {
+                    let predicates: Vec<_> = predicates
+                        .iter()
+                        .map(|predicate| self.evaluate_predicate(predicate))
+                        .try_collect()?;
+                    Ok(Box::new(self.take_latest_filtered_revset(
+                        &*candidate_set,
+                        &predicates,
+                        *count,
+                    )?))
+                }
+            }
+            ResolvedExpression::Exactly { candidates, count } => {
                 let candidate_set = self.evaluate(candidates)?;
-                Ok(Box::new(self.take_latest_revset(&*candidate_set, *count)?))
+                let positions: Vec<_> = candidate_set.positions().attach(index).try_collect()?;
+                if positions.len() != *count {
+                    return Err(RevsetEvaluationError::Other(
+                        format!(
+                            "Revset was expected to contain exactly {count} commits, but contains \
+                             {actual}",
+                            actual = positions.len()
+                        )
+                        .into(),
+                    ));
+                }
+                Ok(Box::new(EagerRevset { positions }))
             }
             ResolvedExpression::Coalesce(expression1, expression2) => {
                 let set1 = self.evaluate(expression1)?;
@@ -1012,11 +1350,35 @@ impl EvaluationContext<'_> {
     fn evaluate_predicate(
         &self,
         expression: &ResolvedPredicateExpression,
+    ) -> Result<Box<dyn ToPredicateFn>, RevsetEvaluationError> {
+        let Some((profile, label)) = self
+            .profile
+            .as_ref()
+            .and_then(|profile| Some((profile, predicate_expression_label(expression)?)))
+        else {
+            return self.evaluate_predicate_expression(expression);
+        };
+        let node = profile.start_node(label);
+        let start = Instant::now();
+        let result = self.evaluate_predicate_expression(expression);
+        profile.finish_node(node, start.elapsed());
+        Ok(Box::new(ProfiledRevset {
+            inner: result?,
+            profile: profile.clone(),
+            node,
+        }))
+    }
+
+    fn evaluate_predicate_expression(
+        &self,
+        expression: &ResolvedPredicateExpression,
     ) -> Result<Box<dyn ToPredicateFn>, RevsetEvaluationError> {
         match expression {
-            ResolvedPredicateExpression::Filter(predicate) => {
-                Ok(build_predicate_fn(self.store.clone(), predicate))
-            }
+            ResolvedPredicateExpression::Filter(predicate) => Ok(build_predicate_fn(
+                self.store.clone(),
+                self.search_index,
+                predicate,
+            )),
             ResolvedPredicateExpression::Set(expression) => {
                 Ok(self.evaluate(expression)?.into_predicate())
             }
@@ -1108,6 +1470,193 @@ impl EvaluationContext<'_> {
         positions.sort_unstable_by_key(|&pos| Reverse(pos));
         Ok(EagerRevset { positions })
     }
+
+    /// Selects the latest `count` commits matching all `predicates`.
+    ///
+    /// Unlike `take_latest_revset()` of filtered set, the predicates are tested
+    /// in order of descending timestamp, and the testing stops once `count`
+    /// commits are found. The `predicates` must be pure functions.
+    fn take_latest_filtered_revset(
+        &self,
+        candidate_set: &dyn InternalRevset,
+        predicates: &[Box<dyn ToPredicateFn>],
+        count: usize,
+    ) -> Result<EagerRevset, RevsetEvaluationError> {
+        if count == 0 {
+            return Ok(EagerRevset::empty());
+        }
+
+        let mut items: Vec<_> = candidate_set
+            .positions()
+            .attach(self.index)
+            .map(|pos| -> Result<_, RevsetEvaluationError> {
+                let entry = self.index.entry_by_pos(pos?);
+                let commit = self.store.get_commit(&entry.commit_id())?;
+                Ok((commit.committer().timestamp.timestamp, entry.position()))
+            })
+            .try_collect()?;
+        // Sort in the same order as take_latest_revset(), the latest first.
+        items.sort_unstable_by(|item1, item2| item2.cmp(item1));
+
+        let mut predicate_fns = predicates
+            .iter()
+            .map(|predicate| predicate.to_predicate_fn())
+            .collect_vec();
+        let mut positions = vec![];
+        'items: for (_, pos) in items {
+            for predicate_fn in &mut predicate_fns {
+                if !predicate_fn(self.index, pos)? {
+                    continue 'items;
+                }
+            }
+            positions.push(pos);
+            if positions.len() == count {
+                break;
+            }
+        }
+        positions.sort_unstable_by_key(|&pos| Reverse(pos));
+        Ok(EagerRevset { positions })
+    }
+
+    fn take_bisect_midpoint_revset(
+        &self,
+        candidate_set: &dyn InternalRevset,
+    ) -> Result<EagerRevset, RevsetEvaluationError> {
+        let index = self.index;
+        let candidate_positions: Vec<_> = candidate_set.positions().attach(index).try_collect()?;
+        let Some(&min_pos) = candidate_positions.last() else {
+            return Ok(EagerRevset::empty());
+        };
+        let total = candidate_positions.len();
+        // Bit index of each candidate in the ancestor sets below
+        let candidate_bits: HashMap<_, _> = candidate_positions
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(bit, &pos)| (pos, bit))
+            .collect();
+
+        // Visit the ancestors of the candidates once, from the oldest, and
+        // propagate the set of candidate ancestors of each commit to its
+        // children. A set is released once all its children have been
+        // visited, so only the sets of the current frontier are kept.
+        let ancestor_positions: Vec<_> = RevWalkBuilder::new(index)
+            .wanted_heads(candidate_positions.clone())
+            .ancestors_until_roots([min_pos])
+            .collect();
+        let ancestor_position_set: HashSet<_> = ancestor_positions.iter().copied().collect();
+        let parents_of = |pos: IndexPosition| {
+            let mut parent_positions = index.entry_by_pos(pos).parent_positions();
+            parent_positions.retain(|parent_pos| ancestor_position_set.contains(parent_pos));
+            parent_positions
+        };
+        let mut num_unvisited_children: HashMap<IndexPosition, usize> = HashMap::new();
+        for &pos in &ancestor_positions {
+            for parent_pos in parents_of(pos) {
+                *num_unvisited_children.entry(parent_pos).or_default() += 1;
+            }
+        }
+        let has_children: HashSet<_> = num_unvisited_children.keys().copied().collect();
+        let num_words = total.div_ceil(u64::BITS as usize);
+        // Candidate ancestors of each commit, and the number of them
+        type AncestorSets = HashMap<IndexPosition, (Vec<u64>, usize)>;
+        let mut ancestor_sets = AncestorSets::new();
+        let mut take_parent_set = |ancestor_sets: &mut AncestorSets, parent_pos| {
+            let num_children = num_unvisited_children.get_mut(&parent_pos).unwrap();
+            *num_children -= 1;
+            if *num_children == 0 {
+                ancestor_sets.remove(&parent_pos).unwrap()
+            } else {
+                ancestor_sets[&parent_pos].clone()
+            }
+        };
+        let mut best: Option<(usize, IndexPosition)> = None;
+        for &pos in ancestor_positions.iter().rev() {
+            let (mut bits, mut count) = match parents_of(pos).as_slice() {
+                [] => (vec![0; num_words], 0),
+                [parent_pos] => take_parent_set(&mut ancestor_sets, *parent_pos),
+                parent_positions => {
+                    let mut bits = vec![0; num_words];
+                    for &parent_pos in parent_positions {
+                        let (parent_bits, _) = take_parent_set(&mut ancestor_sets, parent_pos);
+                        for (word, parent_word) in bits.iter_mut().zip(parent_bits) {
+                            *word |= parent_word;
+                        }
+                    }
+                    let count = bits.iter().map(|word| word.count_ones() as usize).sum();
+                    (bits, count)
+                }
+            };
+            if let Some(&bit) = candidate_bits.get(&pos) {
+                bits[bit / 64] |= 1 << (bit % 64);
+                count += 1;
+                // If the midpoint turns out to be bad, its ancestors remain.
+                // Otherwise the rest of the candidates remain.
+                let remaining = count.min(total - count);
+                if best.is_none_or(|(best_remaining, _)| remaining >= best_remaining) {
+                    best = Some((remaining, pos));
+                }
+            }
+            if has_children.contains(&pos) {
+                ancestor_sets.insert(pos, (bits, count));
+            }
+        }
+        let positions = best.map(|(_, pos)| pos).into_iter().collect();
+        Ok(EagerRevset { positions })
+    }
+
+    fn take_applied_upstream_revset(
+        &self,
+        candidate_set: &dyn InternalRevset,
+        upstream_set: &dyn InternalRevset,
+    ) -> Result<EagerRevset, RevsetEvaluationError> {
+        let index = self.index;
+        let upstream_commits: Vec<_> = upstream_set
+            .positions()
+            .attach(index)
+            .map(|pos| {
+                let entry = index.entry_by_pos(pos?);
+                Ok::<_, RevsetEvaluationError>(self.store.get_commit(&entry.commit_id())?)
+            })
+            .try_collect()?;
+        let mut applied_changes = AppliedChanges::new(upstream_commits);
+        // A duplicate may have been modified since, so it's only applied if
+        // it would become empty on top of the upstream. The trees are loaded
+        // when the first duplicate is found.
+        let mut upstream_head_trees = None;
+        let mut positions = vec![];
+        for pos in candidate_set.positions().attach(index) {
+            let pos = pos?;
+            let commit = self
+                .store
+                .get_commit(&index.entry_by_pos(pos).commit_id())?;
+            if applied_changes.contains(&commit)? {
+                positions.push(pos);
+            } else if applied_changes.is_duplicate(&commit) {
+                if upstream_head_trees.is_none() {
+                    let head_positions =
+                        index.heads_pos(upstream_set.positions().attach(index).try_collect()?);
+                    let trees: Vec<_> = head_positions
+                        .iter()
+                        .map(|&pos| {
+                            let entry = index.entry_by_pos(pos);
+                            self.store.get_commit(&entry.commit_id())?.tree()
+                        })
+                        .try_collect()?;
+                    upstream_head_trees = Some(trees);
+                }
+                let trees = upstream_head_trees.as_ref().unwrap();
+                if trees
+                    .iter()
+                    .map(|tree| rewrite::is_applied_to_tree(&commit, tree))
+                    .process_results(|mut results| results.any(|applied| applied))?
+                {
+                    positions.push(pos);
+                }
+            }
+        }
+        Ok(EagerRevset { positions })
+    }
 }
 
 struct PurePredicateFn<F>(F);
@@ -1145,6 +1694,7 @@ fn box_pure_predicate_fn<'a>(
 
 fn build_predicate_fn(
     store: Arc<Store>,
+    search_index: Option<&SearchIndex>,
     predicate: &RevsetFilterPredicate,
 ) -> Box<dyn ToPredicateFn> {
     match predicate {
@@ -1157,36 +1707,77 @@ fn build_predicate_fn(
         }
         RevsetFilterPredicate::Description(pattern) => {
             let pattern = pattern.clone();
+            let candidates = text_candidates(search_index, SearchField::Description, &pattern);
             box_pure_predicate_fn(move |index, pos| {
                 let entry = index.entry_by_pos(pos);
+                if !may_match(&candidates, &entry.commit_id()) {
+                    return Ok(false);
+                }
                 let commit = store.get_commit(&entry.commit_id())?;
                 Ok(pattern.matches(commit.description()))
             })
         }
         RevsetFilterPredicate::Subject(pattern) => {
             let pattern = pattern.clone();
+            // The subject is part of the description
+            let candidates = text_candidates(search_index, SearchField::Description, &pattern);
             box_pure_predicate_fn(move |index, pos| {
                 let entry = index.entry_by_pos(pos);
+                if !may_match(&candidates, &entry.commit_id()) {
+                    return Ok(false);
+                }
                 let commit = store.get_commit(&entry.commit_id())?;
                 Ok(pattern.matches(commit.description().lines().next().unwrap_or_default()))
             })
         }
         RevsetFilterPredicate::AuthorName(pattern) => {
             let pattern = pattern.clone();
+            let candidates = text_candidates(search_index, SearchField::Author, &pattern);
             box_pure_predicate_fn(move |index, pos| {
                 let entry = index.entry_by_pos(pos);
+                if !may_match(&candidates, &entry.commit_id()) {
+                    return Ok(false);
+                }
                 let commit = store.get_commit(&entry.commit_id())?;
                 Ok(pattern.matches(&commit.author().name))
             })
         }
         RevsetFilterPredicate::AuthorEmail(pattern) => {
             let pattern = pattern.clone();
+            let candidates = text_candidates(search_index, SearchField::Author, &pattern);
             box_pure_predicate_fn(move |index, pos| {
                 let entry = index.entry_by_pos(pos);
+                if !may_match(&candidates, &entry.commit_id()) {
+                    return Ok(false);
+                }
                 let commit = store.get_commit(&entry.commit_id())?;
                 Ok(pattern.matches(&commit.author().email))
             })
         }
+        RevsetFilterPredicate::MailmapAuthorName(pattern, mailmap) => {
+            // The search index can't be used since it contains the recorded
+            // names.
+            let pattern = pattern.clone();
+            let mailmap = mailmap.clone();
+            box_pure_predicate_fn(move |index, pos| {
+                let entry = index.entry_by_pos(pos);
+                let commit = store.get_commit(&entry.commit_id())?;
+                let author = commit.author();
+                let (name, _) = mailmap.resolve(&author.name, &author.email);
+                Ok(pattern.matches(name))
+            })
+        }
+        RevsetFilterPredicate::MailmapAuthorEmail(pattern, mailmap) => {
+            let pattern = pattern.clone();
+            let mailmap = mailmap.clone();
+            box_pure_predicate_fn(move |index, pos| {
+                let entry = index.entry_by_pos(pos);
+                let commit = store.get_commit(&entry.commit_id())?;
+                let author = commit.author();
+                let (_, email) = mailmap.resolve(&author.name, &author.email);
+                Ok(pattern.matches(email))
+            })
+        }
         RevsetFilterPredicate::AuthorDate(expression) => {
             let expression = *expression;
             box_pure_predicate_fn(move |index, pos| {
@@ -1223,8 +1814,12 @@ fn build_predicate_fn(
         }
         RevsetFilterPredicate::File(expr) => {
             let matcher: Rc<dyn Matcher> = expr.to_matcher().into();
+            let candidates = path_candidates(search_index, expr);
             box_pure_predicate_fn(move |index, pos| {
                 let entry = index.entry_by_pos(pos);
+                if !may_match(&candidates, &entry.commit_id()) {
+                    return Ok(false);
+                }
                 let commit = store.get_commit(&entry.commit_id())?;
                 Ok(has_diff_from_parent(&store, index, &commit, &*matcher)?)
             })
@@ -1232,8 +1827,12 @@ fn build_predicate_fn(
         RevsetFilterPredicate::DiffContains { text, files } => {
             let text_pattern = text.clone();
             let files_matcher: Rc<dyn Matcher> = files.to_matcher().into();
+            let candidates = path_candidates(search_index, files);
             box_pure_predicate_fn(move |index, pos| {
                 let entry = index.entry_by_pos(pos);
+                if !may_match(&candidates, &entry.commit_id()) {
+                    return Ok(false);
+                }
                 let commit = store.get_commit(&entry.commit_id())?;
                 Ok(matches_diff_from_parent(
                     &store,
@@ -1260,6 +1859,31 @@ fn build_predicate_fn(
     }
 }
 
+fn text_candidates(
+    search_index: Option<&SearchIndex>,
+    field: SearchField,
+    pattern: &StringPattern,
+) -> Option<Rc<SearchCandidates>> {
+    let candidates = search_index?.text_candidates(field, pattern)?;
+    Some(Rc::new(candidates))
+}
+
+fn path_candidates(
+    search_index: Option<&SearchIndex>,
+    expression: &FilesetExpression,
+) -> Option<Rc<SearchCandidates>> {
+    let candidates = search_index?.path_candidates(expression)?;
+    Some(Rc::new(candidates))
+}
+
+/// Returns false if the search index rules out the commit, so that the commit
+/// object doesn't have to be read.
+fn may_match(candidates: &Option<Rc<SearchCandidates>>, commit_id: &CommitId) -> bool {
+    candidates
+        .as_ref()
+        .is_none_or(|candidates| candidates.may_match(commit_id))
+}
+
 fn has_diff_from_parent(
     store: &Arc<Store>,
     index: &CompositeIndex,
@@ -17,6 +17,7 @@
 use std::cmp::min;
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
 
@@ -112,6 +113,23 @@ pub(super) struct RevsetGraphWalk<'a> {
     /// Edges for commits not in the input set.
     edges: BTreeMap<IndexPosition, Vec<IndexGraphEdge>>,
     skip_transitive_edges: bool,
+    /// State for following first parents only, if enabled.
+    first_parent: Option<FirstParentState>,
+}
+
+/// Tracks which commits are reached by following the first parents of the
+/// emitted commits.
+#[derive(Default)]
+struct FirstParentState {
+    /// Children of the commits yet to be visited, which are thus not heads.
+    child_positions: HashSet<IndexPosition>,
+    /// Commits in the input set reached from the emitted commits by following
+    /// first parents.
+    reached_positions: HashSet<IndexPosition>,
+    /// Commits in the input set (or `None`) reached by following the first
+    /// parents of commits not in the input set, so each part of a first-parent
+    /// chain is walked only once.
+    chain_targets: HashMap<IndexPosition, Option<IndexPosition>>,
 }
 
 impl<'a> RevsetGraphWalk<'a> {
@@ -122,6 +140,18 @@ impl<'a> RevsetGraphWalk<'a> {
             min_position: IndexPosition::MAX,
             edges: Default::default(),
             skip_transitive_edges,
+            first_parent: None,
+        }
+    }
+
+    /// Creates a walk which only emits the heads of the input set and the
+    /// commits in the input set reached from them by following first parents.
+    /// Each emitted commit has a single edge to the commit reached from its
+    /// first parent.
+    pub fn new_first_parent(input_set_walk: BoxedRevWalk<'a>) -> Self {
+        RevsetGraphWalk {
+            first_parent: Some(FirstParentState::default()),
+            ..Self::new(input_set_walk, true)
         }
     }
 
@@ -317,23 +347,97 @@ impl<'a> RevsetGraphWalk<'a> {
         Ok(())
     }
 
+    /// Returns the edge from the emitted commit `index_entry` to the commit
+    /// in the input set reached by following first parents.
+    fn first_parent_edges(
+        &mut self,
+        index: &CompositeIndex,
+        index_entry: &IndexEntry,
+        edges: &[IndexGraphEdge],
+    ) -> Result<Vec<IndexGraphEdge>, RevsetEvaluationError> {
+        let Some(first_parent) = index_entry.parents().next() else {
+            return Ok(vec![]);
+        };
+        let first_parent_position = first_parent.position();
+        // Missing edges are added if the input set has no ancestors of the
+        // commit
+        if edges
+            .iter()
+            .all(|edge| edge.edge_type == GraphEdgeType::Missing)
+        {
+            return Ok(vec![IndexGraphEdge::missing(first_parent_position)]);
+        }
+        let mut position = first_parent_position;
+        let mut walked_positions = vec![];
+        let reached_position = loop {
+            self.consume_to(index, position)?;
+            if self.look_ahead.binary_search(&position).is_ok() {
+                break Some(position);
+            }
+            let state = self.first_parent.as_ref().unwrap();
+            if let Some(&reached_position) = state.chain_targets.get(&position) {
+                break reached_position;
+            }
+            if position < self.min_position {
+                // There are no more commits in the input set
+                break None;
+            }
+            walked_positions.push(position);
+            match index.entry_by_pos(position).parents().next() {
+                Some(parent) => position = parent.position(),
+                None => break None,
+            }
+        };
+        let state = self.first_parent.as_mut().unwrap();
+        for position in walked_positions {
+            state.chain_targets.insert(position, reached_position);
+        }
+        let edge = match reached_position {
+            Some(position) => {
+                state.reached_positions.insert(position);
+                if position == first_parent_position {
+                    IndexGraphEdge::direct(position)
+                } else {
+                    IndexGraphEdge::indirect(position)
+                }
+            }
+            None => IndexGraphEdge::missing(first_parent_position),
+        };
+        Ok(vec![edge])
+    }
+
     fn try_next(
         &mut self,
         index: &CompositeIndex,
     ) -> Result<Option<GraphNode<CommitId>>, RevsetEvaluationError> {
-        let Some(position) = self.next_index_position(index)? else {
-            return Ok(None);
-        };
-        let entry = index.entry_by_pos(position);
-        let mut edges = self.pop_edges_from_internal_commit(index, &entry)?;
-        if self.skip_transitive_edges {
-            edges = self.remove_transitive_edges(index, edges)?;
+        loop {
+            let Some(position) = self.next_index_position(index)? else {
+                return Ok(None);
+            };
+            let entry = index.entry_by_pos(position);
+            let mut edges = self.pop_edges_from_internal_commit(index, &entry)?;
+            if self.skip_transitive_edges {
+                edges = self.remove_transitive_edges(index, edges)?;
+            }
+            if let Some(state) = &mut self.first_parent {
+                let is_head = !state.child_positions.remove(&position);
+                state.child_positions.extend(
+                    edges
+                        .iter()
+                        .filter(|edge| edge.edge_type != GraphEdgeType::Missing)
+                        .map(|edge| edge.target),
+                );
+                if !state.reached_positions.remove(&position) && !is_head {
+                    continue;
+                }
+                edges = self.first_parent_edges(index, &entry, &edges)?;
+            }
+            let edges = edges
+                .iter()
+                .map(|edge| edge.map(|pos| index.entry_by_pos(pos).commit_id()))
+                .collect();
+            return Ok(Some((entry.commit_id(), edges)));
         }
-        let edges = edges
-            .iter()
-            .map(|edge| edge.map(|pos| index.entry_by_pos(pos).commit_id()))
-            .collect();
-        Ok(Some((entry.commit_id(), edges)))
     }
 }
 
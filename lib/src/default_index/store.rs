@@ -27,6 +27,8 @@ use itertools::Itertools;
 use tempfile::NamedTempFile;
 use thiserror::Error;
 
+use super::composite::CompositeIndex;
+use super::entry::IndexPosition;
 use super::mutable::DefaultMutableIndex;
 use super::readonly::DefaultReadonlyIndex;
 use super::readonly::ReadonlyIndexLoadError;
@@ -47,9 +49,12 @@ use crate::index::IndexWriteError;
 use crate::index::MutableIndex;
 use crate::index::ReadonlyIndex;
 use crate::object_id::ObjectId;
+use crate::op_store::OpStore;
 use crate::op_store::OpStoreError;
 use crate::op_store::OperationId;
 use crate::operation::Operation;
+use crate::search_index::SearchIndex;
+use crate::search_index::SearchIndexError;
 use crate::store::Store;
 
 // BLAKE2b-512 hash length in hex string
@@ -91,6 +96,7 @@ pub enum DefaultIndexStoreError {
 #[derive(Debug)]
 pub struct DefaultIndexStore {
     dir: PathBuf,
+    search_index: Arc<SearchIndex>,
 }
 
 impl DefaultIndexStore {
@@ -99,9 +105,7 @@ impl DefaultIndexStore {
     }
 
     pub fn init(dir: &Path) -> Result<Self, DefaultIndexStoreInitError> {
-        let store = DefaultIndexStore {
-            dir: dir.to_owned(),
-        };
+        let store = DefaultIndexStore::load(dir);
         store.ensure_base_dirs()?;
         Ok(store)
     }
@@ -109,6 +113,7 @@ impl DefaultIndexStore {
     pub fn load(dir: &Path) -> DefaultIndexStore {
         DefaultIndexStore {
             dir: dir.to_owned(),
+            search_index: Arc::new(SearchIndex::new(dir.join("search"))),
         }
     }
 
@@ -133,6 +138,23 @@ impl DefaultIndexStore {
         Ok(())
     }
 
+    /// Full-text search index of commits, which is disabled unless it has
+    /// been enabled by [`SearchIndex::enable()`].
+    ///
+    /// Once enabled, the search index is updated whenever new commits are
+    /// saved to the commit index. Commits indexed before that have to be added
+    /// by [`Self::update_search_index()`].
+    pub fn search_index(&self) -> &Arc<SearchIndex> {
+        &self.search_index
+    }
+
+    /// Search index to be used by revset evaluation.
+    fn enabled_search_index(&self) -> Option<Arc<SearchIndex>> {
+        self.search_index
+            .is_enabled()
+            .then(|| self.search_index.clone())
+    }
+
     fn ensure_base_dirs(&self) -> Result<(), PathError> {
         for dir in [self.operations_dir(), self.segments_dir()] {
             file_util::create_or_reuse_dir(&dir).context(&dir)?;
@@ -166,6 +188,92 @@ impl DefaultIndexStore {
         .map_err(DefaultIndexStoreError::LoadIndex)
     }
 
+    /// Checks that the index segments associated with the operation can be
+    /// loaded. Returns `false` if the operation hasn't been indexed yet.
+    pub fn check_index_at_operation(
+        &self,
+        op_id: &OperationId,
+        store: &Arc<Store>,
+    ) -> Result<bool, DefaultIndexStoreError> {
+        if !self.operations_dir().join(op_id.hex()).is_file() {
+            return Ok(false);
+        }
+        self.load_index_segments_at_operation(
+            op_id,
+            store.commit_id_length(),
+            store.change_id_length(),
+        )?;
+        Ok(true)
+    }
+
+    /// Returns the index at the operation like
+    /// [`IndexStore::get_index_at_op()`], but fails instead of rebuilding
+    /// the index if it's corrupt. The index is built if the operation
+    /// hasn't been indexed yet.
+    pub fn load_index_at_operation(
+        &self,
+        op: &Operation,
+        store: &Arc<Store>,
+    ) -> Result<Box<dyn ReadonlyIndex>, DefaultIndexStoreError> {
+        let index_segment = match self.load_index_segments_at_operation(
+            op.id(),
+            store.commit_id_length(),
+            store.change_id_length(),
+        ) {
+            Err(DefaultIndexStoreError::LoadAssociation(err))
+                if err.kind() == io::ErrorKind::NotFound =>
+            {
+                self.build_index_segments_at_operation(op, store)
+            }
+            result => result,
+        }?;
+        Ok(Box::new(DefaultReadonlyIndex::from_segment(
+            index_segment,
+            self.enabled_search_index(),
+        )))
+    }
+
+    /// Returns the ids of the operations which have an associated index file
+    /// but no longer exist in the `op_store`.
+    pub fn find_stale_operation_links(
+        &self,
+        op_store: &dyn OpStore,
+    ) -> Result<Vec<OperationId>, DefaultIndexStoreError> {
+        let operations_dir = self.operations_dir();
+        let mut op_ids = vec![];
+        for entry in operations_dir
+            .read_dir()
+            .map_err(DefaultIndexStoreError::LoadAssociation)?
+        {
+            let entry = entry.map_err(DefaultIndexStoreError::LoadAssociation)?;
+            let Some(op_id) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| OperationId::try_from_hex(name).ok())
+            else {
+                continue;
+            };
+            match op_store.read_operation(&op_id) {
+                Ok(_) => {}
+                Err(OpStoreError::ObjectNotFound { .. }) => op_ids.push(op_id),
+                Err(err) => return Err(err.into()),
+            }
+        }
+        op_ids.sort();
+        Ok(op_ids)
+    }
+
+    /// Removes the associated index files of the given operations. The index
+    /// segments are left in place since they may be shared with other
+    /// operations.
+    pub fn remove_operation_links(&self, op_ids: &[OperationId]) -> Result<(), PathError> {
+        for op_id in op_ids {
+            let path = self.operations_dir().join(op_id.hex());
+            fs::remove_file(&path).context(&path)?;
+        }
+        Ok(())
+    }
+
     /// Rebuilds index for the given `operation`.
     ///
     /// The index to be built will be calculated from one of the ancestor
@@ -176,7 +284,58 @@ impl DefaultIndexStore {
         store: &Arc<Store>,
     ) -> Result<DefaultReadonlyIndex, DefaultIndexStoreError> {
         let index_segment = self.build_index_segments_at_operation(operation, store)?;
-        Ok(DefaultReadonlyIndex::from_segment(index_segment))
+        Ok(DefaultReadonlyIndex::from_segment(
+            index_segment,
+            self.enabled_search_index(),
+        ))
+    }
+
+    /// Adds the commits of the `index` which haven't been indexed yet to the
+    /// search index. Returns the number of added commits.
+    ///
+    /// Does nothing if the search index is disabled.
+    pub fn update_search_index(
+        &self,
+        index: &CompositeIndex,
+        store: &Arc<Store>,
+    ) -> Result<usize, SearchIndexError> {
+        let search_index = &self.search_index;
+        if !search_index.is_enabled() {
+            return Ok(0);
+        }
+        let Some(segment_name) = index
+            .ancestor_index_segments()
+            .find_map(|segment| segment.name())
+        else {
+            return Ok(0);
+        };
+        let num_commits = index.num_commits();
+        let indexed = search_index.indexed_commit_index_segment()?;
+        // If the commit index was built on top of the indexed segment, the
+        // commits after it are new. Otherwise, the commit index may have been
+        // rebuilt from scratch, so all commits have to be checked.
+        let num_indexed_commits = indexed.as_ref().and_then(|(name, num_commits)| {
+            index
+                .ancestor_index_segments()
+                .any(|segment| segment.name().as_ref() == Some(name))
+                .then_some(*num_commits)
+        });
+        let commit_ids = if let Some(num_indexed_commits) = num_indexed_commits {
+            if indexed.is_some_and(|(name, _)| name == segment_name) {
+                return Ok(0);
+            }
+            (num_indexed_commits..num_commits)
+                .map(|pos| index.entry_by_pos(IndexPosition(pos)).commit_id())
+                .collect_vec()
+        } else {
+            let snapshot = search_index.snapshot()?;
+            (0..num_commits)
+                .map(|pos| index.entry_by_pos(IndexPosition(pos)).commit_id())
+                .filter(|id| !snapshot.contains(id))
+                .collect_vec()
+        };
+        search_index.add_commits(store, &commit_ids, &segment_name, num_commits)?;
+        Ok(commit_ids.len())
     }
 
     #[tracing::instrument(skip(self, store))]
@@ -288,6 +447,7 @@ impl DefaultIndexStore {
         mutable_index: DefaultMutableIndex,
         op_id: &OperationId,
     ) -> Result<Arc<ReadonlyIndexSegment>, DefaultIndexStoreError> {
+        let store = mutable_index.store().cloned();
         let index_segment = mutable_index
             .squash_and_save_in(&self.segments_dir())
             .map_err(DefaultIndexStoreError::SaveIndex)?;
@@ -296,9 +456,20 @@ impl DefaultIndexStore {
                 op_id: op_id.to_owned(),
                 source,
             })?;
+        if let Some(store) = &store {
+            self.update_search_index_or_warn(index_segment.as_composite(), store);
+        }
         Ok(index_segment)
     }
 
+    /// Updates the search index with the newly saved commits. Failure isn't
+    /// fatal since revset evaluation falls back to loading the commits.
+    fn update_search_index_or_warn(&self, index: &CompositeIndex, store: &Arc<Store>) {
+        if let Err(err) = self.update_search_index(index, store) {
+            tracing::warn!(?err, "failed to update search index");
+        }
+    }
+
     /// Records a link from the given operation to the this index version.
     fn associate_file_with_operation(
         &self,
@@ -361,7 +532,10 @@ impl IndexStore for DefaultIndexStore {
             result => result,
         }
         .map_err(|err| IndexReadError(err.into()))?;
-        Ok(Box::new(DefaultReadonlyIndex::from_segment(index_segment)))
+        Ok(Box::new(DefaultReadonlyIndex::from_segment(
+            index_segment,
+            self.enabled_search_index(),
+        )))
     }
 
     fn write_index(
@@ -376,6 +550,9 @@ impl IndexStore for DefaultIndexStore {
         let index_segment = self
             .save_mutable_index(*index, op.id())
             .map_err(|err| IndexWriteError(err.into()))?;
-        Ok(Box::new(DefaultReadonlyIndex::from_segment(index_segment)))
+        Ok(Box::new(DefaultReadonlyIndex::from_segment(
+            index_segment,
+            self.enabled_search_index(),
+        )))
     }
 }
@@ -0,0 +1,340 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Repository integrity checks.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::fmt::Write as _;
+use std::io::Read as _;
+use std::sync::Arc;
+
+use crate::backend::BackendError;
+use crate::backend::CommitId;
+use crate::backend::FileId;
+use crate::backend::TreeId;
+use crate::backend::TreeValue;
+use crate::default_index::DefaultIndexStore;
+use crate::index::Index;
+use crate::index::ReadonlyIndex;
+use crate::object_id::ObjectId;
+use crate::op_store::OpStore;
+use crate::op_store::OperationId;
+use crate::operation::Operation;
+use crate::repo::RepoLoader;
+use crate::repo_path::RepoPathBuf;
+use crate::store::Store;
+use crate::view::View;
+
+/// Category of a problem found by [`check_repo()`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum FsckProblemKind {
+    /// An operation or its view couldn't be read from the operation store.
+    Operation,
+    /// A commit couldn't be read from the backend.
+    Commit,
+    /// A tree couldn't be read from the backend.
+    Tree,
+    /// A file or symlink couldn't be read from the backend.
+    File,
+    /// The content of a stored object doesn't match its id.
+    Hash,
+    /// The commit index couldn't be loaded, is missing a commit, or disagrees
+    /// with its parents.
+    Index,
+}
+
+impl FsckProblemKind {
+    /// Short machine-readable name of the problem category.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FsckProblemKind::Operation => "operation",
+            FsckProblemKind::Commit => "commit",
+            FsckProblemKind::Tree => "tree",
+            FsckProblemKind::File => "file",
+            FsckProblemKind::Hash => "hash",
+            FsckProblemKind::Index => "index",
+        }
+    }
+}
+
+impl fmt::Display for FsckProblemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Integrity problem found by [`check_repo()`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FsckProblem {
+    /// Category of the problem.
+    pub kind: FsckProblemKind,
+    /// Hex id of the affected object.
+    pub object_id: String,
+    /// Human-readable description of the problem.
+    pub message: String,
+}
+
+/// Result of [`check_repo()`].
+#[derive(Clone, Debug, Default)]
+pub struct FsckReport {
+    /// Number of operations checked.
+    pub num_operations: usize,
+    /// Number of commits checked.
+    pub num_commits: usize,
+    /// Number of trees checked.
+    pub num_trees: usize,
+    /// Number of files and symlinks checked.
+    pub num_files: usize,
+    /// Problems found, in discovery order.
+    pub problems: Vec<FsckProblem>,
+}
+
+impl FsckReport {
+    /// Returns true if no problems were found.
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Verifies the integrity of the operation log and all objects reachable from
+/// it.
+///
+/// Every operation reachable from the operation `op` is loaded along with its
+/// view. All commits referenced by those views and their ancestors are then
+/// read together with their trees and file contents, and checked against the
+/// commit index. If the backend supports it, object contents are also checked
+/// against their ids.
+///
+/// Problems are collected in the returned report instead of aborting the
+/// check, so a single corrupted object doesn't hide the others. The repo
+/// doesn't have to be loadable: if the commit index can't be loaded, that's
+/// reported as a problem and the other checks still run.
+pub fn check_repo(repo_loader: &RepoLoader, op: &Operation) -> FsckReport {
+    let mut report = FsckReport::default();
+    let index = match load_index(repo_loader, op) {
+        Ok(index) => Some(index),
+        Err(message) => {
+            report.problems.push(FsckProblem {
+                kind: FsckProblemKind::Index,
+                object_id: op.id().hex(),
+                message,
+            });
+            None
+        }
+    };
+    let mut checker = Checker::new(
+        repo_loader.store(),
+        index.as_ref().map(|index| index.as_index()),
+        report,
+    );
+    let referenced_commit_ids = checker.check_operations(repo_loader.op_store().as_ref(), op.id());
+    checker.check_commits(referenced_commit_ids);
+    checker.report
+}
+
+/// Loads the commit index at the `op` without rebuilding it if it's corrupt.
+fn load_index(repo_loader: &RepoLoader, op: &Operation) -> Result<Box<dyn ReadonlyIndex>, String> {
+    let index_store = repo_loader.index_store();
+    let result = if let Some(default_index_store) =
+        index_store.as_any().downcast_ref::<DefaultIndexStore>()
+    {
+        default_index_store
+            .load_index_at_operation(op, repo_loader.store())
+            .map_err(|err| err.into())
+    } else {
+        index_store
+            .get_index_at_op(op, repo_loader.store())
+            .map_err(|err| err.into())
+    };
+    result.map_err(|err: Box<dyn std::error::Error>| {
+        let mut message = err.to_string();
+        let mut source = err.source();
+        while let Some(err) = source {
+            write!(message, ": {err}").unwrap();
+            source = err.source();
+        }
+        message
+    })
+}
+
+struct Checker<'a> {
+    store: &'a Arc<Store>,
+    /// The commit index, or `None` if it couldn't be loaded
+    index: Option<&'a dyn Index>,
+    report: FsckReport,
+    visited_trees: HashSet<TreeId>,
+    visited_files: HashSet<FileId>,
+}
+
+impl<'a> Checker<'a> {
+    fn new(store: &'a Arc<Store>, index: Option<&'a dyn Index>, report: FsckReport) -> Self {
+        Checker {
+            store,
+            index,
+            report,
+            visited_trees: HashSet::new(),
+            visited_files: HashSet::new(),
+        }
+    }
+
+    fn add_problem(&mut self, kind: FsckProblemKind, id: &impl ObjectId, message: String) {
+        self.report.problems.push(FsckProblem {
+            kind,
+            object_id: id.hex(),
+            message,
+        });
+    }
+
+    /// Walks the operation log, returning the commits referenced by all views.
+    fn check_operations(&mut self, op_store: &dyn OpStore, op_id: &OperationId) -> Vec<CommitId> {
+        let mut referenced_commit_ids = vec![];
+        let mut visited: HashSet<OperationId> = HashSet::new();
+        let mut pending = vec![op_id.clone()];
+        while let Some(op_id) = pending.pop() {
+            if !visited.insert(op_id.clone()) {
+                continue;
+            }
+            self.report.num_operations += 1;
+            let op = match op_store.read_operation(&op_id) {
+                Ok(op) => op,
+                Err(err) => {
+                    self.add_problem(FsckProblemKind::Operation, &op_id, err.to_string());
+                    continue;
+                }
+            };
+            match op_store.read_view(&op.view_id) {
+                Ok(view) => {
+                    let view = View::new(view);
+                    referenced_commit_ids.extend(view.all_referenced_commit_ids().cloned());
+                }
+                Err(err) => {
+                    self.add_problem(FsckProblemKind::Operation, &op_id, err.to_string());
+                }
+            }
+            pending.extend(op.parents.iter().rev().cloned());
+        }
+        referenced_commit_ids
+    }
+
+    fn check_commits(&mut self, head_ids: Vec<CommitId>) {
+        let mut visited: HashSet<CommitId> = HashSet::new();
+        let mut pending = head_ids;
+        while let Some(id) = pending.pop() {
+            if !visited.insert(id.clone()) {
+                continue;
+            }
+            self.report.num_commits += 1;
+            if self.index.is_some_and(|index| !index.has_id(&id)) {
+                let message = "Commit is missing from the index".to_owned();
+                self.add_problem(FsckProblemKind::Index, &id, message);
+            }
+            if &id == self.store.root_commit_id() {
+                continue;
+            }
+            let commit = match self.store.get_commit(&id) {
+                Ok(commit) => commit,
+                Err(err) => {
+                    self.add_problem(FsckProblemKind::Commit, &id, err.to_string());
+                    continue;
+                }
+            };
+            self.check_object_hash(&id);
+            for parent_id in commit.parent_ids() {
+                if self.index.is_some_and(|index| {
+                    index.has_id(parent_id)
+                        && index.has_id(&id)
+                        && !index.is_ancestor(parent_id, &id)
+                }) {
+                    let message = format!(
+                        "Index doesn't record parent {} as an ancestor",
+                        parent_id.hex()
+                    );
+                    self.add_problem(FsckProblemKind::Index, &id, message);
+                }
+            }
+            for tree_id in commit.tree_id().to_merge().iter() {
+                self.check_tree(RepoPathBuf::root(), tree_id);
+            }
+            pending.extend(commit.parent_ids().iter().rev().cloned());
+        }
+    }
+
+    fn check_tree(&mut self, dir: RepoPathBuf, id: &TreeId) {
+        if id == self.store.empty_tree_id() || !self.visited_trees.insert(id.clone()) {
+            return;
+        }
+        self.report.num_trees += 1;
+        let tree = match self.store.get_tree(dir, id) {
+            Ok(tree) => tree,
+            Err(err) => {
+                self.add_problem(FsckProblemKind::Tree, id, err.to_string());
+                return;
+            }
+        };
+        self.check_object_hash(id);
+        for entry in tree.entries_non_recursive() {
+            let path = tree.dir().join(entry.name());
+            match entry.value() {
+                TreeValue::File { id, .. } => {
+                    if !self.visited_files.insert(id.clone()) {
+                        continue;
+                    }
+                    self.report.num_files += 1;
+                    let result = self.store.read_file(&path, id).and_then(|mut reader| {
+                        reader
+                            .read_to_end(&mut vec![])
+                            .map_err(|err| BackendError::ReadFile {
+                                path: path.clone(),
+                                id: id.clone(),
+                                source: err.into(),
+                            })
+                    });
+                    match result {
+                        Ok(_) => self.check_object_hash(id),
+                        Err(err) => self.add_problem(FsckProblemKind::File, id, err.to_string()),
+                    }
+                }
+                TreeValue::Symlink(id) => {
+                    self.report.num_files += 1;
+                    match self.store.read_symlink(&path, id) {
+                        Ok(_) => self.check_object_hash(id),
+                        Err(err) => self.add_problem(FsckProblemKind::File, id, err.to_string()),
+                    }
+                }
+                TreeValue::Tree(id) => self.check_tree(path, id),
+                TreeValue::GitSubmodule(_) | TreeValue::Conflict(_) => {}
+            }
+        }
+    }
+
+    #[cfg(feature = "git")]
+    fn check_object_hash(&mut self, id: &impl ObjectId) {
+        use crate::git_backend::GitBackend;
+        let Some(git_backend) = self.store.backend_impl().downcast_ref::<GitBackend>() else {
+            return;
+        };
+        match git_backend.verify_object_hash(id) {
+            Ok(true) => {}
+            Ok(false) => {
+                let message = format!("Content of {} doesn't match its id", id.object_type());
+                self.add_problem(FsckProblemKind::Hash, id, message);
+            }
+            Err(err) => self.add_problem(FsckProblemKind::Hash, id, err.to_string()),
+        }
+    }
+
+    #[cfg(not(feature = "git"))]
+    fn check_object_hash(&mut self, _id: &impl ObjectId) {}
+}
@@ -23,7 +23,9 @@ use std::fmt;
 use std::io::Read;
 use std::num::NonZeroU32;
 use std::path::PathBuf;
+use std::rc::Rc;
 use std::str;
+use std::sync::Arc;
 
 use bstr::BStr;
 use itertools::Itertools;
@@ -33,6 +35,7 @@ use thiserror::Error;
 use crate::backend::BackendError;
 use crate::backend::BackendResult;
 use crate::backend::CommitId;
+use crate::backend::Signature;
 use crate::backend::TreeValue;
 use crate::commit::Commit;
 use crate::git_backend::GitBackend;
@@ -49,10 +52,15 @@ use crate::refs;
 use crate::refs::BookmarkPushUpdate;
 use crate::refs::RemoteRefSymbol;
 use crate::refs::RemoteRefSymbolBuf;
+use crate::repo::IndexingProgress;
 use crate::repo::MutableRepo;
 use crate::repo::Repo;
 use crate::repo_path::RepoPath;
+use crate::revset::ResolvedRevsetExpression;
 use crate::revset::RevsetExpression;
+use crate::revset::RevsetIteratorExt as _;
+use crate::rewrite::compute_patch_id;
+use crate::settings::GitFetchTags;
 use crate::settings::GitSettings;
 use crate::store::Store;
 use crate::str_util::StringPattern;
@@ -125,6 +133,14 @@ impl RefSpec {
         }
     }
 
+    fn fast_forward(source: impl Into<String>, destination: impl Into<String>) -> Self {
+        RefSpec {
+            forced: false,
+            source: Some(source.into()),
+            destination: destination.into(),
+        }
+    }
+
     fn delete(destination: impl Into<String>) -> Self {
         // We don't force push on branch deletion
         RefSpec {
@@ -343,6 +359,9 @@ impl GitImportError {
 pub struct GitImportStats {
     /// Commits superseded by newly imported commits.
     pub abandoned_commits: Vec<CommitId>,
+    /// `(old_id, new_id)`s of commits that were rewritten on the remote. The
+    /// old commits are recorded as rewritten to the new ones.
+    pub reconciled_commits: Vec<(CommitId, CommitId)>,
     /// Remote `(ref_name, (old_remote_ref, new_target))`s to be merged in to
     /// the local refs.
     pub changed_remote_refs: BTreeMap<RefName, (RemoteRef, RefTarget)>,
@@ -376,6 +395,15 @@ pub fn import_some_refs(
     mut_repo: &mut MutableRepo,
     git_settings: &GitSettings,
     git_ref_filter: impl Fn(&RefName) -> bool,
+) -> Result<GitImportStats, GitImportError> {
+    import_some_refs_with_progress(mut_repo, git_settings, git_ref_filter, None)
+}
+
+fn import_some_refs_with_progress(
+    mut_repo: &mut MutableRepo,
+    git_settings: &GitSettings,
+    git_ref_filter: impl Fn(&RefName) -> bool,
+    progress: Option<&mut dyn FnMut(&IndexingProgress)>,
 ) -> Result<GitImportStats, GitImportError> {
     let store = mut_repo.store();
     let git_backend = get_git_backend(store)?;
@@ -420,7 +448,7 @@ pub fn import_some_refs(
     // It's unlikely the imported commits were missing, but I/O-related error
     // can still occur.
     mut_repo
-        .add_heads(&head_commits)
+        .add_heads_with_progress(&head_commits, progress)
         .map_err(GitImportError::InternalBackend)?;
 
     // Apply the change that happened in git since last time we imported refs.
@@ -473,8 +501,20 @@ pub fn import_some_refs(
     } else {
         vec![]
     };
+    let reconciled_commits = if git_settings.reconcile_rewritten_commits {
+        reconcile_rewritten_commits(
+            mut_repo,
+            &git_repo,
+            &changed_remote_refs,
+            &abandoned_commits,
+        )
+        .map_err(GitImportError::InternalBackend)?
+    } else {
+        vec![]
+    };
     let stats = GitImportStats {
         abandoned_commits,
+        reconciled_commits,
         changed_remote_refs,
     };
     Ok(stats)
@@ -519,6 +559,110 @@ fn abandon_unreachable_commits(
     Ok(abandoned_commit_ids)
 }
 
+/// Finds commits that were on a remote bookmark before it moved, and which
+/// have a rewritten version on the bookmark now. Those commits will be recorded
+/// as rewritten in the `MutableRepo`, so local commits on top of them don't
+/// keep the old version alive next to the new one.
+///
+/// The rewritten version is looked up by the `change-id` header written by
+/// some tools, and then by the patch.
+fn reconcile_rewritten_commits(
+    mut_repo: &mut MutableRepo,
+    git_repo: &gix::Repository,
+    changed_remote_refs: &BTreeMap<RefName, (RemoteRef, RefTarget)>,
+    abandoned_commits: &[CommitId],
+) -> BackendResult<Vec<(CommitId, CommitId)>> {
+    let abandoned_commits: HashSet<&CommitId> = abandoned_commits.iter().collect();
+    let mut reconciled_ids: HashSet<CommitId> = HashSet::new();
+    let mut reconciled_new_ids: HashSet<CommitId> = HashSet::new();
+    let mut reconciled_commits = vec![];
+    for (ref_name, (old_remote_ref, new_target)) in changed_remote_refs {
+        if !matches!(ref_name, RefName::RemoteBranch(_)) {
+            continue;
+        }
+        let old_heads =
+            RevsetExpression::commits(old_remote_ref.target.added_ids().cloned().collect());
+        let new_heads = RevsetExpression::commits(new_target.added_ids().cloned().collect());
+        let evaluate = |expression: Rc<ResolvedRevsetExpression>| -> BackendResult<Vec<Commit>> {
+            expression
+                .evaluate(mut_repo)
+                .map_err(|err| err.expect_backend_error())?
+                .iter()
+                .commits(mut_repo.store())
+                .try_collect()
+                .map_err(|err| err.expect_backend_error())
+        };
+        // Commits which were only on the old remote bookmark, and which are
+        // still visible because something local depends on them. This is
+        // usually empty, so check it before diffing the new commits.
+        let old_commits = evaluate(
+            new_heads
+                .range(&old_heads)
+                .intersection(&RevsetExpression::visible_heads().ancestors()),
+        )?
+        .into_iter()
+        .filter(|commit| {
+            !abandoned_commits.contains(commit.id()) && !reconciled_ids.contains(commit.id())
+        })
+        .collect_vec();
+        if old_commits.is_empty() {
+            continue;
+        }
+        // Commits which are only on the new remote bookmark, by change-id
+        // header and by patch id. Keys that appear more than once are
+        // ambiguous.
+        let mut new_commits_by_change: HashMap<Vec<u8>, Option<CommitId>> = HashMap::new();
+        let mut new_commits_by_patch: HashMap<Vec<u8>, Option<CommitId>> = HashMap::new();
+        let insert_unique = |map: &mut HashMap<_, Option<CommitId>>, key, id: &CommitId| {
+            map.entry(key)
+                .and_modify(|entry| *entry = None)
+                .or_insert_with(|| Some(id.clone()));
+        };
+        for commit in evaluate(old_heads.range(&new_heads))? {
+            if let Some(change_id) = read_change_id_header(git_repo, commit.id()) {
+                insert_unique(&mut new_commits_by_change, change_id, commit.id());
+            }
+            if let Some(patch_id) = compute_patch_id(&commit)? {
+                insert_unique(&mut new_commits_by_patch, patch_id, commit.id());
+            }
+        }
+        for commit in old_commits {
+            let new_id_by_change = read_change_id_header(git_repo, commit.id())
+                .and_then(|change_id| new_commits_by_change.get(&change_id).cloned().flatten());
+            let new_id = match new_id_by_change {
+                Some(new_id) => new_id,
+                None => {
+                    let Some(patch_id) = compute_patch_id(&commit)? else {
+                        continue;
+                    };
+                    let Some(Some(new_id)) = new_commits_by_patch.get(&patch_id).cloned() else {
+                        continue;
+                    };
+                    new_id
+                }
+            };
+            // Each new commit replaces at most one old commit.
+            if !reconciled_new_ids.insert(new_id.clone()) {
+                continue;
+            }
+            mut_repo.set_rewritten_commit(commit.id().clone(), new_id.clone());
+            reconciled_ids.insert(commit.id().clone());
+            reconciled_commits.push((commit.id().clone(), new_id));
+        }
+    }
+    Ok(reconciled_commits)
+}
+
+/// Returns the value of the `change-id` header of the Git commit, if any.
+fn read_change_id_header(git_repo: &gix::Repository, id: &CommitId) -> Option<Vec<u8>> {
+    let commit = git_repo
+        .find_commit(gix::ObjectId::from_bytes_or_panic(id.as_bytes()))
+        .ok()?;
+    let commit = commit.decode().ok()?;
+    let change_id = commit.extra_headers().find("change-id")?;
+    Some(change_id.to_vec())
+}
+
 /// Calculates diff of git refs to be imported.
 fn diff_refs_to_import(
     view: &View,
@@ -803,41 +947,15 @@ pub fn export_some_refs(
         &git_ref_filter,
     );
 
-    // TODO: Also check other worktrees' HEAD.
-    if let Ok(head_ref) = git_repo.find_reference("HEAD") {
-        if let Some(parsed_ref) = head_ref
-            .target()
-            .try_name()
-            .and_then(|name| str::from_utf8(name.as_bstr()).ok())
-            .and_then(parse_git_ref)
-        {
-            let old_target = head_ref.inner.target.clone();
-            let current_oid = match head_ref.into_fully_peeled_id() {
-                Ok(id) => Some(id.detach()),
-                Err(gix::reference::peel::Error::ToId(
-                    gix::refs::peel::to_id::Error::FollowToObject(
-                        gix::refs::peel::to_object::Error::Follow(
-                            gix::refs::file::find::existing::Error::NotFound { .. },
-                        ),
-                    ),
-                )) => None, // Unborn ref should be considered absent
-                Err(err) => return Err(GitExportError::from_git(err)),
-            };
-            let new_oid = if let Some((_old_oid, new_oid)) = branches_to_update.get(&parsed_ref) {
-                Some(new_oid)
-            } else if branches_to_delete.contains_key(&parsed_ref) {
-                None
-            } else {
-                current_oid.as_ref()
-            };
-            if new_oid != current_oid.as_ref() {
-                update_git_head(
-                    &git_repo,
-                    gix::refs::transaction::PreviousValue::MustExistAndMatch(old_target),
-                    current_oid,
-                )?;
-            }
-        }
+    // Git doesn't allow moving or deleting a branch checked out in any of the
+    // worktrees, so detach their HEADs first.
+    detach_head_of_exported_branch(&git_repo, &branches_to_update, &branches_to_delete)?;
+    for worktree in git_repo.worktrees().map_err(GitExportError::from_git)? {
+        // The worktree directory might have been removed without pruning it.
+        let Ok(worktree_repo) = worktree.into_repo_with_possibly_inaccessible_worktree() else {
+            continue;
+        };
+        detach_head_of_exported_branch(&worktree_repo, &branches_to_update, &branches_to_delete)?;
     }
     for (parsed_ref_name, old_oid) in branches_to_delete {
         let Some(git_ref_name) = to_git_ref_name(&parsed_ref_name) else {
@@ -878,6 +996,56 @@ pub fn export_some_refs(
     Ok(failed_branches)
 }
 
+/// Detaches the HEAD of the given repo or worktree if it points to a branch to
+/// be updated or deleted.
+fn detach_head_of_exported_branch(
+    git_repo: &gix::Repository,
+    branches_to_update: &BTreeMap<RefName, (Option<gix::ObjectId>, gix::ObjectId)>,
+    branches_to_delete: &BTreeMap<RefName, gix::ObjectId>,
+) -> Result<(), GitExportError> {
+    let Ok(head_ref) = git_repo.find_reference("HEAD") else {
+        return Ok(());
+    };
+    let Some(parsed_ref) = head_ref
+        .target()
+        .try_name()
+        .and_then(|name| str::from_utf8(name.as_bstr()).ok())
+        .and_then(parse_git_ref)
+    else {
+        return Ok(());
+    };
+    let old_target = head_ref.inner.target.clone();
+    let current_oid = match head_ref.into_fully_peeled_id() {
+        Ok(id) => Some(id.detach()),
+        Err(gix::reference::peel::Error::ToId(gix::refs::peel::to_id::Error::FollowToObject(
+            gix::refs::peel::to_object::Error::Follow(
+                gix::refs::file::find::existing::Error::NotFound { .. },
+            ),
+        ))) => None, // Unborn ref should be considered absent
+        Err(err) => return Err(GitExportError::from_git(err)),
+    };
+    let new_oid = if let Some((_old_oid, new_oid)) = branches_to_update.get(&parsed_ref) {
+        Some(new_oid)
+    } else if branches_to_delete.contains_key(&parsed_ref) {
+        None
+    } else {
+        current_oid.as_ref()
+    };
+    if new_oid != current_oid.as_ref() {
+        let message = format!(
+            "export from jj: detaching HEAD from {}",
+            to_git_ref_name(&parsed_ref).unwrap_or_default()
+        );
+        update_git_head(
+            git_repo,
+            gix::refs::transaction::PreviousValue::MustExistAndMatch(old_target),
+            current_oid,
+            message,
+        )?;
+    }
+    Ok(())
+}
+
 fn copy_exportable_local_branches_to_remote_view(
     mut_repo: &mut MutableRepo,
     remote: &str,
@@ -1033,18 +1201,23 @@ fn update_git_ref(
                         git_ref_name,
                         new_oid,
                         gix::refs::transaction::PreviousValue::MustNotExist,
-                        "export from jj",
+                        format!("export from jj: created at {}", new_oid.to_hex_with_len(12)),
                     )
                     .map_err(|err| FailedRefExportReason::FailedToSet(err.into()))?;
             }
         }
         Some(old_oid) => {
             // The branch was modified in jj. We can use gix API for updating under a lock.
+            let message = format!(
+                "export from jj: moving from {} to {}",
+                old_oid.to_hex_with_len(12),
+                new_oid.to_hex_with_len(12)
+            );
             if let Err(err) = git_repo.reference(
                 git_ref_name,
                 new_oid,
                 gix::refs::transaction::PreviousValue::MustExistAndMatch(old_oid.into()),
-                "export from jj",
+                message,
             ) {
                 // The reference was probably updated in git
                 if let Ok(git_repo_ref) = git_repo.find_reference(git_ref_name) {
@@ -1066,11 +1239,13 @@ fn update_git_ref(
 }
 
 /// Ensures Git HEAD is detached and pointing to the `new_oid`. If `new_oid`
-/// is `None` (meaning absent), dummy placeholder ref will be set.
+/// is `None` (meaning absent), dummy placeholder ref will be set. The
+/// `message` is recorded in the HEAD reflog.
 fn update_git_head(
     git_repo: &gix::Repository,
     expected_ref: gix::refs::transaction::PreviousValue,
     new_oid: Option<gix::ObjectId>,
+    message: String,
 ) -> Result<(), GitExportError> {
     let mut ref_edits = Vec::new();
     let new_target = if let Some(oid) = new_oid {
@@ -1093,7 +1268,7 @@ fn update_git_head(
     ref_edits.push(gix::refs::transaction::RefEdit {
         change: gix::refs::transaction::Change::Update {
             log: gix::refs::transaction::LogChange {
-                message: "export from jj".into(),
+                message: message.into(),
                 ..Default::default()
             },
             expected: expected_ref,
@@ -1142,7 +1317,14 @@ pub fn reset_head(mut_repo: &mut MutableRepo, wc_commit: &Commit) -> Result<(),
         let new_oid = new_head_target
             .as_normal()
             .map(|id| gix::ObjectId::from_bytes_or_panic(id.as_bytes()));
-        update_git_head(&git_repo, expected_ref, new_oid)?;
+        let message = match new_oid {
+            Some(oid) => format!(
+                "export from jj: moving working copy onto {}",
+                oid.to_hex_with_len(12)
+            ),
+            None => "export from jj: moving working copy onto root commit".to_owned(),
+        };
+        update_git_head(&git_repo, expected_ref, new_oid, message)?;
         mut_repo.set_git_head_target(new_head_target);
     }
 
@@ -1465,6 +1647,50 @@ pub fn set_remote_url(
     Ok(())
 }
 
+/// Sets the default branch of the remote, which is recorded as the
+/// `refs/remotes/<remote>/HEAD` symbolic ref like `git remote set-head` does.
+/// If `branch_name` is `None`, the symbolic ref is deleted.
+pub fn set_remote_head(
+    git_repo: &git2::Repository,
+    remote_name: &str,
+    branch_name: Option<&str>,
+) -> Result<(), GitRemoteManagementError> {
+    git_repo.find_remote(remote_name).map_err(|err| {
+        if is_remote_not_found_err(&err) {
+            GitRemoteManagementError::NoSuchRemote(remote_name.to_owned())
+        } else {
+            GitRemoteManagementError::InternalGitError(err)
+        }
+    })?;
+
+    let head_ref_name = format!("refs/remotes/{remote_name}/HEAD");
+    if let Some(branch_name) = branch_name {
+        git_repo
+            .reference_symbolic(
+                &head_ref_name,
+                &format!("refs/remotes/{remote_name}/{branch_name}"),
+                true,
+                "set-head from jj",
+            )
+            .map_err(GitRemoteManagementError::InternalGitError)?;
+    } else if let Ok(mut head_ref) = git_repo.find_reference(&head_ref_name) {
+        head_ref
+            .delete()
+            .map_err(GitRemoteManagementError::InternalGitError)?;
+    }
+    Ok(())
+}
+
+/// Returns the default branch of the remote recorded by `set_remote_head()`.
+pub fn get_remote_head(git_repo: &git2::Repository, remote_name: &str) -> Option<String> {
+    let head_ref = git_repo
+        .find_reference(&format!("refs/remotes/{remote_name}/HEAD"))
+        .ok()?;
+    let target = head_ref.symbolic_target()?;
+    let branch_name = target.strip_prefix(&format!("refs/remotes/{remote_name}/"))?;
+    Some(branch_name.to_owned())
+}
+
 fn rename_remote_refs(mut_repo: &mut MutableRepo, old_remote_name: &str, new_remote_name: &str) {
     mut_repo.rename_remote(old_remote_name, new_remote_name);
     let prefix = format!("refs/remotes/{old_remote_name}/");
@@ -1488,6 +1714,370 @@ fn rename_remote_refs(mut_repo: &mut MutableRepo, old_remote_name: &str, new_rem
     }
 }
 
+/// Notes ref used by `git notes` when no other ref is configured.
+pub const DEFAULT_NOTES_REF: &str = "refs/notes/commits";
+
+#[derive(Debug, Error)]
+pub enum GitNotesError {
+    #[error("Invalid notes ref name: {0}")]
+    InvalidNotesRef(String),
+    #[error("Commit {0} already has a note in {1}")]
+    NoteAlreadyExists(String, String),
+    #[error(transparent)]
+    UnexpectedBackend(#[from] UnexpectedGitBackendError),
+    #[error("Unexpected git error when accessing notes")]
+    InternalGitError(#[from] git2::Error),
+}
+
+fn validate_notes_ref(notes_ref: &str) -> Result<(), GitNotesError> {
+    if notes_ref.starts_with("refs/notes/") && git2::Reference::is_valid_name(notes_ref) {
+        Ok(())
+    } else {
+        Err(GitNotesError::InvalidNotesRef(notes_ref.to_owned()))
+    }
+}
+
+fn open_git2_repo(store: &Store) -> Result<git2::Repository, GitNotesError> {
+    Ok(get_git_backend(store)?.open_git_repo()?)
+}
+
+/// Reads all notes stored under `notes_ref`, keyed by annotated commit id.
+///
+/// Returns an empty map if the notes ref doesn't exist. Notes that aren't
+/// valid UTF-8 are decoded lossily.
+pub fn read_notes(
+    store: &Store,
+    notes_ref: &str,
+) -> Result<HashMap<CommitId, String>, GitNotesError> {
+    validate_notes_ref(notes_ref)?;
+    let git_repo = open_git2_repo(store)?;
+    let notes = match git_repo.notes(Some(notes_ref)) {
+        Ok(notes) => notes,
+        Err(err) if err.code() == git2::ErrorCode::NotFound => return Ok(HashMap::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let mut map = HashMap::new();
+    for entry in notes {
+        let (note_blob_id, annotated_id) = entry?;
+        let blob = git_repo.find_blob(note_blob_id)?;
+        let message = String::from_utf8_lossy(blob.content()).into_owned();
+        map.insert(CommitId::from_bytes(annotated_id.as_bytes()), message);
+    }
+    Ok(map)
+}
+
+/// Reads the note attached to `commit_id` under `notes_ref`, if any.
+pub fn read_note(
+    store: &Store,
+    notes_ref: &str,
+    commit_id: &CommitId,
+) -> Result<Option<String>, GitNotesError> {
+    validate_notes_ref(notes_ref)?;
+    let git_repo = open_git2_repo(store)?;
+    let oid = git2::Oid::from_bytes(commit_id.as_bytes())?;
+    let message = match git_repo.find_note(Some(notes_ref), oid) {
+        Ok(note) => Some(String::from_utf8_lossy(note.message_bytes()).into_owned()),
+        Err(err) if err.code() == git2::ErrorCode::NotFound => None,
+        Err(err) => return Err(err.into()),
+    };
+    Ok(message)
+}
+
+/// Attaches `message` to `commit_id` under `notes_ref` in the underlying Git
+/// repo.
+///
+/// The notes ref is updated directly in the Git repo, so the note is visible
+/// to `git notes` and can be pushed along with other refs. If `force` is
+/// false, an existing note is an error; otherwise it is replaced.
+pub fn write_note(
+    store: &Store,
+    notes_ref: &str,
+    commit_id: &CommitId,
+    message: &str,
+    signature: &Signature,
+    force: bool,
+) -> Result<(), GitNotesError> {
+    validate_notes_ref(notes_ref)?;
+    let git_repo = open_git2_repo(store)?;
+    let oid = git2::Oid::from_bytes(commit_id.as_bytes())?;
+    let time = git2::Time::new(
+        signature.timestamp.timestamp.0.div_euclid(1000),
+        signature.timestamp.tz_offset,
+    );
+    let git_signature = git2::Signature::new(&signature.name, &signature.email, &time)?;
+    git_repo
+        .note(
+            &git_signature,
+            &git_signature,
+            Some(notes_ref),
+            oid,
+            message,
+            force,
+        )
+        .map_err(|err| {
+            if err.code() == git2::ErrorCode::Exists {
+                GitNotesError::NoteAlreadyExists(commit_id.hex(), notes_ref.to_owned())
+            } else {
+                GitNotesError::InternalGitError(err)
+            }
+        })?;
+    Ok(())
+}
+
+/// Pushes the local `notes_ref` to the same ref on the remote.
+///
+/// Notes refs aren't tracked by jj, so there's no expected remote location to
+/// check against. The push is only accepted if it fast-forwards the remote
+/// notes ref, which preserves notes added to the remote by others. Returns
+/// `false` if there's no local notes ref to push.
+pub fn push_notes(
+    store: &Store,
+    git_settings: &GitSettings,
+    remote_name: &str,
+    notes_ref: &str,
+    mut callbacks: RemoteCallbacks<'_>,
+) -> Result<bool, GitPushError> {
+    validate_remote_name(remote_name)?;
+    let git_backend = get_git_backend(store)?;
+    let git_repo = git_backend.git_repo();
+    if git_repo
+        .try_find_reference(notes_ref)
+        .ok()
+        .flatten()
+        .is_none()
+    {
+        return Ok(false);
+    }
+    let refspec = RefSpec::fast_forward(notes_ref, notes_ref);
+    let rejected_refs = if git_settings.subprocess {
+        if git_repo.try_find_remote(remote_name).is_none() {
+            return Err(GitPushError::NoSuchRemote(remote_name.to_owned()));
+        }
+        let git_ctx =
+            GitSubprocessContext::from_git_backend(git_backend, &git_settings.executable_path);
+        let (rejected_refs, _pushed_refs) =
+            git_ctx.spawn_push_refspecs(remote_name, &[refspec], &mut callbacks)?;
+        rejected_refs
+    } else {
+        let git_repo = git_backend.open_git_repo()?;
+        let mut remote = git_repo.find_remote(remote_name).map_err(|err| {
+            if is_remote_not_found_err(&err) {
+                GitPushError::NoSuchRemote(remote_name.to_string())
+            } else {
+                GitPushError::InternalGitError(err)
+            }
+        })?;
+        let mut rejected_refs = vec![];
+        let push_result = {
+            let mut push_options = git2::PushOptions::new();
+            let mut proxy_options = git2::ProxyOptions::new();
+            proxy_options.auto();
+            push_options.proxy_options(proxy_options);
+            let mut callbacks = callbacks.into_git();
+            callbacks.push_update_reference(|refname, status| {
+                // The status is Some if the ref update was rejected
+                if status.is_some() {
+                    rejected_refs.push(refname.to_owned());
+                }
+                Ok(())
+            });
+            push_options.remote_callbacks(callbacks);
+            remote.push(&[refspec.to_git_format()], Some(&mut push_options))
+        };
+        match push_result {
+            Ok(()) => {}
+            // libgit2 refuses non-fast-forward updates before sending them
+            Err(err) if err.code() == git2::ErrorCode::NotFastForward => {
+                rejected_refs.push(notes_ref.to_owned());
+            }
+            Err(err) => return Err(err.into()),
+        }
+        rejected_refs
+    };
+    if rejected_refs.is_empty() {
+        Ok(true)
+    } else {
+        Err(GitPushError::RefUpdateRejected(rejected_refs))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum GitWorktreeError {
+    #[error(transparent)]
+    UnexpectedBackend(#[from] UnexpectedGitBackendError),
+    #[error("Unexpected git error when reading worktrees")]
+    InternalGitError(#[source] Box<dyn std::error::Error + Send + Sync>),
+}
+
+/// A linked worktree of the underlying Git repo, as created by `git worktree
+/// add`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GitWorktree {
+    /// Name of the worktree within the Git repo.
+    pub name: String,
+    /// Path to the working directory, or `None` if it can't be determined.
+    pub path: Option<PathBuf>,
+    /// Local branch checked out in the worktree, if HEAD isn't detached.
+    pub branch: Option<String>,
+    /// Commit HEAD points to, or `None` if it is unborn.
+    pub head_id: Option<CommitId>,
+    /// Whether the worktree is locked by `git worktree lock`.
+    pub is_locked: bool,
+}
+
+/// Returns the linked worktrees of the underlying Git repo sorted by name. The
+/// main worktree isn't included.
+pub fn get_worktrees(store: &Store) -> Result<Vec<GitWorktree>, GitWorktreeError> {
+    let git_repo = get_git_repo(store)?;
+    let mut worktrees = vec![];
+    for proxy in git_repo
+        .worktrees()
+        .map_err(|err| GitWorktreeError::InternalGitError(err.into()))?
+    {
+        let name = String::from_utf8_lossy(proxy.id()).into_owned();
+        let path = proxy.base().ok();
+        let is_locked = proxy.is_locked();
+        let (branch, head_id) = match proxy.into_repo_with_possibly_inaccessible_worktree() {
+            Ok(worktree_repo) => {
+                let branch = worktree_repo
+                    .head_name()
+                    .ok()
+                    .flatten()
+                    .and_then(|name| str::from_utf8(name.as_bstr()).ok().and_then(parse_git_ref))
+                    .and_then(|ref_name| match ref_name {
+                        RefName::LocalBranch(branch) => Some(branch),
+                        RefName::RemoteBranch(_) | RefName::Tag(_) => None,
+                    });
+                let head_id = worktree_repo
+                    .head_id()
+                    .ok()
+                    .map(|id| CommitId::from_bytes(id.as_bytes()));
+                (branch, head_id)
+            }
+            Err(_) => (None, None),
+        };
+        worktrees.push(GitWorktree {
+            name,
+            path,
+            branch,
+            head_id,
+            is_locked,
+        });
+    }
+    worktrees.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(worktrees)
+}
+
+/// An entry of the Git stash, as recorded in the reflog of `refs/stash`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GitStashEntry {
+    /// Commit created by `git stash`. Its first parent is the commit the
+    /// changes were stashed on, and the other parents record the index and
+    /// untracked files.
+    pub commit_id: CommitId,
+    /// Reflog message such as "WIP on main: ...".
+    pub message: String,
+}
+
+/// Returns the entries of the Git stash, newest first. The position in the
+/// list corresponds to `stash@{N}`.
+pub fn get_stash_entries(store: &Store) -> Result<Vec<GitStashEntry>, GitImportError> {
+    let git_repo = get_git_repo(store)?;
+    let Some(stash_ref) = git_repo
+        .try_find_reference("refs/stash")
+        .map_err(GitImportError::from_git)?
+    else {
+        return Ok(vec![]);
+    };
+    let mut platform = stash_ref.log_iter();
+    let Some(log) = platform.rev().map_err(GitImportError::from_git)? else {
+        return Ok(vec![]);
+    };
+    log.map(|line| {
+        let line = line.map_err(GitImportError::from_git)?;
+        Ok(GitStashEntry {
+            commit_id: CommitId::from_bytes(line.new_oid.as_bytes()),
+            message: line.message.to_string(),
+        })
+    })
+    .collect()
+}
+
+/// Returns the commit the stash `entry` was made on, and the stashed tree. The
+/// stashed tree includes untracked files if they were stashed.
+pub fn get_stash_contents(
+    store: &Arc<Store>,
+    entry: &GitStashEntry,
+) -> Result<(Commit, MergedTree), GitImportError> {
+    let git_backend = get_git_backend(store)?;
+    git_backend
+        .import_head_commits([&entry.commit_id])
+        .map_err(GitImportError::InternalBackend)?;
+    let stash_commit = store
+        .get_commit(&entry.commit_id)
+        .map_err(GitImportError::InternalBackend)?;
+    let parents: Vec<Commit> = stash_commit
+        .parents()
+        .try_collect()
+        .map_err(GitImportError::InternalBackend)?;
+    let base_commit = parents.first().cloned().ok_or_else(|| {
+        GitImportError::from_git(format!("Stash commit {} has no parents", entry.commit_id))
+    })?;
+    let mut tree = stash_commit
+        .tree()
+        .map_err(GitImportError::InternalBackend)?;
+    // The third parent, if any, contains only the untracked files.
+    if let Some(untracked_commit) = parents.get(2) {
+        let empty_tree = store
+            .get_root_tree(&store.empty_merged_tree_id())
+            .map_err(GitImportError::InternalBackend)?;
+        let untracked_tree = untracked_commit
+            .tree()
+            .map_err(GitImportError::InternalBackend)?;
+        tree = tree
+            .merge(&empty_tree, &untracked_tree)
+            .map_err(GitImportError::InternalBackend)?;
+    }
+    Ok((base_commit, tree))
+}
+
+/// Imports the entries of the Git stash as hidden commits on top of the
+/// commits they were made on.
+///
+/// The imported commits have the same change ids, authors, and descriptions
+/// as the stash commits, so importing the same entry again is a no-op. Returns
+/// the commits which weren't imported before, oldest first.
+pub fn import_stash(mut_repo: &mut MutableRepo) -> Result<Vec<Commit>, GitImportError> {
+    let store = mut_repo.store().clone();
+    let mut imported_commits = vec![];
+    for entry in get_stash_entries(&store)?.iter().rev() {
+        let (base_commit, tree) = get_stash_contents(&store, entry)?;
+        let stash_commit = store
+            .get_commit(&entry.commit_id)
+            .map_err(GitImportError::InternalBackend)?;
+        let commit = mut_repo
+            .new_commit(vec![base_commit.id().clone()], tree.id())
+            .set_change_id(stash_commit.change_id().clone())
+            .set_author(stash_commit.author().clone())
+            .set_committer(stash_commit.committer().clone())
+            .set_description(stash_commit.description())
+            .detach()
+            .write_hidden()
+            .map_err(GitImportError::InternalBackend)?;
+        if mut_repo.index().has_id(commit.id()) {
+            continue;
+        }
+        // Index the commit and hide it by abandoning it, so the commit is
+        // recorded in the operation.
+        mut_repo
+            .add_head(&commit)
+            .map_err(GitImportError::InternalBackend)?;
+        mut_repo.record_abandoned_commit(&commit);
+        imported_commits.push(commit);
+    }
+    Ok(imported_commits)
+}
+
 const INVALID_REFSPEC_CHARS: [char; 5] = [':', '^', '?', '[', ']'];
 
 #[derive(Error, Debug)]
@@ -1518,9 +2108,31 @@ pub enum GitFetchPrepareError {
     UnexpectedBackend(#[from] UnexpectedGitBackendError),
 }
 
+/// Error from [`get_remote_default_branch()`].
+#[derive(Debug, Error)]
+pub enum GitRemoteDefaultBranchError {
+    #[error(transparent)]
+    Prepare(#[from] GitFetchPrepareError),
+    #[error(transparent)]
+    Fetch(#[from] GitFetchError),
+}
+
+/// Queries remote for the default branch name without fetching anything.
+#[tracing::instrument(skip(store, git_settings, callbacks))]
+pub fn get_remote_default_branch(
+    store: &Store,
+    git_settings: &GitSettings,
+    remote_name: &str,
+    callbacks: RemoteCallbacks<'_>,
+) -> Result<Option<String>, GitRemoteDefaultBranchError> {
+    let fetch_impl = GitFetchImpl::new(store, git_settings)?;
+    Ok(fetch_impl.get_default_branch(remote_name, callbacks)?)
+}
+
 fn git2_fetch_options(
     mut callbacks: RemoteCallbacks<'_>,
     depth: Option<NonZeroU32>,
+    fetch_tags: GitFetchTags,
 ) -> git2::FetchOptions<'_> {
     let mut proxy_options = git2::ProxyOptions::new();
     proxy_options.auto();
@@ -1537,10 +2149,21 @@ fn git2_fetch_options(
     if let Some(depth) = depth {
         fetch_options.depth(depth.get().try_into().unwrap_or(i32::MAX));
     }
+    fetch_options.download_tags(git2_autotag_option(fetch_tags));
 
     fetch_options
 }
 
+fn git2_autotag_option(fetch_tags: GitFetchTags) -> git2::AutotagOption {
+    match fetch_tags {
+        GitFetchTags::All => git2::AutotagOption::All,
+        // libgit2's "auto" mode downloads all tags along with the objects
+        // they point to, so reachable tags are fetched separately.
+        GitFetchTags::Reachable => git2::AutotagOption::None,
+        GitFetchTags::None => git2::AutotagOption::None,
+    }
+}
+
 struct FetchedBranches {
     remote: String,
     branches: Vec<StringPattern>,
@@ -1582,8 +2205,13 @@ impl<'a> GitFetch<'a> {
         depth: Option<NonZeroU32>,
     ) -> Result<(), GitFetchError> {
         validate_remote_name(remote_name)?;
-        self.fetch_impl
-            .fetch(remote_name, branch_names, callbacks, depth)?;
+        self.fetch_impl.fetch(
+            remote_name,
+            branch_names,
+            callbacks,
+            depth,
+            self.git_settings.fetch_tags,
+        )?;
         self.fetched.push(FetchedBranches {
             remote: remote_name.to_string(),
             branches: branch_names.to_vec(),
@@ -1603,33 +2231,39 @@ impl<'a> GitFetch<'a> {
 
     /// Import the previously fetched remote-tracking branches into the jj repo
     /// and update jj's local branches. We also import local tags since remote
-    /// tags should have been merged by Git.
+    /// tags should have been merged by Git, unless tags aren't fetched.
     ///
     /// Clears all yet-to-be-imported {branch_names, remote_name} pairs after
     /// the import. If `fetch()` has not been called since the last time
     /// `import_refs()` was called then this will be a no-op.
-    #[tracing::instrument(skip(self))]
-    pub fn import_refs(&mut self) -> Result<GitImportStats, GitImportError> {
+    ///
+    /// Newly fetched commits are indexed incrementally, and the `progress`
+    /// callback is called as the commits are indexed.
+    #[tracing::instrument(skip(self, progress))]
+    pub fn import_refs(
+        &mut self,
+        progress: Option<&mut dyn FnMut(&IndexingProgress)>,
+    ) -> Result<GitImportStats, GitImportError> {
         tracing::debug!("import_refs");
-        let import_stats =
-            import_some_refs(
-                self.mut_repo,
-                self.git_settings,
-                |ref_name| match ref_name {
-                    RefName::LocalBranch(_) => false,
-                    RefName::Tag(_) => true,
-                    RefName::RemoteBranch(symbol) => self.fetched.iter().any(|fetched| {
-                        if fetched.remote != symbol.remote {
-                            return false;
-                        }
+        let import_stats = import_some_refs_with_progress(
+            self.mut_repo,
+            self.git_settings,
+            |ref_name| match ref_name {
+                RefName::LocalBranch(_) => false,
+                RefName::Tag(_) => self.git_settings.fetch_tags != GitFetchTags::None,
+                RefName::RemoteBranch(symbol) => self.fetched.iter().any(|fetched| {
+                    if fetched.remote != symbol.remote {
+                        return false;
+                    }
 
-                        fetched
-                            .branches
-                            .iter()
-                            .any(|pattern| pattern.matches(&symbol.name))
-                    }),
-                },
-            )?;
+                    fetched
+                        .branches
+                        .iter()
+                        .any(|pattern| pattern.matches(&symbol.name))
+                }),
+            },
+            progress,
+        )?;
 
         self.fetched.clear();
 
@@ -1692,11 +2326,17 @@ impl<'a> GitFetchImpl<'a> {
         branch_names: &[StringPattern],
         callbacks: RemoteCallbacks<'_>,
         depth: Option<NonZeroU32>,
+        fetch_tags: GitFetchTags,
     ) -> Result<(), GitFetchError> {
         match self {
-            GitFetchImpl::Git2 { git_repo } => {
-                git2_fetch(git_repo, remote_name, branch_names, callbacks, depth)
-            }
+            GitFetchImpl::Git2 { git_repo } => git2_fetch(
+                git_repo,
+                remote_name,
+                branch_names,
+                callbacks,
+                depth,
+                fetch_tags,
+            ),
             GitFetchImpl::Subprocess { git_repo, git_ctx } => subprocess_fetch(
                 git_repo,
                 git_ctx,
@@ -1704,6 +2344,7 @@ impl<'a> GitFetchImpl<'a> {
                 branch_names,
                 callbacks,
                 depth,
+                fetch_tags,
             ),
         }
     }
@@ -1730,6 +2371,7 @@ fn git2_fetch(
     branch_names: &[StringPattern],
     callbacks: RemoteCallbacks<'_>,
     depth: Option<NonZeroU32>,
+    fetch_tags: GitFetchTags,
 ) -> Result<(), GitFetchError> {
     let mut remote = git_repo.find_remote(remote_name).map_err(|err| {
         if is_remote_not_found_err(&err) {
@@ -1750,22 +2392,83 @@ fn git2_fetch(
         return Ok(());
     }
 
+    let mut fetch_options = git2_fetch_options(callbacks, depth, fetch_tags);
     tracing::debug!("remote.download");
-    remote.download(&refspecs, Some(&mut git2_fetch_options(callbacks, depth)))?;
+    remote.download(&refspecs, Some(&mut fetch_options))?;
     tracing::debug!("remote.prune");
     remote.prune(None)?;
     tracing::debug!("remote.update_tips");
     remote.update_tips(
         None,
         git2::RemoteUpdateFlags::empty(),
-        git2::AutotagOption::Unspecified,
+        git2_autotag_option(fetch_tags),
         None,
     )?;
+    let remote_heads: Vec<git2::Oid> = git_repo
+        .references_glob(&format!("refs/remotes/{remote_name}/*"))?
+        .filter_map(|r| r.ok()?.target())
+        .collect();
+    // git2 0.20 can't list an empty ref advertisement, but there would be no
+    // fetched commits for tags to point to anyway.
+    if fetch_tags == GitFetchTags::Reachable && !remote_heads.is_empty() {
+        let tag_refspecs = git2_reachable_tag_refspecs(git_repo, &remote, &remote_heads)?;
+        if !tag_refspecs.is_empty() {
+            tracing::debug!("remote.download (tags)");
+            remote.download(&tag_refspecs, Some(&mut fetch_options))?;
+            tracing::debug!("remote.update_tips (tags)");
+            remote.update_tips(
+                None,
+                git2::RemoteUpdateFlags::empty(),
+                git2::AutotagOption::None,
+                None,
+            )?;
+        }
+    }
     tracing::debug!("remote.disconnect");
     remote.disconnect()?;
     Ok(())
 }
 
+/// Returns refspecs for the advertised tags which point to commits reachable
+/// from the remote-tracking branches and which don't exist locally yet.
+fn git2_reachable_tag_refspecs(
+    git_repo: &git2::Repository,
+    remote: &git2::Remote<'_>,
+    remote_heads: &[git2::Oid],
+) -> Result<Vec<String>, git2::Error> {
+    let odb = git_repo.odb()?;
+    let mut tag_targets: BTreeMap<&str, git2::Oid> = BTreeMap::new();
+    for head in remote.list()? {
+        let Some(name) = head.name().strip_prefix("refs/tags/") else {
+            continue;
+        };
+        if let Some(name) = name.strip_suffix("^{}") {
+            // The peeled target of an annotated tag takes precedence.
+            tag_targets.insert(name, head.oid());
+        } else {
+            tag_targets.entry(name).or_insert(head.oid());
+        }
+    }
+    let mut refspecs = vec![];
+    for (name, target) in tag_targets {
+        let full_name = format!("refs/tags/{name}");
+        if !odb.exists(target) || git_repo.find_reference(&full_name).is_ok() {
+            continue;
+        }
+        // libgit2 may download more objects than requested, so the target
+        // has to be checked against the fetched heads.
+        let is_reachable = remote_heads.iter().try_fold(false, |found, &head| {
+            Ok::<_, git2::Error>(
+                found || head == target || git_repo.graph_descendant_of(head, target)?,
+            )
+        })?;
+        if is_reachable {
+            refspecs.push(format!("{full_name}:{full_name}"));
+        }
+    }
+    Ok(refspecs)
+}
+
 fn git2_get_default_branch(
     git_repo: &git2::Repository,
     remote_name: &str,
@@ -1811,6 +2514,7 @@ fn subprocess_fetch(
     branch_names: &[StringPattern],
     mut callbacks: RemoteCallbacks<'_>,
     depth: Option<NonZeroU32>,
+    fetch_tags: GitFetchTags,
 ) -> Result<(), GitFetchError> {
     // check the remote exists
     if git_repo.try_find_remote(remote_name).is_none() {
@@ -1832,9 +2536,13 @@ fn subprocess_fetch(
     //
     // even more unfortunately, git errors out one refspec at a time,
     // meaning that the below cycle runs in O(#failed refspecs)
-    while let Some(failing_refspec) =
-        git_ctx.spawn_fetch(remote_name, &remaining_refspecs, &mut callbacks, depth)?
-    {
+    while let Some(failing_refspec) = git_ctx.spawn_fetch(
+        remote_name,
+        &remaining_refspecs,
+        &mut callbacks,
+        depth,
+        fetch_tags,
+    )? {
         remaining_refspecs.retain(|r| r.source.as_ref() != Some(&failing_refspec));
 
         if let Some(branch_name) = failing_refspec.strip_prefix("refs/heads/") {
@@ -420,6 +420,74 @@ impl GitBackend {
         Ok(Box::new(Cursor::new(blob.take_data())))
     }
 
+    /// Checks that the content of the stored object `id` hashes to `id`.
+    ///
+    /// Returns `false` if the object is corrupted.
+    pub fn verify_object_hash(&self, id: &impl ObjectId) -> BackendResult<bool> {
+        let git_object_id = validate_git_object_id(id)?;
+        let locked_repo = self.lock_git_repo();
+        let object = locked_repo
+            .find_object(git_object_id)
+            .map_err(|err| map_not_found_err(err, id))?;
+        let actual_id =
+            gix::objs::compute_hash(locked_repo.object_hash(), object.kind, &object.data);
+        Ok(actual_id == git_object_id)
+    }
+
+    /// Copies the given commits and their ancestors and predecessors from
+    /// `src`, along with the trees and files they reference and the jj-specific
+    /// metadata such as change ids. Object ids are preserved.
+    ///
+    /// Commits already known to this backend are assumed to be complete, so
+    /// their ancestors aren't visited. Returns the number of copied commits.
+    pub fn copy_commits_from(
+        &self,
+        src: &GitBackend,
+        head_ids: &[CommitId],
+    ) -> BackendResult<usize> {
+        let (table, table_lock) = self.read_extra_metadata_table_locked()?;
+        let mut mut_table = table.start_mutation();
+        let mut copied_ids = vec![];
+        let mut visited: HashSet<CommitId> = HashSet::new();
+        let mut pending = head_ids.to_vec();
+        while let Some(id) = pending.pop() {
+            if id == self.root_commit_id
+                || table.get_value(id.as_bytes()).is_some()
+                || !visited.insert(id.clone())
+            {
+                continue;
+            }
+            // Reading the commit also imports missing metadata on the source side.
+            let commit = src.read_commit(&id).block_on()?;
+            let src_table = src.cached_extra_metadata_table()?;
+            let extras = src_table
+                .get_value(id.as_bytes())
+                .map_or_else(|| serialize_extras(&commit), |extras| extras.to_vec());
+            {
+                let src_repo = src.lock_git_repo();
+                let dest_repo = self.lock_git_repo();
+                // Write objects bottom up so that an interrupted copy never
+                // leaves an object whose dependencies are missing.
+                for tree_id in commit.root_tree.to_merge().iter() {
+                    copy_git_tree(&src_repo, &dest_repo, tree_id)?;
+                }
+                copy_git_object(&src_repo, &dest_repo, &id)?;
+            }
+            mut_table.add_entry(id.to_bytes(), extras);
+            pending.extend(commit.parents.iter().cloned());
+            pending.extend(commit.predecessors.iter().cloned());
+            copied_ids.push(id);
+        }
+        if copied_ids.is_empty() {
+            return Ok(0);
+        }
+        self.lock_git_repo()
+            .edit_references(copied_ids.iter().map(to_no_gc_ref_update))
+            .map_err(|err| BackendError::Other(Box::new(err)))?;
+        self.save_extra_metadata_table(mut_table, &table_lock)?;
+        Ok(copied_ids.len())
+    }
+
     fn new_diff_platform(&self) -> BackendResult<gix::diff::blob::Platform> {
         let attributes = gix::worktree::Stack::new(
             Path::new(""),
@@ -597,6 +665,9 @@ fn commit_from_git_without_root_parent(
         description,
         author,
         committer,
+        // If this commit has associated extra metadata, we may set these later.
+        duplicated_from: None,
+        conflict_sources: vec![],
         secure_sig,
     })
 }
@@ -665,6 +736,12 @@ fn serialize_extras(commit: &Commit) -> Vec<u8> {
     for predecessor in &commit.predecessors {
         proto.predecessors.push(predecessor.to_bytes());
     }
+    proto.duplicated_from = commit.duplicated_from.as_ref().map(|id| id.to_bytes());
+    proto.conflict_sources = commit
+        .conflict_sources
+        .iter()
+        .map(|id| id.to_bytes())
+        .collect();
     proto.encode_to_vec()
 }
 
@@ -698,6 +775,65 @@ fn deserialize_extras(commit: &mut Commit, bytes: &[u8]) {
     for predecessor in &proto.predecessors {
         commit.predecessors.push(CommitId::from_bytes(predecessor));
     }
+    commit.duplicated_from = proto.duplicated_from.map(ChangeId::new);
+    commit.conflict_sources = proto
+        .conflict_sources
+        .into_iter()
+        .map(CommitId::new)
+        .collect();
+}
+
+/// Copies a single object from `src` to `dest` unless it already exists there.
+fn copy_git_object(
+    src: &gix::Repository,
+    dest: &gix::Repository,
+    id: &impl ObjectId,
+) -> BackendResult<()> {
+    let git_object_id = validate_git_object_id(id)?;
+    if dest.has_object(git_object_id) {
+        return Ok(());
+    }
+    let object = src
+        .find_object(git_object_id)
+        .map_err(|err| map_not_found_err(err, id))?;
+    gix::objs::Write::write_buf(&dest.objects, object.kind, &object.data).map_err(|err| {
+        BackendError::WriteObject {
+            object_type: "object",
+            source: err,
+        }
+    })?;
+    Ok(())
+}
+
+/// Copies a tree and everything it references from `src` to `dest`. Subtrees
+/// already present in `dest` are assumed to be complete.
+fn copy_git_tree(src: &gix::Repository, dest: &gix::Repository, id: &TreeId) -> BackendResult<()> {
+    let git_tree_id = validate_git_object_id(id)?;
+    if dest.has_object(git_tree_id) {
+        return Ok(());
+    }
+    let tree = src
+        .find_object(git_tree_id)
+        .map_err(|err| map_not_found_err(err, id))?
+        .try_into_tree()
+        .map_err(|err| to_read_object_err(err, id))?;
+    let entries = tree
+        .decode()
+        .map_err(|err| to_read_object_err(err, id))?
+        .entries
+        .iter()
+        .map(|entry| (entry.mode, entry.oid.to_owned()))
+        .collect_vec();
+    for (mode, oid) in entries {
+        if mode.is_tree() {
+            copy_git_tree(src, dest, &TreeId::from_bytes(oid.as_bytes()))?;
+        } else if mode.is_link() {
+            copy_git_object(src, dest, &SymlinkId::from_bytes(oid.as_bytes()))?;
+        } else if !mode.is_commit() {
+            copy_git_object(src, dest, &FileId::from_bytes(oid.as_bytes()))?;
+        }
+    }
+    copy_git_object(src, dest, id)
 }
 
 /// Returns `RefEdit` that will create a ref in `refs/jj/keep` if not exist.
@@ -1908,6 +2044,8 @@ mod tests {
             description: "".to_string(),
             author: create_signature(),
             committer: create_signature(),
+            duplicated_from: None,
+            conflict_sources: vec![],
             secure_sig: None,
         };
 
@@ -1994,6 +2132,8 @@ mod tests {
             description: "".to_string(),
             author: create_signature(),
             committer: create_signature(),
+            duplicated_from: None,
+            conflict_sources: vec![],
             secure_sig: None,
         };
 
@@ -2092,6 +2232,8 @@ mod tests {
             description: "initial".to_string(),
             author: signature.clone(),
             committer: signature,
+            duplicated_from: None,
+            conflict_sources: vec![],
             secure_sig: None,
         };
         let commit_id = backend.write_commit(commit, None).block_on().unwrap().0;
@@ -2170,6 +2312,8 @@ mod tests {
             description: "initial".to_string(),
             author: create_signature(),
             committer: create_signature(),
+            duplicated_from: None,
+            conflict_sources: vec![],
             secure_sig: None,
         };
 
@@ -2216,6 +2360,8 @@ mod tests {
             description: "initial".to_string(),
             author: create_signature(),
             committer: create_signature(),
+            duplicated_from: None,
+            conflict_sources: vec![],
             secure_sig: None,
         };
 
@@ -32,6 +32,7 @@ use crate::git::RefSpec;
 use crate::git::RefToPush;
 use crate::git::RemoteCallbacks;
 use crate::git_backend::GitBackend;
+use crate::settings::GitFetchTags;
 
 /// Error originating by a Git subprocess
 #[derive(Error, Debug)]
@@ -124,6 +125,7 @@ impl<'a> GitSubprocessContext<'a> {
         refspecs: &[RefSpec],
         callbacks: &mut RemoteCallbacks<'_>,
         depth: Option<NonZeroU32>,
+        fetch_tags: GitFetchTags,
     ) -> Result<Option<String>, GitSubprocessError> {
         if refspecs.is_empty() {
             return Ok(None);
@@ -139,6 +141,13 @@ impl<'a> GitSubprocessContext<'a> {
         if let Some(d) = depth {
             command.arg(format!("--depth={d}"));
         }
+        match fetch_tags {
+            // --force is needed to update tags which were moved on the remote.
+            // Unlike a tag refspec, --tags doesn't make --prune delete tags.
+            GitFetchTags::All => command.args(["--tags", "--force"]),
+            GitFetchTags::Reachable => &mut command,
+            GitFetchTags::None => command.arg("--no-tags"),
+        };
         command.arg("--").arg(remote_name);
         command.args(refspecs.iter().map(|x| x.to_git_format()));
 
@@ -232,6 +241,35 @@ impl<'a> GitSubprocessContext<'a> {
 
         parse_git_push_output(output)
     }
+
+    /// Push the refspecs to the remote without any lease
+    ///
+    /// Forced refspecs overwrite the remote refs, and other refspecs are
+    /// rejected unless they fast-forward the remote refs.
+    ///
+    /// Return tuple with
+    ///     1. refs that failed to push
+    ///     2. refs that succeeded to push
+    pub(crate) fn spawn_push_refspecs(
+        &self,
+        remote_name: &str,
+        refspecs: &[RefSpec],
+        callbacks: &mut RemoteCallbacks<'_>,
+    ) -> Result<(Vec<String>, Vec<String>), GitSubprocessError> {
+        let mut command = self.create_command();
+        command.stdout(Stdio::piped());
+        // Hooks are skipped for the same reason as in `spawn_push()`
+        command.args(["push", "--porcelain", "--no-verify"]);
+        if callbacks.progress.is_some() {
+            command.arg("--progress");
+        }
+        command.args(["--", remote_name]);
+        command.args(refspecs.iter().map(RefSpec::to_git_format));
+
+        let output = wait_with_progress(self.spawn_cmd(command)?, callbacks)?;
+
+        parse_git_push_output(output)
+    }
 }
 
 /// Generate a GitSubprocessError::ExternalGitError if the stderr output was not
@@ -228,6 +228,19 @@ impl IdPrefixIndex<'_> {
     }
 }
 
+/// Returns the number of hexadecimal digits with which the IDs of
+/// `num_commits` commits are unlikely to be ambiguous.
+///
+/// Like Git's automatic abbreviation length, this grows by one digit each time
+/// the number of commits quadruples, so abbreviated IDs stay the same length
+/// for a long time as the repo grows.
+pub fn estimate_unambiguous_prefix_len(num_commits: u32) -> usize {
+    // Collisions become likely once the number of IDs reaches the square root
+    // of the number of distinct prefixes. A hexadecimal digit has 4 bits.
+    let bits = u32::BITS - num_commits.leading_zeros();
+    bits.div_ceil(2) as usize
+}
+
 /// In-memory immutable index to do prefix lookup of key `K` through `P`.
 ///
 /// In a nutshell, this is a mapping of `K` -> `P` -> `S::Entry` where `S:
@@ -496,6 +509,18 @@ fn unwrap_as_short_key<const N: usize>(key_bytes: &[u8]) -> &[u8; N] {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_estimate_unambiguous_prefix_len() {
+        assert_eq!(estimate_unambiguous_prefix_len(0), 0);
+        assert_eq!(estimate_unambiguous_prefix_len(1), 1);
+        assert_eq!(estimate_unambiguous_prefix_len(3), 1);
+        assert_eq!(estimate_unambiguous_prefix_len(4), 2);
+        assert_eq!(estimate_unambiguous_prefix_len(65_535), 8);
+        assert_eq!(estimate_unambiguous_prefix_len(65_536), 9);
+        assert_eq!(estimate_unambiguous_prefix_len(1_000_000), 10);
+        assert_eq!(estimate_unambiguous_prefix_len(u32::MAX), 16);
+    }
+
     #[derive(Clone, Copy, Eq, PartialEq)]
     struct Position(usize);
 
@@ -90,6 +90,14 @@ pub trait Index: Send + Sync {
     /// length which matches none of the commits in the index.
     fn shortest_unique_commit_id_prefix_len(&self, commit_id: &CommitId) -> usize;
 
+    /// Returns the number of commits in the index, including hidden ones.
+    ///
+    /// Returns 0 by default, for indexes which can't count their commits
+    /// cheaply.
+    fn num_commits(&self) -> u32 {
+        0
+    }
+
     /// Searches the index for commit IDs matching `prefix`. Returns a
     /// [`PrefixResolution`] with a [`CommitId`] if the prefix matches a single
     /// commit.
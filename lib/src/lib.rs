@@ -36,6 +36,7 @@ pub mod commit_builder;
 pub mod config;
 mod config_resolver;
 pub mod conflicts;
+pub mod conventional_commit;
 pub mod copies;
 pub mod dag_walk;
 pub mod default_index;
@@ -48,6 +49,7 @@ pub mod files;
 pub mod fileset;
 mod fileset_parser;
 pub mod fmt_util;
+pub mod fsck;
 pub mod fsmonitor;
 #[cfg(feature = "git")]
 pub mod git;
@@ -76,12 +78,15 @@ pub mod index;
 pub mod local_backend;
 pub mod local_working_copy;
 pub mod lock;
+pub mod mailmap;
 pub mod matchers;
 pub mod merge;
+pub mod merge_strategy;
 pub mod merged_tree;
 pub mod object_id;
 pub mod op_heads_store;
 pub mod op_store;
+pub mod op_sync;
 pub mod op_walk;
 pub mod operation;
 #[expect(missing_docs)]
@@ -92,6 +97,7 @@ pub mod repo_path;
 pub mod revset;
 mod revset_parser;
 pub mod rewrite;
+pub mod search_index;
 #[cfg(feature = "testing")]
 pub mod secret_backend;
 pub mod settings;
@@ -109,10 +115,12 @@ pub mod time_util;
 pub mod transaction;
 pub mod tree;
 pub mod tree_builder;
+pub mod tree_diff_cache;
 pub mod union_find;
 pub mod view;
 pub mod working_copy;
 pub mod workspace;
+pub mod workspace_store;
 
 #[cfg(test)]
 mod tests {
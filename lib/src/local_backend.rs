@@ -374,6 +374,12 @@ pub fn commit_to_proto(commit: &Commit) -> crate::protos::local_store::Commit {
     proto.description = commit.description.clone();
     proto.author = Some(signature_to_proto(&commit.author));
     proto.committer = Some(signature_to_proto(&commit.committer));
+    proto.duplicated_from = commit.duplicated_from.as_ref().map(|id| id.to_bytes());
+    proto.conflict_sources = commit
+        .conflict_sources
+        .iter()
+        .map(|id| id.to_bytes())
+        .collect();
     proto
 }
 
@@ -403,6 +409,12 @@ fn commit_from_proto(mut proto: crate::protos::local_store::Commit) -> Commit {
         description: proto.description,
         author: signature_from_proto(proto.author.unwrap_or_default()),
         committer: signature_from_proto(proto.committer.unwrap_or_default()),
+        duplicated_from: proto.duplicated_from.map(ChangeId::new),
+        conflict_sources: proto
+            .conflict_sources
+            .into_iter()
+            .map(CommitId::new)
+            .collect(),
         secure_sig,
     }
 }
@@ -563,6 +575,8 @@ mod tests {
             description: "".to_string(),
             author: create_signature(),
             committer: create_signature(),
+            duplicated_from: None,
+            conflict_sources: vec![],
             secure_sig: None,
         };
 
@@ -17,6 +17,7 @@
 
 use std::any::Any;
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::collections::HashSet;
 use std::error::Error;
 use std::fs;
@@ -70,6 +71,7 @@ use crate::conflicts::choose_materialized_conflict_marker_len;
 use crate::conflicts::materialize_merge_result_to_bytes_with_marker_len;
 use crate::conflicts::materialize_tree_value;
 use crate::conflicts::ConflictMarkerStyle;
+use crate::conflicts::ConflictMarkerStyleOverrides;
 use crate::conflicts::MaterializedTreeValue;
 use crate::conflicts::MIN_CONFLICT_MARKER_LEN;
 use crate::file_util::check_symlink_support;
@@ -104,8 +106,10 @@ use crate::tree::Tree;
 use crate::working_copy::CheckoutError;
 use crate::working_copy::CheckoutOptions;
 use crate::working_copy::CheckoutStats;
+use crate::working_copy::FileTypeOverride;
 use crate::working_copy::LockedWorkingCopy;
 use crate::working_copy::ResetError;
+use crate::working_copy::SnapshotContentCheck;
 use crate::working_copy::SnapshotError;
 use crate::working_copy::SnapshotOptions;
 use crate::working_copy::SnapshotProgress;
@@ -122,9 +126,14 @@ type FileExecutableFlag = ();
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum FileType {
-    Normal { executable: FileExecutableFlag },
+    Normal {
+        executable: FileExecutableFlag,
+    },
     Symlink,
     GitSubmodule,
+    /// The path exists in the tree, but can't be represented on the disk. It
+    /// is left as is by snapshots.
+    Skipped,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -186,6 +195,15 @@ impl FileState {
         }
     }
 
+    fn for_skipped() -> Self {
+        FileState {
+            file_type: FileType::Skipped,
+            mtime: MillisSinceEpoch(0),
+            size: 0,
+            materialized_conflict_data: None,
+        }
+    }
+
     fn for_symlink(metadata: &Metadata) -> Self {
         // When using fscrypt, the reported size is not the content size. So if
         // we were to record the content size here (like we do for regular files), we
@@ -414,6 +432,9 @@ pub struct TreeState {
     file_states: FileStatesMap,
     // Currently only path prefixes
     sparse_patterns: Vec<RepoPathBuf>,
+    // Sorted file paths which shouldn't be tracked even if they aren't ignored
+    untracked_paths: Vec<RepoPathBuf>,
+    file_type_overrides: BTreeMap<RepoPathBuf, FileTypeOverride>,
     own_mtime: MillisSinceEpoch,
     symlink_support: bool,
 
@@ -438,6 +459,7 @@ fn file_state_from_proto(proto: &crate::protos::working_copy::FileState) -> File
             executable: FileExecutableFlag::default(),
         },
         crate::protos::working_copy::FileType::GitSubmodule => FileType::GitSubmodule,
+        crate::protos::working_copy::FileType::Skipped => FileType::Skipped,
     };
     FileState {
         file_type,
@@ -462,6 +484,7 @@ fn file_state_to_proto(file_state: &FileState) -> crate::protos::working_copy::F
         FileType::Normal { executable: () } => crate::protos::working_copy::FileType::Normal,
         FileType::Symlink => crate::protos::working_copy::FileType::Symlink,
         FileType::GitSubmodule => crate::protos::working_copy::FileType::GitSubmodule,
+        FileType::Skipped => crate::protos::working_copy::FileType::Skipped,
     };
     proto.file_type = file_type as i32;
     proto.mtime_millis_since_epoch = file_state.mtime.0;
@@ -474,6 +497,34 @@ fn file_state_to_proto(file_state: &FileState) -> crate::protos::working_copy::F
     proto
 }
 
+fn file_type_override_from_proto(
+    proto: &crate::protos::working_copy::FileTypeOverride,
+) -> Option<(RepoPathBuf, FileTypeOverride)> {
+    let file_type = match proto.file_type() {
+        crate::protos::working_copy::FileType::Normal => FileTypeOverride::Normal,
+        crate::protos::working_copy::FileType::Executable => FileTypeOverride::Executable,
+        crate::protos::working_copy::FileType::Symlink => FileTypeOverride::Symlink,
+        // Not written by this version
+        _ => return None,
+    };
+    Some((RepoPathBuf::from_internal_string(&proto.path), file_type))
+}
+
+fn file_type_override_to_proto(
+    path: &RepoPath,
+    file_type: FileTypeOverride,
+) -> crate::protos::working_copy::FileTypeOverride {
+    let file_type = match file_type {
+        FileTypeOverride::Normal => crate::protos::working_copy::FileType::Normal,
+        FileTypeOverride::Executable => crate::protos::working_copy::FileType::Executable,
+        FileTypeOverride::Symlink => crate::protos::working_copy::FileType::Symlink,
+    };
+    crate::protos::working_copy::FileTypeOverride {
+        path: path.as_internal_file_string().to_owned(),
+        file_type: file_type as i32,
+    }
+}
+
 fn file_state_entry_from_proto(
     proto: &crate::protos::working_copy::FileStateEntry,
 ) -> (&RepoPath, FileState) {
@@ -644,6 +695,48 @@ fn can_create_new_file(disk_path: &Path) -> Result<bool, CheckoutError> {
 
 const RESERVED_DIR_NAMES: &[&str] = &[".git", ".jj"];
 
+/// Maximum length of a file name. Most file systems limit names to 255 bytes
+/// (or 255 UTF-16 code units on Windows.)
+const MAX_FILE_NAME_LEN: usize = 255;
+
+/// Returns `false` if the `repo_path` can't be created on the disk on this
+/// platform, because it has too long names, or names reserved by Windows.
+fn is_representable_path(repo_path: &RepoPath) -> bool {
+    repo_path.components().all(|component| {
+        let name = component.as_internal_str();
+        if cfg!(windows) {
+            name.encode_utf16().count() <= MAX_FILE_NAME_LEN && !is_reserved_windows_name(name)
+        } else {
+            name.len() <= MAX_FILE_NAME_LEN
+        }
+    })
+}
+
+/// Checks if the file `name` can't be created on Windows. This follows
+/// `is_valid_win32_path()` of Git: device names such as `aux` (with or without
+/// extension), names with a trailing dot or space, and names with characters
+/// which aren't allowed in Win32 paths are rejected.
+fn is_reserved_windows_name(name: &str) -> bool {
+    const DEVICE_NAMES: &[&str] = &["con", "prn", "aux", "nul", "conin$", "conout$"];
+    const NUMBERED_DEVICE_NAMES: &[&str] = &["com", "lpt"];
+    if name.ends_with(['.', ' ']) {
+        return true;
+    }
+    if name
+        .chars()
+        .any(|c| c.is_ascii_control() || matches!(c, '<' | '>' | ':' | '"' | '|' | '?' | '*'))
+    {
+        return true;
+    }
+    let stem = name.split('.').next().unwrap().trim_end_matches(' ');
+    let stem = stem.to_ascii_lowercase();
+    DEVICE_NAMES.contains(&stem.as_str())
+        || NUMBERED_DEVICE_NAMES.iter().any(|prefix| {
+            stem.strip_prefix(prefix)
+                .is_some_and(|n| matches!(n.as_bytes(), [b'1'..=b'9']))
+        })
+}
+
 /// Suppose the `disk_path` exists, checks if the last component points to
 /// ".git" or ".jj" in the same parent directory.
 fn reject_reserved_existing_path(disk_path: &Path) -> Result<(), CheckoutError> {
@@ -759,6 +852,49 @@ impl TreeState {
         Box::new(PrefixMatcher::new(&self.sparse_patterns))
     }
 
+    pub fn untracked_paths(&self) -> &[RepoPathBuf] {
+        &self.untracked_paths
+    }
+
+    pub fn set_untracked_paths(&mut self, mut untracked_paths: Vec<RepoPathBuf>) {
+        untracked_paths.sort_unstable();
+        untracked_paths.dedup();
+        self.untracked_paths = untracked_paths;
+    }
+
+    pub fn file_type_overrides(&self) -> &BTreeMap<RepoPathBuf, FileTypeOverride> {
+        &self.file_type_overrides
+    }
+
+    pub fn set_file_type_overrides(
+        &mut self,
+        file_type_overrides: BTreeMap<RepoPathBuf, FileTypeOverride>,
+    ) {
+        // Invalidate the file states of the changed paths so they will be
+        // snapshotted again.
+        let changed_file_states = self
+            .file_states
+            .all()
+            .iter()
+            .filter(|(path, _)| {
+                self.file_type_overrides.get(*path) != file_type_overrides.get(*path)
+            })
+            .filter(|(_, state)| {
+                matches!(state.file_type, FileType::Normal { .. } | FileType::Symlink)
+            })
+            .map(|(path, state)| {
+                let state = FileState {
+                    mtime: MillisSinceEpoch(0),
+                    ..state
+                };
+                (path.to_owned(), state)
+            })
+            .collect_vec();
+        self.file_states
+            .merge_in(changed_file_states, &HashSet::new());
+        self.file_type_overrides = file_type_overrides;
+    }
+
     pub fn init(
         store: Arc<Store>,
         working_copy_path: PathBuf,
@@ -778,6 +914,8 @@ impl TreeState {
             tree_id,
             file_states: FileStatesMap::new(),
             sparse_patterns: vec![RepoPathBuf::root()],
+            untracked_paths: vec![],
+            file_type_overrides: BTreeMap::new(),
             own_mtime: MillisSinceEpoch(0),
             symlink_support: check_symlink_support().unwrap_or(false),
             watchman_clock: None,
@@ -843,6 +981,16 @@ impl TreeState {
         self.file_states =
             FileStatesMap::from_proto(proto.file_states, proto.is_file_states_sorted);
         self.sparse_patterns = sparse_patterns_from_proto(proto.sparse_patterns.as_ref());
+        self.untracked_paths = proto
+            .untracked_paths
+            .iter()
+            .map(RepoPathBuf::from_internal_string)
+            .collect();
+        self.file_type_overrides = proto
+            .file_type_overrides
+            .iter()
+            .filter_map(file_type_override_from_proto)
+            .collect();
         self.watchman_clock = proto.watchman_clock;
         Ok(())
     }
@@ -869,6 +1017,16 @@ impl TreeState {
                 .push(path.as_internal_file_string().to_owned());
         }
         proto.sparse_patterns = Some(sparse_patterns);
+        proto.untracked_paths = self
+            .untracked_paths
+            .iter()
+            .map(|path| path.as_internal_file_string().to_owned())
+            .collect();
+        proto.file_type_overrides = self
+            .file_type_overrides
+            .iter()
+            .map(|(path, file_type)| file_type_override_to_proto(path, *file_type))
+            .collect();
         proto.watchman_clock = self.watchman_clock.clone();
 
         let mut temp_file = NamedTempFile::new_in(&self.state_path).unwrap();
@@ -955,9 +1113,16 @@ impl TreeState {
             start_tracking_matcher,
             max_new_file_size,
             conflict_marker_style,
+            ref conflict_marker_style_overrides,
+            content_check,
         } = options;
 
         let sparse_matcher = self.sparse_matcher();
+        // Intentionally untracked paths shouldn't be tracked even if they
+        // match the start_tracking_matcher.
+        let untracked_matcher = FilesMatcher::new(&self.untracked_paths);
+        let start_tracking_matcher =
+            DifferenceMatcher::new(start_tracking_matcher, &untracked_matcher);
 
         let fsmonitor_clock_needs_save = *fsmonitor_settings != FsmonitorSettings::None;
         let mut is_dirty = fsmonitor_clock_needs_save;
@@ -987,7 +1152,7 @@ impl TreeState {
                 tree_state: self,
                 current_tree: &self.current_tree()?,
                 matcher: &matcher,
-                start_tracking_matcher,
+                start_tracking_matcher: &start_tracking_matcher,
                 // Move tx sides so they'll be dropped at the end of the scope.
                 tree_entries_tx,
                 file_states_tx,
@@ -997,6 +1162,8 @@ impl TreeState {
                 progress,
                 max_new_file_size,
                 conflict_marker_style,
+                conflict_marker_style_overrides,
+                content_check,
             };
             let directory_to_visit = DirectoryToVisit {
                 dir: RepoPathBuf::root(),
@@ -1144,6 +1311,8 @@ struct FileSnapshotter<'a> {
     progress: Option<&'a SnapshotProgress<'a>>,
     max_new_file_size: u64,
     conflict_marker_style: ConflictMarkerStyle,
+    conflict_marker_style_overrides: &'a ConflictMarkerStyleOverrides,
+    content_check: Option<&'a SnapshotContentCheck<'a>>,
 }
 
 impl FileSnapshotter<'_> {
@@ -1234,7 +1403,10 @@ impl FileSnapshotter<'_> {
         let path = dir.join(name);
         let maybe_current_file_state = file_states.get_at(dir, name);
         if let Some(file_state) = &maybe_current_file_state {
-            if file_state.file_type == FileType::GitSubmodule {
+            if matches!(
+                file_state.file_type,
+                FileType::GitSubmodule | FileType::Skipped
+            ) {
                 return Ok(None);
             }
         }
@@ -1314,7 +1486,10 @@ impl FileSnapshotter<'_> {
     /// Visits only paths we're already tracking.
     fn visit_tracked_files(&self, file_states: FileStates<'_>) -> Result<(), SnapshotError> {
         for (tracked_path, current_file_state) in file_states {
-            if current_file_state.file_type == FileType::GitSubmodule {
+            if matches!(
+                current_file_state.file_type,
+                FileType::GitSubmodule | FileType::Skipped
+            ) {
                 continue;
             }
             if !self.matcher.matches(tracked_path) {
@@ -1400,8 +1575,11 @@ impl FileSnapshotter<'_> {
                 PresentDirEntryKind::File => !present_entries.files.contains(name),
             })
             .flat_map(|(_, chunk)| chunk)
-            // Whether or not the entry exists, submodule should be ignored
-            .filter(|(_, state)| state.file_type != FileType::GitSubmodule)
+            // Whether or not the entry exists, submodule and skipped paths
+            // should be ignored
+            .filter(|(_, state)| {
+                !matches!(state.file_type, FileType::GitSubmodule | FileType::Skipped)
+            })
             .filter(|(path, _)| self.matcher.matches(path))
             .try_for_each(|(path, _)| self.deleted_files_tx.send(path.to_owned()))
             .ok();
@@ -1430,16 +1608,25 @@ impl FileSnapshotter<'_> {
             Ok(None)
         } else {
             let current_tree_values = self.current_tree.path_value(repo_path)?;
-            let new_file_type = if !self.tree_state.symlink_support {
-                let mut new_file_type = new_file_state.file_type.clone();
-                if matches!(new_file_type, FileType::Normal { .. })
-                    && matches!(current_tree_values.as_normal(), Some(TreeValue::Symlink(_)))
-                {
-                    new_file_type = FileType::Symlink;
+            let file_type_override = self.tree_state.file_type_overrides.get(repo_path);
+            let new_file_type = match (file_type_override, &new_file_state.file_type) {
+                (Some(FileTypeOverride::Symlink), FileType::Normal { .. }) => FileType::Symlink,
+                (Some(_), file_type) => file_type.clone(),
+                (None, file_type) => {
+                    let mut new_file_type = file_type.clone();
+                    if !self.tree_state.symlink_support
+                        && matches!(new_file_type, FileType::Normal { .. })
+                        && matches!(current_tree_values.as_normal(), Some(TreeValue::Symlink(_)))
+                    {
+                        new_file_type = FileType::Symlink;
+                    }
+                    new_file_type
                 }
-                new_file_type
-            } else {
-                new_file_state.file_type.clone()
+            };
+            let executable_override = match file_type_override {
+                Some(FileTypeOverride::Normal) => Some(false),
+                Some(FileTypeOverride::Executable) => Some(true),
+                Some(FileTypeOverride::Symlink) | None => None,
             };
             let new_tree_values = match new_file_type {
                 FileType::Normal { executable } => self
@@ -1448,16 +1635,19 @@ impl FileSnapshotter<'_> {
                         disk_path,
                         &current_tree_values,
                         executable,
+                        executable_override,
                         maybe_current_file_state.and_then(|state| state.materialized_conflict_data),
                     )
                     .block_on()?,
                 FileType::Symlink => {
+                    let is_symlink_on_disk = new_file_state.file_type == FileType::Symlink;
                     let id = self
-                        .write_symlink_to_store(repo_path, disk_path)
+                        .write_symlink_to_store(repo_path, disk_path, is_symlink_on_disk)
                         .block_on()?;
                     Merge::normal(TreeValue::Symlink(id))
                 }
                 FileType::GitSubmodule => panic!("git submodule cannot be written to store"),
+                FileType::Skipped => panic!("skipped path cannot be written to store"),
             };
             if new_tree_values != current_tree_values {
                 Ok(Some(new_tree_values))
@@ -1477,6 +1667,7 @@ impl FileSnapshotter<'_> {
         disk_path: &Path,
         current_tree_values: &MergedTreeValue,
         executable: FileExecutableFlag,
+        executable_override: Option<bool>,
         materialized_conflict_data: Option<MaterializedConflictData>,
     ) -> Result<MergedTreeValue, SnapshotError> {
         if let Some(current_tree_value) = current_tree_values.as_resolved() {
@@ -1493,6 +1684,7 @@ impl FileSnapshotter<'_> {
                     false
                 }
             };
+            let executable = executable_override.unwrap_or(executable);
             Ok(Merge::normal(TreeValue::File { id, executable }))
         } else if let Some(old_file_ids) = current_tree_values.to_file_merge() {
             // If the file contained a conflict before and is a normal file on
@@ -1502,12 +1694,14 @@ impl FileSnapshotter<'_> {
                 message: format!("Failed to open file {}", disk_path.display()),
                 err: err.into(),
             })?;
+            self.check_content(repo_path, &content)?;
             let new_file_ids = conflicts::update_from_content(
                 &old_file_ids,
                 self.store(),
                 repo_path,
                 &content,
-                self.conflict_marker_style,
+                self.conflict_marker_style_overrides
+                    .get(repo_path, self.conflict_marker_style),
                 materialized_conflict_data.map_or(MIN_CONFLICT_MARKER_LEN, |data| {
                     data.conflict_marker_len as usize
                 }),
@@ -1527,7 +1721,7 @@ impl FileSnapshotter<'_> {
                     };
                     Ok(Merge::normal(TreeValue::File {
                         id: file_id.unwrap(),
-                        executable,
+                        executable: executable_override.unwrap_or(executable),
                     }))
                 }
                 Err(new_file_ids) => {
@@ -1548,6 +1742,18 @@ impl FileSnapshotter<'_> {
         path: &RepoPath,
         disk_path: &Path,
     ) -> Result<FileId, SnapshotError> {
+        if self.content_check.is_some() {
+            // The content has to be checked before it's written to the store
+            let content = fs::read(disk_path).map_err(|err| SnapshotError::Other {
+                message: format!("Failed to open file {}", disk_path.display()),
+                err: err.into(),
+            })?;
+            self.check_content(path, &content)?;
+            return Ok(self
+                .store()
+                .write_file(path, &mut content.as_slice())
+                .await?);
+        }
         let mut file = File::open(disk_path).map_err(|err| SnapshotError::Other {
             message: format!("Failed to open file {}", disk_path.display()),
             err: err.into(),
@@ -1555,12 +1761,23 @@ impl FileSnapshotter<'_> {
         Ok(self.store().write_file(path, &mut file).await?)
     }
 
+    fn check_content(&self, path: &RepoPath, content: &[u8]) -> Result<(), SnapshotError> {
+        if let Some(check) = self.content_check {
+            check(path, content).map_err(|err| SnapshotError::ContentRejected {
+                path: path.to_owned(),
+                err,
+            })?;
+        }
+        Ok(())
+    }
+
     async fn write_symlink_to_store(
         &self,
         path: &RepoPath,
         disk_path: &Path,
+        is_symlink_on_disk: bool,
     ) -> Result<SymlinkId, SnapshotError> {
-        if self.tree_state.symlink_support {
+        if is_symlink_on_disk {
             let target = disk_path.read_link().map_err(|err| SnapshotError::Other {
                 message: format!("Failed to read symlink {}", disk_path.display()),
                 err: err.into(),
@@ -1689,12 +1906,7 @@ impl TreeState {
             other => CheckoutError::InternalBackendError(other),
         })?;
         let stats = self
-            .update(
-                &old_tree,
-                new_tree,
-                self.sparse_matcher().as_ref(),
-                options.conflict_marker_style,
-            )
+            .update(&old_tree, new_tree, self.sparse_matcher().as_ref(), options)
             .block_on()?;
         self.tree_id = new_tree.id();
         Ok(stats)
@@ -1717,20 +1929,10 @@ impl TreeState {
         let removed_matcher = DifferenceMatcher::new(&old_matcher, &new_matcher);
         let empty_tree = MergedTree::resolved(Tree::empty(self.store.clone(), RepoPathBuf::root()));
         let added_stats = self
-            .update(
-                &empty_tree,
-                &tree,
-                &added_matcher,
-                options.conflict_marker_style,
-            )
+            .update(&empty_tree, &tree, &added_matcher, options)
             .block_on()?;
         let removed_stats = self
-            .update(
-                &tree,
-                &empty_tree,
-                &removed_matcher,
-                options.conflict_marker_style,
-            )
+            .update(&tree, &empty_tree, &removed_matcher, options)
             .block_on()?;
         self.sparse_patterns = sparse_patterns;
         assert_eq!(added_stats.updated_files, 0);
@@ -1743,6 +1945,8 @@ impl TreeState {
             added_files: added_stats.added_files,
             removed_files: removed_stats.removed_files,
             skipped_files: added_stats.skipped_files,
+            unrepresentable_paths: added_stats.unrepresentable_paths,
+            retracked_paths: added_stats.retracked_paths,
         })
     }
 
@@ -1751,7 +1955,7 @@ impl TreeState {
         old_tree: &MergedTree,
         new_tree: &MergedTree,
         matcher: &dyn Matcher,
-        conflict_marker_style: ConflictMarkerStyle,
+        options: &CheckoutOptions,
     ) -> Result<CheckoutStats, CheckoutError> {
         // TODO: maybe it's better not include the skipped counts in the "intended"
         // counts
@@ -1760,6 +1964,8 @@ impl TreeState {
             added_files: 0,
             removed_files: 0,
             skipped_files: 0,
+            unrepresentable_paths: vec![],
+            retracked_paths: vec![],
         };
         let mut changed_file_states = Vec::new();
         let mut deleted_files = HashSet::new();
@@ -1768,15 +1974,39 @@ impl TreeState {
             .map(|TreeDiffEntry { path, values }| async {
                 match values {
                     Ok((before, after)) => {
+                        // Keep the labels in sync with the simplified file conflict
+                        let labels = options
+                            .conflict_labels
+                            .as_ref()
+                            .filter(|labels| labels.num_sides() == after.num_sides())
+                            .and_then(|labels| {
+                                let file_ids = after.to_file_merge()?;
+                                Some(file_ids.simplify_other(labels))
+                            });
                         let result = materialize_tree_value(&self.store, &path, after).await;
-                        (path, result.map(|value| (before, value)))
+                        (path, result.map(|value| (before, value, labels)))
                     }
                     Err(err) => (path, Err(err)),
                 }
             })
             .buffered(self.store.concurrency().max(1));
         while let Some((path, data)) = diff_stream.next().await {
-            let (before, after) = data?;
+            let (before, after, labels) = data?;
+            // The path doesn't exist on the disk. Just update the file state.
+            if !is_representable_path(&path) {
+                if after.is_absent() {
+                    deleted_files.insert(path);
+                } else {
+                    changed_file_states.push((path.clone(), FileState::for_skipped()));
+                    stats.unrepresentable_paths.push(path);
+                }
+                continue;
+            }
+            // The file state of the path will be updated to match the new tree,
+            // so it can no longer be left untracked.
+            if after.is_present() && self.untracked_paths.binary_search(&path).is_ok() {
+                stats.retracked_paths.push(path.clone());
+            }
             if after.is_absent() {
                 stats.removed_files += 1;
             } else if before.is_absent() {
@@ -1855,10 +2085,14 @@ impl TreeState {
                     executable,
                 } => {
                     let conflict_marker_len = choose_materialized_conflict_marker_len(&contents);
+                    let conflict_marker_style = options
+                        .conflict_marker_style_overrides
+                        .get(&path, options.conflict_marker_style);
                     let data = materialize_merge_result_to_bytes_with_marker_len(
                         &contents,
                         conflict_marker_style,
                         conflict_marker_len,
+                        labels.as_ref(),
                     )
                     .into();
                     let materialized_conflict_data = MaterializedConflictData {
@@ -1883,6 +2117,8 @@ impl TreeState {
         }
         self.file_states
             .merge_in(changed_file_states, &deleted_files);
+        self.untracked_paths
+            .retain(|path| !stats.retracked_paths.contains(path));
         Ok(stats)
     }
 
@@ -1928,11 +2164,15 @@ impl TreeState {
                         }
                     }
                 };
-                let file_state = FileState {
-                    file_type,
-                    mtime: MillisSinceEpoch(0),
-                    size: 0,
-                    materialized_conflict_data: None,
+                let file_state = if is_representable_path(&path) {
+                    FileState {
+                        file_type,
+                        mtime: MillisSinceEpoch(0),
+                        size: 0,
+                        materialized_conflict_data: None,
+                    }
+                } else {
+                    FileState::for_skipped()
                 };
                 changed_file_states.push((path, file_state));
             }
@@ -1997,6 +2237,16 @@ impl WorkingCopy for LocalWorkingCopy {
         Ok(self.tree_state()?.sparse_patterns())
     }
 
+    fn untracked_paths(&self) -> Result<&[RepoPathBuf], WorkingCopyStateError> {
+        Ok(self.tree_state()?.untracked_paths())
+    }
+
+    fn file_type_overrides(
+        &self,
+    ) -> Result<&BTreeMap<RepoPathBuf, FileTypeOverride>, WorkingCopyStateError> {
+        Ok(self.tree_state()?.file_type_overrides())
+    }
+
     fn start_mutation(&self) -> Result<Box<dyn LockedWorkingCopy>, WorkingCopyStateError> {
         let lock_path = self.state_path.join("working_copy.lock");
         let lock = FileLock::lock(lock_path).map_err(|err| WorkingCopyStateError {
@@ -2337,6 +2587,38 @@ impl LockedWorkingCopy for LockedLocalWorkingCopy {
         Ok(stats)
     }
 
+    fn untracked_paths(&self) -> Result<&[RepoPathBuf], WorkingCopyStateError> {
+        self.wc.untracked_paths()
+    }
+
+    fn set_untracked_paths(
+        &mut self,
+        new_untracked_paths: Vec<RepoPathBuf>,
+    ) -> Result<(), WorkingCopyStateError> {
+        self.wc
+            .tree_state_mut()?
+            .set_untracked_paths(new_untracked_paths);
+        self.tree_state_dirty = true;
+        Ok(())
+    }
+
+    fn file_type_overrides(
+        &self,
+    ) -> Result<&BTreeMap<RepoPathBuf, FileTypeOverride>, WorkingCopyStateError> {
+        self.wc.file_type_overrides()
+    }
+
+    fn set_file_type_overrides(
+        &mut self,
+        new_file_type_overrides: BTreeMap<RepoPathBuf, FileTypeOverride>,
+    ) -> Result<(), WorkingCopyStateError> {
+        self.wc
+            .tree_state_mut()?
+            .set_file_type_overrides(new_file_type_overrides);
+        self.tree_state_dirty = true;
+        Ok(())
+    }
+
     #[instrument(skip_all)]
     fn finish(
         mut self: Box<Self>,
@@ -2579,4 +2861,28 @@ mod tests {
             None
         );
     }
+
+    #[test]
+    fn test_is_reserved_windows_name() {
+        assert!(is_reserved_windows_name("aux"));
+        assert!(is_reserved_windows_name("AUX"));
+        assert!(is_reserved_windows_name("nul.txt"));
+        assert!(is_reserved_windows_name("con .tar.gz"));
+        assert!(is_reserved_windows_name("com1"));
+        assert!(is_reserved_windows_name("LPT9.log"));
+        assert!(is_reserved_windows_name("CONOUT$"));
+        assert!(is_reserved_windows_name("foo."));
+        assert!(is_reserved_windows_name("foo "));
+        assert!(is_reserved_windows_name("a:b"));
+        assert!(is_reserved_windows_name("a?b"));
+        assert!(is_reserved_windows_name("a\tb"));
+
+        assert!(!is_reserved_windows_name("auxiliary"));
+        assert!(!is_reserved_windows_name("com0"));
+        assert!(!is_reserved_windows_name("com10"));
+        assert!(!is_reserved_windows_name("lpt"));
+        assert!(!is_reserved_windows_name(".aux"));
+        assert!(!is_reserved_windows_name("foo.bar"));
+        assert!(!is_reserved_windows_name(" foo"));
+    }
 }
@@ -20,6 +20,7 @@ use std::time::Duration;
 use tracing::instrument;
 
 use super::FileLockError;
+use super::LockOwner;
 
 pub struct FileLock {
     path: PathBuf,
@@ -66,6 +67,15 @@ impl FileLock {
         loop {
             match options.open(&path) {
                 Ok(file) => {
+                    if let Err(err) = LockOwner::current().write_to(&file) {
+                        let lock = FileLock { path, _file: file };
+                        return Err(FileLockError {
+                            message: "Failed to write lock file",
+                            path: lock.path.clone(),
+                            owner: None,
+                            err,
+                        });
+                    }
                     return Ok(FileLock { path, _file: file });
                 }
                 Err(err)
@@ -76,9 +86,11 @@ impl FileLock {
                     if let Some(duration) = backoff_iterator.next() {
                         std::thread::sleep(duration);
                     } else {
+                        let owner = LockOwner::read(&path).ok().flatten();
                         return Err(FileLockError {
                             message: "Timed out while trying to create lock file",
                             path,
+                            owner,
                             err,
                         });
                     }
@@ -87,6 +99,7 @@ impl FileLock {
                     return Err(FileLockError {
                         message: "Failed to create lock file",
                         path,
+                        owner: None,
                         err,
                     })
                 }
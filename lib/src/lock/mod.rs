@@ -18,8 +18,14 @@ mod fallback;
 #[cfg(unix)]
 mod unix;
 
+use std::fmt;
+use std::fs;
 use std::io;
+use std::io::Write as _;
+use std::path::Path;
 use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
 
 use thiserror::Error;
 
@@ -29,14 +35,184 @@ pub use self::fallback::FileLock;
 pub use self::unix::FileLock;
 
 #[derive(Debug, Error)]
-#[error("{message}: {path}")]
+#[error(
+    "{message}: {path}{}",
+    owner.as_ref().map(|owner| format!(" (held by {owner})")).unwrap_or_default()
+)]
 pub struct FileLockError {
     pub message: &'static str,
     pub path: PathBuf,
+    /// Holder of the lock, if known.
+    pub owner: Option<LockOwner>,
     #[source]
     pub err: io::Error,
 }
 
+/// How long a lock is expected to be held at most. A lock held for longer is
+/// reported as stale, since its owner has probably gone away without
+/// releasing it, e.g. on a network file system.
+pub const LOCK_LEASE: Duration = Duration::from_secs(60 * 60);
+
+/// Process that holds a lock, as recorded in the lock file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LockOwner {
+    pub pid: u32,
+    pub hostname: String,
+    /// When the lock was acquired, in seconds since the Unix epoch.
+    pub acquired: u64,
+}
+
+impl LockOwner {
+    /// Returns the owner record for the current process.
+    pub fn current() -> Self {
+        LockOwner {
+            pid: std::process::id(),
+            hostname: current_hostname(),
+            acquired: seconds_since_epoch(SystemTime::now()),
+        }
+    }
+
+    /// Reads the owner record from the lock file at `path`.
+    ///
+    /// Returns `None` if the file doesn't exist or doesn't contain a valid
+    /// record, which happens if the holder crashed before writing it.
+    pub fn read(path: &Path) -> io::Result<Option<Self>> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(Self::parse(&content)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn parse(content: &str) -> Option<Self> {
+        let mut pid = None;
+        let mut hostname = None;
+        let mut acquired = None;
+        for line in content.lines() {
+            match line.split_once(' ')? {
+                ("pid", value) => pid = value.parse().ok(),
+                ("host", value) => hostname = Some(value.to_owned()),
+                ("acquired", value) => acquired = value.parse().ok(),
+                _ => {}
+            }
+        }
+        Some(LockOwner {
+            pid: pid?,
+            hostname: hostname?,
+            acquired: acquired?,
+        })
+    }
+
+    fn write_to(&self, mut file: &fs::File) -> io::Result<()> {
+        file.set_len(0)?;
+        write!(
+            file,
+            "pid {}\nhost {}\nacquired {}\n",
+            self.pid, self.hostname, self.acquired
+        )?;
+        file.flush()
+    }
+
+    /// Returns why the lock looks stale, or `None` if the owner is probably
+    /// still holding it.
+    ///
+    /// This is a heuristic for diagnostics. A process with the same pid may
+    /// still hold the lock, e.g. if it was forked, or if it runs in another
+    /// pid namespace. Stale locks are therefore never removed automatically.
+    pub fn stale_reason(&self) -> Option<StaleLockReason> {
+        if self.hostname == current_hostname() && is_process_dead(self.pid) {
+            Some(StaleLockReason::DeadProcess)
+        } else if seconds_since_epoch(SystemTime::now()).saturating_sub(self.acquired)
+            > LOCK_LEASE.as_secs()
+        {
+            Some(StaleLockReason::LeaseExpired)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for LockOwner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "process {} on {}", self.pid, self.hostname)
+    }
+}
+
+/// Reason why a lock is considered abandoned by its owner.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StaleLockReason {
+    /// The owner process on this host is no longer running.
+    DeadProcess,
+    /// The lock has been held for longer than [`LOCK_LEASE`].
+    LeaseExpired,
+}
+
+impl fmt::Display for StaleLockReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StaleLockReason::DeadProcess => f.write_str("the owner process is not running"),
+            StaleLockReason::LeaseExpired => f.write_str("the lock lease has expired"),
+        }
+    }
+}
+
+/// Removes the lock file at `path`, even if its owner still holds it.
+///
+/// This is only meant to be requested by the user, after making sure that no
+/// other process is using the repo. Returns `true` if the lock file existed.
+pub fn break_lock(path: &Path) -> io::Result<bool> {
+    match fs::remove_file(path) {
+        Ok(()) => {
+            tracing::warn!(?path, "Removed lock file");
+            Ok(true)
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+fn seconds_since_epoch(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+fn current_hostname() -> String {
+    #[cfg(unix)]
+    {
+        rustix::system::uname()
+            .nodename()
+            .to_string_lossy()
+            .into_owned()
+    }
+    #[cfg(not(unix))]
+    {
+        std::env::var("COMPUTERNAME").unwrap_or_default()
+    }
+}
+
+/// Returns `true` if there's definitely no process with the given `pid` on
+/// this host. Returns `false` if the process is running or if that can't be
+/// determined.
+fn is_process_dead(pid: u32) -> bool {
+    #[cfg(unix)]
+    {
+        // A pid that can't name any process can't be held by a live owner.
+        let Some(pid) = i32::try_from(pid)
+            .ok()
+            .and_then(rustix::process::Pid::from_raw)
+        else {
+            return true;
+        };
+        rustix::process::test_kill_process(pid) == Err(rustix::io::Errno::SRCH)
+    }
+    #[cfg(not(unix))]
+    {
+        // We have no way to tell, so assume the worst.
+        let _ = pid;
+        false
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::cmp::max;
@@ -85,4 +261,60 @@ mod tests {
         let value = u32::from_le_bytes(data.try_into().unwrap());
         assert_eq!(value, num_threads as u32);
     }
+
+    #[test_case(FileLock::lock)]
+    #[cfg_attr(unix, test_case(fallback::FileLock::lock))]
+    fn lock_records_owner<T>(lock_fn: fn(PathBuf) -> Result<T, FileLockError>) {
+        let temp_dir = new_temp_dir();
+        let lock_path = temp_dir.path().join("test.lock");
+        let _lock = lock_fn(lock_path.clone()).unwrap();
+        let owner = LockOwner::read(&lock_path).unwrap().unwrap();
+        assert_eq!(owner.pid, std::process::id());
+        assert_eq!(owner.stale_reason(), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn lock_reuses_unlocked_file() {
+        // A lock file left behind by a crashed process isn't locked by flock.
+        let temp_dir = new_temp_dir();
+        let lock_path = temp_dir.path().join("test.lock");
+        let hostname = current_hostname();
+        fs::write(&lock_path, format!("pid 0\nhost {hostname}\nacquired 0\n")).unwrap();
+        let _lock = FileLock::lock(lock_path.clone()).unwrap();
+        let owner = LockOwner::read(&lock_path).unwrap().unwrap();
+        assert_eq!(owner.pid, std::process::id());
+    }
+
+    #[test]
+    fn lock_owner_stale_reason() {
+        let current = LockOwner::current();
+        assert_eq!(current.stale_reason(), None);
+        // Processes on other hosts can't be checked, but the lease expires.
+        let other_host = LockOwner {
+            pid: 0,
+            hostname: format!("{}-other", current.hostname),
+            ..current.clone()
+        };
+        assert_eq!(other_host.stale_reason(), None);
+        let expired = LockOwner {
+            acquired: current.acquired - LOCK_LEASE.as_secs() - 1,
+            ..other_host
+        };
+        assert_eq!(expired.stale_reason(), Some(StaleLockReason::LeaseExpired));
+        #[cfg(unix)]
+        {
+            let dead = LockOwner { pid: 0, ..current };
+            assert_eq!(dead.stale_reason(), Some(StaleLockReason::DeadProcess));
+        }
+    }
+
+    #[test]
+    fn lock_owner_parse() {
+        let owner = LockOwner::parse("pid 42\nhost example\nacquired 1000\n").unwrap();
+        assert_eq!(owner.to_string(), "process 42 on example");
+        assert_eq!(owner.acquired, 1000);
+        assert_eq!(LockOwner::parse(""), None);
+        assert_eq!(LockOwner::parse("pid 42\nhost example\n"), None);
+    }
 }
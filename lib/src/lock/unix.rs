@@ -15,12 +15,14 @@
 #![allow(missing_docs)]
 
 use std::fs::File;
+use std::fs::OpenOptions;
 use std::path::PathBuf;
 
 use rustix::fs::FlockOperation;
 use tracing::instrument;
 
 use super::FileLockError;
+use super::LockOwner;
 
 pub struct FileLock {
     path: PathBuf,
@@ -30,24 +32,50 @@ pub struct FileLock {
 impl FileLock {
     pub fn lock(path: PathBuf) -> Result<FileLock, FileLockError> {
         loop {
-            // Create lockfile, or open pre-existing one
-            let file = File::create(&path).map_err(|err| FileLockError {
-                message: "Failed to open lock file",
-                path: path.clone(),
-                err,
-            })?;
-            // If the lock was already held, wait for it to be released
-            rustix::fs::flock(&file, FlockOperation::LockExclusive).map_err(|errno| {
-                FileLockError {
-                    message: "Failed to lock lock file",
+            // Create lockfile, or open pre-existing one. Don't truncate it
+            // since it records the current holder.
+            let file = OpenOptions::new()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(&path)
+                .map_err(|err| FileLockError {
+                    message: "Failed to open lock file",
                     path: path.clone(),
-                    err: errno.into(),
+                    owner: None,
+                    err,
+                })?;
+            // If the lock is already held, wait for it to be released. The lock
+            // is never broken since flock locks are released when the holder
+            // exits, so someone is still holding it.
+            match rustix::fs::flock(&file, FlockOperation::NonBlockingLockExclusive) {
+                Ok(()) => {}
+                Err(rustix::io::Errno::WOULDBLOCK) => {
+                    let owner = LockOwner::read(&path).ok().flatten();
+                    tracing::debug!(?path, ?owner, "Waiting for lock");
+                    rustix::fs::flock(&file, FlockOperation::LockExclusive).map_err(|errno| {
+                        FileLockError {
+                            message: "Failed to lock lock file",
+                            path: path.clone(),
+                            owner,
+                            err: errno.into(),
+                        }
+                    })?;
                 }
-            })?;
+                Err(errno) => {
+                    return Err(FileLockError {
+                        message: "Failed to lock lock file",
+                        path: path.clone(),
+                        owner: None,
+                        err: errno.into(),
+                    });
+                }
+            }
 
             let stat = rustix::fs::fstat(&file).map_err(|errno| FileLockError {
                 message: "failed to stat lock file",
                 path: path.clone(),
+                owner: None,
                 err: errno.into(),
             })?;
             if stat.st_nlink == 0 {
@@ -58,6 +86,14 @@ impl FileLock {
                 continue;
             }
 
+            LockOwner::current()
+                .write_to(&file)
+                .map_err(|err| FileLockError {
+                    message: "Failed to write lock file",
+                    path: path.clone(),
+                    owner: None,
+                    err,
+                })?;
             return Ok(Self { path, file });
         }
     }
@@ -0,0 +1,219 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Mapping of author and committer identities to canonical ones, as described
+//! by Git's `.mailmap` files.
+
+use std::collections::HashMap;
+use std::io::Read as _;
+
+use crate::backend::BackendError;
+use crate::backend::BackendResult;
+use crate::backend::Signature;
+use crate::backend::TreeValue;
+use crate::merged_tree::MergedTree;
+use crate::repo_path::RepoPath;
+
+/// Canonical name and email which replace the ones recorded in a commit. Unset
+/// fields keep the recorded value.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct Replacement {
+    name: Option<String>,
+    email: Option<String>,
+}
+
+impl Replacement {
+    fn update(&mut self, name: Option<&str>, email: Option<&str>) {
+        if let Some(name) = name {
+            self.name = Some(name.to_owned());
+        }
+        if let Some(email) = email {
+            self.email = Some(email.to_owned());
+        }
+    }
+}
+
+/// Replacements for one recorded email address.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct EmailEntry {
+    /// Replacement for any recorded name.
+    default: Option<Replacement>,
+    /// Replacements for specific recorded names, keyed by lowercase name.
+    by_name: HashMap<String, Replacement>,
+}
+
+/// Parsed contents of one or more mailmap files.
+///
+/// Recorded names and emails are matched case-insensitively, and entries
+/// parsed later take precedence over earlier ones, as in Git.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Mailmap {
+    /// Entries keyed by lowercase recorded email.
+    entries: HashMap<String, EmailEntry>,
+}
+
+impl Mailmap {
+    /// Parses the contents of a mailmap file. Malformed lines are ignored.
+    pub fn parse(text: &str) -> Self {
+        let mut mailmap = Mailmap::default();
+        mailmap.add_text(text);
+        mailmap
+    }
+
+    /// Reads the `.mailmap` file at the root of the `tree`. Returns an empty
+    /// mailmap if there's no such file or if it's conflicted.
+    pub fn from_tree(tree: &MergedTree) -> BackendResult<Self> {
+        let path = RepoPath::from_internal_string(".mailmap");
+        let Ok(Some(TreeValue::File { id, .. })) = tree.path_value(path)?.into_resolved() else {
+            return Ok(Mailmap::default());
+        };
+        let mut content = vec![];
+        tree.store()
+            .read_file(path, &id)?
+            .read_to_end(&mut content)
+            .map_err(|err| BackendError::ReadFile {
+                path: path.to_owned(),
+                id: id.clone(),
+                source: err.into(),
+            })?;
+        Ok(Mailmap::parse(&String::from_utf8_lossy(&content)))
+    }
+
+    /// Adds the entries of another mailmap file, which take precedence over
+    /// the existing entries.
+    pub fn add_text(&mut self, text: &str) {
+        for line in text.lines() {
+            self.add_line(line);
+        }
+    }
+
+    /// Returns true if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn add_line(&mut self, line: &str) {
+        if line.starts_with('#') {
+            return;
+        }
+        let Some((name1, email1, rest)) = parse_name_and_email(line) else {
+            return;
+        };
+        let (old_name, old_email, new_email) = match parse_name_and_email(rest) {
+            Some((name2, email2, _)) => (name2, email2, Some(email1)),
+            None => (None, email1, None),
+        };
+        let entry = self.entries.entry(old_email.to_lowercase()).or_default();
+        let replacement = match old_name {
+            Some(old_name) => entry.by_name.entry(old_name.to_lowercase()).or_default(),
+            None => entry.default.get_or_insert_with(Default::default),
+        };
+        replacement.update(name1, new_email);
+    }
+
+    /// Returns the canonical name and email for the recorded `name` and
+    /// `email`.
+    pub fn resolve<'a>(&'a self, name: &'a str, email: &'a str) -> (&'a str, &'a str) {
+        let Some(entry) = self.entries.get(&email.to_lowercase()) else {
+            return (name, email);
+        };
+        let replacement = entry
+            .by_name
+            .get(&name.to_lowercase())
+            .or(entry.default.as_ref());
+        match replacement {
+            Some(replacement) => (
+                replacement.name.as_deref().unwrap_or(name),
+                replacement.email.as_deref().unwrap_or(email),
+            ),
+            None => (name, email),
+        }
+    }
+
+    /// Returns the `signature` with the canonical name and email.
+    pub fn resolve_signature(&self, signature: &Signature) -> Signature {
+        let (name, email) = self.resolve(&signature.name, &signature.email);
+        Signature {
+            name: name.to_owned(),
+            email: email.to_owned(),
+            timestamp: signature.timestamp,
+        }
+    }
+}
+
+/// Parses `Name <email>` at the start of `text`, returning the name (if not
+/// empty), the email, and the remaining text.
+fn parse_name_and_email(text: &str) -> Option<(Option<&str>, &str, &str)> {
+    let (name, rest) = text.split_once('<')?;
+    let (email, rest) = rest.split_once('>')?;
+    let name = name.trim();
+    let name = (!name.is_empty()).then_some(name);
+    Some((name, email.trim(), rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let mailmap = Mailmap::parse(
+            "\
+# Comment
+Proper Name <old@example.com>
+<proper@example.com> <Other@Example.com>
+Both Name <both@example.com> <both-old@example.com>
+Specific <specific@example.com> Old Name <shared@example.com>
+Fallback <shared@example.com>
+malformed line
+",
+        );
+        assert_eq!(
+            mailmap.resolve("Old", "old@example.com"),
+            ("Proper Name", "old@example.com")
+        );
+        assert_eq!(
+            mailmap.resolve("Other", "other@example.COM"),
+            ("Other", "proper@example.com")
+        );
+        assert_eq!(
+            mailmap.resolve("Someone", "both-old@example.com"),
+            ("Both Name", "both@example.com")
+        );
+        assert_eq!(
+            mailmap.resolve("old name", "shared@example.com"),
+            ("Specific", "specific@example.com")
+        );
+        assert_eq!(
+            mailmap.resolve("Another Name", "shared@example.com"),
+            ("Fallback", "shared@example.com")
+        );
+        assert_eq!(
+            mailmap.resolve("Unknown", "unknown@example.com"),
+            ("Unknown", "unknown@example.com")
+        );
+    }
+
+    #[test]
+    fn test_later_entries_take_precedence() {
+        let mut mailmap = Mailmap::parse("First <first@example.com> <old@example.com>\n");
+        mailmap.add_text("Second <old@example.com>\n");
+        assert_eq!(
+            mailmap.resolve("Old", "old@example.com"),
+            ("Second", "first@example.com")
+        );
+        assert!(!mailmap.is_empty());
+        assert!(Mailmap::parse("# only a comment\n").is_empty());
+    }
+}
@@ -289,6 +289,22 @@ impl<T> Merge<T> {
         self
     }
 
+    /// Reorders and removes the terms of `other` the same way `simplify()`
+    /// would for this merge. Useful for keeping data associated with each term
+    /// in sync with the simplified merge.
+    pub fn simplify_other<U: Clone>(&self, other: &Merge<U>) -> Merge<U>
+    where
+        T: PartialEq,
+    {
+        assert_eq!(self.values.len(), other.values.len());
+        let values = self
+            .get_simplified_mapping()
+            .iter()
+            .map(|index| other.values[*index].clone())
+            .collect();
+        Merge { values }
+    }
+
     /// Updates the merge based on the given simplified merge.
     pub fn update_from_simplified(mut self, simplified: Merge<T>) -> Self
     where
@@ -967,6 +983,24 @@ mod tests {
         assert_eq!(c(&[0, 1, 2, 3, 4, 5, 1]).simplify(), c(&[0, 3, 4, 5, 2]));
     }
 
+    #[test]
+    fn test_simplify_other() {
+        let labels =
+            |terms: &[&str]| Merge::from_vec(terms.iter().map(|s| s.to_string()).collect_vec());
+        assert_eq!(
+            c(&[0, 1, 2]).simplify_other(&labels(&["a", "b", "c"])),
+            labels(&["a", "b", "c"])
+        );
+        assert_eq!(
+            c(&[0, 0, 1]).simplify_other(&labels(&["a", "b", "c"])),
+            labels(&["c"])
+        );
+        assert_eq!(
+            c(&[0, 1, 2, 3, 4, 5, 1]).simplify_other(&labels(&["a", "b", "c", "d", "e", "f", "g"])),
+            labels(&["a", "d", "e", "f", "c"])
+        );
+    }
+
     #[test]
     fn test_update_from_simplified() {
         // 1-way merge
@@ -0,0 +1,307 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-path strategies for resolving file conflicts automatically.
+
+use std::cmp::Reverse;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use bstr::BString;
+use itertools::Itertools as _;
+use pollster::FutureExt as _;
+use serde::Deserialize;
+
+use crate::backend::BackendError;
+use crate::backend::BackendResult;
+use crate::backend::MergedTreeId;
+use crate::backend::TreeValue;
+use crate::config::ConfigGetError;
+use crate::config::ConfigNamePathBuf;
+use crate::conflicts::extract_as_single_hunk;
+use crate::files;
+use crate::files::MergeResult;
+use crate::merge::Merge;
+use crate::merged_tree::MergedTree;
+use crate::merged_tree::MergedTreeBuilder;
+use crate::repo_path::RepoPath;
+use crate::settings::UserSettings;
+
+/// How to resolve the conflicting regions of a file.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MergeStrategy {
+    /// Take the first side of each conflicting region.
+    Ours,
+    /// Take the last side of each conflicting region.
+    Theirs,
+    /// Take all sides of each conflicting region, one after another.
+    Union,
+    /// Run an external program to merge the whole file.
+    Driver(MergeDriver),
+}
+
+/// Config representation of a `MergeStrategy`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawMergeStrategy {
+    Builtin(String),
+    Driver { command: Vec<String> },
+}
+
+impl MergeStrategy {
+    fn from_raw(raw: RawMergeStrategy) -> Result<Self, String> {
+        match raw {
+            RawMergeStrategy::Builtin(name) => match name.as_str() {
+                "ours" => Ok(MergeStrategy::Ours),
+                "theirs" => Ok(MergeStrategy::Theirs),
+                "union" => Ok(MergeStrategy::Union),
+                _ => Err(r#"Expected one of "ours", "theirs", or "union""#.to_owned()),
+            },
+            RawMergeStrategy::Driver { command } => {
+                if command.is_empty() {
+                    Err("The merge driver command is empty".to_owned())
+                } else {
+                    Ok(MergeStrategy::Driver(MergeDriver { command }))
+                }
+            }
+        }
+    }
+
+    /// Resolves the conflict given the `hunks` of its merge result. Returns
+    /// `None` for merge drivers, which are run by a [`MergeDriverRunner`]
+    /// instead.
+    pub fn resolve(&self, hunks: &[Merge<BString>]) -> Option<BString> {
+        match self {
+            MergeStrategy::Ours => Some(resolve_hunks(hunks, |hunk, content| {
+                content.extend_from_slice(hunk.first());
+            })),
+            MergeStrategy::Theirs => Some(resolve_hunks(hunks, |hunk, content| {
+                content.extend_from_slice(hunk.adds().last().unwrap());
+            })),
+            MergeStrategy::Union => Some(resolve_hunks(hunks, |hunk, content| {
+                for side in hunk.adds() {
+                    content.extend_from_slice(side);
+                }
+            })),
+            MergeStrategy::Driver(_) => None,
+        }
+    }
+}
+
+fn resolve_hunks(
+    hunks: &[Merge<BString>],
+    resolve_conflict: impl Fn(&Merge<BString>, &mut BString),
+) -> BString {
+    let mut content = BString::default();
+    for hunk in hunks {
+        if let Some(resolved) = hunk.as_resolved() {
+            content.extend_from_slice(resolved);
+        } else {
+            resolve_conflict(hunk, &mut content);
+        }
+    }
+    content
+}
+
+/// External program that merges the sides of a 2-sided file conflict.
+///
+/// The arguments `$base`, `$left`, `$right`, and `$output` are replaced with
+/// paths to temporary files, and `$path` with the path of the file in the repo.
+/// The output file initially has the contents of the left side. If the program
+/// exits successfully, the output file is taken as the resolution. Otherwise,
+/// the conflict is left as is.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MergeDriver {
+    /// Program and arguments to run.
+    pub command: Vec<String>,
+}
+
+/// Runs merge drivers on behalf of [`MergeStrategies`]. The library doesn't
+/// spawn processes itself, so merge drivers are only used if the caller
+/// provides a runner.
+pub trait MergeDriverRunner: Debug + Send + Sync {
+    /// Runs `driver` for the 2-sided conflict with the given `contents` in the
+    /// file at `path`. Returns the resolved content, or `None` if the driver
+    /// left the conflict unresolved.
+    fn run(
+        &self,
+        driver: &MergeDriver,
+        path: &RepoPath,
+        contents: &Merge<BString>,
+    ) -> Result<Option<BString>, Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// Merge strategies configured for paths in the `merge.strategies` table.
+#[derive(Clone, Debug, Default)]
+pub struct MergeStrategies {
+    /// Patterns and their strategies, most specific (longest) pattern first.
+    rules: Vec<(glob::Pattern, MergeStrategy)>,
+    driver_runner: Option<Arc<dyn MergeDriverRunner>>,
+}
+
+impl MergeStrategies {
+    /// Loads the strategies from the `merge.strategies` config table, which
+    /// maps glob patterns relative to the repo root to strategy names or
+    /// merge driver tables.
+    pub fn from_settings(settings: &UserSettings) -> Result<Self, ConfigGetError> {
+        let rules = settings
+            .table_keys("merge.strategies")
+            .map(|pattern| {
+                let name = ["merge", "strategies", pattern];
+                let raw: RawMergeStrategy = settings.get(name)?;
+                let invalid =
+                    |error: Box<dyn std::error::Error + Send + Sync>| ConfigGetError::Type {
+                        name: ConfigNamePathBuf::from_iter(name).to_string(),
+                        error,
+                        source_path: None,
+                    };
+                let glob = glob::Pattern::new(pattern).map_err(|err| invalid(err.into()))?;
+                let strategy = MergeStrategy::from_raw(raw).map_err(|err| invalid(err.into()))?;
+                Ok((glob, strategy))
+            })
+            .try_collect()?;
+        Ok(Self::new(rules))
+    }
+
+    /// Creates strategies from pairs of patterns and strategies.
+    pub fn new(mut rules: Vec<(glob::Pattern, MergeStrategy)>) -> Self {
+        rules.sort_by_key(|(glob, _)| (Reverse(glob.as_str().len()), glob.as_str().to_owned()));
+        MergeStrategies {
+            rules,
+            driver_runner: None,
+        }
+    }
+
+    /// Sets the runner of merge drivers. Without one, conflicts in files with
+    /// a merge driver are left as is.
+    pub fn with_driver_runner(mut self, runner: Arc<dyn MergeDriverRunner>) -> Self {
+        self.driver_runner = Some(runner);
+        self
+    }
+
+    /// Returns the strategy of the most specific pattern matching `path`.
+    pub fn get(&self, path: &RepoPath) -> Option<&MergeStrategy> {
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        };
+        let path = path.as_internal_file_string();
+        self.rules
+            .iter()
+            .find(|(glob, _)| glob.matches_with(path, options))
+            .map(|(_, strategy)| strategy)
+    }
+
+    /// Resolves the file conflicts in `tree` at paths that have a strategy,
+    /// and returns the id of the resulting tree.
+    pub fn resolve_tree_conflicts(&self, tree: &MergedTree) -> BackendResult<MergedTreeId> {
+        if self.rules.is_empty() || !tree.has_conflict() {
+            return Ok(tree.id());
+        }
+        let store = tree.store();
+        let mut tree_builder = MergedTreeBuilder::new(tree.id());
+        for (path, value) in tree.conflicts() {
+            let Some(strategy) = self.get(&path) else {
+                continue;
+            };
+            let value = value?;
+            // Like when merging trees, only conflicts between files with the
+            // same executable bit can be resolved.
+            let (Some(file_merge), Some(executable_merge)) =
+                (value.to_file_merge(), value.to_executable_merge())
+            else {
+                continue;
+            };
+            let Some(&executable) = executable_merge.resolve_trivial() else {
+                continue;
+            };
+            let file_merge = file_merge.simplify();
+            if file_merge.iter().any(Option::is_none) {
+                continue;
+            }
+            let contents = extract_as_single_hunk(&file_merge, store, &path).block_on()?;
+            let MergeResult::Conflict(hunks) = files::merge(&contents) else {
+                continue;
+            };
+            let merged_content = match strategy {
+                MergeStrategy::Driver(driver) => {
+                    let Some(runner) = &self.driver_runner else {
+                        continue;
+                    };
+                    if contents.num_sides() != 2 {
+                        continue;
+                    }
+                    runner
+                        .run(driver, &path, &contents)
+                        .map_err(BackendError::Other)?
+                }
+                _ => strategy.resolve(&hunks),
+            };
+            let Some(merged_content) = merged_content else {
+                continue;
+            };
+            let id = store
+                .write_file(&path, &mut merged_content.as_slice())
+                .block_on()?;
+            tree_builder.set_or_remove(path, Merge::normal(TreeValue::File { id, executable }));
+        }
+        tree_builder.write_tree(store)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hunks() -> Vec<Merge<BString>> {
+        vec![
+            Merge::resolved("a\n".into()),
+            Merge::from_vec(vec!["left\n".into(), "base\n".into(), "right\n".into()]),
+            Merge::resolved("b\n".into()),
+        ]
+    }
+
+    #[test]
+    fn test_resolve_hunks() {
+        let resolve = |strategy: MergeStrategy| strategy.resolve(&hunks());
+        assert_eq!(resolve(MergeStrategy::Ours), Some("a\nleft\nb\n".into()));
+        assert_eq!(resolve(MergeStrategy::Theirs), Some("a\nright\nb\n".into()));
+        assert_eq!(
+            resolve(MergeStrategy::Union),
+            Some("a\nleft\nright\nb\n".into())
+        );
+    }
+
+    #[test]
+    fn test_get_strategy() {
+        let strategies = MergeStrategies::new(vec![
+            (
+                glob::Pattern::new("**/*.lock").unwrap(),
+                MergeStrategy::Theirs,
+            ),
+            (glob::Pattern::new("*.md").unwrap(), MergeStrategy::Union),
+            (
+                glob::Pattern::new("docs/CHANGES.md").unwrap(),
+                MergeStrategy::Ours,
+            ),
+        ]);
+        let get = |path| strategies.get(RepoPath::from_internal_string(path));
+        assert_eq!(get("CHANGELOG.md"), Some(&MergeStrategy::Union));
+        assert_eq!(get("docs/index.md"), None);
+        assert_eq!(get("docs/CHANGES.md"), Some(&MergeStrategy::Ours));
+        assert_eq!(get("Cargo.lock"), Some(&MergeStrategy::Theirs));
+        assert_eq!(get("sub/dir/Cargo.lock"), Some(&MergeStrategy::Theirs));
+        assert_eq!(get("src/main.rs"), None);
+    }
+}
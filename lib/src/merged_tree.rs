@@ -47,6 +47,7 @@ use crate::copies::CopiesTreeDiffStream;
 use crate::copies::CopyRecords;
 use crate::matchers::EverythingMatcher;
 use crate::matchers::Matcher;
+use crate::matchers::Visit;
 use crate::merge::Merge;
 use crate::merge::MergeBuilder;
 use crate::merge::MergedTreeVal;
@@ -58,6 +59,8 @@ use crate::store::Store;
 use crate::tree::try_resolve_file_conflict;
 use crate::tree::Tree;
 use crate::tree_builder::TreeBuilder;
+use crate::tree_diff_cache::TreeConflictsCacheEntry;
+use crate::tree_diff_cache::TreeDiffCacheEntry;
 
 /// Presents a view of a merged set of trees.
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -185,7 +188,16 @@ impl MergedTree {
     pub fn conflicts(
         &self,
     ) -> impl Iterator<Item = (RepoPathBuf, BackendResult<MergedTreeValue>)> + use<> {
-        ConflictIterator::new(self)
+        let cached_entries = self
+            .store()
+            .tree_diff_cache()
+            .filter(|_| self.has_conflict())
+            .and_then(|cache| cache.get_conflicts(&self.id()));
+        if let Some(entries) = cached_entries {
+            Either::Left(entries.into_iter().map(|(path, value)| (path, Ok(value))))
+        } else {
+            Either::Right(ConflictIterator::new(self))
+        }
     }
 
     /// Whether this tree has conflicts.
@@ -283,10 +295,49 @@ impl MergedTree {
     ///
     /// The files in a removed tree will be returned before a file that replaces
     /// it.
+    ///
+    /// The full diff is looked up in and saved to the store's tree diff cache
+    /// if enabled. Diffs restricted by the `matcher` are filtered from the
+    /// cached full diff, but aren't cached themselves.
     pub fn diff_stream<'matcher>(
         &self,
         other: &MergedTree,
         matcher: &'matcher dyn Matcher,
+    ) -> TreeDiffStream<'matcher> {
+        let Some(cache) = self.store().tree_diff_cache() else {
+            return self.diff_stream_uncached(other, matcher);
+        };
+        if self.trees == other.trees {
+            return self.diff_stream_uncached(other, matcher);
+        }
+        let (from_id, to_id) = (self.id(), other.id());
+        if let Some(entries) = cache.get_diff(&from_id, &to_id) {
+            let entries = entries
+                .into_iter()
+                .filter(|(path, _, _)| matcher.matches(path))
+                .map(|(path, before, after)| TreeDiffEntry {
+                    path,
+                    values: Ok((before, after)),
+                })
+                .collect_vec();
+            return Box::pin(futures::stream::iter(entries));
+        }
+        // Only full diffs are cached
+        if !matches!(matcher.visit(RepoPath::root()), Visit::AllRecursively) {
+            return self.diff_stream_uncached(other, matcher);
+        }
+        Box::pin(CachingTreeDiffStream {
+            inner: self.diff_stream_uncached(other, matcher),
+            store: self.store().clone(),
+            ids: (from_id, to_id),
+            entries: Some(vec![]),
+        })
+    }
+
+    fn diff_stream_uncached<'matcher>(
+        &self,
+        other: &MergedTree,
+        matcher: &'matcher dyn Matcher,
     ) -> TreeDiffStream<'matcher> {
         let concurrency = self.store().concurrency();
         if concurrency <= 1 {
@@ -327,6 +378,35 @@ impl MergedTree {
         self.merge_no_resolve(base, other).resolve()
     }
 
+    /// Like `merge()`, but also reorders and removes the `terms` the same way
+    /// as the terms of the merged tree. `terms` are associated with the terms
+    /// of `self`, `base`, and `other` in that order. Returns `None` instead of
+    /// the terms if the merged tree is resolved.
+    pub fn merge_with_terms<T: Clone>(
+        &self,
+        base: &MergedTree,
+        other: &MergedTree,
+        terms: Merge<Merge<T>>,
+    ) -> BackendResult<(MergedTree, Option<Merge<T>>)> {
+        let nested = Merge::from_vec(vec![
+            self.trees.clone(),
+            base.trees.clone(),
+            other.trees.clone(),
+        ]);
+        let trees = nested.flatten();
+        let terms = trees.simplify_other(&terms.flatten());
+        let trees = trees.simplify();
+        // Same as `resolve()`, which preserves the arity of the conflicts
+        // before simplifying
+        let merged = merge_trees(&trees)?;
+        if merged.is_resolved() {
+            return Ok((MergedTree { trees: merged }, None));
+        }
+        let terms = merged.simplify_other(&terms);
+        let trees = merged.simplify();
+        Ok((MergedTree { trees }, Some(terms)))
+    }
+
     /// Merges this tree with `other`, using `base` as base, without attempting
     /// to resolve file conflicts.
     pub fn merge_no_resolve(&self, base: &MergedTree, other: &MergedTree) -> MergedTree {
@@ -354,6 +434,42 @@ pub struct TreeDiffEntry {
 /// ones) can fetch trees asynchronously.
 pub type TreeDiffStream<'matcher> = BoxStream<'matcher, TreeDiffEntry>;
 
+/// Saves the entries of the diff to the cache once the inner stream is
+/// exhausted.
+struct CachingTreeDiffStream<'matcher> {
+    inner: TreeDiffStream<'matcher>,
+    store: Arc<Store>,
+    ids: (MergedTreeId, MergedTreeId),
+    /// Entries yielded so far. `None` if an error occurred.
+    entries: Option<Vec<TreeDiffCacheEntry>>,
+}
+
+impl Stream for CachingTreeDiffStream<'_> {
+    type Item = TreeDiffEntry;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let poll = this.inner.poll_next_unpin(cx);
+        match (&poll, &mut this.entries) {
+            (Poll::Ready(Some(entry)), Some(entries)) => match &entry.values {
+                Ok((before, after)) => {
+                    entries.push((entry.path.clone(), before.clone(), after.clone()));
+                }
+                Err(_) => this.entries = None,
+            },
+            (Poll::Ready(None), Some(_)) => {
+                let entries = this.entries.take().unwrap();
+                let cache = this.store.tree_diff_cache().unwrap();
+                let (from_id, to_id) = &this.ids;
+                // The diff can be recomputed if the cache couldn't be written
+                cache.insert_diff(from_id, to_id, &entries).ok();
+            }
+            (Poll::Ready(_) | Poll::Pending, _) => {}
+        }
+        poll
+    }
+}
+
 fn all_tree_basenames(trees: &Merge<Tree>) -> impl Iterator<Item = &RepoPathComponent> {
     trees
         .iter()
@@ -641,21 +757,23 @@ impl From<&Merge<Tree>> for ConflictsDirItem {
 struct ConflictIterator {
     store: Arc<Store>,
     stack: Vec<ConflictsDirItem>,
+    /// Tree id and the conflicts found so far, which will be saved to the
+    /// cache. `None` if the cache is disabled or an error occurred.
+    cache_entries: Option<(MergedTreeId, Vec<TreeConflictsCacheEntry>)>,
 }
 
 impl ConflictIterator {
     fn new(tree: &MergedTree) -> Self {
+        let cache_entries = (tree.store().tree_diff_cache().is_some() && tree.has_conflict())
+            .then(|| (tree.id(), vec![]));
         ConflictIterator {
             store: tree.store().clone(),
             stack: vec![ConflictsDirItem::from(&tree.trees)],
+            cache_entries,
         }
     }
-}
 
-impl Iterator for ConflictIterator {
-    type Item = (RepoPathBuf, BackendResult<MergedTreeValue>);
-
-    fn next(&mut self) -> Option<Self::Item> {
+    fn next_conflict(&mut self) -> Option<(RepoPathBuf, BackendResult<MergedTreeValue>)> {
         while let Some(top) = self.stack.last_mut() {
             if let Some((path, tree_values)) = top.entries.pop() {
                 match tree_values.to_tree_merge(&self.store, &path) {
@@ -681,6 +799,30 @@ impl Iterator for ConflictIterator {
     }
 }
 
+impl Iterator for ConflictIterator {
+    type Item = (RepoPathBuf, BackendResult<MergedTreeValue>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.next_conflict();
+        match (&item, &mut self.cache_entries) {
+            (Some((path, Ok(value))), Some((_, entries))) => {
+                entries.push((path.clone(), value.clone()));
+            }
+            (Some((_, Err(_))), _) => self.cache_entries = None,
+            (None, _) => {
+                if let Some((tree_id, entries)) = self.cache_entries.take() {
+                    let cache = self.store.tree_diff_cache().unwrap();
+                    // The conflicts can be recomputed if the cache couldn't be
+                    // written
+                    cache.insert_conflicts(&tree_id, &entries).ok();
+                }
+            }
+            (Some(_), None) => {}
+        }
+        item
+    }
+}
+
 /// Iterator over the differences between two trees.
 pub struct TreeDiffIterator<'matcher> {
     store: Arc<Store>,
@@ -16,6 +16,7 @@
 
 use std::any::Any;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Debug;
@@ -28,10 +29,12 @@ use itertools::Itertools as _;
 use once_cell::sync::Lazy;
 use thiserror::Error;
 
+use crate::backend::ChangeId;
 use crate::backend::CommitId;
 use crate::backend::MillisSinceEpoch;
 use crate::backend::Timestamp;
 use crate::content_hash::ContentHash;
+use crate::content_hash::DigestUpdate;
 use crate::merge::Merge;
 use crate::object_id::id_type;
 use crate::object_id::HexPrefix;
@@ -264,11 +267,14 @@ pub struct BookmarkTarget<'a> {
 
 /// Represents the way the repo looks at a given time, just like how a Tree
 /// object represents how the file system looks at a given time.
-#[derive(ContentHash, PartialEq, Eq, Clone, Debug)]
+#[derive(PartialEq, Eq, Clone, Debug)]
 pub struct View {
     /// All head commits
     pub head_ids: HashSet<CommitId>,
     pub local_bookmarks: BTreeMap<String, RefTarget>,
+    /// Names of the local bookmarks which are archived. Archived bookmarks
+    /// are kept as is, but hidden from the UI by default.
+    pub archived_bookmarks: BTreeSet<String>,
     pub tags: BTreeMap<String, RefTarget>,
     pub remote_views: BTreeMap<String, RemoteView>,
     pub git_refs: BTreeMap<String, RefTarget>,
@@ -280,6 +286,43 @@ pub struct View {
     // (.jj/working_copy/) has the source of truth about which commit *is* checked out (to be
     // precise: the commit to which we most recently completed an update to).
     pub wc_commit_ids: HashMap<WorkspaceId, CommitId>,
+    /// User-defined key/value data attached to changes. Since it's keyed by
+    /// change id, it's carried over when the change is rewritten.
+    pub change_metadata: BTreeMap<ChangeId, BTreeMap<String, String>>,
+}
+
+impl ContentHash for View {
+    fn hash(&self, state: &mut impl DigestUpdate) {
+        let View {
+            head_ids,
+            local_bookmarks,
+            archived_bookmarks,
+            tags,
+            remote_views,
+            git_refs,
+            git_head,
+            wc_commit_ids,
+            change_metadata,
+        } = self;
+        head_ids.hash(state);
+        local_bookmarks.hash(state);
+        tags.hash(state);
+        remote_views.hash(state);
+        git_refs.hash(state);
+        git_head.hash(state);
+        wc_commit_ids.hash(state);
+        // Appended only if present to keep the hashes of the views without
+        // these fields unchanged. Each field is tagged so that different views
+        // can't hash the same.
+        if !archived_bookmarks.is_empty() {
+            b"archived_bookmarks".as_slice().hash(state);
+            archived_bookmarks.hash(state);
+        }
+        if !change_metadata.is_empty() {
+            b"change_metadata".as_slice().hash(state);
+            change_metadata.hash(state);
+        }
+    }
 }
 
 impl View {
@@ -291,11 +334,13 @@ impl View {
         View {
             head_ids: HashSet::new(),
             local_bookmarks: BTreeMap::new(),
+            archived_bookmarks: BTreeSet::new(),
             tags: BTreeMap::new(),
             remote_views: BTreeMap::new(),
             git_refs: BTreeMap::new(),
             git_head: RefTarget::absent(),
             wc_commit_ids: HashMap::new(),
+            change_metadata: BTreeMap::new(),
         }
     }
 
@@ -304,11 +349,13 @@ impl View {
         View {
             head_ids: HashSet::from([root_commit_id]),
             local_bookmarks: BTreeMap::new(),
+            archived_bookmarks: BTreeSet::new(),
             tags: BTreeMap::new(),
             remote_views: BTreeMap::new(),
             git_refs: BTreeMap::new(),
             git_head: RefTarget::absent(),
             wc_commit_ids: HashMap::new(),
+            change_metadata: BTreeMap::new(),
         }
     }
 }
@@ -0,0 +1,215 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Replication of the operation log between repositories.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use thiserror::Error;
+
+use crate::backend::BackendError;
+use crate::backend::CommitId;
+use crate::dag_walk;
+use crate::object_id::ObjectId;
+use crate::op_heads_store::OpHeadsStoreError;
+use crate::op_store::OpStoreError;
+use crate::op_store::Operation;
+use crate::op_store::OperationId;
+use crate::repo::RepoLoader;
+use crate::view::View;
+
+/// Error that may occur while transferring operations between repositories.
+#[derive(Debug, Error)]
+pub enum OpSyncError {
+    /// The storage backend doesn't support copying objects between
+    /// repositories.
+    #[error("Operation log synchronization is only supported with the Git backend")]
+    UnsupportedBackend,
+    /// A copied object ended up with a different id.
+    #[error("Copied {object_type} {expected} was stored as {actual}")]
+    IdMismatch {
+        /// Type of the copied object.
+        object_type: &'static str,
+        /// Id in the source repository.
+        expected: String,
+        /// Id in the destination repository.
+        actual: String,
+    },
+    /// Failed to read or write operations or views.
+    #[error(transparent)]
+    OpStore(#[from] OpStoreError),
+    /// Failed to update operation heads.
+    #[error(transparent)]
+    OpHeadsStore(#[from] OpHeadsStoreError),
+    /// Failed to copy commits.
+    #[error(transparent)]
+    Backend(#[from] BackendError),
+}
+
+/// Statistics of [`transfer_operations()`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OpSyncStats {
+    /// Number of operations copied to the destination.
+    pub num_operations: usize,
+    /// Number of commits copied to the destination.
+    pub num_commits: usize,
+}
+
+/// Copies the operations `head_ids` and their ancestors from `src` to `dest`,
+/// along with their views and all commits the views reference.
+///
+/// Operations already present in `dest` aren't copied again. Objects are
+/// written before anything that refers to them, so an interrupted transfer
+/// can be resumed by running it again. Each transferred head is recorded as a
+/// new operation head in `dest`, replacing the heads it descends from. If
+/// `dest` had diverged, the op heads are merged the next time it is loaded.
+///
+/// The repositories don't have to be related. Since every repository has the
+/// same root operation, the operation logs of unrelated repositories are
+/// merged like any other divergent operations, with the root operation as
+/// their common ancestor.
+pub fn transfer_operations(
+    src: &RepoLoader,
+    dest: &RepoLoader,
+    head_ids: &[OperationId],
+) -> Result<OpSyncStats, OpSyncError> {
+    let mut stats = OpSyncStats::default();
+    let mut missing_ops: HashMap<OperationId, Operation> = HashMap::new();
+    let mut visited: HashSet<OperationId> = HashSet::new();
+    let mut pending = head_ids.to_vec();
+    while let Some(id) = pending.pop() {
+        if !visited.insert(id.clone()) {
+            continue;
+        }
+        if operation_exists(dest, &id)? {
+            continue;
+        }
+        let op = src.op_store().read_operation(&id)?;
+        pending.extend(op.parents.iter().cloned());
+        missing_ops.insert(id, op);
+    }
+    if missing_ops.is_empty() {
+        return Ok(stats);
+    }
+
+    // Parents come first so that every stored operation is complete.
+    let missing_ids = dag_walk::topo_order_forward(
+        missing_ops.keys().cloned(),
+        |id| id.clone(),
+        |id| {
+            missing_ops[id]
+                .parents
+                .iter()
+                .filter(|id| missing_ops.contains_key(id))
+                .cloned()
+                .collect::<Vec<_>>()
+        },
+    );
+
+    let mut views = Vec::with_capacity(missing_ops.len());
+    let mut referenced_commit_ids: Vec<CommitId> = vec![];
+    for id in &missing_ids {
+        let view = src.op_store().read_view(&missing_ops[id].view_id)?;
+        referenced_commit_ids.extend(View::new(view.clone()).all_referenced_commit_ids().cloned());
+        views.push(view);
+    }
+    referenced_commit_ids.sort_unstable();
+    referenced_commit_ids.dedup();
+    stats.num_commits = copy_commits(src, dest, &referenced_commit_ids)?;
+
+    for (id, view) in missing_ids.iter().zip(&views) {
+        let op = &missing_ops[id];
+        let view_id = dest.op_store().write_view(view)?;
+        check_id("view", &op.view_id, &view_id)?;
+        let new_id = dest.op_store().write_operation(op)?;
+        check_id("operation", id, &new_id)?;
+        stats.num_operations += 1;
+    }
+
+    let old_heads = dest.op_heads_store().get_op_heads()?;
+    for head_id in head_ids {
+        if !missing_ops.contains_key(head_id) {
+            continue;
+        }
+        // Only the heads this head descends from are replaced. The others
+        // have to stay until the heads descending from them are recorded.
+        let ancestor_ids: HashSet<OperationId> = dag_walk::dfs(
+            [head_id.clone()],
+            |id| id.clone(),
+            |id| {
+                missing_ops
+                    .get(id)
+                    .map_or_else(Vec::new, |op| op.parents.clone())
+            },
+        )
+        .collect();
+        let replaced_ids = old_heads
+            .iter()
+            .filter(|id| ancestor_ids.contains(id))
+            .cloned()
+            .collect::<Vec<_>>();
+        dest.op_heads_store()
+            .update_op_heads(&replaced_ids, head_id)?;
+    }
+    Ok(stats)
+}
+
+fn operation_exists(repo_loader: &RepoLoader, id: &OperationId) -> Result<bool, OpSyncError> {
+    match repo_loader.op_store().read_operation(id) {
+        Ok(_) => Ok(true),
+        Err(OpStoreError::ObjectNotFound { .. }) => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn check_id<T: ObjectId + Eq>(
+    object_type: &'static str,
+    expected: &T,
+    actual: &T,
+) -> Result<(), OpSyncError> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(OpSyncError::IdMismatch {
+            object_type,
+            expected: expected.hex(),
+            actual: actual.hex(),
+        })
+    }
+}
+
+#[cfg(feature = "git")]
+fn copy_commits(
+    src: &RepoLoader,
+    dest: &RepoLoader,
+    head_ids: &[CommitId],
+) -> Result<usize, OpSyncError> {
+    use crate::git_backend::GitBackend;
+    let src_backend = src.store().backend_impl().downcast_ref::<GitBackend>();
+    let dest_backend = dest.store().backend_impl().downcast_ref::<GitBackend>();
+    let (Some(src_backend), Some(dest_backend)) = (src_backend, dest_backend) else {
+        return Err(OpSyncError::UnsupportedBackend);
+    };
+    Ok(dest_backend.copy_commits_from(src_backend, head_ids)?)
+}
+
+#[cfg(not(feature = "git"))]
+fn copy_commits(
+    _src: &RepoLoader,
+    _dest: &RepoLoader,
+    _head_ids: &[CommitId],
+) -> Result<usize, OpSyncError> {
+    Err(OpSyncError::UnsupportedBackend)
+}
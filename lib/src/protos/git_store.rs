@@ -13,6 +13,12 @@ pub struct Commit {
     /// TODO(#1624): delete when we assume that all commits use this format
     #[prost(bool, tag = "10")]
     pub uses_tree_conflict_format: bool,
+    /// Change id of the commit this one was duplicated from
+    #[prost(bytes = "vec", optional, tag = "11")]
+    pub duplicated_from: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    /// Commits whose trees the terms of the conflicted root tree came from
+    #[prost(bytes = "vec", repeated, tag = "12")]
+    pub conflict_sources: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
     #[deprecated]
     #[prost(bool, tag = "8")]
     pub is_open: bool,
@@ -68,6 +68,12 @@ pub struct Commit {
     pub committer: ::core::option::Option<commit::Signature>,
     #[prost(bytes = "vec", optional, tag = "9")]
     pub secure_sig: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    /// Change id of the commit this one was duplicated from
+    #[prost(bytes = "vec", optional, tag = "10")]
+    pub duplicated_from: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    /// Commits whose trees the terms of the conflicted root tree came from
+    #[prost(bytes = "vec", repeated, tag = "11")]
+    pub conflict_sources: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
 }
 /// Nested message and enum types in `Commit`.
 pub mod commit {
@@ -8,6 +8,15 @@ pub mod local_store {
 pub mod op_store {
     include!("op_store.rs");
 }
+pub mod search_index {
+    include!("search_index.rs");
+}
+pub mod tree_diff_cache {
+    include!("tree_diff_cache.rs");
+}
 pub mod working_copy {
     include!("working_copy.rs");
 }
+pub mod workspace_store {
+    include!("workspace_store.rs");
+}
@@ -0,0 +1,46 @@
+// This file is @generated by prost-build.
+/// Positions of the commits containing a term, in ascending order. Positions
+/// are local to the segment.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PostingList {
+    #[prost(uint32, repeated, tag = "1")]
+    pub positions: ::prost::alloc::vec::Vec<u32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Segment {
+    /// Name of the parent segment, empty if this is the first segment
+    #[prost(string, tag = "1")]
+    pub parent: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", repeated, tag = "2")]
+    pub commit_ids: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    /// Trigrams of the ASCII-lowercased descriptions
+    #[prost(map = "string, message", tag = "3")]
+    pub description_trigrams: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        PostingList,
+    >,
+    /// Trigrams of the ASCII-lowercased author names and emails
+    #[prost(map = "string, message", tag = "4")]
+    pub author_trigrams: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        PostingList,
+    >,
+    /// Paths modified by the commits, and their parent directories
+    #[prost(map = "string, message", tag = "5")]
+    pub paths: ::std::collections::HashMap<::prost::alloc::string::String, PostingList>,
+}
+/// The latest segment, and how much of the commit index it covers
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Head {
+    #[prost(string, tag = "1")]
+    pub segment: ::prost::alloc::string::String,
+    /// Name of the commit index segment whose commits have been indexed
+    #[prost(string, tag = "2")]
+    pub commit_index_segment: ::prost::alloc::string::String,
+    /// Number of commits in that commit index segment and its ancestors
+    #[prost(uint32, tag = "3")]
+    pub num_commits: u32,
+}
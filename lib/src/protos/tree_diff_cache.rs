@@ -0,0 +1,78 @@
+// This file is @generated by prost-build.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TreeValue {
+    #[prost(oneof = "tree_value::Value", tags = "1, 2, 3, 4, 5")]
+    pub value: ::core::option::Option<tree_value::Value>,
+}
+/// Nested message and enum types in `TreeValue`.
+pub mod tree_value {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct File {
+        #[prost(bytes = "vec", tag = "1")]
+        pub id: ::prost::alloc::vec::Vec<u8>,
+        #[prost(bool, tag = "2")]
+        pub executable: bool,
+    }
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Value {
+        #[prost(message, tag = "1")]
+        File(File),
+        #[prost(bytes, tag = "2")]
+        SymlinkId(::prost::alloc::vec::Vec<u8>),
+        #[prost(bytes, tag = "3")]
+        TreeId(::prost::alloc::vec::Vec<u8>),
+        #[prost(bytes, tag = "4")]
+        GitSubmoduleId(::prost::alloc::vec::Vec<u8>),
+        #[prost(bytes, tag = "5")]
+        ConflictId(::prost::alloc::vec::Vec<u8>),
+    }
+}
+/// Term of a merged value. The content is unset if the path is absent in the
+/// term.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Term {
+    #[prost(message, optional, tag = "1")]
+    pub content: ::core::option::Option<TreeValue>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TreeDiff {
+    #[prost(message, repeated, tag = "1")]
+    pub entries: ::prost::alloc::vec::Vec<tree_diff::Entry>,
+}
+/// Nested message and enum types in `TreeDiff`.
+pub mod tree_diff {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Entry {
+        #[prost(string, tag = "1")]
+        pub path: ::prost::alloc::string::String,
+        /// Alternating positive and negative terms
+        #[prost(message, repeated, tag = "2")]
+        pub before: ::prost::alloc::vec::Vec<super::Term>,
+        #[prost(message, repeated, tag = "3")]
+        pub after: ::prost::alloc::vec::Vec<super::Term>,
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TreeConflicts {
+    #[prost(message, repeated, tag = "1")]
+    pub entries: ::prost::alloc::vec::Vec<tree_conflicts::Entry>,
+}
+/// Nested message and enum types in `TreeConflicts`.
+pub mod tree_conflicts {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Entry {
+        #[prost(string, tag = "1")]
+        pub path: ::prost::alloc::string::String,
+        /// Alternating positive and negative terms
+        #[prost(message, repeated, tag = "2")]
+        pub value: ::prost::alloc::vec::Vec<super::Term>,
+    }
+}
@@ -32,6 +32,15 @@ pub struct FileStateEntry {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FileTypeOverride {
+    #[prost(string, tag = "1")]
+    pub path: ::prost::alloc::string::String,
+    /// One of Normal, Executable, or Symlink
+    #[prost(enumeration = "FileType", tag = "2")]
+    pub file_type: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SparsePatterns {
     #[prost(string, repeated, tag = "1")]
     pub prefixes: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
@@ -53,6 +62,12 @@ pub struct TreeState {
     pub sparse_patterns: ::core::option::Option<SparsePatterns>,
     #[prost(message, optional, tag = "4")]
     pub watchman_clock: ::core::option::Option<WatchmanClock>,
+    /// Paths which shouldn't be tracked even if they aren't ignored
+    #[prost(string, repeated, tag = "7")]
+    pub untracked_paths: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// File types to record instead of the types of the files on disk
+    #[prost(message, repeated, tag = "8")]
+    pub file_type_overrides: ::prost::alloc::vec::Vec<FileTypeOverride>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -92,6 +107,8 @@ pub enum FileType {
     Executable = 2,
     Conflict = 3,
     GitSubmodule = 4,
+    /// The path can't be represented on the disk, and wasn't checked out.
+    Skipped = 5,
 }
 impl FileType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -105,6 +122,7 @@ impl FileType {
             FileType::Executable => "Executable",
             FileType::Conflict => "Conflict",
             FileType::GitSubmodule => "GitSubmodule",
+            FileType::Skipped => "Skipped",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -115,6 +133,7 @@ impl FileType {
             "Executable" => Some(Self::Executable),
             "Conflict" => Some(Self::Conflict),
             "GitSubmodule" => Some(Self::GitSubmodule),
+            "Skipped" => Some(Self::Skipped),
             _ => None,
         }
     }
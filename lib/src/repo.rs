@@ -15,6 +15,7 @@
 #![allow(missing_docs)]
 
 use std::collections::hash_map::Entry;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Debug;
@@ -840,6 +841,17 @@ impl Rewrite {
     }
 }
 
+/// Progress of indexing new commits, reported by
+/// [`MutableRepo::add_heads_with_progress()`].
+#[derive(Clone, Copy, Debug)]
+pub struct IndexingProgress {
+    /// Number of new commits found so far.
+    pub found_commits: usize,
+    /// Number of new commits added to the index. Commits are indexed after
+    /// all of them are found.
+    pub indexed_commits: usize,
+}
+
 pub struct MutableRepo {
     base_repo: Arc<ReadonlyRepo>,
     index: Box<dyn MutableIndex>,
@@ -1471,6 +1483,19 @@ impl MutableRepo {
     /// and ancestors of the other heads. The `heads` and ancestor commits
     /// should exist in the store.
     pub fn add_heads(&mut self, heads: &[Commit]) -> BackendResult<()> {
+        self.add_heads_with_progress(heads, None)
+    }
+
+    /// Adds the given `heads` and their ancestors to the repo, reporting
+    /// progress of indexing the new commits.
+    ///
+    /// The ancestors not in the index are loaded from the backend first, and
+    /// then indexed at once.
+    pub fn add_heads_with_progress(
+        &mut self,
+        heads: &[Commit],
+        mut progress: Option<&mut dyn FnMut(&IndexingProgress)>,
+    ) -> BackendResult<()> {
         let current_heads = self.view.get_mut().heads();
         // Use incremental update for common case of adding a single commit on top a
         // current head. TODO: Also use incremental update when adding a single
@@ -1490,25 +1515,40 @@ impl MutableRepo {
                 }
             }
             _ => {
-                let missing_commits = dag_walk::topo_order_reverse_ord_ok(
-                    heads
+                let missing_commits = self.load_missing_ancestors(heads, &mut |p| {
+                    if let Some(progress) = progress.as_mut() {
+                        progress(p);
+                    }
+                })?;
+                let found_commits = missing_commits.len();
+                let sorted_commits = {
+                    let commits_by_id: HashMap<&CommitId, &Commit> = missing_commits
                         .iter()
-                        .cloned()
-                        .map(CommitByCommitterTimestamp)
-                        .map(Ok),
-                    |CommitByCommitterTimestamp(commit)| commit.id().clone(),
-                    |CommitByCommitterTimestamp(commit)| {
-                        commit
-                            .parent_ids()
-                            .iter()
-                            .filter(|id| !self.index().has_id(id))
-                            .map(|id| self.store().get_commit(id))
-                            .map_ok(CommitByCommitterTimestamp)
-                            .collect_vec()
-                    },
-                )?;
-                for CommitByCommitterTimestamp(missing_commit) in missing_commits.iter().rev() {
+                        .map(|commit| (commit.id(), commit))
+                        .collect();
+                    dag_walk::topo_order_reverse_ord(
+                        heads.iter().cloned().map(CommitByCommitterTimestamp),
+                        |CommitByCommitterTimestamp(commit)| commit.id().clone(),
+                        |CommitByCommitterTimestamp(commit)| {
+                            commit
+                                .parent_ids()
+                                .iter()
+                                .filter_map(|id| commits_by_id.get(id))
+                                .map(|&commit| CommitByCommitterTimestamp(commit.clone()))
+                                .collect_vec()
+                        },
+                    )
+                };
+                for (i, CommitByCommitterTimestamp(missing_commit)) in
+                    sorted_commits.iter().rev().enumerate()
+                {
                     self.index.add_commit(missing_commit);
+                    if let Some(progress) = progress.as_mut() {
+                        progress(&IndexingProgress {
+                            found_commits,
+                            indexed_commits: i + 1,
+                        });
+                    }
                 }
                 for head in heads {
                     self.view.get_mut().add_head(head.id());
@@ -1519,6 +1559,46 @@ impl MutableRepo {
         Ok(())
     }
 
+    /// Loads ancestors of the `heads` which aren't in the index yet. The
+    /// returned commits include the `heads` themselves, and aren't sorted.
+    ///
+    /// Commits are loaded one by one. Loading them in parallel wouldn't help
+    /// with the Git backend, which serializes the reads from the Git repo.
+    fn load_missing_ancestors(
+        &self,
+        heads: &[Commit],
+        progress: &mut dyn FnMut(&IndexingProgress),
+    ) -> BackendResult<Vec<Commit>> {
+        let index = self.index();
+        let store = self.store();
+        let mut visited: HashSet<CommitId> =
+            heads.iter().map(|commit| commit.id().clone()).collect();
+        let mut missing_commits = heads.to_vec();
+        let mut num_loaded = 0;
+        loop {
+            // Parents of the commits loaded in the last iteration
+            let pending_ids = missing_commits[num_loaded..]
+                .iter()
+                .flat_map(|commit| commit.parent_ids())
+                .filter(|&id| !index.has_id(id) && visited.insert(id.clone()))
+                .collect_vec();
+            if pending_ids.is_empty() {
+                break;
+            }
+            let new_commits: Vec<Commit> = pending_ids
+                .into_iter()
+                .map(|id| store.get_commit(id))
+                .collect::<BackendResult<_>>()?;
+            num_loaded = missing_commits.len();
+            missing_commits.extend(new_commits);
+            progress(&IndexingProgress {
+                found_commits: missing_commits.len(),
+                indexed_commits: 0,
+            });
+        }
+        Ok(missing_commits)
+    }
+
     pub fn remove_head(&mut self, head: &CommitId) {
         self.view_mut().remove_head(head);
         self.view.mark_dirty();
@@ -1536,6 +1616,14 @@ impl MutableRepo {
         view.set_local_bookmark_target(name, target);
     }
 
+    pub fn set_bookmark_archived(&mut self, name: &str, archived: bool) {
+        self.view_mut().set_bookmark_archived(name, archived);
+    }
+
+    pub fn set_change_metadata(&mut self, change_id: &ChangeId, key: &str, value: Option<String>) {
+        self.view_mut().set_change_metadata(change_id, key, value);
+    }
+
     pub fn merge_local_bookmark(
         &mut self,
         name: &str,
@@ -1721,6 +1809,35 @@ impl MutableRepo {
             self.merge_local_bookmark(name, base_target, other_target);
         }
 
+        // Apply the archive flags changed by the other side: bookmarks archived
+        // since the base are archived, and bookmarks unarchived since the base
+        // are unarchived.
+        for name in other.archived_bookmarks() {
+            if !base.is_bookmark_archived(name) {
+                self.set_bookmark_archived(name, true);
+            }
+        }
+        for name in base.archived_bookmarks() {
+            if !other.is_bookmark_archived(name) {
+                self.set_bookmark_archived(name, false);
+            }
+        }
+
+        // Metadata entries changed by the other side win.
+        let metadata_keys: BTreeSet<(&ChangeId, &str)> =
+            itertools::chain(base.all_change_metadata(), other.all_change_metadata())
+                .flat_map(|(change_id, entries)| {
+                    entries.keys().map(move |key| (change_id, key.as_str()))
+                })
+                .collect();
+        for (change_id, key) in metadata_keys {
+            let base_value = base.get_change_metadata(change_id, key);
+            let other_value = other.get_change_metadata(change_id, key);
+            if other_value != base_value {
+                self.set_change_metadata(change_id, key, other_value.map(ToOwned::to_owned));
+            }
+        }
+
         let changed_tags = diff_named_ref_targets(base.tags(), other.tags());
         for (name, (base_target, other_target)) in changed_tags {
             self.merge_tag(name, base_target, other_target);
@@ -34,6 +34,7 @@ use crate::commit::Commit;
 use crate::dsl_util;
 use crate::dsl_util::collect_similar;
 use crate::dsl_util::AliasExpandError as _;
+use crate::extensions_map::ExtensionsMap;
 use crate::fileset;
 use crate::fileset::FilesetDiagnostics;
 use crate::fileset::FilesetExpression;
@@ -41,6 +42,7 @@ use crate::graph::GraphNode;
 use crate::hex_util::to_forward_hex;
 use crate::id_prefix::IdPrefixContext;
 use crate::id_prefix::IdPrefixIndex;
+use crate::mailmap::Mailmap;
 use crate::object_id::HexPrefix;
 use crate::object_id::PrefixResolution;
 use crate::op_store::RemoteRefState;
@@ -69,6 +71,7 @@ use crate::store::Store;
 use crate::str_util::StringPattern;
 use crate::time_util::DatePattern;
 use crate::time_util::DatePatternContext;
+use crate::view::TOPIC_METADATA_KEY;
 
 /// Error occurred during symbol resolution.
 #[derive(Debug, Error)]
@@ -129,7 +132,7 @@ pub enum RevsetModifier {
 #[derive(Clone, Debug)]
 pub enum RevsetCommitRef {
     WorkingCopy(WorkspaceId),
-    WorkingCopies,
+    WorkingCopies(StringPattern),
     Symbol(String),
     RemoteSymbol(RemoteRefSymbolBuf),
     Bookmarks(StringPattern),
@@ -141,6 +144,12 @@ pub enum RevsetCommitRef {
     Tags(StringPattern),
     GitRefs,
     GitHead,
+    /// Commits of the changes which have the metadata `key`, optionally with
+    /// value matching the pattern.
+    ChangeMetadata {
+        key: String,
+        value_pattern: StringPattern,
+    },
 }
 
 /// A custom revset filter expression, defined by an extension.
@@ -163,6 +172,12 @@ pub enum RevsetFilterPredicate {
     AuthorName(StringPattern),
     /// Commits with author email matching the pattern.
     AuthorEmail(StringPattern),
+    /// Commits with canonical author name, as mapped by the mailmap, matching
+    /// the pattern.
+    MailmapAuthorName(StringPattern, Arc<Mailmap>),
+    /// Commits with canonical author email, as mapped by the mailmap, matching
+    /// the pattern.
+    MailmapAuthorEmail(StringPattern, Arc<Mailmap>),
     /// Commits with author dates matching the given date pattern.
     AuthorDate(DatePattern),
     /// Commits with committer name matching the pattern.
@@ -257,10 +272,21 @@ pub enum RevsetExpression<St: ExpressionState> {
     Heads(Rc<Self>),
     Roots(Rc<Self>),
     ForkPoint(Rc<Self>),
+    BisectMidpoint(Rc<Self>),
+    // Commits in "candidates" whose changes are already in "upstream"
+    AppliedUpstream {
+        candidates: Rc<Self>,
+        upstream: Rc<Self>,
+    },
     Latest {
         candidates: Rc<Self>,
         count: usize,
     },
+    /// Fails evaluation unless `candidates` contains exactly `count` commits.
+    Exactly {
+        candidates: Rc<Self>,
+        count: usize,
+    },
     Filter(RevsetFilterPredicate),
     /// Marker for subtree that should be intersected as filter.
     AsFilter(Rc<Self>),
@@ -327,7 +353,12 @@ impl<St: ExpressionState<CommitRef = RevsetCommitRef>> RevsetExpression<St> {
     }
 
     pub fn working_copies() -> Rc<Self> {
-        Rc::new(Self::CommitRef(RevsetCommitRef::WorkingCopies))
+        Self::working_copies_matching(StringPattern::everything())
+    }
+
+    /// Working-copy commits of the workspaces whose names match `pattern`.
+    pub fn working_copies_matching(pattern: StringPattern) -> Rc<Self> {
+        Rc::new(Self::CommitRef(RevsetCommitRef::WorkingCopies(pattern)))
     }
 
     pub fn symbol(value: String) -> Rc<Self> {
@@ -363,6 +394,13 @@ impl<St: ExpressionState<CommitRef = RevsetCommitRef>> RevsetExpression<St> {
         Rc::new(Self::CommitRef(RevsetCommitRef::GitRefs))
     }
 
+    pub fn change_metadata(key: String, value_pattern: StringPattern) -> Rc<Self> {
+        Rc::new(Self::CommitRef(RevsetCommitRef::ChangeMetadata {
+            key,
+            value_pattern,
+        }))
+    }
+
     pub fn git_head() -> Rc<Self> {
         Rc::new(Self::CommitRef(RevsetCommitRef::GitHead))
     }
@@ -377,6 +415,14 @@ impl<St: ExpressionState> RevsetExpression<St> {
         })
     }
 
+    /// Commits in `self`, which must contain exactly `count` commits.
+    pub fn exactly(self: &Rc<Self>, count: usize) -> Rc<Self> {
+        Rc::new(Self::Exactly {
+            candidates: self.clone(),
+            count,
+        })
+    }
+
     /// Commits in `self` that don't have descendants in `self`.
     pub fn heads(self: &Rc<Self>) -> Rc<Self> {
         Rc::new(Self::Heads(self.clone()))
@@ -440,6 +486,21 @@ impl<St: ExpressionState> RevsetExpression<St> {
         Rc::new(Self::ForkPoint(self.clone()))
     }
 
+    /// Commit in `self` which splits `self` most evenly into its ancestors and
+    /// the rest.
+    pub fn bisect_midpoint(self: &Rc<Self>) -> Rc<Self> {
+        Rc::new(Self::BisectMidpoint(self.clone()))
+    }
+
+    /// Commits in `self` which aren't ancestors of `upstream`, but whose
+    /// changes are already applied in `upstream`.
+    pub fn applied_upstream(self: &Rc<Self>, upstream: &Rc<Self>) -> Rc<Self> {
+        Rc::new(Self::AppliedUpstream {
+            candidates: self.clone(),
+            upstream: upstream.clone(),
+        })
+    }
+
     /// Filter all commits by `predicate` in `self`.
     pub fn filtered(self: &Rc<Self>, predicate: RevsetFilterPredicate) -> Rc<Self> {
         self.intersection(&Self::filter(predicate))
@@ -629,10 +690,21 @@ pub enum ResolvedExpression {
     Heads(Box<Self>),
     Roots(Box<Self>),
     ForkPoint(Box<Self>),
+    BisectMidpoint(Box<Self>),
+    /// Commits in `candidates` whose changes are already made by a commit in
+    /// `upstream`.
+    AppliedUpstream {
+        candidates: Box<Self>,
+        upstream: Box<Self>,
+    },
     Latest {
         candidates: Box<Self>,
         count: usize,
     },
+    Exactly {
+        candidates: Box<Self>,
+        count: usize,
+    },
     Coalesce(Box<Self>, Box<Self>),
     Union(Box<Self>, Box<Self>),
     /// Intersects `candidates` with `predicate` by filtering.
@@ -706,9 +778,14 @@ static BUILTIN_FUNCTION_MAP: Lazy<HashMap<&'static str, RevsetFunction>> = Lazy:
         function.expect_no_arguments()?;
         Ok(RevsetExpression::all())
     });
-    map.insert("working_copies", |_diagnostics, function, _context| {
-        function.expect_no_arguments()?;
-        Ok(RevsetExpression::working_copies())
+    map.insert("working_copies", |diagnostics, function, _context| {
+        let ([], [opt_arg]) = function.expect_arguments()?;
+        let pattern = if let Some(arg) = opt_arg {
+            expect_string_pattern(diagnostics, arg)?
+        } else {
+            StringPattern::everything()
+        };
+        Ok(RevsetExpression::working_copies_matching(pattern))
     });
     map.insert("heads", |diagnostics, function, context| {
         let [arg] = function.expect_exact_arguments()?;
@@ -761,6 +838,28 @@ static BUILTIN_FUNCTION_MAP: Lazy<HashMap<&'static str, RevsetFunction>> = Lazy:
         };
         Ok(RevsetExpression::tags(pattern))
     });
+    map.insert("metadata", |diagnostics, function, _context| {
+        let ([key_arg], [value_opt_arg]) = function.expect_arguments()?;
+        let key = expect_literal(diagnostics, "string", key_arg)?;
+        let value_pattern = if let Some(value_arg) = value_opt_arg {
+            expect_string_pattern(diagnostics, value_arg)?
+        } else {
+            StringPattern::everything()
+        };
+        Ok(RevsetExpression::change_metadata(key, value_pattern))
+    });
+    map.insert("topic", |diagnostics, function, _context| {
+        let ([], [opt_arg]) = function.expect_arguments()?;
+        let pattern = if let Some(arg) = opt_arg {
+            expect_string_pattern(diagnostics, arg)?
+        } else {
+            StringPattern::everything()
+        };
+        Ok(RevsetExpression::change_metadata(
+            TOPIC_METADATA_KEY.to_owned(),
+            pattern,
+        ))
+    });
     map.insert("git_refs", |_diagnostics, function, _context| {
         function.expect_no_arguments()?;
         Ok(RevsetExpression::git_refs())
@@ -779,10 +878,35 @@ static BUILTIN_FUNCTION_MAP: Lazy<HashMap<&'static str, RevsetFunction>> = Lazy:
         };
         Ok(candidates.latest(count))
     });
+    map.insert("exactly", |diagnostics, function, context| {
+        let ([candidates_arg], [count_opt_arg]) = function.expect_arguments()?;
+        let candidates = lower_expression(diagnostics, candidates_arg, context)?;
+        let count = if let Some(count_arg) = count_opt_arg {
+            expect_literal(diagnostics, "integer", count_arg)?
+        } else {
+            1
+        };
+        Ok(candidates.exactly(count))
+    });
     map.insert("fork_point", |diagnostics, function, context| {
+        let ([expression_arg], [other_arg]) = function.expect_arguments()?;
+        let mut expression = lower_expression(diagnostics, expression_arg, context)?;
+        if let Some(other_arg) = other_arg {
+            let other = lower_expression(diagnostics, other_arg, context)?;
+            expression = expression.union(&other);
+        }
+        Ok(RevsetExpression::fork_point(&expression))
+    });
+    map.insert("bisect_midpoint", |diagnostics, function, context| {
         let [expression_arg] = function.expect_exact_arguments()?;
         let expression = lower_expression(diagnostics, expression_arg, context)?;
-        Ok(RevsetExpression::fork_point(&expression))
+        Ok(RevsetExpression::bisect_midpoint(&expression))
+    });
+    map.insert("applied_upstream", |diagnostics, function, context| {
+        let [candidates_arg, upstream_arg] = function.expect_exact_arguments()?;
+        let candidates = lower_expression(diagnostics, candidates_arg, context)?;
+        let upstream = lower_expression(diagnostics, upstream_arg, context)?;
+        Ok(candidates.applied_upstream(&upstream))
     });
     map.insert("merges", |_diagnostics, function, _context| {
         function.expect_no_arguments()?;
@@ -803,24 +927,24 @@ static BUILTIN_FUNCTION_MAP: Lazy<HashMap<&'static str, RevsetFunction>> = Lazy:
         let predicate = RevsetFilterPredicate::Subject(pattern);
         Ok(RevsetExpression::filter(predicate))
     });
-    map.insert("author", |diagnostics, function, _context| {
+    map.insert("author", |diagnostics, function, context| {
         let [arg] = function.expect_exact_arguments()?;
         let pattern = expect_string_pattern(diagnostics, arg)?;
-        let name_predicate = RevsetFilterPredicate::AuthorName(pattern.clone());
-        let email_predicate = RevsetFilterPredicate::AuthorEmail(pattern);
+        let name_predicate = author_name_predicate(pattern.clone(), context);
+        let email_predicate = author_email_predicate(pattern, context);
         Ok(RevsetExpression::filter(name_predicate)
             .union(&RevsetExpression::filter(email_predicate)))
     });
-    map.insert("author_name", |diagnostics, function, _context| {
+    map.insert("author_name", |diagnostics, function, context| {
         let [arg] = function.expect_exact_arguments()?;
         let pattern = expect_string_pattern(diagnostics, arg)?;
-        let predicate = RevsetFilterPredicate::AuthorName(pattern);
+        let predicate = author_name_predicate(pattern, context);
         Ok(RevsetExpression::filter(predicate))
     });
-    map.insert("author_email", |diagnostics, function, _context| {
+    map.insert("author_email", |diagnostics, function, context| {
         let [arg] = function.expect_exact_arguments()?;
         let pattern = expect_string_pattern(diagnostics, arg)?;
-        let predicate = RevsetFilterPredicate::AuthorEmail(pattern);
+        let predicate = author_email_predicate(pattern, context);
         Ok(RevsetExpression::filter(predicate))
     });
     map.insert("author_date", |diagnostics, function, context| {
@@ -835,8 +959,13 @@ static BUILTIN_FUNCTION_MAP: Lazy<HashMap<&'static str, RevsetFunction>> = Lazy:
         // Email address domains are inherently case‐insensitive, and the local‐parts
         // are generally (although not universally) treated as case‐insensitive too, so
         // we use a case‐insensitive match here.
-        let predicate =
-            RevsetFilterPredicate::AuthorEmail(StringPattern::exact_i(context.user_email));
+        // If the mailmap maps the user's email, commits authored under any of
+        // the user's identities are matched.
+        let user_email = match &context.mailmap {
+            Some(mailmap) => mailmap.resolve("", context.user_email).1,
+            None => context.user_email,
+        };
+        let predicate = author_email_predicate(StringPattern::exact_i(user_email), context);
         Ok(RevsetExpression::filter(predicate))
     });
     map.insert("committer", |diagnostics, function, _context| {
@@ -956,6 +1085,26 @@ pub fn expect_fileset_expression(
     })
 }
 
+fn author_name_predicate(
+    pattern: StringPattern,
+    context: &RevsetParseContext,
+) -> RevsetFilterPredicate {
+    match &context.mailmap {
+        Some(mailmap) => RevsetFilterPredicate::MailmapAuthorName(pattern, mailmap.clone()),
+        None => RevsetFilterPredicate::AuthorName(pattern),
+    }
+}
+
+fn author_email_predicate(
+    pattern: StringPattern,
+    context: &RevsetParseContext,
+) -> RevsetFilterPredicate {
+    match &context.mailmap {
+        Some(mailmap) => RevsetFilterPredicate::MailmapAuthorEmail(pattern, mailmap.clone()),
+        None => RevsetFilterPredicate::AuthorEmail(pattern),
+    }
+}
+
 pub fn expect_string_pattern(
     diagnostics: &mut RevsetDiagnostics,
     node: &ExpressionNode,
@@ -1235,11 +1384,29 @@ fn try_transform_expression<St: ExpressionState, E>(
             RevsetExpression::ForkPoint(expression) => {
                 transform_rec(expression, pre, post)?.map(RevsetExpression::ForkPoint)
             }
+            RevsetExpression::BisectMidpoint(expression) => {
+                transform_rec(expression, pre, post)?.map(RevsetExpression::BisectMidpoint)
+            }
+            RevsetExpression::AppliedUpstream {
+                candidates,
+                upstream,
+            } => transform_rec_pair((candidates, upstream), pre, post)?.map(
+                |(candidates, upstream)| RevsetExpression::AppliedUpstream {
+                    candidates,
+                    upstream,
+                },
+            ),
             RevsetExpression::Latest { candidates, count } => transform_rec(candidates, pre, post)?
                 .map(|candidates| RevsetExpression::Latest {
                     candidates,
                     count: *count,
                 }),
+            RevsetExpression::Exactly { candidates, count } => {
+                transform_rec(candidates, pre, post)?.map(|candidates| RevsetExpression::Exactly {
+                    candidates,
+                    count: *count,
+                })
+            }
             RevsetExpression::Filter(_) => None,
             RevsetExpression::AsFilter(candidates) => {
                 transform_rec(candidates, pre, post)?.map(RevsetExpression::AsFilter)
@@ -1429,11 +1596,32 @@ where
             let expression = folder.fold_expression(expression)?;
             RevsetExpression::ForkPoint(expression).into()
         }
+        RevsetExpression::BisectMidpoint(expression) => {
+            let expression = folder.fold_expression(expression)?;
+            RevsetExpression::BisectMidpoint(expression).into()
+        }
+        RevsetExpression::AppliedUpstream {
+            candidates,
+            upstream,
+        } => {
+            let candidates = folder.fold_expression(candidates)?;
+            let upstream = folder.fold_expression(upstream)?;
+            RevsetExpression::AppliedUpstream {
+                candidates,
+                upstream,
+            }
+            .into()
+        }
         RevsetExpression::Latest { candidates, count } => {
             let candidates = folder.fold_expression(candidates)?;
             let count = *count;
             RevsetExpression::Latest { candidates, count }.into()
         }
+        RevsetExpression::Exactly { candidates, count } => {
+            let candidates = folder.fold_expression(candidates)?;
+            let count = *count;
+            RevsetExpression::Exactly { candidates, count }.into()
+        }
         RevsetExpression::Filter(predicate) => RevsetExpression::Filter(predicate.clone()).into(),
         RevsetExpression::AsFilter(candidates) => {
             let candidates = folder.fold_expression(candidates)?;
@@ -1569,6 +1757,97 @@ fn internalize_filter<St: ExpressionState>(
     })
 }
 
+/// Estimates cost of testing the filter predicate against a commit.
+fn filter_predicate_cost(predicate: &RevsetFilterPredicate) -> u32 {
+    match predicate {
+        // Looks up the index only
+        RevsetFilterPredicate::ParentCount(_) => 0,
+        // Reads the commit object
+        RevsetFilterPredicate::Description(_)
+        | RevsetFilterPredicate::Subject(_)
+        | RevsetFilterPredicate::AuthorName(_)
+        | RevsetFilterPredicate::AuthorEmail(_)
+        | RevsetFilterPredicate::MailmapAuthorName(..)
+        | RevsetFilterPredicate::MailmapAuthorEmail(..)
+        | RevsetFilterPredicate::AuthorDate(_)
+        | RevsetFilterPredicate::CommitterName(_)
+        | RevsetFilterPredicate::CommitterEmail(_)
+        | RevsetFilterPredicate::CommitterDate(_)
+        | RevsetFilterPredicate::HasConflict => 1,
+        // Diffs the trees
+        RevsetFilterPredicate::File(_) | RevsetFilterPredicate::Extension(_) => 2,
+        // Diffs the trees and the file contents
+        RevsetFilterPredicate::DiffContains { .. } => 3,
+    }
+}
+
+/// Estimates cost of testing the filter expression against a commit, which
+/// is the cost of the most expensive predicate in it. Testing membership of
+/// set is considered free.
+fn filter_cost<St: ExpressionState>(expression: &RevsetExpression<St>) -> u32 {
+    match expression {
+        RevsetExpression::Filter(predicate) => filter_predicate_cost(predicate),
+        RevsetExpression::AsFilter(expression)
+        | RevsetExpression::Present(expression)
+        | RevsetExpression::NotIn(expression) => filter_cost(expression),
+        RevsetExpression::Union(expression1, expression2)
+        | RevsetExpression::Intersection(expression1, expression2)
+        | RevsetExpression::Difference(expression1, expression2) => {
+            filter_cost(expression1).max(filter_cost(expression2))
+        }
+        _ => 0,
+    }
+}
+
+/// Reorders intersection of filters so cheaper filters are tested first.
+///
+/// Filter intersection `(c & f1) & f2` is evaluated by testing `f1` against
+/// `c`, and then testing `f2` against the commits matched `f1`. If `f2` is
+/// cheaper, it's rewritten to `(c & f2) & f1`. Filters of the same cost are
+/// tested in the original order.
+fn sort_filters_by_cost<St: ExpressionState>(
+    expression: &Rc<RevsetExpression<St>>,
+) -> TransformedExpression<St> {
+    fn is_filter<St: ExpressionState>(expression: &RevsetExpression<St>) -> bool {
+        matches!(
+            expression,
+            RevsetExpression::Filter(_) | RevsetExpression::AsFilter(_)
+        )
+    }
+
+    // Since the left-hand side must have already been sorted, the new filter
+    // `f2` is sunk down to the position where the filter cost is not greater
+    // than `f2`.
+    fn sink_filter<St: ExpressionState>(
+        expression1: &Rc<RevsetExpression<St>>,
+        filter2: &Rc<RevsetExpression<St>>,
+    ) -> TransformedExpression<St> {
+        let cost2 = filter_cost(filter2);
+        match expression1.as_ref() {
+            // (c & f1) & f2 -> (c & f2) & f1
+            RevsetExpression::Intersection(candidates, filter1)
+                if is_filter(filter1) && cost2 < filter_cost(filter1) =>
+            {
+                let candidates = sink_filter(candidates, filter2)
+                    .unwrap_or_else(|| candidates.intersection(filter2));
+                Some(candidates.intersection(filter1))
+            }
+            // f1 & f2 -> f2 & f1
+            e1 if is_filter(e1) && cost2 < filter_cost(e1) => {
+                Some(filter2.intersection(expression1))
+            }
+            _ => None,
+        }
+    }
+
+    transform_expression_bottom_up(expression, |expression| match expression.as_ref() {
+        RevsetExpression::Intersection(expression1, expression2) if is_filter(expression2) => {
+            sink_filter(expression1, expression2)
+        }
+        _ => None,
+    })
+}
+
 /// Eliminates redundant nodes like `x & all()`, `~~x`.
 ///
 /// This does not rewrite 'x & none()' to 'none()' because 'x' may be an invalid
@@ -1772,6 +2051,7 @@ pub fn optimize<St: ExpressionState>(
     let expression = fold_redundant_expression(&expression).unwrap_or(expression);
     let expression = fold_generation(&expression).unwrap_or(expression);
     let expression = internalize_filter(&expression).unwrap_or(expression);
+    let expression = sort_filters_by_cost(&expression).unwrap_or(expression);
     let expression = fold_difference(&expression).unwrap_or(expression);
     fold_not_in_ancestors(&expression).unwrap_or(expression)
 }
@@ -2100,8 +2380,14 @@ fn resolve_commit_ref(
                 })
             }
         }
-        RevsetCommitRef::WorkingCopies => {
-            let wc_commits = repo.view().wc_commit_ids().values().cloned().collect_vec();
+        RevsetCommitRef::WorkingCopies(pattern) => {
+            let wc_commits = repo
+                .view()
+                .wc_commit_ids()
+                .iter()
+                .filter(|(workspace_id, _)| pattern.matches(workspace_id.as_str()))
+                .map(|(_, commit_id)| commit_id.clone())
+                .collect_vec();
             Ok(wc_commits)
         }
         RevsetCommitRef::Bookmarks(pattern) => {
@@ -2148,6 +2434,20 @@ fn resolve_commit_ref(
             Ok(commit_ids)
         }
         RevsetCommitRef::GitHead => Ok(repo.view().git_head().added_ids().cloned().collect()),
+        RevsetCommitRef::ChangeMetadata { key, value_pattern } => {
+            let commit_ids = repo
+                .view()
+                .all_change_metadata()
+                .filter(|(_, entries)| {
+                    entries
+                        .get(key)
+                        .is_some_and(|value| value_pattern.matches(value))
+                })
+                .filter_map(|(change_id, _)| repo.resolve_change_id(change_id))
+                .flatten()
+                .collect();
+            Ok(commit_ids)
+        }
     }
 }
 
@@ -2310,10 +2610,28 @@ impl VisibilityResolutionContext<'_> {
             RevsetExpression::ForkPoint(expression) => {
                 ResolvedExpression::ForkPoint(self.resolve(expression).into())
             }
+            RevsetExpression::BisectMidpoint(expression) => {
+                ResolvedExpression::BisectMidpoint(self.resolve(expression).into())
+            }
+            RevsetExpression::AppliedUpstream {
+                candidates,
+                upstream,
+            } => ResolvedExpression::AppliedUpstream {
+                // Ancestors of the upstream are trivially applied, and the
+                // upstream commits are the ones not in the candidates' history.
+                candidates: self
+                    .resolve(&candidates.minus(&upstream.ancestors()))
+                    .into(),
+                upstream: self.resolve(&candidates.range(upstream)).into(),
+            },
             RevsetExpression::Latest { candidates, count } => ResolvedExpression::Latest {
                 candidates: self.resolve(candidates).into(),
                 count: *count,
             },
+            RevsetExpression::Exactly { candidates, count } => ResolvedExpression::Exactly {
+                candidates: self.resolve(candidates).into(),
+                count: *count,
+            },
             RevsetExpression::Filter(_) | RevsetExpression::AsFilter(_) => {
                 // Top-level filter without intersection: e.g. "~author(_)" is represented as
                 // `AsFilter(NotIn(Filter(Author(_))))`.
@@ -2415,7 +2733,10 @@ impl VisibilityResolutionContext<'_> {
             | RevsetExpression::Heads(_)
             | RevsetExpression::Roots(_)
             | RevsetExpression::ForkPoint(_)
-            | RevsetExpression::Latest { .. } => {
+            | RevsetExpression::BisectMidpoint(_)
+            | RevsetExpression::AppliedUpstream { .. }
+            | RevsetExpression::Latest { .. }
+            | RevsetExpression::Exactly { .. } => {
                 ResolvedPredicateExpression::Set(self.resolve(expression).into())
             }
             RevsetExpression::Filter(predicate) => {
@@ -2468,6 +2789,15 @@ pub trait Revset: fmt::Debug {
     where
         Self: 'a;
 
+    /// Like `iter_graph()`, but only emits the heads of the revset and the
+    /// commits in it reached from them by following first parents. Each commit
+    /// has a single edge to the commit reached by following its first parent.
+    fn iter_first_parent_graph<'a>(
+        &self,
+    ) -> Box<dyn Iterator<Item = Result<GraphNode<CommitId>, RevsetEvaluationError>> + 'a>
+    where
+        Self: 'a;
+
     /// Returns true if iterator will emit no commit nor error.
     fn is_empty(&self) -> bool;
 
@@ -2569,6 +2899,8 @@ pub struct RevsetParseContext<'a> {
     date_pattern_context: DatePatternContext,
     extensions: &'a RevsetExtensions,
     workspace: Option<RevsetWorkspaceContext<'a>>,
+    mailmap: Option<Arc<Mailmap>>,
+    extension_data: Option<&'a ExtensionsMap>,
 }
 
 impl<'a> RevsetParseContext<'a> {
@@ -2585,9 +2917,24 @@ impl<'a> RevsetParseContext<'a> {
             date_pattern_context,
             extensions,
             workspace,
+            mailmap: None,
+            extension_data: None,
         }
     }
 
+    /// Maps author identities through the `mailmap` when matching commits by
+    /// author.
+    pub fn with_mailmap(mut self, mailmap: Arc<Mailmap>) -> Self {
+        self.mailmap = Some(mailmap);
+        self
+    }
+
+    /// Makes the `data` available to the functions provided by extensions.
+    pub fn with_extension_data(mut self, data: &'a ExtensionsMap) -> Self {
+        self.extension_data = Some(data);
+        self
+    }
+
     pub fn aliases_map(&self) -> &'a RevsetAliasesMap {
         self.aliases_map
     }
@@ -2596,10 +2943,19 @@ impl<'a> RevsetParseContext<'a> {
         self.user_email
     }
 
+    pub fn mailmap(&self) -> Option<&Arc<Mailmap>> {
+        self.mailmap.as_ref()
+    }
+
     pub fn date_pattern_context(&self) -> &DatePatternContext {
         &self.date_pattern_context
     }
 
+    /// Data provided for the functions registered by extensions.
+    pub fn extension_data(&self) -> Option<&'a ExtensionsMap> {
+        self.extension_data
+    }
+
     pub fn symbol_resolvers(&self) -> &'a [impl AsRef<dyn SymbolResolverExtension> + use<>] {
         self.extensions.symbol_resolvers()
     }
@@ -3859,6 +4215,42 @@ mod tests {
             Filter(File(Pattern(PrefixPath("bar")))),
         )
         "#);
+        insta::assert_debug_snapshot!(
+            optimize(parse_with_workspace(
+                "files(foo) & diff_contains(bar) & description(baz) & qux",
+                &WorkspaceId::default()).unwrap(),
+            ), @r#"
+        Intersection(
+            Intersection(
+                Intersection(
+                    CommitRef(Symbol("qux")),
+                    Filter(Description(Substring("baz"))),
+                ),
+                Filter(File(Pattern(PrefixPath("foo")))),
+            ),
+            Filter(
+                DiffContains {
+                    text: Substring("bar"),
+                    files: All,
+                },
+            ),
+        )
+        "#);
+        insta::assert_debug_snapshot!(
+            optimize(parse_with_workspace(
+                "files(foo) & (description(bar) | baz)",
+                &WorkspaceId::default()).unwrap(),
+            ), @r#"
+        Intersection(
+            AsFilter(
+                Union(
+                    Filter(Description(Substring("bar"))),
+                    CommitRef(Symbol("baz")),
+                ),
+            ),
+            Filter(File(Pattern(PrefixPath("foo")))),
+        )
+        "#);
         insta::assert_debug_snapshot!(
             optimize(parse_with_workspace(
                 "committer_name(foo) & files(bar) & author_name(baz)",
@@ -3867,9 +4259,9 @@ mod tests {
         Intersection(
             Intersection(
                 Filter(CommitterName(Substring("foo"))),
-                Filter(File(Pattern(PrefixPath("bar")))),
+                Filter(AuthorName(Substring("baz"))),
             ),
-            Filter(AuthorName(Substring("baz"))),
+            Filter(File(Pattern(PrefixPath("bar")))),
         )
         "#);
         insta::assert_debug_snapshot!(
@@ -16,8 +16,12 @@
 
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::io::Read as _;
+use std::slice;
 use std::sync::Arc;
 
+use blake2::Blake2b512;
+use digest::Digest;
 use futures::StreamExt;
 use indexmap::IndexMap;
 use indexmap::IndexSet;
@@ -27,16 +31,25 @@ use tracing::instrument;
 
 use crate::backend::BackendError;
 use crate::backend::BackendResult;
+use crate::backend::ChangeId;
 use crate::backend::CommitId;
 use crate::backend::MergedTreeId;
 use crate::commit::Commit;
 use crate::commit::CommitIteratorExt;
 use crate::commit_builder::CommitBuilder;
+use crate::conflicts::materialized_diff_stream;
+use crate::conflicts::MaterializedTreeValue;
+use crate::copies::CopyRecords;
 use crate::dag_walk;
+use crate::diff::Diff;
+use crate::diff::DiffHunkKind;
 use crate::index::Index;
 use crate::index::IndexError;
+use crate::matchers::EverythingMatcher;
 use crate::matchers::Matcher;
 use crate::matchers::Visit;
+use crate::merge::Merge;
+use crate::merge_strategy::MergeStrategies;
 use crate::merged_tree::MergedTree;
 use crate::merged_tree::MergedTreeBuilder;
 use crate::merged_tree::TreeDiffEntry;
@@ -57,6 +70,22 @@ pub fn merge_commit_trees(repo: &dyn Repo, commits: &[Commit]) -> BackendResult<
     }
 }
 
+/// Merges `commits` like [`merge_commit_trees()`], and resolves the remaining
+/// file conflicts with the `merge_strategies`.
+pub fn merge_commit_trees_with_strategies(
+    repo: &dyn Repo,
+    commits: &[Commit],
+    merge_strategies: &MergeStrategies,
+) -> BackendResult<MergedTree> {
+    let tree = merge_commit_trees(repo, commits)?;
+    let tree_id = merge_strategies.resolve_tree_conflicts(&tree)?;
+    if tree_id == tree.id() {
+        Ok(tree)
+    } else {
+        repo.store().get_root_tree(&tree_id)
+    }
+}
+
 /// Merges `commits` without attempting to resolve file conflicts.
 #[instrument(skip(index))]
 pub fn merge_commit_trees_no_resolve_without_repo(
@@ -87,6 +116,34 @@ pub fn merge_commit_trees_no_resolve_without_repo(
     }
 }
 
+/// Returns the ids of the commits the terms of `tree` came from, where `tree`
+/// is the result of merging `commits`. Returns `None` if unknown.
+fn tree_term_sources(commits: &[Commit], tree: &MergedTree) -> Option<Merge<CommitId>> {
+    match commits {
+        [commit] if tree.has_conflict() => commit.conflict_sources(),
+        [commit] => Some(Merge::resolved(commit.id().clone())),
+        _ => None,
+    }
+}
+
+/// Merges `tree` with `other` using `base` as base like
+/// [`MergedTree::merge()`], and keeps track of the commits the terms of the
+/// merged tree came from if they're known for all inputs.
+fn merge_trees_with_sources(
+    (tree, sources): (&MergedTree, Option<Merge<CommitId>>),
+    (base, base_sources): (&MergedTree, Option<Merge<CommitId>>),
+    (other, other_sources): (&MergedTree, Option<Merge<CommitId>>),
+) -> BackendResult<(MergedTree, Option<Merge<CommitId>>)> {
+    match (sources, base_sources, other_sources) {
+        (Some(sources), Some(base_sources), Some(other_sources)) => tree.merge_with_terms(
+            base,
+            other,
+            Merge::from_vec(vec![sources, base_sources, other_sources]),
+        ),
+        _ => Ok((tree.merge(base, other)?, None)),
+    }
+}
+
 /// Restore matching paths from the source into the destination.
 pub fn restore_tree(
     source: &MergedTree,
@@ -224,6 +281,14 @@ impl<'repo> CommitRewriter<'repo> {
     pub fn rebase_with_empty_behavior(
         self,
         empty: EmptyBehaviour,
+    ) -> BackendResult<Option<CommitBuilder<'repo>>> {
+        self.rebase_with_merge_strategies(empty, &MergeStrategies::default())
+    }
+
+    fn rebase_with_merge_strategies(
+        self,
+        empty: EmptyBehaviour,
+        merge_strategies: &MergeStrategies,
     ) -> BackendResult<Option<CommitBuilder<'repo>>> {
         let old_parents: Vec<_> = self.old_commit.parents().try_collect()?;
         let old_parent_trees = old_parents
@@ -240,21 +305,37 @@ impl<'repo> CommitRewriter<'repo> {
             .map(|parent| parent.tree_id().clone())
             .collect_vec();
 
-        let (was_empty, new_tree_id) = if new_parent_trees == old_parent_trees {
+        let (was_empty, new_tree_id, conflict_sources) = if new_parent_trees == old_parent_trees {
             (
                 // Optimization: was_empty is only used for newly empty, but when the
                 // parents haven't changed it can't be newly empty.
                 true,
                 // Optimization: Skip merging.
                 self.old_commit.tree_id().clone(),
+                self.old_commit.conflict_sources(),
             )
         } else {
             let old_base_tree = merge_commit_trees(self.mut_repo, &old_parents)?;
             let new_base_tree = merge_commit_trees(self.mut_repo, &new_parents)?;
             let old_tree = self.old_commit.tree()?;
+            let (new_tree, conflict_sources) = merge_trees_with_sources(
+                (
+                    &new_base_tree,
+                    tree_term_sources(&new_parents, &new_base_tree),
+                ),
+                (
+                    &old_base_tree,
+                    tree_term_sources(&old_parents, &old_base_tree),
+                ),
+                (
+                    &old_tree,
+                    tree_term_sources(slice::from_ref(&self.old_commit), &old_tree),
+                ),
+            )?;
             (
                 old_base_tree.id() == *self.old_commit.tree_id(),
-                new_base_tree.merge(&old_base_tree, &old_tree)?.id(),
+                merge_strategies.resolve_tree_conflicts(&new_tree)?,
+                conflict_sources,
             )
         };
         // Ensure we don't abandon commits with multiple parents (merge commits), even
@@ -275,7 +356,8 @@ impl<'repo> CommitRewriter<'repo> {
             .mut_repo
             .rewrite_commit(&self.old_commit)
             .set_parents(self.new_parents)
-            .set_tree_id(new_tree_id);
+            .set_tree_id(new_tree_id)
+            .set_conflict_sources(conflict_sources);
         Ok(Some(builder))
     }
 
@@ -318,7 +400,9 @@ pub fn rebase_commit_with_options(
         _ => None,
     };
     let new_parents_len = rewriter.new_parents.len();
-    if let Some(builder) = rewriter.rebase_with_empty_behavior(options.empty)? {
+    if let Some(builder) =
+        rewriter.rebase_with_merge_strategies(options.empty, &options.merge_strategies)?
+    {
         let new_commit = builder.write()?;
         Ok(RebasedCommit::Rewritten(new_commit))
     } else {
@@ -378,6 +462,12 @@ pub struct RebaseOptions {
     /// If a merge commit would end up with one parent being an ancestor of the
     /// other, then filter out the ancestor.
     pub simplify_ancestor_merge: bool,
+    /// Abandon target commits whose changes are already present in the new
+    /// parents, either because a commit there was duplicated from the target
+    /// commit (or vice versa), or because a commit there has the same patch.
+    pub skip_applied: bool,
+    /// Strategies to resolve the file conflicts left by rebasing.
+    pub merge_strategies: MergeStrategies,
 }
 
 /// Configuration for [`MutableRepo::update_rewritten_references()`].
@@ -401,6 +491,9 @@ pub struct MoveCommitsStats {
     pub num_skipped_rebases: u32,
     /// The number of commits which were abandoned.
     pub num_abandoned: u32,
+    /// The number of target commits which were abandoned because their changes
+    /// were already present in the new parents.
+    pub num_skipped_applied: u32,
 }
 
 pub enum MoveCommitsTarget {
@@ -741,12 +834,34 @@ pub fn move_commits(
     let mut num_rebased_descendants = 0;
     let mut num_skipped_rebases = 0;
     let mut num_abandoned = 0;
+    let mut num_skipped_applied = 0;
 
     // Always keep empty commits when rebasing descendants.
     let rebase_descendant_options = &RebaseOptions {
         empty: EmptyBehaviour::Keep,
         rewrite_refs: options.rewrite_refs.clone(),
         simplify_ancestor_merge: options.simplify_ancestor_merge,
+        skip_applied: false,
+        merge_strategies: options.merge_strategies.clone(),
+    };
+
+    let mut applied_changes = if options.skip_applied {
+        // Commits which are ancestors of the new parents but not of the old
+        // parents of the target roots.
+        let upstream_commits: Vec<Commit> =
+            RevsetExpression::commits(target_roots.iter().cloned().collect_vec())
+                .parents()
+                .range(&RevsetExpression::commits(new_parent_ids.clone()))
+                .evaluate(mut_repo)
+                .map_err(|err| err.expect_backend_error())?
+                .iter()
+                .commits(mut_repo.store())
+                .try_collect()
+                // TODO: Return evaluation error to caller
+                .map_err(|err| err.expect_backend_error())?;
+        Some(AppliedChanges::new(upstream_commits))
+    } else {
+        None
     };
 
     // Rebase each commit onto its new parents in the reverse topological order
@@ -758,16 +873,41 @@ pub fn move_commits(
         let rewriter = CommitRewriter::new(mut_repo, old_commit.clone(), new_parent_ids);
         if rewriter.parents_changed() {
             let is_target_commit = target_commit_ids.contains(&old_commit_id);
+            let (is_applied, is_duplicate) = match &mut applied_changes {
+                Some(applied_changes) if is_target_commit => (
+                    applied_changes.contains(old_commit)?,
+                    applied_changes.is_duplicate(old_commit),
+                ),
+                _ => (false, false),
+            };
+            if is_applied {
+                rewriter.abandon();
+                num_skipped_applied += 1;
+                continue;
+            }
+            // A duplicate may have been modified after it was duplicated, so
+            // it's only skipped if rebasing it leaves nothing to apply.
+            let duplicate_options;
             let rebased_commit = rebase_commit_with_options(
                 rewriter,
-                if is_target_commit {
+                if is_duplicate && options.empty == EmptyBehaviour::Keep {
+                    duplicate_options = RebaseOptions {
+                        empty: EmptyBehaviour::AbandonNewlyEmpty,
+                        ..options.clone()
+                    };
+                    &duplicate_options
+                } else if is_target_commit {
                     options
                 } else {
                     rebase_descendant_options
                 },
             )?;
             if let RebasedCommit::Abandoned { .. } = rebased_commit {
-                num_abandoned += 1;
+                if is_duplicate {
+                    num_skipped_applied += 1;
+                } else {
+                    num_abandoned += 1;
+                }
             } else if is_target_commit {
                 num_rebased_targets += 1;
             } else {
@@ -784,9 +924,142 @@ pub fn move_commits(
         num_rebased_descendants,
         num_skipped_rebases,
         num_abandoned,
+        num_skipped_applied,
     })
 }
 
+/// Changes made by a set of upstream commits, used to find commits which are
+/// already applied upstream.
+pub struct AppliedChanges {
+    upstream_commits: Vec<Commit>,
+    change_ids: HashSet<ChangeId>,
+    duplicated_from: HashSet<ChangeId>,
+    // Computed on first use since it requires diffing all upstream commits.
+    patch_ids: Option<HashSet<Vec<u8>>>,
+}
+
+impl AppliedChanges {
+    pub fn new(upstream_commits: Vec<Commit>) -> Self {
+        let change_ids = upstream_commits
+            .iter()
+            .map(|commit| commit.change_id().clone())
+            .collect();
+        let duplicated_from = upstream_commits
+            .iter()
+            .filter_map(|commit| commit.duplicated_from().cloned())
+            .collect();
+        AppliedChanges {
+            upstream_commits,
+            change_ids,
+            duplicated_from,
+            patch_ids: None,
+        }
+    }
+
+    /// Returns true if an upstream commit was duplicated from `commit`, or vice
+    /// versa. Either commit may have been modified since, so the changes
+    /// aren't necessarily the same.
+    pub fn is_duplicate(&self, commit: &Commit) -> bool {
+        self.duplicated_from.contains(commit.change_id())
+            || commit
+                .duplicated_from()
+                .is_some_and(|change_id| self.change_ids.contains(change_id))
+    }
+
+    /// Returns true if the changes in `commit` are already present upstream
+    /// because an upstream commit has the same patch id.
+    pub fn contains(&mut self, commit: &Commit) -> BackendResult<bool> {
+        let Some(patch_id) = compute_patch_id(commit)? else {
+            return Ok(false);
+        };
+        if self.patch_ids.is_none() {
+            let patch_ids = self
+                .upstream_commits
+                .iter()
+                .filter_map(|commit| compute_patch_id(commit).transpose())
+                .try_collect()?;
+            self.patch_ids = Some(patch_ids);
+        }
+        Ok(self.patch_ids.as_ref().unwrap().contains(&patch_id))
+    }
+}
+
+/// Returns true if applying the changes of the single-parent `commit` on top
+/// of `tree` leaves it unchanged, i.e. if the commit would become empty when
+/// rebased onto the tree.
+pub fn is_applied_to_tree(commit: &Commit, tree: &MergedTree) -> BackendResult<bool> {
+    let [parent_id] = commit.parent_ids() else {
+        return Ok(false);
+    };
+    let parent_tree = commit.store().get_commit(parent_id)?.tree()?;
+    let new_tree = tree.merge(&parent_tree, &commit.tree()?)?;
+    Ok(new_tree.id() == tree.id())
+}
+
+/// Computes a hash of the lines added and removed by a single-parent commit,
+/// ignoring where in the files they are. Returns `None` for merge commits,
+/// empty commits, and commits which change anything other than files and
+/// symlinks.
+pub fn compute_patch_id(commit: &Commit) -> BackendResult<Option<Vec<u8>>> {
+    let [parent_id] = commit.parent_ids() else {
+        return Ok(None);
+    };
+    let store = commit.store();
+    let parent_tree = store.get_commit(parent_id)?.tree()?;
+    let tree = commit.tree()?;
+    let copy_records = CopyRecords::default();
+    let tree_diff = parent_tree.diff_stream_with_copies(&tree, &EverythingMatcher, &copy_records);
+    let mut diff_stream = materialized_diff_stream(store, tree_diff);
+    let mut hasher = Blake2b512::new();
+    let mut update = |data: &[u8]| {
+        hasher.update((data.len() as u64).to_le_bytes());
+        hasher.update(data);
+    };
+    let mut has_changes = false;
+    while let Some(entry) = diff_stream.next().block_on() {
+        let path = entry.path.target();
+        let (left_value, right_value) = entry.values?;
+        let (Some(left_content), Some(right_content)) = (
+            patch_content(path, left_value)?,
+            patch_content(path, right_value)?,
+        ) else {
+            return Ok(None);
+        };
+        update(path.as_internal_file_string().as_bytes());
+        for hunk in Diff::by_line([&left_content, &right_content]).hunks() {
+            if hunk.kind == DiffHunkKind::Different {
+                update(hunk.contents[0]);
+                update(hunk.contents[1]);
+            }
+        }
+        has_changes = true;
+    }
+    Ok(has_changes.then(|| hasher.finalize().to_vec()))
+}
+
+fn patch_content(path: &RepoPath, value: MaterializedTreeValue) -> BackendResult<Option<Vec<u8>>> {
+    match value {
+        MaterializedTreeValue::Absent => Ok(Some(vec![])),
+        MaterializedTreeValue::File { id, mut reader, .. } => {
+            let mut content = vec![];
+            reader
+                .read_to_end(&mut content)
+                .map_err(|err| BackendError::ReadFile {
+                    path: path.to_owned(),
+                    id,
+                    source: err.into(),
+                })?;
+            Ok(Some(content))
+        }
+        MaterializedTreeValue::Symlink { id: _, target } => Ok(Some(target.into_bytes())),
+        MaterializedTreeValue::AccessDenied(_)
+        | MaterializedTreeValue::FileConflict { .. }
+        | MaterializedTreeValue::OtherConflict { .. }
+        | MaterializedTreeValue::GitSubmodule(_)
+        | MaterializedTreeValue::Tree(_) => Ok(None),
+    }
+}
+
 #[derive(Default)]
 pub struct DuplicateCommitsStats {
     /// Map of original commit ID to newly duplicated commit.
@@ -882,9 +1155,11 @@ pub fn duplicate_commits(
                 })
                 .collect()
         };
+        let original_change_id = original_commit.change_id().clone();
         let new_commit = CommitRewriter::new(mut_repo, original_commit, new_parent_ids)
             .rebase()?
             .generate_new_change_id()
+            .set_duplicated_from(Some(original_change_id))
             .write()?;
         duplicated_old_to_new.insert(original_commit_id.clone(), new_commit);
     }
@@ -964,6 +1239,7 @@ pub fn duplicate_commits_onto_parents(
         let new_commit = mut_repo
             .rewrite_commit(&original_commit)
             .generate_new_change_id()
+            .set_duplicated_from(Some(original_commit.change_id().clone()))
             .set_parents(new_parent_ids)
             .write()?;
         duplicated_old_to_new.insert(original_commit_id.clone(), new_commit);
@@ -1133,9 +1409,29 @@ pub fn squash_commits<'repo>(
     }
     // Apply the selected changes onto the destination
     let mut destination_tree = rewritten_destination.tree()?;
+    let mut conflict_sources =
+        tree_term_sources(slice::from_ref(&rewritten_destination), &destination_tree);
     for source in &source_commits {
-        destination_tree =
-            destination_tree.merge(&source.commit.parent_tree, &source.commit.selected_tree)?;
+        let source_parents: Vec<_> = source.commit.commit.parents().try_collect()?;
+        (destination_tree, conflict_sources) = merge_trees_with_sources(
+            (&destination_tree, conflict_sources),
+            (
+                &source.commit.parent_tree,
+                tree_term_sources(&source_parents, &source.commit.parent_tree),
+            ),
+            (
+                &source.commit.selected_tree,
+                // Partially selected changes don't come from the source commit
+                if source.commit.is_full_selection() {
+                    tree_term_sources(
+                        slice::from_ref(&source.commit.commit),
+                        &source.commit.selected_tree,
+                    )
+                } else {
+                    None
+                },
+            ),
+        )?;
     }
     let mut predecessors = vec![destination.id().clone()];
     predecessors.extend(
@@ -1147,6 +1443,7 @@ pub fn squash_commits<'repo>(
     let commit_builder = repo
         .rewrite_commit(&rewritten_destination)
         .set_tree_id(destination_tree.id().clone())
+        .set_conflict_sources(conflict_sources)
         .set_predecessors(predecessors);
     Ok(Some(SquashedCommit {
         commit_builder,
@@ -0,0 +1,603 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Optional on-disk full-text search index of commits.
+//!
+//! The index maps trigrams of the descriptions and authors, and the paths
+//! modified by each commit, to the commits containing them. Revset filters use
+//! it to skip commits which can't match without reading the commit objects.
+//! Commits which haven't been indexed yet are always tested against the
+//! filters, so the index doesn't have to be up to date to give correct
+//! results.
+//!
+//! The index is enabled if its directory exists. It's stored as a stack of
+//! segments, each of which indexes the commits added to the commit index
+//! since the previous segment. Small segments are squashed into their parents
+//! so that the stack stays shallow.
+
+#![allow(missing_docs)]
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use blake2::Blake2b512;
+use blake2::Digest as _;
+use futures::StreamExt as _;
+use pollster::FutureExt as _;
+use prost::Message as _;
+use tempfile::NamedTempFile;
+use thiserror::Error;
+
+use crate::backend::BackendError;
+use crate::backend::BackendResult;
+use crate::backend::CommitId;
+use crate::commit::Commit;
+use crate::file_util::create_or_reuse_dir;
+use crate::file_util::persist_content_addressed_temp_file;
+use crate::file_util::IoResultExt as _;
+use crate::file_util::PathError;
+use crate::fileset::FilePattern;
+use crate::fileset::FilesetExpression;
+use crate::matchers::EverythingMatcher;
+use crate::object_id::ObjectId as _;
+use crate::repo_path::RepoPath;
+use crate::repo_path::RepoPathBuf;
+use crate::store::Store;
+use crate::str_util::StringPattern;
+
+/// Error that may occur while reading or updating the search index.
+#[derive(Debug, Error)]
+pub enum SearchIndexError {
+    #[error(transparent)]
+    Backend(#[from] BackendError),
+    #[error(transparent)]
+    Io(#[from] PathError),
+    #[error("Corrupt search index file {path}")]
+    Corrupt {
+        path: PathBuf,
+        #[source]
+        source: prost::DecodeError,
+    },
+}
+
+/// Commit field whose trigrams are indexed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SearchField {
+    /// The full description.
+    Description,
+    /// The author name and email.
+    Author,
+}
+
+/// Stores the search index in files under the given directory.
+pub struct SearchIndex {
+    dir: PathBuf,
+    /// The loaded index, cleared when the index is updated.
+    snapshot: Mutex<Option<Arc<SearchIndexSnapshot>>>,
+}
+
+impl Debug for SearchIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SearchIndex")
+            .field("dir", &self.dir)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SearchIndex {
+    pub fn new(dir: PathBuf) -> Self {
+        SearchIndex {
+            dir,
+            snapshot: Mutex::new(None),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns true if the index should be maintained and used.
+    pub fn is_enabled(&self) -> bool {
+        self.dir.is_dir()
+    }
+
+    /// Creates the index directory. Commits are added by
+    /// [`SearchIndex::add_commits()`].
+    pub fn enable(&self) -> Result<(), SearchIndexError> {
+        create_or_reuse_dir(&self.dir).context(&self.dir)?;
+        let segments_dir = self.segments_dir();
+        create_or_reuse_dir(&segments_dir).context(&segments_dir)?;
+        Ok(())
+    }
+
+    /// Removes the index and all its data.
+    pub fn disable(&self) -> Result<(), SearchIndexError> {
+        *self.snapshot.lock().unwrap() = None;
+        match fs::remove_dir_all(&self.dir) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(PathError {
+                path: self.dir.clone(),
+                error,
+            }
+            .into()),
+        }
+    }
+
+    fn segments_dir(&self) -> PathBuf {
+        self.dir.join("segments")
+    }
+
+    fn head_path(&self) -> PathBuf {
+        self.dir.join("head")
+    }
+
+    fn read_head(&self) -> Result<Option<crate::protos::search_index::Head>, SearchIndexError> {
+        let path = self.head_path();
+        let buf = match fs::read(&path) {
+            Ok(buf) => buf,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(PathError { path, error }.into()),
+        };
+        let head = crate::protos::search_index::Head::decode(&*buf)
+            .map_err(|source| SearchIndexError::Corrupt { path, source })?;
+        Ok(Some(head))
+    }
+
+    /// Returns the name of the commit index segment whose commits have been
+    /// indexed, and the number of commits in it and its ancestors.
+    pub fn indexed_commit_index_segment(&self) -> Result<Option<(String, u32)>, SearchIndexError> {
+        Ok(self
+            .read_head()?
+            .map(|head| (head.commit_index_segment, head.num_commits)))
+    }
+
+    /// Indexes the given commits, and records that the commits in the commit
+    /// index segment `commit_index_segment` (which has `num_commits` commits
+    /// including its ancestors) have been indexed.
+    pub fn add_commits(
+        &self,
+        store: &Arc<Store>,
+        commit_ids: &[CommitId],
+        commit_index_segment: &str,
+        num_commits: u32,
+    ) -> Result<(), SearchIndexError> {
+        let old_head = self.read_head()?;
+        let mut parent_name = old_head
+            .map(|head| head.segment)
+            .filter(|name| !name.is_empty());
+        if !commit_ids.is_empty() {
+            let mut segment = SegmentData::default();
+            for commit_id in commit_ids {
+                segment.add_commit(&store.get_commit(commit_id)?)?;
+            }
+            // Squash small segments into their parents, like the commit index
+            // does.
+            while let Some(name) = &parent_name {
+                let (parent_parent_name, mut parent) = self.load_segment(name)?;
+                if parent.commit_ids.len() > 2 * segment.commit_ids.len() {
+                    break;
+                }
+                parent.append(segment);
+                segment = parent;
+                parent_name = parent_parent_name;
+            }
+            parent_name = Some(self.save_segment(parent_name.unwrap_or_default(), segment)?);
+        }
+        let head = crate::protos::search_index::Head {
+            segment: parent_name.unwrap_or_default(),
+            commit_index_segment: commit_index_segment.to_owned(),
+            num_commits,
+        };
+        let path = self.head_path();
+        let mut temp_file = NamedTempFile::new_in(&self.dir).context(&self.dir)?;
+        temp_file
+            .write_all(&head.encode_to_vec())
+            .context(temp_file.path())?;
+        temp_file
+            .persist(&path)
+            .map_err(|err| err.error)
+            .context(&path)?;
+        *self.snapshot.lock().unwrap() = None;
+        Ok(())
+    }
+
+    fn load_segment(&self, name: &str) -> Result<(Option<String>, SegmentData), SearchIndexError> {
+        let path = self.segments_dir().join(name);
+        let buf = fs::read(&path).context(&path)?;
+        let proto = crate::protos::search_index::Segment::decode(&*buf)
+            .map_err(|source| SearchIndexError::Corrupt { path, source })?;
+        let parent = Some(proto.parent).filter(|name| !name.is_empty());
+        let postings_from_proto =
+            |map: HashMap<String, crate::protos::search_index::PostingList>| {
+                map.into_iter()
+                    .map(|(term, list)| (term, list.positions))
+                    .collect()
+            };
+        let segment = SegmentData {
+            commit_ids: proto.commit_ids.into_iter().map(CommitId::new).collect(),
+            description_trigrams: postings_from_proto(proto.description_trigrams),
+            author_trigrams: postings_from_proto(proto.author_trigrams),
+            paths: postings_from_proto(proto.paths),
+        };
+        Ok((parent, segment))
+    }
+
+    fn save_segment(
+        &self,
+        parent: String,
+        segment: SegmentData,
+    ) -> Result<String, SearchIndexError> {
+        let postings_to_proto = |map: HashMap<String, Vec<u32>>| {
+            map.into_iter()
+                .map(|(term, positions)| {
+                    (term, crate::protos::search_index::PostingList { positions })
+                })
+                .collect()
+        };
+        let proto = crate::protos::search_index::Segment {
+            parent,
+            commit_ids: segment
+                .commit_ids
+                .into_iter()
+                .map(|id| id.to_bytes())
+                .collect(),
+            description_trigrams: postings_to_proto(segment.description_trigrams),
+            author_trigrams: postings_to_proto(segment.author_trigrams),
+            paths: postings_to_proto(segment.paths),
+        };
+        let buf = proto.encode_to_vec();
+        let name = hex::encode(Blake2b512::digest(&buf));
+        let dir = self.segments_dir();
+        let mut temp_file = NamedTempFile::new_in(&dir).context(&dir)?;
+        temp_file.write_all(&buf).context(temp_file.path())?;
+        let path = dir.join(&name);
+        persist_content_addressed_temp_file(temp_file, &path).context(&path)?;
+        Ok(name)
+    }
+
+    /// Loads all segments of the index.
+    pub fn snapshot(&self) -> Result<Arc<SearchIndexSnapshot>, SearchIndexError> {
+        let mut cached = self.snapshot.lock().unwrap();
+        if let Some(snapshot) = &*cached {
+            return Ok(snapshot.clone());
+        }
+        let mut segments = vec![];
+        let mut name = self
+            .read_head()?
+            .map(|head| head.segment)
+            .filter(|name| !name.is_empty());
+        while let Some(current_name) = name {
+            let (parent_name, segment) = self.load_segment(&current_name)?;
+            segments.push(segment);
+            name = parent_name;
+        }
+        let mut data = SegmentData::default();
+        for segment in segments.into_iter().rev() {
+            data.append(segment);
+        }
+        let snapshot = Arc::new(SearchIndexSnapshot::new(data));
+        *cached = Some(snapshot.clone());
+        Ok(snapshot)
+    }
+
+    /// Finds the commits whose `field` may match the `pattern`. Returns `None`
+    /// if the index is disabled or can't narrow down the commits.
+    pub fn text_candidates(
+        &self,
+        field: SearchField,
+        pattern: &StringPattern,
+    ) -> Option<SearchCandidates> {
+        let trigrams: HashSet<String> = pattern_literals(pattern)?
+            .iter()
+            .flat_map(|literal| trigrams(literal))
+            .collect();
+        if trigrams.is_empty() {
+            return None;
+        }
+        let snapshot = self.enabled_snapshot()?;
+        let postings = match field {
+            SearchField::Description => &snapshot.data.description_trigrams,
+            SearchField::Author => &snapshot.data.author_trigrams,
+        };
+        let positions = trigrams
+            .iter()
+            .map(|trigram| postings.get(trigram).map_or(&[][..], |list| list))
+            .map(|list| list.iter().copied().collect::<HashSet<_>>())
+            .reduce(|set1, set2| &set1 & &set2)?;
+        Some(SearchCandidates {
+            snapshot,
+            positions,
+        })
+    }
+
+    /// Finds the commits which may modify the paths matched by the
+    /// `expression`. Returns `None` if the index is disabled or can't narrow
+    /// down the commits.
+    pub fn path_candidates(&self, expression: &FilesetExpression) -> Option<SearchCandidates> {
+        // Check that the expression can be used before loading the index.
+        path_positions(expression, &HashMap::new())?;
+        let snapshot = self.enabled_snapshot()?;
+        let positions = path_positions(expression, &snapshot.data.paths)?;
+        Some(SearchCandidates {
+            snapshot,
+            positions,
+        })
+    }
+
+    fn enabled_snapshot(&self) -> Option<Arc<SearchIndexSnapshot>> {
+        if !self.is_enabled() {
+            return None;
+        }
+        match self.snapshot() {
+            Ok(snapshot) => Some(snapshot),
+            Err(err) => {
+                tracing::warn!(?err, "failed to load search index");
+                None
+            }
+        }
+    }
+}
+
+/// Commits and their indexed terms. Positions index into `commit_ids`.
+#[derive(Debug, Default)]
+struct SegmentData {
+    commit_ids: Vec<CommitId>,
+    description_trigrams: HashMap<String, Vec<u32>>,
+    author_trigrams: HashMap<String, Vec<u32>>,
+    paths: HashMap<String, Vec<u32>>,
+}
+
+impl SegmentData {
+    fn add_commit(&mut self, commit: &Commit) -> BackendResult<()> {
+        let pos = u32::try_from(self.commit_ids.len()).unwrap();
+        self.commit_ids.push(commit.id().clone());
+        for trigram in trigrams(commit.description()) {
+            add_posting(&mut self.description_trigrams, trigram, pos);
+        }
+        let author = commit.author();
+        for trigram in itertools::chain(trigrams(&author.name), trigrams(&author.email)) {
+            add_posting(&mut self.author_trigrams, trigram, pos);
+        }
+        let mut paths = HashSet::new();
+        for path in modified_paths(commit)? {
+            let mut path: &RepoPath = &path;
+            while !path.is_root() && paths.insert(path.to_owned()) {
+                path = path.parent().unwrap();
+            }
+        }
+        for path in paths {
+            add_posting(&mut self.paths, path.into_internal_string(), pos);
+        }
+        Ok(())
+    }
+
+    /// Appends the commits of the `other` segment after the commits of this
+    /// segment.
+    fn append(&mut self, other: SegmentData) {
+        let offset = u32::try_from(self.commit_ids.len()).unwrap();
+        self.commit_ids.extend(other.commit_ids);
+        for (postings, other_postings) in [
+            (&mut self.description_trigrams, other.description_trigrams),
+            (&mut self.author_trigrams, other.author_trigrams),
+            (&mut self.paths, other.paths),
+        ] {
+            for (term, positions) in other_postings {
+                postings
+                    .entry(term)
+                    .or_default()
+                    .extend(positions.into_iter().map(|pos| pos + offset));
+            }
+        }
+    }
+}
+
+fn add_posting(postings: &mut HashMap<String, Vec<u32>>, term: String, pos: u32) {
+    let positions = postings.entry(term).or_default();
+    if positions.last() != Some(&pos) {
+        positions.push(pos);
+    }
+}
+
+/// Returns the paths which differ between the commit and any of its parents.
+///
+/// This is a superset of the paths modified relative to the merged parents.
+fn modified_paths(commit: &Commit) -> BackendResult<Vec<RepoPathBuf>> {
+    let tree = commit.tree()?;
+    let mut paths = vec![];
+    for parent in commit.parents() {
+        let parent_tree = parent?.tree()?;
+        let mut diff = parent_tree.diff_stream(&tree, &EverythingMatcher);
+        async {
+            while let Some(entry) = diff.next().await {
+                entry.values?;
+                paths.push(entry.path);
+            }
+            Ok::<_, BackendError>(())
+        }
+        .block_on()?;
+    }
+    Ok(paths)
+}
+
+/// Returns the trigrams of the ASCII-lowercased text.
+fn trigrams(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().map(|c| c.to_ascii_lowercase()).collect();
+    chars
+        .windows(3)
+        .map(|window| window.iter().collect())
+        .collect()
+}
+
+/// Returns the literal strings which a string matching the `pattern` must
+/// contain, or `None` if they can't be determined.
+fn pattern_literals(pattern: &StringPattern) -> Option<Vec<String>> {
+    match pattern {
+        StringPattern::Exact(literal)
+        | StringPattern::ExactI(literal)
+        | StringPattern::Substring(literal)
+        | StringPattern::SubstringI(literal) => Some(vec![literal.clone()]),
+        StringPattern::Glob(pattern) | StringPattern::GlobI(pattern) => {
+            Some(glob_literals(pattern.as_str()))
+        }
+        StringPattern::Regex(_) => None,
+    }
+}
+
+/// Splits the glob pattern into the literal strings between wildcards.
+fn glob_literals(src: &str) -> Vec<String> {
+    let mut literals = vec![String::new()];
+    let mut chars = src.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' | '?' => literals.push(String::new()),
+            '[' => {
+                literals.push(String::new());
+                // Skip the character class. A leading ']' (after an optional
+                // '!') is part of the class.
+                let mut class_len = 0;
+                for c in chars.by_ref() {
+                    if c == ']' && class_len > 0 {
+                        break;
+                    }
+                    if class_len > 0 || c != '!' {
+                        class_len += 1;
+                    }
+                }
+            }
+            _ => literals.last_mut().unwrap().push(c),
+        }
+    }
+    literals.retain(|literal| !literal.is_empty());
+    literals
+}
+
+/// Returns the positions of the commits which may modify the paths matched by
+/// the `expression`, or `None` if any commit may.
+fn path_positions(
+    expression: &FilesetExpression,
+    postings: &HashMap<String, Vec<u32>>,
+) -> Option<HashSet<u32>> {
+    let lookup = |path: &RepoPath| {
+        if path.is_root() {
+            return None;
+        }
+        let positions = postings
+            .get(path.as_internal_file_string())
+            .map_or(&[][..], |list| list);
+        Some(positions.iter().copied().collect())
+    };
+    match expression {
+        FilesetExpression::None => Some(HashSet::new()),
+        FilesetExpression::All => None,
+        FilesetExpression::Pattern(pattern) => match pattern {
+            FilePattern::FilePath(path) | FilePattern::PrefixPath(path) => lookup(path),
+            FilePattern::FileGlob { dir, .. } => lookup(dir),
+        },
+        FilesetExpression::UnionAll(expressions) => {
+            let mut positions = HashSet::new();
+            for expression in expressions {
+                positions.extend(path_positions(expression, postings)?);
+            }
+            Some(positions)
+        }
+        FilesetExpression::Intersection(expression1, expression2) => {
+            match (
+                path_positions(expression1, postings),
+                path_positions(expression2, postings),
+            ) {
+                (Some(positions1), Some(positions2)) => Some(&positions1 & &positions2),
+                (Some(positions), None) | (None, Some(positions)) => Some(positions),
+                (None, None) => None,
+            }
+        }
+        FilesetExpression::Difference(expression1, _) => path_positions(expression1, postings),
+    }
+}
+
+/// All segments of the search index, loaded into memory.
+#[derive(Debug)]
+pub struct SearchIndexSnapshot {
+    data: SegmentData,
+    positions: HashMap<CommitId, u32>,
+}
+
+impl SearchIndexSnapshot {
+    fn new(data: SegmentData) -> Self {
+        let positions = data
+            .commit_ids
+            .iter()
+            .enumerate()
+            .map(|(pos, id)| (id.clone(), u32::try_from(pos).unwrap()))
+            .collect();
+        SearchIndexSnapshot { data, positions }
+    }
+
+    pub fn num_commits(&self) -> usize {
+        self.data.commit_ids.len()
+    }
+
+    /// Returns true if the commit has been indexed.
+    pub fn contains(&self, commit_id: &CommitId) -> bool {
+        self.positions.contains_key(commit_id)
+    }
+}
+
+/// Commits which may match a filter, according to the search index.
+#[derive(Clone, Debug)]
+pub struct SearchCandidates {
+    snapshot: Arc<SearchIndexSnapshot>,
+    positions: HashSet<u32>,
+}
+
+impl SearchCandidates {
+    /// Returns false if the commit has been indexed and can't match the
+    /// filter.
+    pub fn may_match(&self, commit_id: &CommitId) -> bool {
+        self.snapshot
+            .positions
+            .get(commit_id)
+            .is_none_or(|pos| self.positions.contains(pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trigrams() {
+        assert_eq!(trigrams("ab"), Vec::<String>::new());
+        assert_eq!(trigrams("AbCd"), vec!["abc", "bcd"]);
+        assert_eq!(trigrams("ÀÀÀ"), vec!["ÀÀÀ"]);
+    }
+
+    #[test]
+    fn test_glob_literals() {
+        assert_eq!(glob_literals("foo"), vec!["foo"]);
+        assert_eq!(glob_literals("foo*bar?baz"), vec!["foo", "bar", "baz"]);
+        assert_eq!(glob_literals("*[abc]def"), vec!["def"]);
+        assert_eq!(glob_literals("a[!]x]b[]]c"), vec!["a", "b", "c"]);
+        assert_eq!(glob_literals("[*]"), Vec::<String>::new());
+    }
+}
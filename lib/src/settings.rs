@@ -54,6 +54,7 @@ struct UserSettingsData {
     operation_hostname: String,
     operation_username: String,
     signing_behavior: SignBehavior,
+    signing_on_rewrite: bool,
     signing_key: Option<String>,
 }
 
@@ -63,6 +64,9 @@ pub struct GitSettings {
     pub abandon_unreachable_commits: bool,
     pub subprocess: bool,
     pub executable_path: PathBuf,
+    pub fetch_tags: GitFetchTags,
+    pub import_stash: bool,
+    pub reconcile_rewritten_commits: bool,
 }
 
 impl GitSettings {
@@ -72,6 +76,9 @@ impl GitSettings {
             abandon_unreachable_commits: settings.get_bool("git.abandon-unreachable-commits")?,
             subprocess: settings.get_bool("git.subprocess")?,
             executable_path: settings.get("git.executable-path")?,
+            fetch_tags: settings.get("git.fetch-tags")?,
+            import_stash: settings.get_bool("git.import-stash")?,
+            reconcile_rewritten_commits: settings.get_bool("git.reconcile-rewritten-commits")?,
         })
     }
 }
@@ -83,15 +90,33 @@ impl Default for GitSettings {
             abandon_unreachable_commits: true,
             subprocess: true,
             executable_path: PathBuf::from("git"),
+            fetch_tags: GitFetchTags::Reachable,
+            import_stash: false,
+            reconcile_rewritten_commits: true,
         }
     }
 }
 
+/// Which tags are fetched from Git remotes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GitFetchTags {
+    /// Fetch all tags, and update tags which were moved on the remote.
+    All,
+    /// Fetch tags pointing to fetched commits, like Git does by default.
+    Reachable,
+    /// Don't fetch tags.
+    None,
+}
+
 /// Commit signing settings, describes how to and if to sign commits.
 #[derive(Debug, Clone)]
 pub struct SignSettings {
     /// What to actually do, see [SignBehavior].
     pub behavior: SignBehavior,
+    /// Whether to re-sign our own signed commits when they are rewritten,
+    /// regardless of the `behavior`.
+    pub sign_on_rewrite: bool,
     /// The email address to compare against the commit author when determining
     /// if the existing signature is "our own" in terms of the sign behavior.
     pub user_email: String,
@@ -103,11 +128,14 @@ impl SignSettings {
     /// Check if a commit should be signed according to the configured behavior
     /// and email.
     pub fn should_sign(&self, commit: &Commit) -> bool {
+        // A commit which is already signed is being rewritten.
+        let is_own_signed = commit.secure_sig.is_some() && commit.author.email == self.user_email;
+        if self.sign_on_rewrite && is_own_signed {
+            return true;
+        }
         match self.behavior {
             SignBehavior::Drop => false,
-            SignBehavior::Keep => {
-                commit.secure_sig.is_some() && commit.author.email == self.user_email
-            }
+            SignBehavior::Keep => is_own_signed,
             SignBehavior::Own => commit.author.email == self.user_email,
             SignBehavior::Force => true,
         }
@@ -147,6 +175,7 @@ impl UserSettings {
         let operation_hostname = config.get("operation.hostname")?;
         let operation_username = config.get("operation.username")?;
         let signing_behavior = config.get("signing.behavior")?;
+        let signing_on_rewrite = config.get("signing.sign-on-rewrite")?;
         let signing_key = config.get("signing.key").optional()?;
         let data = UserSettingsData {
             user_name,
@@ -156,6 +185,7 @@ impl UserSettings {
             operation_hostname,
             operation_username,
             signing_behavior,
+            signing_on_rewrite,
             signing_key,
         };
         Ok(UserSettings {
@@ -242,6 +272,7 @@ impl UserSettings {
     pub fn sign_settings(&self) -> SignSettings {
         SignSettings {
             behavior: self.data.signing_behavior,
+            sign_on_rewrite: self.data.signing_on_rewrite,
             user_email: self.data.user_email.clone(),
             key: self.data.signing_key.clone(),
         }
@@ -33,6 +33,7 @@ use tempfile::NamedTempFile;
 use thiserror::Error;
 
 use crate::backend::BackendInitError;
+use crate::backend::ChangeId;
 use crate::backend::CommitId;
 use crate::backend::MillisSinceEpoch;
 use crate::backend::Timestamp;
@@ -486,6 +487,7 @@ fn view_to_proto(view: &View) -> crate::protos::op_store::View {
     }
 
     proto.bookmarks = bookmark_views_to_proto_legacy(&view.local_bookmarks, &view.remote_views);
+    proto.archived_bookmarks = view.archived_bookmarks.iter().cloned().collect();
 
     for (name, target) in &view.tags {
         proto.tags.push(crate::protos::op_store::Tag {
@@ -504,6 +506,15 @@ fn view_to_proto(view: &View) -> crate::protos::op_store::View {
 
     proto.git_head = ref_target_to_proto(&view.git_head);
 
+    for (change_id, entries) in &view.change_metadata {
+        proto
+            .change_metadata
+            .push(crate::protos::op_store::ChangeMetadata {
+                change_id: change_id.to_bytes(),
+                entries: entries.clone().into_iter().collect(),
+            });
+    }
+
     proto
 }
 
@@ -528,6 +539,7 @@ fn view_from_proto(proto: crate::protos::op_store::View) -> View {
     let (local_bookmarks, remote_views) = bookmark_views_from_proto_legacy(proto.bookmarks);
     view.local_bookmarks = local_bookmarks;
     view.remote_views = remote_views;
+    view.archived_bookmarks = proto.archived_bookmarks.into_iter().collect();
 
     for tag_proto in proto.tags {
         view.tags
@@ -551,6 +563,13 @@ fn view_from_proto(proto: crate::protos::op_store::View) -> View {
         view.git_head = RefTarget::normal(CommitId::new(proto.git_head_legacy));
     }
 
+    for metadata in proto.change_metadata {
+        view.change_metadata.insert(
+            ChangeId::new(metadata.change_id),
+            metadata.entries.into_iter().collect(),
+        );
+    }
+
     view
 }
 
@@ -722,6 +741,7 @@ mod tests {
     use insta::assert_snapshot;
     use itertools::Itertools as _;
     use maplit::btreemap;
+    use maplit::btreeset;
     use maplit::hashmap;
     use maplit::hashset;
 
@@ -755,6 +775,7 @@ mod tests {
             local_bookmarks: btreemap! {
                 "main".to_string() => bookmark_main_local_target,
             },
+            archived_bookmarks: btreeset! {},
             tags: btreemap! {
                 "v1.0".to_string() => tag_v1_target,
             },
@@ -775,6 +796,7 @@ mod tests {
                 WorkspaceId::default() => default_wc_commit_id,
                 WorkspaceId::new("test".to_string()) => test_wc_commit_id,
             },
+            change_metadata: btreemap! {},
         }
     }
 
@@ -840,6 +862,29 @@ mod tests {
         let view_id = store.write_view(&view).unwrap();
         let read_view = store.read_view(&view_id).unwrap();
         assert_eq!(read_view, view);
+
+        let view = View {
+            archived_bookmarks: btreeset! {"main".to_string()},
+            ..create_view()
+        };
+        let archived_view_id = store.write_view(&view).unwrap();
+        assert_ne!(archived_view_id, view_id);
+        let read_view = store.read_view(&archived_view_id).unwrap();
+        assert_eq!(read_view, view);
+
+        let view = View {
+            change_metadata: btreemap! {
+                ChangeId::from_hex("ccc111") => btreemap! {
+                    "review".to_string() => "https://example.com/1".to_string(),
+                    "ticket".to_string() => "ABC-1".to_string(),
+                },
+            },
+            ..create_view()
+        };
+        let metadata_view_id = store.write_view(&view).unwrap();
+        assert_ne!(metadata_view_id, view_id);
+        let read_view = store.read_view(&metadata_view_id).unwrap();
+        assert_eq!(read_view, view);
     }
 
     #[test]
@@ -20,6 +20,7 @@ use std::fmt::Formatter;
 use std::io::Read;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::sync::OnceLock;
 use std::time::SystemTime;
 
 use clru::CLruCache;
@@ -28,6 +29,7 @@ use pollster::FutureExt;
 
 use crate::backend;
 use crate::backend::Backend;
+use crate::backend::BackendError;
 use crate::backend::BackendResult;
 use crate::backend::ChangeId;
 use crate::backend::CommitId;
@@ -48,6 +50,7 @@ use crate::repo_path::RepoPathBuf;
 use crate::signing::Signer;
 use crate::tree::Tree;
 use crate::tree_builder::TreeBuilder;
+use crate::tree_diff_cache::TreeDiffCache;
 
 // There are more tree objects than commits, and trees are often shared across
 // commits.
@@ -59,6 +62,7 @@ const TREE_CACHE_CAPACITY: usize = 1000;
 pub struct Store {
     backend: Box<dyn Backend>,
     signer: Signer,
+    tree_diff_cache: OnceLock<TreeDiffCache>,
     commit_cache: Mutex<CLruCache<CommitId, Arc<backend::Commit>>>,
     tree_cache: Mutex<CLruCache<(RepoPathBuf, TreeId), Arc<backend::Tree>>>,
 }
@@ -76,6 +80,7 @@ impl Store {
         Arc::new(Store {
             backend,
             signer,
+            tree_diff_cache: OnceLock::new(),
             commit_cache: Mutex::new(CLruCache::new(COMMIT_CACHE_CAPACITY.try_into().unwrap())),
             tree_cache: Mutex::new(CLruCache::new(TREE_CACHE_CAPACITY.try_into().unwrap())),
         })
@@ -85,10 +90,26 @@ impl Store {
         self.backend.as_any()
     }
 
+    /// Returns the name of the commit backend.
+    pub fn backend_name(&self) -> &str {
+        self.backend.name()
+    }
+
     pub fn signer(&self) -> &Signer {
         &self.signer
     }
 
+    /// Persistent cache of tree diffs and conflicts, if enabled.
+    pub fn tree_diff_cache(&self) -> Option<&TreeDiffCache> {
+        self.tree_diff_cache.get()
+    }
+
+    /// Enables the persistent cache of tree diffs and conflicts. Does nothing
+    /// if the cache has already been enabled.
+    pub fn enable_tree_diff_cache(&self, cache: TreeDiffCache) {
+        self.tree_diff_cache.get_or_init(|| cache);
+    }
+
     pub fn get_copy_records(
         &self,
         paths: Option<&[RepoPathBuf]>,
@@ -289,6 +310,13 @@ impl Store {
     }
 
     pub fn gc(&self, index: &dyn Index, keep_newer: SystemTime) -> BackendResult<()> {
-        self.backend.gc(index, keep_newer)
+        self.backend.gc(index, keep_newer)?;
+        // The cached diffs may refer to the deleted objects
+        if let Some(cache) = self.tree_diff_cache.get() {
+            cache
+                .clear()
+                .map_err(|err| BackendError::Other(err.into()))?;
+        }
+        Ok(())
     }
 }
@@ -227,6 +227,30 @@ impl StringPattern {
         }
     }
 
+    /// Returns the first part of the `haystack` that matches this pattern.
+    ///
+    /// Exact and glob patterns match the whole `haystack` if any.
+    pub fn find<'a>(&self, haystack: &'a str) -> Option<&'a str> {
+        match self {
+            StringPattern::Exact(_)
+            | StringPattern::ExactI(_)
+            | StringPattern::Glob(_)
+            | StringPattern::GlobI(_) => self.matches(haystack).then_some(haystack),
+            StringPattern::Substring(needle) => {
+                let start = haystack.find(needle.as_str())?;
+                Some(&haystack[start..start + needle.len()])
+            }
+            StringPattern::SubstringI(needle) => {
+                // ASCII case folding preserves byte offsets
+                let start = haystack
+                    .to_ascii_lowercase()
+                    .find(&needle.to_ascii_lowercase())?;
+                Some(&haystack[start..start + needle.len()])
+            }
+            StringPattern::Regex(pattern) => pattern.find(haystack).map(|m| m.as_str()),
+        }
+    }
+
     /// Iterates entries of the given `map` whose keys matches this pattern.
     pub fn filter_btree_map<'a, 'b, K: Borrow<str> + Ord, V>(
         &'b self,
@@ -334,4 +358,24 @@ mod tests {
             Err(StringPatternParseError::InvalidKind(_))
         );
     }
+
+    #[test]
+    fn test_find() {
+        let find =
+            |src: &str, haystack: &'static str| StringPattern::parse(src).unwrap().find(haystack);
+        assert_eq!(find("exact:foo", "foo"), Some("foo"));
+        assert_eq!(find("exact:foo", "foobar"), None);
+        assert_eq!(find("exact-i:FOO", "foo"), Some("foo"));
+        assert_eq!(find("substring:oo", "foobar"), Some("oo"));
+        assert_eq!(find("substring:baz", "foobar"), None);
+        assert_eq!(find("substring-i:BAR", "fooBar"), Some("Bar"));
+        assert_eq!(find("glob:foo*", "foobar"), Some("foobar"));
+        assert_eq!(find("glob:bar*", "foobar"), None);
+        assert_eq!(find("glob-i:FOO*", "foobar"), Some("foobar"));
+        assert_eq!(
+            find("regex:[A-Z]+-[0-9]+", "fix/PROJ-123-bug"),
+            Some("PROJ-123")
+        );
+        assert_eq!(find("regex:[0-9]+", "foobar"), None);
+    }
 }
@@ -0,0 +1,359 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Persistent cache of tree diffs and conflicts.
+//!
+//! Trees are immutable, so the diff between two trees (and the conflicts in a
+//! tree) can be cached by the tree ids forever. The cached data may refer to
+//! objects deleted by GC, so the cache is cleared when the store is GC-ed.
+//!
+//! Only full diffs are cached. They're used for any matcher by filtering the
+//! entries.
+//!
+//! The cache is disabled by default. It's enabled by the
+//! `core.tree-diff-cache.enabled` setting, and holds up to
+//! `core.tree-diff-cache.max-entries` diffs and as many conflict lists.
+
+#![allow(missing_docs)]
+
+use std::collections::hash_map;
+use std::collections::HashMap;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use indexmap::IndexSet;
+use prost::Message as _;
+use tempfile::NamedTempFile;
+
+use crate::backend::CommitId;
+use crate::backend::ConflictId;
+use crate::backend::FileId;
+use crate::backend::MergedTreeId;
+use crate::backend::SymlinkId;
+use crate::backend::TreeId;
+use crate::backend::TreeValue;
+use crate::config::ConfigGetError;
+use crate::content_hash::blake2b_hash;
+use crate::file_util::create_or_reuse_dir;
+use crate::file_util::persist_content_addressed_temp_file;
+use crate::merge::Merge;
+use crate::merge::MergedTreeValue;
+use crate::object_id::ObjectId as _;
+use crate::repo_path::RepoPathBuf;
+use crate::settings::UserSettings;
+
+/// Entry of cached tree diff: the path and the values before and after.
+pub type TreeDiffCacheEntry = (RepoPathBuf, MergedTreeValue, MergedTreeValue);
+
+/// Entry of cached tree conflicts: the path and the conflicted value.
+pub type TreeConflictsCacheEntry = (RepoPathBuf, MergedTreeValue);
+
+/// Kind of cached data, which is also the name of its subdirectory.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum EntryKind {
+    Diff,
+    Conflicts,
+}
+
+impl EntryKind {
+    fn dir_name(self) -> &'static str {
+        match self {
+            EntryKind::Diff => "diff",
+            EntryKind::Conflicts => "conflicts",
+        }
+    }
+}
+
+/// Stores tree diffs and conflicts in files under the cache directory.
+///
+/// Errors are generally ignored since the data can always be recomputed. The
+/// cache directory is created on first write. Once there are more than
+/// `max_entries` files of either kind, the least recently used ones are
+/// removed. The files are ordered by modification time, which is updated when
+/// a file is read, so the order persists across processes.
+#[derive(Debug)]
+pub struct TreeDiffCache {
+    dir: PathBuf,
+    max_entries: usize,
+    /// File names of each kind, from the least recently used. Loaded from the
+    /// directory on first write. Files written by other processes since then
+    /// aren't known, so the directory may temporarily exceed the limit.
+    lru: Mutex<HashMap<EntryKind, IndexSet<String>>>,
+}
+
+impl TreeDiffCache {
+    pub fn new(dir: PathBuf, max_entries: usize) -> Self {
+        TreeDiffCache {
+            dir,
+            max_entries,
+            lru: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Creates the cache under the `repo_path` if enabled by the `settings`.
+    pub fn from_settings(
+        settings: &UserSettings,
+        repo_path: &Path,
+    ) -> Result<Option<Self>, ConfigGetError> {
+        if !settings.get_bool("core.tree-diff-cache.enabled")? {
+            return Ok(None);
+        }
+        let max_entries = settings.get("core.tree-diff-cache.max-entries")?;
+        Ok(Some(TreeDiffCache::new(
+            repo_path.join("tree_diff_cache"),
+            max_entries,
+        )))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Looks up the full diff between `from` and `to` trees.
+    pub fn get_diff(
+        &self,
+        from: &MergedTreeId,
+        to: &MergedTreeId,
+    ) -> Option<Vec<TreeDiffCacheEntry>> {
+        let buf = self.read_file(EntryKind::Diff, &diff_key(from, to))?;
+        let proto = crate::protos::tree_diff_cache::TreeDiff::decode(&*buf).ok()?;
+        proto
+            .entries
+            .into_iter()
+            .map(|entry| {
+                Some((
+                    RepoPathBuf::from_internal_string(entry.path),
+                    merged_tree_value_from_proto(entry.before)?,
+                    merged_tree_value_from_proto(entry.after)?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Saves the full diff between `from` and `to` trees.
+    pub fn insert_diff(
+        &self,
+        from: &MergedTreeId,
+        to: &MergedTreeId,
+        entries: &[TreeDiffCacheEntry],
+    ) -> io::Result<()> {
+        let proto = crate::protos::tree_diff_cache::TreeDiff {
+            entries: entries
+                .iter()
+                .map(
+                    |(path, before, after)| crate::protos::tree_diff_cache::tree_diff::Entry {
+                        path: path.as_internal_file_string().to_owned(),
+                        before: merged_tree_value_to_proto(before),
+                        after: merged_tree_value_to_proto(after),
+                    },
+                )
+                .collect(),
+        };
+        let key = diff_key(from, to);
+        self.write_file(EntryKind::Diff, key, &proto.encode_to_vec())
+    }
+
+    /// Looks up the conflicts in the `tree`.
+    pub fn get_conflicts(&self, tree: &MergedTreeId) -> Option<Vec<TreeConflictsCacheEntry>> {
+        let buf = self.read_file(EntryKind::Conflicts, &conflicts_key(tree))?;
+        let proto = crate::protos::tree_diff_cache::TreeConflicts::decode(&*buf).ok()?;
+        proto
+            .entries
+            .into_iter()
+            .map(|entry| {
+                Some((
+                    RepoPathBuf::from_internal_string(entry.path),
+                    merged_tree_value_from_proto(entry.value)?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Saves the conflicts in the `tree`.
+    pub fn insert_conflicts(
+        &self,
+        tree: &MergedTreeId,
+        entries: &[TreeConflictsCacheEntry],
+    ) -> io::Result<()> {
+        let proto = crate::protos::tree_diff_cache::TreeConflicts {
+            entries: entries
+                .iter()
+                .map(
+                    |(path, value)| crate::protos::tree_diff_cache::tree_conflicts::Entry {
+                        path: path.as_internal_file_string().to_owned(),
+                        value: merged_tree_value_to_proto(value),
+                    },
+                )
+                .collect(),
+        };
+        self.write_file(
+            EntryKind::Conflicts,
+            conflicts_key(tree),
+            &proto.encode_to_vec(),
+        )
+    }
+
+    /// Removes all cached data.
+    pub fn clear(&self) -> io::Result<()> {
+        self.lru.lock().unwrap().clear();
+        match fs::remove_dir_all(&self.dir) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn read_file(&self, kind: EntryKind, name: &str) -> Option<Vec<u8>> {
+        let path = self.dir.join(kind.dir_name()).join(name);
+        let buf = fs::read(&path).ok()?;
+        // Mark the file as recently used. It's fine if that fails.
+        if let Ok(file) = File::options().write(true).open(&path) {
+            file.set_modified(SystemTime::now()).ok();
+        }
+        if let Some(names) = self.lru.lock().unwrap().get_mut(&kind) {
+            names.shift_remove(name);
+            names.insert(name.to_owned());
+        }
+        Some(buf)
+    }
+
+    fn write_file(&self, kind: EntryKind, name: String, data: &[u8]) -> io::Result<()> {
+        let dir = self.dir.join(kind.dir_name());
+        create_or_reuse_dir(&self.dir)?;
+        create_or_reuse_dir(&dir)?;
+        let mut temp_file = NamedTempFile::new_in(&dir)?;
+        temp_file.as_file_mut().write_all(data)?;
+        let path = dir.join(&name);
+        persist_content_addressed_temp_file(temp_file, &path)?;
+
+        let mut lru = self.lru.lock().unwrap();
+        let names = match lru.entry(kind) {
+            hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            hash_map::Entry::Vacant(entry) => entry.insert(load_names_by_use(&dir)?),
+        };
+        names.shift_remove(&name);
+        names.insert(name);
+        while names.len() > self.max_entries {
+            let name = names.shift_remove_index(0).unwrap();
+            match fs::remove_file(dir.join(name)) {
+                Ok(()) => {}
+                // Removed by concurrent process
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the name of the cache file of the diff between `from` and `to`
+/// trees.
+fn diff_key(from: &MergedTreeId, to: &MergedTreeId) -> String {
+    // Legacy and merge tree ids of the same tree are considered equal
+    hex::encode(blake2b_hash(&[from.to_merge(), to.to_merge()][..]))
+}
+
+fn conflicts_key(tree: &MergedTreeId) -> String {
+    hex::encode(blake2b_hash(&tree.to_merge()))
+}
+
+/// Lists the files in the `dir`, from the least recently used.
+fn load_names_by_use(dir: &Path) -> io::Result<IndexSet<String>> {
+    let mut entries: Vec<_> = fs::read_dir(dir)?
+        .filter_map(|entry| {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) => return Some(Err(err)),
+            };
+            let name = entry.file_name().into_string().ok()?;
+            // Skip temporary files being written
+            if name.starts_with('.') {
+                return None;
+            }
+            Some(
+                entry
+                    .metadata()
+                    .and_then(|metadata| Ok((metadata.modified()?, name))),
+            )
+        })
+        .collect::<io::Result<_>>()?;
+    entries.sort_unstable();
+    Ok(entries.into_iter().map(|(_, name)| name).collect())
+}
+
+fn merged_tree_value_to_proto(
+    value: &MergedTreeValue,
+) -> Vec<crate::protos::tree_diff_cache::Term> {
+    value
+        .iter()
+        .map(|term| crate::protos::tree_diff_cache::Term {
+            content: term.as_ref().map(tree_value_to_proto),
+        })
+        .collect()
+}
+
+fn merged_tree_value_from_proto(
+    terms: Vec<crate::protos::tree_diff_cache::Term>,
+) -> Option<MergedTreeValue> {
+    // A merge has an odd number of terms
+    if terms.len() % 2 == 0 {
+        return None;
+    }
+    let values: Vec<_> = terms
+        .into_iter()
+        .map(|term| match term.content {
+            Some(value) => tree_value_from_proto(value).map(Some),
+            None => Some(None),
+        })
+        .collect::<Option<_>>()?;
+    Some(Merge::from_vec(values))
+}
+
+fn tree_value_to_proto(value: &TreeValue) -> crate::protos::tree_diff_cache::TreeValue {
+    use crate::protos::tree_diff_cache::tree_value::File;
+    use crate::protos::tree_diff_cache::tree_value::Value;
+    let value = match value {
+        TreeValue::File { id, executable } => Value::File(File {
+            id: id.to_bytes(),
+            executable: *executable,
+        }),
+        TreeValue::Symlink(id) => Value::SymlinkId(id.to_bytes()),
+        TreeValue::Tree(id) => Value::TreeId(id.to_bytes()),
+        TreeValue::GitSubmodule(id) => Value::GitSubmoduleId(id.to_bytes()),
+        TreeValue::Conflict(id) => Value::ConflictId(id.to_bytes()),
+    };
+    crate::protos::tree_diff_cache::TreeValue { value: Some(value) }
+}
+
+fn tree_value_from_proto(proto: crate::protos::tree_diff_cache::TreeValue) -> Option<TreeValue> {
+    use crate::protos::tree_diff_cache::tree_value::File;
+    use crate::protos::tree_diff_cache::tree_value::Value;
+    let value = match proto.value? {
+        Value::File(File { id, executable }) => TreeValue::File {
+            id: FileId::new(id),
+            executable,
+        },
+        Value::SymlinkId(id) => TreeValue::Symlink(SymlinkId::new(id)),
+        Value::TreeId(id) => TreeValue::Tree(TreeId::new(id)),
+        Value::GitSubmoduleId(id) => TreeValue::GitSubmodule(CommitId::new(id)),
+        Value::ConflictId(id) => TreeValue::Conflict(ConflictId::new(id)),
+    };
+    Some(value)
+}
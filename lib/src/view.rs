@@ -21,6 +21,7 @@ use std::collections::HashSet;
 use itertools::Itertools;
 use thiserror::Error;
 
+use crate::backend::ChangeId;
 use crate::backend::CommitId;
 use crate::op_store;
 use crate::op_store::BookmarkTarget;
@@ -33,6 +34,9 @@ use crate::refs::LocalAndRemoteRef;
 use crate::refs::RemoteRefSymbol;
 use crate::str_util::StringPattern;
 
+/// Metadata key under which the topic of a change is recorded.
+pub const TOPIC_METADATA_KEY: &str = "topic";
+
 /// A wrapper around [`op_store::View`] that defines additional methods.
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub struct View {
@@ -169,6 +173,76 @@ impl View {
             self.data.local_bookmarks.insert(name.to_owned(), target);
         } else {
             self.data.local_bookmarks.remove(name);
+            self.data.archived_bookmarks.remove(name);
+        }
+    }
+
+    /// Iterates names of the archived local bookmarks in lexicographical order.
+    pub fn archived_bookmarks(&self) -> impl Iterator<Item = &str> {
+        self.data.archived_bookmarks.iter().map(AsRef::as_ref)
+    }
+
+    pub fn is_bookmark_archived(&self, name: &str) -> bool {
+        self.data.archived_bookmarks.contains(name)
+    }
+
+    /// Marks the local bookmark as archived or not. Absent bookmark cannot be
+    /// archived.
+    pub fn set_bookmark_archived(&mut self, name: &str, archived: bool) {
+        if archived && self.data.local_bookmarks.contains_key(name) {
+            self.data.archived_bookmarks.insert(name.to_owned());
+        } else {
+            self.data.archived_bookmarks.remove(name);
+        }
+    }
+
+    /// Iterates over `(change_id, entries)` for all changes with metadata.
+    pub fn all_change_metadata(
+        &self,
+    ) -> impl Iterator<Item = (&ChangeId, &BTreeMap<String, String>)> {
+        self.data.change_metadata.iter()
+    }
+
+    /// Returns the metadata entries of the change in lexicographical order of
+    /// keys.
+    pub fn change_metadata(&self, change_id: &ChangeId) -> Option<&BTreeMap<String, String>> {
+        self.data.change_metadata.get(change_id)
+    }
+
+    pub fn get_change_metadata(&self, change_id: &ChangeId, key: &str) -> Option<&str> {
+        let entries = self.data.change_metadata.get(change_id)?;
+        entries.get(key).map(AsRef::as_ref)
+    }
+
+    /// Returns the name of the topic the change belongs to.
+    pub fn get_topic(&self, change_id: &ChangeId) -> Option<&str> {
+        self.get_change_metadata(change_id, TOPIC_METADATA_KEY)
+    }
+
+    /// Iterates over `(change_id, topic)` for all changes which belong to a
+    /// topic.
+    pub fn all_topics(&self) -> impl Iterator<Item = (&ChangeId, &str)> {
+        self.all_change_metadata()
+            .filter_map(|(change_id, entries)| {
+                let topic = entries.get(TOPIC_METADATA_KEY)?;
+                Some((change_id, topic.as_str()))
+            })
+    }
+
+    /// Sets the metadata entry of the change. If the value is `None`, the
+    /// entry will be removed.
+    pub fn set_change_metadata(&mut self, change_id: &ChangeId, key: &str, value: Option<String>) {
+        if let Some(value) = value {
+            self.data
+                .change_metadata
+                .entry(change_id.clone())
+                .or_default()
+                .insert(key.to_owned(), value);
+        } else if let Some(entries) = self.data.change_metadata.get_mut(change_id) {
+            entries.remove(key);
+            if entries.is_empty() {
+                self.data.change_metadata.remove(change_id);
+            }
         }
     }
 
@@ -377,11 +451,13 @@ impl View {
         let op_store::View {
             head_ids,
             local_bookmarks,
+            archived_bookmarks: _,
             tags,
             remote_views,
             git_refs,
             git_head,
             wc_commit_ids,
+            change_metadata: _,
         } = &self.data;
         itertools::chain!(
             head_ids,
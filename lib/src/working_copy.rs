@@ -29,12 +29,14 @@ use crate::backend::BackendError;
 use crate::backend::MergedTreeId;
 use crate::commit::Commit;
 use crate::conflicts::ConflictMarkerStyle;
+use crate::conflicts::ConflictMarkerStyleOverrides;
 use crate::dag_walk;
 use crate::fsmonitor::FsmonitorSettings;
 use crate::gitignore::GitIgnoreError;
 use crate::gitignore::GitIgnoreFile;
 use crate::matchers::EverythingMatcher;
 use crate::matchers::Matcher;
+use crate::merge::Merge;
 use crate::op_heads_store::OpHeadsStoreError;
 use crate::op_store::OpStoreError;
 use crate::op_store::OperationId;
@@ -72,11 +74,32 @@ pub trait WorkingCopy: Send {
     /// that all files should be checked out.
     fn sparse_patterns(&self) -> Result<&[RepoPathBuf], WorkingCopyStateError>;
 
+    /// Paths which are intentionally left untracked. These paths won't be
+    /// tracked by snapshots even if they aren't ignored. The list is sorted.
+    ///
+    /// The default implementation returns an empty list.
+    fn untracked_paths(&self) -> Result<&[RepoPathBuf], WorkingCopyStateError> {
+        Ok(&[])
+    }
+
+    /// File types to be recorded by snapshots instead of the types of the
+    /// files in the working copy. This is useful on file systems which can't
+    /// represent executable bits or symlinks.
+    ///
+    /// The default implementation returns an empty map.
+    fn file_type_overrides(
+        &self,
+    ) -> Result<&BTreeMap<RepoPathBuf, FileTypeOverride>, WorkingCopyStateError> {
+        Ok(&EMPTY_FILE_TYPE_OVERRIDES)
+    }
+
     /// Locks the working copy and returns an instance with methods for updating
     /// the working copy files and state.
     fn start_mutation(&self) -> Result<Box<dyn LockedWorkingCopy>, WorkingCopyStateError>;
 }
 
+static EMPTY_FILE_TYPE_OVERRIDES: BTreeMap<RepoPathBuf, FileTypeOverride> = BTreeMap::new();
+
 /// The factory which creates and loads a specific type of working copy.
 pub trait WorkingCopyFactory {
     /// Create a new working copy from scratch.
@@ -150,6 +173,55 @@ pub trait LockedWorkingCopy {
         options: &CheckoutOptions,
     ) -> Result<CheckoutStats, CheckoutError>;
 
+    /// See `WorkingCopy::untracked_paths()`
+    fn untracked_paths(&self) -> Result<&[RepoPathBuf], WorkingCopyStateError> {
+        Ok(&[])
+    }
+
+    /// Updates the paths which are intentionally left untracked. This doesn't
+    /// touch the files in the working copy, nor the current tree.
+    ///
+    /// The default implementation fails unless the list is empty.
+    fn set_untracked_paths(
+        &mut self,
+        new_untracked_paths: Vec<RepoPathBuf>,
+    ) -> Result<(), WorkingCopyStateError> {
+        if new_untracked_paths.is_empty() {
+            Ok(())
+        } else {
+            Err(WorkingCopyStateError {
+                message: "Failed to set untracked paths".to_owned(),
+                err: "Untracked paths are not supported by this working copy".into(),
+            })
+        }
+    }
+
+    /// See `WorkingCopy::file_type_overrides()`
+    fn file_type_overrides(
+        &self,
+    ) -> Result<&BTreeMap<RepoPathBuf, FileTypeOverride>, WorkingCopyStateError> {
+        Ok(&EMPTY_FILE_TYPE_OVERRIDES)
+    }
+
+    /// Updates the file types to be recorded by snapshots. This doesn't touch
+    /// the files in the working copy, nor the current tree. The overridden
+    /// paths will be snapshotted again by the next snapshot.
+    ///
+    /// The default implementation fails unless the map is empty.
+    fn set_file_type_overrides(
+        &mut self,
+        new_file_type_overrides: BTreeMap<RepoPathBuf, FileTypeOverride>,
+    ) -> Result<(), WorkingCopyStateError> {
+        if new_file_type_overrides.is_empty() {
+            Ok(())
+        } else {
+            Err(WorkingCopyStateError {
+                message: "Failed to set file type overrides".to_owned(),
+                err: "File type overrides are not supported by this working copy".into(),
+            })
+        }
+    }
+
     /// Finish the modifications to the working copy by writing the updated
     /// states to disk. Returns the new (unlocked) working copy.
     fn finish(
@@ -183,6 +255,16 @@ pub enum SnapshotError {
     /// Checking path with ignore patterns failed.
     #[error(transparent)]
     GitIgnoreError(#[from] GitIgnoreError),
+    /// The content of a file was rejected by
+    /// [`SnapshotOptions::content_check`].
+    #[error("Content of {} was rejected", path.as_internal_file_string())]
+    ContentRejected {
+        /// The path of the rejected file.
+        path: RepoPathBuf,
+        /// The error returned by the check.
+        #[source]
+        err: Box<dyn std::error::Error + Send + Sync>,
+    },
     /// Some other error happened while snapshotting the working copy.
     #[error("{message}")]
     Other {
@@ -221,6 +303,12 @@ pub struct SnapshotOptions<'a> {
     pub max_new_file_size: u64,
     /// Expected conflict marker style for checking for changed files.
     pub conflict_marker_style: ConflictMarkerStyle,
+    /// Conflict marker styles to expect instead of `conflict_marker_style` for
+    /// matching paths.
+    pub conflict_marker_style_overrides: ConflictMarkerStyleOverrides,
+    /// A callback to check the content of new or modified files before it's
+    /// written to the store. If it returns an error, the snapshot fails.
+    pub content_check: Option<&'a SnapshotContentCheck<'a>>,
 }
 
 impl SnapshotOptions<'_> {
@@ -233,6 +321,8 @@ impl SnapshotOptions<'_> {
             start_tracking_matcher: &EverythingMatcher,
             max_new_file_size: u64::MAX,
             conflict_marker_style: ConflictMarkerStyle::default(),
+            conflict_marker_style_overrides: ConflictMarkerStyleOverrides::default(),
+            content_check: None,
         }
     }
 }
@@ -240,6 +330,10 @@ impl SnapshotOptions<'_> {
 /// A callback for getting progress updates.
 pub type SnapshotProgress<'a> = dyn Fn(&RepoPath) + 'a + Sync;
 
+/// A callback for checking file content before it's snapshotted.
+pub type SnapshotContentCheck<'a> =
+    dyn Fn(&RepoPath, &[u8]) -> Result<(), Box<dyn std::error::Error + Send + Sync>> + 'a + Sync;
+
 /// Stats about a snapshot operation on a working copy.
 #[derive(Clone, Debug, Default)]
 pub struct SnapshotStats {
@@ -266,6 +360,13 @@ pub enum UntrackedReason {
 pub struct CheckoutOptions {
     /// Conflict marker style to use when materializing files
     pub conflict_marker_style: ConflictMarkerStyle,
+    /// Conflict marker styles to use instead of `conflict_marker_style` for
+    /// matching paths
+    pub conflict_marker_style_overrides: ConflictMarkerStyleOverrides,
+    /// Labels describing where the terms of the conflicts in the tree came
+    /// from, shown next to the conflict markers. Ignored unless the tree has
+    /// the same number of terms.
+    pub conflict_labels: Option<Merge<String>>,
 }
 
 impl CheckoutOptions {
@@ -273,10 +374,25 @@ impl CheckoutOptions {
     pub fn empty_for_test() -> Self {
         CheckoutOptions {
             conflict_marker_style: ConflictMarkerStyle::default(),
+            conflict_marker_style_overrides: ConflictMarkerStyleOverrides::default(),
+            conflict_labels: None,
         }
     }
 }
 
+/// File type to be recorded by snapshots regardless of the type of the file in
+/// the working copy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileTypeOverride {
+    /// A non-executable file.
+    Normal,
+    /// An executable file.
+    Executable,
+    /// A symlink. If the file in the working copy is a regular file, its
+    /// content is used as the symlink target.
+    Symlink,
+}
+
 /// Stats about a checkout operation on a working copy. All "files" mentioned
 /// below may also be symlinks or materialized conflicts.
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
@@ -292,6 +408,15 @@ pub struct CheckoutStats {
     /// working copy but were skipped because there was an untracked (probably
     /// ignored) file in its place.
     pub skipped_files: u32,
+    /// Paths that were supposed to be updated or added in the working copy
+    /// but were skipped because they can't be represented on the disk (e.g.
+    /// reserved names on Windows). These paths aren't included in the other
+    /// counts.
+    pub unrepresentable_paths: Vec<RepoPathBuf>,
+    /// Intentionally untracked paths which are tracked again because they
+    /// were added or updated by the checkout. These paths are also included in
+    /// the other counts.
+    pub retracked_paths: Vec<RepoPathBuf>,
 }
 
 /// The working-copy checkout failed.
@@ -60,6 +60,9 @@ use crate::working_copy::LockedWorkingCopy;
 use crate::working_copy::WorkingCopy;
 use crate::working_copy::WorkingCopyFactory;
 use crate::working_copy::WorkingCopyStateError;
+use crate::workspace_store::SimpleWorkspaceStore;
+use crate::workspace_store::WorkspaceStore as _;
+use crate::workspace_store::WorkspaceStoreError;
 
 #[derive(Error, Debug)]
 pub enum WorkspaceInitError {
@@ -79,6 +82,8 @@ pub enum WorkspaceInitError {
     Backend(#[from] BackendInitError),
     #[error(transparent)]
     SignInit(#[from] SignInitError),
+    #[error(transparent)]
+    WorkspaceStore(#[from] WorkspaceStoreError),
 }
 
 #[derive(Error, Debug)]
@@ -317,6 +322,7 @@ impl Workspace {
             )?;
             let repo_loader = repo.loader().clone();
             let workspace = Workspace::new(workspace_root, repo_dir, working_copy, repo_loader)?;
+            workspace.record_in_workspace_store()?;
             Ok((workspace, repo))
         })()
         .inspect_err(|_err| {
@@ -378,6 +384,7 @@ impl Workspace {
             working_copy,
             repo.loader().clone(),
         )?;
+        workspace.record_in_workspace_store()?;
         Ok((workspace, repo))
     }
 
@@ -396,6 +403,11 @@ impl Workspace {
         &self.workspace_root
     }
 
+    /// Records the location of this workspace in the repo's workspace store.
+    pub fn record_in_workspace_store(&self) -> Result<(), WorkspaceStoreError> {
+        SimpleWorkspaceStore::load(&self.repo_path).add(self.workspace_id(), &self.workspace_root)
+    }
+
     pub fn workspace_id(&self) -> &WorkspaceId {
         self.working_copy.workspace_id()
     }
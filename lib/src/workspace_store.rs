@@ -0,0 +1,209 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Records where the workspaces of a repo are located on disk.
+//!
+//! The view only knows the names of the workspaces. The workspace store maps
+//! them to their roots so that a workspace can be found by name. Workspaces
+//! created before the store existed aren't recorded.
+
+#![allow(missing_docs)]
+
+use std::fmt::Debug;
+use std::fs;
+use std::io;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use prost::Message as _;
+use tempfile::NamedTempFile;
+use thiserror::Error;
+
+use crate::file_util::create_or_reuse_dir;
+use crate::file_util::IoResultExt as _;
+use crate::file_util::PathError;
+use crate::lock::FileLock;
+use crate::lock::FileLockError;
+use crate::op_store::WorkspaceId;
+
+/// Error that may occur while reading or updating the workspace store.
+#[derive(Debug, Error)]
+pub enum WorkspaceStoreError {
+    #[error(transparent)]
+    Io(#[from] PathError),
+    #[error(transparent)]
+    Lock(#[from] FileLockError),
+    #[error("Corrupt workspace store file {path}")]
+    Corrupt {
+        path: PathBuf,
+        #[source]
+        source: prost::DecodeError,
+    },
+    #[error("Workspace path {0} could not be interpreted as Unicode text")]
+    NonUnicodePath(PathBuf),
+}
+
+/// Records the locations of the workspaces of a repo.
+pub trait WorkspaceStore: Send + Sync + Debug {
+    fn name(&self) -> &str;
+
+    /// Records that the workspace `workspace_id` is located at `path`,
+    /// replacing the previous location if any.
+    fn add(&self, workspace_id: &WorkspaceId, path: &Path) -> Result<(), WorkspaceStoreError>;
+
+    /// Removes the given workspaces. Unknown workspaces are ignored.
+    fn forget(&self, workspace_ids: &[&WorkspaceId]) -> Result<(), WorkspaceStoreError>;
+
+    /// Returns the root of the workspace `workspace_id`, if recorded.
+    fn get_workspace_path(
+        &self,
+        workspace_id: &WorkspaceId,
+    ) -> Result<Option<PathBuf>, WorkspaceStoreError>;
+}
+
+/// Stores the workspace locations in a single file under the repo directory.
+#[derive(Debug)]
+pub struct SimpleWorkspaceStore {
+    store_dir: PathBuf,
+}
+
+impl SimpleWorkspaceStore {
+    /// Returns the store of the repo at `repo_path`. The store directory is
+    /// created when the first workspace is added.
+    pub fn load(repo_path: &Path) -> Self {
+        SimpleWorkspaceStore {
+            store_dir: repo_path.join("workspace_store"),
+        }
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.store_dir.join("index")
+    }
+
+    fn lock(&self) -> Result<FileLock, WorkspaceStoreError> {
+        create_or_reuse_dir(&self.store_dir).context(&self.store_dir)?;
+        Ok(FileLock::lock(self.store_dir.join("lock"))?)
+    }
+
+    fn read_index(
+        &self,
+    ) -> Result<crate::protos::workspace_store::Workspaces, WorkspaceStoreError> {
+        let path = self.index_path();
+        let buf = match fs::read(&path) {
+            Ok(buf) => buf,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Default::default()),
+            Err(error) => return Err(PathError { path, error }.into()),
+        };
+        crate::protos::workspace_store::Workspaces::decode(&*buf)
+            .map_err(|source| WorkspaceStoreError::Corrupt { path, source })
+    }
+
+    fn write_index(
+        &self,
+        index: &crate::protos::workspace_store::Workspaces,
+    ) -> Result<(), WorkspaceStoreError> {
+        let path = self.index_path();
+        let mut temp_file = NamedTempFile::new_in(&self.store_dir).context(&self.store_dir)?;
+        temp_file
+            .write_all(&index.encode_to_vec())
+            .context(temp_file.path())?;
+        temp_file
+            .persist(&path)
+            .map_err(|err| err.error)
+            .context(&path)?;
+        Ok(())
+    }
+}
+
+impl WorkspaceStore for SimpleWorkspaceStore {
+    fn name(&self) -> &str {
+        "simple"
+    }
+
+    fn add(&self, workspace_id: &WorkspaceId, path: &Path) -> Result<(), WorkspaceStoreError> {
+        let path_str = path
+            .to_str()
+            .ok_or_else(|| WorkspaceStoreError::NonUnicodePath(path.to_owned()))?;
+        let _lock = self.lock()?;
+        let mut index = self.read_index()?;
+        index
+            .workspaces
+            .retain(|workspace| workspace.name != workspace_id.as_str());
+        index
+            .workspaces
+            .push(crate::protos::workspace_store::Workspace {
+                name: workspace_id.as_str().to_owned(),
+                path: path_str.to_owned(),
+            });
+        self.write_index(&index)
+    }
+
+    fn forget(&self, workspace_ids: &[&WorkspaceId]) -> Result<(), WorkspaceStoreError> {
+        if !self.index_path().exists() {
+            return Ok(());
+        }
+        let _lock = self.lock()?;
+        let mut index = self.read_index()?;
+        index
+            .workspaces
+            .retain(|workspace| !workspace_ids.iter().any(|id| id.as_str() == workspace.name));
+        self.write_index(&index)
+    }
+
+    fn get_workspace_path(
+        &self,
+        workspace_id: &WorkspaceId,
+    ) -> Result<Option<PathBuf>, WorkspaceStoreError> {
+        let index = self.read_index()?;
+        Ok(index
+            .workspaces
+            .into_iter()
+            .find(|workspace| workspace.name == workspace_id.as_str())
+            .map(|workspace| PathBuf::from(workspace.path)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_forget_get() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = SimpleWorkspaceStore::load(temp_dir.path());
+        let default_id = WorkspaceId::default();
+        let other_id = WorkspaceId::new("other".to_owned());
+        assert_eq!(store.get_workspace_path(&default_id).unwrap(), None);
+
+        store.add(&default_id, Path::new("/repo")).unwrap();
+        store.add(&other_id, Path::new("/other")).unwrap();
+        store.add(&other_id, Path::new("/moved")).unwrap();
+        assert_eq!(
+            store.get_workspace_path(&default_id).unwrap(),
+            Some(PathBuf::from("/repo"))
+        );
+        assert_eq!(
+            store.get_workspace_path(&other_id).unwrap(),
+            Some(PathBuf::from("/moved"))
+        );
+
+        store.forget(&[&default_id]).unwrap();
+        assert_eq!(store.get_workspace_path(&default_id).unwrap(), None);
+        assert_eq!(
+            store.get_workspace_path(&other_id).unwrap(),
+            Some(PathBuf::from("/moved"))
+        );
+    }
+}
@@ -31,6 +31,7 @@ mod test_revset;
 mod test_rewrite;
 mod test_rewrite_duplicate;
 mod test_rewrite_transform;
+mod test_search_index;
 mod test_signing;
 mod test_ssh_signing;
 mod test_view;
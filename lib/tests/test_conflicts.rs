@@ -18,6 +18,7 @@ use jj_lib::backend::FileId;
 use jj_lib::conflicts::choose_materialized_conflict_marker_len;
 use jj_lib::conflicts::extract_as_single_hunk;
 use jj_lib::conflicts::materialize_merge_result_to_bytes;
+use jj_lib::conflicts::materialize_merge_result_to_bytes_with_marker_len;
 use jj_lib::conflicts::parse_conflict;
 use jj_lib::conflicts::update_from_content;
 use jj_lib::conflicts::ConflictMarkerStyle;
@@ -811,6 +812,89 @@ fn test_materialize_conflict_two_forward_diffs() {
     );
 }
 
+#[test]
+fn test_materialize_conflict_with_labels() {
+    let test_repo = TestRepo::init();
+    let store = test_repo.repo.store();
+
+    let path = RepoPath::from_internal_string("file");
+    let base_id = testutils::write_file(store, path, "line 1\nline 2\n");
+    let left_id = testutils::write_file(store, path, "line 1 left\nline 2\n");
+    let right_id = testutils::write_file(store, path, "line 1 right\nline 2\n");
+    let conflict =
+        Merge::from_removes_adds(vec![Some(base_id)], vec![Some(left_id), Some(right_id)]);
+    let labels = Merge::from_removes_adds(
+        vec!["base label".to_owned()],
+        vec!["left label".to_owned(), "right label".to_owned()],
+    );
+    let materialize = |conflict_marker_style, labels| {
+        let contents = extract_as_single_hunk(&conflict, store, path)
+            .block_on()
+            .unwrap();
+        let materialized = materialize_merge_result_to_bytes_with_marker_len(
+            &contents,
+            conflict_marker_style,
+            MIN_CONFLICT_MARKER_LEN,
+            labels,
+        );
+        String::from_utf8(materialized.into()).unwrap()
+    };
+
+    let materialized = materialize(ConflictMarkerStyle::Diff, Some(&labels));
+    insta::assert_snapshot!(materialized, @r"
+    <<<<<<< Conflict 1 of 1
+    %%%%%%% Changes from base (base label) to side #1 (left label)
+    -line 1
+    +line 1 left
+    +++++++ Contents of side #2 (right label)
+    line 1 right
+    >>>>>>> Conflict 1 of 1 ends
+    line 2
+    ");
+    assert_eq!(
+        parse_conflict(materialized.as_bytes(), 2, MIN_CONFLICT_MARKER_LEN),
+        parse_conflict(
+            materialize(ConflictMarkerStyle::Diff, None).as_bytes(),
+            2,
+            MIN_CONFLICT_MARKER_LEN
+        )
+    );
+    insta::assert_snapshot!(materialize(ConflictMarkerStyle::Snapshot, Some(&labels)), @r"
+    <<<<<<< Conflict 1 of 1
+    +++++++ Contents of side #1 (left label)
+    line 1 left
+    ------- Contents of base (base label)
+    line 1
+    +++++++ Contents of side #2 (right label)
+    line 1 right
+    >>>>>>> Conflict 1 of 1 ends
+    line 2
+    ");
+    insta::assert_snapshot!(materialize(ConflictMarkerStyle::Git, Some(&labels)), @r"
+    <<<<<<< Side #1 (left label) (Conflict 1 of 1)
+    line 1 left
+    ||||||| Base (base label)
+    line 1
+    =======
+    line 1 right
+    >>>>>>> Side #2 (right label) (Conflict 1 of 1 ends)
+    line 2
+    ");
+
+    // Labels are ignored if they don't match the number of terms
+    let labels = Merge::resolved("label".to_owned());
+    insta::assert_snapshot!(materialize(ConflictMarkerStyle::Diff, Some(&labels)), @r"
+    <<<<<<< Conflict 1 of 1
+    %%%%%%% Changes from base to side #1
+    -line 1
+    +line 1 left
+    +++++++ Contents of side #2
+    line 1 right
+    >>>>>>> Conflict 1 of 1 ends
+    line 2
+    ");
+}
+
 #[test]
 fn test_parse_conflict_resolved() {
     assert_eq!(
@@ -37,7 +37,7 @@ fn revset_for_commits(
         .unwrap();
     let expression =
         ResolvedExpression::Commits(commits.iter().map(|commit| commit.id().clone()).collect());
-    evaluate(&expression, repo.store(), index.clone()).unwrap()
+    evaluate(&expression, repo.store(), None, index.clone()).unwrap()
 }
 
 fn direct(commit: &Commit) -> GraphEdge<CommitId> {
@@ -330,3 +330,39 @@ fn test_graph_iterator_edge_escapes_from_(skip_transitive_edges: bool) {
     assert_eq!(commits[3].1, vec![indirect(&commit_a)]);
     assert_eq!(commits[4].1, vec![missing(&root_commit)]);
 }
+
+#[test]
+fn test_graph_iterator_first_parent() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    // Tests that only the first-parent chain from the heads is emitted, and
+    // that it's followed through commits outside of the set:
+    //
+    // E
+    // |
+    // d           E
+    // |\          :
+    // b C   =>    A
+    // |/          ~
+    // A
+    // |
+    // root
+    let mut tx = repo.start_transaction();
+    let mut graph_builder = CommitGraphBuilder::new(tx.repo_mut());
+    let commit_a = graph_builder.initial_commit();
+    let commit_b = graph_builder.commit_with_parents(&[&commit_a]);
+    let commit_c = graph_builder.commit_with_parents(&[&commit_a]);
+    let commit_d = graph_builder.commit_with_parents(&[&commit_b, &commit_c]);
+    let commit_e = graph_builder.commit_with_parents(&[&commit_d]);
+    let repo = tx.commit("test").unwrap();
+    let root_commit = repo.store().root_commit();
+
+    let revset = revset_for_commits(repo.as_ref(), &[&commit_a, &commit_c, &commit_e]);
+    let commits: Vec<_> = revset.iter_first_parent_graph_impl().try_collect().unwrap();
+    assert_eq!(commits.len(), 2);
+    assert_eq!(commits[0].0, *commit_e.id());
+    assert_eq!(commits[1].0, *commit_a.id());
+    assert_eq!(commits[0].1, vec![indirect(&commit_a)]);
+    assert_eq!(commits[1].1, vec![missing(&root_commit)]);
+}
@@ -147,7 +147,7 @@ fn git_fetch(
     let default_branch =
         git_fetch.get_default_branch(remote_name, git::RemoteCallbacks::default())?;
 
-    let import_stats = git_fetch.import_refs().unwrap();
+    let import_stats = git_fetch.import_refs(None).unwrap();
     let stats = GitFetchStats {
         default_branch,
         import_stats,
@@ -816,6 +816,179 @@ fn test_import_refs_reimport_with_moved_remote_ref() {
     assert_eq!(*view.heads(), expected_heads);
 }
 
+#[test]
+fn test_import_refs_reimport_with_rewritten_remote_ref() {
+    let git_settings = GitSettings::default();
+    let test_workspace = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let repo = &test_workspace.repo;
+    let git_repo = get_git_repo(repo);
+
+    let commit_base = empty_git_commit(&git_repo, "refs/heads/main", &[]);
+    let commit_old = testutils::git::add_commit(
+        &git_repo,
+        "refs/remotes/origin/feature",
+        "file",
+        b"contents\n",
+        "old message",
+        &[commit_base],
+    );
+    let mut tx = repo.start_transaction();
+    git::import_refs(tx.repo_mut(), &git_settings).unwrap();
+    tx.repo_mut().rebase_descendants().unwrap();
+    let repo = tx.commit("test").unwrap();
+
+    // A local bookmarked commit on top of the remote commit keeps it visible
+    let mut tx = repo.start_transaction();
+    let commit_local = create_random_commit(tx.repo_mut())
+        .set_parents(vec![jj_id(commit_old.commit_id)])
+        .write()
+        .unwrap();
+    tx.repo_mut()
+        .set_local_bookmark_target("local", RefTarget::normal(commit_local.id().clone()));
+    let repo = tx.commit("test").unwrap();
+
+    // Simulate fetching from a remote where the commit was reworded, and
+    // another one was added before it
+    delete_git_ref(&git_repo, "refs/remotes/origin/feature");
+    let commit_other = testutils::git::add_commit(
+        &git_repo,
+        "refs/remotes/origin/feature",
+        "other",
+        b"other\n",
+        "other",
+        &[commit_base],
+    );
+    let commit_new = testutils::git::add_commit(
+        &git_repo,
+        "refs/remotes/origin/feature",
+        "file",
+        b"contents\n",
+        "new message",
+        &[commit_other.commit_id],
+    );
+
+    let mut tx = repo.start_transaction();
+    let stats = git::import_refs(tx.repo_mut(), &git_settings).unwrap();
+    assert_eq!(
+        stats.reconciled_commits,
+        vec![(jj_id(commit_old.commit_id), jj_id(commit_new.commit_id))]
+    );
+    tx.repo_mut().rebase_descendants().unwrap();
+    let repo = tx.commit("test").unwrap();
+
+    // The local commit is rebased onto the new remote commit, and the old one
+    // is hidden
+    let heads = repo.view().heads().iter().collect_vec();
+    assert_eq!(heads.len(), 1);
+    let new_local = repo.store().get_commit(heads[0]).unwrap();
+    assert_eq!(new_local.change_id(), commit_local.change_id());
+    assert_eq!(new_local.parent_ids(), [jj_id(commit_new.commit_id)]);
+    assert_eq!(
+        repo.view().get_local_bookmark("local"),
+        &RefTarget::normal(new_local.id().clone())
+    );
+
+    // Nothing is reconciled if disabled
+    let git_settings = GitSettings {
+        reconcile_rewritten_commits: false,
+        ..Default::default()
+    };
+    let mut tx = repo.start_transaction();
+    let commit_local = create_random_commit(tx.repo_mut())
+        .set_parents(vec![jj_id(commit_new.commit_id)])
+        .write()
+        .unwrap();
+    tx.repo_mut()
+        .set_local_bookmark_target("local", RefTarget::normal(commit_local.id().clone()));
+    let repo = tx.commit("test").unwrap();
+    delete_git_ref(&git_repo, "refs/remotes/origin/feature");
+    let commit_newer = testutils::git::add_commit(
+        &git_repo,
+        "refs/remotes/origin/feature",
+        "file",
+        b"contents\n",
+        "newer message",
+        &[commit_other.commit_id],
+    );
+    let mut tx = repo.start_transaction();
+    let stats = git::import_refs(tx.repo_mut(), &git_settings).unwrap();
+    assert_eq!(stats.reconciled_commits, vec![]);
+    tx.repo_mut().rebase_descendants().unwrap();
+    let repo = tx.commit("test").unwrap();
+    assert!(repo.view().heads().contains(commit_local.id()));
+    assert!(repo.view().heads().contains(&jj_id(commit_newer.commit_id)));
+}
+
+fn write_git_tree(git_repo: &gix::Repository, filename: &str, content: &[u8]) -> gix::ObjectId {
+    let blob_id = git_repo.write_blob(content).unwrap();
+    let mut tree_editor = git_repo
+        .edit_tree(gix::ObjectId::empty_tree(git_repo.object_hash()))
+        .unwrap();
+    tree_editor
+        .upsert(filename, gix::object::tree::EntryKind::Blob, blob_id)
+        .unwrap();
+    tree_editor.write().unwrap().detach()
+}
+
+#[test]
+fn test_import_refs_reimport_with_rewritten_remote_ref_by_change_id() {
+    let git_settings = GitSettings::default();
+    let test_workspace = TestRepo::init_with_backend(TestRepoBackend::Git);
+    let repo = &test_workspace.repo;
+    let git_repo = get_git_repo(repo);
+
+    let commit_base = empty_git_commit(&git_repo, "refs/heads/main", &[]);
+    let tree_old = write_git_tree(&git_repo, "file", b"old contents\n");
+    let commit_old = testutils::git::write_commit_with_extra_headers(
+        &git_repo,
+        "refs/remotes/origin/feature",
+        tree_old,
+        "message",
+        &[commit_base],
+        &[("change-id", "zzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzz")],
+    );
+    let mut tx = repo.start_transaction();
+    git::import_refs(tx.repo_mut(), &git_settings).unwrap();
+    tx.repo_mut().rebase_descendants().unwrap();
+    let repo = tx.commit("test").unwrap();
+
+    let mut tx = repo.start_transaction();
+    let commit_local = create_random_commit(tx.repo_mut())
+        .set_parents(vec![jj_id(commit_old)])
+        .write()
+        .unwrap();
+    tx.repo_mut()
+        .set_local_bookmark_target("local", RefTarget::normal(commit_local.id().clone()));
+    let repo = tx.commit("test").unwrap();
+
+    // Simulate fetching from a remote where the patch of the commit was
+    // edited. It can only be matched by the change-id header.
+    delete_git_ref(&git_repo, "refs/remotes/origin/feature");
+    let tree_new = write_git_tree(&git_repo, "file", b"new contents\n");
+    let commit_new = testutils::git::write_commit_with_extra_headers(
+        &git_repo,
+        "refs/remotes/origin/feature",
+        tree_new,
+        "message",
+        &[commit_base],
+        &[("change-id", "zzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzz")],
+    );
+
+    let mut tx = repo.start_transaction();
+    let stats = git::import_refs(tx.repo_mut(), &git_settings).unwrap();
+    assert_eq!(
+        stats.reconciled_commits,
+        vec![(jj_id(commit_old), jj_id(commit_new))]
+    );
+    tx.repo_mut().rebase_descendants().unwrap();
+    let repo = tx.commit("test").unwrap();
+    let heads = repo.view().heads().iter().collect_vec();
+    assert_eq!(heads.len(), 1);
+    let new_local = repo.store().get_commit(heads[0]).unwrap();
+    assert_eq!(new_local.change_id(), commit_local.change_id());
+    assert_eq!(new_local.parent_ids(), [jj_id(commit_new)]);
+}
+
 #[test]
 fn test_import_refs_reimport_with_moved_untracked_remote_ref() {
     let git_settings = GitSettings {
@@ -1665,6 +1838,72 @@ fn test_export_refs_current_bookmark_changed() {
     assert!(git_repo.head().unwrap().is_detached(), "HEAD is detached");
 }
 
+fn git_reflog_messages(git_repo: &gix::Repository, ref_name: &str) -> Vec<String> {
+    let git_ref = git_repo.find_reference(ref_name).unwrap();
+    let mut platform = git_ref.log_iter();
+    let Some(log) = platform.all().unwrap() else {
+        return vec![];
+    };
+    log.map(|line| line.unwrap().message.to_string()).collect()
+}
+
+#[test]
+fn test_export_refs_reflog() {
+    // Bookmark and HEAD movements made by jj are recorded in the reflogs of a
+    // colocated repo
+    let settings = testutils::user_settings();
+    let temp_dir = testutils::new_temp_dir();
+    let workspace_root = temp_dir.path().join("repo");
+    let git_repo = testutils::git::init(&workspace_root);
+    let commit1 = empty_git_commit(&git_repo, "refs/heads/main", &[]);
+    testutils::git::set_symbolic_reference(&git_repo, "HEAD", "refs/heads/main");
+    let (_workspace, repo) =
+        Workspace::init_external_git(&settings, &workspace_root, &workspace_root.join(".git"))
+            .unwrap();
+
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    git::import_head(mut_repo).unwrap();
+    git::import_refs(mut_repo, &GitSettings::default()).unwrap();
+    mut_repo.rebase_descendants().unwrap();
+    let commit2 = create_random_commit(mut_repo)
+        .set_parents(vec![jj_id(commit1)])
+        .write()
+        .unwrap();
+    let commit3 = create_random_commit(mut_repo)
+        .set_parents(vec![commit2.id().clone()])
+        .write()
+        .unwrap();
+    mut_repo.set_local_bookmark_target("main", RefTarget::normal(commit2.id().clone()));
+    mut_repo.set_local_bookmark_target("feature", RefTarget::normal(commit2.id().clone()));
+    assert!(git::export_refs(mut_repo).unwrap().is_empty());
+    git::reset_head(mut_repo, &commit3).unwrap();
+
+    let hex = |id: &CommitId| id.hex()[..12].to_owned();
+    assert_eq!(
+        git_reflog_messages(&git_repo, "refs/heads/main")[1..],
+        [format!(
+            "export from jj: moving from {} to {}",
+            commit1.to_hex_with_len(12),
+            hex(commit2.id())
+        )]
+    );
+    assert_eq!(
+        git_reflog_messages(&git_repo, "refs/heads/feature"),
+        [format!("export from jj: created at {}", hex(commit2.id()))]
+    );
+    assert_eq!(
+        git_reflog_messages(&git_repo, "HEAD"),
+        [
+            "export from jj: detaching HEAD from refs/heads/main".to_owned(),
+            format!(
+                "export from jj: moving working copy onto {}",
+                hex(commit2.id())
+            ),
+        ]
+    );
+}
+
 #[test_case(false; "without moved placeholder ref")]
 #[test_case(true; "with moved placeholder ref")]
 fn test_export_refs_unborn_git_bookmark(move_placeholder_ref: bool) {
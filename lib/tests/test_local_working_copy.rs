@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 #[cfg(unix)]
@@ -19,6 +20,7 @@ use std::os::unix::net::UnixListener;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Mutex;
 
 use assert_matches::assert_matches;
 use indoc::indoc;
@@ -30,6 +32,7 @@ use jj_lib::file_util::check_symlink_support;
 use jj_lib::file_util::try_symlink;
 use jj_lib::fsmonitor::FsmonitorSettings;
 use jj_lib::gitignore::GitIgnoreFile;
+use jj_lib::local_working_copy::FileType;
 use jj_lib::local_working_copy::LocalWorkingCopy;
 use jj_lib::merge::Merge;
 use jj_lib::merge::MergedTreeValue;
@@ -46,6 +49,8 @@ use jj_lib::secret_backend::SecretBackend;
 use jj_lib::working_copy::CheckoutError;
 use jj_lib::working_copy::CheckoutOptions;
 use jj_lib::working_copy::CheckoutStats;
+use jj_lib::working_copy::FileTypeOverride;
+use jj_lib::working_copy::SnapshotError;
 use jj_lib::working_copy::SnapshotOptions;
 use jj_lib::working_copy::UntrackedReason;
 use jj_lib::working_copy::WorkingCopy;
@@ -647,7 +652,9 @@ fn test_conflicting_changes_on_disk() {
             updated_files: 0,
             added_files: 3,
             removed_files: 0,
-            skipped_files: 3
+            skipped_files: 3,
+            unrepresentable_paths: vec![],
+            retracked_paths: vec![],
         }
     );
 
@@ -788,6 +795,126 @@ fn test_checkout_discard() {
     assert!(!reloaded_wc.file_states().unwrap().contains_path(file2_path));
 }
 
+#[test]
+fn test_snapshot_untracked_paths() {
+    let mut test_workspace = TestWorkspace::init();
+    let repo = test_workspace.repo.clone();
+    let store = repo.store().clone();
+    let op_id = repo.op_id().clone();
+    let workspace_root = test_workspace.workspace.workspace_root().to_owned();
+
+    let file1_path = RepoPath::from_internal_string("file1");
+    let file2_path = RepoPath::from_internal_string("file2");
+    testutils::write_working_copy_file(&workspace_root, file1_path, "contents");
+    testutils::write_working_copy_file(&workspace_root, file2_path, "contents");
+
+    // Record "file1" as intentionally untracked
+    let ws = &mut test_workspace.workspace;
+    let mut locked_ws = ws.start_working_copy_mutation().unwrap();
+    locked_ws
+        .locked_wc()
+        .set_untracked_paths(vec![file1_path.to_owned()])
+        .unwrap();
+    locked_ws.finish(op_id.clone()).unwrap();
+    let wc: &LocalWorkingCopy = ws.working_copy().as_any().downcast_ref().unwrap();
+    let state_path = wc.state_path().to_path_buf();
+    let reloaded_wc = LocalWorkingCopy::load(store.clone(), workspace_root.clone(), state_path);
+    assert_eq!(
+        reloaded_wc.untracked_paths().unwrap(),
+        [file1_path.to_owned()]
+    );
+
+    // The untracked path shouldn't be tracked even though it isn't ignored
+    let tree = test_workspace.snapshot().unwrap();
+    assert_eq!(
+        tree.entries().map(|(path, _)| path).collect_vec(),
+        [file2_path.to_owned()]
+    );
+
+    // Once removed from the list, the path should be tracked
+    let ws = &mut test_workspace.workspace;
+    let mut locked_ws = ws.start_working_copy_mutation().unwrap();
+    locked_ws.locked_wc().set_untracked_paths(vec![]).unwrap();
+    locked_ws.finish(op_id.clone()).unwrap();
+    let tree = test_workspace.snapshot().unwrap();
+    assert_eq!(
+        tree.entries().map(|(path, _)| path).collect_vec(),
+        [file1_path.to_owned(), file2_path.to_owned()]
+    );
+}
+
+#[test]
+fn test_snapshot_file_type_overrides() {
+    let mut test_workspace = TestWorkspace::init();
+    let repo = test_workspace.repo.clone();
+    let store = repo.store().clone();
+    let op_id = repo.op_id().clone();
+    let workspace_root = test_workspace.workspace.workspace_root().to_owned();
+
+    let script_path = RepoPath::from_internal_string("script");
+    let link_path = RepoPath::from_internal_string("link");
+    testutils::write_working_copy_file(&workspace_root, script_path, "contents");
+    testutils::write_working_copy_file(&workspace_root, link_path, "script");
+    test_workspace.snapshot().unwrap();
+
+    // Override the file types of both files
+    let ws = &mut test_workspace.workspace;
+    let mut locked_ws = ws.start_working_copy_mutation().unwrap();
+    locked_ws
+        .locked_wc()
+        .set_file_type_overrides(BTreeMap::from([
+            (script_path.to_owned(), FileTypeOverride::Executable),
+            (link_path.to_owned(), FileTypeOverride::Symlink),
+        ]))
+        .unwrap();
+    locked_ws.finish(op_id.clone()).unwrap();
+    let wc: &LocalWorkingCopy = ws.working_copy().as_any().downcast_ref().unwrap();
+    let state_path = wc.state_path().to_path_buf();
+    let reloaded_wc = LocalWorkingCopy::load(store.clone(), workspace_root.clone(), state_path);
+    assert_eq!(
+        reloaded_wc.file_type_overrides().unwrap(),
+        &BTreeMap::from([
+            (link_path.to_owned(), FileTypeOverride::Symlink),
+            (script_path.to_owned(), FileTypeOverride::Executable),
+        ])
+    );
+
+    // The overrides should apply even though the files didn't change on disk
+    let tree = test_workspace.snapshot().unwrap();
+    assert_matches!(
+        tree.path_value(script_path).unwrap().into_resolved(),
+        Ok(Some(TreeValue::File {
+            executable: true,
+            ..
+        }))
+    );
+    assert_matches!(
+        tree.path_value(link_path).unwrap().into_resolved(),
+        Ok(Some(TreeValue::Symlink(_)))
+    );
+
+    // Once removed, the files should be recorded as they are on disk
+    let ws = &mut test_workspace.workspace;
+    let mut locked_ws = ws.start_working_copy_mutation().unwrap();
+    locked_ws
+        .locked_wc()
+        .set_file_type_overrides(BTreeMap::new())
+        .unwrap();
+    locked_ws.finish(op_id.clone()).unwrap();
+    let tree = test_workspace.snapshot().unwrap();
+    assert_matches!(
+        tree.path_value(script_path).unwrap().into_resolved(),
+        Ok(Some(TreeValue::File {
+            executable: false,
+            ..
+        }))
+    );
+    assert_matches!(
+        tree.path_value(link_path).unwrap().into_resolved(),
+        Ok(Some(TreeValue::File { .. }))
+    );
+}
+
 #[test]
 fn test_snapshot_file_directory_transition() {
     let mut test_workspace = TestWorkspace::init();
@@ -875,7 +1002,9 @@ fn test_materialize_snapshot_conflicted_files() {
             updated_files: 0,
             added_files: 2,
             removed_files: 0,
-            skipped_files: 0
+            skipped_files: 0,
+            unrepresentable_paths: vec![],
+            retracked_paths: vec![],
         }
     );
 
@@ -1767,6 +1896,168 @@ fn test_check_out_malformed_file_path_windows(file_path_str: &str) {
     assert!(!workspace_root.parent().unwrap().join("pwned").exists());
 }
 
+#[test]
+fn test_check_out_unrepresentable_file_path() {
+    let mut test_workspace = TestWorkspace::init();
+    let repo = &test_workspace.repo;
+    let op_id = repo.op_id().clone();
+    let workspace_root = test_workspace.workspace.workspace_root().to_owned();
+
+    // File names longer than 255 bytes can't be created on most file systems.
+    let long_name = "x".repeat(300);
+    let normal_path = RepoPath::from_internal_string("file");
+    let long_file_path = RepoPathBuf::from_internal_string(&long_name);
+    let long_dir_path = RepoPathBuf::from_internal_string(format!("sub/{long_name}/file"));
+    let tree1 = create_tree(
+        repo,
+        &[
+            (normal_path, "normal"),
+            (&long_file_path, "long file"),
+            (&long_dir_path, "long dir"),
+        ],
+    );
+    let commit1 = commit_with_tree(repo.store(), tree1.id());
+    let tree2 = create_tree(repo, &[(normal_path, "normal")]);
+    let commit2 = commit_with_tree(repo.store(), tree2.id());
+
+    // The other paths should be checked out
+    let ws = &mut test_workspace.workspace;
+    let stats = ws
+        .check_out(
+            op_id.clone(),
+            None,
+            &commit1,
+            &CheckoutOptions::empty_for_test(),
+        )
+        .unwrap();
+    assert_eq!(stats.added_files, 1);
+    assert_eq!(stats.skipped_files, 0);
+    assert_eq!(
+        stats.unrepresentable_paths,
+        vec![long_dir_path.clone(), long_file_path.clone()]
+    );
+    assert!(normal_path.to_fs_path_unchecked(&workspace_root).is_file());
+    assert!(!workspace_root.join("sub").exists());
+    let wc: &LocalWorkingCopy = ws.working_copy().as_any().downcast_ref().unwrap();
+    let file_states = wc.file_states().unwrap();
+    assert_eq!(
+        file_states.get(&long_file_path).unwrap().file_type,
+        FileType::Skipped
+    );
+    assert_eq!(
+        file_states.get(&long_dir_path).unwrap().file_type,
+        FileType::Skipped
+    );
+
+    // The skipped paths should be preserved by snapshot
+    let new_tree = test_workspace.snapshot().unwrap();
+    assert_eq!(new_tree.id(), tree1.id());
+
+    // Removing the skipped paths should only update the file states
+    let ws = &mut test_workspace.workspace;
+    let stats = ws
+        .check_out(
+            op_id.clone(),
+            None,
+            &commit2,
+            &CheckoutOptions::empty_for_test(),
+        )
+        .unwrap();
+    assert_eq!(stats, CheckoutStats::default());
+    let wc: &LocalWorkingCopy = ws.working_copy().as_any().downcast_ref().unwrap();
+    assert!(!wc.file_states().unwrap().contains_path(&long_file_path));
+    assert!(!wc.file_states().unwrap().contains_path(&long_dir_path));
+
+    // Reset should also mark the paths as skipped
+    let mut locked_ws = ws.start_working_copy_mutation().unwrap();
+    locked_ws.locked_wc().reset(&commit1).unwrap();
+    locked_ws.finish(op_id.clone()).unwrap();
+    let wc: &LocalWorkingCopy = ws.working_copy().as_any().downcast_ref().unwrap();
+    assert_eq!(
+        wc.file_states()
+            .unwrap()
+            .get(&long_file_path)
+            .unwrap()
+            .file_type,
+        FileType::Skipped
+    );
+    let new_tree = test_workspace.snapshot().unwrap();
+    assert_eq!(new_tree.id(), tree1.id());
+}
+
+#[test_case("aux"; "device name")]
+#[test_case("sub/Com1.txt"; "device name with extension")]
+#[test_case("sub/trailing."; "trailing dot")]
+#[test_case("trailing "; "trailing space")]
+#[test_case("a<b"; "invalid character")]
+fn test_check_out_unrepresentable_file_path_windows(file_path_str: &str) {
+    let mut test_workspace = TestWorkspace::init();
+    let repo = &test_workspace.repo;
+    let workspace_root = test_workspace.workspace.workspace_root().to_owned();
+
+    let file_path = RepoPath::from_internal_string(file_path_str);
+    let tree = create_tree(repo, &[(file_path, "contents")]);
+    let commit = commit_with_tree(repo.store(), tree.id());
+
+    // Checkout should skip the path on Windows
+    let ws = &mut test_workspace.workspace;
+    let stats = ws
+        .check_out(
+            repo.op_id().clone(),
+            None,
+            &commit,
+            &CheckoutOptions::empty_for_test(),
+        )
+        .unwrap();
+    if cfg!(windows) {
+        assert_eq!(stats.unrepresentable_paths, vec![file_path.to_owned()]);
+        let wc: &LocalWorkingCopy = ws.working_copy().as_any().downcast_ref().unwrap();
+        assert_eq!(
+            wc.file_states().unwrap().get(file_path).unwrap().file_type,
+            FileType::Skipped
+        );
+    } else {
+        assert_eq!(stats.added_files, 1);
+        assert!(stats.unrepresentable_paths.is_empty());
+        assert!(file_path.to_fs_path_unchecked(&workspace_root).is_file());
+    }
+
+    // The path should be preserved by snapshot
+    let new_tree = test_workspace.snapshot().unwrap();
+    assert_eq!(new_tree.id(), tree.id());
+}
+
+#[cfg(windows)]
+#[test]
+fn test_check_out_long_file_path() {
+    let mut test_workspace = TestWorkspace::init();
+    let repo = &test_workspace.repo;
+    let workspace_root = test_workspace.workspace.workspace_root().to_owned();
+
+    // The total path length exceeds MAX_PATH, but each name is short.
+    let dir_name = "d".repeat(100);
+    let file_path =
+        RepoPathBuf::from_internal_string(format!("{dir_name}/{dir_name}/{dir_name}/file"));
+    let tree = create_tree(repo, &[(&file_path, "contents")]);
+    let commit = commit_with_tree(repo.store(), tree.id());
+
+    let ws = &mut test_workspace.workspace;
+    let stats = ws
+        .check_out(
+            repo.op_id().clone(),
+            None,
+            &commit,
+            &CheckoutOptions::empty_for_test(),
+        )
+        .unwrap();
+    assert_eq!(stats.added_files, 1);
+    assert!(stats.unrepresentable_paths.is_empty());
+    assert!(file_path.to_fs_path_unchecked(&workspace_root).is_file());
+
+    let new_tree = test_workspace.snapshot().unwrap();
+    assert_eq!(new_tree.id(), tree.id());
+}
+
 #[test_case(".git"; "root .git file")]
 #[test_case(".jj"; "root .jj file")]
 #[test_case(".git/pwned"; "root .git dir")]
@@ -2149,6 +2440,41 @@ fn test_fsmonitor() {
     }
 }
 
+#[test]
+fn test_snapshot_content_check() {
+    let mut test_workspace = TestWorkspace::init();
+    let workspace_root = test_workspace.workspace.workspace_root().to_owned();
+    let good_path = RepoPath::from_internal_string("good");
+    let bad_path = RepoPath::from_internal_string("bad");
+    std::fs::write(good_path.to_fs_path_unchecked(&workspace_root), "good").unwrap();
+    std::fs::write(bad_path.to_fs_path_unchecked(&workspace_root), "secret").unwrap();
+    let checked_paths = Mutex::new(vec![]);
+    let content_check = |path: &RepoPath, content: &[u8]| {
+        checked_paths.lock().unwrap().push(path.to_owned());
+        if content == b"secret" {
+            Err("rejected".into())
+        } else {
+            Ok(())
+        }
+    };
+    let options = SnapshotOptions {
+        content_check: Some(&content_check),
+        ..SnapshotOptions::empty_for_test()
+    };
+    let result = test_workspace.snapshot_with_options(&options);
+    assert_matches!(
+        result,
+        Err(SnapshotError::ContentRejected { path, .. }) if path.as_ref() == bad_path
+    );
+
+    std::fs::write(bad_path.to_fs_path_unchecked(&workspace_root), "fixed").unwrap();
+    checked_paths.lock().unwrap().clear();
+    test_workspace.snapshot_with_options(&options).unwrap();
+    let mut checked_paths = checked_paths.into_inner().unwrap();
+    checked_paths.sort();
+    assert_eq!(checked_paths, [bad_path.to_owned(), good_path.to_owned()]);
+}
+
 #[test]
 fn test_snapshot_max_new_file_size() {
     let mut test_workspace = TestWorkspace::init();
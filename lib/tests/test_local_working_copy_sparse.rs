@@ -85,6 +85,8 @@ fn test_sparse_checkout() {
             added_files: 0,
             removed_files: 3,
             skipped_files: 0,
+            unrepresentable_paths: vec![],
+            retracked_paths: vec![],
         }
     );
     assert_eq!(
@@ -144,6 +146,8 @@ fn test_sparse_checkout() {
             added_files: 2,
             removed_files: 2,
             skipped_files: 0,
+            unrepresentable_paths: vec![],
+            retracked_paths: vec![],
         }
     );
     assert_eq!(locked_wc.sparse_patterns().unwrap(), sparse_patterns);
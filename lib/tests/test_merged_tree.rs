@@ -39,6 +39,7 @@ use jj_lib::repo::Repo;
 use jj_lib::repo_path::RepoPath;
 use jj_lib::repo_path::RepoPathBuf;
 use jj_lib::repo_path::RepoPathComponent;
+use jj_lib::tree_diff_cache::TreeDiffCache;
 use pollster::FutureExt as _;
 use pretty_assertions::assert_eq;
 use testutils::create_single_tree;
@@ -827,6 +828,166 @@ fn test_diff_resolved() {
     diff_stream_equals_iter(&before_merged, &after_merged, &EverythingMatcher);
 }
 
+#[test]
+fn test_diff_cache() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    // Disabled by default
+    assert!(repo.store().tree_diff_cache().is_none());
+    let cache_dir = test_repo.repo_path().join("tree_diff_cache");
+    repo.store()
+        .enable_tree_diff_cache(TreeDiffCache::new(cache_dir, 10));
+    let cache = repo.store().tree_diff_cache().unwrap();
+
+    let path1 = RepoPath::from_internal_string("dir1/file");
+    let path2 = RepoPath::from_internal_string("dir2/file");
+    let before = create_single_tree(repo, &[(path1, "before"), (path2, "before")]);
+    let after = create_single_tree(repo, &[(path1, "after"), (path2, "after")]);
+    let before_merged = MergedTree::new(Merge::resolved(before.clone()));
+    let after_merged = MergedTree::new(Merge::resolved(after.clone()));
+    let before_id = before_merged.id();
+    let after_id = after_merged.id();
+    let diff = |matcher: &dyn Matcher| -> Vec<_> {
+        before_merged
+            .diff_stream(&after_merged, matcher)
+            .map(diff_entry_tuple)
+            .collect()
+            .block_on()
+    };
+    let entry = |path: &RepoPath| {
+        (
+            path.to_owned(),
+            (
+                Merge::resolved(before.path_value(path).unwrap()),
+                Merge::resolved(after.path_value(path).unwrap()),
+            ),
+        )
+    };
+
+    // Partial diff isn't cached
+    let matcher = PrefixMatcher::new([RepoPath::from_internal_string("dir1")]);
+    assert_eq!(diff(&matcher), vec![entry(path1)]);
+    assert!(cache.get_diff(&before_id, &after_id).is_none());
+
+    // Full diff is cached
+    assert_eq!(diff(&EverythingMatcher), vec![entry(path1), entry(path2)]);
+    let cached_entries = cache.get_diff(&before_id, &after_id).unwrap();
+    assert_eq!(
+        cached_entries
+            .into_iter()
+            .map(|(path, before, after)| (path, (before, after)))
+            .collect_vec(),
+        vec![entry(path1), entry(path2)]
+    );
+    assert!(cache.get_diff(&after_id, &before_id).is_none());
+
+    // Cached diff is filtered by matcher
+    let matcher = PrefixMatcher::new([RepoPath::from_internal_string("dir2")]);
+    assert_eq!(diff(&matcher), vec![entry(path2)]);
+
+    // Cached diff is used instead of the trees
+    let (_, (fake_before, fake_after)) = entry(path1);
+    cache
+        .insert_diff(
+            &before_id,
+            &after_id,
+            &[(path2.to_owned(), fake_before.clone(), fake_after.clone())],
+        )
+        .unwrap();
+    assert_eq!(
+        diff(&EverythingMatcher),
+        vec![(path2.to_owned(), (fake_before, fake_after))]
+    );
+
+    cache.clear().unwrap();
+    assert!(cache.get_diff(&before_id, &after_id).is_none());
+    assert_eq!(diff(&EverythingMatcher), vec![entry(path1), entry(path2)]);
+}
+
+#[test]
+fn test_conflicts_cache() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    let cache_dir = test_repo.repo_path().join("tree_diff_cache");
+    repo.store()
+        .enable_tree_diff_cache(TreeDiffCache::new(cache_dir, 10));
+    let cache = repo.store().tree_diff_cache().unwrap();
+
+    let conflict_path = RepoPath::from_internal_string("dir/conflict");
+    let clean_path = RepoPath::from_internal_string("dir/clean");
+    let base = create_single_tree(repo, &[(conflict_path, "base"), (clean_path, "base")]);
+    let side1 = create_single_tree(repo, &[(conflict_path, "side1"), (clean_path, "base")]);
+    let side2 = create_single_tree(repo, &[(conflict_path, "side2"), (clean_path, "side2")]);
+    let tree = MergedTree::new(Merge::from_removes_adds(
+        vec![base.clone()],
+        vec![side1.clone(), side2.clone()],
+    ));
+    let conflicts = || {
+        tree.conflicts()
+            .map(|(path, value)| (path, value.unwrap()))
+            .collect_vec()
+    };
+    let expected_value = Merge::from_removes_adds(
+        vec![base.path_value(conflict_path).unwrap()],
+        vec![
+            side1.path_value(conflict_path).unwrap(),
+            side2.path_value(conflict_path).unwrap(),
+        ],
+    );
+
+    assert!(cache.get_conflicts(&tree.id()).is_none());
+    assert_eq!(
+        conflicts(),
+        vec![(conflict_path.to_owned(), expected_value.clone())]
+    );
+    assert_eq!(
+        cache.get_conflicts(&tree.id()),
+        Some(vec![(conflict_path.to_owned(), expected_value.clone())])
+    );
+    assert_eq!(
+        conflicts(),
+        vec![(conflict_path.to_owned(), expected_value.clone())]
+    );
+
+    // Resolved tree has no conflicts to cache
+    let resolved_tree = MergedTree::resolved(side1.clone());
+    assert_eq!(resolved_tree.conflicts().count(), 0);
+    assert!(cache.get_conflicts(&resolved_tree.id()).is_none());
+}
+
+#[test]
+fn test_diff_cache_eviction() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    let cache = TreeDiffCache::new(test_repo.repo_path().join("tree_diff_cache"), 2);
+
+    let path = RepoPath::from_internal_string("file");
+    let tree_ids = (0..4)
+        .map(|i| MergedTree::resolved(create_single_tree(repo, &[(path, &i.to_string())])).id())
+        .collect_vec();
+    let empty_id = repo.store().empty_merged_tree_id();
+    let is_cached_in =
+        |cache: &TreeDiffCache, tree_id| cache.get_diff(&empty_id, tree_id).is_some();
+    let is_cached = |tree_id| is_cached_in(&cache, tree_id);
+    cache.insert_diff(&empty_id, &tree_ids[0], &[]).unwrap();
+    cache.insert_diff(&empty_id, &tree_ids[1], &[]).unwrap();
+    // The least recently used entry is evicted
+    assert!(is_cached(&tree_ids[0]));
+    cache.insert_diff(&empty_id, &tree_ids[2], &[]).unwrap();
+    assert!(!is_cached(&tree_ids[1]));
+    cache.insert_diff(&empty_id, &tree_ids[3], &[]).unwrap();
+    assert!(!is_cached(&tree_ids[0]));
+    assert!(is_cached(&tree_ids[2]));
+    assert!(is_cached(&tree_ids[3]));
+
+    // The order is restored from the modification times of the files
+    let new_cache = TreeDiffCache::new(test_repo.repo_path().join("tree_diff_cache"), 2);
+    assert!(is_cached_in(&new_cache, &tree_ids[2]));
+    new_cache.insert_diff(&empty_id, &tree_ids[0], &[]).unwrap();
+    assert!(is_cached_in(&new_cache, &tree_ids[2]));
+    assert!(!is_cached_in(&new_cache, &tree_ids[3]));
+}
+
 fn create_copy_records(paths: &[(&RepoPath, &RepoPath)]) -> CopyRecords {
     let mut copy_records = CopyRecords::default();
     copy_records
@@ -18,6 +18,7 @@ use jj_lib::op_store::RemoteRef;
 use jj_lib::op_store::RemoteRefState;
 use jj_lib::op_store::WorkspaceId;
 use jj_lib::refs::RemoteRefSymbol;
+use jj_lib::repo::IndexingProgress;
 use jj_lib::repo::Repo;
 use jj_lib::rewrite::RebaseOptions;
 use maplit::hashset;
@@ -414,6 +415,50 @@ fn test_add_head_not_immediate_child() {
     assert!(mut_repo.index().has_id(child.id()));
 }
 
+#[test]
+fn test_add_heads_with_progress() {
+    // Test that MutableRepo::add_heads_with_progress() indexes all missing
+    // ancestors, and reports the progress.
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    // Create some commits outside of the repo by using a temporary transaction.
+    let mut tx = repo.start_transaction();
+    let mut graph_builder = CommitGraphBuilder::new(tx.repo_mut());
+    let commit1 = graph_builder.initial_commit();
+    let commit2 = graph_builder.commit_with_parents(&[&commit1]);
+    let commit3 = graph_builder.commit_with_parents(&[&commit1]);
+    let commit4 = graph_builder.commit_with_parents(&[&commit2, &commit3]);
+    let commit5 = graph_builder.commit_with_parents(&[&commit1]);
+    drop(tx);
+
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let mut progress_events = vec![];
+    mut_repo
+        .add_heads_with_progress(
+            &[commit4.clone(), commit5.clone()],
+            Some(&mut |progress: &IndexingProgress| {
+                progress_events.push((progress.found_commits, progress.indexed_commits));
+            }),
+        )
+        .unwrap();
+    assert_eq!(
+        mut_repo.view().heads(),
+        &hashset! {commit4.id().clone(), commit5.id().clone()}
+    );
+    for commit in [&commit1, &commit2, &commit3, &commit4, &commit5] {
+        assert!(mut_repo.index().has_id(commit.id()));
+    }
+    assert!(mut_repo.index().is_ancestor(commit1.id(), commit4.id()));
+    assert!(mut_repo.index().is_ancestor(commit3.id(), commit4.id()));
+    assert!(!mut_repo.index().is_ancestor(commit5.id(), commit4.id()));
+    assert_eq!(
+        progress_events,
+        [(5, 0), (5, 1), (5, 2), (5, 3), (5, 4), (5, 5)]
+    );
+}
+
 #[test]
 fn test_remove_head() {
     // Test that MutableRepo::remove_head() removes the head, and that it's still
@@ -44,6 +44,7 @@ use jj_lib::revset::FailingSymbolResolver;
 use jj_lib::revset::Revset;
 use jj_lib::revset::RevsetAliasesMap;
 use jj_lib::revset::RevsetDiagnostics;
+use jj_lib::revset::RevsetEvaluationError;
 use jj_lib::revset::RevsetExpression;
 use jj_lib::revset::RevsetExtensions;
 use jj_lib::revset::RevsetFilterPredicate;
@@ -52,6 +53,7 @@ use jj_lib::revset::RevsetResolutionError;
 use jj_lib::revset::RevsetWorkspaceContext;
 use jj_lib::revset::SymbolResolver;
 use jj_lib::revset::SymbolResolverExtension;
+use jj_lib::str_util::StringPattern;
 use jj_lib::workspace::Workspace;
 use test_case::test_case;
 use testutils::create_random_commit;
@@ -500,6 +502,22 @@ fn test_resolve_working_copies() {
 
     // ensure our output has those two commits
     assert_eq!(resolve(), vec![commit2.id().clone(), commit1.id().clone()]);
+
+    let resolve_matching = |pattern: StringPattern| -> Vec<CommitId> {
+        RevsetExpression::working_copies_matching(pattern)
+            .resolve_user_expression(mut_repo, &FailingSymbolResolver)
+            .unwrap()
+            .evaluate(mut_repo)
+            .unwrap()
+            .iter()
+            .map(Result::unwrap)
+            .collect()
+    };
+    assert_eq!(
+        resolve_matching(StringPattern::exact("ws1")),
+        vec![commit1.id().clone()]
+    );
+    assert_eq!(resolve_matching(StringPattern::exact("ws3")), vec![]);
 }
 
 #[test]
@@ -2421,6 +2439,107 @@ fn test_evaluate_expression_latest() {
     );
 }
 
+#[test]
+fn test_evaluate_expression_latest_filtered() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+
+    let mut write_commit = |description: &str, sec: i64| {
+        let builder = create_random_commit(mut_repo).set_description(description);
+        let mut committer = builder.committer().clone();
+        committer.timestamp.timestamp = MillisSinceEpoch(sec * 1000);
+        builder.set_committer(committer).write().unwrap()
+    };
+    let commit1_t3 = write_commit("foo", 3);
+    let commit2_t4 = write_commit("bar", 4);
+    let commit3_t2 = write_commit("foo", 2);
+    let commit4_t2 = write_commit("foo bar", 2);
+    let commit5_t1 = write_commit("foo", 1);
+
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "latest(description(foo))"),
+        vec![commit1_t3.id().clone()],
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "latest(description(foo), 0)"),
+        vec![],
+    );
+
+    // Tie-breaking: pick the later entry in position
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "latest(description(foo), 2)"),
+        vec![commit4_t2.id().clone(), commit1_t3.id().clone()],
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "latest(description(foo), 10)"),
+        vec![
+            commit5_t1.id().clone(),
+            commit4_t2.id().clone(),
+            commit3_t2.id().clone(),
+            commit1_t3.id().clone(),
+        ],
+    );
+
+    // Multiple filters, and filter of negated set
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "latest(description(foo) & description(bar))"),
+        vec![commit4_t2.id().clone()],
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "latest(~description(foo) & ~root(), 2)"),
+        vec![commit2_t4.id().clone()],
+    );
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("latest(description(foo) & ~{}, 2)", commit4_t2.id()),
+        ),
+        vec![commit3_t2.id().clone(), commit1_t3.id().clone()],
+    );
+}
+
+#[test]
+fn test_evaluate_expression_exactly() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let commit1 = write_random_commit(mut_repo);
+    let commit2 = write_random_commit(mut_repo);
+
+    assert_eq!(
+        resolve_commit_ids(mut_repo, &format!("exactly({})", commit1.id())),
+        vec![commit1.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("exactly({} | {}, 2)", commit1.id(), commit2.id())
+        ),
+        vec![commit2.id().clone(), commit1.id().clone()]
+    );
+    assert_eq!(resolve_commit_ids(mut_repo, "exactly(none(), 0)"), vec![]);
+
+    // Evaluation fails if the number of commits doesn't match
+    let evaluate = |count| {
+        RevsetExpression::commits(vec![commit1.id().clone(), commit2.id().clone()])
+            .exactly(count)
+            .evaluate(mut_repo)
+            .map(|_| ())
+    };
+    assert_matches!(evaluate(2), Ok(()));
+    assert_matches!(
+        evaluate(1),
+        Err(RevsetEvaluationError::Other(err))
+            if err.to_string() == "Revset was expected to contain exactly 1 commits, but contains 2"
+    );
+    assert_matches!(evaluate(3), Err(RevsetEvaluationError::Other(_)));
+}
+
 #[test]
 fn test_evaluate_expression_fork_point() {
     let test_repo = TestRepo::init();
@@ -2599,6 +2718,258 @@ fn test_evaluate_expression_fork_point_merge_with_ancestor() {
     );
 }
 
+#[test]
+fn test_evaluate_expression_fork_point_two_args() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    // 3 4
+    // |/
+    // 2
+    // |
+    // 1
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let mut graph_builder = CommitGraphBuilder::new(mut_repo);
+    let commit1 = graph_builder.initial_commit();
+    let commit2 = graph_builder.commit_with_parents(&[&commit1]);
+    let commit3 = graph_builder.commit_with_parents(&[&commit2]);
+    let commit4 = graph_builder.commit_with_parents(&[&commit2]);
+
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("fork_point({}, {})", commit3.id(), commit4.id())
+        ),
+        vec![commit2.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("fork_point({}, {})", commit3.id(), commit1.id())
+        ),
+        vec![commit1.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, &format!("fork_point({}, none())", commit3.id())),
+        vec![commit3.id().clone()]
+    );
+}
+
+#[test]
+fn test_evaluate_expression_bisect_midpoint() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    //     7
+    //    /|
+    //   6 |
+    //   | 5
+    //   4 |
+    //   |/
+    //   3
+    //   |
+    //   2
+    //   |
+    //   1
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let mut graph_builder = CommitGraphBuilder::new(mut_repo);
+    let commit1 = graph_builder.initial_commit();
+    let commit2 = graph_builder.commit_with_parents(&[&commit1]);
+    let commit3 = graph_builder.commit_with_parents(&[&commit2]);
+    let commit4 = graph_builder.commit_with_parents(&[&commit3]);
+    let commit5 = graph_builder.commit_with_parents(&[&commit3]);
+    let commit6 = graph_builder.commit_with_parents(&[&commit4]);
+    let commit7 = graph_builder.commit_with_parents(&[&commit6, &commit5]);
+
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "bisect_midpoint(none())"),
+        vec![]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, &format!("bisect_midpoint({})", commit1.id())),
+        vec![commit1.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("bisect_midpoint({} | {})", commit1.id(), commit2.id())
+        ),
+        vec![commit1.id().clone()]
+    );
+
+    // Linear history is split in the middle
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("bisect_midpoint({}..{})", commit1.id(), commit6.id())
+        ),
+        vec![commit3.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, &format!("bisect_midpoint(::{})", commit4.id())),
+        vec![commit2.id().clone()]
+    );
+
+    // Ancestors through merges are counted once. Ties are broken by taking
+    // the newer commit.
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("bisect_midpoint({}..{})", commit1.id(), commit7.id())
+        ),
+        vec![commit5.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("bisect_midpoint({}..{})", commit3.id(), commit7.id())
+        ),
+        vec![commit6.id().clone()]
+    );
+
+    // Ancestors are counted through commits which aren't candidates
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!(
+                "bisect_midpoint({} | {} | {} | {})",
+                commit2.id(),
+                commit4.id(),
+                commit5.id(),
+                commit7.id()
+            )
+        ),
+        vec![commit5.id().clone()]
+    );
+
+    // More candidates than bits in a word
+    let mut commits = vec![commit7];
+    for _ in 0..100 {
+        let commit =
+            CommitGraphBuilder::new(mut_repo).commit_with_parents(&[commits.last().unwrap()]);
+        commits.push(commit);
+    }
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!(
+                "bisect_midpoint({}..{})",
+                commits[0].id(),
+                commits.last().unwrap().id()
+            )
+        ),
+        vec![commits[50].id().clone()]
+    );
+}
+
+#[test]
+fn test_evaluate_expression_applied_upstream() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    let root_commit_id = repo.store().root_commit_id();
+
+    let path1 = RepoPath::from_internal_string("file1");
+    let path2 = RepoPath::from_internal_string("file2");
+    let tree1 = create_tree(repo, &[(path1, "1\n")]);
+    let tree2 = create_tree(repo, &[(path2, "2\n")]);
+    let tree12 = create_tree(repo, &[(path1, "1\n"), (path2, "2\n")]);
+    let tree13 = create_tree(repo, &[(path1, "1\n3\n")]);
+
+    // 4 [+file2]   3 [+file2]
+    // 2 [+file1]   1 [+file1]
+    //  \          /
+    //     root
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let commit1 = mut_repo
+        .new_commit(vec![root_commit_id.clone()], tree1.id())
+        .write()
+        .unwrap();
+    let commit2 = mut_repo
+        .new_commit(vec![root_commit_id.clone()], tree1.id())
+        .write()
+        .unwrap();
+    let commit3 = mut_repo
+        .new_commit(vec![commit1.id().clone()], tree12.id())
+        .write()
+        .unwrap();
+    let commit4 = mut_repo
+        .new_commit(vec![commit2.id().clone()], tree12.id())
+        .write()
+        .unwrap();
+    // 6 and 7 record that they were duplicated from 5. 6 makes conflicting
+    // changes, and 7 makes the same changes to file1 but from another base.
+    let commit5 = mut_repo
+        .new_commit(vec![commit4.id().clone()], tree13.id())
+        .write()
+        .unwrap();
+    let commit6 = mut_repo
+        .new_commit(vec![commit1.id().clone()], tree2.id())
+        .set_duplicated_from(Some(commit5.change_id().clone()))
+        .write()
+        .unwrap();
+    let commit7 = mut_repo
+        .new_commit(vec![commit1.id().clone()], tree13.id())
+        .set_duplicated_from(Some(commit5.change_id().clone()))
+        .write()
+        .unwrap();
+
+    // Commits with the same patch are applied
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("applied_upstream({}::, {})", commit2.id(), commit3.id())
+        ),
+        vec![commit4.id().clone(), commit2.id().clone()]
+    );
+    // Ancestors of the upstream aren't included
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("applied_upstream({}::, {})", commit1.id(), commit3.id())
+        ),
+        vec![]
+    );
+    // Duplicates are applied only if they would become empty when rebased
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("applied_upstream({}, {})", commit5.id(), commit6.id())
+        ),
+        vec![]
+    );
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("applied_upstream({}, {})", commit6.id(), commit5.id())
+        ),
+        vec![]
+    );
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("applied_upstream({}, {})", commit5.id(), commit7.id())
+        ),
+        vec![commit5.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("applied_upstream({}, {})", commit7.id(), commit5.id())
+        ),
+        vec![commit7.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(
+            mut_repo,
+            &format!("applied_upstream({}, {})", commit5.id(), commit3.id())
+        ),
+        vec![]
+    );
+}
+
 #[test]
 fn test_evaluate_expression_merges() {
     let test_repo = TestRepo::init();
@@ -3925,6 +4296,48 @@ fn test_evaluate_expression_conflict() {
     );
 }
 
+#[test]
+fn test_evaluate_expression_change_metadata() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+
+    let commit1 = write_random_commit(mut_repo);
+    let commit2 = write_random_commit(mut_repo);
+    let commit3 = write_random_commit(mut_repo);
+    mut_repo.set_change_metadata(commit1.change_id(), "ticket", Some("ABC-1".to_owned()));
+    mut_repo.set_change_metadata(commit2.change_id(), "ticket", Some("XYZ-2".to_owned()));
+    mut_repo.set_change_metadata(commit3.change_id(), "review", Some("url".to_owned()));
+
+    assert_eq!(
+        resolve_commit_ids(mut_repo, "metadata(ticket)"),
+        vec![commit2.id().clone(), commit1.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, r#"metadata("ticket", "ABC")"#),
+        vec![commit1.id().clone()]
+    );
+    assert_eq!(
+        resolve_commit_ids(mut_repo, r#"metadata("ticket", exact:"ABC")"#),
+        vec![]
+    );
+    assert_eq!(resolve_commit_ids(mut_repo, "metadata(unknown)"), vec![]);
+
+    // Metadata is kept when the change is rewritten
+    let commit1_rewritten = mut_repo
+        .rewrite_commit(&commit1)
+        .set_description("rewritten")
+        .write()
+        .unwrap();
+    mut_repo.rebase_descendants().unwrap();
+    assert_eq!(
+        resolve_commit_ids(mut_repo, r#"metadata("ticket", "ABC")"#),
+        vec![commit1_rewritten.id().clone()]
+    );
+}
+
 #[test]
 fn test_reverse_graph() {
     let test_repo = TestRepo::init();
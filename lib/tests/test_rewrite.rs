@@ -17,6 +17,7 @@ use jj_lib::commit::Commit;
 use jj_lib::matchers::EverythingMatcher;
 use jj_lib::matchers::FilesMatcher;
 use jj_lib::merge::Merge;
+use jj_lib::merge_strategy::MergeStrategies;
 use jj_lib::merged_tree::MergedTree;
 use jj_lib::op_store::RefTarget;
 use jj_lib::op_store::RemoteRef;
@@ -915,6 +916,97 @@ fn test_rebase_descendants_contents() {
     );
 }
 
+#[test]
+fn test_rebase_records_conflict_sources() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    // Commit B is rebased onto C, which conflicts with the changes from B.
+    // Commit D inherits the conflict when rebased onto the new B.
+    //
+    // D
+    // B C
+    // |/
+    // A
+    let path = RepoPath::from_internal_string("file");
+    let mut tx = repo.start_transaction();
+    let commit_a = tx
+        .repo_mut()
+        .new_commit(
+            vec![repo.store().root_commit_id().clone()],
+            create_tree(repo, &[(path, "a")]).id(),
+        )
+        .write()
+        .unwrap();
+    let commit_b = tx
+        .repo_mut()
+        .new_commit(
+            vec![commit_a.id().clone()],
+            create_tree(repo, &[(path, "b")]).id(),
+        )
+        .write()
+        .unwrap();
+    let commit_c = tx
+        .repo_mut()
+        .new_commit(
+            vec![commit_a.id().clone()],
+            create_tree(repo, &[(path, "c")]).id(),
+        )
+        .write()
+        .unwrap();
+    let commit_d = tx
+        .repo_mut()
+        .new_commit(
+            vec![commit_b.id().clone()],
+            create_tree(repo, &[(path, "d")]).id(),
+        )
+        .write()
+        .unwrap();
+    assert_eq!(commit_b.conflict_sources(), None);
+
+    let new_commit_b =
+        CommitRewriter::new(tx.repo_mut(), commit_b.clone(), vec![commit_c.id().clone()])
+            .rebase()
+            .unwrap()
+            .write()
+            .unwrap();
+    assert!(new_commit_b.has_conflict().unwrap());
+    assert_eq!(
+        new_commit_b.conflict_sources(),
+        Some(Merge::from_vec(vec![
+            commit_c.id().clone(),
+            commit_a.id().clone(),
+            commit_b.id().clone(),
+        ]))
+    );
+
+    // The sources of the terms of the child's conflict are derived from the
+    // parent's
+    let rebase_map =
+        rebase_descendants_with_options_return_map(tx.repo_mut(), &RebaseOptions::default());
+    let new_commit_d = repo
+        .store()
+        .get_commit(rebase_map.get(commit_d.id()).unwrap())
+        .unwrap();
+    assert_eq!(
+        new_commit_d.conflict_sources(),
+        Some(Merge::from_vec(vec![
+            commit_c.id().clone(),
+            commit_a.id().clone(),
+            commit_d.id().clone(),
+        ]))
+    );
+
+    // Rewriting the conflicted tree drops the sources
+    let rewritten_commit_b = tx
+        .repo_mut()
+        .rewrite_commit(&new_commit_b)
+        .set_tree_id(create_tree(repo, &[(path, "b")]).id())
+        .write()
+        .unwrap();
+    assert_eq!(rewritten_commit_b.conflict_sources(), None);
+}
+
 #[test]
 fn test_rebase_descendants_basic_bookmark_update() {
     let test_repo = TestRepo::init();
@@ -1693,6 +1785,8 @@ fn test_empty_commit_option(empty_behavior: EmptyBehaviour) {
                 delete_abandoned_bookmarks: false,
             },
             simplify_ancestor_merge: true,
+            skip_applied: false,
+            merge_strategies: MergeStrategies::default(),
         },
     );
 
@@ -1828,6 +1922,8 @@ fn test_rebase_abandoning_empty() {
             delete_abandoned_bookmarks: false,
         },
         simplify_ancestor_merge: true,
+        skip_applied: false,
+        merge_strategies: MergeStrategies::default(),
     };
     let rewriter = CommitRewriter::new(tx.repo_mut(), commit_b, vec![commit_b2.id().clone()]);
     rebase_commit_with_options(rewriter, &rebase_options).unwrap();
@@ -17,8 +17,14 @@ use jj_lib::backend::CommitId;
 use jj_lib::repo::Repo;
 use jj_lib::repo_path::RepoPath;
 use jj_lib::rewrite::duplicate_commits;
+use jj_lib::rewrite::duplicate_commits_onto_parents;
+use jj_lib::rewrite::move_commits;
+use jj_lib::rewrite::MoveCommitsTarget;
+use jj_lib::rewrite::RebaseOptions;
 use jj_lib::transaction::Transaction;
+use maplit::hashset;
 use testutils::create_tree;
+use testutils::write_random_commit;
 use testutils::TestRepo;
 
 #[test]
@@ -177,3 +183,168 @@ fn test_duplicate_linear_contents() {
         &tree_1_2.id()
     );
 }
+
+#[test]
+fn test_duplicate_records_provenance() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let commit_a = write_random_commit(tx.repo_mut());
+    let commit_b = write_random_commit(tx.repo_mut());
+    let stats = duplicate_commits(
+        tx.repo_mut(),
+        &[commit_b.id().clone()],
+        &[commit_a.id().clone()],
+        &[],
+    )
+    .unwrap();
+    let new_commit_b = &stats.duplicated_commits[commit_b.id()];
+    assert_eq!(new_commit_b.duplicated_from(), Some(commit_b.change_id()));
+    assert_eq!(commit_b.duplicated_from(), None);
+
+    // The provenance is kept when the duplicate is rewritten
+    let rewritten_commit_b = tx
+        .repo_mut()
+        .rewrite_commit(new_commit_b)
+        .set_description("rewritten")
+        .write()
+        .unwrap();
+    assert_eq!(
+        rewritten_commit_b.duplicated_from(),
+        Some(commit_b.change_id())
+    );
+
+    let stats = duplicate_commits_onto_parents(tx.repo_mut(), &[commit_a.id().clone()]).unwrap();
+    assert_eq!(
+        stats.duplicated_commits[commit_a.id()].duplicated_from(),
+        Some(commit_a.change_id())
+    );
+}
+
+#[test]
+fn test_rebase_skip_applied() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    let root_commit_id = repo.store().root_commit_id();
+
+    let path_1 = RepoPath::from_internal_string("file1");
+    let path_2 = RepoPath::from_internal_string("file2");
+    let path_3 = RepoPath::from_internal_string("file3");
+    let tree_1 = create_tree(repo, &[(path_1, "1\n")]);
+    let tree_2 = create_tree(repo, &[(path_2, "2\n")]);
+    let tree_2_3 = create_tree(repo, &[(path_2, "2\n"), (path_3, "3\n")]);
+    let tree_1_3 = create_tree(repo, &[(path_1, "1\n"), (path_3, "3\n")]);
+
+    // C [+file3]
+    // B [+file2]    D [+file3]
+    // |             A [+file1]
+    // root ---------+
+    let mut tx = repo.start_transaction();
+    let commit_a = tx
+        .repo_mut()
+        .new_commit(vec![root_commit_id.clone()], tree_1.id())
+        .write()
+        .unwrap();
+    let commit_b = tx
+        .repo_mut()
+        .new_commit(vec![root_commit_id.clone()], tree_2.id())
+        .write()
+        .unwrap();
+    let commit_c = tx
+        .repo_mut()
+        .new_commit(vec![commit_b.id().clone()], tree_2_3.id())
+        .write()
+        .unwrap();
+    let commit_d = tx
+        .repo_mut()
+        .new_commit(vec![commit_a.id().clone()], tree_1_3.id())
+        .write()
+        .unwrap();
+    // B' is a duplicate of B on top of D
+    let stats = duplicate_commits(
+        tx.repo_mut(),
+        &[commit_b.id().clone()],
+        &[commit_d.id().clone()],
+        &[],
+    )
+    .unwrap();
+    let new_commit_b = stats.duplicated_commits[commit_b.id()].clone();
+    let repo = tx.commit("test").unwrap();
+
+    let options = RebaseOptions {
+        skip_applied: true,
+        ..Default::default()
+    };
+
+    // B was duplicated onto the destination, and C makes the same changes as
+    // D, so both are abandoned
+    let mut tx = repo.start_transaction();
+    let stats = move_commits(
+        tx.repo_mut(),
+        &[new_commit_b.id().clone()],
+        &[],
+        &MoveCommitsTarget::Roots(vec![commit_b.clone()]),
+        &options,
+    )
+    .unwrap();
+    assert_eq!(stats.num_skipped_applied, 2);
+    assert_eq!(stats.num_rebased_targets, 0);
+    assert_eq!(
+        *tx.repo().view().heads(),
+        hashset! {new_commit_b.id().clone()}
+    );
+    assert_eq!(
+        tx.repo().new_parents(&[commit_c.id().clone()]),
+        vec![new_commit_b.id().clone()]
+    );
+
+    // Only the commits in the destination are considered applied
+    let mut tx = repo.start_transaction();
+    let stats = move_commits(
+        tx.repo_mut(),
+        &[commit_a.id().clone()],
+        &[],
+        &MoveCommitsTarget::Roots(vec![commit_b.clone()]),
+        &options,
+    )
+    .unwrap();
+    assert_eq!(stats.num_skipped_applied, 0);
+    assert_eq!(stats.num_rebased_targets, 2);
+
+    // Without the option, the commits are kept
+    let mut tx = repo.start_transaction();
+    let stats = move_commits(
+        tx.repo_mut(),
+        &[new_commit_b.id().clone()],
+        &[],
+        &MoveCommitsTarget::Roots(vec![commit_b.clone()]),
+        &RebaseOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(stats.num_skipped_applied, 0);
+    assert_eq!(stats.num_rebased_targets, 2);
+
+    // B' was modified after it was duplicated, so B isn't applied anymore
+    let mut tx = repo.start_transaction();
+    let tree_modified = create_tree(
+        &repo,
+        &[(path_1, "1\n"), (path_2, "modified\n"), (path_3, "3\n")],
+    );
+    let modified_commit_b = tx
+        .repo_mut()
+        .rewrite_commit(&new_commit_b)
+        .set_tree_id(tree_modified.id())
+        .write()
+        .unwrap();
+    let stats = move_commits(
+        tx.repo_mut(),
+        &[modified_commit_b.id().clone()],
+        &[],
+        &MoveCommitsTarget::Roots(vec![commit_b]),
+        &options,
+    )
+    .unwrap();
+    assert_eq!(stats.num_skipped_applied, 1);
+    assert_eq!(stats.num_rebased_targets, 1);
+}
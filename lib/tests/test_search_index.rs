@@ -0,0 +1,240 @@
+// Copyright 2025 The Jujutsu Authors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// https://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use itertools::Itertools as _;
+use jj_lib::backend::CommitId;
+use jj_lib::backend::MillisSinceEpoch;
+use jj_lib::backend::Signature;
+use jj_lib::backend::Timestamp;
+use jj_lib::commit::Commit;
+use jj_lib::default_index::AsCompositeIndex as _;
+use jj_lib::default_index::DefaultIndexStore;
+use jj_lib::default_index::DefaultReadonlyIndex;
+use jj_lib::fileset::FilePattern;
+use jj_lib::fileset::FilesetExpression;
+use jj_lib::repo::ReadonlyRepo;
+use jj_lib::repo::Repo;
+use jj_lib::repo_path::RepoPath;
+use jj_lib::repo_path::RepoPathBuf;
+use jj_lib::revset::DefaultSymbolResolver;
+use jj_lib::revset::RevsetExpression;
+use jj_lib::revset::RevsetFilterPredicate;
+use jj_lib::revset::RevsetIteratorExt as _;
+use jj_lib::revset::SymbolResolverExtension;
+use jj_lib::search_index::SearchField;
+use jj_lib::search_index::SearchIndex;
+use jj_lib::str_util::StringPattern;
+use testutils::create_tree;
+use testutils::write_random_commit;
+use testutils::TestRepo;
+
+fn signature(name: &str, email: &str) -> Signature {
+    Signature {
+        name: name.to_owned(),
+        email: email.to_owned(),
+        timestamp: Timestamp {
+            timestamp: MillisSinceEpoch(0),
+            tz_offset: 0,
+        },
+    }
+}
+
+fn search_index(repo: &ReadonlyRepo) -> &SearchIndex {
+    let index_store: &DefaultIndexStore = repo.index_store().as_any().downcast_ref().unwrap();
+    index_store.search_index()
+}
+
+fn update_search_index(repo: &ReadonlyRepo) -> usize {
+    let index_store: &DefaultIndexStore = repo.index_store().as_any().downcast_ref().unwrap();
+    let index: &DefaultReadonlyIndex = repo.readonly_index().as_any().downcast_ref().unwrap();
+    index_store
+        .update_search_index(index.as_composite(), repo.store())
+        .unwrap()
+}
+
+fn evaluate_filter(repo: &dyn Repo, predicate: RevsetFilterPredicate) -> Vec<CommitId> {
+    let symbol_resolver =
+        DefaultSymbolResolver::new(repo, &([] as [&Box<dyn SymbolResolverExtension>; 0]));
+    RevsetExpression::filter(predicate)
+        .resolve_user_expression(repo, &symbol_resolver)
+        .unwrap()
+        .evaluate(repo)
+        .unwrap()
+        .iter()
+        .commits(repo.store())
+        .map_ok(|commit: Commit| commit.id().clone())
+        .try_collect()
+        .unwrap()
+}
+
+#[test]
+fn test_search_index() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    assert!(!search_index(repo).is_enabled());
+    search_index(repo).enable().unwrap();
+
+    let path1 = RepoPath::from_internal_string("dir/file1");
+    let path2 = RepoPath::from_internal_string("file2");
+    let tree1 = create_tree(repo, &[(path1, "1")]);
+    let tree2 = create_tree(repo, &[(path1, "1"), (path2, "2")]);
+    let mut tx = repo.start_transaction();
+    let commit1 = tx
+        .repo_mut()
+        .new_commit(vec![repo.store().root_commit_id().clone()], tree1.id())
+        .set_description("Fix the parser")
+        .set_author(signature("Alice", "alice@example.com"))
+        .write()
+        .unwrap();
+    let commit2 = tx
+        .repo_mut()
+        .new_commit(vec![commit1.id().clone()], tree2.id())
+        .set_description("Add a feature")
+        .set_author(signature("Bob", "bob@example.com"))
+        .write()
+        .unwrap();
+    let repo = tx.commit("test").unwrap();
+
+    // The new commits are indexed when the commit index is written
+    let search_index = search_index(&repo);
+    assert_eq!(update_search_index(&repo), 0);
+    let snapshot = search_index.snapshot().unwrap();
+    assert_eq!(snapshot.num_commits(), 3);
+    assert!(snapshot.contains(commit1.id()));
+    assert!(snapshot.contains(commit2.id()));
+
+    // Commits created later are indexed, too
+    let mut tx = repo.start_transaction();
+    let commit3 = write_random_commit(tx.repo_mut());
+    let repo = tx.commit("test").unwrap();
+    assert!(search_index.snapshot().unwrap().contains(commit3.id()));
+
+    let candidates = search_index
+        .text_candidates(
+            SearchField::Description,
+            &StringPattern::substring("PARSER"),
+        )
+        .unwrap();
+    assert!(candidates.may_match(commit1.id()));
+    assert!(!candidates.may_match(commit2.id()));
+    assert!(!candidates.may_match(commit3.id()));
+    let candidates = search_index
+        .text_candidates(
+            SearchField::Author,
+            &StringPattern::exact("bob@example.com"),
+        )
+        .unwrap();
+    assert!(!candidates.may_match(commit1.id()));
+    assert!(candidates.may_match(commit2.id()));
+    let candidates = search_index
+        .text_candidates(
+            SearchField::Description,
+            &StringPattern::glob("*the par*").unwrap(),
+        )
+        .unwrap();
+    assert!(candidates.may_match(commit1.id()));
+    assert!(!candidates.may_match(commit2.id()));
+    // Patterns without literal trigrams can't use the index
+    assert!(search_index
+        .text_candidates(SearchField::Description, &StringPattern::substring("ix"))
+        .is_none());
+    assert!(search_index
+        .text_candidates(
+            SearchField::Description,
+            &StringPattern::regex("parser").unwrap()
+        )
+        .is_none());
+
+    let prefix_path = |path: &str| {
+        FilesetExpression::pattern(FilePattern::PrefixPath(RepoPathBuf::from_internal_string(
+            path,
+        )))
+    };
+    let candidates = search_index.path_candidates(&prefix_path("dir")).unwrap();
+    assert!(candidates.may_match(commit1.id()));
+    assert!(!candidates.may_match(commit2.id()));
+    let candidates = search_index.path_candidates(&prefix_path("file2")).unwrap();
+    assert!(!candidates.may_match(commit1.id()));
+    assert!(candidates.may_match(commit2.id()));
+    assert!(search_index
+        .path_candidates(&FilesetExpression::all())
+        .is_none());
+
+    // Revsets give the same results with the index
+    assert_eq!(
+        evaluate_filter(
+            repo.as_ref(),
+            RevsetFilterPredicate::Description(StringPattern::substring("parser"))
+        ),
+        vec![commit1.id().clone()]
+    );
+    assert_eq!(
+        evaluate_filter(
+            repo.as_ref(),
+            RevsetFilterPredicate::AuthorName(StringPattern::exact("Bob"))
+        ),
+        vec![commit2.id().clone()]
+    );
+    assert_eq!(
+        evaluate_filter(
+            repo.as_ref(),
+            RevsetFilterPredicate::File(prefix_path("file2"))
+        ),
+        vec![commit2.id().clone()]
+    );
+}
+
+#[test]
+fn test_search_index_incremental() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+    search_index(repo).enable().unwrap();
+
+    let mut commit_ids = vec![];
+    for _ in 0..5 {
+        let repo = test_repo
+            .env
+            .load_repo_at_head(&testutils::user_settings(), test_repo.repo_path());
+        let mut tx = repo.start_transaction();
+        commit_ids.push(write_random_commit(tx.repo_mut()).id().clone());
+        tx.commit("test").unwrap();
+    }
+    let repo = test_repo
+        .env
+        .load_repo_at_head(&testutils::user_settings(), test_repo.repo_path());
+    assert_eq!(update_search_index(&repo), 0);
+    let snapshot = search_index(&repo).snapshot().unwrap();
+    assert_eq!(snapshot.num_commits(), 6);
+    for commit_id in &commit_ids {
+        assert!(snapshot.contains(commit_id));
+    }
+
+    // The index stays up to date after the commit index is rebuilt
+    let repo = test_repo
+        .env
+        .load_repo_at_head(&testutils::user_settings(), test_repo.repo_path());
+    let mut tx = repo.start_transaction();
+    let new_commit_id = write_random_commit(tx.repo_mut()).id().clone();
+    tx.commit("test").unwrap();
+    let default_index_store: &jj_lib::default_index::DefaultIndexStore =
+        repo.index_store().as_any().downcast_ref().unwrap();
+    default_index_store.reinit().unwrap();
+    let repo = test_repo
+        .env
+        .load_repo_at_head(&testutils::user_settings(), test_repo.repo_path());
+    assert_eq!(update_search_index(&repo), 0);
+    let snapshot = search_index(&repo).snapshot().unwrap();
+    assert_eq!(snapshot.num_commits(), 7);
+    assert!(snapshot.contains(&new_commit_id));
+}
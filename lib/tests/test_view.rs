@@ -297,6 +297,75 @@ fn test_merge_views_bookmarks() {
     );
 }
 
+#[test]
+fn test_merge_views_archived_bookmarks() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let commit = write_random_commit(mut_repo);
+    for name in ["archived1", "archived2", "deleted", "unarchived"] {
+        mut_repo.set_local_bookmark_target(name, RefTarget::normal(commit.id().clone()));
+    }
+    mut_repo.set_bookmark_archived("unarchived", true);
+    let repo = tx.commit("test").unwrap();
+
+    let mut tx1 = repo.start_transaction();
+    tx1.repo_mut().set_bookmark_archived("archived1", true);
+    tx1.repo_mut().set_bookmark_archived("deleted", true);
+    tx1.repo_mut().set_bookmark_archived("unarchived", false);
+
+    let mut tx2 = repo.start_transaction();
+    tx2.repo_mut().set_bookmark_archived("archived2", true);
+    tx2.repo_mut()
+        .set_local_bookmark_target("deleted", RefTarget::absent());
+
+    let repo = commit_transactions(vec![tx1, tx2]);
+    assert_eq!(
+        repo.view().archived_bookmarks().collect::<Vec<_>>(),
+        ["archived1", "archived2"]
+    );
+}
+
+#[test]
+fn test_merge_views_change_metadata() {
+    let test_repo = TestRepo::init();
+    let repo = &test_repo.repo;
+
+    let mut tx = repo.start_transaction();
+    let mut_repo = tx.repo_mut();
+    let commit = write_random_commit(mut_repo);
+    let change_id = commit.change_id();
+    for key in ["kept", "changed1", "removed2"] {
+        mut_repo.set_change_metadata(change_id, key, Some("base".to_owned()));
+    }
+    let repo = tx.commit("test").unwrap();
+
+    let mut tx1 = repo.start_transaction();
+    tx1.repo_mut()
+        .set_change_metadata(change_id, "changed1", Some("side1".to_owned()));
+    tx1.repo_mut()
+        .set_change_metadata(change_id, "added1", Some("side1".to_owned()));
+
+    let mut tx2 = repo.start_transaction();
+    tx2.repo_mut()
+        .set_change_metadata(change_id, "removed2", None);
+    tx2.repo_mut()
+        .set_change_metadata(change_id, "added2", Some("side2".to_owned()));
+
+    let repo = commit_transactions(vec![tx1, tx2]);
+    assert_eq!(
+        repo.view().change_metadata(change_id),
+        Some(&btreemap! {
+            "added1".to_owned() => "side1".to_owned(),
+            "added2".to_owned() => "side2".to_owned(),
+            "changed1".to_owned() => "side1".to_owned(),
+            "kept".to_owned() => "base".to_owned(),
+        })
+    );
+}
+
 #[test]
 fn test_merge_views_tags() {
     // Tests merging of tags (by performing divergent operations). See
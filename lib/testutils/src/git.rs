@@ -159,6 +159,37 @@ pub fn write_commit(
     .detach()
 }
 
+pub fn write_commit_with_extra_headers(
+    repo: &gix::Repository,
+    reference: &str,
+    tree_id: gix::ObjectId,
+    message: &str,
+    parents: &[gix::ObjectId],
+    extra_headers: &[(&str, &str)],
+) -> gix::ObjectId {
+    let commit = gix::objs::Commit {
+        tree: tree_id,
+        parents: parents.iter().copied().collect(),
+        author: signature(),
+        committer: signature(),
+        encoding: None,
+        message: message.into(),
+        extra_headers: extra_headers
+            .iter()
+            .map(|&(key, value)| (key.into(), value.into()))
+            .collect(),
+    };
+    let commit_id = repo.write_object(&commit).unwrap().detach();
+    repo.reference(
+        reference,
+        commit_id,
+        gix::refs::transaction::PreviousValue::Any,
+        "write commit",
+    )
+    .unwrap();
+    commit_id
+}
+
 pub fn set_head_to_id(repo: &gix::Repository, target: gix::ObjectId) {
     repo.edit_reference(gix::refs::transaction::RefEdit {
         change: gix::refs::transaction::Change::Update {
@@ -396,3 +427,47 @@ pub fn fetch(repo_dir: impl AsRef<Path>, remote: &str) {
         bstr::BString::from(output.stderr),
     );
 }
+
+pub fn add_worktree(repo_dir: impl AsRef<Path>, worktree_path: &Path, branch: &str) {
+    let output = std::process::Command::new("git")
+        .current_dir(repo_dir)
+        .arg("worktree")
+        .arg("add")
+        .arg(worktree_path)
+        .arg(branch)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "git worktree add failed with {}:\n{}\n----- stderr -----\n{}",
+        output.status,
+        bstr::BString::from(output.stdout),
+        bstr::BString::from(output.stderr),
+    );
+}
+
+pub fn stash_push(repo_dir: impl AsRef<Path>, message: &str, include_untracked: bool) {
+    let mut command = std::process::Command::new("git");
+    command
+        .current_dir(repo_dir)
+        .args([
+            "-c",
+            "user.name=Test User",
+            "-c",
+            "user.email=test.user@example.com",
+        ])
+        .args(["stash", "push", "--message", message])
+        .env("GIT_AUTHOR_DATE", "2001-02-03T04:05:06+07:00")
+        .env("GIT_COMMITTER_DATE", "2001-02-03T04:05:06+07:00");
+    if include_untracked {
+        command.arg("--include-untracked");
+    }
+    let output = command.output().unwrap();
+    assert!(
+        output.status.success(),
+        "git stash push failed with {}:\n{}\n----- stderr -----\n{}",
+        output.status,
+        bstr::BString::from(output.stdout),
+        bstr::BString::from(output.stderr),
+    );
+}
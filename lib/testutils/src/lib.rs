@@ -441,6 +441,8 @@ pub fn commit_with_tree(store: &Arc<Store>, tree_id: MergedTreeId) -> Commit {
         description: "description".to_string(),
         author: signature.clone(),
         committer: signature,
+        duplicated_from: None,
+        conflict_sources: vec![],
         secure_sig: None,
     };
     store.write_commit(commit, None).block_on().unwrap()